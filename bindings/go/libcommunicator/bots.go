@@ -0,0 +1,155 @@
+package libcommunicator
+
+/*
+#include <communicator.h>
+#include <stdlib.h>
+*/
+import "C"
+import "encoding/json"
+
+// Bot is a bot account. Bots act through personal access tokens created
+// with CreateAccessToken for their UserID.
+type Bot struct {
+	UserID      string `json:"user_id"`
+	Username    string `json:"username"`
+	DisplayName string `json:"display_name"`
+	Description string `json:"description"`
+	OwnerID     string `json:"owner_id"`
+	IsDisabled  bool   `json:"is_disabled"`
+	CreatedAt   int64  `json:"created_at"` // Unix timestamp in milliseconds
+}
+
+// BotSettings holds the settings for creating a bot account
+type BotSettings struct {
+	Username    string `json:"username"`
+	DisplayName string `json:"display_name,omitempty"`
+	Description string `json:"description,omitempty"`
+}
+
+// parseBot decodes a Bot returned by the library and frees the string
+func parseBot(cstr *C.char) (*Bot, error) {
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var bot Bot
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &bot); err != nil {
+		return nil, err
+	}
+
+	return &bot, nil
+}
+
+// CreateBot creates a bot account owned by the current user
+func (p *Platform) CreateBot(settings BotSettings) (*Bot, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	settingsJSON, err := json.Marshal(settings)
+	if err != nil {
+		return nil, err
+	}
+
+	cs, free := cStringFree(string(settingsJSON))
+	defer free()
+
+	return parseBot(C.communicator_platform_create_bot(p.handle, cs))
+}
+
+// GetBots returns the bot accounts visible to the current user
+func (p *Platform) GetBots(includeDisabled bool) ([]Bot, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	var includeInt C.int
+	if includeDisabled {
+		includeInt = 1
+	}
+
+	cstr := C.communicator_platform_get_bots(p.handle, includeInt)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var bots []Bot
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &bots); err != nil {
+		return nil, err
+	}
+
+	return bots, nil
+}
+
+// SetBotEnabled enables or disables a bot account
+func (p *Platform) SetBotEnabled(botUserID string, enabled bool) (*Bot, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	cs, free := cStringFree(botUserID)
+	defer free()
+
+	var enabledInt C.int
+	if enabled {
+		enabledInt = 1
+	}
+
+	return parseBot(C.communicator_platform_set_bot_enabled(p.handle, cs, enabledInt))
+}
+
+// AssignBot transfers a bot account to another owner
+func (p *Platform) AssignBot(botUserID, ownerID string) (*Bot, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	csBotUserID, freeBotUserID := cStringFree(botUserID)
+	defer freeBotUserID()
+
+	csOwnerID, freeOwnerID := cStringFree(ownerID)
+	defer freeOwnerID()
+
+	return parseBot(C.communicator_platform_assign_bot(p.handle, csBotUserID, csOwnerID))
+}
+
+// ConvertUserToBot converts a user account into a bot account
+func (p *Platform) ConvertUserToBot(userID string) (*Bot, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	cs, free := cStringFree(userID)
+	defer free()
+
+	return parseBot(C.communicator_platform_convert_user_to_bot(p.handle, cs))
+}
+
+// ConvertBotToUser converts a bot account into a regular user account that
+// logs in with the given password
+func (p *Platform) ConvertBotToUser(botUserID, password string) (*User, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	csBotUserID, freeBotUserID := cStringFree(botUserID)
+	defer freeBotUserID()
+
+	csPassword, freePassword := cStringFree(password)
+	defer freePassword()
+
+	cstr := C.communicator_platform_convert_bot_to_user(p.handle, csBotUserID, csPassword)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var user User
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &user); err != nil {
+		return nil, err
+	}
+
+	return &user, nil
+}
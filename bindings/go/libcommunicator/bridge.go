@@ -0,0 +1,141 @@
+package libcommunicator
+
+/*
+#include <communicator.h>
+#include <stdlib.h>
+*/
+import "C"
+import (
+	"encoding/json"
+	"runtime"
+)
+
+// Bridge decision values
+const (
+	BridgeDecisionForward      = "forward"
+	BridgeDecisionEcho         = "echo"
+	BridgeDecisionLoopDetected = "loop_detected"
+)
+
+// Bridge relays messages between platforms with echo suppression and loop detection
+type Bridge struct {
+	handle C.CommunicatorBridge
+}
+
+// BridgeResult is the outcome of relaying a message
+type BridgeResult struct {
+	Decision string   `json:"decision"`
+	Message  *Message `json:"message,omitempty"`
+}
+
+// NewBridge creates a new bridge
+// maxHops is the maximum number of bridges a message may pass through (0 for the default of 1)
+func NewBridge(bridgeID string, maxHops uint32) (*Bridge, error) {
+	if err := ensureInitialized(); err != nil {
+		return nil, err
+	}
+
+	cs, free := cStringFree(bridgeID)
+	defer free()
+
+	handle := C.communicator_bridge_create(cs, C.uint32_t(maxHops))
+	if handle == nil {
+		return nil, getLastError()
+	}
+
+	b := &Bridge{handle: handle}
+
+	runtime.SetFinalizer(b, func(b *Bridge) {
+		b.Destroy()
+	})
+
+	return b, nil
+}
+
+// Forward relays a message received on source to a channel on target
+// Echoes and looping messages are skipped; check the returned Decision
+func (b *Bridge) Forward(source, target *Platform, targetChannelID string, msg *Message) (*BridgeResult, error) {
+	if b.handle == nil || source.handle == nil || target.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	msgJSON, err := json.Marshal(msg)
+	if err != nil {
+		return nil, err
+	}
+
+	csChannelID, freeChannelID := cStringFree(targetChannelID)
+	defer freeChannelID()
+
+	csMessage, freeMessage := cStringFree(string(msgJSON))
+	defer freeMessage()
+
+	cstr := C.communicator_bridge_forward(b.handle, source.handle, target.handle, csChannelID, csMessage)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var result BridgeResult
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &result); err != nil {
+		return nil, err
+	}
+
+	return &result, nil
+}
+
+// CopyItem is the outcome of copying a part of a message
+type CopyItem struct {
+	Kind    string  `json:"kind"` // "text", "attachment" or "delete_source"
+	Name    *string `json:"name,omitempty"`
+	Success bool    `json:"success"`
+	Error   *string `json:"error,omitempty"`
+}
+
+// CopyReport is the outcome of copying a message
+type CopyReport struct {
+	Message *Message   `json:"message,omitempty"`
+	Items   []CopyItem `json:"items"`
+}
+
+// CopyMessage copies a message from source to a channel on target (another
+// account, or the same one). Attachments are uploaded again. With
+// deleteSource, the original is deleted once every part was copied (a move).
+// Parts can fail independently; check the items of the report.
+func CopyMessage(source *Platform, messageID string, target *Platform, targetChannelID string, deleteSource bool) (*CopyReport, error) {
+	if source.handle == nil || target.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	csMessageID, freeMessageID := cStringFree(messageID)
+	defer freeMessageID()
+
+	csChannelID, freeChannelID := cStringFree(targetChannelID)
+	defer freeChannelID()
+
+	var cDelete C.int
+	if deleteSource {
+		cDelete = 1
+	}
+
+	cstr := C.communicator_copy_message(source.handle, csMessageID, target.handle, csChannelID, cDelete)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var report CopyReport
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &report); err != nil {
+		return nil, err
+	}
+
+	return &report, nil
+}
+
+// Destroy frees the bridge resources
+func (b *Bridge) Destroy() {
+	if b.handle != nil {
+		C.communicator_bridge_destroy(b.handle)
+		b.handle = nil
+	}
+}
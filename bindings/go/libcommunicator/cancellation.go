@@ -0,0 +1,31 @@
+package libcommunicator
+
+/*
+#include <communicator.h>
+*/
+import "C"
+import "context"
+
+// withCancellation makes a blocking call that is cancelled when ctx is done.
+// The call gets a nil cancellation if ctx can never be cancelled.
+func withCancellation(ctx context.Context, call func(C.CommunicatorCancellation)) {
+	if ctx.Done() == nil {
+		call(nil)
+		return
+	}
+
+	handle := C.communicator_cancellation_create()
+	defer C.communicator_cancellation_destroy(handle)
+
+	done := make(chan struct{})
+	defer close(done)
+	go func() {
+		select {
+		case <-ctx.Done():
+			C.communicator_cancellation_cancel(handle)
+		case <-done:
+		}
+	}()
+
+	call(handle)
+}
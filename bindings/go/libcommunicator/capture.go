@@ -0,0 +1,93 @@
+package libcommunicator
+
+/*
+#include <communicator.h>
+#include <stdlib.h>
+*/
+import "C"
+import (
+	"encoding/json"
+	"unsafe"
+)
+
+// CaptureConfig configures a debug capture
+type CaptureConfig struct {
+	// Entries kept in memory; the oldest are dropped first
+	Capacity int `json:"capacity"`
+	// File the entries are also appended to, one JSON object per line
+	Path *string `json:"path,omitempty"`
+	// Longest frame payload kept, in bytes; longer ones are truncated
+	MaxPayloadBytes int `json:"max_payload_bytes"`
+}
+
+// DefaultCaptureConfig returns the configuration used by StartDebugCapture(nil)
+func DefaultCaptureConfig() CaptureConfig {
+	return CaptureConfig{Capacity: 1000, MaxPayloadBytes: 64 * 1024}
+}
+
+// CaptureEntry is a captured HTTP request ("http") or WebSocket frame ("frame")
+type CaptureEntry struct {
+	Kind string `json:"kind"`
+	// Milliseconds since the epoch
+	At int64 `json:"at"`
+	// URL of the request or WebSocket, with secrets redacted
+	URL string `json:"url"`
+
+	// HTTP requests
+	Method    string  `json:"method,omitempty"`
+	Status    *uint16 `json:"status,omitempty"`
+	RequestID *string `json:"request_id,omitempty"`
+	ElapsedMs uint64  `json:"elapsed_ms,omitempty"`
+	Error     *string `json:"error,omitempty"`
+
+	// WebSocket frames
+	Direction string `json:"direction,omitempty"`
+	Opcode    string `json:"opcode,omitempty"`
+	Payload   string `json:"payload,omitempty"`
+	Truncated bool   `json:"truncated,omitempty"`
+}
+
+// StartDebugCapture starts recording HTTP request summaries and WebSocket
+// frames, with tokens and passwords redacted; nil uses the defaults
+// Starting a capture discards the entries of the last one
+func StartDebugCapture(config *CaptureConfig) error {
+	var cConfig *C.char
+	if config != nil {
+		jsonBytes, err := json.Marshal(config)
+		if err != nil {
+			return err
+		}
+		cConfig = C.CString(string(jsonBytes))
+		defer C.free(unsafe.Pointer(cConfig))
+	}
+
+	code := C.communicator_debug_capture_start(cConfig)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
+// StopDebugCapture stops recording; the entries stay available until the
+// next capture starts
+func StopDebugCapture() {
+	C.communicator_debug_capture_stop()
+}
+
+// DebugCaptureEntries returns the entries of the running or last capture,
+// oldest first
+func DebugCaptureEntries() ([]CaptureEntry, error) {
+	cstr := C.communicator_debug_capture_entries()
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var entries []CaptureEntry
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &entries); err != nil {
+		return nil, err
+	}
+
+	return entries, nil
+}
@@ -0,0 +1,78 @@
+package libcommunicator
+
+/*
+#include <communicator.h>
+#include <stdlib.h>
+*/
+import "C"
+import "encoding/json"
+
+// SlashCommand is a slash command users can run
+type SlashCommand struct {
+	Trigger     string `json:"trigger"` // Without the slash, e.g. "away"
+	Description string `json:"description"`
+	Hint        string `json:"hint"`         // Argument hint, e.g. "[message]"
+	DisplayName string `json:"display_name"` // Integration or plugin providing the command
+}
+
+// CommandResponse is the result of running a slash command
+type CommandResponse struct {
+	ResponseType string                 `json:"response_type"` // "ephemeral" or "in_channel"
+	Text         string                 `json:"text"`
+	GotoLocation *string                `json:"goto_location,omitempty"`
+	Props        map[string]interface{} `json:"props,omitempty"`
+}
+
+// ExecuteCommand runs a slash command (e.g. "/away") in a channel.
+// Messages the command posts arrive as regular message events.
+func (p *Platform) ExecuteCommand(channelID, command string) (*CommandResponse, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	csChannelID, freeChannelID := cStringFree(channelID)
+	defer freeChannelID()
+
+	csCommand, freeCommand := cStringFree(command)
+	defer freeCommand()
+
+	cstr := C.communicator_platform_execute_command(p.handle, csChannelID, csCommand)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var response CommandResponse
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &response); err != nil {
+		return nil, err
+	}
+
+	return &response, nil
+}
+
+// AutocompleteCommands lists the slash commands starting with prefix in a
+// team, or in the current team if teamID is empty
+func (p *Platform) AutocompleteCommands(teamID, prefix string, limit uint32) ([]SlashCommand, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	csTeamID, freeTeamID := cStringFree(teamID)
+	defer freeTeamID()
+
+	csPrefix, freePrefix := cStringFree(prefix)
+	defer freePrefix()
+
+	cstr := C.communicator_platform_autocomplete_commands(p.handle, csTeamID, csPrefix, C.uint32_t(limit))
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var commands []SlashCommand
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &commands); err != nil {
+		return nil, err
+	}
+
+	return commands, nil
+}
@@ -8,8 +8,10 @@ package libcommunicator
 */
 import "C"
 import (
+	"encoding/json"
 	"errors"
 	"fmt"
+	"time"
 	"unsafe"
 )
 
@@ -25,20 +27,25 @@ type Version struct {
 type ErrorCode int
 
 const (
-	Success           ErrorCode = 0
-	ErrorUnknown      ErrorCode = 1
-	ErrorInvalidArg   ErrorCode = 2
-	ErrorNullPointer  ErrorCode = 3
-	ErrorOutOfMemory  ErrorCode = 4
-	ErrorInvalidUTF8  ErrorCode = 5
-	ErrorNetwork      ErrorCode = 6
-	ErrorAuthFailed   ErrorCode = 7
-	ErrorNotFound     ErrorCode = 8
-	ErrorPermDenied   ErrorCode = 9
-	ErrorTimeout      ErrorCode = 10
-	ErrorInvalidState ErrorCode = 11
-	ErrorUnsupported  ErrorCode = 12
-	ErrorRateLimited  ErrorCode = 13
+	Success               ErrorCode = 0
+	ErrorUnknown          ErrorCode = 1
+	ErrorInvalidArg       ErrorCode = 2
+	ErrorNullPointer      ErrorCode = 3
+	ErrorOutOfMemory      ErrorCode = 4
+	ErrorInvalidUTF8      ErrorCode = 5
+	ErrorNetwork          ErrorCode = 6
+	ErrorAuthFailed       ErrorCode = 7
+	ErrorNotFound         ErrorCode = 8
+	ErrorPermDenied       ErrorCode = 9
+	ErrorTimeout          ErrorCode = 10
+	ErrorInvalidState     ErrorCode = 11
+	ErrorUnsupported      ErrorCode = 12
+	ErrorRateLimited      ErrorCode = 13
+	ErrorMsgTooLong       ErrorCode = 14
+	ErrorFileTooLarge     ErrorCode = 15
+	ErrorUnavailable      ErrorCode = 16
+	ErrorChecksumMismatch ErrorCode = 17
+	ErrorCancelled        ErrorCode = 18
 )
 
 var initialized bool
@@ -59,6 +66,87 @@ func Init() error {
 	return nil
 }
 
+// RuntimeOptions configures the library's threads. Zero fields keep the
+// defaults: one worker thread per CPU core, named by the runtime.
+type RuntimeOptions struct {
+	WorkerThreads    int    `json:"worker_threads,omitempty"`
+	ThreadNamePrefix string `json:"thread_name_prefix,omitempty"`
+}
+
+// RuntimeMetrics describes the library's runtime
+type RuntimeMetrics struct {
+	Workers          int  `json:"workers"`
+	AliveTasks       int  `json:"alive_tasks"`
+	GlobalQueueDepth int  `json:"global_queue_depth"`
+	HostDriven       bool `json:"host_driven"`
+	Shared           bool `json:"shared"`
+}
+
+// InitWithOptions initializes the library like Init, with control over its
+// threads. It fails if the library is already initialized with other options.
+func InitWithOptions(options RuntimeOptions) error {
+	jsonBytes, err := json.Marshal(options)
+	if err != nil {
+		return err
+	}
+
+	cs := C.CString(string(jsonBytes))
+	defer C.free(unsafe.Pointer(cs))
+
+	code := C.communicator_init_with_options(cs)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	initialized = true
+	return nil
+}
+
+// GetRuntimeMetrics returns the number of worker threads and tasks of the
+// library's runtime
+func GetRuntimeMetrics() (*RuntimeMetrics, error) {
+	cStr := C.communicator_runtime_metrics()
+	if cStr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cStr)
+
+	var metrics RuntimeMetrics
+	if err := json.Unmarshal([]byte(C.GoString(cStr)), &metrics); err != nil {
+		return nil, err
+	}
+
+	return &metrics, nil
+}
+
+// InitHostDriven initializes the library without background threads, for
+// embedders driving it from their own event loop with Tick. Events must be
+// polled; event callbacks are not available.
+func InitHostDriven() error {
+	if initialized {
+		return nil
+	}
+
+	code := C.communicator_init_host_driven()
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	initialized = true
+	return nil
+}
+
+// Tick runs ready background work (I/O, timers, tasks) without waiting, at
+// most maxWork passes. Returns the number of background tasks still alive.
+// Only available after InitHostDriven.
+func Tick(maxWork uint32) (int, error) {
+	alive := C.communicator_tick(C.uint32_t(maxWork))
+	if alive < 0 {
+		return 0, getLastError()
+	}
+	return int(alive), nil
+}
+
 // Cleanup cleans up the library
 // Should be called when done using the library
 func Cleanup() {
@@ -70,6 +158,12 @@ func Cleanup() {
 	initialized = false
 }
 
+// SetCallTimeout sets one deadline for all requests made by each library call,
+// instead of a timeout per request (Mattermost). Zero removes the budget.
+func SetCallTimeout(timeout time.Duration) {
+	C.communicator_set_call_timeout(C.uint64_t(timeout.Milliseconds()))
+}
+
 // GetVersion returns the library version information
 func GetVersion() Version {
 	return Version{
@@ -97,6 +191,37 @@ func getLastError() error {
 	return fmt.Errorf("libcommunicator error %d: %s", code, C.GoString(msg))
 }
 
+// LastErrorLimit returns the limit exceeded by the last error: the maximum
+// message length (characters) for ErrorMsgTooLong or file size (bytes) for
+// ErrorFileTooLarge. ok is false if the last error has no limit.
+func LastErrorLimit() (limit int64, ok bool) {
+	limit = int64(C.communicator_last_error_limit())
+	return limit, limit >= 0
+}
+
+// JSONErrorDetails tells where JSON input was rejected
+type JSONErrorDetails struct {
+	Pointer string `json:"pointer"`
+	Line    int    `json:"line"`
+	Column  int    `json:"column"`
+	Schema  string `json:"schema"`
+}
+
+// LastErrorDetails returns where the JSON input of the last call failed to
+// parse. ok is false if the last error did not come from parsing JSON input.
+func LastErrorDetails() (details JSONErrorDetails, ok bool) {
+	cDetails := C.communicator_last_error_details()
+	if cDetails == nil {
+		return details, false
+	}
+	defer C.communicator_free_string(cDetails)
+
+	if err := json.Unmarshal([]byte(C.GoString(cDetails)), &details); err != nil {
+		return details, false
+	}
+	return details, true
+}
+
 // clearError clears the last error
 func clearError() {
 	C.communicator_clear_error()
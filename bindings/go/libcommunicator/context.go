@@ -187,6 +187,21 @@ func (c *Context) ClearLogCallback() error {
 	return nil
 }
 
+// SetLogLevel sets the least severe level passed to the log callback
+// The default is LogInfo; the level may be changed at any time
+func (c *Context) SetLogLevel(level LogLevel) error {
+	if c.handle == nil {
+		return ErrInvalidContext
+	}
+
+	code := C.communicator_context_set_log_level(c.handle, C.CommunicatorLogLevel(level))
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
 // ErrInvalidContext is returned when operations are attempted on a nil or destroyed context
 var ErrInvalidContext = newError(ErrorInvalidState, "invalid context handle")
 
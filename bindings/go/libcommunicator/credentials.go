@@ -0,0 +1,94 @@
+package libcommunicator
+
+/*
+#include <communicator.h>
+#include <stdlib.h>
+*/
+import "C"
+import (
+	"encoding/json"
+)
+
+// CredentialStoreConfig selects where credentials are stored
+type CredentialStoreConfig struct {
+	// Backend is "memory" (the default), "file" or "keyring"
+	Backend string `json:"backend"`
+	// Path and Passphrase of the encrypted file ("file")
+	Path       string `json:"path,omitempty"`
+	Passphrase string `json:"passphrase,omitempty"`
+	// Service the keychain items are stored under ("keyring")
+	Service string `json:"service,omitempty"`
+}
+
+// ConfigureCredentials chooses where credentials are stored
+// Credentials in the previous store are not carried over
+func ConfigureCredentials(config CredentialStoreConfig) error {
+	jsonBytes, err := json.Marshal(config)
+	if err != nil {
+		return err
+	}
+
+	cs, free := cStringFree(string(jsonBytes))
+	defer free()
+
+	code := C.communicator_credentials_configure(cs)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
+// StoreCredentials stores credentials under an alias, for use with
+// PlatformConfig.WithCredentialsAlias
+func StoreCredentials(alias string, credentials map[string]string) error {
+	jsonBytes, err := json.Marshal(credentials)
+	if err != nil {
+		return err
+	}
+
+	cAlias, freeAlias := cStringFree(alias)
+	defer freeAlias()
+
+	cs, free := cStringFree(string(jsonBytes))
+	defer free()
+
+	code := C.communicator_credentials_store(cAlias, cs)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
+// RetrieveCredentials returns the credentials stored under an alias
+func RetrieveCredentials(alias string) (map[string]string, error) {
+	cAlias, free := cStringFree(alias)
+	defer free()
+
+	cstr := C.communicator_credentials_retrieve(cAlias)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var credentials map[string]string
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &credentials); err != nil {
+		return nil, err
+	}
+
+	return credentials, nil
+}
+
+// DeleteCredentials deletes the credentials stored under an alias
+func DeleteCredentials(alias string) error {
+	cAlias, free := cStringFree(alias)
+	defer free()
+
+	code := C.communicator_credentials_delete(cAlias)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
@@ -0,0 +1,47 @@
+package libcommunicator
+
+/*
+#include <communicator.h>
+#include <stdlib.h>
+*/
+import "C"
+import (
+	"unsafe"
+)
+
+// EnableDesktopNotifications publishes mentions and direct messages as desktop
+// notifications over D-Bus (org.freedesktop.Notifications)
+// Requires the library to be built with the dbus feature on Linux.
+// If appName is empty, "libcommunicator" is used.
+func (p *Platform) EnableDesktopNotifications(appName string) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	var csAppName *C.char
+	if appName != "" {
+		csAppName = C.CString(appName)
+		defer C.free(unsafe.Pointer(csAppName))
+	}
+
+	code := C.communicator_platform_enable_desktop_notifications(p.handle, csAppName)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
+// DisableDesktopNotifications stops publishing desktop notifications
+func (p *Platform) DisableDesktopNotifications() error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	code := C.communicator_platform_disable_desktop_notifications(p.handle)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
@@ -0,0 +1,126 @@
+package libcommunicator
+
+/*
+#include <communicator.h>
+#include <stdlib.h>
+*/
+import "C"
+import (
+	"encoding/json"
+	"time"
+)
+
+// Draft represents an unsent message being composed in a channel or thread
+type Draft struct {
+	ChannelID  string                 `json:"channel_id"`
+	RootID     string                 `json:"root_id,omitempty"`
+	Message    string                 `json:"message"`
+	FileIDs    []string               `json:"file_ids,omitempty"`
+	Props      map[string]interface{} `json:"props,omitempty"`
+	UpdatedAt  time.Time              `json:"updated_at"`
+	ServerSide bool                   `json:"server_side"` // Synced through the server
+}
+
+// SaveDraft saves the draft of a channel or thread, replacing any previous one
+func (p *Platform) SaveDraft(draft Draft) (*Draft, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	draftJSON, err := json.Marshal(draft)
+	if err != nil {
+		return nil, err
+	}
+
+	csDraft, freeDraft := cStringFree(string(draftJSON))
+	defer freeDraft()
+
+	cstr := C.communicator_platform_save_draft(p.handle, csDraft)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var saved Draft
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &saved); err != nil {
+		return nil, err
+	}
+
+	return &saved, nil
+}
+
+// GetDraft returns the draft of a channel or, if rootID is not empty, a
+// thread; nil if there is none
+func (p *Platform) GetDraft(channelID, rootID string) (*Draft, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	csChannelID, freeChannelID := cStringFree(channelID)
+	defer freeChannelID()
+
+	var csRootID *C.char
+	if rootID != "" {
+		var freeRootID func()
+		csRootID, freeRootID = cStringFree(rootID)
+		defer freeRootID()
+	}
+
+	cstr := C.communicator_platform_get_draft(p.handle, csChannelID, csRootID)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var draft *Draft
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &draft); err != nil {
+		return nil, err
+	}
+
+	return draft, nil
+}
+
+// GetDrafts returns the drafts of the current user, most recently saved first
+func (p *Platform) GetDrafts() ([]Draft, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	cstr := C.communicator_platform_get_drafts(p.handle)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var drafts []Draft
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &drafts); err != nil {
+		return nil, err
+	}
+
+	return drafts, nil
+}
+
+// DeleteDraft deletes the draft of a channel or, if rootID is not empty, a
+// thread
+func (p *Platform) DeleteDraft(channelID, rootID string) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	csChannelID, freeChannelID := cStringFree(channelID)
+	defer freeChannelID()
+
+	var csRootID *C.char
+	if rootID != "" {
+		var freeRootID func()
+		csRootID, freeRootID = cStringFree(rootID)
+		defer freeRootID()
+	}
+
+	code := C.communicator_platform_delete_draft(p.handle, csChannelID, csRootID)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
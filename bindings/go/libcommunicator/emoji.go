@@ -0,0 +1,132 @@
+package libcommunicator
+
+/*
+#include <communicator.h>
+#include <stdlib.h>
+*/
+import "C"
+import "encoding/json"
+
+// UnicodeEmoji is a standard emoji and the shortcode naming it
+type UnicodeEmoji struct {
+	Name    string `json:"name"` // Without colons, e.g. "thumbsup"
+	Unicode string `json:"unicode"`
+}
+
+// CreateEmoji uploads a custom emoji. Standard emoji names are taken.
+func (p *Platform) CreateEmoji(name string, image []byte) (*Emoji, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	csName, freeName := cStringFree(name)
+	defer freeName()
+
+	cImage := C.CBytes(image)
+	defer C.free(cImage)
+
+	cstr := C.communicator_platform_create_emoji(p.handle, csName, (*C.uint8_t)(cImage), C.size_t(len(image)))
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var emoji Emoji
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &emoji); err != nil {
+		return nil, err
+	}
+
+	return &emoji, nil
+}
+
+// DeleteEmoji deletes a custom emoji
+func (p *Platform) DeleteEmoji(emojiID string) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	csEmojiID, freeEmojiID := cStringFree(emojiID)
+	defer freeEmojiID()
+
+	code := C.communicator_platform_delete_emoji(p.handle, csEmojiID)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
+// AutocompleteEmojis suggests custom emojis for a partly typed name.
+// Standard emojis are suggested by AutocompleteUnicodeEmojis.
+func (p *Platform) AutocompleteEmojis(prefix string, limit uint32) ([]Emoji, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	csPrefix, freePrefix := cStringFree(prefix)
+	defer freePrefix()
+
+	cstr := C.communicator_platform_autocomplete_emojis(p.handle, csPrefix, C.uint32_t(limit))
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var emojis []Emoji
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &emojis); err != nil {
+		return nil, err
+	}
+
+	return emojis, nil
+}
+
+// ResolveEmoji resolves a standard emoji shortcode ("thumbsup" or ":+1:") to
+// its emoji. It returns false for unknown shortcodes, which may name custom
+// emojis.
+func ResolveEmoji(shortcode string) (string, bool) {
+	cs, free := cStringFree(shortcode)
+	defer free()
+
+	cstr := C.communicator_emoji_resolve(cs)
+	if cstr == nil {
+		return "", false
+	}
+	defer freeString(cstr)
+
+	return C.GoString(cstr), true
+}
+
+// ReplaceEmojiShortcodes replaces the standard emoji :shortcodes: in a text
+// with their emojis, keeping those in code and unknown (custom) ones
+func ReplaceEmojiShortcodes(text string) (string, error) {
+	cs, free := cStringFree(text)
+	defer free()
+
+	cstr := C.communicator_emoji_replace_shortcodes(cs)
+	if cstr == nil {
+		return "", getLastError()
+	}
+	defer freeString(cstr)
+
+	return C.GoString(cstr), nil
+}
+
+// AutocompleteUnicodeEmojis suggests standard emojis for a partly typed
+// shortcode: those starting with the prefix, then those containing it
+func AutocompleteUnicodeEmojis(prefix string, limit uint32) ([]UnicodeEmoji, error) {
+	cs, free := cStringFree(prefix)
+	defer free()
+
+	cstr := C.communicator_emoji_autocomplete(cs, C.uint32_t(limit))
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var emojis []UnicodeEmoji
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &emojis); err != nil {
+		return nil, err
+	}
+
+	return emojis, nil
+}
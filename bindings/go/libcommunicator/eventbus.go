@@ -0,0 +1,113 @@
+package libcommunicator
+
+/*
+#include <communicator.h>
+#include <stdlib.h>
+*/
+import "C"
+import (
+	"encoding/json"
+	"runtime"
+	"time"
+)
+
+// EventBus merges the events of several platforms into one queue, so one
+// polling loop serves every connection
+type EventBus struct {
+	handle C.CommunicatorEventBus
+}
+
+// BusEvent is an event taken from one of the platforms of a bus
+type BusEvent struct {
+	PlatformID string    `json:"platform_id"`
+	Sequence   uint64    `json:"sequence"`
+	ReceivedAt time.Time `json:"received_at"`
+	Event      Event     `json:"event"`
+}
+
+// NewEventBus creates an event bus without platforms
+func NewEventBus() (*EventBus, error) {
+	if err := ensureInitialized(); err != nil {
+		return nil, err
+	}
+
+	handle := C.communicator_eventbus_create()
+	if handle == nil {
+		return nil, getLastError()
+	}
+
+	b := &EventBus{handle: handle}
+
+	runtime.SetFinalizer(b, func(b *EventBus) {
+		b.Destroy()
+	})
+
+	return b, nil
+}
+
+// AddPlatform adds a platform whose events are tagged with platformID
+// The platform's events must then only be polled through the bus
+func (b *EventBus) AddPlatform(p *Platform, platformID string) error {
+	if b.handle == nil || p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	cs, free := cStringFree(platformID)
+	defer free()
+
+	code := C.communicator_eventbus_add_platform(b.handle, p.handle, cs)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
+// RemovePlatform removes a platform; its events still queued are dropped
+func (b *EventBus) RemovePlatform(platformID string) error {
+	if b.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	cs, free := cStringFree(platformID)
+	defer free()
+
+	code := C.communicator_eventbus_remove_platform(b.handle, cs)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
+// Poll returns the next event of any platform on the bus, or nil if none has one
+func (b *EventBus) Poll() (*BusEvent, error) {
+	if b.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	cstr := C.communicator_eventbus_poll(b.handle)
+	if cstr == nil {
+		// Check if it's an error or just no events
+		if C.communicator_last_error_code() != C.COMMUNICATOR_SUCCESS {
+			return nil, getLastError()
+		}
+		return nil, nil // No events available
+	}
+	defer freeString(cstr)
+
+	var event BusEvent
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &event); err != nil {
+		return nil, err
+	}
+
+	return &event, nil
+}
+
+// Destroy frees the event bus resources; the platforms on it are not destroyed
+func (b *EventBus) Destroy() {
+	if b.handle != nil {
+		C.communicator_eventbus_destroy(b.handle)
+		b.handle = nil
+	}
+}
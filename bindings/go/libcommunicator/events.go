@@ -136,6 +136,46 @@ func (r *EventRouter) OnMessagePosted(handler EventHandler) {
 	r.On(EventMessagePosted, handler)
 }
 
+// OnMessageConfirmed registers a handler for the server's echo of messages sent by this client
+func (r *EventRouter) OnMessageConfirmed(handler EventHandler) {
+	r.On(EventMessageConfirmed, handler)
+}
+
+// OnMessageSendSucceeded registers a handler for queued messages that were sent after a reconnect
+func (r *EventRouter) OnMessageSendSucceeded(handler EventHandler) {
+	r.On(EventMessageSendSucceeded, handler)
+}
+
+// OnMessageSendFailed registers a handler for queued messages that could not be sent
+func (r *EventRouter) OnMessageSendFailed(handler EventHandler) {
+	r.On(EventMessageSendFailed, handler)
+}
+
+// OnSessionExpired registers a handler for sessions that expired and need new credentials
+func (r *EventRouter) OnSessionExpired(handler EventHandler) {
+	r.On(EventSessionExpired, handler)
+}
+
+// OnChannelListChanged registers a handler for coarse channel list invalidation hints
+func (r *EventRouter) OnChannelListChanged(handler EventHandler) {
+	r.On(EventChannelListChanged, handler)
+}
+
+// OnUnreadsChanged registers a handler for coarse unread invalidation hints
+func (r *EventRouter) OnUnreadsChanged(handler EventHandler) {
+	r.On(EventUnreadsChanged, handler)
+}
+
+// OnProfileCacheChanged registers a handler for coarse profile invalidation hints
+func (r *EventRouter) OnProfileCacheChanged(handler EventHandler) {
+	r.On(EventProfileCacheChanged, handler)
+}
+
+// OnNotificationTriggered registers a handler for messages that mention the user or match their keywords
+func (r *EventRouter) OnNotificationTriggered(handler EventHandler) {
+	r.On(EventNotificationTriggered, handler)
+}
+
 // OnMessageUpdated registers a handler for message updated events
 func (r *EventRouter) OnMessageUpdated(handler EventHandler) {
 	r.On(EventMessageUpdated, handler)
@@ -146,6 +186,16 @@ func (r *EventRouter) OnMessageDeleted(handler EventHandler) {
 	r.On(EventMessageDeleted, handler)
 }
 
+// OnMessagePinned registers a handler for messages pinned to their channel
+func (r *EventRouter) OnMessagePinned(handler EventHandler) {
+	r.On(EventMessagePinned, handler)
+}
+
+// OnMessageUnpinned registers a handler for messages unpinned from their channel
+func (r *EventRouter) OnMessageUnpinned(handler EventHandler) {
+	r.On(EventMessageUnpinned, handler)
+}
+
 // OnUserStatusChanged registers a handler for user status changed events
 func (r *EventRouter) OnUserStatusChanged(handler EventHandler) {
 	r.On(EventUserStatusChanged, handler)
@@ -186,6 +236,11 @@ func (r *EventRouter) OnConnectionStateChanged(handler EventHandler) {
 	r.On(EventConnectionStateChange, handler)
 }
 
+// OnEventsMissed registers a handler for gaps in the real-time event stream
+func (r *EventRouter) OnEventsMissed(handler EventHandler) {
+	r.On(EventEventsMissed, handler)
+}
+
 // Handle dispatches an event to all registered handlers
 func (r *EventRouter) Handle(event *Event) {
 	r.mu.RLock()
@@ -8,6 +8,7 @@ package libcommunicator
 */
 import "C"
 import (
+	"context"
 	"encoding/json"
 	"errors"
 	"fmt"
@@ -15,7 +16,8 @@ import (
 )
 
 // UploadFile uploads a file to a channel
-// Returns the file ID on success
+// Returns the file ID on success; an earlier, not yet attached upload of the
+// same content to the channel is reused instead of uploading it again
 func (p *Platform) UploadFile(channelID, filePath string) (string, error) {
 	cChannelID := C.CString(channelID)
 	defer C.free(unsafe.Pointer(cChannelID))
@@ -32,16 +34,44 @@ func (p *Platform) UploadFile(channelID, filePath string) (string, error) {
 	return C.GoString(result), nil
 }
 
+// UploadFileResumable uploads a file from disk in chunks, holding only one
+// chunk in memory. If it fails, calling it again with the unchanged file and
+// the same channel resumes the upload. Returns the file ID
+func (p *Platform) UploadFileResumable(channelID, filePath string) (string, error) {
+	cChannelID := C.CString(channelID)
+	defer C.free(unsafe.Pointer(cChannelID))
+
+	cFilePath := C.CString(filePath)
+	defer C.free(unsafe.Pointer(cFilePath))
+
+	result := C.communicator_platform_upload_file_with_progress(p.handle, cChannelID, cFilePath, nil, nil)
+	if result == nil {
+		return "", getLastError()
+	}
+
+	defer C.communicator_free_string(result)
+	return C.GoString(result), nil
+}
+
 // DownloadFile downloads a file by its ID
 // Returns the file contents as bytes
 func (p *Platform) DownloadFile(fileID string) ([]byte, error) {
+	return p.DownloadFileContext(context.Background(), fileID)
+}
+
+// DownloadFileContext is DownloadFile, returning an ErrorCancelled error once
+// ctx is done
+func (p *Platform) DownloadFileContext(ctx context.Context, fileID string) ([]byte, error) {
 	cFileID := C.CString(fileID)
 	defer C.free(unsafe.Pointer(cFileID))
 
 	var data *C.uint8_t
 	var size C.size_t
 
-	code := C.communicator_platform_download_file(p.handle, cFileID, &data, &size)
+	var code C.CommunicatorErrorCode
+	withCancellation(ctx, func(cancellation C.CommunicatorCancellation) {
+		code = C.communicator_platform_download_file_ex(p.handle, cFileID, &data, &size, cancellation)
+	})
 	if code != C.COMMUNICATOR_SUCCESS {
 		return nil, getLastError()
 	}
@@ -56,6 +86,29 @@ func (p *Platform) DownloadFile(fileID string) ([]byte, error) {
 	return goData, nil
 }
 
+// DownloadFileToPath downloads a file straight to disk, for files too large to
+// hold in memory. An empty path downloads to a new file in the temp directory,
+// which the caller must remove. An interrupted download to a path resumes when
+// called again with the same path. Returns the path of the downloaded file
+func (p *Platform) DownloadFileToPath(fileID, path string) (string, error) {
+	cFileID := C.CString(fileID)
+	defer C.free(unsafe.Pointer(cFileID))
+
+	var cPath *C.char
+	if path != "" {
+		cPath = C.CString(path)
+		defer C.free(unsafe.Pointer(cPath))
+	}
+
+	result := C.communicator_platform_download_file_to_path(p.handle, cFileID, cPath)
+	if result == nil {
+		return "", getLastError()
+	}
+
+	defer C.communicator_free_string(result)
+	return C.GoString(result), nil
+}
+
 // GetFileMetadata retrieves file metadata without downloading the file
 func (p *Platform) GetFileMetadata(fileID string) (*Attachment, error) {
 	cFileID := C.CString(fileID)
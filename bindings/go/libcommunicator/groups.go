@@ -0,0 +1,90 @@
+package libcommunicator
+
+/*
+#include <communicator.h>
+#include <stdlib.h>
+*/
+import "C"
+import "encoding/json"
+
+// Group is a group of users. Only groups with a Name can be mentioned.
+type Group struct {
+	ID          string  `json:"id"`
+	Name        *string `json:"name,omitempty"` // Mention name without "@"
+	DisplayName string  `json:"display_name"`
+	Description string  `json:"description"`
+	Source      string  `json:"source"` // e.g. "ldap" or "custom"
+	MemberCount *uint32 `json:"member_count,omitempty"`
+}
+
+// GroupMention is a group mentioned in a message, with its members
+type GroupMention struct {
+	Group   Group  `json:"group"`
+	Members []User `json:"members"`
+}
+
+// GetGroups returns the user groups visible to the current user
+func (p *Platform) GetGroups() ([]Group, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	cstr := C.communicator_platform_get_groups(p.handle)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var groups []Group
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &groups); err != nil {
+		return nil, err
+	}
+
+	return groups, nil
+}
+
+// GetGroupMembers returns the members of a user group
+func (p *Platform) GetGroupMembers(groupID string) ([]User, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	cs, free := cStringFree(groupID)
+	defer free()
+
+	cstr := C.communicator_platform_get_group_members(p.handle, cs)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var users []User
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &users); err != nil {
+		return nil, err
+	}
+
+	return users, nil
+}
+
+// ResolveGroupMentions finds the groups mentioned in a message, with their members
+func (p *Platform) ResolveGroupMentions(text string) ([]GroupMention, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	cs, free := cStringFree(text)
+	defer free()
+
+	cstr := C.communicator_platform_resolve_group_mentions(p.handle, cs)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var mentions []GroupMention
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &mentions); err != nil {
+		return nil, err
+	}
+
+	return mentions, nil
+}
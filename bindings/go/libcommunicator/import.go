@@ -0,0 +1,77 @@
+package libcommunicator
+
+/*
+#include <communicator.h>
+#include <stdlib.h>
+*/
+import "C"
+import "encoding/json"
+
+// ImportOptions controls how a history is imported. Zero values are not
+// defaults; start from DefaultImportOptions.
+type ImportOptions struct {
+	BatchSize    int    `json:"batch_size"`     // Messages sent per batch
+	BatchDelayMs uint64 `json:"batch_delay_ms"` // Pause between batches
+	MaxRetries   uint32 `json:"max_retries"`    // Retries of a rate-limited message
+	Attribution  bool   `json:"attribution"`    // Prefix texts with the original author and time
+}
+
+// DefaultImportOptions returns the options used when none are given
+func DefaultImportOptions() ImportOptions {
+	return ImportOptions{
+		BatchSize:    20,
+		BatchDelayMs: 1000,
+		MaxRetries:   5,
+		Attribution:  true,
+	}
+}
+
+// ImportFailure is a line of an export that could not be imported
+type ImportFailure struct {
+	Line  int    `json:"line"`
+	Error string `json:"error"`
+}
+
+// ImportReport is the outcome of an import
+type ImportReport struct {
+	Imported int             `json:"imported"`
+	Skipped  int             `json:"skipped"` // Lines without messages
+	Failed   []ImportFailure `json:"failed"`
+}
+
+// ImportHistory replays a JSONL export into a channel. Lines are Message
+// objects or Mattermost bulk export lines. Messages are sent by this account
+// in batches and tagged with their original author and time. The call blocks
+// until the whole export was sent.
+func (p *Platform) ImportHistory(channelID, jsonl string, options ImportOptions) (*ImportReport, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	optionsJSON, err := json.Marshal(options)
+	if err != nil {
+		return nil, err
+	}
+
+	csChannelID, freeChannelID := cStringFree(channelID)
+	defer freeChannelID()
+
+	csJSONL, freeJSONL := cStringFree(jsonl)
+	defer freeJSONL()
+
+	csOptions, freeOptions := cStringFree(string(optionsJSON))
+	defer freeOptions()
+
+	cstr := C.communicator_import_history(p.handle, csChannelID, csJSONL, csOptions)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var report ImportReport
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &report); err != nil {
+		return nil, err
+	}
+
+	return &report, nil
+}
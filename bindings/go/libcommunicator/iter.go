@@ -0,0 +1,215 @@
+package libcommunicator
+
+/*
+#include <communicator.h>
+#include <stdlib.h>
+*/
+import "C"
+import (
+	"encoding/json"
+	"runtime"
+)
+
+// entityIter yields a snapshot of cached entities as JSON
+type entityIter struct {
+	handle C.CommunicatorEntityIter
+}
+
+func newEntityIter(handle C.CommunicatorEntityIter) (*entityIter, error) {
+	if handle == nil {
+		return nil, getLastError()
+	}
+
+	it := &entityIter{handle: handle}
+
+	runtime.SetFinalizer(it, func(it *entityIter) {
+		it.destroy()
+	})
+
+	return it, nil
+}
+
+// next decodes the next entity into v, returning false when exhausted
+func (it *entityIter) next(v interface{}) (bool, error) {
+	if it.handle == nil {
+		return false, ErrInvalidHandle
+	}
+
+	cstr := C.communicator_iter_next(it.handle)
+	if cstr == nil {
+		// Check if it's an error or just the end
+		if C.communicator_last_error_code() != C.COMMUNICATOR_SUCCESS {
+			return false, getLastError()
+		}
+		return false, nil
+	}
+	defer freeString(cstr)
+
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), v); err != nil {
+		return false, err
+	}
+
+	return true, nil
+}
+
+func (it *entityIter) remaining() int {
+	if it.handle == nil {
+		return 0
+	}
+	n := C.communicator_iter_remaining(it.handle)
+	if n < 0 {
+		return 0
+	}
+	return int(n)
+}
+
+func (it *entityIter) destroy() {
+	if it.handle != nil {
+		C.communicator_iter_destroy(it.handle)
+		it.handle = nil
+	}
+}
+
+// ChannelIter yields the channels a platform had cached when it was created
+type ChannelIter struct {
+	it *entityIter
+}
+
+// IterChannels iterates over the cached channels, ordered by ID, without network calls
+func (p *Platform) IterChannels() (*ChannelIter, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	it, err := newEntityIter(C.communicator_platform_iter_channels(p.handle))
+	if err != nil {
+		return nil, err
+	}
+
+	return &ChannelIter{it: it}, nil
+}
+
+// Next returns the next channel, or nil when the iterator is exhausted
+func (c *ChannelIter) Next() (*Channel, error) {
+	var channel Channel
+	ok, err := c.it.next(&channel)
+	if !ok {
+		return nil, err
+	}
+	return &channel, nil
+}
+
+// Remaining returns the number of channels not yielded yet
+func (c *ChannelIter) Remaining() int {
+	return c.it.remaining()
+}
+
+// Close frees the iterator resources
+func (c *ChannelIter) Close() {
+	c.it.destroy()
+}
+
+// UserIter yields the users a platform had cached when it was created
+type UserIter struct {
+	it *entityIter
+}
+
+// IterUsers iterates over the cached users, ordered by ID, without network calls
+func (p *Platform) IterUsers() (*UserIter, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	it, err := newEntityIter(C.communicator_platform_iter_users(p.handle))
+	if err != nil {
+		return nil, err
+	}
+
+	return &UserIter{it: it}, nil
+}
+
+// Next returns the next user, or nil when the iterator is exhausted
+func (u *UserIter) Next() (*User, error) {
+	var user User
+	ok, err := u.it.next(&user)
+	if !ok {
+		return nil, err
+	}
+	return &user, nil
+}
+
+// Remaining returns the number of users not yielded yet
+func (u *UserIter) Remaining() int {
+	return u.it.remaining()
+}
+
+// Close frees the iterator resources
+func (u *UserIter) Close() {
+	u.it.destroy()
+}
+
+// MessageCursor walks a channel's message history in batches
+type MessageCursor struct {
+	handle C.CommunicatorMessageCursor
+}
+
+// OpenMessageCursor opens a cursor over a channel's history, starting after
+// the message from (or at the newest message if from is empty) and walking in
+// direction "older" or "newer"
+func (p *Platform) OpenMessageCursor(channelID, from, direction string) (*MessageCursor, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	cChannelID, freeChannelID := cStringFree(channelID)
+	defer freeChannelID()
+	cDirection, freeDirection := cStringFree(direction)
+	defer freeDirection()
+	var cFrom *C.char
+	if from != "" {
+		var freeFrom func()
+		cFrom, freeFrom = cStringFree(from)
+		defer freeFrom()
+	}
+
+	handle := C.communicator_platform_open_message_cursor(p.handle, cChannelID, cFrom, cDirection)
+	if handle == nil {
+		return nil, getLastError()
+	}
+
+	cursor := &MessageCursor{handle: handle}
+	runtime.SetFinalizer(cursor, func(c *MessageCursor) {
+		c.Close()
+	})
+
+	return cursor, nil
+}
+
+// Next fetches up to batchSize messages, oldest first; an empty slice means
+// the cursor is exhausted
+func (c *MessageCursor) Next(batchSize uint32) ([]Message, error) {
+	if c.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	cstr := C.communicator_message_cursor_next(c.handle, C.uint32_t(batchSize))
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var messages []Message
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &messages); err != nil {
+		return nil, err
+	}
+
+	return messages, nil
+}
+
+// Close frees the cursor resources
+func (c *MessageCursor) Close() {
+	if c.handle != nil {
+		C.communicator_message_cursor_close(c.handle)
+		c.handle = nil
+	}
+}
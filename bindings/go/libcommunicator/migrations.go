@@ -0,0 +1,67 @@
+package libcommunicator
+
+/*
+#include <communicator.h>
+#include <stdlib.h>
+*/
+import "C"
+import (
+	"encoding/json"
+)
+
+// MigrationReport describes migrations applied to (or, in a dry run,
+// pending for) one persisted file
+type MigrationReport struct {
+	Store       string   `json:"store"`
+	Path        string   `json:"path"`
+	FromVersion uint32   `json:"from_version"`
+	ToVersion   uint32   `json:"to_version"`
+	Migrations  []string `json:"migrations"`
+	Backup      *string  `json:"backup"`
+	DryRun      bool     `json:"dry_run"`
+}
+
+// MigrateStore upgrades a persisted file ("reminders", "outbox",
+// "startup_cache" or "entity_cache") to the current format ahead of opening it
+// Returns nil if the file does not exist or is up to date
+func MigrateStore(store, path string, dryRun bool) (*MigrationReport, error) {
+	cStore, freeStore := cStringFree(store)
+	defer freeStore()
+
+	cPath, freePath := cStringFree(path)
+	defer freePath()
+
+	var cDryRun C.int
+	if dryRun {
+		cDryRun = 1
+	}
+
+	cstr := C.communicator_migrate_store(cStore, cPath, cDryRun)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var report *MigrationReport
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &report); err != nil {
+		return nil, err
+	}
+
+	return report, nil
+}
+
+// AppliedMigrations lists the migrations applied since the library was loaded
+func AppliedMigrations() ([]MigrationReport, error) {
+	cstr := C.communicator_migrations_applied()
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var reports []MigrationReport
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &reports); err != nil {
+		return nil, err
+	}
+
+	return reports, nil
+}
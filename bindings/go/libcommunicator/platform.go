@@ -6,8 +6,11 @@ package libcommunicator
 */
 import "C"
 import (
+	"context"
 	"encoding/json"
+	"fmt"
 	"runtime"
+	"time"
 	"unsafe"
 )
 
@@ -40,8 +43,106 @@ func NewMattermostPlatform(serverURL string) (*Platform, error) {
 	return p, nil
 }
 
+// NewMatrixPlatform creates a new Matrix platform instance
+func NewMatrixPlatform(homeserverURL string) (*Platform, error) {
+	if err := ensureInitialized(); err != nil {
+		return nil, err
+	}
+
+	cs, free := cStringFree(homeserverURL)
+	defer free()
+
+	handle := C.communicator_matrix_create(cs)
+	if handle == nil {
+		return nil, getLastError()
+	}
+
+	p := &Platform{handle: handle}
+
+	// Set up finalizer to ensure cleanup
+	runtime.SetFinalizer(p, func(p *Platform) {
+		p.Destroy()
+	})
+
+	return p, nil
+}
+
+// NewXMPPPlatform creates a new XMPP platform instance. server is
+// "host[:port]", or "" to connect to the domain of the JID given to Connect.
+func NewXMPPPlatform(server string) (*Platform, error) {
+	if err := ensureInitialized(); err != nil {
+		return nil, err
+	}
+
+	cs, free := cStringFree(server)
+	defer free()
+
+	handle := C.communicator_xmpp_create(cs)
+	if handle == nil {
+		return nil, getLastError()
+	}
+
+	p := &Platform{handle: handle}
+
+	// Set up finalizer to ensure cleanup
+	runtime.SetFinalizer(p, func(p *Platform) {
+		p.Destroy()
+	})
+
+	return p, nil
+}
+
+// NewSharedMattermostPlatform returns a Mattermost platform shared with other
+// consumers in the process that use the same connection key. The connection is
+// only closed once every consumer has destroyed its Platform. Another consumer
+// may already have connected it; check IsConnected before calling Connect.
+func NewSharedMattermostPlatform(connectionKey, serverURL string) (*Platform, error) {
+	if err := ensureInitialized(); err != nil {
+		return nil, err
+	}
+
+	csKey, freeKey := cStringFree(connectionKey)
+	defer freeKey()
+	csURL, freeURL := cStringFree(serverURL)
+	defer freeURL()
+
+	handle := C.communicator_mattermost_create_shared(csKey, csURL)
+	if handle == nil {
+		return nil, getLastError()
+	}
+
+	p := &Platform{handle: handle}
+
+	// Set up finalizer to ensure cleanup
+	runtime.SetFinalizer(p, func(p *Platform) {
+		p.Destroy()
+	})
+
+	return p, nil
+}
+
+// SharedCount returns the number of consumers sharing this platform's connection,
+// or 0 if it was not created with NewSharedMattermostPlatform
+func (p *Platform) SharedCount() (int, error) {
+	if p.handle == nil {
+		return 0, ErrInvalidHandle
+	}
+
+	count := C.communicator_platform_shared_count(p.handle)
+	if count < 0 {
+		return 0, getLastError()
+	}
+
+	return int(count), nil
+}
+
 // Connect connects to the platform and authenticates
 func (p *Platform) Connect(config *PlatformConfig) error {
+	return p.ConnectContext(context.Background(), config)
+}
+
+// ConnectContext is Connect, returning an ErrorCancelled error once ctx is done
+func (p *Platform) ConnectContext(ctx context.Context, config *PlatformConfig) error {
 	if p.handle == nil {
 		return ErrInvalidHandle
 	}
@@ -55,7 +156,10 @@ func (p *Platform) Connect(config *PlatformConfig) error {
 	cs, free := cStringFree(string(jsonBytes))
 	defer free()
 
-	code := C.communicator_platform_connect(p.handle, cs)
+	var code C.CommunicatorErrorCode
+	withCancellation(ctx, func(cancellation C.CommunicatorCancellation) {
+		code = C.communicator_platform_connect_ex(p.handle, cs, cancellation)
+	})
 	if code != C.COMMUNICATOR_SUCCESS {
 		return getLastError()
 	}
@@ -100,6 +204,74 @@ func (p *Platform) ConnectWithMFA(config *PlatformConfig) error {
 	return nil
 }
 
+// BeginSSOLogin starts a single sign-on login through an OAuth 2.0 provider
+//
+// The platform doesn't need to be connected. Open the returned URL in a
+// browser and pass the URL the provider redirects to to CompleteSSOLogin.
+// Mattermost providers are "gitlab", "google", "office365" and "openid".
+func (p *Platform) BeginSSOLogin(provider string) (string, error) {
+	if p.handle == nil {
+		return "", ErrInvalidHandle
+	}
+
+	cs, free := cStringFree(provider)
+	defer free()
+
+	cstr := C.communicator_platform_begin_sso_login(p.handle, cs)
+	if cstr == nil {
+		return "", getLastError()
+	}
+	defer freeString(cstr)
+
+	return C.GoString(cstr), nil
+}
+
+// CompleteSSOLogin finishes a single sign-on login and returns the session token
+//
+// Connect with the token as the "token" credential.
+func (p *Platform) CompleteSSOLogin(callbackURL string) (string, error) {
+	if p.handle == nil {
+		return "", ErrInvalidHandle
+	}
+
+	cs, free := cStringFree(callbackURL)
+	defer free()
+
+	cstr := C.communicator_platform_complete_sso_login(p.handle, cs)
+	if cstr == nil {
+		return "", getLastError()
+	}
+	defer freeString(cstr)
+
+	return C.GoString(cstr), nil
+}
+
+// RenewSession replaces an expired session with one from new credentials
+//
+// Call it after an EventSessionExpired event: requests fail until it succeeds.
+// Sessions from a password login without MFA are renewed automatically.
+// The credentials take the same keys as PlatformConfig.Credentials.
+func (p *Platform) RenewSession(credentials map[string]string) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	jsonBytes, err := json.Marshal(credentials)
+	if err != nil {
+		return err
+	}
+
+	cs, free := cStringFree(string(jsonBytes))
+	defer free()
+
+	code := C.communicator_platform_renew_session(p.handle, cs)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
 // Disconnect disconnects from the platform
 func (p *Platform) Disconnect() error {
 	if p.handle == nil {
@@ -144,6 +316,26 @@ func (p *Platform) GetConnectionInfo() (*ConnectionInfo, error) {
 	return &info, nil
 }
 
+// GetCapabilities returns the platform's capabilities, including server limits
+func (p *Platform) GetCapabilities() (*PlatformCapabilities, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	cstr := C.communicator_platform_get_capabilities(p.handle)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var caps PlatformCapabilities
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &caps); err != nil {
+		return nil, err
+	}
+
+	return &caps, nil
+}
+
 // SendMessage sends a message to a channel
 func (p *Platform) SendMessage(channelID, text string) (*Message, error) {
 	if p.handle == nil {
@@ -170,6 +362,349 @@ func (p *Platform) SendMessage(channelID, text string) (*Message, error) {
 	return &msg, nil
 }
 
+// SendMessageChunked sends a message, splitting it into numbered parts if it
+// exceeds maxLen characters (0 uses the server limit)
+func (p *Platform) SendMessageChunked(channelID, text string, maxLen uint32) ([]Message, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	csChannelID, freeChannelID := cStringFree(channelID)
+	defer freeChannelID()
+
+	csText, freeText := cStringFree(text)
+	defer freeText()
+
+	cstr := C.communicator_platform_send_message_chunked(p.handle, csChannelID, csText, C.uint32_t(maxLen))
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var messages []Message
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &messages); err != nil {
+		return nil, err
+	}
+
+	return messages, nil
+}
+
+// SetChunkReassembly enables or disables reassembly of split messages in PollEvent
+func (p *Platform) SetChunkReassembly(enabled bool) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	var cEnabled C.int
+	if enabled {
+		cEnabled = 1
+	}
+
+	code := C.communicator_platform_set_chunk_reassembly(p.handle, cEnabled)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
+// SetLanguageDetection enables or disables language detection for incoming
+// messages, setting Message.Language. An empty channelID configures all
+// channels without their own setting.
+func (p *Platform) SetLanguageDetection(channelID string, enabled bool) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	var csChannelID *C.char
+	if channelID != "" {
+		var freeChannelID func()
+		csChannelID, freeChannelID = cStringFree(channelID)
+		defer freeChannelID()
+	}
+
+	var cEnabled C.int
+	if enabled {
+		cEnabled = 1
+	}
+
+	code := C.communicator_platform_set_language_detection(p.handle, csChannelID, cEnabled)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
+// SetInvalidationHints enables coarse invalidation hints (EventChannelListChanged,
+// EventUnreadsChanged, EventProfileCacheChanged), each delivered at most once
+// per interval; an interval of 0 disables them.
+func (p *Platform) SetInvalidationHints(interval time.Duration) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	code := C.communicator_platform_set_invalidation_hints(p.handle, C.uint64_t(interval.Milliseconds()))
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
+// SetEventCoalescing holds back EventUserStatusChanged and EventUserTyping
+// events and delivers them once per interval, collapsed to the latest status
+// of each user and each user still typing; an interval of 0 disables it.
+func (p *Platform) SetEventCoalescing(interval time.Duration) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	code := C.communicator_platform_set_event_coalescing(p.handle, C.uint64_t(interval.Milliseconds()))
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
+// SetNotifications enables or disables mention and keyword notifications:
+// messages that mention the current user, mention the whole channel, contain
+// one of their keywords or arrive in a direct message channel are followed by
+// an EventNotificationTriggered event. keywords are added to the user's own.
+func (p *Platform) SetNotifications(enabled bool, keywords []string) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	var csKeywords *C.char
+	if len(keywords) > 0 {
+		keywordsJSON, err := json.Marshal(keywords)
+		if err != nil {
+			return err
+		}
+		var freeKeywords func()
+		csKeywords, freeKeywords = cStringFree(string(keywordsJSON))
+		defer freeKeywords()
+	}
+
+	var cEnabled C.int
+	if enabled {
+		cEnabled = 1
+	}
+
+	code := C.communicator_platform_set_notifications(p.handle, cEnabled, csKeywords)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
+// LanguageDetection is a detected language
+type LanguageDetection struct {
+	Language   string  `json:"language"`   // ISO 639-1 code
+	Confidence float64 `json:"confidence"` // Between 0 and 1
+}
+
+// DetectLanguage detects the language of a text
+// Returns nil without an error if the text is too short or ambiguous
+func DetectLanguage(text string) (*LanguageDetection, error) {
+	cs, free := cStringFree(text)
+	defer free()
+
+	cstr := C.communicator_detect_language(cs)
+	if cstr == nil {
+		if C.communicator_last_error_code() != C.COMMUNICATOR_SUCCESS {
+			return nil, getLastError()
+		}
+		return nil, nil
+	}
+	defer freeString(cstr)
+
+	var detection LanguageDetection
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &detection); err != nil {
+		return nil, err
+	}
+
+	return &detection, nil
+}
+
+// SendCodeSnippet posts code as a fenced code block, or uploads it as a file if
+// it is too long for a message. Empty language and filename are omitted.
+func (p *Platform) SendCodeSnippet(channelID, language, code, filename string) (*Message, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	csChannelID, freeChannelID := cStringFree(channelID)
+	defer freeChannelID()
+
+	csCode, freeCode := cStringFree(code)
+	defer freeCode()
+
+	var csLanguage *C.char
+	if language != "" {
+		var freeLanguage func()
+		csLanguage, freeLanguage = cStringFree(language)
+		defer freeLanguage()
+	}
+
+	var csFilename *C.char
+	if filename != "" {
+		var freeFilename func()
+		csFilename, freeFilename = cStringFree(filename)
+		defer freeFilename()
+	}
+
+	cstr := C.communicator_platform_send_code_snippet(p.handle, csChannelID, csLanguage, csCode, csFilename)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var msg Message
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &msg); err != nil {
+		return nil, err
+	}
+
+	return &msg, nil
+}
+
+// QuoteMessage quotes a message, with a comment, in the quoted message's
+// channel. The quoted reference is kept in the "libcommunicator_quote" prop.
+// An empty comment posts the quote alone.
+func (p *Platform) QuoteMessage(targetID, comment string) (*Message, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	csTargetID, freeTargetID := cStringFree(targetID)
+	defer freeTargetID()
+
+	csComment, freeComment := cStringFree(comment)
+	defer freeComment()
+
+	cstr := C.communicator_platform_quote_message(p.handle, csTargetID, csComment)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var msg Message
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &msg); err != nil {
+		return nil, err
+	}
+
+	return &msg, nil
+}
+
+// CreatePoll creates a poll in a channel (on Mattermost this requires the
+// Matterpoll plugin)
+func (p *Platform) CreatePoll(channelID, question string, options []string, settings PollSettings) (*Poll, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	optionsJSON, err := json.Marshal(options)
+	if err != nil {
+		return nil, err
+	}
+
+	settingsJSON, err := json.Marshal(settings)
+	if err != nil {
+		return nil, err
+	}
+
+	csChannelID, freeChannelID := cStringFree(channelID)
+	defer freeChannelID()
+
+	csQuestion, freeQuestion := cStringFree(question)
+	defer freeQuestion()
+
+	csOptions, freeOptions := cStringFree(string(optionsJSON))
+	defer freeOptions()
+
+	csSettings, freeSettings := cStringFree(string(settingsJSON))
+	defer freeSettings()
+
+	cstr := C.communicator_platform_create_poll(p.handle, csChannelID, csQuestion, csOptions, csSettings)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var poll Poll
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &poll); err != nil {
+		return nil, err
+	}
+
+	return &poll, nil
+}
+
+// GetPoll returns the current state of a poll
+func (p *Platform) GetPoll(pollID string) (*Poll, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	csPollID, freePollID := cStringFree(pollID)
+	defer freePollID()
+
+	cstr := C.communicator_platform_get_poll(p.handle, csPollID)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var poll Poll
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &poll); err != nil {
+		return nil, err
+	}
+
+	return &poll, nil
+}
+
+// VotePoll votes for the option at optionIndex in the poll's Options
+func (p *Platform) VotePoll(pollID string, optionIndex uint32) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	csPollID, freePollID := cStringFree(pollID)
+	defer freePollID()
+
+	code := C.communicator_platform_vote_poll(p.handle, csPollID, C.uint32_t(optionIndex))
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
+// ClosePoll ends voting on a poll and returns its final results
+func (p *Platform) ClosePoll(pollID string) (*Poll, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	csPollID, freePollID := cStringFree(pollID)
+	defer freePollID()
+
+	cstr := C.communicator_platform_close_poll(p.handle, csPollID)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var poll Poll
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &poll); err != nil {
+		return nil, err
+	}
+
+	return &poll, nil
+}
+
 // GetChannels returns all channels for the current user
 func (p *Platform) GetChannels() ([]Channel, error) {
 	if p.handle == nil {
@@ -259,6 +794,58 @@ func (p *Platform) GetChannelMembers(channelID string) ([]User, error) {
 	return users, nil
 }
 
+// GetUserAvatar downloads a user's profile image
+// Images are cached by the library, see PrefetchChannelProfiles
+func (p *Platform) GetUserAvatar(userID string) ([]byte, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	csUserID, freeUserID := cStringFree(userID)
+	defer freeUserID()
+
+	var data *C.uint8_t
+	var size C.size_t
+
+	code := C.communicator_platform_get_user_avatar(p.handle, csUserID, &data, &size)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return nil, getLastError()
+	}
+
+	// Copy the data before freeing it
+	goData := C.GoBytes(unsafe.Pointer(data), C.int(size))
+	C.communicator_free_file_data(data, size)
+
+	return goData, nil
+}
+
+// PrefetchChannelProfiles warms the user, avatar and custom status caches for the
+// channels visible in the UI. It returns immediately; each call cancels the
+// previous prefetch. Pass no channels to only cancel.
+func (p *Platform) PrefetchChannelProfiles(channelIDs []string) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	if channelIDs == nil {
+		channelIDs = []string{}
+	}
+	jsonBytes, err := json.Marshal(channelIDs)
+	if err != nil {
+		return err
+	}
+
+	cs, free := cStringFree(string(jsonBytes))
+	defer free()
+
+	code := C.communicator_platform_prefetch_channel_profiles(p.handle, cs)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
 // GetUser returns a specific user by ID
 func (p *Platform) GetUser(userID string) (*User, error) {
 	if p.handle == nil {
@@ -405,6 +992,32 @@ func (p *Platform) UnsubscribeEvents() error {
 	return nil
 }
 
+// SetEventFilter drops real-time events before they are queued; nil delivers
+// every event again
+func (p *Platform) SetEventFilter(filter *EventFilter) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	var csFilter *C.char
+	if filter != nil {
+		filterJSON, err := json.Marshal(filter)
+		if err != nil {
+			return err
+		}
+		var freeFilter func()
+		csFilter, freeFilter = cStringFree(string(filterJSON))
+		defer freeFilter()
+	}
+
+	code := C.communicator_platform_set_event_filter(p.handle, csFilter)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
 // PollEvent polls for the next event
 // Returns nil, nil if no events are available
 func (p *Platform) PollEvent() (*Event, error) {
@@ -430,8 +1043,108 @@ func (p *Platform) PollEvent() (*Event, error) {
 	return &event, nil
 }
 
-// SendReply sends a reply to a message (threaded conversation)
-func (p *Platform) SendReply(channelID, text, rootID string) (*Message, error) {
+// SendMessageEx sends a message with options such as a thread, props,
+// attached files or a priority
+func (p *Platform) SendMessageEx(channelID, text string, options SendOptions) (*Message, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	optionsJSON, err := json.Marshal(options)
+	if err != nil {
+		return nil, err
+	}
+
+	csChannelID, freeChannelID := cStringFree(channelID)
+	defer freeChannelID()
+
+	csText, freeText := cStringFree(text)
+	defer freeText()
+
+	csOptions, freeOptions := cStringFree(string(optionsJSON))
+	defer freeOptions()
+
+	cstr := C.communicator_platform_send_message_ex(p.handle, csChannelID, csText, csOptions)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var msg Message
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &msg); err != nil {
+		return nil, err
+	}
+
+	return &msg, nil
+}
+
+// SendMessageWithAttachments sends a message with files uploaded to the
+// channel with UploadFile attached
+func (p *Platform) SendMessageWithAttachments(channelID, text string, fileIDs []string) (*Message, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	fileIDsJSON, err := json.Marshal(fileIDs)
+	if err != nil {
+		return nil, err
+	}
+
+	csChannelID, freeChannelID := cStringFree(channelID)
+	defer freeChannelID()
+
+	csText, freeText := cStringFree(text)
+	defer freeText()
+
+	csFileIDs, freeFileIDs := cStringFree(string(fileIDsJSON))
+	defer freeFileIDs()
+
+	cstr := C.communicator_platform_send_message_with_attachments(p.handle, csChannelID, csText, csFileIDs)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var msg Message
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &msg); err != nil {
+		return nil, err
+	}
+
+	return &msg, nil
+}
+
+// SendReply sends a reply to a message (threaded conversation)
+func (p *Platform) SendReply(channelID, text, rootID string) (*Message, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	csChannelID, freeChannelID := cStringFree(channelID)
+	defer freeChannelID()
+
+	csText, freeText := cStringFree(text)
+	defer freeText()
+
+	csRootID, freeRootID := cStringFree(rootID)
+	defer freeRootID()
+
+	cstr := C.communicator_platform_send_reply(p.handle, csChannelID, csText, csRootID)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var msg Message
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &msg); err != nil {
+		return nil, err
+	}
+
+	return &msg, nil
+}
+
+// SendMessageAs sends a message with an overridden sender name and/or avatar
+// Pass "" for username or iconURL to keep the sender's own
+func (p *Platform) SendMessageAs(channelID, text, username, iconURL string) (*Message, error) {
 	if p.handle == nil {
 		return nil, ErrInvalidHandle
 	}
@@ -442,10 +1155,21 @@ func (p *Platform) SendReply(channelID, text, rootID string) (*Message, error) {
 	csText, freeText := cStringFree(text)
 	defer freeText()
 
-	csRootID, freeRootID := cStringFree(rootID)
-	defer freeRootID()
+	var csUsername *C.char
+	if username != "" {
+		var freeUsername func()
+		csUsername, freeUsername = cStringFree(username)
+		defer freeUsername()
+	}
 
-	cstr := C.communicator_platform_send_reply(p.handle, csChannelID, csText, csRootID)
+	var csIconURL *C.char
+	if iconURL != "" {
+		var freeIconURL func()
+		csIconURL, freeIconURL = cStringFree(iconURL)
+		defer freeIconURL()
+	}
+
+	cstr := C.communicator_platform_send_message_as(p.handle, csChannelID, csText, csUsername, csIconURL)
 	if cstr == nil {
 		return nil, getLastError()
 	}
@@ -527,6 +1251,12 @@ func (p *Platform) GetMessage(messageID string) (*Message, error) {
 
 // SearchMessages searches for messages
 func (p *Platform) SearchMessages(query string, limit uint32) ([]Message, error) {
+	return p.SearchMessagesContext(context.Background(), query, limit)
+}
+
+// SearchMessagesContext is SearchMessages, returning an ErrorCancelled error
+// once ctx is done
+func (p *Platform) SearchMessagesContext(ctx context.Context, query string, limit uint32) ([]Message, error) {
 	if p.handle == nil {
 		return nil, ErrInvalidHandle
 	}
@@ -534,7 +1264,37 @@ func (p *Platform) SearchMessages(query string, limit uint32) ([]Message, error)
 	cs, free := cStringFree(query)
 	defer free()
 
-	cstr := C.communicator_platform_search_messages(p.handle, cs, C.uint32_t(limit))
+	var cstr *C.char
+	withCancellation(ctx, func(cancellation C.CommunicatorCancellation) {
+		cstr = C.communicator_platform_search_messages_ex(p.handle, cs, C.uint32_t(limit), cancellation)
+	})
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var messages []Message
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &messages); err != nil {
+		return nil, err
+	}
+
+	return messages, nil
+}
+
+// GetMessagesAtDate gets the messages of a channel around a point in time ("jump to date")
+// Messages are returned oldest first
+func (p *Platform) GetMessagesAtDate(channelID string, date time.Time, limit uint32) ([]Message, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	csChannelID, freeChannelID := cStringFree(channelID)
+	defer freeChannelID()
+
+	csDate, freeDate := cStringFree(date.UTC().Format(time.RFC3339))
+	defer freeDate()
+
+	cstr := C.communicator_platform_get_messages_at_date(p.handle, csChannelID, csDate, C.uint32_t(limit))
 	if cstr == nil {
 		return nil, getLastError()
 	}
@@ -600,6 +1360,55 @@ func (p *Platform) GetMessagesAfter(channelID, afterID string, limit uint32) ([]
 	return messages, nil
 }
 
+// GetMessagesPage gets the most recent messages of a channel as the first page
+// of its history. Pass NextCursor to NextPage to fetch older messages.
+func (p *Platform) GetMessagesPage(channelID string, limit uint32) (*MessagePage, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	cs, free := cStringFree(channelID)
+	defer free()
+
+	cstr := C.communicator_platform_get_messages_page(p.handle, cs, C.uint32_t(limit))
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var page MessagePage
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &page); err != nil {
+		return nil, err
+	}
+
+	return &page, nil
+}
+
+// NextPage fetches the page a cursor points to. It works with the cursor of
+// any paginated list (message history, emojis, followed threads and saved
+// messages); Items have the format of the list that issued the cursor.
+func (p *Platform) NextPage(cursor string) (*Page, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	cs, free := cStringFree(cursor)
+	defer free()
+
+	cstr := C.communicator_platform_next_page(p.handle, cs)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var page Page
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &page); err != nil {
+		return nil, err
+	}
+
+	return &page, nil
+}
+
 // AddReaction adds a reaction to a message
 func (p *Platform) AddReaction(messageID, emojiName string) error {
 	if p.handle == nil {
@@ -640,6 +1449,29 @@ func (p *Platform) RemoveReaction(messageID, emojiName string) error {
 	return nil
 }
 
+// GetReactions returns the reactions to a message, oldest first
+func (p *Platform) GetReactions(messageID string) ([]Reaction, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	csMessageID, freeMessageID := cStringFree(messageID)
+	defer freeMessageID()
+
+	cstr := C.communicator_platform_get_reactions(p.handle, csMessageID)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var reactions []Reaction
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &reactions); err != nil {
+		return nil, err
+	}
+
+	return reactions, nil
+}
+
 // PinPost pins a message/post to its channel
 func (p *Platform) PinPost(messageID string) error {
 	if p.handle == nil {
@@ -697,6 +1529,61 @@ func (p *Platform) GetPinnedPosts(channelID string) ([]Message, error) {
 	return messages, nil
 }
 
+// FlagPost saves a message for the current user
+func (p *Platform) FlagPost(messageID string) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	csMessageID, freeMessageID := cStringFree(messageID)
+	defer freeMessageID()
+
+	result := C.communicator_platform_flag_post(p.handle, csMessageID)
+	if result != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
+// UnflagPost removes a message from the current user's saved messages
+func (p *Platform) UnflagPost(messageID string) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	csMessageID, freeMessageID := cStringFree(messageID)
+	defer freeMessageID()
+
+	result := C.communicator_platform_unflag_post(p.handle, csMessageID)
+	if result != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
+// GetFlaggedPosts retrieves a page of the current user's saved messages,
+// most recent first. Pass NextCursor to NextPage to fetch more.
+func (p *Platform) GetFlaggedPosts(page, perPage uint32) (*MessagePage, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	cstr := C.communicator_platform_get_flagged_posts(p.handle, C.uint32_t(page), C.uint32_t(perPage))
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var messages MessagePage
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &messages); err != nil {
+		return nil, err
+	}
+
+	return &messages, nil
+}
+
 // GetEmojis retrieves a list of custom emojis from the platform
 func (p *Platform) GetEmojis(page, perPage uint32) ([]Emoji, error) {
 	if p.handle == nil {
@@ -717,6 +1604,52 @@ func (p *Platform) GetEmojis(page, perPage uint32) ([]Emoji, error) {
 	return emojis, nil
 }
 
+// GetEmojisPage retrieves the first page of custom emojis.
+// Pass NextCursor to NextPage to fetch more.
+func (p *Platform) GetEmojisPage(perPage uint32) (*EmojiPage, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	cstr := C.communicator_platform_get_emojis_page(p.handle, C.uint32_t(perPage))
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var page EmojiPage
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &page); err != nil {
+		return nil, err
+	}
+
+	return &page, nil
+}
+
+// GetEmojiImage downloads the image for a custom emoji by name or ID
+// Images are cached by the library, so repeated lookups are cheap
+func (p *Platform) GetEmojiImage(nameOrID string) ([]byte, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	csNameOrID, freeNameOrID := cStringFree(nameOrID)
+	defer freeNameOrID()
+
+	var data *C.uint8_t
+	var size C.size_t
+
+	code := C.communicator_platform_get_emoji_image(p.handle, csNameOrID, &data, &size)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return nil, getLastError()
+	}
+
+	// Copy the data before freeing it
+	goData := C.GoBytes(unsafe.Pointer(data), C.int(size))
+	C.communicator_free_file_data(data, size)
+
+	return goData, nil
+}
+
 // GetChannelByName gets a channel by name
 func (p *Platform) GetChannelByName(teamID, channelName string) (*Channel, error) {
 	if p.handle == nil {
@@ -812,8 +1745,96 @@ func (p *Platform) RemoveChannelMember(channelID, userID string) error {
 	return nil
 }
 
+// GetChannelMemberRoles returns a member's roles in a channel
+func (p *Platform) GetChannelMemberRoles(channelID, userID string) (*ChannelMemberRoles, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	csChannelID, freeChannelID := cStringFree(channelID)
+	defer freeChannelID()
+
+	csUserID, freeUserID := cStringFree(userID)
+	defer freeUserID()
+
+	cstr := C.communicator_platform_get_channel_member_roles(p.handle, csChannelID, csUserID)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var roles ChannelMemberRoles
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &roles); err != nil {
+		return nil, err
+	}
+
+	return &roles, nil
+}
+
+// SetChannelAdmin makes a channel member a channel admin, or revokes it
+func (p *Platform) SetChannelAdmin(channelID, userID string, isAdmin bool) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	csChannelID, freeChannelID := cStringFree(channelID)
+	defer freeChannelID()
+
+	csUserID, freeUserID := cStringFree(userID)
+	defer freeUserID()
+
+	var adminInt C.int
+	if isAdmin {
+		adminInt = 1
+	}
+
+	code := C.communicator_platform_set_channel_admin(p.handle, csChannelID, csUserID, adminInt)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
+// SetChannelPrivacy converts a channel between public and private
+func (p *Platform) SetChannelPrivacy(channelID string, isPrivate bool) (*Channel, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	csChannelID, freeChannelID := cStringFree(channelID)
+	defer freeChannelID()
+
+	var privateInt C.int
+	if isPrivate {
+		privateInt = 1
+	}
+
+	cstr := C.communicator_platform_set_channel_privacy(p.handle, csChannelID, privateInt)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var channel Channel
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &channel); err != nil {
+		return nil, err
+	}
+
+	return &channel, nil
+}
+
 // ViewChannel marks a channel as viewed (read) by the current user
+// Repeated views of the same channel are debounced, and the previously viewed
+// channel is marked read too.
 func (p *Platform) ViewChannel(channelID string) error {
+	return p.ViewChannelFrom(channelID, "")
+}
+
+// ViewChannelFrom marks a channel as viewed (read) by the current user
+// prevChannelID is the channel being switched away from; pass "" to use the
+// previously viewed channel. Repeated views of the same channel are debounced.
+func (p *Platform) ViewChannelFrom(channelID string, prevChannelID string) error {
 	if p.handle == nil {
 		return ErrInvalidHandle
 	}
@@ -821,7 +1842,14 @@ func (p *Platform) ViewChannel(channelID string) error {
 	cs, free := cStringFree(channelID)
 	defer free()
 
-	code := C.communicator_platform_view_channel(p.handle, cs)
+	var csPrev *C.char
+	var freePrev func()
+	if prevChannelID != "" {
+		csPrev, freePrev = cStringFree(prevChannelID)
+		defer freePrev()
+	}
+
+	code := C.communicator_platform_view_channel_ex(p.handle, cs, csPrev)
 	if code != C.COMMUNICATOR_SUCCESS {
 		return getLastError()
 	}
@@ -852,6 +1880,53 @@ func (p *Platform) GetChannelUnread(channelID string) (*ChannelUnread, error) {
 	return &unread, nil
 }
 
+// GetChannelActivity gets hourly message counts for a channel over the last days
+func (p *Platform) GetChannelActivity(channelID string, days uint32) (*ChannelActivity, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	cs, free := cStringFree(channelID)
+	defer free()
+
+	cstr := C.communicator_platform_get_channel_activity(p.handle, cs, C.uint32_t(days))
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var activity ChannelActivity
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &activity); err != nil {
+		return nil, err
+	}
+
+	return &activity, nil
+}
+
+// GetWindow gets the most recent messages of a channel, oldest first, from its
+// in-memory window. The window follows new messages while events are polled.
+func (p *Platform) GetWindow(channelID string) ([]Message, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	cs, free := cStringFree(channelID)
+	defer free()
+
+	cstr := C.communicator_platform_get_window(p.handle, cs)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var messages []Message
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &messages); err != nil {
+		return nil, err
+	}
+
+	return messages, nil
+}
+
 // GetTeamUnreads gets unread counts for all channels in a specific team
 func (p *Platform) GetTeamUnreads(teamID string) ([]ChannelUnread, error) {
 	if p.handle == nil {
@@ -896,6 +1971,44 @@ func (p *Platform) GetAllUnreads() ([]TeamUnread, error) {
 	return unreads, nil
 }
 
+// GetChannelUnreads gets unread counts for all channels in the current team
+func (p *Platform) GetChannelUnreads() ([]ChannelUnread, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	cstr := C.communicator_platform_get_channel_unreads(p.handle)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var unreads []ChannelUnread
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &unreads); err != nil {
+		return nil, err
+	}
+
+	return unreads, nil
+}
+
+// MarkChannelRead marks all messages in a channel as read without switching
+// to it
+func (p *Platform) MarkChannelRead(channelID string) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	csChannelID, freeChannelID := cStringFree(channelID)
+	defer freeChannelID()
+
+	code := C.communicator_platform_mark_channel_read(p.handle, csChannelID)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
 // GetUnreadPosts gets the actual unread messages in a channel
 // limitAfter: maximum number of posts to retrieve after last read (newer posts)
 // limitBefore: maximum number of posts to retrieve before last read (context)
@@ -1142,6 +2255,30 @@ func (p *Platform) GetUsersStatus(userIDs []string) (map[string]string, error) {
 	return statusMap, nil
 }
 
+// GetTypingUsers gets the IDs of the users currently typing in a channel
+// Typing state is cleared when the event connection is re-established
+func (p *Platform) GetTypingUsers(channelID string) ([]string, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	cs, free := cStringFree(channelID)
+	defer free()
+
+	cstr := C.communicator_platform_get_typing_users(p.handle, cs)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var userIDs []string
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &userIDs); err != nil {
+		return nil, err
+	}
+
+	return userIDs, nil
+}
+
 // GetTeams gets all teams the user belongs to
 func (p *Platform) GetTeams() ([]Team, error) {
 	if p.handle == nil {
@@ -1232,6 +2369,140 @@ func (p *Platform) SetTeamID(teamID string) error {
 	return nil
 }
 
+// SetCircuitBreaker configures the concurrent request limit and the circuit breaker.
+// Zero fields keep their defaults. While the circuit is open, requests fail with
+// ErrorUnavailable; state changes are delivered as EventCircuitStateChanged events.
+func (p *Platform) SetCircuitBreaker(config CircuitBreakerConfig) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	configJSON, err := json.Marshal(config)
+	if err != nil {
+		return err
+	}
+
+	cs, free := cStringFree(string(configJSON))
+	defer free()
+
+	code := C.communicator_platform_set_circuit_breaker(p.handle, cs)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
+// GetCircuitState returns the state of the circuit breaker
+func (p *Platform) GetCircuitState() (CircuitState, error) {
+	if p.handle == nil {
+		return "", ErrInvalidHandle
+	}
+
+	cStr := C.communicator_platform_get_circuit_state(p.handle)
+	if cStr == nil {
+		return "", getLastError()
+	}
+	defer freeString(cStr)
+
+	var result struct {
+		State CircuitState `json:"state"`
+	}
+	if err := json.Unmarshal([]byte(C.GoString(cStr)), &result); err != nil {
+		return "", err
+	}
+
+	return result.State, nil
+}
+
+// GetConnectionHealth returns the health of the real-time connection: its
+// state, time since the server was last heard from, reconnection attempts and
+// backoff, and the rate of received events.
+func (p *Platform) GetConnectionHealth() (*ConnectionHealth, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	cStr := C.communicator_platform_get_connection_health(p.handle)
+	if cStr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cStr)
+
+	var health ConnectionHealth
+	if err := json.Unmarshal([]byte(C.GoString(cStr)), &health); err != nil {
+		return nil, err
+	}
+
+	return &health, nil
+}
+
+// GetRequestStats returns statistics of requests queued for the server's rate
+// limit. Requests exceeding the limit wait for it to reset instead of failing,
+// and requests refused with 429 are retried with backoff.
+func (p *Platform) GetRequestStats() (*RequestStats, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	cStr := C.communicator_platform_get_request_stats(p.handle)
+	if cStr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cStr)
+
+	var stats RequestStats
+	if err := json.Unmarshal([]byte(C.GoString(cStr)), &stats); err != nil {
+		return nil, err
+	}
+
+	return &stats, nil
+}
+
+// ConfigureCache sets the TTLs and size limit of the caches of users, channels,
+// teams and emojis. New TTLs apply to entries cached from now on; disabling
+// caching clears the caches.
+func (p *Platform) ConfigureCache(config CacheConfig) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	configJSON, err := json.Marshal(config)
+	if err != nil {
+		return err
+	}
+
+	cs, free := cStringFree(string(configJSON))
+	defer free()
+
+	code := C.communicator_platform_configure_cache(p.handle, cs)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
+// GetCacheStats returns the usage statistics of the platform's caches
+func (p *Platform) GetCacheStats() ([]CacheStats, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	cStr := C.communicator_platform_get_cache_stats(p.handle)
+	if cStr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cStr)
+
+	var stats []CacheStats
+	if err := json.Unmarshal([]byte(C.GoString(cStr)), &stats); err != nil {
+		return nil, err
+	}
+
+	return stats, nil
+}
+
 // ==============================================================================
 // Thread Operations
 // ==============================================================================
@@ -1396,6 +2667,35 @@ func (p *Platform) GetUserThread(userID, teamID, threadID string) (string, error
 	return C.GoString(result), nil
 }
 
+// GetFollowedThreads retrieves a page of the threads the current user
+// follows in a team, or in the current team if teamID is empty.
+// Pass NextCursor to NextPage to fetch more.
+func (p *Platform) GetFollowedThreads(teamID string, page, perPage uint32) (*ThreadPage, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	var csTeamID *C.char
+	if teamID != "" {
+		cs, freeTeamID := cStringFree(teamID)
+		defer freeTeamID()
+		csTeamID = cs
+	}
+
+	cstr := C.communicator_platform_get_followed_threads(p.handle, csTeamID, C.uint32_t(page), C.uint32_t(perPage))
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var threads ThreadPage
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &threads); err != nil {
+		return nil, err
+	}
+
+	return &threads, nil
+}
+
 // MarkAllThreadsRead marks all threads as read for a user in a team
 func (p *Platform) MarkAllThreadsRead(userID, teamID string) error {
 	if p.handle == nil {
@@ -1511,6 +2811,167 @@ func (p *Platform) DeleteChannel(channelID string) error {
 	return nil
 }
 
+// RegisterOAuthApp registers an OAuth 2.0 app. The returned app's ClientSecret
+// is only available here.
+func (p *Platform) RegisterOAuthApp(settings OAuthAppSettings) (*OAuthApp, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	settingsJSON, err := json.Marshal(settings)
+	if err != nil {
+		return nil, err
+	}
+
+	cs, free := cStringFree(string(settingsJSON))
+	defer free()
+
+	cstr := C.communicator_platform_register_oauth_app(p.handle, cs)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var app OAuthApp
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &app); err != nil {
+		return nil, err
+	}
+
+	return &app, nil
+}
+
+// GetOAuthApps gets the OAuth 2.0 apps visible to the current user
+func (p *Platform) GetOAuthApps() ([]OAuthApp, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	cstr := C.communicator_platform_get_oauth_apps(p.handle)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var apps []OAuthApp
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &apps); err != nil {
+		return nil, err
+	}
+
+	return apps, nil
+}
+
+// DeleteOAuthApp deletes an OAuth 2.0 app
+func (p *Platform) DeleteOAuthApp(appID string) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	cs, free := cStringFree(appID)
+	defer free()
+
+	result := C.communicator_platform_delete_oauth_app(p.handle, cs)
+	if result != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
+// CreateAccessToken creates a personal access token for a user. The returned
+// token's Token value is only available here.
+func (p *Platform) CreateAccessToken(userID, description string) (*AccessToken, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	csUserID, freeUserID := cStringFree(userID)
+	defer freeUserID()
+
+	csDescription, freeDescription := cStringFree(description)
+	defer freeDescription()
+
+	cstr := C.communicator_platform_create_access_token(p.handle, csUserID, csDescription)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var token AccessToken
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &token); err != nil {
+		return nil, err
+	}
+
+	return &token, nil
+}
+
+// GetAccessTokens gets a user's personal access tokens, without their values
+func (p *Platform) GetAccessTokens(userID string) ([]AccessToken, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	cs, free := cStringFree(userID)
+	defer free()
+
+	cstr := C.communicator_platform_get_access_tokens(p.handle, cs)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var tokens []AccessToken
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &tokens); err != nil {
+		return nil, err
+	}
+
+	return tokens, nil
+}
+
+// RevokeAccessToken revokes a personal access token by its ID (not its value)
+func (p *Platform) RevokeAccessToken(tokenID string) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	cs, free := cStringFree(tokenID)
+	defer free()
+
+	result := C.communicator_platform_revoke_access_token(p.handle, cs)
+	if result != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
+// LastError returns the last error of a failed call on this platform, from
+// any goroutine. It is not cleared by calls that succeed; use ClearError.
+func (p *Platform) LastError() error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	var msg *C.char
+	code := C.communicator_platform_last_error(p.handle, &msg)
+	if code == C.COMMUNICATOR_SUCCESS {
+		return nil
+	}
+	if msg == nil {
+		codeStr := C.communicator_error_code_string(code)
+		return fmt.Errorf("libcommunicator error %d: %s", code, C.GoString(codeStr))
+	}
+
+	defer freeString(msg)
+	return fmt.Errorf("libcommunicator error %d: %s", code, C.GoString(msg))
+}
+
+// ClearError clears the last error of this platform
+func (p *Platform) ClearError() {
+	if p.handle != nil {
+		C.communicator_platform_clear_error(p.handle)
+	}
+}
+
 // Destroy destroys the platform and frees its resources
 func (p *Platform) Destroy() {
 	if p.handle != nil {
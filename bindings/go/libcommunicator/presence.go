@@ -0,0 +1,83 @@
+package libcommunicator
+
+/*
+#include <communicator.h>
+#include <stdlib.h>
+*/
+import "C"
+import (
+	"encoding/json"
+	"unsafe"
+)
+
+// Well-known activities
+const (
+	ActivityInMeeting  = "in_meeting"
+	ActivityPresenting = "presenting"
+	ActivityOnCall     = "on_call"
+)
+
+// PresenceRule maps an activity to a status
+type PresenceRule struct {
+	Activity string `json:"activity"`
+	Status   string `json:"status"`
+	Message  string `json:"message,omitempty"`
+}
+
+// PresenceConfig configures activity-driven presence
+// Rules are ordered by precedence (first match wins)
+type PresenceConfig struct {
+	Rules      []PresenceRule `json:"rules"`
+	IdleStatus string         `json:"idle_status,omitempty"`
+}
+
+// ConfigurePresence sets how reported activities map to the user's status
+// Pass nil to use the default rules
+func (p *Platform) ConfigurePresence(config *PresenceConfig) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	var csConfig *C.char
+	if config != nil {
+		configJSON, err := json.Marshal(config)
+		if err != nil {
+			return err
+		}
+		csConfig = C.CString(string(configJSON))
+		defer C.free(unsafe.Pointer(csConfig))
+	}
+
+	code := C.communicator_platform_configure_presence(p.handle, csConfig)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
+// ReportActivity reports that an activity started (active=true) or ended
+// The platform status is updated if the resulting status changed
+func (p *Platform) ReportActivity(source, activity string, active bool) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	csSource, freeSource := cStringFree(source)
+	defer freeSource()
+
+	csActivity, freeActivity := cStringFree(activity)
+	defer freeActivity()
+
+	var cActive C.int
+	if active {
+		cActive = 1
+	}
+
+	code := C.communicator_platform_report_activity(p.handle, csSource, csActivity, cActive)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
@@ -0,0 +1,63 @@
+package libcommunicator
+
+/*
+#include <communicator.h>
+#include <stdlib.h>
+*/
+import "C"
+import "encoding/json"
+
+// ProfileUpdate holds changes to the current user's profile.
+// Nil fields are not changed.
+type ProfileUpdate struct {
+	FirstName *string `json:"first_name,omitempty"`
+	LastName  *string `json:"last_name,omitempty"`
+	Nickname  *string `json:"nickname,omitempty"`
+	Username  *string `json:"username,omitempty"` // Where the platform allows changing it
+	Position  *string `json:"position,omitempty"`
+}
+
+// UpdateProfile changes fields of the current user's profile
+func (p *Platform) UpdateProfile(update ProfileUpdate) (*User, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	updateJSON, err := json.Marshal(update)
+	if err != nil {
+		return nil, err
+	}
+
+	cs, free := cStringFree(string(updateJSON))
+	defer free()
+
+	cstr := C.communicator_platform_update_profile(p.handle, cs)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var user User
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &user); err != nil {
+		return nil, err
+	}
+
+	return &user, nil
+}
+
+// SetProfileImage uploads a new profile image for the current user
+func (p *Platform) SetProfileImage(image []byte) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	cImage := C.CBytes(image)
+	defer C.free(cImage)
+
+	code := C.communicator_platform_set_profile_image(p.handle, (*C.uint8_t)(cImage), C.size_t(len(image)))
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
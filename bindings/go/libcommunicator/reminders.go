@@ -0,0 +1,122 @@
+package libcommunicator
+
+/*
+#include <communicator.h>
+#include <stdlib.h>
+*/
+import "C"
+import (
+	"encoding/json"
+	"time"
+)
+
+// Reminder represents a reminder about a message or channel
+type Reminder struct {
+	ID         string    `json:"id"`
+	ChannelID  string    `json:"channel_id"`
+	MessageID  string    `json:"message_id,omitempty"`
+	Note       string    `json:"note,omitempty"`
+	DueAt      time.Time `json:"due_at"`
+	CreatedAt  time.Time `json:"created_at"`
+	ServerSide bool      `json:"server_side"` // Delivered by the server instead of PollEvent
+}
+
+// SetReminderStore persists reminders to a JSON file, loading any reminders
+// already stored there
+func (p *Platform) SetReminderStore(path string) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	csPath, freePath := cStringFree(path)
+	defer freePath()
+
+	code := C.communicator_platform_set_reminder_store(p.handle, csPath)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
+// AddReminder schedules a reminder about a channel or, if messageID is not
+// empty, a message. Due reminders are delivered as EventReminderDue events.
+// With serverSide, reminders about messages are delivered by the server where
+// supported.
+func (p *Platform) AddReminder(channelID, messageID, note string, dueAt time.Time, serverSide bool) (*Reminder, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	csChannelID, freeChannelID := cStringFree(channelID)
+	defer freeChannelID()
+
+	var csMessageID *C.char
+	if messageID != "" {
+		var freeMessageID func()
+		csMessageID, freeMessageID = cStringFree(messageID)
+		defer freeMessageID()
+	}
+
+	var csNote *C.char
+	if note != "" {
+		var freeNote func()
+		csNote, freeNote = cStringFree(note)
+		defer freeNote()
+	}
+
+	var cServerSide C.int
+	if serverSide {
+		cServerSide = 1
+	}
+
+	cstr := C.communicator_platform_add_reminder(p.handle, csChannelID, csMessageID, csNote, C.int64_t(dueAt.UnixMilli()), cServerSide)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var reminder Reminder
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &reminder); err != nil {
+		return nil, err
+	}
+
+	return &reminder, nil
+}
+
+// ListReminders returns the pending local reminders, earliest first
+func (p *Platform) ListReminders() ([]Reminder, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	cstr := C.communicator_platform_list_reminders(p.handle)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var reminders []Reminder
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &reminders); err != nil {
+		return nil, err
+	}
+
+	return reminders, nil
+}
+
+// CancelReminder cancels a pending local reminder
+func (p *Platform) CancelReminder(reminderID string) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	csReminderID, freeReminderID := cStringFree(reminderID)
+	defer freeReminderID()
+
+	code := C.communicator_platform_cancel_reminder(p.handle, csReminderID)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
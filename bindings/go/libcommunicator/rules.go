@@ -0,0 +1,156 @@
+package libcommunicator
+
+/*
+#include <communicator.h>
+#include <stdlib.h>
+*/
+import "C"
+import (
+	"encoding/json"
+	"time"
+)
+
+// Rule action types
+const (
+	RuleActionSendMessage = "send_message"
+	RuleActionAddReaction = "add_reaction"
+	RuleActionCallback    = "callback"
+)
+
+// RuleTrigger filters the events a rule fires on
+type RuleTrigger struct {
+	Event      string `json:"event,omitempty"`
+	Channel    string `json:"channel,omitempty"`
+	MentionsMe bool   `json:"mentions_me,omitempty"`
+	Contains   string `json:"contains,omitempty"`
+	FromUser   string `json:"from_user,omitempty"`
+	Language   string `json:"language,omitempty"` // ISO 639-1, see SetLanguageDetection
+	IncludeOwn bool   `json:"include_own,omitempty"`
+}
+
+// RuleAction is performed when a rule fires
+type RuleAction struct {
+	Type      string `json:"type"`
+	ChannelID string `json:"channel_id,omitempty"`
+	Text      string `json:"text,omitempty"`
+	Emoji     string `json:"emoji,omitempty"`
+}
+
+// Rule is an automation rule
+type Rule struct {
+	Name    string       `json:"name"`
+	Trigger RuleTrigger  `json:"trigger"`
+	Actions []RuleAction `json:"actions"`
+}
+
+// SetRules installs automation rules, evaluated against every polled event
+// Callback actions are not delivered to Go; use event webhooks or the event
+// stream to react to events in Go code instead.
+func (p *Platform) SetRules(rules []Rule) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	rulesJSON, err := json.Marshal(rules)
+	if err != nil {
+		return err
+	}
+
+	cs, free := cStringFree(string(rulesJSON))
+	defer free()
+
+	code := C.communicator_platform_set_rules(p.handle, cs, nil, nil)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
+// ClearRules removes all automation rules
+func (p *Platform) ClearRules() error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	code := C.communicator_platform_clear_rules(p.handle)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
+// Script hook stages
+const (
+	ScriptStageInbound  = "inbound"
+	ScriptStageOutbound = "outbound"
+)
+
+// AddScriptHook adds an external script to the message pipeline
+// The script reads a message as JSON on stdin and writes the transformed
+// message JSON (or null to drop it) to stdout.
+// A zero timeout uses the default (500ms); a zero memory limit means no limit.
+// Requires the library to be built with the scripting feature.
+func (p *Platform) AddScriptHook(stage string, command []string, timeout time.Duration, memoryLimitMB uint64) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	commandJSON, err := json.Marshal(command)
+	if err != nil {
+		return err
+	}
+
+	csStage, freeStage := cStringFree(stage)
+	defer freeStage()
+
+	csCommand, freeCommand := cStringFree(string(commandJSON))
+	defer freeCommand()
+
+	code := C.communicator_platform_add_script_hook(p.handle, csStage, csCommand,
+		C.uint64_t(timeout.Milliseconds()), C.uint64_t(memoryLimitMB))
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
+// ClearScriptHooks removes all script hooks
+func (p *Platform) ClearScriptHooks() error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	code := C.communicator_platform_clear_script_hooks(p.handle)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
+// GetOriginalText returns the text of a message before inbound script hooks
+// changed it, along with the changes that were made
+func (p *Platform) GetOriginalText(messageID string) (*OriginalText, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	csMessageID, freeMessageID := cStringFree(messageID)
+	defer freeMessageID()
+
+	cstr := C.communicator_platform_get_original_text(p.handle, csMessageID)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var original OriginalText
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &original); err != nil {
+		return nil, err
+	}
+
+	return &original, nil
+}
@@ -0,0 +1,89 @@
+package libcommunicator
+
+/*
+#include <communicator.h>
+#include <stdlib.h>
+*/
+import "C"
+import (
+	"encoding/json"
+	"time"
+)
+
+// ScheduledMessage represents a message to be sent at a later time
+type ScheduledMessage struct {
+	ID          string    `json:"id"`
+	ChannelID   string    `json:"channel_id"`
+	RootID      string    `json:"root_id,omitempty"`
+	Message     string    `json:"message"`
+	ScheduledAt time.Time `json:"scheduled_at"`
+	CreatedAt   time.Time `json:"created_at"`
+	ServerSide  bool      `json:"server_side"`     // Sent by the server instead of the library
+	Error       string    `json:"error,omitempty"` // Why sending failed, once it has
+}
+
+// ScheduleMessage schedules a message to be sent at sendAt. Without
+// server-side support, the library sends it while the application polls
+// events.
+func (p *Platform) ScheduleMessage(channelID, text string, sendAt time.Time) (*ScheduledMessage, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	csChannelID, freeChannelID := cStringFree(channelID)
+	defer freeChannelID()
+
+	csText, freeText := cStringFree(text)
+	defer freeText()
+
+	cstr := C.communicator_platform_schedule_message(p.handle, csChannelID, csText, C.int64_t(sendAt.UnixMilli()))
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var scheduled ScheduledMessage
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &scheduled); err != nil {
+		return nil, err
+	}
+
+	return &scheduled, nil
+}
+
+// GetScheduledMessages returns the scheduled messages not sent yet, earliest
+// first
+func (p *Platform) GetScheduledMessages() ([]ScheduledMessage, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	cstr := C.communicator_platform_get_scheduled_messages(p.handle)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var scheduled []ScheduledMessage
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &scheduled); err != nil {
+		return nil, err
+	}
+
+	return scheduled, nil
+}
+
+// CancelScheduledMessage cancels a scheduled message before it is sent
+func (p *Platform) CancelScheduledMessage(scheduledID string) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	csScheduledID, freeScheduledID := cStringFree(scheduledID)
+	defer freeScheduledID()
+
+	code := C.communicator_platform_cancel_scheduled_message(p.handle, csScheduledID)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
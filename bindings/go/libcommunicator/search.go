@@ -104,7 +104,8 @@ func (p *Platform) AutocompleteUsers(name, teamID, channelID string, limit uint3
 	return users, nil
 }
 
-// SearchChannels searches for channels in a team
+// SearchChannels searches for channels in a team, or in the current team
+// if teamID is empty
 func (p *Platform) SearchChannels(teamID, term string) ([]Channel, error) {
 	if p.handle == nil {
 		return nil, ErrInvalidHandle
@@ -130,7 +131,8 @@ func (p *Platform) SearchChannels(teamID, term string) ([]Channel, error) {
 	return channels, nil
 }
 
-// AutocompleteChannels autocompletes channels for references
+// AutocompleteChannels autocompletes channels for references in a team,
+// or in the current team if teamID is empty
 func (p *Platform) AutocompleteChannels(teamID, name string) ([]Channel, error) {
 	if p.handle == nil {
 		return nil, ErrInvalidHandle
@@ -205,3 +207,67 @@ func (p *Platform) SearchPostsAdvanced(options *PostSearchOptions) (string, erro
 
 	return C.GoString(result), nil
 }
+
+// SearchQuery is a platform-independent message search query
+// Dates use the YYYY-MM-DD format
+type SearchQuery struct {
+	Terms         []string `json:"terms,omitempty"`
+	Phrases       []string `json:"phrases,omitempty"`
+	ExcludedTerms []string `json:"excluded_terms,omitempty"`
+	FromUsers     []string `json:"from_users,omitempty"`
+	ToUsers       []string `json:"to_users,omitempty"`
+	InChannels    []string `json:"in_channels,omitempty"`
+	Before        string   `json:"before,omitempty"`
+	After         string   `json:"after,omitempty"`
+	On            string   `json:"on,omitempty"`
+	HasAttachment bool     `json:"has_attachment,omitempty"`
+}
+
+// ParseSearchQuery parses a search string in the common search mini-language
+// (words, "phrases", -excluded, from:, to:, in:, before:, after:, on:, has:attachment)
+func ParseSearchQuery(text string) (*SearchQuery, error) {
+	cs, free := cStringFree(text)
+	defer free()
+
+	cstr := C.communicator_search_query_parse(cs)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var query SearchQuery
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &query); err != nil {
+		return nil, err
+	}
+
+	return &query, nil
+}
+
+// SearchMessagesQuery searches for messages with a structured query
+// The query is translated into the platform's native search syntax
+func (p *Platform) SearchMessagesQuery(query *SearchQuery, limit uint32) ([]Message, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	queryJSON, err := json.Marshal(query)
+	if err != nil {
+		return nil, err
+	}
+
+	cs, free := cStringFree(string(queryJSON))
+	defer free()
+
+	cstr := C.communicator_platform_search_messages_query(p.handle, cs, C.uint32_t(limit))
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var messages []Message
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &messages); err != nil {
+		return nil, err
+	}
+
+	return messages, nil
+}
@@ -0,0 +1,69 @@
+package libcommunicator
+
+/*
+#include <communicator.h>
+#include <stdlib.h>
+*/
+import "C"
+import (
+	"encoding/json"
+)
+
+// SidebarCategory is a group of channels in the sidebar
+type SidebarCategory struct {
+	ID          string   `json:"id"`
+	TeamID      string   `json:"team_id"`
+	DisplayName string   `json:"display_name"`
+	Type        string   `json:"type"`        // "favorites", "channels", "direct_messages" or "custom"
+	Sorting     string   `json:"sorting"`     // "default", "alphabetical", "recent" or "manual"
+	ChannelIDs  []string `json:"channel_ids"` // In display order
+	Collapsed   bool     `json:"collapsed"`
+	Muted       bool     `json:"muted"`
+}
+
+// GetSidebarCategories gets the current user's sidebar categories in the
+// current team, in display order
+func (p *Platform) GetSidebarCategories() ([]SidebarCategory, error) {
+	if p.handle == nil {
+		return nil, ErrInvalidHandle
+	}
+
+	cstr := C.communicator_platform_get_sidebar_categories(p.handle)
+	if cstr == nil {
+		return nil, getLastError()
+	}
+	defer freeString(cstr)
+
+	var categories []SidebarCategory
+	if err := json.Unmarshal([]byte(C.GoString(cstr)), &categories); err != nil {
+		return nil, err
+	}
+
+	return categories, nil
+}
+
+// MoveChannelToCategory moves a channel into a sidebar category at a
+// position, or at its end for a negative index. A position sorts the
+// category manually.
+func (p *Platform) MoveChannelToCategory(channelID, categoryID string, index int) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	csChannelID, freeChannelID := cStringFree(channelID)
+	defer freeChannelID()
+
+	csCategoryID, freeCategoryID := cStringFree(categoryID)
+	defer freeCategoryID()
+
+	if index < 0 {
+		index = -1
+	}
+
+	code := C.communicator_platform_move_channel_to_category(p.handle, csChannelID, csCategoryID, C.int32_t(index))
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
@@ -0,0 +1,110 @@
+package libcommunicator
+
+/*
+#include <communicator.h>
+#include <stdlib.h>
+*/
+import "C"
+import (
+	"encoding/json"
+	"time"
+)
+
+// Simulation functions need a library built with the `simulation` feature.
+// They make scenario tests deterministic: simulated servers answer in memory
+// and the clock only moves with SimulationAdvance.
+
+// EnableSimulation pauses the clock; requires InitHostDriven
+func EnableSimulation() error {
+	if C.communicator_simulation_enable() != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+	return nil
+}
+
+// SimulationAdvance moves the paused clock, running everything due meanwhile
+// in order (e.g. each reconnect attempt of a backoff sequence)
+func SimulationAdvance(d time.Duration) error {
+	if C.communicator_simulation_advance(C.uint64_t(d.Milliseconds())) != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+	return nil
+}
+
+// AddSimulatedServer simulates a Mattermost server for a host name; platforms
+// connecting to http://<host> then talk to it. user is a Mattermost user
+// object returned by logins; nil uses a default one.
+func AddSimulatedServer(host string, user map[string]interface{}) error {
+	cHost, freeHost := cStringFree(host)
+	defer freeHost()
+
+	var cUser *C.char
+	if user != nil {
+		userJSON, err := json.Marshal(user)
+		if err != nil {
+			return err
+		}
+		var freeUser func()
+		cUser, freeUser = cStringFree(string(userJSON))
+		defer freeUser()
+	}
+
+	if C.communicator_simulation_add_server(cHost, cUser) != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+	return nil
+}
+
+// SetSimulatedRoute answers requests to an API path (without /api/v4) of a
+// simulated server with a fixed response
+func SetSimulatedRoute(host, method, path string, status uint16, body string) error {
+	cHost, freeHost := cStringFree(host)
+	defer freeHost()
+	cMethod, freeMethod := cStringFree(method)
+	defer freeMethod()
+	cPath, freePath := cStringFree(path)
+	defer freePath()
+	cBody, freeBody := cStringFree(body)
+	defer freeBody()
+
+	if C.communicator_simulation_set_route(cHost, cMethod, cPath, C.uint16_t(status), cBody) != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+	return nil
+}
+
+// PushSimulatedEvent sends a Mattermost WebSocket event from a simulated
+// server, or queues it until a client connects
+func PushSimulatedEvent(host string, event map[string]interface{}) error {
+	eventJSON, err := json.Marshal(event)
+	if err != nil {
+		return err
+	}
+
+	cHost, freeHost := cStringFree(host)
+	defer freeHost()
+	cEvent, freeEvent := cStringFree(string(eventJSON))
+	defer freeEvent()
+
+	if C.communicator_simulation_push_event(cHost, cEvent) != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+	return nil
+}
+
+// SetSimulatedReachable takes a simulated server off the network (dropping
+// its WebSocket connection) or brings it back
+func SetSimulatedReachable(host string, reachable bool) error {
+	cHost, freeHost := cStringFree(host)
+	defer freeHost()
+
+	var cReachable C.int
+	if reachable {
+		cReachable = 1
+	}
+
+	if C.communicator_simulation_set_reachable(cHost, cReachable) != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+	return nil
+}
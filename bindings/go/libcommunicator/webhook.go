@@ -0,0 +1,55 @@
+package libcommunicator
+
+/*
+#include <communicator.h>
+#include <stdlib.h>
+*/
+import "C"
+import (
+	"encoding/json"
+	"unsafe"
+)
+
+// AddEventWebhook forwards polled events to a local HTTP endpoint
+// The URL must be http(s) on a loopback address (localhost, 127.0.0.1, ::1).
+// If eventTypes is empty, all events are forwarded.
+// Delivery happens in the background and is best-effort.
+func (p *Platform) AddEventWebhook(url string, eventTypes []string) error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	csURL, freeURL := cStringFree(url)
+	defer freeURL()
+
+	var csTypes *C.char
+	if len(eventTypes) > 0 {
+		typesJSON, err := json.Marshal(eventTypes)
+		if err != nil {
+			return err
+		}
+		csTypes = C.CString(string(typesJSON))
+		defer C.free(unsafe.Pointer(csTypes))
+	}
+
+	code := C.communicator_platform_add_event_webhook(p.handle, csURL, csTypes)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
+
+// ClearEventWebhooks removes all event webhooks registered for the platform
+func (p *Platform) ClearEventWebhooks() error {
+	if p.handle == nil {
+		return ErrInvalidHandle
+	}
+
+	code := C.communicator_platform_clear_event_webhooks(p.handle)
+	if code != C.COMMUNICATOR_SUCCESS {
+		return getLastError()
+	}
+
+	return nil
+}
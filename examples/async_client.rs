@@ -0,0 +1,56 @@
+//! Using the library from async Rust
+//!
+//! Connects to a Mattermost server with a personal access token, lists the
+//! user's channels and prints messages as they are posted. The C API isn't
+//! needed, so it can be left out:
+//!
+//! ```text
+//! MM_SERVER=https://chat.example.com MM_TOKEN=... \
+//!     cargo run --example async_client --no-default-features
+//! ```
+
+use std::time::Duration;
+
+use communicator::platforms::mattermost::MattermostPlatform;
+use communicator::{Platform, PlatformConfig, PlatformEvent};
+
+#[tokio::main]
+async fn main() -> communicator::Result<()> {
+    let (Ok(server), Ok(token)) = (std::env::var("MM_SERVER"), std::env::var("MM_TOKEN")) else {
+        eprintln!("Set MM_SERVER and MM_TOKEN");
+        std::process::exit(1);
+    };
+
+    let mut platform = MattermostPlatform::new(&server)?;
+    let config = PlatformConfig::new(&server).with_credential("token", token);
+    let info = platform.connect(config).await?;
+    println!("Connected to {} as {}", info.server, info.user_id);
+
+    for channel in platform.get_channels().await? {
+        println!("#{} ({})", channel.display_name, channel.id);
+    }
+
+    platform.subscribe_events().await?;
+    // Signalled whenever events are queued, so there's no need to poll on a timer
+    let signal = platform.event_signal();
+    loop {
+        while let Some(event) = platform.poll_event().await? {
+            match event {
+                PlatformEvent::MessagePosted(message) => {
+                    println!(
+                        "[{}] {}: {}",
+                        message.channel_id, message.sender_id, message.text
+                    )
+                }
+                PlatformEvent::ConnectionStateChanged { state, reason, .. } => {
+                    println!("Connection {state:?} {}", reason.unwrap_or_default())
+                }
+                _ => {}
+            }
+        }
+        match &signal {
+            Some(signal) => signal.notified().await,
+            None => tokio::time::sleep(Duration::from_millis(250)).await,
+        }
+    }
+}
@@ -0,0 +1,37 @@
+//! Runs libcommunicator as a daemon serving its API on a Unix domain socket
+//!
+//! Usage: communicator-daemon <socket-path> <server-url>
+//!
+//! Frontends connect to the socket and, if the daemon is not connected yet,
+//! send a `connect` request with their credentials. See the `daemon` module
+//! for the protocol.
+
+use std::process::ExitCode;
+
+use communicator::daemon::Daemon;
+use communicator::platforms::mattermost::MattermostPlatform;
+use communicator::runtime;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().collect();
+    let (socket_path, server_url) = match args.as_slice() {
+        [_, socket_path, server_url] => (socket_path, server_url),
+        _ => {
+            eprintln!("Usage: communicator-daemon <socket-path> <server-url>");
+            return ExitCode::from(2);
+        }
+    };
+
+    let result = runtime::init_runtime().and_then(|()| {
+        let platform = MattermostPlatform::new(server_url)?;
+        runtime::block_on(Daemon::new(Box::new(platform)).serve(socket_path))
+    });
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("communicator-daemon: {}", e.message);
+            ExitCode::FAILURE
+        }
+    }
+}
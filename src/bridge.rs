@@ -0,0 +1,290 @@
+//! Bridging helpers for relaying messages between platforms
+//!
+//! When the library is used to bridge two platforms (or two servers of the same
+//! platform), every relayed message is tagged with origin metadata stored in the
+//! message props. The bridge uses these tags to avoid relaying a message back to
+//! where it came from (echo suppression) and to stop messages that have already
+//! passed through too many bridges (loop detection).
+//!
+//! Platforms expose props through `Message::metadata["props"]`; the origin tag is
+//! stored under [`BRIDGE_ORIGIN_PROP`].
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::platforms::Platform;
+use crate::types::Message;
+
+/// Message prop key holding the bridge origin tag
+pub const BRIDGE_ORIGIN_PROP: &str = "communicator_bridge_origin";
+
+/// Origin metadata attached to bridged messages
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BridgeOrigin {
+    /// ID of the bridge that relayed the message
+    pub bridge_id: String,
+    /// Name of the platform the message was originally posted on
+    pub platform: String,
+    /// Channel ID on the originating platform
+    pub channel_id: String,
+    /// Message ID on the originating platform
+    pub message_id: String,
+    /// Number of bridges the message has passed through
+    pub hops: u32,
+}
+
+impl BridgeOrigin {
+    /// Extract the origin tag from a message, if it was relayed by a bridge
+    pub fn from_message(message: &Message) -> Option<Self> {
+        let tag = message
+            .metadata
+            .as_ref()?
+            .get("props")?
+            .get(BRIDGE_ORIGIN_PROP)?;
+        serde_json::from_value(tag.clone()).ok()
+    }
+}
+
+/// Configuration for a bridge
+#[derive(Debug, Clone)]
+pub struct BridgeConfig {
+    /// Unique identifier for this bridge (used to recognize its own messages)
+    pub bridge_id: String,
+    /// Maximum number of bridges a message may pass through (default: 1)
+    pub max_hops: u32,
+    /// How long IDs of relayed messages are remembered for echo suppression (default: 10 minutes)
+    pub echo_window: Duration,
+    /// Maximum number of relayed message IDs remembered (default: 1000)
+    pub max_tracked_messages: usize,
+}
+
+impl BridgeConfig {
+    /// Create a configuration with default limits
+    pub fn new(bridge_id: impl Into<String>) -> Self {
+        Self {
+            bridge_id: bridge_id.into(),
+            max_hops: 1,
+            echo_window: Duration::from_secs(600),
+            max_tracked_messages: 1000,
+        }
+    }
+
+    /// Set the maximum number of hops
+    pub fn with_max_hops(mut self, max_hops: u32) -> Self {
+        self.max_hops = max_hops;
+        self
+    }
+
+    /// Set the echo suppression window
+    pub fn with_echo_window(mut self, echo_window: Duration) -> Self {
+        self.echo_window = echo_window;
+        self
+    }
+}
+
+/// Whether a message should be relayed by a bridge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForwardDecision {
+    /// The message should be relayed
+    Forward,
+    /// The message was relayed by this bridge (echo) and must not be relayed back
+    Echo,
+    /// The message already passed through the maximum number of bridges
+    LoopDetected,
+}
+
+/// Relays messages between platforms with echo suppression and loop detection
+pub struct Bridge {
+    config: BridgeConfig,
+    /// IDs of messages this bridge created, with the time they were sent
+    sent: Mutex<VecDeque<(String, Instant)>>,
+}
+
+impl Bridge {
+    /// Create a new bridge
+    pub fn new(config: BridgeConfig) -> Self {
+        Self {
+            config,
+            sent: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Get the bridge configuration
+    pub fn config(&self) -> &BridgeConfig {
+        &self.config
+    }
+
+    /// Decide whether a message should be relayed
+    ///
+    /// # Arguments
+    /// * `message` - A message received from one side of the bridge
+    ///
+    /// # Returns
+    /// The forwarding decision
+    pub fn check(&self, message: &Message) -> ForwardDecision {
+        if self.was_sent_by_bridge(&message.id) {
+            return ForwardDecision::Echo;
+        }
+
+        match BridgeOrigin::from_message(message) {
+            Some(origin) if origin.bridge_id == self.config.bridge_id => ForwardDecision::Echo,
+            Some(origin) if origin.hops >= self.config.max_hops => ForwardDecision::LoopDetected,
+            _ => ForwardDecision::Forward,
+        }
+    }
+
+    /// Build the origin tag for relaying a message
+    ///
+    /// If the message was already relayed by another bridge, the original
+    /// origin is preserved and the hop count incremented.
+    ///
+    /// # Arguments
+    /// * `message` - The message being relayed
+    /// * `source_platform` - Name of the platform the message was received from
+    pub fn origin_for(&self, message: &Message, source_platform: &str) -> BridgeOrigin {
+        match BridgeOrigin::from_message(message) {
+            Some(origin) => BridgeOrigin {
+                bridge_id: self.config.bridge_id.clone(),
+                hops: origin.hops + 1,
+                ..origin
+            },
+            None => BridgeOrigin {
+                bridge_id: self.config.bridge_id.clone(),
+                platform: source_platform.to_string(),
+                channel_id: message.channel_id.clone(),
+                message_id: message.id.clone(),
+                hops: 1,
+            },
+        }
+    }
+
+    /// Remember a message created by this bridge so its echo is suppressed
+    ///
+    /// This covers platforms that drop custom props, where the origin tag
+    /// would not survive the round trip.
+    pub fn record_sent(&self, message_id: &str) {
+        let mut sent = self.sent.lock().unwrap();
+        let now = Instant::now();
+
+        while let Some((_, at)) = sent.front() {
+            if now.duration_since(*at) > self.config.echo_window
+                || sent.len() >= self.config.max_tracked_messages
+            {
+                sent.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        sent.push_back((message_id.to_string(), now));
+    }
+
+    /// Check whether a message ID was created by this bridge recently
+    fn was_sent_by_bridge(&self, message_id: &str) -> bool {
+        let sent = self.sent.lock().unwrap();
+        let now = Instant::now();
+        sent.iter()
+            .any(|(id, at)| id == message_id && now.duration_since(*at) <= self.config.echo_window)
+    }
+
+    /// Relay a message to another platform if it passes echo and loop checks
+    ///
+    /// # Arguments
+    /// * `message` - The message received from the source platform
+    /// * `source` - The platform the message was received from
+    /// * `target` - The platform to relay the message to
+    /// * `target_channel_id` - The channel on the target platform
+    ///
+    /// # Returns
+    /// The forwarding decision and, if relayed, the created message
+    pub async fn forward(
+        &self,
+        message: &Message,
+        source: &dyn Platform,
+        target: &dyn Platform,
+        target_channel_id: &str,
+    ) -> Result<(ForwardDecision, Option<Message>)> {
+        let decision = self.check(message);
+        if decision != ForwardDecision::Forward {
+            return Ok((decision, None));
+        }
+
+        let origin = self.origin_for(message, &source.capabilities().platform_name);
+        let mut props = HashMap::new();
+        props.insert(
+            BRIDGE_ORIGIN_PROP.to_string(),
+            serde_json::to_value(&origin).unwrap_or_default(),
+        );
+
+        let sent = target
+            .send_message_with_props(target_channel_id, &message.text, props)
+            .await?;
+        self.record_sent(&sent.id);
+
+        Ok((decision, Some(sent)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tagged_message(id: &str, origin: &BridgeOrigin) -> Message {
+        Message::new(id, "hello", "user-1", "ch-1").with_metadata(serde_json::json!({
+            "props": { BRIDGE_ORIGIN_PROP: origin }
+        }))
+    }
+
+    #[test]
+    fn test_untagged_message_is_forwarded() {
+        let bridge = Bridge::new(BridgeConfig::new("bridge-a"));
+        let message = Message::new("msg-1", "hello", "user-1", "ch-1");
+        assert_eq!(bridge.check(&message), ForwardDecision::Forward);
+
+        let origin = bridge.origin_for(&message, "mattermost");
+        assert_eq!(origin.bridge_id, "bridge-a");
+        assert_eq!(origin.platform, "mattermost");
+        assert_eq!(origin.message_id, "msg-1");
+        assert_eq!(origin.hops, 1);
+    }
+
+    #[test]
+    fn test_own_messages_are_echoes() {
+        let bridge = Bridge::new(BridgeConfig::new("bridge-a").with_max_hops(5));
+        let origin = bridge.origin_for(&Message::new("msg-1", "hi", "u", "c"), "mattermost");
+        let relayed = tagged_message("msg-2", &origin);
+        assert_eq!(bridge.check(&relayed), ForwardDecision::Echo);
+
+        // Props stripped by the platform: recognized by message ID instead
+        bridge.record_sent("msg-3");
+        let stripped = Message::new("msg-3", "hello", "user-1", "ch-1");
+        assert_eq!(bridge.check(&stripped), ForwardDecision::Echo);
+    }
+
+    #[test]
+    fn test_loop_detection() {
+        let bridge = Bridge::new(BridgeConfig::new("bridge-b").with_max_hops(2));
+        let mut origin = BridgeOrigin {
+            bridge_id: "bridge-a".to_string(),
+            platform: "mattermost".to_string(),
+            channel_id: "ch-0".to_string(),
+            message_id: "msg-0".to_string(),
+            hops: 1,
+        };
+
+        let message = tagged_message("msg-1", &origin);
+        assert_eq!(bridge.check(&message), ForwardDecision::Forward);
+        let next = bridge.origin_for(&message, "mattermost");
+        assert_eq!(next.hops, 2);
+        assert_eq!(next.message_id, "msg-0");
+
+        origin.hops = 2;
+        let message = tagged_message("msg-1", &origin);
+        assert_eq!(bridge.check(&message), ForwardDecision::LoopDetected);
+    }
+}
@@ -0,0 +1,111 @@
+//! Cancellation of long-running calls
+//!
+//! FFI calls block until their operation completes. A caller that wants to
+//! abort one, e.g. when the user presses Cancel in a GUI, creates a
+//! cancellation handle, passes it to the `_ex` variant of the operation
+//! (connecting, searching, downloading a file) and cancels it from another
+//! thread. The call then returns `ErrorCode::Cancelled` right away; requests
+//! still in flight are dropped.
+//!
+//! A cancelled handle stays cancelled, so later calls passed the same handle
+//! fail immediately. A handle may be shared by several calls to cancel them
+//! all at once.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tokio::sync::Notify;
+
+use crate::error::{Error, ErrorCode, Result};
+
+/// A request to abort the calls it was passed to
+#[derive(Debug, Default)]
+pub struct Cancellation {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+impl Cancellation {
+    /// Create a cancellation that is not cancelled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel the calls running with this cancellation, and the ones to come
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.notify.notify_waiters();
+    }
+
+    /// Whether the cancellation was cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Wait until the cancellation is cancelled
+    pub async fn cancelled(&self) {
+        loop {
+            // Registered before checking, so a cancel in between isn't missed
+            let notified = self.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// Run an operation unless it is cancelled first
+    ///
+    /// # Returns
+    /// The operation's result, or an `ErrorCode::Cancelled` error if the
+    /// cancellation was cancelled before the operation completed
+    pub async fn run<T, F>(&self, operation: F) -> Result<T>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        tokio::select! {
+            biased;
+            _ = self.cancelled() => Err(Error::new(ErrorCode::Cancelled, "Operation cancelled")),
+            result = operation => result,
+        }
+    }
+}
+
+/// Run an operation, cancellable if a cancellation is given
+pub async fn run<T, F>(cancellation: Option<&Cancellation>, operation: F) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+{
+    match cancellation {
+        Some(cancellation) => cancellation.run(operation).await,
+        None => operation.await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cancel_running_operation() {
+        let cancellation = Arc::new(Cancellation::new());
+        let canceller = Arc::clone(&cancellation);
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            canceller.cancel();
+        });
+
+        let slow = async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Ok(())
+        };
+        let error = cancellation.run(slow).await.unwrap_err();
+        assert_eq!(error.code, ErrorCode::Cancelled);
+
+        // Later calls fail right away, while uncancellable ones still run
+        assert!(cancellation.run(async { Ok(1) }).await.is_err());
+        assert_eq!(run(None, async { Ok(1) }).await.unwrap(), 1);
+    }
+}
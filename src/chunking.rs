@@ -0,0 +1,426 @@
+//! Splitting long messages into numbered parts
+//!
+//! Messages longer than the platform's length limit can be split into parts
+//! that are sent one after another. Splits prefer paragraph and line breaks
+//! outside code blocks; when a code block has to be split, it is closed at the
+//! end of one part and reopened (with the same fence) at the start of the next,
+//! so every part renders correctly on its own.
+//!
+//! Each part carries a [`CHUNK_PROP`] property describing its group, and a
+//! visible `(i/n)` footer. Receivers using this library reassemble the original
+//! message with [`ChunkReassembler`].
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::error::{Error, Result};
+use crate::platforms::Platform;
+use crate::types::Message;
+
+/// Message property holding the [`ChunkInfo`] of a part
+pub const CHUNK_PROP: &str = "libcommunicator_chunk";
+
+/// Smallest length limit messages can be split for
+pub const MIN_CHUNK_LENGTH: usize = 64;
+
+/// Characters reserved for the `(i/n)` footer
+const FOOTER_RESERVE: usize = "\n\n(9999/9999)".len();
+
+/// Characters reserved for closing a code block at the end of a part
+const FENCE_CLOSE_RESERVE: usize = "\n```".len();
+
+/// Maximum number of incomplete groups kept by a reassembler
+const MAX_PENDING_GROUPS: usize = 64;
+
+/// Describes one part of a split message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkInfo {
+    /// Identifies the parts of one message
+    pub group_id: String,
+    /// Position of this part (1-based)
+    pub part: usize,
+    /// Total number of parts
+    pub total: usize,
+    /// Characters added before the original content (a reopened code fence)
+    pub prefix_len: usize,
+    /// Length of the original content in this part, in characters
+    pub content_len: usize,
+}
+
+impl ChunkInfo {
+    /// Read the chunk info of a received message, if it is a part
+    pub fn from_message(message: &Message) -> Option<Self> {
+        let value = message.metadata.as_ref()?.get("props")?.get(CHUNK_PROP)?;
+        serde_json::from_value(value.clone()).ok()
+    }
+
+    /// Extract the original content from the text of a part
+    fn content<'a>(&self, text: &'a str) -> &'a str {
+        let mut indices = text.char_indices().map(|(i, _)| i).chain([text.len()]);
+        let start = indices.nth(self.prefix_len).unwrap_or(text.len());
+        let end = indices
+            .nth(self.content_len.saturating_sub(1))
+            .filter(|_| self.content_len > 0)
+            .unwrap_or(start);
+        &text[start..end.max(start)]
+    }
+}
+
+/// One part of a split message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageChunk {
+    /// The text to send
+    pub text: String,
+    /// Group information (None if the message did not need splitting)
+    pub info: Option<ChunkInfo>,
+}
+
+/// Split a message into parts of at most `max_len` characters
+///
+/// # Arguments
+/// * `text` - The message text
+/// * `max_len` - The maximum length of a part, in characters
+///
+/// # Returns
+/// The parts in sending order. A message that fits is returned unchanged as a
+/// single part without chunk info.
+pub fn split_message(text: &str, max_len: usize) -> Result<Vec<MessageChunk>> {
+    let chars: Vec<char> = text.chars().collect();
+    if chars.len() <= max_len {
+        return Ok(vec![MessageChunk {
+            text: text.to_string(),
+            info: None,
+        }]);
+    }
+    if max_len < MIN_CHUNK_LENGTH {
+        return Err(Error::invalid_argument(format!(
+            "Cannot split messages into parts shorter than {MIN_CHUNK_LENGTH} characters"
+        )));
+    }
+
+    let fences = open_fences(&chars);
+
+    // Find the slice boundaries of the original text
+    let mut bounds = Vec::new();
+    let mut start = 0;
+    while start < chars.len() {
+        let prefix = fences[start].as_ref().map(|f| f.chars().count() + 1);
+        let budget = max_len
+            .saturating_sub(FOOTER_RESERVE + FENCE_CLOSE_RESERVE + prefix.unwrap_or(0))
+            .max(1);
+        let end = if chars.len() - start <= budget {
+            chars.len()
+        } else {
+            split_point(&chars, &fences, start, start + budget)
+        };
+        bounds.push((start, end));
+        start = end;
+    }
+
+    let group_id = new_group_id();
+    let total = bounds.len();
+    Ok(bounds
+        .into_iter()
+        .enumerate()
+        .map(|(i, (start, end))| {
+            let content: String = chars[start..end].iter().collect();
+            let mut text = String::new();
+            let mut prefix_len = 0;
+
+            if let Some(fence) = &fences[start] {
+                text.push_str(fence);
+                text.push('\n');
+                prefix_len = fence.chars().count() + 1;
+            }
+            text.push_str(&content);
+            if fences[end].is_some() {
+                if !content.ends_with('\n') {
+                    text.push('\n');
+                }
+                text.push_str("```");
+            }
+            text.push_str(&format!("\n\n({}/{})", i + 1, total));
+
+            MessageChunk {
+                text,
+                info: Some(ChunkInfo {
+                    group_id: group_id.clone(),
+                    part: i + 1,
+                    total,
+                    prefix_len,
+                    content_len: end - start,
+                }),
+            }
+        })
+        .collect())
+}
+
+/// For every boundary between characters, the fence line of the code block it is in
+fn open_fences(chars: &[char]) -> Vec<Option<String>> {
+    let mut fences = Vec::with_capacity(chars.len() + 1);
+    let mut open: Option<String> = None;
+    fences.push(None);
+
+    let mut line_start = 0;
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '\n' || i + 1 == chars.len() {
+            let line: String = chars[line_start..=i].iter().collect();
+            let trimmed = line.trim();
+            // Boundaries inside the line keep the state from before it
+            fences.extend(std::iter::repeat_n(open.clone(), i - line_start));
+            if trimmed.starts_with("```") {
+                open = match open {
+                    Some(_) => None,
+                    None => Some(trimmed.to_string()),
+                };
+            }
+            fences.push(open.clone());
+            line_start = i + 1;
+        }
+    }
+
+    fences
+}
+
+/// Choose where to end a part that starts at `start` and may end at `limit`
+///
+/// Prefers, in order: a paragraph break outside code, a line break outside
+/// code, a line break inside code, whitespace outside code. Only breaks in the
+/// second half of the window are considered, to avoid tiny parts.
+fn split_point(chars: &[char], fences: &[Option<String>], start: usize, limit: usize) -> usize {
+    let min = start + (limit - start) / 2;
+    let after =
+        |pred: &dyn Fn(usize) -> bool| (min.max(start + 1)..=limit).rev().find(|&p| pred(p));
+
+    let outside = |p: usize| fences[p].is_none();
+    let after_newline = |p: usize| chars[p - 1] == '\n';
+
+    after(&|p| outside(p) && after_newline(p) && p >= 2 && chars[p - 2] == '\n')
+        .or_else(|| after(&|p| outside(p) && after_newline(p)))
+        .or_else(|| after(&after_newline))
+        .or_else(|| after(&|p| outside(p) && chars[p - 1].is_whitespace()))
+        .unwrap_or(limit)
+}
+
+/// Generate an identifier for a group of parts
+fn new_group_id() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0);
+    format!(
+        "{:x}{:x}{:x}",
+        nanos,
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+/// Send a message, splitting it into parts if it exceeds the length limit
+///
+/// # Arguments
+/// * `platform` - The platform to send through
+/// * `channel_id` - The channel to send the message to
+/// * `text` - The message text
+/// * `max_len` - Maximum part length; defaults to the platform's
+///   `max_message_length` limit
+///
+/// # Returns
+/// The sent messages in order. Messages that fit are sent unchanged as one
+/// message; parts are sent with [`Platform::send_message_with_props`].
+pub async fn send_chunked(
+    platform: &dyn Platform,
+    channel_id: &str,
+    text: &str,
+    max_len: Option<usize>,
+) -> Result<Vec<Message>> {
+    let max_len = match max_len.or(platform.capabilities().limits.max_message_length) {
+        Some(max_len) => max_len,
+        None => return Ok(vec![platform.send_message(channel_id, text).await?]),
+    };
+
+    let mut sent = Vec::new();
+    for chunk in split_message(text, max_len)? {
+        let message = match chunk.info {
+            None => platform.send_message(channel_id, &chunk.text).await?,
+            Some(info) => {
+                let mut props = HashMap::new();
+                props.insert(
+                    CHUNK_PROP.to_string(),
+                    serde_json::to_value(&info).unwrap_or_default(),
+                );
+                platform
+                    .send_message_with_props(channel_id, &chunk.text, props)
+                    .await?
+            }
+        };
+        sent.push(message);
+    }
+    Ok(sent)
+}
+
+/// Reassembles split messages from their parts
+#[derive(Debug, Default)]
+pub struct ChunkReassembler {
+    /// Received parts per group, in arrival order of the groups
+    pending: Vec<(String, Vec<Option<Message>>)>,
+}
+
+impl ChunkReassembler {
+    /// Create an empty reassembler
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a received message
+    ///
+    /// # Returns
+    /// The message itself if it is not a part, the reassembled message once
+    /// all parts of its group have arrived, or None while parts are missing.
+    /// The reassembled message has the ID and metadata of the first part.
+    pub fn add(&mut self, message: Message) -> Option<Message> {
+        let info = match ChunkInfo::from_message(&message) {
+            Some(info) if info.part >= 1 && info.part <= info.total => info,
+            _ => return Some(message),
+        };
+
+        let index = match self.pending.iter().position(|(id, _)| *id == info.group_id) {
+            Some(index) => index,
+            None => {
+                if self.pending.len() >= MAX_PENDING_GROUPS {
+                    self.pending.remove(0);
+                }
+                self.pending
+                    .push((info.group_id.clone(), vec![None; info.total]));
+                self.pending.len() - 1
+            }
+        };
+
+        let parts = &mut self.pending[index].1;
+        if let Some(slot) = parts.get_mut(info.part - 1) {
+            *slot = Some(message);
+        }
+        if parts.iter().any(|part| part.is_none()) {
+            return None;
+        }
+
+        let (_, parts) = self.pending.remove(index);
+        let mut parts = parts.into_iter().flatten();
+        let mut combined = parts.next()?;
+        let mut text = content_of(&combined);
+        for part in parts {
+            text.push_str(&content_of(&part));
+        }
+        combined.text = text;
+        Some(combined)
+    }
+
+    /// Number of groups still waiting for parts
+    pub fn pending_groups(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+fn content_of(message: &Message) -> String {
+    ChunkInfo::from_message(message)
+        .map(|info| info.content(&message.text).to_string())
+        .unwrap_or_else(|| message.text.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Turn chunks into received messages, as the server would return them
+    fn received(chunks: &[MessageChunk]) -> Vec<Message> {
+        chunks
+            .iter()
+            .enumerate()
+            .map(|(i, chunk)| {
+                let props = serde_json::json!({ CHUNK_PROP: chunk.info });
+                Message::new(format!("msg-{i}"), &chunk.text, "user-1", "ch-1")
+                    .with_metadata(serde_json::json!({ "props": props }))
+            })
+            .collect()
+    }
+
+    fn long_text() -> String {
+        let mut text = String::new();
+        for i in 0..20 {
+            text.push_str(&format!("Paragraph {i} has some words in it.\n\n"));
+        }
+        text.push_str("```rust\n");
+        for i in 0..30 {
+            text.push_str(&format!("let value_{i} = compute({i});\n"));
+        }
+        text.push_str("```\nDone.");
+        text
+    }
+
+    #[test]
+    fn test_short_message_is_not_split() {
+        let chunks = split_message("hello", 100).unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].text, "hello");
+        assert!(chunks[0].info.is_none());
+    }
+
+    #[test]
+    fn test_parts_fit_and_keep_code_blocks_balanced() {
+        let text = long_text();
+        let chunks = split_message(&text, 200).unwrap();
+        assert!(chunks.len() > 2);
+
+        for (i, chunk) in chunks.iter().enumerate() {
+            assert!(chunk.text.chars().count() <= 200);
+            assert_eq!(chunk.text.matches("```").count() % 2, 0);
+            assert!(chunk
+                .text
+                .ends_with(&format!("({}/{})", i + 1, chunks.len())));
+        }
+    }
+
+    #[test]
+    fn test_reassembly_restores_original() {
+        let text = long_text();
+        let chunks = split_message(&text, 200).unwrap();
+        let mut messages = received(&chunks);
+        messages.reverse();
+
+        let mut reassembler = ChunkReassembler::new();
+        let last = messages.pop().unwrap();
+        for message in messages {
+            assert!(reassembler.add(message).is_none());
+        }
+        assert_eq!(reassembler.pending_groups(), 1);
+
+        let combined = reassembler.add(last).unwrap();
+        assert_eq!(combined.text, text);
+        assert_eq!(reassembler.pending_groups(), 0);
+    }
+
+    #[test]
+    fn test_unsplittable_text_is_hard_cut() {
+        let text = "x".repeat(500);
+        let chunks = split_message(&text, 100).unwrap();
+        assert!(chunks.iter().all(|c| c.text.chars().count() <= 100));
+
+        let mut reassembler = ChunkReassembler::new();
+        let combined = received(&chunks)
+            .into_iter()
+            .filter_map(|m| reassembler.add(m))
+            .next()
+            .unwrap();
+        assert_eq!(combined.text, text);
+    }
+
+    #[test]
+    fn test_regular_messages_pass_through() {
+        let mut reassembler = ChunkReassembler::new();
+        let message = Message::new("msg-1", "hi", "user-1", "ch-1");
+        assert_eq!(reassembler.add(message).unwrap().text, "hi");
+        assert!(split_message(&"x".repeat(100), 10).is_err());
+    }
+}
@@ -0,0 +1,159 @@
+//! Coalescing of presence and typing events
+//!
+//! On large teams, status changes and typing indicators can make up most of
+//! the event stream. With coalescing enabled for a platform, these
+//! events are held back and delivered as a summary once per interval: the
+//! latest status of each user whose status changed, and each user still typing
+//! in a channel, once. A held typing indicator is dropped when its user's
+//! message arrives, since the user is no longer typing.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::platforms::PlatformEvent;
+use crate::types::user::UserStatus;
+
+/// Holds back the presence and typing events of one platform
+#[derive(Debug)]
+pub struct Coalescer {
+    interval: Duration,
+    /// When the first of the held events arrived
+    held_since: Option<Instant>,
+    /// Latest status of each user, in order of their first change
+    statuses: Vec<(String, UserStatus)>,
+    /// Users typing, as (channel ID, user ID), in order of arrival
+    typing: Vec<(String, String)>,
+    /// Summarized events waiting to be delivered
+    ready: VecDeque<PlatformEvent>,
+}
+
+impl Coalescer {
+    /// Create a coalescer
+    ///
+    /// # Arguments
+    /// * `interval` - How long events are held before their summary is delivered
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            held_since: None,
+            statuses: Vec::new(),
+            typing: Vec::new(),
+            ready: VecDeque::new(),
+        }
+    }
+
+    /// Hold back an event if it is coalesced
+    ///
+    /// # Returns
+    /// The event if it is to be delivered now, None if it was held back
+    pub fn hold(&mut self, event: PlatformEvent, now: Instant) -> Option<PlatformEvent> {
+        match event {
+            PlatformEvent::UserStatusChanged { user_id, status } => {
+                match self.statuses.iter_mut().find(|(id, _)| *id == user_id) {
+                    Some(held) => held.1 = status,
+                    None => self.statuses.push((user_id, status)),
+                }
+            }
+            PlatformEvent::UserTyping {
+                user_id,
+                channel_id,
+            } => {
+                let key = (channel_id, user_id);
+                if !self.typing.contains(&key) {
+                    self.typing.push(key);
+                }
+            }
+            PlatformEvent::MessagePosted(ref message)
+            | PlatformEvent::MessageConfirmed { ref message, .. } => {
+                self.typing.retain(|(channel_id, user_id)| {
+                    *channel_id != message.channel_id || *user_id != message.sender_id
+                });
+                return Some(event);
+            }
+            event => return Some(event),
+        }
+        self.held_since.get_or_insert(now);
+        None
+    }
+
+    /// Take the next summarized event, once the interval has passed since the
+    /// first held event
+    pub fn pop_due(&mut self, now: Instant) -> Option<PlatformEvent> {
+        if self.ready.is_empty() {
+            let held_since = self.held_since?;
+            if now.duration_since(held_since) < self.interval {
+                return None;
+            }
+            self.held_since = None;
+            self.ready.extend(
+                self.statuses
+                    .drain(..)
+                    .map(|(user_id, status)| PlatformEvent::UserStatusChanged { user_id, status }),
+            );
+            self.ready
+                .extend(self.typing.drain(..).map(|(channel_id, user_id)| {
+                    PlatformEvent::UserTyping {
+                        user_id,
+                        channel_id,
+                    }
+                }));
+        }
+        self.ready.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Message;
+
+    fn status(user_id: &str, status: UserStatus) -> PlatformEvent {
+        PlatformEvent::UserStatusChanged {
+            user_id: user_id.to_string(),
+            status,
+        }
+    }
+
+    fn typing(user_id: &str) -> PlatformEvent {
+        PlatformEvent::UserTyping {
+            user_id: user_id.to_string(),
+            channel_id: "c1".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_coalesces_until_interval() {
+        let start = Instant::now();
+        let interval = Duration::from_millis(100);
+        let mut coalescer = Coalescer::new(interval);
+
+        assert!(coalescer
+            .hold(status("u1", UserStatus::Away), start)
+            .is_none());
+        assert!(coalescer.hold(typing("u2"), start).is_none());
+        assert!(coalescer.hold(typing("u3"), start).is_none());
+        assert!(coalescer
+            .hold(status("u1", UserStatus::Online), start)
+            .is_none());
+        assert!(coalescer.hold(typing("u2"), start).is_none());
+        // u3's message ends their typing
+        let posted = PlatformEvent::MessagePosted(Message::new("m1", "hi", "u3", "c1"));
+        assert!(coalescer.hold(posted, start).is_some());
+        assert!(coalescer.pop_due(start + interval / 2).is_none());
+
+        let mut summary = Vec::new();
+        while let Some(event) = coalescer.pop_due(start + interval) {
+            summary.push(event);
+        }
+        assert_eq!(summary.len(), 2);
+        assert!(matches!(
+            &summary[0],
+            PlatformEvent::UserStatusChanged { user_id, status: UserStatus::Online } if user_id == "u1"
+        ));
+        assert!(matches!(
+            &summary[1],
+            PlatformEvent::UserTyping { user_id, .. } if user_id == "u2"
+        ));
+        assert!(coalescer.pop_due(start + interval * 10).is_none());
+    }
+}
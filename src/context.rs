@@ -5,12 +5,18 @@
 //! then converted back when needed.
 
 use crate::error::{Error, ErrorCode, Result};
+use crate::telemetry::LogRecord;
+use lazy_static::lazy_static;
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::ffi::CString;
 use std::os::raw::c_void;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::{Condvar, Mutex};
 
 /// Log levels for callbacks
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum LogLevel {
     Debug = 0,
     Info = 1,
@@ -18,10 +24,170 @@ pub enum LogLevel {
     Error = 3,
 }
 
+impl LogLevel {
+    /// Convert a level passed over FFI
+    pub fn from_raw(level: i32) -> Option<Self> {
+        match level {
+            0 => Some(LogLevel::Debug),
+            1 => Some(LogLevel::Info),
+            2 => Some(LogLevel::Warning),
+            3 => Some(LogLevel::Error),
+            _ => None,
+        }
+    }
+}
+
 /// Callback function type for logging
-/// Parameters: level, message, user_data
+/// Parameters: level, message (plain text, or a JSON log record for structured
+/// callbacks, see `telemetry`), user_data
 pub type LogCallback = extern "C" fn(LogLevel, *const std::os::raw::c_char, *mut c_void);
 
+/// Form in which a log callback receives messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// The message as text, followed by the fields of tracing events
+    Text,
+    /// A JSON log record (see `telemetry`)
+    Json,
+}
+
+/// Next key of a context in `LOG_CALLBACKS`
+static NEXT_LOG_KEY: AtomicU64 = AtomicU64::new(1);
+
+/// Least severe level any log callback wants, or `u8::MAX` if there are none
+static MIN_LOG_LEVEL: AtomicU8 = AtomicU8::new(u8::MAX);
+
+/// The log callback of a context
+#[derive(Clone, Copy)]
+struct Listener {
+    callback: LogCallback,
+    /// User data as an address
+    user_data: usize,
+    /// Least severe level passed to the callback
+    level: LogLevel,
+    format: LogFormat,
+}
+
+/// Log callbacks of all contexts, and the calls of them in progress
+#[derive(Default)]
+struct Listeners {
+    by_key: HashMap<u64, Listener>,
+    /// Number of calls in progress, by context key
+    in_flight: HashMap<u64, usize>,
+}
+
+lazy_static! {
+    static ref LOG_CALLBACKS: Mutex<Listeners> = Mutex::new(Listeners::default());
+}
+
+/// Signalled whenever a call of a log callback returns
+static CALLBACK_RETURNED: Condvar = Condvar::new();
+
+thread_local! {
+    /// Keys of the log callbacks being called on this thread
+    static RUNNING: RefCell<Vec<u64>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Whether any log callback wants messages of a level
+pub(crate) fn wants(level: LogLevel) -> bool {
+    level as u8 >= MIN_LOG_LEVEL.load(Ordering::Relaxed)
+}
+
+/// Update `MIN_LOG_LEVEL` after the log callbacks changed
+fn update_min_level(listeners: &HashMap<u64, Listener>) {
+    let min = listeners
+        .values()
+        .map(|listener| listener.level as u8)
+        .min()
+        .unwrap_or(u8::MAX);
+    if MIN_LOG_LEVEL.swap(min, Ordering::Relaxed) != min {
+        crate::telemetry::levels_changed();
+    }
+}
+
+/// Pass a log record to the log callbacks of all contexts that want its level
+///
+/// The callbacks may be called from any thread. They are called without
+/// holding the lock, so they may log themselves; removing a callback waits
+/// for its calls in progress instead (see `remove_listener`).
+pub(crate) fn log_all(level: LogLevel, record: &LogRecord<'_>) {
+    let mut text = None;
+    let mut json = None;
+    let listeners: Vec<(u64, Listener)> = {
+        let mut callbacks = LOG_CALLBACKS.lock().unwrap();
+        let listeners: Vec<_> = callbacks
+            .by_key
+            .iter()
+            .filter(|(_, listener)| level >= listener.level)
+            .map(|(key, listener)| (*key, *listener))
+            .collect();
+        for (key, _) in &listeners {
+            *callbacks.in_flight.entry(*key).or_default() += 1;
+        }
+        listeners
+    };
+    for (key, listener) in listeners {
+        // Formatted once for all callbacks wanting the same form
+        let c_string = match listener.format {
+            LogFormat::Text => text.get_or_insert_with(|| CString::new(record.to_text())),
+            LogFormat::Json => json.get_or_insert_with(|| CString::new(record.to_json())),
+        };
+        if let Ok(c_string) = c_string {
+            RUNNING.with(|running| running.borrow_mut().push(key));
+            (listener.callback)(level, c_string.as_ptr(), listener.user_data as *mut c_void);
+            RUNNING.with(|running| running.borrow_mut().pop());
+        }
+
+        let mut callbacks = LOG_CALLBACKS.lock().unwrap();
+        if let Some(count) = callbacks.in_flight.get_mut(&key) {
+            *count -= 1;
+            if *count == 0 {
+                callbacks.in_flight.remove(&key);
+            }
+        }
+        CALLBACK_RETURNED.notify_all();
+    }
+}
+
+/// Remove the log callback of a context
+///
+/// Its calls in progress may still use its user data, see [`InFlight`].
+fn remove_listener(key: u64) -> InFlight {
+    let mut callbacks = LOG_CALLBACKS.lock().unwrap();
+    callbacks.by_key.remove(&key);
+    update_min_level(&callbacks.by_key);
+    InFlight { key }
+}
+
+/// Calls of a removed or replaced log callback that may still be in progress
+/// on other threads
+///
+/// Contexts behind C handles are locked while their methods run, so the FFI
+/// waits for the calls after unlocking the context: a call in progress may
+/// itself be waiting for the context.
+#[must_use = "the callback's user data may still be in use until wait() returns"]
+pub struct InFlight {
+    key: u64,
+}
+
+impl InFlight {
+    /// Wait for the calls, so the caller may free the user data afterwards
+    ///
+    /// Calls on this thread (a callback removing its own callback) can't return
+    /// before this does, so they are not waited for.
+    pub fn wait(self) {
+        let callbacks = LOG_CALLBACKS.lock().unwrap();
+        let key = self.key;
+        let own_calls =
+            RUNNING.with(|running| running.borrow().iter().filter(|k| **k == key).count());
+        let _callbacks = CALLBACK_RETURNED
+            .wait_while(callbacks, |callbacks| {
+                callbacks.in_flight.get(&key).copied().unwrap_or(0) > own_calls
+            })
+            .unwrap();
+    }
+}
+
 /// A communication context that manages connections to platforms
 ///
 /// This is a Rust struct that will be exposed as an opaque handle through FFI
@@ -34,8 +200,15 @@ pub struct Context {
     initialized: bool,
     /// Optional log callback
     log_callback: Option<LogCallback>,
-    /// User data passed to callbacks
-    user_data: *mut c_void,
+    /// Form in which the log callback receives messages
+    log_format: LogFormat,
+    /// User data passed to callbacks, as an address so contexts can be
+    /// locked from any thread; the callback runs on any thread anyway
+    user_data: usize,
+    /// Least severe level passed to the log callback
+    log_level: LogLevel,
+    /// Key of the log callback in `LOG_CALLBACKS`
+    log_key: u64,
 }
 
 impl Context {
@@ -46,28 +219,111 @@ impl Context {
             config: HashMap::new(),
             initialized: false,
             log_callback: None,
-            user_data: std::ptr::null_mut(),
+            log_format: LogFormat::Text,
+            user_data: 0,
+            log_level: LogLevel::Info,
+            log_key: NEXT_LOG_KEY.fetch_add(1, Ordering::Relaxed),
         }
     }
 
-    /// Set a log callback
-    pub fn set_log_callback(&mut self, callback: LogCallback, user_data: *mut c_void) {
+    /// Set a log callback receiving messages as plain text
+    ///
+    /// The callback also receives the library's tracing events (see
+    /// `telemetry`) of the context's log level or above. Calls of the callback
+    /// being replaced may still use its user data until the returned
+    /// [`InFlight`] is waited for.
+    pub fn set_log_callback(&mut self, callback: LogCallback, user_data: *mut c_void) -> InFlight {
+        self.set_log_callback_with_format(callback, user_data, LogFormat::Text)
+    }
+
+    /// Set a log callback receiving messages as JSON log records, with the
+    /// fields and spans of tracing events
+    ///
+    /// Replaces a plain log callback, like `set_log_callback` replaces a
+    /// structured one.
+    pub fn set_structured_log_callback(
+        &mut self,
+        callback: LogCallback,
+        user_data: *mut c_void,
+    ) -> InFlight {
+        self.set_log_callback_with_format(callback, user_data, LogFormat::Json)
+    }
+
+    fn set_log_callback_with_format(
+        &mut self,
+        callback: LogCallback,
+        user_data: *mut c_void,
+        format: LogFormat,
+    ) -> InFlight {
         self.log_callback = Some(callback);
-        self.user_data = user_data;
+        self.log_format = format;
+        self.user_data = user_data as usize;
+        self.register_listener();
+        InFlight { key: self.log_key }
     }
 
     /// Clear the log callback
-    pub fn clear_log_callback(&mut self) {
+    ///
+    /// Calls of the callback in progress on other threads may still use its
+    /// user data until the returned [`InFlight`] is waited for.
+    pub fn clear_log_callback(&mut self) -> InFlight {
         self.log_callback = None;
-        self.user_data = std::ptr::null_mut();
+        self.user_data = 0;
+        remove_listener(self.log_key)
+    }
+
+    /// Set the least severe level passed to the log callback (Info by default)
+    ///
+    /// May be changed at any time, e.g. to debug a problem while it happens.
+    pub fn set_log_level(&mut self, level: LogLevel) {
+        self.log_level = level;
+        self.register_listener();
+    }
+
+    /// Get the least severe level passed to the log callback
+    pub fn log_level(&self) -> LogLevel {
+        self.log_level
+    }
+
+    /// Add or update the log callback in `LOG_CALLBACKS`
+    fn register_listener(&self) {
+        let Some(callback) = self.log_callback else {
+            return;
+        };
+        let mut callbacks = LOG_CALLBACKS.lock().unwrap();
+        callbacks.by_key.insert(
+            self.log_key,
+            Listener {
+                callback,
+                user_data: self.user_data,
+                level: self.log_level,
+                format: self.log_format,
+            },
+        );
+        update_min_level(&callbacks.by_key);
     }
 
     /// Log a message (internal helper)
     pub(crate) fn log(&self, level: LogLevel, message: &str) {
-        if let Some(callback) = self.log_callback {
-            if let Ok(c_string) = std::ffi::CString::new(message) {
-                callback(level, c_string.as_ptr(), self.user_data);
+        let Some(callback) = self.log_callback.filter(|_| level >= self.log_level) else {
+            return;
+        };
+        let text = match self.log_format {
+            LogFormat::Text => message.to_string(),
+            LogFormat::Json => {
+                let mut fields = serde_json::Map::new();
+                fields.insert("context".to_string(), self.id.clone().into());
+                LogRecord {
+                    target: module_path!(),
+                    message,
+                    fields,
+                    spans: Vec::new(),
+                }
+                .to_json()
             }
+        };
+        if let Ok(c_string) = CString::new(text) {
+            callback(level, c_string.as_ptr(), self.user_data as *mut c_void);
         }
     }
 
@@ -125,6 +381,7 @@ impl Drop for Context {
         if self.initialized {
             let _ = self.shutdown();
         }
+        remove_listener(self.log_key).wait();
     }
 }
 
@@ -147,6 +404,48 @@ mod tests {
         assert!(!ctx.is_initialized());
     }
 
+    static SLOW_CALL_STARTED: std::sync::atomic::AtomicBool =
+        std::sync::atomic::AtomicBool::new(false);
+    static SLOW_CALL_RETURNED: std::sync::atomic::AtomicBool =
+        std::sync::atomic::AtomicBool::new(false);
+
+    extern "C" fn slow_callback(
+        _level: LogLevel,
+        message: *const std::os::raw::c_char,
+        _user_data: *mut c_void,
+    ) {
+        let message = unsafe { std::ffi::CStr::from_ptr(message) };
+        if message.to_bytes() == b"slow" {
+            SLOW_CALL_STARTED.store(true, Ordering::SeqCst);
+            std::thread::sleep(std::time::Duration::from_millis(100));
+            SLOW_CALL_RETURNED.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_clear_waits_for_calls_in_progress() {
+        let mut ctx = Context::new("test");
+        ctx.set_log_callback(slow_callback, std::ptr::null_mut())
+            .wait();
+        let logger = std::thread::spawn(|| {
+            let record = LogRecord {
+                target: module_path!(),
+                message: "slow",
+                fields: serde_json::Map::new(),
+                spans: Vec::new(),
+            };
+            log_all(LogLevel::Error, &record)
+        });
+        while !SLOW_CALL_STARTED.load(Ordering::SeqCst) {
+            std::thread::yield_now();
+        }
+
+        // Afterwards the user data may be freed, so the call must be over
+        ctx.clear_log_callback().wait();
+        assert!(SLOW_CALL_RETURNED.load(Ordering::SeqCst));
+        logger.join().unwrap();
+    }
+
     #[test]
     fn test_double_initialize() {
         let mut ctx = Context::new("test");
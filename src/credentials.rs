@@ -0,0 +1,654 @@
+//! Credential storage
+//!
+//! Credentials are stored under an alias as the same key/value map that is
+//! passed as "credentials" to connect. A connect configuration can then name
+//! the alias instead, so tokens and passwords don't cross the FFI on every
+//! connect and need not be kept by the embedding application.
+//!
+//! The process has one store, in memory by default. It can be switched to an
+//! encrypted file (AES-256-GCM, key derived from a passphrase with
+//! PBKDF2-HMAC-SHA256) or, with the `keyring` feature, the OS keychain: the
+//! Secret Service through `secret-tool` on Linux, the login keychain through
+//! `security` on macOS. Like D-Bus notifications, the keychain is reached
+//! through the command-line tool so no platform library needs to be linked.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+
+use base64::Engine;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, ErrorCode, Result};
+use crate::platforms::PlatformConfig;
+
+/// Credentials as passed to connect (e.g. "token", or "login_id" and "password")
+pub type Credentials = HashMap<String, String>;
+
+/// Storage for credentials by alias
+pub trait CredentialStore: Send + Sync {
+    /// Store credentials, replacing any stored under the alias
+    fn store(&self, alias: &str, credentials: &Credentials) -> Result<()>;
+
+    /// Get the credentials stored under an alias
+    ///
+    /// # Returns
+    /// The credentials, or an `ErrorCode::NotFound` error
+    fn retrieve(&self, alias: &str) -> Result<Credentials>;
+
+    /// Delete the credentials stored under an alias
+    ///
+    /// # Returns
+    /// An `ErrorCode::NotFound` error if nothing was stored under it
+    fn delete(&self, alias: &str) -> Result<()>;
+}
+
+fn not_found(alias: &str) -> Error {
+    Error::new(
+        ErrorCode::NotFound,
+        format!("No credentials stored as \"{alias}\""),
+    )
+}
+
+/// Credentials kept in memory for the life of the process
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    entries: Mutex<HashMap<String, Credentials>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CredentialStore for MemoryStore {
+    fn store(&self, alias: &str, credentials: &Credentials) -> Result<()> {
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(alias.to_string(), credentials.clone());
+        Ok(())
+    }
+
+    fn retrieve(&self, alias: &str) -> Result<Credentials> {
+        self.entries
+            .lock()
+            .unwrap()
+            .get(alias)
+            .cloned()
+            .ok_or_else(|| not_found(alias))
+    }
+
+    fn delete(&self, alias: &str) -> Result<()> {
+        match self.entries.lock().unwrap().remove(alias) {
+            Some(_) => Ok(()),
+            None => Err(not_found(alias)),
+        }
+    }
+}
+
+/// PBKDF2 iterations for new files
+const PBKDF2_ITERATIONS: u32 = 100_000;
+
+/// Contents of an encrypted credentials file
+#[derive(Debug, Serialize, Deserialize)]
+struct EncryptedFile {
+    version: u32,
+    /// PBKDF2 iterations and salt the key was derived with
+    iterations: u32,
+    salt: String,
+    /// AES-256-GCM nonce and ciphertext of the JSON map of aliases to credentials
+    nonce: String,
+    data: String,
+}
+
+/// Credentials kept in a file encrypted with a passphrase
+///
+/// The whole file is encrypted, aliases included, and rewritten atomically on
+/// every change.
+pub struct EncryptedFileStore {
+    path: PathBuf,
+    key: ring::aead::LessSafeKey,
+    iterations: u32,
+    salt: Vec<u8>,
+    entries: Mutex<HashMap<String, Credentials>>,
+}
+
+impl std::fmt::Debug for EncryptedFileStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EncryptedFileStore")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+fn encoding() -> base64::engine::GeneralPurpose {
+    base64::engine::general_purpose::STANDARD
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> Result<ring::aead::LessSafeKey> {
+    let iterations = std::num::NonZeroU32::new(iterations)
+        .ok_or_else(|| Error::invalid_argument("Invalid PBKDF2 iteration count"))?;
+    let mut key = [0u8; 32];
+    ring::pbkdf2::derive(
+        ring::pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    let key = ring::aead::UnboundKey::new(&ring::aead::AES_256_GCM, &key)
+        .map_err(|_| Error::new(ErrorCode::Unknown, "Failed to create encryption key"))?;
+    Ok(ring::aead::LessSafeKey::new(key))
+}
+
+fn random_bytes<const N: usize>() -> Result<[u8; N]> {
+    use ring::rand::SecureRandom;
+    let mut bytes = [0u8; N];
+    ring::rand::SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| Error::new(ErrorCode::Unknown, "Failed to generate random bytes"))?;
+    Ok(bytes)
+}
+
+impl EncryptedFileStore {
+    /// Open a credentials file, creating it on the first store
+    ///
+    /// # Arguments
+    /// * `path` - The file
+    /// * `passphrase` - The passphrase the file is encrypted with
+    ///
+    /// # Returns
+    /// An `ErrorCode::AuthenticationFailed` error if the passphrase is wrong
+    /// or the file was tampered with
+    pub fn open(path: impl Into<PathBuf>, passphrase: &str) -> Result<Self> {
+        Self::open_with_iterations(path.into(), passphrase, PBKDF2_ITERATIONS)
+    }
+
+    fn open_with_iterations(path: PathBuf, passphrase: &str, iterations: u32) -> Result<Self> {
+        let read_error = |e: &dyn std::fmt::Display| {
+            Error::new(
+                ErrorCode::InvalidArgument,
+                format!("Invalid credentials file {}: {e}", path.display()),
+            )
+        };
+
+        let json = match std::fs::read_to_string(&path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                let salt = random_bytes::<16>()?.to_vec();
+                return Ok(Self {
+                    key: derive_key(passphrase, &salt, iterations)?,
+                    path,
+                    iterations,
+                    salt,
+                    entries: Mutex::new(HashMap::new()),
+                });
+            }
+            Err(e) => return Err(read_error(&e)),
+        };
+
+        let file: EncryptedFile = serde_json::from_str(&json).map_err(|e| read_error(&e))?;
+        if file.version != 1 {
+            return Err(read_error(&format!("unknown version {}", file.version)));
+        }
+        let decode = |value: &str| encoding().decode(value).map_err(|e| read_error(&e));
+        let salt = decode(&file.salt)?;
+        let nonce = ring::aead::Nonce::try_assume_unique_for_key(&decode(&file.nonce)?)
+            .map_err(|_| read_error(&"invalid nonce"))?;
+        let mut data = decode(&file.data)?;
+
+        let key = derive_key(passphrase, &salt, file.iterations)?;
+        let plaintext = key
+            .open_in_place(nonce, ring::aead::Aad::empty(), &mut data)
+            .map_err(|_| {
+                Error::new(
+                    ErrorCode::AuthenticationFailed,
+                    format!(
+                        "Wrong passphrase for credentials file {}, or the file was modified",
+                        path.display()
+                    ),
+                )
+            })?;
+        let entries = serde_json::from_slice(plaintext).map_err(|e| read_error(&e))?;
+
+        Ok(Self {
+            path,
+            key,
+            iterations: file.iterations,
+            salt,
+            entries: Mutex::new(entries),
+        })
+    }
+
+    /// Encrypt the entries and replace the file atomically
+    fn save(&self, entries: &HashMap<String, Credentials>) -> Result<()> {
+        let mut data = serde_json::to_vec(entries).map_err(|e| {
+            Error::new(
+                ErrorCode::Unknown,
+                format!("Failed to serialize credentials: {e}"),
+            )
+        })?;
+        let nonce = random_bytes::<{ ring::aead::NONCE_LEN }>()?;
+        self.key
+            .seal_in_place_append_tag(
+                ring::aead::Nonce::assume_unique_for_key(nonce),
+                ring::aead::Aad::empty(),
+                &mut data,
+            )
+            .map_err(|_| Error::new(ErrorCode::Unknown, "Failed to encrypt credentials"))?;
+
+        let file = EncryptedFile {
+            version: 1,
+            iterations: self.iterations,
+            salt: encoding().encode(&self.salt),
+            nonce: encoding().encode(nonce),
+            data: encoding().encode(&data),
+        };
+        let json = serde_json::to_string(&file).map_err(|e| {
+            Error::new(
+                ErrorCode::Unknown,
+                format!("Failed to serialize credentials: {e}"),
+            )
+        })?;
+        write_private(&self.path, json.as_bytes())
+    }
+}
+
+/// Write a file readable by the owner only, replacing it atomically
+fn write_private(path: &Path, contents: &[u8]) -> Result<()> {
+    use std::io::Write;
+
+    let write_error = |e: std::io::Error| {
+        Error::new(
+            ErrorCode::Unknown,
+            format!("Failed to write credentials file {}: {e}", path.display()),
+        )
+    };
+    let temp_path = path.with_extension("tmp");
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&temp_path).map_err(write_error)?;
+    file.write_all(contents).map_err(write_error)?;
+    drop(file);
+    std::fs::rename(&temp_path, path).map_err(write_error)
+}
+
+impl CredentialStore for EncryptedFileStore {
+    fn store(&self, alias: &str, credentials: &Credentials) -> Result<()> {
+        let mut entries = self.entries.lock().unwrap();
+        let mut updated = entries.clone();
+        updated.insert(alias.to_string(), credentials.clone());
+        self.save(&updated)?;
+        *entries = updated;
+        Ok(())
+    }
+
+    fn retrieve(&self, alias: &str) -> Result<Credentials> {
+        self.entries
+            .lock()
+            .unwrap()
+            .get(alias)
+            .cloned()
+            .ok_or_else(|| not_found(alias))
+    }
+
+    fn delete(&self, alias: &str) -> Result<()> {
+        let mut entries = self.entries.lock().unwrap();
+        if !entries.contains_key(alias) {
+            return Err(not_found(alias));
+        }
+        let mut updated = entries.clone();
+        updated.remove(alias);
+        self.save(&updated)?;
+        *entries = updated;
+        Ok(())
+    }
+}
+
+/// Credentials kept in the OS keychain (`keyring` feature)
+///
+/// Each alias is one keychain item of the service, holding the credentials
+/// as base64-encoded JSON. Secrets are passed to the tools on stdin, never
+/// as arguments other processes could see.
+#[cfg(feature = "keyring")]
+#[derive(Debug, Clone)]
+pub struct KeyringStore {
+    service: String,
+}
+
+#[cfg(feature = "keyring")]
+impl KeyringStore {
+    /// Use the keychain items of a service
+    ///
+    /// # Arguments
+    /// * `service` - Name the items are stored under (e.g. the application name)
+    pub fn new(service: impl Into<String>) -> Self {
+        Self {
+            service: service.into(),
+        }
+    }
+
+    /// Run a keychain tool
+    ///
+    /// # Returns
+    /// Whether it succeeded, and its output
+    fn run(program: &str, args: &[&str], stdin: Option<&str>) -> Result<(bool, String)> {
+        use std::io::Write;
+        use std::process::{Command, Stdio};
+
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| Error::unsupported(format!("OS keychain unavailable ({program}: {e})")))?;
+        if let Some(input) = stdin {
+            if let Some(mut pipe) = child.stdin.take() {
+                pipe.write_all(input.as_bytes())
+                    .map_err(|e| Error::new(ErrorCode::Unknown, format!("{program}: {e}")))?;
+            }
+        }
+        drop(child.stdin.take());
+        let output = child
+            .wait_with_output()
+            .map_err(|e| Error::new(ErrorCode::Unknown, format!("{program}: {e}")))?;
+        Ok((
+            output.status.success(),
+            String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        ))
+    }
+
+    fn encode(credentials: &Credentials) -> Result<String> {
+        let json = serde_json::to_vec(credentials).map_err(|e| {
+            Error::new(
+                ErrorCode::Unknown,
+                format!("Failed to serialize credentials: {e}"),
+            )
+        })?;
+        Ok(encoding().encode(json))
+    }
+
+    fn decode(alias: &str, secret: &str) -> Result<Credentials> {
+        encoding()
+            .decode(secret)
+            .ok()
+            .and_then(|json| serde_json::from_slice(&json).ok())
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorCode::InvalidState,
+                    format!("Keychain item \"{alias}\" does not hold credentials"),
+                )
+            })
+    }
+}
+
+#[cfg(all(feature = "keyring", target_os = "linux"))]
+impl CredentialStore for KeyringStore {
+    fn store(&self, alias: &str, credentials: &Credentials) -> Result<()> {
+        let label = format!("{} ({alias})", self.service);
+        let secret = Self::encode(credentials)?;
+        let args = [
+            "store",
+            "--label",
+            &label,
+            "service",
+            &self.service,
+            "account",
+            alias,
+        ];
+        match Self::run("secret-tool", &args, Some(&secret))? {
+            (true, _) => Ok(()),
+            (false, _) => Err(Error::new(
+                ErrorCode::PermissionDenied,
+                "The Secret Service refused to store the credentials",
+            )),
+        }
+    }
+
+    fn retrieve(&self, alias: &str) -> Result<Credentials> {
+        let args = ["lookup", "service", &self.service, "account", alias];
+        match Self::run("secret-tool", &args, None)? {
+            (true, secret) if !secret.is_empty() => Self::decode(alias, &secret),
+            _ => Err(not_found(alias)),
+        }
+    }
+
+    fn delete(&self, alias: &str) -> Result<()> {
+        // secret-tool succeeds whether or not the item existed
+        self.retrieve(alias)?;
+        let args = ["clear", "service", &self.service, "account", alias];
+        match Self::run("secret-tool", &args, None)? {
+            (true, _) => Ok(()),
+            (false, _) => Err(Error::new(
+                ErrorCode::PermissionDenied,
+                "The Secret Service refused to delete the credentials",
+            )),
+        }
+    }
+}
+
+#[cfg(all(feature = "keyring", target_os = "macos"))]
+impl CredentialStore for KeyringStore {
+    fn store(&self, alias: &str, credentials: &Credentials) -> Result<()> {
+        // Commands read by `security -i` keep the secret out of the arguments;
+        // base64 needs no quoting, the service and alias are quoted
+        let secret = Self::encode(credentials)?;
+        let command = format!(
+            "add-generic-password -U -s {} -a {} -w {secret}\n",
+            quote(&self.service),
+            quote(alias)
+        );
+        match Self::run("security", &["-i"], Some(&command))? {
+            (true, _) => Ok(()),
+            (false, _) => Err(Error::new(
+                ErrorCode::PermissionDenied,
+                "The keychain refused to store the credentials",
+            )),
+        }
+    }
+
+    fn retrieve(&self, alias: &str) -> Result<Credentials> {
+        let args = [
+            "find-generic-password",
+            "-s",
+            &self.service,
+            "-a",
+            alias,
+            "-w",
+        ];
+        match Self::run("security", &args, None)? {
+            (true, secret) if !secret.is_empty() => Self::decode(alias, &secret),
+            _ => Err(not_found(alias)),
+        }
+    }
+
+    fn delete(&self, alias: &str) -> Result<()> {
+        let args = ["delete-generic-password", "-s", &self.service, "-a", alias];
+        match Self::run("security", &args, None)? {
+            (true, _) => Ok(()),
+            (false, _) => Err(not_found(alias)),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "keyring",
+    not(any(target_os = "linux", target_os = "macos"))
+))]
+impl CredentialStore for KeyringStore {
+    fn store(&self, _alias: &str, _credentials: &Credentials) -> Result<()> {
+        Err(Error::unsupported(
+            "OS keychain not supported on this system",
+        ))
+    }
+
+    fn retrieve(&self, _alias: &str) -> Result<Credentials> {
+        Err(Error::unsupported(
+            "OS keychain not supported on this system",
+        ))
+    }
+
+    fn delete(&self, _alias: &str) -> Result<()> {
+        Err(Error::unsupported(
+            "OS keychain not supported on this system",
+        ))
+    }
+}
+
+/// Quote an argument for a command read by `security -i`
+#[cfg(all(feature = "keyring", target_os = "macos"))]
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Store configuration, as passed to communicator_credentials_configure()
+#[derive(Debug, Deserialize)]
+#[serde(tag = "backend", rename_all = "lowercase")]
+pub enum StoreConfig {
+    Memory,
+    File {
+        path: String,
+        passphrase: String,
+    },
+    Keyring {
+        #[serde(default = "default_service")]
+        service: String,
+    },
+}
+
+fn default_service() -> String {
+    "libcommunicator".to_string()
+}
+
+impl StoreConfig {
+    /// Open the configured store
+    pub fn open(self) -> Result<Arc<dyn CredentialStore>> {
+        match self {
+            StoreConfig::Memory => Ok(Arc::new(MemoryStore::new())),
+            StoreConfig::File { path, passphrase } => {
+                Ok(Arc::new(EncryptedFileStore::open(path, &passphrase)?))
+            }
+            #[cfg(feature = "keyring")]
+            StoreConfig::Keyring { service } => Ok(Arc::new(KeyringStore::new(service))),
+            #[cfg(not(feature = "keyring"))]
+            StoreConfig::Keyring { .. } => Err(Error::unsupported(
+                "OS keychain support requires the keyring feature",
+            )),
+        }
+    }
+}
+
+lazy_static! {
+    /// The process's credential store
+    static ref STORE: RwLock<Arc<dyn CredentialStore>> = RwLock::new(Arc::new(MemoryStore::new()));
+}
+
+/// Replace the process's credential store
+///
+/// Credentials in the previous store are not carried over.
+pub fn set_store(store: Arc<dyn CredentialStore>) {
+    *STORE.write().unwrap() = store;
+}
+
+/// The process's credential store
+pub fn store() -> Arc<dyn CredentialStore> {
+    STORE.read().unwrap().clone()
+}
+
+/// Fill in a configuration's credentials from its credentials alias
+///
+/// Credentials given in the configuration itself take precedence over stored
+/// ones (e.g. a fresh "mfa_token" next to a stored password).
+pub fn resolve(config: &mut PlatformConfig) -> Result<()> {
+    let Some(alias) = &config.credentials_alias else {
+        return Ok(());
+    };
+    for (key, value) in store().retrieve(alias)? {
+        config.credentials.entry(key).or_insert(value);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn credentials(token: &str) -> Credentials {
+        HashMap::from([("token".to_string(), token.to_string())])
+    }
+
+    #[test]
+    fn test_memory_store() {
+        let store = MemoryStore::new();
+        store.store("work", &credentials("a")).unwrap();
+        assert_eq!(store.retrieve("work").unwrap(), credentials("a"));
+
+        store.delete("work").unwrap();
+        assert_eq!(
+            store.retrieve("work").unwrap_err().code,
+            ErrorCode::NotFound
+        );
+        assert_eq!(store.delete("work").unwrap_err().code, ErrorCode::NotFound);
+    }
+
+    #[test]
+    fn test_encrypted_file_store() {
+        let path = std::env::temp_dir().join(format!(
+            "libcommunicator-credentials-test-{}.json",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        let store = EncryptedFileStore::open_with_iterations(path.clone(), "secret", 1000).unwrap();
+        store.store("work", &credentials("a")).unwrap();
+        store.store("home", &credentials("b")).unwrap();
+        store.delete("home").unwrap();
+
+        // Neither aliases nor secrets are readable in the file
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(!contents.contains("work") && !contents.contains("token"));
+
+        let reopened = EncryptedFileStore::open(path.clone(), "secret").unwrap();
+        assert_eq!(reopened.retrieve("work").unwrap(), credentials("a"));
+        assert_eq!(
+            reopened.retrieve("home").unwrap_err().code,
+            ErrorCode::NotFound
+        );
+
+        let error = EncryptedFileStore::open(path.clone(), "wrong").unwrap_err();
+        assert_eq!(error.code, ErrorCode::AuthenticationFailed);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_resolve_prefers_given_credentials() {
+        let store = MemoryStore::new();
+        let mut stored = credentials("stored");
+        stored.insert("login_id".to_string(), "user".to_string());
+        store.store("work", &stored).unwrap();
+        set_store(Arc::new(store));
+
+        let mut config = PlatformConfig::new("https://chat.example.com")
+            .with_credential("token", "given")
+            .with_credentials_alias("work");
+        resolve(&mut config).unwrap();
+        assert_eq!(config.credentials["token"], "given");
+        assert_eq!(config.credentials["login_id"], "user");
+
+        let mut config =
+            PlatformConfig::new("https://chat.example.com").with_credentials_alias("missing");
+        assert_eq!(resolve(&mut config).unwrap_err().code, ErrorCode::NotFound);
+
+        set_store(Arc::new(MemoryStore::new()));
+    }
+}
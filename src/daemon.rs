@@ -0,0 +1,966 @@
+//! Daemon mode: the platform API over a Unix domain socket
+//!
+//! Runs one platform connection and serves it to any number of local
+//! frontends (a TUI, a notifier, a status bar widget, ...) that then share the
+//! connection and its caches instead of each linking the library.
+//!
+//! The protocol is line-delimited JSON. Each request is one line:
+//!
+//! ```text
+//! {"id": 1, "method": "send_message", "params": {"channel_id": "...", "text": "hi"}}
+//! ```
+//!
+//! and is answered by one line carrying the same `id` and either a `result`
+//! or an `error` (`{"code": 8, "message": "..."}`, codes as in [`ErrorCode`]).
+//! Requests are processed concurrently, so responses may arrive out of order.
+//!
+//! Method names and parameter names follow the [`Platform`] trait (e.g.
+//! `get_messages` takes `channel_id` and `limit`). In addition:
+//! - `connect` takes the same configuration as `communicator_platform_connect`
+//!   and is only needed once for all frontends
+//! - `subscribe_events` makes the daemon push every platform event to this
+//!   frontend as `{"method": "event", "params": {...}}`, in the JSON format of
+//!   `communicator_platform_poll_event`; `unsubscribe_events` stops that
+//! - `ping` returns `"pong"`
+//!
+//! Binary data (file downloads, images) is not available over the socket.
+//! Events run through the connection's [`Pipeline`] before they are pushed,
+//! so webhooks, rules and the other stages configured on
+//! [`Daemon::pipeline`] apply to every frontend.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::{broadcast, mpsc, RwLock};
+
+use crate::error::{Error, ErrorCode, Result};
+use crate::import::ImportOptions;
+use crate::pipeline::{Pipeline, PipelinePlatform, Polled};
+use crate::platforms::{Platform, PlatformConfig};
+use crate::types::user::UserStatus;
+use crate::types::{BotSettings, OAuthAppSettings, PollSettings, ProfileUpdate, SendOptions};
+
+/// How often events are polled while no event is pending
+const EVENT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Events buffered per frontend before the slowest ones start missing events
+const EVENT_BUFFER: usize = 1024;
+
+/// A request line
+#[derive(Debug, Deserialize)]
+struct Request {
+    #[serde(default)]
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+/// An error in a response line
+#[derive(Debug, Serialize)]
+struct ResponseError {
+    code: i32,
+    message: String,
+}
+
+impl From<Error> for ResponseError {
+    fn from(e: Error) -> Self {
+        ResponseError {
+            code: e.code as i32,
+            message: e.message,
+        }
+    }
+}
+
+/// Build the response line for a request
+fn response(id: Value, outcome: Result<Value>) -> Value {
+    match outcome {
+        Ok(result) => serde_json::json!({ "id": id, "result": result }),
+        Err(e) => serde_json::json!({ "id": id, "error": ResponseError::from(e) }),
+    }
+}
+
+/// Named parameters of a request
+struct Params<'a>(&'a Value);
+
+impl Params<'_> {
+    fn get<T: DeserializeOwned>(&self, name: &str) -> Result<T> {
+        let value = self.0.get(name).cloned().unwrap_or(Value::Null);
+        serde_json::from_value(value)
+            .map_err(|e| Error::invalid_argument(format!("Invalid parameter '{name}': {e}")))
+    }
+
+    fn str(&self, name: &str) -> Result<&str> {
+        self.0
+            .get(name)
+            .and_then(Value::as_str)
+            .ok_or_else(|| Error::invalid_argument(format!("Missing parameter '{name}'")))
+    }
+
+    fn opt_str(&self, name: &str) -> Option<&str> {
+        self.0.get(name).and_then(Value::as_str)
+    }
+
+    fn usize_or(&self, name: &str, default: usize) -> Result<usize> {
+        match self.0.get(name) {
+            None | Some(Value::Null) => Ok(default),
+            Some(_) => self.get(name),
+        }
+    }
+}
+
+fn to_json<T: Serialize>(value: T) -> Result<Value> {
+    serde_json::to_value(value).map_err(|e| {
+        Error::new(
+            ErrorCode::Unknown,
+            format!("Failed to serialize result: {e}"),
+        )
+    })
+}
+
+/// Serves one platform connection on a Unix domain socket
+pub struct Daemon {
+    platform: Arc<RwLock<PipelinePlatform>>,
+    pipeline: Arc<Pipeline>,
+    events: broadcast::Sender<Value>,
+    /// Whether the platform has been subscribed to events
+    subscribed: Arc<AtomicBool>,
+}
+
+impl Daemon {
+    /// Create a daemon serving the given platform
+    ///
+    /// The platform may already be connected; otherwise the first frontend
+    /// connects it with the `connect` method.
+    pub fn new(platform: Box<dyn Platform>) -> Self {
+        let platform = PipelinePlatform::new(platform);
+        let (events, _) = broadcast::channel(EVENT_BUFFER);
+        Daemon {
+            pipeline: Arc::clone(platform.pipeline()),
+            platform: Arc::new(RwLock::new(platform)),
+            events,
+            subscribed: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// The event pipeline of the served connection, to configure its stages
+    pub fn pipeline(&self) -> &Pipeline {
+        &self.pipeline
+    }
+
+    /// Listen on a socket and serve frontends until an I/O error occurs
+    ///
+    /// # Arguments
+    /// * `socket_path` - Path of the socket to create; a stale socket file is replaced
+    ///
+    /// # Notes
+    /// The socket is only accessible to the current user, since every frontend
+    /// acts with the user's session.
+    pub async fn serve(self, socket_path: impl AsRef<Path>) -> Result<()> {
+        let listener = bind(socket_path.as_ref())?;
+        let _pump = AbortOnDrop(tokio::spawn(pump_events(
+            Arc::clone(&self.platform),
+            self.events.clone(),
+            Arc::clone(&self.subscribed),
+        )));
+
+        loop {
+            let (stream, _) = listener.accept().await.map_err(|e| {
+                Error::new(
+                    ErrorCode::NetworkError,
+                    format!("Failed to accept daemon connection: {e}"),
+                )
+            })?;
+            tokio::spawn(serve_frontend(
+                stream,
+                Arc::clone(&self.platform),
+                self.events.clone(),
+                Arc::clone(&self.subscribed),
+            ));
+        }
+    }
+}
+
+/// Stops the event pump when the daemon stops serving
+struct AbortOnDrop(tokio::task::JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Create the listening socket, readable and writable by the owner only
+///
+/// The socket is bound in a private directory next to it and only moved into
+/// place once its permissions are restricted, so other users can't connect
+/// in between.
+fn bind(path: &Path) -> Result<UnixListener> {
+    use std::os::unix::fs::{DirBuilderExt, FileTypeExt, PermissionsExt};
+
+    let io_error = |what: &str, e: std::io::Error| {
+        Error::new(
+            ErrorCode::Unknown,
+            format!("Failed to {what} {}: {e}", path.display()),
+        )
+    };
+
+    let Some(file_name) = path.file_name() else {
+        return Err(Error::invalid_argument(format!(
+            "{} is not a socket path",
+            path.display()
+        )));
+    };
+
+    if let Ok(metadata) = std::fs::symlink_metadata(path) {
+        if !metadata.file_type().is_socket() {
+            return Err(Error::invalid_argument(format!(
+                "{} exists and is not a socket",
+                path.display()
+            )));
+        }
+        std::fs::remove_file(path).map_err(|e| io_error("remove stale socket", e))?;
+    }
+
+    let mut private_dir = path.as_os_str().to_owned();
+    private_dir.push(format!(".{}.tmp", std::process::id()));
+    let private_dir = PathBuf::from(private_dir);
+    // Left behind by an earlier daemon with the same process ID
+    let _ = std::fs::remove_dir_all(&private_dir);
+    std::fs::DirBuilder::new()
+        .mode(0o700)
+        .create(&private_dir)
+        .map_err(|e| io_error("create a private directory for", e))?;
+
+    let bound_path = private_dir.join(file_name);
+    let listener = UnixListener::bind(&bound_path)
+        .map_err(|e| io_error("bind", e))
+        .and_then(|listener| {
+            std::fs::set_permissions(&bound_path, std::fs::Permissions::from_mode(0o600))
+                .map_err(|e| io_error("restrict permissions of", e))?;
+            std::fs::rename(&bound_path, path).map_err(|e| io_error("move socket to", e))?;
+            Ok(listener)
+        });
+    let _ = std::fs::remove_dir_all(&private_dir);
+    listener
+}
+
+/// Poll platform events and broadcast them to subscribed frontends
+async fn pump_events(
+    platform: Arc<RwLock<PipelinePlatform>>,
+    events: broadcast::Sender<Value>,
+    subscribed: Arc<AtomicBool>,
+) {
+    loop {
+        if !subscribed.load(Ordering::Acquire) {
+            tokio::time::sleep(EVENT_POLL_INTERVAL).await;
+            continue;
+        }
+
+        match PipelinePlatform::next_event_shared(&platform).await {
+            Ok(Polled::Event(event)) => {
+                // Sending only fails if no frontend is listening
+                let _ = events.send(crate::event_json::event_to_json(&event));
+            }
+            Ok(Polled::Skipped) => {}
+            Ok(Polled::Empty) | Err(_) => tokio::time::sleep(EVENT_POLL_INTERVAL).await,
+        }
+    }
+}
+
+/// Handle the requests of one frontend until it disconnects
+async fn serve_frontend(
+    stream: UnixStream,
+    platform: Arc<RwLock<PipelinePlatform>>,
+    events: broadcast::Sender<Value>,
+    subscribed: Arc<AtomicBool>,
+) {
+    let (reader, mut writer) = stream.into_split();
+    let (lines, mut outgoing) = mpsc::unbounded_channel::<Value>();
+
+    // Responses and events are written by a single task so lines never interleave
+    let write_task = tokio::spawn(async move {
+        while let Some(line) = outgoing.recv().await {
+            let mut bytes = line.to_string().into_bytes();
+            bytes.push(b'\n');
+            if writer.write_all(&bytes).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut event_task: Option<tokio::task::JoinHandle<()>> = None;
+    let mut reader = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = reader.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let request: Request = match serde_json::from_str(&line) {
+            Ok(r) => r,
+            Err(e) => {
+                let error = Error::invalid_argument(format!("Invalid request: {e}"));
+                let _ = lines.send(response(Value::Null, Err(error)));
+                continue;
+            }
+        };
+
+        match request.method.as_str() {
+            "subscribe_events" => {
+                let outcome = subscribe(&platform, &subscribed).await;
+                if outcome.is_ok() && event_task.is_none() {
+                    event_task = Some(forward_events(events.subscribe(), lines.clone()));
+                }
+                let _ = lines.send(response(request.id, outcome.map(|_| Value::Null)));
+            }
+            "unsubscribe_events" => {
+                if let Some(task) = event_task.take() {
+                    task.abort();
+                }
+                let _ = lines.send(response(request.id, Ok(Value::Null)));
+            }
+            _ => {
+                let platform = Arc::clone(&platform);
+                let lines = lines.clone();
+                tokio::spawn(async move {
+                    let outcome =
+                        dispatch(&platform, &request.method, Params(&request.params)).await;
+                    let _ = lines.send(response(request.id, outcome));
+                });
+            }
+        }
+    }
+
+    if let Some(task) = event_task {
+        task.abort();
+    }
+    drop(lines);
+    let _ = write_task.await;
+}
+
+/// Subscribe the platform to events, once for all frontends
+async fn subscribe(platform: &RwLock<PipelinePlatform>, subscribed: &AtomicBool) -> Result<()> {
+    if subscribed.load(Ordering::Acquire) {
+        return Ok(());
+    }
+    let mut platform = platform.write().await;
+    // Another frontend may have subscribed while we waited for the lock
+    if !subscribed.load(Ordering::Acquire) {
+        platform.subscribe_events().await?;
+        subscribed.store(true, Ordering::Release);
+    }
+    Ok(())
+}
+
+/// Forward broadcast events to a frontend
+fn forward_events(
+    mut events: broadcast::Receiver<Value>,
+    lines: mpsc::UnboundedSender<Value>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            let event = match events.recv().await {
+                Ok(event) => event,
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    serde_json::json!({ "type": "events_lagged", "missed": missed })
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+            let notification = serde_json::json!({ "method": "event", "params": event });
+            if lines.send(notification).is_err() {
+                break;
+            }
+        }
+    })
+}
+
+/// Configuration accepted by the `connect` method
+#[derive(Debug, Deserialize)]
+struct ConnectParams {
+    server: String,
+    #[serde(default)]
+    credentials: HashMap<String, String>,
+    #[serde(default)]
+    credentials_alias: Option<String>,
+    team_id: Option<String>,
+    #[serde(default)]
+    fallback_servers: Vec<String>,
+    #[serde(default)]
+    proxy: Option<String>,
+    #[serde(default)]
+    tls: crate::tls::TlsConfig,
+    #[serde(default)]
+    hosts: HashMap<String, String>,
+    #[serde(default)]
+    startup_cache: Option<String>,
+    #[serde(default)]
+    download_spill_threshold: Option<u64>,
+    #[serde(default)]
+    outbox: Option<String>,
+    #[serde(default)]
+    retry: crate::types::RetryPolicy,
+    #[serde(default)]
+    cache: crate::types::CacheConfig,
+    #[serde(default)]
+    enrich_events: bool,
+    #[serde(default)]
+    resync_on_reconnect: Option<bool>,
+    #[serde(default)]
+    resync_on_gap: bool,
+}
+
+/// Run a request against the platform
+async fn dispatch(
+    platform: &RwLock<PipelinePlatform>,
+    method: &str,
+    params: Params<'_>,
+) -> Result<Value> {
+    match method {
+        "ping" => Ok(Value::String("pong".to_string())),
+        "connect" => {
+            let connect: ConnectParams = serde_json::from_value(params.0.clone())
+                .map_err(|e| Error::invalid_argument(format!("Invalid config: {e}")))?;
+            let mut config = PlatformConfig::new(connect.server);
+            config.credentials = connect.credentials;
+            config.team_id = connect.team_id;
+            config.fallback_servers = connect.fallback_servers;
+            config.proxy = connect.proxy;
+            config.tls = connect.tls;
+            config.hosts = connect.hosts;
+            config.startup_cache = connect.startup_cache;
+            config.download_spill_threshold = connect.download_spill_threshold;
+            config.outbox = connect.outbox;
+            config.retry = connect.retry;
+            config.cache = connect.cache;
+            config.enrich_events = connect.enrich_events;
+            config.resync_on_reconnect = connect.resync_on_reconnect.unwrap_or(true);
+            config.resync_on_gap = connect.resync_on_gap;
+            config.credentials_alias = connect.credentials_alias;
+            crate::credentials::resolve(&mut config)?;
+            to_json(platform.write().await.connect(config).await?)
+        }
+        "disconnect" => {
+            platform.write().await.disconnect().await?;
+            Ok(Value::Null)
+        }
+        _ => {
+            let platform = platform.read().await;
+            call(&*platform, method, params).await
+        }
+    }
+}
+
+/// Run a request that only needs shared access to the platform
+async fn call(platform: &dyn Platform, method: &str, p: Params<'_>) -> Result<Value> {
+    match method {
+        // Connection
+        "connection_info" => to_json(platform.connection_info()),
+        "capabilities" => to_json(platform.capabilities()),
+        "set_team_id" => to_json(platform.set_team_id(p.get("team_id")?).await?),
+        "renew_session" => to_json(platform.renew_session(p.get("credentials")?).await?),
+        "begin_sso_login" => to_json(platform.begin_sso_login(p.str("provider")?).await?),
+        "complete_sso_login" => to_json(platform.complete_sso_login(p.str("callback_url")?).await?),
+        "get_circuit_state" => to_json(platform.get_circuit_state().await?),
+        "get_request_stats" => to_json(platform.get_request_stats().await?),
+        "get_connection_health" => to_json(platform.connection_health().await?),
+        "configure_cache" => to_json(platform.configure_cache(p.get("config")?).await?),
+        "get_cache_stats" => to_json(platform.get_cache_stats().await?),
+        "set_event_filter" => to_json(
+            platform
+                .set_event_filter(
+                    p.get::<Option<crate::types::EventFilter>>("filter")?
+                        .unwrap_or_default(),
+                )
+                .await?,
+        ),
+
+        // Messages
+        "send_message" => to_json(
+            platform
+                .send_message(p.str("channel_id")?, p.str("text")?)
+                .await?,
+        ),
+        "send_reply" => to_json(
+            platform
+                .send_reply(p.str("channel_id")?, p.str("text")?, p.str("root_id")?)
+                .await?,
+        ),
+        "send_message_with_attachments" => to_json(
+            platform
+                .send_message_with_attachments(
+                    p.str("channel_id")?,
+                    p.opt_str("text").unwrap_or_default(),
+                    p.get("file_ids")?,
+                )
+                .await?,
+        ),
+        "send_message_ex" => to_json(
+            platform
+                .send_message_ex(
+                    p.str("channel_id")?,
+                    p.opt_str("text").unwrap_or_default(),
+                    &p.get::<Option<SendOptions>>("options")?.unwrap_or_default(),
+                )
+                .await?,
+        ),
+        "send_code_snippet" => to_json(
+            platform
+                .send_code_snippet(
+                    p.str("channel_id")?,
+                    p.opt_str("language"),
+                    p.str("code")?,
+                    p.opt_str("filename"),
+                )
+                .await?,
+        ),
+        "quote_message" => to_json(
+            platform
+                .quote_message(
+                    p.str("target_id")?,
+                    p.opt_str("comment").unwrap_or_default(),
+                )
+                .await?,
+        ),
+        "update_message" => to_json(
+            platform
+                .update_message(p.str("message_id")?, p.str("text")?)
+                .await?,
+        ),
+        "delete_message" => to_json(platform.delete_message(p.str("message_id")?).await?),
+        "get_message" => to_json(platform.get_message(p.str("message_id")?).await?),
+        "get_messages" => to_json(
+            platform
+                .get_messages(p.str("channel_id")?, p.usize_or("limit", 60)?)
+                .await?,
+        ),
+        "get_window" => to_json(platform.get_window(p.str("channel_id")?).await?),
+        "get_messages_before" => to_json(
+            platform
+                .get_messages_before(
+                    p.str("channel_id")?,
+                    p.str("before_id")?,
+                    p.usize_or("limit", 60)?,
+                )
+                .await?,
+        ),
+        "get_messages_after" => to_json(
+            platform
+                .get_messages_after(
+                    p.str("channel_id")?,
+                    p.str("after_id")?,
+                    p.usize_or("limit", 60)?,
+                )
+                .await?,
+        ),
+        "search_messages" => to_json(
+            platform
+                .search_messages(p.str("query")?, p.usize_or("limit", 60)?)
+                .await?,
+        ),
+        "add_reaction" => to_json(
+            platform
+                .add_reaction(p.str("message_id")?, p.str("emoji")?)
+                .await?,
+        ),
+        "remove_reaction" => to_json(
+            platform
+                .remove_reaction(p.str("message_id")?, p.str("emoji")?)
+                .await?,
+        ),
+        "get_reactions" => to_json(platform.get_reactions(p.str("message_id")?).await?),
+        "get_emojis" => to_json(
+            platform
+                .get_emojis(
+                    p.get::<Option<u32>>("page")?.unwrap_or(0),
+                    p.get::<Option<u32>>("per_page")?.unwrap_or(60),
+                )
+                .await?,
+        ),
+        "create_emoji" => {
+            let file_path = p.str("file_path")?;
+            let image = tokio::fs::read(file_path).await.map_err(|e| {
+                Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to read emoji image {file_path}: {e}"),
+                )
+            })?;
+            to_json(platform.create_emoji(p.str("name")?, image).await?)
+        }
+        "delete_emoji" => to_json(platform.delete_emoji(p.str("emoji_id")?).await?),
+        "autocomplete_emojis" => to_json(
+            platform
+                .autocomplete_emojis(p.str("prefix")?, p.usize_or("limit", 20)?)
+                .await?,
+        ),
+        "pin_post" => to_json(platform.pin_post(p.str("message_id")?).await?),
+        "unpin_post" => to_json(platform.unpin_post(p.str("message_id")?).await?),
+        "get_pinned_posts" => to_json(platform.get_pinned_posts(p.str("channel_id")?).await?),
+        "flag_post" => to_json(platform.flag_post(p.str("message_id")?).await?),
+        "unflag_post" => to_json(platform.unflag_post(p.str("message_id")?).await?),
+        "get_flagged_posts" => to_json(
+            platform
+                .get_flagged_posts(
+                    p.get::<Option<u32>>("page")?.unwrap_or(0),
+                    p.get::<Option<u32>>("per_page")?.unwrap_or(60),
+                )
+                .await?,
+        ),
+        "import_history" => {
+            let file_path = p.str("file_path")?;
+            let jsonl = tokio::fs::read_to_string(file_path).await.map_err(|e| {
+                Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to read export {file_path}: {e}"),
+                )
+            })?;
+            let options: Option<ImportOptions> = p.get("options")?;
+            to_json(
+                crate::import::import_history(
+                    platform,
+                    p.str("channel_id")?,
+                    &jsonl,
+                    &options.unwrap_or_default(),
+                )
+                .await?,
+            )
+        }
+        "send_typing_indicator" => to_json(
+            platform
+                .send_typing_indicator(p.str("channel_id")?, p.opt_str("parent_id"))
+                .await?,
+        ),
+
+        // Polls
+        "execute_command" => to_json(
+            platform
+                .execute_command(p.str("channel_id")?, p.str("command")?)
+                .await?,
+        ),
+        "autocomplete_commands" => to_json(
+            platform
+                .autocomplete_commands(
+                    p.opt_str("team_id"),
+                    p.opt_str("prefix").unwrap_or(""),
+                    p.usize_or("limit", 20)?,
+                )
+                .await?,
+        ),
+        "create_poll" => {
+            let options: Vec<String> = p.get("options")?;
+            let settings: Option<PollSettings> = p.get("settings")?;
+            to_json(
+                platform
+                    .create_poll(
+                        p.str("channel_id")?,
+                        p.str("question")?,
+                        &options,
+                        &settings.unwrap_or_default(),
+                    )
+                    .await?,
+            )
+        }
+        "get_poll" => to_json(platform.get_poll(p.str("poll_id")?).await?),
+        "vote_poll" => to_json(
+            platform
+                .vote_poll(p.str("poll_id")?, p.get("option_index")?)
+                .await?,
+        ),
+        "close_poll" => to_json(platform.close_poll(p.str("poll_id")?).await?),
+
+        // Channels
+        "get_channels" => to_json(platform.get_channels().await?),
+        "get_channel" => to_json(platform.get_channel(p.str("channel_id")?).await?),
+        "cached_channels" => to_json(platform.cached_channels().await?),
+        "get_channel_by_name" => to_json(
+            platform
+                .get_channel_by_name(p.str("team_id")?, p.str("channel_name")?)
+                .await?,
+        ),
+        "get_channel_members" => to_json(platform.get_channel_members(p.str("channel_id")?).await?),
+        "create_direct_channel" => {
+            to_json(platform.create_direct_channel(p.str("user_id")?).await?)
+        }
+        "create_group_channel" => to_json(platform.create_group_channel(p.get("user_ids")?).await?),
+        "add_channel_member" => to_json(
+            platform
+                .add_channel_member(p.str("channel_id")?, p.str("user_id")?)
+                .await?,
+        ),
+        "remove_channel_member" => to_json(
+            platform
+                .remove_channel_member(p.str("channel_id")?, p.str("user_id")?)
+                .await?,
+        ),
+        "get_channel_member_roles" => to_json(
+            platform
+                .get_channel_member_roles(p.str("channel_id")?, p.str("user_id")?)
+                .await?,
+        ),
+        "set_channel_admin" => to_json(
+            platform
+                .set_channel_admin(p.str("channel_id")?, p.str("user_id")?, p.get("is_admin")?)
+                .await?,
+        ),
+        "set_channel_privacy" => to_json(
+            platform
+                .set_channel_privacy(p.str("channel_id")?, p.get("is_private")?)
+                .await?,
+        ),
+        "search_channels" => to_json(
+            platform
+                .search_channels(
+                    p.opt_str("team_id"),
+                    p.str("query")?,
+                    p.usize_or("limit", 20)?,
+                )
+                .await?,
+        ),
+        "autocomplete_channels" => to_json(
+            platform
+                .autocomplete_channels(
+                    p.opt_str("team_id"),
+                    p.str("query")?,
+                    p.usize_or("limit", 20)?,
+                )
+                .await?,
+        ),
+        "view_channel" => to_json(
+            platform
+                .view_channel(p.str("channel_id")?, p.opt_str("prev_channel_id"))
+                .await?,
+        ),
+        "get_channel_unread" => to_json(platform.get_channel_unread(p.str("channel_id")?).await?),
+        "get_team_unreads" => to_json(platform.get_team_unreads(p.str("team_id")?).await?),
+        "get_all_unreads" => to_json(platform.get_all_unreads().await?),
+        "get_channel_unreads" => to_json(platform.get_channel_unreads().await?),
+        "mark_channel_read" => to_json(platform.mark_channel_read(p.str("channel_id")?).await?),
+        "get_sidebar_categories" => to_json(platform.get_sidebar_categories().await?),
+        "move_channel_to_category" => to_json(
+            platform
+                .move_channel_to_category(
+                    p.str("channel_id")?,
+                    p.str("category_id")?,
+                    p.get::<Option<usize>>("index")?,
+                )
+                .await?,
+        ),
+        "mute_channel" => to_json(platform.mute_channel(p.str("channel_id")?).await?),
+        "unmute_channel" => to_json(platform.unmute_channel(p.str("channel_id")?).await?),
+
+        // Users
+        "get_user" => to_json(platform.get_user(p.str("user_id")?).await?),
+        "get_current_user" => to_json(platform.get_current_user().await?),
+        "cached_users" => to_json(platform.cached_users().await?),
+        "get_groups" => to_json(platform.get_groups().await?),
+        "get_group_members" => to_json(platform.get_group_members(p.str("group_id")?).await?),
+        "resolve_group_mentions" => to_json(platform.resolve_group_mentions(p.str("text")?).await?),
+        "update_profile" => {
+            let update: ProfileUpdate = p.get("update")?;
+            to_json(platform.update_profile(&update).await?)
+        }
+        "set_profile_image" => {
+            let file_path = p.str("file_path")?;
+            let image = tokio::fs::read(file_path).await.map_err(|e| {
+                Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to read profile image {file_path}: {e}"),
+                )
+            })?;
+            to_json(platform.set_profile_image(image).await?)
+        }
+        "get_user_by_username" => to_json(platform.get_user_by_username(p.str("username")?).await?),
+        "get_users_by_ids" => to_json(platform.get_users_by_ids(p.get("user_ids")?).await?),
+        "search_users" => to_json(
+            platform
+                .search_users(p.str("query")?, p.usize_or("limit", 20)?)
+                .await?,
+        ),
+        "prefetch_channel_profiles" => to_json(
+            platform
+                .prefetch_channel_profiles(p.get("channel_ids")?)
+                .await?,
+        ),
+        "set_status" => to_json(
+            platform
+                .set_status(p.get::<UserStatus>("status")?, p.opt_str("custom_message"))
+                .await?,
+        ),
+        "get_user_status" => to_json(platform.get_user_status(p.str("user_id")?).await?),
+        "get_users_status" => to_json(platform.get_users_status(p.get("user_ids")?).await?),
+        "get_typing_users" => to_json(platform.get_typing_users(p.str("channel_id")?).await?),
+        "set_custom_status" => to_json(
+            platform
+                .set_custom_status(p.opt_str("emoji"), p.str("text")?, p.get("expires_at")?)
+                .await?,
+        ),
+        "remove_custom_status" => to_json(platform.remove_custom_status().await?),
+
+        // Teams
+        "get_teams" => to_json(platform.get_teams().await?),
+        "get_team" => to_json(platform.get_team(p.str("team_id")?).await?),
+        "get_team_by_name" => to_json(platform.get_team_by_name(p.str("team_name")?).await?),
+
+        // Files
+        "get_file_metadata" => to_json(platform.get_file_metadata(p.str("file_id")?).await?),
+        "get_file_link" => to_json(platform.get_file_link(p.str("file_id")?).await?),
+        "upload_file" => to_json(
+            platform
+                .upload_file(p.str("channel_id")?, &PathBuf::from(p.str("file_path")?))
+                .await?,
+        ),
+        "download_file_to_path" => {
+            let path = p.opt_str("path").map(PathBuf::from);
+            to_json(
+                platform
+                    .download_file_to_path(p.str("file_id")?, path.as_deref())
+                    .await?,
+            )
+        }
+
+        // Threads
+        "get_thread" => to_json(platform.get_thread(p.str("post_id")?).await?),
+        "get_followed_threads" => to_json(
+            platform
+                .get_followed_threads(
+                    p.opt_str("team_id"),
+                    p.get::<Option<u32>>("page")?.unwrap_or(0),
+                    p.get::<Option<u32>>("per_page")?.unwrap_or(60),
+                )
+                .await?,
+        ),
+        "follow_thread" => to_json(platform.follow_thread(p.str("thread_id")?).await?),
+        "unfollow_thread" => to_json(platform.unfollow_thread(p.str("thread_id")?).await?),
+        "mark_thread_read" => to_json(platform.mark_thread_read(p.str("thread_id")?).await?),
+
+        // Integrations
+        "register_oauth_app" => {
+            let settings: OAuthAppSettings = p.get("settings")?;
+            to_json(platform.register_oauth_app(&settings).await?)
+        }
+        "get_oauth_apps" => to_json(platform.get_oauth_apps().await?),
+        "delete_oauth_app" => to_json(platform.delete_oauth_app(p.str("app_id")?).await?),
+        "create_access_token" => to_json(
+            platform
+                .create_access_token(p.str("user_id")?, p.str("description")?)
+                .await?,
+        ),
+        "get_access_tokens" => to_json(platform.get_access_tokens(p.str("user_id")?).await?),
+        "revoke_access_token" => to_json(platform.revoke_access_token(p.str("token_id")?).await?),
+        "create_bot" => {
+            let settings: BotSettings = p.get("settings")?;
+            to_json(platform.create_bot(&settings).await?)
+        }
+        "get_bots" => {
+            let include_disabled: Option<bool> = p.get("include_disabled")?;
+            to_json(platform.get_bots(include_disabled.unwrap_or(false)).await?)
+        }
+        "set_bot_enabled" => to_json(
+            platform
+                .set_bot_enabled(p.str("bot_user_id")?, p.get("enabled")?)
+                .await?,
+        ),
+        "assign_bot" => to_json(
+            platform
+                .assign_bot(p.str("bot_user_id")?, p.str("owner_id")?)
+                .await?,
+        ),
+        "convert_user_to_bot" => to_json(platform.convert_user_to_bot(p.str("user_id")?).await?),
+        "convert_bot_to_user" => to_json(
+            platform
+                .convert_bot_to_user(p.str("bot_user_id")?, p.str("password")?)
+                .await?,
+        ),
+
+        _ => Err(Error::unsupported(format!("Unknown method: {method}"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    use crate::platforms::mattermost::MattermostPlatform;
+
+    async fn request(
+        lines: &mut tokio::io::Lines<BufReader<tokio::net::unix::OwnedReadHalf>>,
+        writer: &mut tokio::net::unix::OwnedWriteHalf,
+        line: &str,
+    ) -> Value {
+        writer
+            .write_all(format!("{line}\n").as_bytes())
+            .await
+            .unwrap();
+        let reply = lines.next_line().await.unwrap().unwrap();
+        serde_json::from_str(&reply).unwrap()
+    }
+
+    #[test]
+    fn test_params() {
+        let value = serde_json::json!({"channel_id": "ch1", "limit": 5, "user_ids": ["a"]});
+        let params = Params(&value);
+        assert_eq!(params.str("channel_id").unwrap(), "ch1");
+        assert_eq!(params.usize_or("limit", 60).unwrap(), 5);
+        assert_eq!(params.usize_or("missing", 60).unwrap(), 60);
+        assert_eq!(params.get::<Vec<String>>("user_ids").unwrap(), vec!["a"]);
+        assert_eq!(
+            params.str("text").unwrap_err().code,
+            ErrorCode::InvalidArgument
+        );
+    }
+
+    #[tokio::test]
+    async fn test_serve() {
+        let path = std::env::temp_dir().join(format!(
+            "libcommunicator-daemon-{}.sock",
+            std::process::id()
+        ));
+        let platform = MattermostPlatform::new("https://mattermost.example.com").unwrap();
+        let daemon = Daemon::new(Box::new(platform));
+        let listener = tokio::spawn(daemon.serve(path.clone()));
+
+        let mut stream = None;
+        for _ in 0..100 {
+            if let Ok(s) = UnixStream::connect(&path).await {
+                stream = Some(s);
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let (reader, mut writer) = stream.expect("daemon did not start").into_split();
+        let mut lines = BufReader::new(reader).lines();
+
+        // Bound privately and moved into place with its final permissions
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        let private_dir = format!("{}.{}.tmp", path.display(), std::process::id());
+        assert!(!Path::new(&private_dir).exists());
+
+        let reply = request(&mut lines, &mut writer, r#"{"id": 1, "method": "ping"}"#).await;
+        assert_eq!(reply, serde_json::json!({"id": 1, "result": "pong"}));
+
+        let reply = request(
+            &mut lines,
+            &mut writer,
+            r#"{"id": "a", "method": "connection_info"}"#,
+        )
+        .await;
+        assert_eq!(reply, serde_json::json!({"id": "a", "result": null}));
+
+        let reply = request(&mut lines, &mut writer, r#"{"id": 2, "method": "nope"}"#).await;
+        assert_eq!(reply["error"]["code"], ErrorCode::Unsupported as i32);
+
+        let reply = request(&mut lines, &mut writer, "not json").await;
+        assert_eq!(reply["error"]["code"], ErrorCode::InvalidArgument as i32);
+
+        listener.abort();
+        let _ = std::fs::remove_file(&path);
+    }
+}
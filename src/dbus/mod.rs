@@ -0,0 +1,210 @@
+//! D-Bus integration (Linux, `dbus` feature)
+//!
+//! Publishes mentions and direct messages to the desktop through the
+//! `org.freedesktop.Notifications` service on the session bus, and exports a
+//! platform connection on the bus with [`DbusService`], so lightweight
+//! integrations can send and read messages without linking the library.
+//!
+//! Whether a message notifies is decided by the user's mention rules, as for
+//! `notification_triggered` events (see `crate::notifications`). Notifiers are
+//! set on a platform's event pipeline (see `crate::pipeline`), like event
+//! webhooks.
+
+mod service;
+
+pub use service::{DbusService, DEFAULT_BUS_NAME, INTERFACE_NAME, OBJECT_PATH};
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tokio::sync::OnceCell;
+
+use crate::error::{Error, ErrorCode, Result};
+use crate::notifications;
+use crate::platforms::{Platform, PlatformEvent};
+use crate::types::{MentionRules, Message};
+
+/// Application name reported to the notification server
+pub const DEFAULT_APP_NAME: &str = "libcommunicator";
+
+/// A desktop notification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    /// Notification title (e.g. the sender)
+    pub summary: String,
+    /// Notification text (e.g. the message)
+    pub body: String,
+}
+
+/// Publishes mention and DM notifications for a user
+#[derive(Debug, Clone)]
+pub struct DesktopNotifier {
+    /// Mention rules of the connected user
+    rules: MentionRules,
+    app_name: String,
+    /// Session bus connection, opened with the first notification
+    connection: Arc<OnceCell<zbus::Connection>>,
+}
+
+impl DesktopNotifier {
+    /// Create a notifier for the given user
+    ///
+    /// # Arguments
+    /// * `rules` - The mention rules of the connected user
+    ///   (see `Platform::get_mention_rules`)
+    pub fn new(rules: MentionRules) -> Self {
+        Self {
+            rules,
+            app_name: DEFAULT_APP_NAME.to_string(),
+            connection: Arc::default(),
+        }
+    }
+
+    /// Set the application name shown by the notification server
+    pub fn with_app_name(mut self, app_name: impl Into<String>) -> Self {
+        self.app_name = app_name.into();
+        self
+    }
+
+    /// Build the notification for a new message, if it warrants one
+    ///
+    /// Only messages from other users that are direct messages or that
+    /// mention the user produce a notification.
+    ///
+    /// # Arguments
+    /// * `message` - The posted message
+    /// * `is_direct` - Whether the message was posted in a direct message channel
+    pub fn notification_for(&self, message: &Message, is_direct: bool) -> Option<Notification> {
+        notifications::evaluate(&self.rules, message, is_direct)?;
+
+        let metadata = message.metadata.as_ref();
+        let sender = metadata
+            .and_then(|m| m.get("sender_name"))
+            .and_then(|s| s.as_str())
+            .unwrap_or(&message.sender_id);
+
+        let summary = match metadata
+            .and_then(|m| m.get("channel_display_name"))
+            .and_then(|c| c.as_str())
+        {
+            Some(channel) if !is_direct => format!("{sender} in {channel}"),
+            _ => sender.to_string(),
+        };
+
+        Some(Notification {
+            summary,
+            body: message.text.clone(),
+        })
+    }
+
+    /// Send a notification to org.freedesktop.Notifications on the session bus
+    ///
+    /// # Returns
+    /// The ID the notification server assigned to the notification
+    pub async fn send(&self, notification: &Notification) -> Result<u32> {
+        let connection = self
+            .connection
+            .get_or_try_init(zbus::Connection::session)
+            .await
+            .map_err(bus_error)?;
+
+        let actions: Vec<&str> = Vec::new();
+        let hints: HashMap<&str, zbus::zvariant::Value> = HashMap::new();
+        let reply = connection
+            .call_method(
+                Some("org.freedesktop.Notifications"),
+                "/org/freedesktop/Notifications",
+                Some("org.freedesktop.Notifications"),
+                "Notify",
+                &(
+                    self.app_name.as_str(),
+                    // replaces_id
+                    0u32,
+                    // app_icon
+                    "",
+                    notification.summary.as_str(),
+                    notification.body.as_str(),
+                    actions,
+                    hints,
+                    // expire_timeout (server default)
+                    -1i32,
+                ),
+            )
+            .await
+            .map_err(bus_error)?;
+        reply.body().deserialize().map_err(bus_error)
+    }
+
+    /// Publish a notification for an event in the background, if it warrants one
+    ///
+    /// Must be called within a Tokio runtime.
+    pub(crate) async fn dispatch(self: &Arc<Self>, platform: &dyn Platform, event: &PlatformEvent) {
+        let PlatformEvent::MessagePosted(message) = event else {
+            return;
+        };
+        // Spare the channel lookup for the user's own messages
+        if message.sender_id == self.rules.user_id {
+            return;
+        }
+
+        let is_direct = notifications::is_direct(platform, &message.channel_id).await;
+        if let Some(notification) = self.notification_for(message, is_direct) {
+            let notifier = Arc::clone(self);
+            // Best-effort; a missing notification server must not affect polling
+            tokio::spawn(async move {
+                let _ = notifier.send(&notification).await;
+            });
+        }
+    }
+}
+
+/// Convert a D-Bus failure into an error
+pub(crate) fn bus_error(e: impl std::fmt::Display) -> Error {
+    Error::new(ErrorCode::NetworkError, format!("D-Bus call failed: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(sender: &str, text: &str) -> Message {
+        Message::new("msg-1", text, sender, "ch-1").with_metadata(serde_json::json!({
+            "channel_display_name": "Town Square",
+            "sender_name": "@jay"
+        }))
+    }
+
+    fn notifier() -> DesktopNotifier {
+        DesktopNotifier::new(MentionRules::new("me", "alice"))
+    }
+
+    #[test]
+    fn test_direct_message_notifies() {
+        assert_eq!(
+            notifier().notification_for(&message("other", "hello there"), true),
+            Some(Notification {
+                summary: "@jay".to_string(),
+                body: "hello there".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_mention_notifies() {
+        let notification = notifier()
+            .notification_for(&message("other", "hey @alice"), false)
+            .unwrap();
+        assert_eq!(notification.summary, "@jay in Town Square");
+    }
+
+    #[test]
+    fn test_other_messages_do_not_notify() {
+        let notifier = notifier();
+        assert!(notifier
+            .notification_for(&message("other", "hey @bob"), false)
+            .is_none());
+        assert!(notifier
+            .notification_for(&message("me", "hello"), true)
+            .is_none());
+    }
+}
@@ -0,0 +1,345 @@
+//! D-Bus service mirroring the send and read APIs
+//!
+//! [`DbusService`] exports one platform connection on a bus (the session bus
+//! by default), so scripts and small desktop integrations can use it with
+//! `busctl`, `gdbus` or any D-Bus binding instead of linking the library. The
+//! object [`OBJECT_PATH`] implements the [`INTERFACE_NAME`] interface:
+//!
+//! | Method | Arguments | Returns |
+//! |--------|-----------|---------|
+//! | `SendMessage` | channel ID, text | the sent message |
+//! | `SendReply` | channel ID, text, root message ID | the sent reply |
+//! | `GetChannels` | | the user's channels |
+//! | `GetChannel` | channel ID | the channel |
+//! | `GetMessages` | channel ID, limit | the most recent messages |
+//! | `GetMessage` | message ID | the message |
+//! | `GetChannelUnread` | channel ID | the channel's unread counts |
+//! | `MarkChannelRead` | channel ID | nothing |
+//! | `GetUser` | user ID | the user |
+//! | `GetCurrentUser` | | the connected user |
+//!
+//! Arguments are strings except the limit (`u`); results are JSON strings in
+//! the format of the C API. Every event of the connection is emitted as the
+//! `Event` signal, carrying the event in the JSON format of
+//! `communicator_platform_poll_event`. Failures are returned as the closest
+//! `org.freedesktop.DBus.Error` (e.g. `NotSupported`, `InvalidArgs`) with the
+//! library's error message.
+//!
+//! The program serving the connection connects it before serving; the bus
+//! only gives access to processes of the same user. Like the daemon, the
+//! service runs events through the connection's [`Pipeline`] before
+//! emitting them.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::sync::RwLock;
+use zbus::fdo;
+use zbus::object_server::SignalEmitter;
+
+use super::bus_error;
+use crate::error::{Error, ErrorCode, Result};
+use crate::event_json::event_to_json;
+use crate::pipeline::{Pipeline, PipelinePlatform, Polled};
+use crate::platforms::Platform;
+
+/// Well-known name the service owns unless another one is set
+pub const DEFAULT_BUS_NAME: &str = "org.libcommunicator.Communicator";
+
+/// Path of the exported platform object
+pub const OBJECT_PATH: &str = "/org/libcommunicator/Platform";
+
+/// Interface of the exported platform object
+pub const INTERFACE_NAME: &str = "org.libcommunicator.Platform1";
+
+/// How often events are polled while no event is pending
+const EVENT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Map an error to the closest standard D-Bus error
+fn to_bus_error(e: Error) -> fdo::Error {
+    let message = e.message;
+    match e.code {
+        ErrorCode::InvalidArgument
+        | ErrorCode::NullPointer
+        | ErrorCode::InvalidUtf8
+        | ErrorCode::MessageTooLong
+        | ErrorCode::FileTooLarge => fdo::Error::InvalidArgs(message),
+        ErrorCode::OutOfMemory => fdo::Error::NoMemory(message),
+        ErrorCode::NetworkError | ErrorCode::ServiceUnavailable => fdo::Error::NoNetwork(message),
+        ErrorCode::AuthenticationFailed => fdo::Error::AuthFailed(message),
+        ErrorCode::PermissionDenied => fdo::Error::AccessDenied(message),
+        ErrorCode::Timeout => fdo::Error::TimedOut(message),
+        ErrorCode::Unsupported => fdo::Error::NotSupported(message),
+        ErrorCode::RateLimited => fdo::Error::LimitsExceeded(message),
+        _ => fdo::Error::Failed(message),
+    }
+}
+
+/// Serialize the result of a platform call for a method reply
+fn reply<T: Serialize>(outcome: Result<T>) -> fdo::Result<String> {
+    let value = outcome.map_err(to_bus_error)?;
+    serde_json::to_string(&value)
+        .map_err(|e| fdo::Error::Failed(format!("Failed to serialize result: {e}")))
+}
+
+/// The exported platform object
+struct PlatformObject {
+    platform: Arc<RwLock<PipelinePlatform>>,
+}
+
+#[zbus::interface(name = "org.libcommunicator.Platform1")]
+impl PlatformObject {
+    async fn send_message(&self, channel_id: &str, text: &str) -> fdo::Result<String> {
+        let platform = self.platform.read().await;
+        reply(platform.send_message(channel_id, text).await)
+    }
+
+    async fn send_reply(&self, channel_id: &str, text: &str, root_id: &str) -> fdo::Result<String> {
+        let platform = self.platform.read().await;
+        reply(platform.send_reply(channel_id, text, root_id).await)
+    }
+
+    async fn get_channels(&self) -> fdo::Result<String> {
+        let platform = self.platform.read().await;
+        reply(platform.get_channels().await)
+    }
+
+    async fn get_channel(&self, channel_id: &str) -> fdo::Result<String> {
+        let platform = self.platform.read().await;
+        reply(platform.get_channel(channel_id).await)
+    }
+
+    async fn get_messages(&self, channel_id: &str, limit: u32) -> fdo::Result<String> {
+        let platform = self.platform.read().await;
+        reply(platform.get_messages(channel_id, limit as usize).await)
+    }
+
+    async fn get_message(&self, message_id: &str) -> fdo::Result<String> {
+        let platform = self.platform.read().await;
+        reply(platform.get_message(message_id).await)
+    }
+
+    async fn get_channel_unread(&self, channel_id: &str) -> fdo::Result<String> {
+        let platform = self.platform.read().await;
+        reply(platform.get_channel_unread(channel_id).await)
+    }
+
+    async fn mark_channel_read(&self, channel_id: &str) -> fdo::Result<()> {
+        let platform = self.platform.read().await;
+        platform
+            .mark_channel_read(channel_id)
+            .await
+            .map_err(to_bus_error)
+    }
+
+    async fn get_user(&self, user_id: &str) -> fdo::Result<String> {
+        let platform = self.platform.read().await;
+        reply(platform.get_user(user_id).await)
+    }
+
+    async fn get_current_user(&self) -> fdo::Result<String> {
+        let platform = self.platform.read().await;
+        reply(platform.get_current_user().await)
+    }
+
+    /// A platform event, as JSON
+    #[zbus(signal)]
+    async fn event(emitter: &SignalEmitter<'_>, event: &str) -> zbus::Result<()>;
+}
+
+/// Serves one platform connection on D-Bus
+pub struct DbusService {
+    platform: Arc<RwLock<PipelinePlatform>>,
+    pipeline: Arc<Pipeline>,
+    bus_name: String,
+}
+
+impl DbusService {
+    /// Create a service for the given platform, which must be connected
+    /// before serving
+    pub fn new(platform: Box<dyn Platform>) -> Self {
+        let platform = PipelinePlatform::new(platform);
+        DbusService {
+            pipeline: Arc::clone(platform.pipeline()),
+            platform: Arc::new(RwLock::new(platform)),
+            bus_name: DEFAULT_BUS_NAME.to_string(),
+        }
+    }
+
+    /// Set the well-known name to own, e.g. to serve several connections
+    pub fn with_bus_name(mut self, bus_name: impl Into<String>) -> Self {
+        self.bus_name = bus_name.into();
+        self
+    }
+
+    /// The event pipeline of the served connection, to configure its stages
+    pub fn pipeline(&self) -> &Pipeline {
+        &self.pipeline
+    }
+
+    /// Export the platform on a bus and emit its events until the bus
+    /// connection fails
+    ///
+    /// # Arguments
+    /// * `address` - Address of the bus to serve on, or None for the session bus
+    pub async fn serve(self, address: Option<&str>) -> Result<()> {
+        let builder = match address {
+            Some(address) => zbus::connection::Builder::address(address),
+            None => zbus::connection::Builder::session(),
+        }
+        .map_err(bus_error)?;
+        let object = PlatformObject {
+            platform: Arc::clone(&self.platform),
+        };
+        let connection = builder
+            .name(self.bus_name.as_str())
+            .and_then(|builder| builder.serve_at(OBJECT_PATH, object))
+            .map_err(bus_error)?
+            .build()
+            .await
+            .map_err(bus_error)?;
+        let emitter = SignalEmitter::new(&connection, OBJECT_PATH).map_err(bus_error)?;
+
+        self.platform.write().await.subscribe_events().await?;
+        loop {
+            match PipelinePlatform::next_event_shared(&self.platform).await {
+                Ok(Polled::Event(event)) => {
+                    let json = event_to_json(&event).to_string();
+                    PlatformObject::event(&emitter, &json)
+                        .await
+                        .map_err(bus_error)?;
+                }
+                Ok(Polled::Skipped) => {}
+                Ok(Polled::Empty) | Err(_) => tokio::time::sleep(EVENT_POLL_INTERVAL).await,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+    use std::process::{Child, Command, Stdio};
+
+    use futures::StreamExt;
+
+    use crate::pipeline::test_platform::{posted, QueuedPlatform};
+
+    /// A private bus, stopped when dropped
+    struct PrivateBus(Child);
+
+    impl PrivateBus {
+        /// Start a bus, or None if dbus-daemon is not installed
+        fn start() -> Option<(Self, String)> {
+            let mut child = Command::new("dbus-daemon")
+                .args(["--session", "--nofork", "--print-address"])
+                .stdout(Stdio::piped())
+                .stderr(Stdio::null())
+                .spawn()
+                .ok()?;
+            let mut address = String::new();
+            BufReader::new(child.stdout.take()?)
+                .read_line(&mut address)
+                .ok()?;
+            Some((PrivateBus(child), address.trim().to_string()))
+        }
+    }
+
+    impl Drop for PrivateBus {
+        fn drop(&mut self) {
+            let _ = self.0.kill();
+            let _ = self.0.wait();
+        }
+    }
+
+    async fn call<B>(
+        client: &zbus::Connection,
+        method: &str,
+        body: &B,
+    ) -> zbus::Result<zbus::Message>
+    where
+        B: Serialize + zbus::zvariant::DynamicType,
+    {
+        client
+            .call_method(
+                Some(DEFAULT_BUS_NAME),
+                OBJECT_PATH,
+                Some(INTERFACE_NAME),
+                method,
+                body,
+            )
+            .await
+    }
+
+    #[test]
+    fn test_error_mapping() {
+        assert!(matches!(
+            to_bus_error(Error::unsupported("nope")),
+            fdo::Error::NotSupported(message) if message == "nope"
+        ));
+        assert!(matches!(
+            to_bus_error(Error::new(ErrorCode::NotFound, "gone")),
+            fdo::Error::Failed(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_serve() {
+        let Some((_bus, address)) = PrivateBus::start() else {
+            return;
+        };
+        let client = zbus::connection::Builder::address(address.as_str())
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+        let rule = zbus::MatchRule::builder()
+            .msg_type(zbus::message::Type::Signal)
+            .interface(INTERFACE_NAME)
+            .unwrap()
+            .member("Event")
+            .unwrap()
+            .build();
+        let mut events = zbus::MessageStream::for_match_rule(rule, &client, None)
+            .await
+            .unwrap();
+
+        let service = DbusService::new(Box::new(QueuedPlatform::new([posted("m1")])));
+        let server = tokio::spawn(async move { service.serve(Some(&address)).await });
+
+        // Wait for the service to own its name
+        let mut sent = call(&client, "SendMessage", &("ch-1", "hello")).await;
+        for _ in 0..100 {
+            if sent.is_ok() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            sent = call(&client, "SendMessage", &("ch-1", "hello")).await;
+        }
+        let sent: String = sent.unwrap().body().deserialize().unwrap();
+        let sent: serde_json::Value = serde_json::from_str(&sent).unwrap();
+        assert_eq!(sent["text"], "hello");
+        assert_eq!(sent["channel_id"], "ch-1");
+
+        let error = call(&client, "GetChannels", &()).await.unwrap_err();
+        assert!(
+            matches!(&error, zbus::Error::MethodError(name, _, _)
+                if name.as_str() == "org.freedesktop.DBus.Error.NotSupported"),
+            "expected NotSupported, got {error:?}"
+        );
+
+        let signal = tokio::time::timeout(Duration::from_secs(5), events.next())
+            .await
+            .expect("no event signal")
+            .unwrap()
+            .unwrap();
+        let event: String = signal.body().deserialize().unwrap();
+        let event: serde_json::Value = serde_json::from_str(&event).unwrap();
+        assert_eq!(event["type"], "message_posted");
+        assert_eq!(event["data"]["id"], "m1");
+
+        server.abort();
+    }
+}
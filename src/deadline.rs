@@ -0,0 +1,95 @@
+//! Deadlines for composite operations
+//!
+//! An operation like connecting (authentication, user and limits lookups) or
+//! listing channels (with partner lookups for direct messages) makes several
+//! requests. Instead of each request getting its own timeout, the whole
+//! operation can run under one deadline: every request made within
+//! [`with_deadline`] is cut short at the deadline, and requests started after
+//! it fail right away with `ErrorCode::Timeout`.
+//!
+//! The deadline is carried in a task-local, so it reaches every request made
+//! by the operation without being passed around. Background tasks spawned by
+//! the operation are not bound by it.
+
+use std::future::Future;
+use std::time::Duration;
+use tokio::time::Instant;
+
+use crate::error::{Error, ErrorCode, Result};
+
+tokio::task_local! {
+    static DEADLINE: Instant;
+}
+
+/// Run an operation under a deadline
+///
+/// A deadline set by an enclosing scope still applies if it is earlier.
+pub async fn with_deadline<F: Future>(deadline: Instant, operation: F) -> F::Output {
+    let deadline = current().map_or(deadline, |outer| outer.min(deadline));
+    DEADLINE.scope(deadline, operation).await
+}
+
+/// Run an operation under a deadline the given time from now
+pub async fn with_timeout<F: Future>(timeout: Duration, operation: F) -> F::Output {
+    with_deadline(Instant::now() + timeout, operation).await
+}
+
+/// The deadline of the current operation, if any
+pub fn current() -> Option<Instant> {
+    DEADLINE.try_with(|deadline| *deadline).ok()
+}
+
+/// Time left until the deadline of the current operation
+///
+/// # Returns
+/// None without a deadline, or an error if the deadline has passed
+pub fn remaining() -> Result<Option<Duration>> {
+    let Some(deadline) = current() else {
+        return Ok(None);
+    };
+    match deadline.checked_duration_since(Instant::now()) {
+        Some(remaining) if !remaining.is_zero() => Ok(Some(remaining)),
+        _ => Err(expired()),
+    }
+}
+
+/// The error for an operation that ran past its deadline
+pub fn expired() -> Error {
+    Error::new(ErrorCode::Timeout, "Deadline exceeded")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_no_deadline() {
+        assert!(current().is_none());
+        assert_eq!(remaining().unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_nested_deadlines_keep_the_earliest() {
+        with_timeout(Duration::from_secs(1), async {
+            let outer = current().unwrap();
+            with_timeout(Duration::from_secs(60), async {
+                assert_eq!(current(), Some(outer));
+            })
+            .await;
+            with_timeout(Duration::from_millis(10), async {
+                assert!(current().unwrap() < outer);
+            })
+            .await;
+            assert!(remaining().unwrap().unwrap() <= Duration::from_secs(1));
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_expired_deadline() {
+        with_timeout(Duration::ZERO, async {
+            assert_eq!(remaining().unwrap_err().code, ErrorCode::Timeout);
+        })
+        .await;
+    }
+}
@@ -0,0 +1,201 @@
+//! In-process DNS resolution
+//!
+//! Host names are resolved through the system resolver and cached for the
+//! whole process, so reconnects and the HTTP and WebSocket transports don't
+//! repeat lookups. The system resolver doesn't report record TTLs, so cached
+//! answers expire after [`CACHE_TTL`]; failed lookups are not cached.
+//!
+//! A [`Resolver`] can also carry a hosts-override table mapping host names to
+//! fixed addresses, e.g. to point a production host name at a staging server.
+//! Overridden names never reach the system resolver.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+
+use crate::error::{Error, ErrorCode, Result};
+
+/// How long a resolved address stays cached
+pub const CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Cached lookups: lowercase host name -> (addresses, expiry)
+type Cache = HashMap<String, (Vec<IpAddr>, Instant)>;
+
+fn cache() -> &'static Mutex<Cache> {
+    static CACHE: OnceLock<Mutex<Cache>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// A caching resolver with a hosts-override table
+#[derive(Debug, Clone, Default)]
+pub struct Resolver {
+    overrides: Arc<HashMap<String, IpAddr>>,
+}
+
+impl Resolver {
+    /// Create a resolver
+    ///
+    /// # Arguments
+    /// * `hosts` - Host names mapped to the IP address to use instead of DNS
+    ///
+    /// # Returns
+    /// A Result containing the resolver or an Error if an address is invalid
+    pub fn new(hosts: &HashMap<String, String>) -> Result<Self> {
+        let overrides = hosts
+            .iter()
+            .map(|(host, addr)| {
+                let ip = addr
+                    .trim_start_matches('[')
+                    .trim_end_matches(']')
+                    .parse::<IpAddr>()
+                    .map_err(|_| {
+                        Error::invalid_argument(format!(
+                            "Invalid address '{addr}' for host '{host}'"
+                        ))
+                    })?;
+                Ok((host.to_ascii_lowercase(), ip))
+            })
+            .collect::<Result<HashMap<_, _>>>()?;
+        Ok(Self {
+            overrides: Arc::new(overrides),
+        })
+    }
+
+    /// Resolve a host name to IP addresses
+    ///
+    /// IP literals are returned as-is, overridden names from the override
+    /// table, and other names from the cache or the system resolver.
+    pub async fn lookup(&self, host: &str) -> Result<Vec<IpAddr>> {
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        if let Ok(ip) = host.parse::<IpAddr>() {
+            return Ok(vec![ip]);
+        }
+        let host = host.to_ascii_lowercase();
+        if let Some(ip) = self.overrides.get(&host) {
+            return Ok(vec![*ip]);
+        }
+
+        if let Some(addrs) = cached(&host, Instant::now()) {
+            return Ok(addrs);
+        }
+        let addrs: Vec<IpAddr> = tokio::net::lookup_host((host.as_str(), 0))
+            .await
+            .map_err(|e| {
+                Error::new(
+                    ErrorCode::NetworkError,
+                    format!("Failed to resolve {host}: {e}"),
+                )
+            })?
+            .map(|addr| addr.ip())
+            .collect();
+        if addrs.is_empty() {
+            return Err(Error::new(
+                ErrorCode::NetworkError,
+                format!("No addresses found for {host}"),
+            ));
+        }
+        cache()
+            .lock()
+            .unwrap()
+            .insert(host, (addrs.clone(), Instant::now() + CACHE_TTL));
+        Ok(addrs)
+    }
+
+    /// Open a TCP connection to a host, trying each of its addresses in turn
+    pub async fn connect(&self, host: &str, port: u16) -> Result<TcpStream> {
+        let mut last_error = None;
+        for ip in self.lookup(host).await? {
+            match TcpStream::connect(SocketAddr::new(ip, port)).await {
+                Ok(stream) => return Ok(stream),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(Error::new(
+            ErrorCode::NetworkError,
+            format!(
+                "Failed to connect to {host}:{port}: {}",
+                last_error.map_or_else(|| "no addresses".to_string(), |e| e.to_string())
+            ),
+        ))
+    }
+}
+
+/// The cached addresses of a host, dropping the entry if it has expired
+fn cached(host: &str, now: Instant) -> Option<Vec<IpAddr>> {
+    let mut cache = cache().lock().unwrap();
+    match cache.get(host) {
+        Some((addrs, expires)) if *expires > now => Some(addrs.clone()),
+        Some(_) => {
+            cache.remove(host);
+            None
+        }
+        None => None,
+    }
+}
+
+impl reqwest::dns::Resolve for Resolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let resolver = self.clone();
+        Box::pin(async move {
+            let addrs = resolver.lookup(name.as_str()).await?;
+            let addrs: reqwest::dns::Addrs =
+                Box::new(addrs.into_iter().map(|ip| SocketAddr::new(ip, 0)));
+            Ok(addrs)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolver_with(hosts: &[(&str, &str)]) -> Result<Resolver> {
+        Resolver::new(
+            &hosts
+                .iter()
+                .map(|(host, addr)| (host.to_string(), addr.to_string()))
+                .collect(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_overrides_and_literals() {
+        let resolver = resolver_with(&[
+            ("Chat.Example.com", "10.0.0.5"),
+            ("v6.example.com", "[::1]"),
+        ])
+        .unwrap();
+        assert_eq!(
+            resolver.lookup("chat.example.COM").await.unwrap(),
+            vec!["10.0.0.5".parse::<IpAddr>().unwrap()]
+        );
+        assert_eq!(
+            resolver.lookup("v6.example.com").await.unwrap(),
+            vec!["::1".parse::<IpAddr>().unwrap()]
+        );
+        assert_eq!(
+            resolver.lookup("[fe80::1]").await.unwrap(),
+            vec!["fe80::1".parse::<IpAddr>().unwrap()]
+        );
+        assert!(resolver_with(&[("chat.example.com", "not-an-ip")]).is_err());
+    }
+
+    #[test]
+    fn test_cache_expiry() {
+        let now = Instant::now();
+        let ip: IpAddr = "192.0.2.1".parse().unwrap();
+        cache().lock().unwrap().insert(
+            "cache-test.example".to_string(),
+            (vec![ip], now + Duration::from_secs(5)),
+        );
+
+        assert_eq!(cached("cache-test.example", now), Some(vec![ip]));
+        assert_eq!(
+            cached("cache-test.example", now + Duration::from_secs(10)),
+            None
+        );
+        assert!(!cache().lock().unwrap().contains_key("cache-test.example"));
+    }
+}
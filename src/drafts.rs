@@ -0,0 +1,136 @@
+//! Message drafts
+//!
+//! Drafts are saved on the server where the platform syncs them across devices
+//! (`Platform::save_draft` and friends). Where it does not, or the server has
+//! drafts turned off, they are kept by the library instead, by the platform's
+//! event pipeline (see `crate::pipeline`) and for the lifetime of the platform. Either way a channel (or thread) has at
+//! most one draft; saving replaces it.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use chrono::Utc;
+
+use crate::error::{ErrorCode, Result};
+use crate::platforms::Platform;
+use crate::types::Draft;
+
+/// Drafts kept by the library
+#[derive(Debug, Default)]
+pub struct DraftStore {
+    /// Drafts keyed by channel ID and root ID ("" for channel drafts)
+    drafts: Mutex<HashMap<(String, String), Draft>>,
+}
+
+impl DraftStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Save a draft, replacing the draft of the same channel and thread
+    pub fn save(&self, mut draft: Draft) -> Draft {
+        draft.updated_at = Utc::now();
+        draft.server_side = false;
+        let key = (
+            draft.channel_id.clone(),
+            draft.root_id.clone().unwrap_or_default(),
+        );
+        self.drafts.lock().unwrap().insert(key, draft.clone());
+        draft
+    }
+
+    /// Get the draft of a channel or thread
+    pub fn get(&self, channel_id: &str, root_id: Option<&str>) -> Option<Draft> {
+        let key = (channel_id.to_string(), root_id.unwrap_or("").to_string());
+        self.drafts.lock().unwrap().get(&key).cloned()
+    }
+
+    /// List all drafts, most recently saved first
+    pub fn list(&self) -> Vec<Draft> {
+        let mut drafts: Vec<Draft> = self.drafts.lock().unwrap().values().cloned().collect();
+        drafts.sort_by_key(|draft| std::cmp::Reverse(draft.updated_at));
+        drafts
+    }
+
+    /// Delete the draft of a channel or thread
+    ///
+    /// # Returns
+    /// Whether there was a draft to delete
+    pub fn remove(&self, channel_id: &str, root_id: Option<&str>) -> bool {
+        let key = (channel_id.to_string(), root_id.unwrap_or("").to_string());
+        self.drafts.lock().unwrap().remove(&key).is_some()
+    }
+}
+
+/// Save a draft on the server, or locally if the platform cannot
+pub(crate) async fn save(
+    store: &DraftStore,
+    platform: &dyn Platform,
+    draft: &Draft,
+) -> Result<Draft> {
+    match platform.save_draft(draft).await {
+        Ok(saved) => Ok(saved),
+        Err(e) if e.code == ErrorCode::Unsupported => Ok(store.save(draft.clone())),
+        Err(e) => Err(e),
+    }
+}
+
+/// List the drafts on the server, or the local ones if the platform cannot
+/// store drafts
+pub(crate) async fn list(store: &DraftStore, platform: &dyn Platform) -> Result<Vec<Draft>> {
+    match platform.get_drafts().await {
+        Ok(drafts) => Ok(drafts),
+        Err(e) if e.code == ErrorCode::Unsupported => Ok(store.list()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Get the draft of a channel or thread
+#[cfg(feature = "ffi")]
+pub(crate) async fn find(
+    platform: &dyn Platform,
+    channel_id: &str,
+    root_id: Option<&str>,
+) -> Result<Option<Draft>> {
+    let drafts = platform.get_drafts().await?;
+    Ok(drafts
+        .into_iter()
+        .find(|draft| draft.is_for(channel_id, root_id)))
+}
+
+/// Delete the draft of a channel or thread, on the server and locally
+pub(crate) async fn delete(
+    store: &DraftStore,
+    platform: &dyn Platform,
+    channel_id: &str,
+    root_id: Option<&str>,
+) -> Result<()> {
+    store.remove(channel_id, root_id);
+    match platform.delete_draft(channel_id, root_id).await {
+        Err(e) if e.code != ErrorCode::Unsupported => Err(e),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_one_draft_per_thread() {
+        let store = DraftStore::new();
+        store.save(Draft::new("ch1", "first"));
+        store.save(Draft::new("ch1", "second"));
+        store.save(Draft::new("ch1", "reply").with_root("post1"));
+
+        assert_eq!(store.list().len(), 2);
+        assert_eq!(store.get("ch1", None).unwrap().message, "second");
+        assert_eq!(store.get("ch1", Some("post1")).unwrap().message, "reply");
+
+        assert!(store.remove("ch1", Some("post1")));
+        assert!(!store.remove("ch1", Some("post1")));
+        assert!(store.get("ch1", Some("post1")).is_none());
+        assert!(store.get("ch1", None).is_some());
+    }
+}
@@ -0,0 +1,595 @@
+//! Unicode emoji shortcodes
+//!
+//! Messages refer to emojis by `:shortcode:`. Custom emojis live on the
+//! server (`Platform::get_emojis`, `Platform::get_emoji_image`); standard
+//! emojis are resolved here, so every client renders the same shortcodes the
+//! same way. The names follow the ones Mattermost and GitHub use, including
+//! common aliases such as `+1` for `thumbsup`.
+//!
+//! The table covers the commonly used emojis, not all of Unicode; unknown
+//! shortcodes are left as they are, as are custom emojis.
+
+use serde::Serialize;
+
+/// A standard emoji and the shortcode naming it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct UnicodeEmoji {
+    /// Shortcode without colons, e.g. "thumbsup"
+    pub name: &'static str,
+    /// The emoji itself
+    pub unicode: &'static str,
+}
+
+/// Shortcodes and their emojis; aliases share an emoji
+const SHORTCODES: &[(&str, &str)] = &[
+    // Smileys
+    ("grinning", "😀"),
+    ("smiley", "😃"),
+    ("smile", "😄"),
+    ("grin", "😁"),
+    ("laughing", "😆"),
+    ("satisfied", "😆"),
+    ("sweat_smile", "😅"),
+    ("rofl", "🤣"),
+    ("joy", "😂"),
+    ("slightly_smiling_face", "🙂"),
+    ("upside_down_face", "🙃"),
+    ("wink", "😉"),
+    ("blush", "😊"),
+    ("innocent", "😇"),
+    ("smiling_face_with_three_hearts", "🥰"),
+    ("heart_eyes", "😍"),
+    ("star_struck", "🤩"),
+    ("kissing_heart", "😘"),
+    ("yum", "😋"),
+    ("stuck_out_tongue", "😛"),
+    ("stuck_out_tongue_winking_eye", "😜"),
+    ("zany_face", "🤪"),
+    ("money_mouth_face", "🤑"),
+    ("hugs", "🤗"),
+    ("hugging_face", "🤗"),
+    ("thinking", "🤔"),
+    ("thinking_face", "🤔"),
+    ("shushing_face", "🤫"),
+    ("zipper_mouth_face", "🤐"),
+    ("raised_eyebrow", "🤨"),
+    ("neutral_face", "😐"),
+    ("expressionless", "😑"),
+    ("no_mouth", "😶"),
+    ("smirk", "😏"),
+    ("unamused", "😒"),
+    ("roll_eyes", "🙄"),
+    ("face_with_rolling_eyes", "🙄"),
+    ("grimacing", "😬"),
+    ("relieved", "😌"),
+    ("pensive", "😔"),
+    ("sleepy", "😪"),
+    ("sleeping", "😴"),
+    ("mask", "😷"),
+    ("face_with_thermometer", "🤒"),
+    ("nauseated_face", "🤢"),
+    ("sneezing_face", "🤧"),
+    ("hot_face", "🥵"),
+    ("cold_face", "🥶"),
+    ("dizzy_face", "😵"),
+    ("exploding_head", "🤯"),
+    ("cowboy_hat_face", "🤠"),
+    ("partying_face", "🥳"),
+    ("sunglasses", "😎"),
+    ("nerd_face", "🤓"),
+    ("confused", "😕"),
+    ("worried", "😟"),
+    ("slightly_frowning_face", "🙁"),
+    ("open_mouth", "😮"),
+    ("hushed", "😯"),
+    ("astonished", "😲"),
+    ("flushed", "😳"),
+    ("pleading_face", "🥺"),
+    ("frowning", "😦"),
+    ("fearful", "😨"),
+    ("cold_sweat", "😰"),
+    ("cry", "😢"),
+    ("sob", "😭"),
+    ("scream", "😱"),
+    ("confounded", "😖"),
+    ("persevere", "😣"),
+    ("disappointed", "😞"),
+    ("sweat", "😓"),
+    ("weary", "😩"),
+    ("tired_face", "😫"),
+    ("yawning_face", "🥱"),
+    ("triumph", "😤"),
+    ("rage", "😡"),
+    ("angry", "😠"),
+    ("cursing_face", "🤬"),
+    ("smiling_imp", "😈"),
+    ("skull", "💀"),
+    ("poop", "💩"),
+    ("hankey", "💩"),
+    ("clown_face", "🤡"),
+    ("ghost", "👻"),
+    ("alien", "👽"),
+    ("robot", "🤖"),
+    ("see_no_evil", "🙈"),
+    ("hear_no_evil", "🙉"),
+    ("speak_no_evil", "🙊"),
+    // Hearts and symbols
+    ("heart", "❤️"),
+    ("orange_heart", "🧡"),
+    ("yellow_heart", "💛"),
+    ("green_heart", "💚"),
+    ("blue_heart", "💙"),
+    ("purple_heart", "💜"),
+    ("black_heart", "🖤"),
+    ("white_heart", "🤍"),
+    ("broken_heart", "💔"),
+    ("sparkling_heart", "💖"),
+    ("two_hearts", "💕"),
+    ("100", "💯"),
+    ("anger", "💢"),
+    ("boom", "💥"),
+    ("collision", "💥"),
+    ("dizzy", "💫"),
+    ("sweat_drops", "💦"),
+    ("zzz", "💤"),
+    ("speech_balloon", "💬"),
+    ("thought_balloon", "💭"),
+    ("white_check_mark", "✅"),
+    ("heavy_check_mark", "✔️"),
+    ("ballot_box_with_check", "☑️"),
+    ("x", "❌"),
+    ("negative_squared_cross_mark", "❎"),
+    ("heavy_plus_sign", "➕"),
+    ("heavy_minus_sign", "➖"),
+    ("question", "❓"),
+    ("grey_question", "❔"),
+    ("exclamation", "❗"),
+    ("heavy_exclamation_mark", "❗"),
+    ("grey_exclamation", "❕"),
+    ("bangbang", "‼️"),
+    ("warning", "⚠️"),
+    ("no_entry", "⛔"),
+    ("no_entry_sign", "🚫"),
+    ("stop_sign", "🛑"),
+    ("recycle", "♻️"),
+    ("red_circle", "🔴"),
+    ("orange_circle", "🟠"),
+    ("yellow_circle", "🟡"),
+    ("green_circle", "🟢"),
+    ("large_blue_circle", "🔵"),
+    ("white_circle", "⚪"),
+    ("black_circle", "⚫"),
+    ("arrow_up", "⬆️"),
+    ("arrow_down", "⬇️"),
+    ("arrow_left", "⬅️"),
+    ("arrow_right", "➡️"),
+    ("arrows_counterclockwise", "🔄"),
+    ("new", "🆕"),
+    ("free", "🆓"),
+    ("sos", "🆘"),
+    ("copyright", "©️"),
+    ("registered", "®️"),
+    ("tm", "™️"),
+    // People and gestures
+    ("thumbsup", "👍"),
+    ("+1", "👍"),
+    ("thumbsdown", "👎"),
+    ("-1", "👎"),
+    ("ok_hand", "👌"),
+    ("pinched_fingers", "🤌"),
+    ("v", "✌️"),
+    ("crossed_fingers", "🤞"),
+    ("metal", "🤘"),
+    ("call_me_hand", "🤙"),
+    ("point_left", "👈"),
+    ("point_right", "👉"),
+    ("point_up", "☝️"),
+    ("point_up_2", "👆"),
+    ("point_down", "👇"),
+    ("wave", "👋"),
+    ("raised_hand", "✋"),
+    ("hand", "✋"),
+    ("raised_back_of_hand", "🤚"),
+    ("vulcan_salute", "🖖"),
+    ("fist", "✊"),
+    ("facepunch", "👊"),
+    ("punch", "👊"),
+    ("clap", "👏"),
+    ("raised_hands", "🙌"),
+    ("open_hands", "👐"),
+    ("handshake", "🤝"),
+    ("pray", "🙏"),
+    ("writing_hand", "✍️"),
+    ("muscle", "💪"),
+    ("eyes", "👀"),
+    ("eye", "👁️"),
+    ("brain", "🧠"),
+    ("facepalm", "🤦"),
+    ("shrug", "🤷"),
+    ("bow", "🙇"),
+    ("raising_hand", "🙋"),
+    ("ok_woman", "🙆"),
+    ("no_good", "🙅"),
+    ("person_running", "🏃"),
+    ("runner", "🏃"),
+    ("dancer", "💃"),
+    ("man_dancing", "🕺"),
+    // Animals and nature
+    ("dog", "🐶"),
+    ("cat", "🐱"),
+    ("mouse", "🐭"),
+    ("rabbit", "🐰"),
+    ("fox_face", "🦊"),
+    ("bear", "🐻"),
+    ("panda_face", "🐼"),
+    ("koala", "🐨"),
+    ("tiger", "🐯"),
+    ("lion", "🦁"),
+    ("cow", "🐮"),
+    ("pig", "🐷"),
+    ("frog", "🐸"),
+    ("monkey_face", "🐵"),
+    ("chicken", "🐔"),
+    ("penguin", "🐧"),
+    ("bird", "🐦"),
+    ("owl", "🦉"),
+    ("unicorn", "🦄"),
+    ("bee", "🐝"),
+    ("bug", "🐛"),
+    ("butterfly", "🦋"),
+    ("snail", "🐌"),
+    ("turtle", "🐢"),
+    ("snake", "🐍"),
+    ("octopus", "🐙"),
+    ("crab", "🦀"),
+    ("whale", "🐳"),
+    ("dolphin", "🐬"),
+    ("fish", "🐟"),
+    ("shark", "🦈"),
+    ("sloth", "🦥"),
+    ("parrot", "🦜"),
+    ("rose", "🌹"),
+    ("sunflower", "🌻"),
+    ("tulip", "🌷"),
+    ("cherry_blossom", "🌸"),
+    ("seedling", "🌱"),
+    ("evergreen_tree", "🌲"),
+    ("deciduous_tree", "🌳"),
+    ("palm_tree", "🌴"),
+    ("cactus", "🌵"),
+    ("four_leaf_clover", "🍀"),
+    ("fallen_leaf", "🍂"),
+    ("mushroom", "🍄"),
+    ("sunny", "☀️"),
+    ("cloud", "☁️"),
+    ("partly_sunny", "⛅"),
+    ("umbrella", "☔"),
+    ("zap", "⚡"),
+    ("snowflake", "❄️"),
+    ("snowman", "⛄"),
+    ("fire", "🔥"),
+    ("droplet", "💧"),
+    ("ocean", "🌊"),
+    ("rainbow", "🌈"),
+    ("star", "⭐"),
+    ("star2", "🌟"),
+    ("sparkles", "✨"),
+    ("crescent_moon", "🌙"),
+    ("earth_africa", "🌍"),
+    ("earth_americas", "🌎"),
+    ("earth_asia", "🌏"),
+    // Food and drink
+    ("apple", "🍎"),
+    ("green_apple", "🍏"),
+    ("banana", "🍌"),
+    ("grapes", "🍇"),
+    ("watermelon", "🍉"),
+    ("strawberry", "🍓"),
+    ("peach", "🍑"),
+    ("cherries", "🍒"),
+    ("lemon", "🍋"),
+    ("avocado", "🥑"),
+    ("eggplant", "🍆"),
+    ("carrot", "🥕"),
+    ("corn", "🌽"),
+    ("hot_pepper", "🌶️"),
+    ("bread", "🍞"),
+    ("cheese", "🧀"),
+    ("egg", "🥚"),
+    ("bacon", "🥓"),
+    ("hamburger", "🍔"),
+    ("fries", "🍟"),
+    ("pizza", "🍕"),
+    ("hotdog", "🌭"),
+    ("taco", "🌮"),
+    ("burrito", "🌯"),
+    ("sushi", "🍣"),
+    ("ramen", "🍜"),
+    ("spaghetti", "🍝"),
+    ("popcorn", "🍿"),
+    ("doughnut", "🍩"),
+    ("cookie", "🍪"),
+    ("cake", "🍰"),
+    ("birthday", "🎂"),
+    ("chocolate_bar", "🍫"),
+    ("candy", "🍬"),
+    ("icecream", "🍦"),
+    ("coffee", "☕"),
+    ("tea", "🍵"),
+    ("beer", "🍺"),
+    ("beers", "🍻"),
+    ("wine_glass", "🍷"),
+    ("cocktail", "🍸"),
+    ("tropical_drink", "🍹"),
+    ("champagne", "🍾"),
+    ("clinking_glasses", "🥂"),
+    // Activities and celebrations
+    ("tada", "🎉"),
+    ("confetti_ball", "🎊"),
+    ("balloon", "🎈"),
+    ("gift", "🎁"),
+    ("ribbon", "🎀"),
+    ("christmas_tree", "🎄"),
+    ("jack_o_lantern", "🎃"),
+    ("fireworks", "🎆"),
+    ("trophy", "🏆"),
+    ("medal_sports", "🏅"),
+    ("1st_place_medal", "🥇"),
+    ("2nd_place_medal", "🥈"),
+    ("3rd_place_medal", "🥉"),
+    ("soccer", "⚽"),
+    ("basketball", "🏀"),
+    ("football", "🏈"),
+    ("baseball", "⚾"),
+    ("tennis", "🎾"),
+    ("video_game", "🎮"),
+    ("game_die", "🎲"),
+    ("dart", "🎯"),
+    ("jigsaw", "🧩"),
+    ("art", "🎨"),
+    ("musical_note", "🎵"),
+    ("notes", "🎶"),
+    ("microphone", "🎤"),
+    ("headphones", "🎧"),
+    ("guitar", "🎸"),
+    // Travel and places
+    ("car", "🚗"),
+    ("taxi", "🚕"),
+    ("bus", "🚌"),
+    ("train", "🚋"),
+    ("bike", "🚲"),
+    ("airplane", "✈️"),
+    ("rocket", "🚀"),
+    ("ship", "🚢"),
+    ("house", "🏠"),
+    ("office", "🏢"),
+    ("hospital", "🏥"),
+    ("construction", "🚧"),
+    ("rotating_light", "🚨"),
+    ("vertical_traffic_light", "🚦"),
+    ("world_map", "🗺️"),
+    ("mountain", "⛰️"),
+    ("beach_umbrella", "🏖️"),
+    ("checkered_flag", "🏁"),
+    ("triangular_flag_on_post", "🚩"),
+    ("white_flag", "🏳️"),
+    ("rainbow_flag", "🏳️‍🌈"),
+    ("pirate_flag", "🏴‍☠️"),
+    // Objects
+    ("watch", "⌚"),
+    ("iphone", "📱"),
+    ("computer", "💻"),
+    ("keyboard", "⌨️"),
+    ("desktop_computer", "🖥️"),
+    ("printer", "🖨️"),
+    ("floppy_disk", "💾"),
+    ("cd", "💿"),
+    ("camera", "📷"),
+    ("movie_camera", "🎥"),
+    ("tv", "📺"),
+    ("radio", "📻"),
+    ("telephone_receiver", "📞"),
+    ("battery", "🔋"),
+    ("electric_plug", "🔌"),
+    ("bulb", "💡"),
+    ("flashlight", "🔦"),
+    ("candle", "🕯️"),
+    ("moneybag", "💰"),
+    ("dollar", "💵"),
+    ("credit_card", "💳"),
+    ("gem", "💎"),
+    ("wrench", "🔧"),
+    ("hammer", "🔨"),
+    ("hammer_and_wrench", "🛠️"),
+    ("gear", "⚙️"),
+    ("nut_and_bolt", "🔩"),
+    ("link", "🔗"),
+    ("paperclip", "📎"),
+    ("scissors", "✂️"),
+    ("lock", "🔒"),
+    ("unlock", "🔓"),
+    ("key", "🔑"),
+    ("shield", "🛡️"),
+    ("mag", "🔍"),
+    ("microscope", "🔬"),
+    ("telescope", "🔭"),
+    ("test_tube", "🧪"),
+    ("pill", "💊"),
+    ("syringe", "💉"),
+    ("bell", "🔔"),
+    ("no_bell", "🔕"),
+    ("loudspeaker", "📢"),
+    ("mega", "📣"),
+    ("hourglass", "⌛"),
+    ("hourglass_flowing_sand", "⏳"),
+    ("alarm_clock", "⏰"),
+    ("stopwatch", "⏱️"),
+    ("calendar", "📆"),
+    ("date", "📅"),
+    ("memo", "📝"),
+    ("pencil", "📝"),
+    ("pencil2", "✏️"),
+    ("pushpin", "📌"),
+    ("round_pushpin", "📍"),
+    ("bookmark", "🔖"),
+    ("label", "🏷️"),
+    ("book", "📖"),
+    ("books", "📚"),
+    ("notebook", "📓"),
+    ("newspaper", "📰"),
+    ("clipboard", "📋"),
+    ("file_folder", "📁"),
+    ("open_file_folder", "📂"),
+    ("chart_with_upwards_trend", "📈"),
+    ("chart_with_downwards_trend", "📉"),
+    ("bar_chart", "📊"),
+    ("email", "📧"),
+    ("envelope", "✉️"),
+    ("inbox_tray", "📥"),
+    ("outbox_tray", "📤"),
+    ("package", "📦"),
+    ("mailbox", "📫"),
+    ("wastebasket", "🗑️"),
+    ("crown", "👑"),
+    ("eyeglasses", "👓"),
+    ("necktie", "👔"),
+    ("shirt", "👕"),
+    ("tshirt", "👕"),
+    ("magic_wand", "🪄"),
+    ("crystal_ball", "🔮"),
+    ("teddy_bear", "🧸"),
+    ("shopping_cart", "🛒"),
+    ("smoking", "🚬"),
+    ("coffin", "⚰️"),
+];
+
+/// Characters a shortcode may contain
+fn is_shortcode_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '+' || c == '-'
+}
+
+/// Resolve a shortcode to its emoji
+///
+/// # Arguments
+/// * `shortcode` - The shortcode, with or without surrounding colons
+///
+/// # Returns
+/// The emoji, or None for unknown (e.g. custom) shortcodes
+pub fn resolve(shortcode: &str) -> Option<&'static str> {
+    let name = shortcode.trim().trim_matches(':').to_ascii_lowercase();
+    SHORTCODES
+        .iter()
+        .find(|(code, _)| *code == name)
+        .map(|(_, unicode)| *unicode)
+}
+
+/// Replace the known `:shortcodes:` in a text with their emojis
+///
+/// Shortcodes in inline code and code blocks are kept, as are unknown ones,
+/// which are left for the client to render as custom emojis.
+pub fn replace_shortcodes(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut in_code = false;
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        if c == '`' {
+            in_code = !in_code;
+        } else if c == ':' && !in_code {
+            let candidate = &rest[1..];
+            let len = candidate
+                .find(|c: char| !is_shortcode_char(c))
+                .unwrap_or(candidate.len());
+            if len > 0 && candidate[len..].starts_with(':') {
+                if let Some(unicode) = resolve(&candidate[..len]) {
+                    result.push_str(unicode);
+                    rest = &candidate[len + 1..];
+                    continue;
+                }
+            }
+        }
+        result.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    result
+}
+
+/// Suggest standard emojis for a partly typed shortcode
+///
+/// # Arguments
+/// * `prefix` - What was typed, with or without a leading colon
+/// * `limit` - Maximum number of suggestions
+///
+/// # Returns
+/// Emojis whose shortcode starts with the prefix, then those containing it,
+/// each group in alphabetical order
+pub fn autocomplete(prefix: &str, limit: usize) -> Vec<UnicodeEmoji> {
+    let needle = prefix.trim().trim_matches(':').to_ascii_lowercase();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+
+    let mut starts = Vec::new();
+    let mut contains = Vec::new();
+    for &(name, unicode) in SHORTCODES {
+        let emoji = UnicodeEmoji { name, unicode };
+        if name.starts_with(&needle) {
+            starts.push(emoji);
+        } else if name.contains(&needle) {
+            contains.push(emoji);
+        }
+    }
+    starts.sort_by_key(|emoji| emoji.name);
+    contains.sort_by_key(|emoji| emoji.name);
+    starts.into_iter().chain(contains).take(limit).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(resolve("thumbsup"), Some("👍"));
+        assert_eq!(resolve(":+1:"), Some("👍"));
+        assert_eq!(resolve("Tada"), Some("🎉"));
+        assert_eq!(resolve("party_parrot"), None);
+    }
+
+    #[test]
+    fn test_replace_shortcodes() {
+        assert_eq!(
+            replace_shortcodes("ship it :rocket: :party_parrot: at 10:30:00"),
+            "ship it 🚀 :party_parrot: at 10:30:00"
+        );
+        assert_eq!(replace_shortcodes(":+1::tada:"), "👍🎉");
+        assert_eq!(
+            replace_shortcodes("use `:smile:` for :smile:"),
+            "use `:smile:` for 😄"
+        );
+        assert_eq!(replace_shortcodes("héllo :"), "héllo :");
+    }
+
+    #[test]
+    fn test_autocomplete() {
+        let names: Vec<_> = autocomplete(":thu", 10)
+            .into_iter()
+            .map(|emoji| emoji.name)
+            .collect();
+        assert_eq!(names, vec!["thumbsdown", "thumbsup"]);
+
+        let suggestions = autocomplete("heart", 3);
+        assert_eq!(suggestions[0].name, "heart");
+        assert_eq!(suggestions.len(), 3);
+        assert!(autocomplete("", 10).is_empty());
+    }
+
+    #[test]
+    fn test_shortcodes_are_unique() {
+        let mut names: Vec<_> = SHORTCODES.iter().map(|(name, _)| *name).collect();
+        names.sort_unstable();
+        let count = names.len();
+        names.dedup();
+        assert_eq!(names.len(), count);
+    }
+}
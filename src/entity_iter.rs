@@ -0,0 +1,69 @@
+//! Iterators over cached entities
+//!
+//! Pickers and status-bar widgets enumerate the channels and users a platform
+//! already knows, often on every redraw. An iterator takes a snapshot of the
+//! platform's cache when it is created: it makes no requests, and yields the
+//! same entities in the same order however the cache changes meanwhile.
+
+use serde::Serialize;
+
+use crate::error::{Error, ErrorCode, Result};
+
+/// Snapshot of cached entities, handed out one at a time as JSON
+#[derive(Debug)]
+pub struct EntityIter {
+    items: std::vec::IntoIter<serde_json::Value>,
+}
+
+impl EntityIter {
+    /// Take a snapshot of entities
+    ///
+    /// # Arguments
+    /// * `entities` - The entities, in the order they are yielded
+    pub fn new<T: Serialize>(entities: Vec<T>) -> Result<Self> {
+        let items = entities
+            .iter()
+            .map(serde_json::to_value)
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| {
+                Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize entity: {e}"),
+                )
+            })?;
+        Ok(Self {
+            items: items.into_iter(),
+        })
+    }
+
+    /// Number of entities not yielded yet
+    pub fn remaining(&self) -> usize {
+        self.items.len()
+    }
+}
+
+impl Iterator for EntityIter {
+    type Item = serde_json::Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.items.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_yields_snapshot_in_order() {
+        let mut entities = vec!["a", "b"];
+        let mut iter = EntityIter::new(entities.clone()).unwrap();
+        entities.push("c");
+
+        assert_eq!(iter.remaining(), 2);
+        assert_eq!(iter.next(), Some(serde_json::json!("a")));
+        assert_eq!(iter.next(), Some(serde_json::json!("b")));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.remaining(), 0);
+    }
+}
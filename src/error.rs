@@ -2,9 +2,16 @@
 //!
 //! This module provides error types and FFI-compatible error handling mechanisms.
 
+#[cfg(feature = "ffi")]
+use std::cell::{Cell, RefCell};
+#[cfg(feature = "ffi")]
+use std::collections::HashMap;
 use std::fmt;
+#[cfg(feature = "ffi")]
 use std::sync::Mutex;
 
+use crate::json_input::JsonErrorLocation;
+
 /// Result type used throughout the library
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -40,6 +47,16 @@ pub enum ErrorCode {
     Unsupported = 12,
     /// Rate limit exceeded
     RateLimited = 13,
+    /// Message exceeds the server's maximum length
+    MessageTooLong = 14,
+    /// File exceeds the server's maximum upload size
+    FileTooLarge = 15,
+    /// Server is unhealthy; requests are failing fast until it recovers
+    ServiceUnavailable = 16,
+    /// Downloaded data does not match the file's checksum
+    ChecksumMismatch = 17,
+    /// Operation was cancelled through its cancellation handle
+    Cancelled = 18,
 }
 
 impl ErrorCode {
@@ -59,6 +76,11 @@ impl ErrorCode {
             ErrorCode::InvalidState => "Invalid state",
             ErrorCode::Unsupported => "Feature not supported",
             ErrorCode::RateLimited => "Rate limit exceeded",
+            ErrorCode::MessageTooLong => "Message too long",
+            ErrorCode::FileTooLarge => "File too large",
+            ErrorCode::ServiceUnavailable => "Service unavailable",
+            ErrorCode::ChecksumMismatch => "Checksum mismatch",
+            ErrorCode::Cancelled => "Operation cancelled",
         }
     }
 }
@@ -74,6 +96,10 @@ pub struct Error {
     pub(crate) request_id: Option<String>,
     /// HTTP status code if this error came from an HTTP response
     pub(crate) http_status: Option<u16>,
+    /// The limit that was exceeded (characters or bytes), for size limit errors
+    pub(crate) limit: Option<u64>,
+    /// Where JSON input failed to parse, for errors from parsing caller JSON
+    pub(crate) json_location: Option<Box<JsonErrorLocation>>,
 }
 
 impl Error {
@@ -84,6 +110,8 @@ impl Error {
             mattermost_error_id: None,
             request_id: None,
             http_status: None,
+            limit: None,
+            json_location: None,
         }
     }
 
@@ -121,6 +149,18 @@ impl Error {
         self
     }
 
+    /// Add the limit that was exceeded (builder pattern)
+    pub fn with_limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Add where JSON input failed to parse (builder pattern)
+    pub fn with_json_location(mut self, location: JsonErrorLocation) -> Self {
+        self.json_location = Some(Box::new(location));
+        self
+    }
+
     /// Get the Mattermost error ID if available
     pub fn mattermost_error_id(&self) -> Option<&str> {
         self.mattermost_error_id.as_deref()
@@ -135,6 +175,16 @@ impl Error {
     pub fn http_status(&self) -> Option<u16> {
         self.http_status
     }
+
+    /// Get the exceeded limit if available
+    pub fn limit(&self) -> Option<u64> {
+        self.limit
+    }
+
+    /// Get where JSON input failed to parse if available
+    pub fn json_location(&self) -> Option<&JsonErrorLocation> {
+        self.json_location.as_deref()
+    }
 }
 
 impl fmt::Display for Error {
@@ -145,28 +195,71 @@ impl fmt::Display for Error {
 
 impl std::error::Error for Error {}
 
-// Thread-local error storage for FFI
+// Error storage for FFI
+//
+// The last error is kept per thread, so concurrent calls on different threads
+// don't overwrite each other's errors. Calls on a platform handle also record
+// their error for the handle, where it stays until the next failing call on
+// the handle or until it is cleared.
+#[cfg(feature = "ffi")]
+thread_local! {
+    static LAST_ERROR: RefCell<Option<Error>> = const { RefCell::new(None) };
+    /// Platform handle of the FFI call running on this thread, if any
+    static CURRENT_HANDLE: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+#[cfg(feature = "ffi")]
 lazy_static::lazy_static! {
-    static ref LAST_ERROR: Mutex<Option<Error>> = Mutex::new(None);
+    /// Last error of each platform handle, keyed by handle address
+    static ref HANDLE_ERRORS: Mutex<HashMap<usize, Error>> = Mutex::new(HashMap::new());
 }
 
 /// Set the last error (called internally when FFI functions fail)
+#[cfg(feature = "ffi")]
 pub(crate) fn set_last_error(error: Error) {
-    if let Ok(mut last) = LAST_ERROR.lock() {
-        *last = Some(error);
+    if let Some(handle_key) = CURRENT_HANDLE.with(Cell::get) {
+        if let Ok(mut errors) = HANDLE_ERRORS.lock() {
+            errors.insert(handle_key, error.clone());
+        }
     }
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(error));
 }
 
 /// Clear the last error
+#[cfg(feature = "ffi")]
 pub(crate) fn clear_last_error() {
-    if let Ok(mut last) = LAST_ERROR.lock() {
-        *last = None;
-    }
+    CURRENT_HANDLE.with(|current| current.set(None));
+    LAST_ERROR.with(|last| *last.borrow_mut() = None);
+}
+
+/// Clear the last error at the start of an FFI call on a platform handle
+///
+/// Errors set during the call are also recorded for the handle.
+#[cfg(feature = "ffi")]
+pub(crate) fn begin_call(handle_key: usize) {
+    clear_last_error();
+    // Null handles fail before doing anything worth attributing
+    CURRENT_HANDLE.with(|current| current.set((handle_key != 0).then_some(handle_key)));
 }
 
 /// Get the last error (for FFI)
+#[cfg(feature = "ffi")]
 pub(crate) fn get_last_error() -> Option<Error> {
-    LAST_ERROR.lock().ok()?.clone()
+    LAST_ERROR.with(|last| last.borrow().clone())
+}
+
+/// Get the last error of a platform handle
+#[cfg(feature = "ffi")]
+pub(crate) fn get_handle_error(handle_key: usize) -> Option<Error> {
+    HANDLE_ERRORS.lock().ok()?.get(&handle_key).cloned()
+}
+
+/// Forget the last error of a platform handle
+#[cfg(feature = "ffi")]
+pub(crate) fn clear_handle_error(handle_key: usize) {
+    if let Ok(mut errors) = HANDLE_ERRORS.lock() {
+        errors.remove(&handle_key);
+    }
 }
 
 #[cfg(test)]
@@ -180,6 +273,7 @@ mod tests {
         assert_eq!(err.message, "Connection failed");
     }
 
+    #[cfg(feature = "ffi")]
     #[test]
     fn test_error_storage() {
         clear_last_error();
@@ -191,6 +285,38 @@ mod tests {
         let retrieved = get_last_error();
         assert!(retrieved.is_some());
         assert_eq!(retrieved.unwrap().code, ErrorCode::InvalidArgument);
+
+        // Other threads have their own last error
+        std::thread::spawn(|| assert!(get_last_error().is_none()))
+            .join()
+            .unwrap();
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_handle_errors_are_sticky() {
+        let handle_key = 0x4009;
+        begin_call(handle_key);
+        set_last_error(Error::new(ErrorCode::NotFound, "Channel not found"));
+
+        // A later successful call on the handle keeps its error
+        begin_call(handle_key);
+        assert!(get_last_error().is_none());
+        assert_eq!(
+            get_handle_error(handle_key).unwrap().code,
+            ErrorCode::NotFound
+        );
+
+        // Errors outside of handle calls are not attributed to the handle
+        clear_last_error();
+        set_last_error(Error::new(ErrorCode::Timeout, "Timed out"));
+        assert_eq!(
+            get_handle_error(handle_key).unwrap().code,
+            ErrorCode::NotFound
+        );
+
+        clear_handle_error(handle_key);
+        assert!(get_handle_error(handle_key).is_none());
     }
 
     #[test]
@@ -227,5 +353,13 @@ mod tests {
         assert_eq!(error.mattermost_error_id(), None);
         assert_eq!(error.request_id(), None);
         assert_eq!(error.http_status(), None);
+        assert_eq!(error.limit(), None);
+    }
+
+    #[test]
+    fn test_error_with_limit() {
+        let error = Error::new(ErrorCode::FileTooLarge, "File too large").with_limit(1024);
+        assert_eq!(error.limit(), Some(1024));
+        assert_eq!(error.code.as_str(), "File too large");
     }
 }
@@ -0,0 +1,231 @@
+//! Merging the events of several platforms into one queue
+//!
+//! Clients connected to several accounts would otherwise need one polling
+//! loop per platform handle. An event bus polls the platforms added to it in
+//! turn, through the same event pipeline as communicator_platform_poll_event(),
+//! and hands out their events in the order they were taken, each tagged with
+//! the identifier the platform was added under and a sequence number.
+
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::error::{Error, Result};
+use crate::event_callback::next_delivered;
+use crate::pipeline::Polled;
+
+/// An event taken from one of the platforms of a bus
+#[derive(Debug, Clone, Serialize)]
+pub struct BusEvent {
+    /// Identifier the platform was added to the bus under
+    pub platform_id: String,
+    /// Position in the bus's queue; increases by one per event
+    pub sequence: u64,
+    /// When the bus took the event from the platform
+    pub received_at: DateTime<Utc>,
+    /// The event, as returned by communicator_platform_poll_event()
+    pub event: serde_json::Value,
+}
+
+/// A platform added to a bus
+#[derive(Debug)]
+struct Source {
+    id: String,
+    /// Address of the platform handle
+    key: usize,
+}
+
+/// Queue of the events of several platforms
+#[derive(Debug, Default)]
+pub struct EventBus {
+    sources: Vec<Source>,
+    queue: VecDeque<BusEvent>,
+    next_sequence: u64,
+    /// Platform polled first in the next round, so none is favored
+    cursor: usize,
+}
+
+impl EventBus {
+    /// Create a bus without platforms
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a platform
+    ///
+    /// # Arguments
+    /// * `platform_id` - Identifier events of the platform are tagged with
+    /// * `key` - Address of the platform handle
+    ///
+    /// # Returns
+    /// An InvalidArgument error if the identifier or the platform is already on the bus
+    pub fn add(&mut self, platform_id: &str, key: usize) -> Result<()> {
+        if platform_id.is_empty() {
+            return Err(Error::invalid_argument("Platform ID must not be empty"));
+        }
+        if let Some(source) = self
+            .sources
+            .iter()
+            .find(|source| source.id == platform_id || source.key == key)
+        {
+            return Err(Error::invalid_argument(format!(
+                "Platform is already on the event bus as \"{}\"",
+                source.id
+            )));
+        }
+        self.sources.push(Source {
+            id: platform_id.to_string(),
+            key,
+        });
+        Ok(())
+    }
+
+    /// Remove a platform; its events still queued are dropped
+    ///
+    /// # Returns
+    /// Whether a platform was added under the identifier
+    pub fn remove(&mut self, platform_id: &str) -> bool {
+        let before = self.sources.len();
+        self.sources.retain(|source| source.id != platform_id);
+        self.queue.retain(|event| event.platform_id != platform_id);
+        self.sources.len() != before
+    }
+
+    /// Identifiers of the platforms on the bus, in the order they were added
+    pub fn platform_ids(&self) -> Vec<&str> {
+        self.sources
+            .iter()
+            .map(|source| source.id.as_str())
+            .collect()
+    }
+
+    /// Take the next event
+    ///
+    /// When the queue is empty, every platform is polled once, starting with
+    /// the one after the platform polled first last time.
+    ///
+    /// # Arguments
+    /// * `next` - Takes the next event of a platform handle
+    /// * `alive` - Whether a platform handle is still valid; destroyed
+    ///   platforms are removed from the bus
+    ///
+    /// # Returns
+    /// The oldest queued event, None if no platform has one, or the first
+    /// polling error if no platform had an event
+    pub(crate) fn poll(
+        &mut self,
+        mut next: impl FnMut(usize) -> Result<Polled<serde_json::Value>>,
+        alive: impl Fn(usize) -> bool,
+    ) -> Result<Option<BusEvent>> {
+        self.sources.retain(|source| alive(source.key));
+
+        if self.queue.is_empty() && !self.sources.is_empty() {
+            let count = self.sources.len();
+            let first = self.cursor % count;
+            let mut error = None;
+            for i in 0..count {
+                let source = &self.sources[(first + i) % count];
+                match next_delivered(|| next(source.key)) {
+                    Ok(Some(event)) => {
+                        self.queue.push_back(BusEvent {
+                            platform_id: source.id.clone(),
+                            sequence: self.next_sequence,
+                            received_at: Utc::now(),
+                            event,
+                        });
+                        self.next_sequence += 1;
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        error.get_or_insert(Error::new(
+                            e.code,
+                            format!("Polling platform \"{}\" failed: {}", source.id, e.message),
+                        ));
+                    }
+                }
+            }
+            self.cursor = (first + 1) % count;
+
+            if let (true, Some(error)) = (self.queue.is_empty(), error) {
+                return Err(error);
+            }
+        }
+
+        Ok(self.queue.pop_front())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorCode;
+    use std::collections::HashMap;
+
+    fn event(name: &str) -> Polled<serde_json::Value> {
+        Polled::Event(serde_json::json!({ "type": name }))
+    }
+
+    #[test]
+    fn test_merged_order() {
+        let mut bus = EventBus::new();
+        bus.add("work", 1).unwrap();
+        bus.add("home", 2).unwrap();
+        assert!(bus.add("work", 3).is_err());
+        assert!(bus.add("other", 2).is_err());
+        assert_eq!(bus.platform_ids(), vec!["work", "home"]);
+
+        let mut pending: HashMap<usize, VecDeque<Polled<serde_json::Value>>> = HashMap::new();
+        pending.insert(1, VecDeque::from([event("a1"), event("a2")]));
+        pending.insert(2, VecDeque::from([Polled::Skipped, event("b1")]));
+        let mut next = |key: usize| {
+            Ok(pending
+                .get_mut(&key)
+                .unwrap()
+                .pop_front()
+                .unwrap_or(Polled::Empty))
+        };
+
+        let mut taken = Vec::new();
+        while let Some(event) = bus.poll(&mut next, |_| true).unwrap() {
+            taken.push((
+                event.platform_id,
+                event.sequence,
+                event.event["type"].clone(),
+            ));
+        }
+        // Rounds alternate which platform is polled first
+        assert_eq!(
+            taken,
+            vec![
+                ("work".to_string(), 0, "a1".into()),
+                ("home".to_string(), 1, "b1".into()),
+                ("work".to_string(), 2, "a2".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_errors_and_destroyed_platforms() {
+        let mut bus = EventBus::new();
+        bus.add("broken", 1).unwrap();
+        bus.add("ok", 2).unwrap();
+
+        let next = |key: usize| match key {
+            1 => Err(Error::new(ErrorCode::NetworkError, "down")),
+            _ => Ok(event("x")),
+        };
+        // Events of other platforms are delivered despite the error
+        let taken = bus.poll(next, |_| true).unwrap().unwrap();
+        assert_eq!(taken.platform_id, "ok");
+
+        bus.remove("ok");
+        let error = bus.poll(next, |_| true).unwrap_err();
+        assert_eq!(error.code, ErrorCode::NetworkError);
+        assert!(error.message.contains("\"broken\""));
+
+        // A destroyed platform leaves the bus
+        assert!(bus.poll(next, |key| key != 1).unwrap().is_none());
+        assert!(bus.platform_ids().is_empty());
+    }
+}
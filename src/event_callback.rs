@@ -0,0 +1,275 @@
+//! Callback-based event delivery
+//!
+//! Instead of polling communicator_platform_poll_event(), C callers can
+//! register a function that is invoked with every event. Each platform handle
+//! with a callback gets a delivery thread that runs the same event pipeline as
+//! polling and sleeps on the platform's event signal in between, so events are
+//! delivered as soon as the platform queues them. Platforms without an event
+//! signal are polled every [`POLL_INTERVAL`].
+//!
+//! Callbacks are invoked on the delivery thread, one event at a time.
+
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::os::raw::{c_char, c_void};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use lazy_static::lazy_static;
+use tokio::sync::Notify;
+
+use crate::error::Result;
+use crate::pipeline::Polled;
+
+/// Callback invoked with each event
+/// Parameters: event JSON, user_data
+pub type EventCallback = extern "C" fn(*const c_char, *mut c_void);
+
+/// How often platforms without an event signal are polled
+pub const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How often platforms with an event signal are polled anyway, for events
+/// that are not queued by the platform (e.g. due reminders)
+const IDLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Poll until an event is delivered or none is available
+///
+/// For pollers that return nothing for an empty queue, so that an event held
+/// back or dropped by the pipeline isn't mistaken for the end of the events
+pub(crate) fn next_delivered<T>(mut poll: impl FnMut() -> Result<Polled<T>>) -> Result<Option<T>> {
+    loop {
+        match poll()? {
+            Polled::Event(event) => return Ok(Some(event)),
+            Polled::Skipped => {}
+            Polled::Empty => return Ok(None),
+        }
+    }
+}
+
+/// Takes the next event of the platform handle with the given address
+pub(crate) type NextEvent = fn(usize) -> Result<Polled<String>>;
+
+/// A running delivery thread
+struct Delivery {
+    stop: Arc<AtomicBool>,
+    wake: Arc<Notify>,
+    thread: JoinHandle<()>,
+}
+
+impl Delivery {
+    /// Stop the thread and wait for it to finish
+    ///
+    /// Does not wait when called from the delivery thread itself, i.e. when a
+    /// callback removes or replaces itself; the thread exits after the callback.
+    fn stop(self) {
+        self.stop.store(true, Ordering::SeqCst);
+        self.wake.notify_one();
+        if self.thread.thread().id() != std::thread::current().id() {
+            let _ = self.thread.join();
+        }
+    }
+}
+
+lazy_static! {
+    /// Delivery threads, keyed by platform handle address
+    static ref DELIVERIES: Mutex<HashMap<usize, Delivery>> = Mutex::new(HashMap::new());
+}
+
+/// Start delivering the events of a platform handle to a callback
+///
+/// Replaces the callback registered before, if any.
+///
+/// # Arguments
+/// * `handle_key` - Address of the platform handle
+/// * `callback` - Invoked with each event
+/// * `user_data` - Passed back to the callback
+/// * `signal` - The platform's event signal, if it has one
+/// * `next_event` - Takes the next event of the handle
+pub(crate) fn register(
+    handle_key: usize,
+    callback: EventCallback,
+    user_data: *mut c_void,
+    signal: Option<Arc<Notify>>,
+    next_event: NextEvent,
+) {
+    clear(handle_key);
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let wake = Arc::new(Notify::new());
+    let user_data = user_data as usize;
+    let thread = {
+        let stop = Arc::clone(&stop);
+        let wake = Arc::clone(&wake);
+        std::thread::spawn(move || {
+            deliver(
+                handle_key, callback, user_data, &stop, &wake, signal, next_event,
+            )
+        })
+    };
+
+    if let Ok(mut deliveries) = DELIVERIES.lock() {
+        deliveries.insert(handle_key, Delivery { stop, wake, thread });
+    }
+}
+
+/// Stop delivering the events of a platform handle
+///
+/// Once this returns, the callback is no longer invoked (unless it is called
+/// from the callback itself, which finishes first).
+pub(crate) fn clear(handle_key: usize) {
+    let delivery = DELIVERIES
+        .lock()
+        .ok()
+        .and_then(|mut deliveries| deliveries.remove(&handle_key));
+    if let Some(delivery) = delivery {
+        delivery.stop();
+    }
+}
+
+/// Stop all delivery threads
+pub(crate) fn clear_all() {
+    let deliveries: Vec<Delivery> = match DELIVERIES.lock() {
+        Ok(mut deliveries) => deliveries.drain().map(|(_, delivery)| delivery).collect(),
+        Err(_) => return,
+    };
+    for delivery in deliveries {
+        delivery.stop();
+    }
+}
+
+/// Whether a callback is registered for a platform handle
+pub(crate) fn is_registered(handle_key: usize) -> bool {
+    DELIVERIES
+        .lock()
+        .map(|deliveries| deliveries.contains_key(&handle_key))
+        .unwrap_or(false)
+}
+
+/// Body of a delivery thread
+fn deliver(
+    handle_key: usize,
+    callback: EventCallback,
+    user_data: usize,
+    stop: &AtomicBool,
+    wake: &Notify,
+    signal: Option<Arc<Notify>>,
+    next_event: NextEvent,
+) {
+    let interval = if signal.is_some() {
+        IDLE_INTERVAL
+    } else {
+        POLL_INTERVAL
+    };
+
+    while !stop.load(Ordering::SeqCst) {
+        // The runtime is gone after communicator_cleanup()
+        let Some(runtime) = crate::runtime::runtime_handle() else {
+            break;
+        };
+
+        match next_event(handle_key) {
+            Ok(Polled::Event(json)) => {
+                if let Ok(json) = CString::new(json) {
+                    callback(json.as_ptr(), user_data as *mut c_void);
+                }
+            }
+            Ok(Polled::Skipped) => {}
+            // Errors are retried after the interval, like an empty queue
+            Ok(Polled::Empty) | Err(_) => {
+                runtime.block_on(async {
+                    match &signal {
+                        Some(signal) => tokio::select! {
+                            _ = signal.notified() => {}
+                            _ = wake.notified() => {}
+                            _ = tokio::time::sleep(interval) => {}
+                        },
+                        None => tokio::select! {
+                            _ = wake.notified() => {}
+                            _ = tokio::time::sleep(interval) => {}
+                        },
+                    }
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    static TAKEN: AtomicUsize = AtomicUsize::new(0);
+    static DELIVERED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    extern "C" fn record(json: *const c_char, user_data: *mut c_void) {
+        let json = unsafe { std::ffi::CStr::from_ptr(json) };
+        assert_eq!(user_data as usize, 42);
+        DELIVERED
+            .lock()
+            .unwrap()
+            .push(json.to_str().unwrap().to_string());
+    }
+
+    /// Two events, with a skipped one in between, then nothing
+    fn next_event(_handle_key: usize) -> Result<Polled<String>> {
+        Ok(match TAKEN.fetch_add(1, Ordering::SeqCst) {
+            0 => Polled::Event("first".to_string()),
+            1 => Polled::Skipped,
+            2 => Polled::Event("second".to_string()),
+            _ => Polled::Empty,
+        })
+    }
+
+    #[test]
+    fn test_delivers_events_until_cleared() {
+        crate::runtime::init_runtime().unwrap();
+        let signal = Arc::new(Notify::new());
+        let key = 0x4006;
+        register(
+            key,
+            record,
+            42 as *mut c_void,
+            Some(Arc::clone(&signal)),
+            next_event,
+        );
+        assert!(is_registered(key));
+
+        for _ in 0..100 {
+            if DELIVERED.lock().unwrap().len() == 2 {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(*DELIVERED.lock().unwrap(), vec!["first", "second"]);
+
+        // The thread waits on the signal rather than spinning
+        std::thread::sleep(Duration::from_millis(50));
+        let taken = TAKEN.load(Ordering::SeqCst);
+        assert!(taken <= 5, "polled {taken} times");
+        signal.notify_one();
+        for _ in 0..100 {
+            if TAKEN.load(Ordering::SeqCst) > taken {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(TAKEN.load(Ordering::SeqCst) > taken);
+
+        clear(key);
+        assert!(!is_registered(key));
+    }
+
+    #[test]
+    fn test_next_delivered_passes_over_skipped_events() {
+        let mut polled = vec![Polled::Empty, Polled::Skipped, Polled::Skipped];
+        let mut poll = || Ok(polled.pop().unwrap_or(Polled::Empty));
+        assert_eq!(next_delivered::<String>(&mut poll).unwrap(), None);
+
+        let mut polled = vec![Polled::Event("first"), Polled::Skipped];
+        let poll = || Ok(polled.pop().unwrap_or(Polled::Empty));
+        assert_eq!(next_delivered(poll).unwrap(), Some("first"));
+    }
+}
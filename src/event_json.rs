@@ -0,0 +1,528 @@
+//! JSON representation of platform events
+//!
+//! Events are handed to C callers, daemon clients and webhooks as JSON
+//! objects with a snake_case "type" field plus event-specific fields.
+
+use crate::platforms::PlatformEvent;
+use crate::types;
+
+/// Add the user and channel a membership event was enriched with
+fn with_resolved(
+    mut json: serde_json::Value,
+    user: &Option<Box<types::User>>,
+    channel: &Option<Box<types::Channel>>,
+) -> serde_json::Value {
+    if let Some(user) = user {
+        json["user"] = serde_json::json!(user);
+    }
+    if let Some(channel) = channel {
+        json["channel"] = serde_json::json!(channel);
+    }
+    json
+}
+
+/// Serialize a platform event to the JSON representation used by the C API
+///
+/// Every event is an object with a snake_case "type" field plus event-specific fields.
+pub fn event_to_json(event: &PlatformEvent) -> serde_json::Value {
+    // Note: PlatformEvent enum needs custom serialization
+    match event {
+        PlatformEvent::MessagePosted(msg) => {
+            serde_json::json!({
+                "type": "message_posted",
+                "data": msg
+            })
+        }
+        PlatformEvent::MessageConfirmed {
+            pending_id,
+            message,
+        } => {
+            serde_json::json!({
+                "type": "message_confirmed",
+                "pending_id": pending_id,
+                "data": message
+            })
+        }
+        PlatformEvent::MessageSendSucceeded { outbox_id, message } => {
+            serde_json::json!({
+                "type": "message_send_succeeded",
+                "outbox_id": outbox_id,
+                "data": message
+            })
+        }
+        PlatformEvent::MessageSendFailed {
+            outbox_id,
+            channel_id,
+            error,
+        } => {
+            serde_json::json!({
+                "type": "message_send_failed",
+                "outbox_id": outbox_id,
+                "channel_id": channel_id,
+                "code": error.code as i32,
+                "message": error.message
+            })
+        }
+        PlatformEvent::MessageUpdated(msg) => {
+            serde_json::json!({
+                "type": "message_updated",
+                "data": msg
+            })
+        }
+        PlatformEvent::MessageDeleted {
+            message_id,
+            channel_id,
+        } => {
+            serde_json::json!({
+                "type": "message_deleted",
+                "message_id": message_id,
+                "channel_id": channel_id
+            })
+        }
+        PlatformEvent::MessagePinned(msg) => {
+            serde_json::json!({
+                "type": "message_pinned",
+                "data": msg
+            })
+        }
+        PlatformEvent::MessageUnpinned {
+            message_id,
+            channel_id,
+        } => {
+            serde_json::json!({
+                "type": "message_unpinned",
+                "message_id": message_id,
+                "channel_id": channel_id
+            })
+        }
+        PlatformEvent::UserStatusChanged { user_id, status } => {
+            serde_json::json!({
+                "type": "user_status_changed",
+                "user_id": user_id,
+                "status": status
+            })
+        }
+        PlatformEvent::UserTyping {
+            user_id,
+            channel_id,
+        } => {
+            serde_json::json!({
+                "type": "user_typing",
+                "user_id": user_id,
+                "channel_id": channel_id
+            })
+        }
+        PlatformEvent::ChannelCreated(channel) => {
+            serde_json::json!({
+                "type": "channel_created",
+                "data": channel
+            })
+        }
+        PlatformEvent::ChannelUpdated(channel) => {
+            serde_json::json!({
+                "type": "channel_updated",
+                "data": channel
+            })
+        }
+        PlatformEvent::ChannelDeleted { channel_id } => {
+            serde_json::json!({
+                "type": "channel_deleted",
+                "channel_id": channel_id
+            })
+        }
+        PlatformEvent::UserJoinedChannel {
+            user_id,
+            channel_id,
+            user,
+            channel,
+        } => with_resolved(
+            serde_json::json!({
+                "type": "user_joined_channel",
+                "user_id": user_id,
+                "channel_id": channel_id
+            }),
+            user,
+            channel,
+        ),
+        PlatformEvent::UserLeftChannel {
+            user_id,
+            channel_id,
+            user,
+            channel,
+        } => with_resolved(
+            serde_json::json!({
+                "type": "user_left_channel",
+                "user_id": user_id,
+                "channel_id": channel_id
+            }),
+            user,
+            channel,
+        ),
+        PlatformEvent::ConnectionStateChanged {
+            state,
+            attempt,
+            reason,
+        } => {
+            let mut json = serde_json::json!({
+                "type": "connection_state_changed",
+                "state": state
+            });
+            if let Some(attempt) = attempt {
+                json["attempt"] = serde_json::json!(attempt);
+            }
+            if let Some(reason) = reason {
+                json["disconnect_reason"] = serde_json::json!(reason);
+            }
+            json
+        }
+        PlatformEvent::EventsMissed { from_seq, to_seq } => {
+            serde_json::json!({
+                "type": "events_missed",
+                "from_seq": from_seq,
+                "to_seq": to_seq
+            })
+        }
+        PlatformEvent::ReactionAdded {
+            message_id,
+            user_id,
+            emoji_name,
+            channel_id,
+        } => {
+            serde_json::json!({
+                "type": "reaction_added",
+                "message_id": message_id,
+                "user_id": user_id,
+                "emoji_name": emoji_name,
+                "channel_id": channel_id
+            })
+        }
+        PlatformEvent::ReactionRemoved {
+            message_id,
+            user_id,
+            emoji_name,
+            channel_id,
+        } => {
+            serde_json::json!({
+                "type": "reaction_removed",
+                "message_id": message_id,
+                "user_id": user_id,
+                "emoji_name": emoji_name,
+                "channel_id": channel_id
+            })
+        }
+        PlatformEvent::DirectChannelAdded { channel_id } => {
+            serde_json::json!({
+                "type": "direct_channel_added",
+                "channel_id": channel_id
+            })
+        }
+        PlatformEvent::GroupChannelAdded { channel_id } => {
+            serde_json::json!({
+                "type": "group_channel_added",
+                "channel_id": channel_id
+            })
+        }
+        PlatformEvent::PreferenceChanged {
+            category,
+            name,
+            value,
+        } => {
+            serde_json::json!({
+                "type": "preference_changed",
+                "category": category,
+                "name": name,
+                "value": value
+            })
+        }
+        PlatformEvent::EphemeralMessage {
+            message,
+            channel_id,
+        } => {
+            serde_json::json!({
+                "type": "ephemeral_message",
+                "message": message,
+                "channel_id": channel_id
+            })
+        }
+        PlatformEvent::UserAdded { user_id } => {
+            serde_json::json!({
+                "type": "user_added",
+                "user_id": user_id
+            })
+        }
+        PlatformEvent::UserUpdated { user_id } => {
+            serde_json::json!({
+                "type": "user_updated",
+                "user_id": user_id
+            })
+        }
+        PlatformEvent::UserRoleUpdated { user_id } => {
+            serde_json::json!({
+                "type": "user_role_updated",
+                "user_id": user_id
+            })
+        }
+        PlatformEvent::ChannelViewed {
+            user_id,
+            channel_id,
+        } => {
+            serde_json::json!({
+                "type": "channel_viewed",
+                "user_id": user_id,
+                "channel_id": channel_id
+            })
+        }
+        PlatformEvent::ThreadUpdated {
+            thread_id,
+            channel_id,
+        } => {
+            serde_json::json!({
+                "type": "thread_updated",
+                "thread_id": thread_id,
+                "channel_id": channel_id
+            })
+        }
+        PlatformEvent::ThreadReadChanged {
+            thread_id,
+            user_id,
+            channel_id,
+        } => {
+            serde_json::json!({
+                "type": "thread_read_changed",
+                "thread_id": thread_id,
+                "user_id": user_id,
+                "channel_id": channel_id
+            })
+        }
+        PlatformEvent::ThreadFollowChanged {
+            thread_id,
+            user_id,
+            channel_id,
+            following,
+        } => {
+            serde_json::json!({
+                "type": "thread_follow_changed",
+                "thread_id": thread_id,
+                "user_id": user_id,
+                "channel_id": channel_id,
+                "following": following
+            })
+        }
+        PlatformEvent::PostUnread {
+            post_id,
+            channel_id,
+            user_id,
+        } => {
+            serde_json::json!({
+                "type": "post_unread",
+                "post_id": post_id,
+                "channel_id": channel_id,
+                "user_id": user_id
+            })
+        }
+        PlatformEvent::EmojiAdded {
+            emoji_id,
+            emoji_name,
+        } => {
+            serde_json::json!({
+                "type": "emoji_added",
+                "emoji_id": emoji_id,
+                "emoji_name": emoji_name
+            })
+        }
+        PlatformEvent::AddedToTeam { team_id, user_id } => {
+            serde_json::json!({
+                "type": "added_to_team",
+                "team_id": team_id,
+                "user_id": user_id
+            })
+        }
+        PlatformEvent::LeftTeam { team_id, user_id } => {
+            serde_json::json!({
+                "type": "left_team",
+                "team_id": team_id,
+                "user_id": user_id
+            })
+        }
+        PlatformEvent::ConfigChanged => {
+            serde_json::json!({
+                "type": "config_changed"
+            })
+        }
+        PlatformEvent::LicenseChanged => {
+            serde_json::json!({
+                "type": "license_changed"
+            })
+        }
+        PlatformEvent::ChannelConverted { channel_id } => {
+            serde_json::json!({
+                "type": "channel_converted",
+                "channel_id": channel_id
+            })
+        }
+        PlatformEvent::SharedChannelRemoteUpdated {
+            channel_id,
+            remote_id,
+        } => {
+            serde_json::json!({
+                "type": "shared_channel_remote_updated",
+                "channel_id": channel_id,
+                "remote_id": remote_id
+            })
+        }
+        PlatformEvent::ChannelMemberUpdated {
+            channel_id,
+            user_id,
+            user,
+            channel,
+        } => with_resolved(
+            serde_json::json!({
+                "type": "channel_member_updated",
+                "channel_id": channel_id,
+                "user_id": user_id
+            }),
+            user,
+            channel,
+        ),
+        PlatformEvent::TeamDeleted { team_id } => {
+            serde_json::json!({
+                "type": "team_deleted",
+                "team_id": team_id
+            })
+        }
+        PlatformEvent::TeamUpdated { team_id } => {
+            serde_json::json!({
+                "type": "team_updated",
+                "team_id": team_id
+            })
+        }
+        PlatformEvent::MemberRoleUpdated {
+            channel_id,
+            user_id,
+            user,
+            channel,
+        } => with_resolved(
+            serde_json::json!({
+                "type": "member_role_updated",
+                "channel_id": channel_id,
+                "user_id": user_id
+            }),
+            user,
+            channel,
+        ),
+        PlatformEvent::PluginDisabled { plugin_id } => {
+            serde_json::json!({
+                "type": "plugin_disabled",
+                "plugin_id": plugin_id
+            })
+        }
+        PlatformEvent::PluginEnabled { plugin_id } => {
+            serde_json::json!({
+                "type": "plugin_enabled",
+                "plugin_id": plugin_id
+            })
+        }
+        PlatformEvent::PluginStatusesChanged => {
+            serde_json::json!({
+                "type": "plugin_statuses_changed"
+            })
+        }
+        PlatformEvent::PreferencesDeleted { category, name } => {
+            serde_json::json!({
+                "type": "preferences_deleted",
+                "category": category,
+                "name": name
+            })
+        }
+        PlatformEvent::Response {
+            status,
+            seq_reply,
+            error,
+        } => {
+            serde_json::json!({
+                "type": "response",
+                "status": status,
+                "seq_reply": seq_reply,
+                "error": error
+            })
+        }
+        PlatformEvent::DialogOpened { dialog_id } => {
+            serde_json::json!({
+                "type": "dialog_opened",
+                "dialog_id": dialog_id
+            })
+        }
+        PlatformEvent::RoleUpdated { role_id } => {
+            serde_json::json!({
+                "type": "role_updated",
+                "role_id": role_id
+            })
+        }
+        PlatformEvent::PollUpdated(poll) => {
+            serde_json::json!({
+                "type": "poll_updated",
+                "data": poll
+            })
+        }
+        PlatformEvent::ReminderDue(reminder) => {
+            serde_json::json!({
+                "type": "reminder_due",
+                "data": reminder
+            })
+        }
+        PlatformEvent::NotificationTriggered { message, reason } => {
+            serde_json::json!({
+                "type": "notification_triggered",
+                "data": message,
+                "reason": reason
+            })
+        }
+        PlatformEvent::CircuitStateChanged(state) => {
+            serde_json::json!({
+                "type": "circuit_state_changed",
+                "state": state
+            })
+        }
+        PlatformEvent::ServerEndpointChanged { server_url } => {
+            serde_json::json!({
+                "type": "server_endpoint_changed",
+                "server_url": server_url
+            })
+        }
+        PlatformEvent::SessionReady(info) => {
+            serde_json::json!({
+                "type": "session_ready",
+                "data": info
+            })
+        }
+        PlatformEvent::SessionFailed(error) => {
+            serde_json::json!({
+                "type": "session_failed",
+                "code": error.code as i32,
+                "message": error.message
+            })
+        }
+        PlatformEvent::SessionExpired(error) => {
+            serde_json::json!({
+                "type": "session_expired",
+                "code": error.code as i32,
+                "message": error.message
+            })
+        }
+        PlatformEvent::ChannelListChanged => {
+            serde_json::json!({
+                "type": "channel_list_changed"
+            })
+        }
+        PlatformEvent::UnreadsChanged => {
+            serde_json::json!({
+                "type": "unreads_changed"
+            })
+        }
+        PlatformEvent::ProfileCacheChanged => {
+            serde_json::json!({
+                "type": "profile_cache_changed"
+            })
+        }
+    }
+}
@@ -0,0 +1,925 @@
+//! Structured events for C callers
+//!
+//! communicator_platform_poll_event() returns events as JSON, which every
+//! consumer has to parse. The structured surface returns the same events as a
+//! tagged C struct: a [`StructuredEvent`] carries the event type and a union of
+//! payload structs, whose strings point into the event itself. Events are built
+//! by the same pipeline as the JSON ones (reminders, reassembly, script hooks,
+//! webhooks, rules), and the event's JSON stays available for fields that have
+//! no struct member (e.g. poll options).
+//!
+//! Each event type uses one payload kind; simple events that only refer to
+//! users, channels, teams or other objects share the [`ReferencePayload`].
+
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::ptr;
+
+use serde::Serialize;
+
+use crate::error::ErrorCode;
+use crate::platforms::PlatformEvent;
+use crate::types::Message;
+
+/// Event types, in the order of `PlatformEvent`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventType {
+    MessagePosted = 1,
+    MessageConfirmed = 2,
+    MessageUpdated = 3,
+    MessageDeleted = 4,
+    UserStatusChanged = 5,
+    UserTyping = 6,
+    ChannelCreated = 7,
+    ChannelUpdated = 8,
+    ChannelDeleted = 9,
+    UserJoinedChannel = 10,
+    UserLeftChannel = 11,
+    ConnectionStateChanged = 12,
+    ReactionAdded = 13,
+    ReactionRemoved = 14,
+    DirectChannelAdded = 15,
+    GroupChannelAdded = 16,
+    PreferenceChanged = 17,
+    EphemeralMessage = 18,
+    UserAdded = 19,
+    UserUpdated = 20,
+    UserRoleUpdated = 21,
+    ChannelViewed = 22,
+    ThreadUpdated = 23,
+    ThreadReadChanged = 24,
+    ThreadFollowChanged = 25,
+    PostUnread = 26,
+    EmojiAdded = 27,
+    AddedToTeam = 28,
+    LeftTeam = 29,
+    ConfigChanged = 30,
+    LicenseChanged = 31,
+    ChannelConverted = 32,
+    SharedChannelRemoteUpdated = 33,
+    ChannelMemberUpdated = 34,
+    TeamDeleted = 35,
+    TeamUpdated = 36,
+    MemberRoleUpdated = 37,
+    PluginDisabled = 38,
+    PluginEnabled = 39,
+    PluginStatusesChanged = 40,
+    PreferencesDeleted = 41,
+    Response = 42,
+    DialogOpened = 43,
+    RoleUpdated = 44,
+    PollUpdated = 45,
+    ReminderDue = 46,
+    CircuitStateChanged = 47,
+    ServerEndpointChanged = 48,
+    SessionReady = 49,
+    SessionFailed = 50,
+    MessageSendSucceeded = 51,
+    MessageSendFailed = 52,
+    SessionExpired = 53,
+    ChannelListChanged = 54,
+    UnreadsChanged = 55,
+    ProfileCacheChanged = 56,
+    NotificationTriggered = 57,
+    MessagePinned = 58,
+    MessageUnpinned = 59,
+    EventsMissed = 60,
+}
+
+/// Which member of the payload union is set
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadKind {
+    /// No payload (e.g. config_changed)
+    None = 0,
+    Message = 1,
+    Channel = 2,
+    Reaction = 3,
+    Preference = 4,
+    Response = 5,
+    Error = 6,
+    Reference = 7,
+}
+
+/// Payload of message_posted, message_confirmed and message_updated
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct MessagePayload {
+    pub id: *const c_char,
+    pub channel_id: *const c_char,
+    pub sender_id: *const c_char,
+    pub text: *const c_char,
+    /// Creation time in milliseconds since the Unix epoch
+    pub created_at: i64,
+    /// Last edit time in milliseconds since the Unix epoch (0 if never edited)
+    pub edited_at: i64,
+    /// For message_confirmed, the pending ID of the sent message; for
+    /// message_send_succeeded, the outbox ID of the queued message (NULL otherwise)
+    pub pending_id: *const c_char,
+    /// Detected language (ISO 639-1 code), NULL if not detected
+    pub language: *const c_char,
+}
+
+/// Payload of channel_created and channel_updated
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct ChannelPayload {
+    pub id: *const c_char,
+    pub name: *const c_char,
+    pub display_name: *const c_char,
+    /// "public", "private", "direct_message" or "group_message"
+    pub channel_type: *const c_char,
+    /// NULL if not set
+    pub topic: *const c_char,
+    /// NULL if not set
+    pub purpose: *const c_char,
+    pub is_archived: bool,
+}
+
+/// Payload of reaction_added and reaction_removed
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct ReactionPayload {
+    pub message_id: *const c_char,
+    pub user_id: *const c_char,
+    pub channel_id: *const c_char,
+    pub emoji_name: *const c_char,
+}
+
+/// Payload of preference_changed and preferences_deleted
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct PreferencePayload {
+    pub category: *const c_char,
+    pub name: *const c_char,
+    /// NULL for preferences_deleted
+    pub value: *const c_char,
+}
+
+/// Payload of response
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct ResponsePayload {
+    pub status: *const c_char,
+    pub seq_reply: i64,
+    /// NULL if the action succeeded
+    pub error: *const c_char,
+}
+
+/// Payload of session_failed
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct ErrorPayload {
+    pub code: ErrorCode,
+    pub message: *const c_char,
+}
+
+/// Payload of events that refer to other objects
+///
+/// Fields an event does not use are NULL (or false).
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct ReferencePayload {
+    pub user_id: *const c_char,
+    pub channel_id: *const c_char,
+    pub team_id: *const c_char,
+    /// ID of the object the event is about (message, thread, emoji, plugin, ...)
+    pub target_id: *const c_char,
+    /// Name of the object the event is about, if it has one
+    pub name: *const c_char,
+    /// New value (status, state, URL, text), if the event carries one
+    pub value: *const c_char,
+    pub flag: bool,
+}
+
+impl Default for ReferencePayload {
+    fn default() -> Self {
+        Self {
+            user_id: ptr::null(),
+            channel_id: ptr::null(),
+            team_id: ptr::null(),
+            target_id: ptr::null(),
+            name: ptr::null(),
+            value: ptr::null(),
+            flag: false,
+        }
+    }
+}
+
+/// Event payload; `StructuredEvent::payload_kind` tells which member is set
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub union EventPayload {
+    pub message: MessagePayload,
+    pub channel: ChannelPayload,
+    pub reaction: ReactionPayload,
+    pub preference: PreferencePayload,
+    pub response: ResponsePayload,
+    pub error: ErrorPayload,
+    pub reference: ReferencePayload,
+}
+
+/// An event as a tagged C struct
+#[repr(C)]
+pub struct StructuredEvent {
+    pub event_type: EventType,
+    pub payload_kind: PayloadKind,
+    pub payload: EventPayload,
+}
+
+impl StructuredEvent {
+    /// The payload, if it is of the given kind
+    pub fn payload(&self, kind: PayloadKind) -> Option<&EventPayload> {
+        (self.payload_kind == kind).then_some(&self.payload)
+    }
+}
+
+/// A structured event together with the data its pointers refer to
+///
+/// Handed to C as a pointer to its first field, so it must stay `repr(C)`.
+#[repr(C)]
+pub(crate) struct OwnedEvent {
+    event: StructuredEvent,
+    /// Strings the payload points to
+    _strings: Vec<CString>,
+    json: serde_json::Value,
+}
+
+impl OwnedEvent {
+    /// Build the structured form of an event
+    ///
+    /// # Arguments
+    /// * `event` - The event
+    /// * `json` - The event's JSON representation, kept for callers that need
+    ///   fields without a struct member
+    pub(crate) fn new(event: &PlatformEvent, json: serde_json::Value) -> Self {
+        let mut strings = Strings::default();
+        let event = build(event, &mut strings);
+        Self {
+            event,
+            _strings: strings.0,
+            json,
+        }
+    }
+
+    /// Move the event to the heap and hand it to C
+    pub(crate) fn into_raw(self) -> *mut StructuredEvent {
+        Box::into_raw(Box::new(self)) as *mut StructuredEvent
+    }
+
+    /// Take back an event handed to C
+    ///
+    /// # Safety
+    /// The pointer must come from [`OwnedEvent::into_raw`] and not have been freed
+    pub(crate) unsafe fn from_raw(event: *mut StructuredEvent) -> Box<Self> {
+        Box::from_raw(event as *mut OwnedEvent)
+    }
+
+    /// The event's JSON representation
+    ///
+    /// # Safety
+    /// The pointer must come from [`OwnedEvent::into_raw`] and not have been freed
+    pub(crate) unsafe fn json_of<'a>(event: *const StructuredEvent) -> &'a serde_json::Value {
+        &(*(event as *const OwnedEvent)).json
+    }
+}
+
+/// C strings owned by an event
+#[derive(Default)]
+struct Strings(Vec<CString>);
+
+impl Strings {
+    /// Copy a string; interior NUL bytes are dropped
+    fn add(&mut self, s: &str) -> *const c_char {
+        let c_string = CString::new(s).unwrap_or_else(|e| {
+            let mut bytes = e.into_vec();
+            bytes.retain(|b| *b != 0);
+            CString::new(bytes).expect("NUL bytes were removed")
+        });
+        // The heap buffer does not move when the CString is moved
+        let pointer = c_string.as_ptr();
+        self.0.push(c_string);
+        pointer
+    }
+
+    /// Copy an optional string; None becomes NULL
+    fn opt(&mut self, s: Option<&str>) -> *const c_char {
+        s.map_or(ptr::null(), |s| self.add(s))
+    }
+
+    /// Copy the serialized name of an enum value, as used in the JSON events
+    fn name<T: Serialize>(&mut self, value: &T) -> *const c_char {
+        match serde_json::to_value(value) {
+            Ok(serde_json::Value::String(name)) => self.add(&name),
+            _ => ptr::null(),
+        }
+    }
+}
+
+fn message_payload(
+    message: &Message,
+    pending_id: Option<&str>,
+    strings: &mut Strings,
+) -> EventPayload {
+    EventPayload {
+        message: MessagePayload {
+            id: strings.add(&message.id),
+            channel_id: strings.add(&message.channel_id),
+            sender_id: strings.add(&message.sender_id),
+            text: strings.add(&message.text),
+            created_at: message.created_at.timestamp_millis(),
+            edited_at: message.edited_at.map_or(0, |t| t.timestamp_millis()),
+            pending_id: strings.opt(pending_id),
+            language: strings.opt(message.language.as_deref()),
+        },
+    }
+}
+
+/// Build the structured event, copying its strings into `strings`
+fn build(event: &PlatformEvent, strings: &mut Strings) -> StructuredEvent {
+    use PlatformEvent as E;
+
+    let (event_type, payload_kind, payload) = match event {
+        E::MessagePosted(message) => (
+            EventType::MessagePosted,
+            PayloadKind::Message,
+            message_payload(message, None, strings),
+        ),
+        E::MessageConfirmed {
+            pending_id,
+            message,
+        } => (
+            EventType::MessageConfirmed,
+            PayloadKind::Message,
+            message_payload(message, Some(pending_id), strings),
+        ),
+        E::MessageSendSucceeded { outbox_id, message } => (
+            EventType::MessageSendSucceeded,
+            PayloadKind::Message,
+            message_payload(message, Some(outbox_id), strings),
+        ),
+        E::MessageUpdated(message) => (
+            EventType::MessageUpdated,
+            PayloadKind::Message,
+            message_payload(message, None, strings),
+        ),
+        E::MessagePinned(message) => (
+            EventType::MessagePinned,
+            PayloadKind::Message,
+            message_payload(message, None, strings),
+        ),
+        E::ChannelCreated(channel) | E::ChannelUpdated(channel) => (
+            if matches!(event, E::ChannelCreated(_)) {
+                EventType::ChannelCreated
+            } else {
+                EventType::ChannelUpdated
+            },
+            PayloadKind::Channel,
+            EventPayload {
+                channel: ChannelPayload {
+                    id: strings.add(&channel.id),
+                    name: strings.add(&channel.name),
+                    display_name: strings.add(&channel.display_name),
+                    channel_type: strings.name(&channel.channel_type),
+                    topic: strings.opt(channel.topic.as_deref()),
+                    purpose: strings.opt(channel.purpose.as_deref()),
+                    is_archived: channel.is_archived,
+                },
+            },
+        ),
+        E::ReactionAdded {
+            message_id,
+            user_id,
+            emoji_name,
+            channel_id,
+        }
+        | E::ReactionRemoved {
+            message_id,
+            user_id,
+            emoji_name,
+            channel_id,
+        } => (
+            if matches!(event, E::ReactionAdded { .. }) {
+                EventType::ReactionAdded
+            } else {
+                EventType::ReactionRemoved
+            },
+            PayloadKind::Reaction,
+            EventPayload {
+                reaction: ReactionPayload {
+                    message_id: strings.add(message_id),
+                    user_id: strings.add(user_id),
+                    channel_id: strings.add(channel_id),
+                    emoji_name: strings.add(emoji_name),
+                },
+            },
+        ),
+        E::PreferenceChanged {
+            category,
+            name,
+            value,
+        } => (
+            EventType::PreferenceChanged,
+            PayloadKind::Preference,
+            EventPayload {
+                preference: PreferencePayload {
+                    category: strings.add(category),
+                    name: strings.add(name),
+                    value: strings.add(value),
+                },
+            },
+        ),
+        E::PreferencesDeleted { category, name } => (
+            EventType::PreferencesDeleted,
+            PayloadKind::Preference,
+            EventPayload {
+                preference: PreferencePayload {
+                    category: strings.add(category),
+                    name: strings.add(name),
+                    value: ptr::null(),
+                },
+            },
+        ),
+        E::Response {
+            status,
+            seq_reply,
+            error,
+        } => (
+            EventType::Response,
+            PayloadKind::Response,
+            EventPayload {
+                response: ResponsePayload {
+                    status: strings.add(status),
+                    seq_reply: *seq_reply,
+                    error: strings.opt(error.as_deref()),
+                },
+            },
+        ),
+        E::SessionFailed(error) => (
+            EventType::SessionFailed,
+            PayloadKind::Error,
+            EventPayload {
+                error: ErrorPayload {
+                    code: error.code,
+                    message: strings.add(&error.message),
+                },
+            },
+        ),
+        E::SessionExpired(error) => (
+            EventType::SessionExpired,
+            PayloadKind::Error,
+            EventPayload {
+                error: ErrorPayload {
+                    code: error.code,
+                    message: strings.add(&error.message),
+                },
+            },
+        ),
+        E::ConfigChanged => (
+            EventType::ConfigChanged,
+            PayloadKind::None,
+            EventPayload {
+                reference: ReferencePayload::default(),
+            },
+        ),
+        E::ChannelListChanged => (
+            EventType::ChannelListChanged,
+            PayloadKind::None,
+            EventPayload {
+                reference: ReferencePayload::default(),
+            },
+        ),
+        E::UnreadsChanged => (
+            EventType::UnreadsChanged,
+            PayloadKind::None,
+            EventPayload {
+                reference: ReferencePayload::default(),
+            },
+        ),
+        E::ProfileCacheChanged => (
+            EventType::ProfileCacheChanged,
+            PayloadKind::None,
+            EventPayload {
+                reference: ReferencePayload::default(),
+            },
+        ),
+        E::LicenseChanged => (
+            EventType::LicenseChanged,
+            PayloadKind::None,
+            EventPayload {
+                reference: ReferencePayload::default(),
+            },
+        ),
+        E::PluginStatusesChanged => (
+            EventType::PluginStatusesChanged,
+            PayloadKind::None,
+            EventPayload {
+                reference: ReferencePayload::default(),
+            },
+        ),
+        _ => {
+            let (event_type, reference) = reference(event, strings);
+            (
+                event_type,
+                PayloadKind::Reference,
+                EventPayload { reference },
+            )
+        }
+    };
+
+    StructuredEvent {
+        event_type,
+        payload_kind,
+        payload,
+    }
+}
+
+/// Build the reference payload of an event that refers to other objects
+fn reference(event: &PlatformEvent, strings: &mut Strings) -> (EventType, ReferencePayload) {
+    use PlatformEvent as E;
+
+    let mut r = ReferencePayload::default();
+    let event_type = match event {
+        E::MessageDeleted {
+            message_id,
+            channel_id,
+        } => {
+            r.target_id = strings.add(message_id);
+            r.channel_id = strings.add(channel_id);
+            EventType::MessageDeleted
+        }
+        E::MessageUnpinned {
+            message_id,
+            channel_id,
+        } => {
+            r.target_id = strings.add(message_id);
+            r.channel_id = strings.add(channel_id);
+            EventType::MessageUnpinned
+        }
+        E::UserStatusChanged { user_id, status } => {
+            r.user_id = strings.add(user_id);
+            r.value = strings.name(status);
+            EventType::UserStatusChanged
+        }
+        E::UserTyping {
+            user_id,
+            channel_id,
+        } => {
+            r.user_id = strings.add(user_id);
+            r.channel_id = strings.add(channel_id);
+            EventType::UserTyping
+        }
+        E::ChannelDeleted { channel_id } => {
+            r.channel_id = strings.add(channel_id);
+            EventType::ChannelDeleted
+        }
+        E::UserJoinedChannel {
+            user_id,
+            channel_id,
+            user,
+            ..
+        } => {
+            r.user_id = strings.add(user_id);
+            r.channel_id = strings.add(channel_id);
+            r.name = strings.opt(user.as_ref().map(|user| user.display_name.as_str()));
+            EventType::UserJoinedChannel
+        }
+        E::UserLeftChannel {
+            user_id,
+            channel_id,
+            user,
+            ..
+        } => {
+            r.user_id = strings.add(user_id);
+            r.channel_id = strings.add(channel_id);
+            r.name = strings.opt(user.as_ref().map(|user| user.display_name.as_str()));
+            EventType::UserLeftChannel
+        }
+        E::ConnectionStateChanged {
+            state,
+            attempt,
+            reason,
+        } => {
+            r.value = strings.name(state);
+            r.name = match (attempt, reason) {
+                (Some(attempt), _) => strings.add(&attempt.to_string()),
+                (None, reason) => strings.opt(reason.as_deref()),
+            };
+            EventType::ConnectionStateChanged
+        }
+        E::EventsMissed { from_seq, to_seq } => {
+            r.name = strings.add(&from_seq.to_string());
+            r.value = strings.add(&to_seq.to_string());
+            EventType::EventsMissed
+        }
+        E::DirectChannelAdded { channel_id } => {
+            r.channel_id = strings.add(channel_id);
+            EventType::DirectChannelAdded
+        }
+        E::GroupChannelAdded { channel_id } => {
+            r.channel_id = strings.add(channel_id);
+            EventType::GroupChannelAdded
+        }
+        E::EphemeralMessage {
+            message,
+            channel_id,
+        } => {
+            r.channel_id = strings.add(channel_id);
+            r.value = strings.add(message);
+            EventType::EphemeralMessage
+        }
+        E::UserAdded { user_id } => {
+            r.user_id = strings.add(user_id);
+            EventType::UserAdded
+        }
+        E::UserUpdated { user_id } => {
+            r.user_id = strings.add(user_id);
+            EventType::UserUpdated
+        }
+        E::UserRoleUpdated { user_id } => {
+            r.user_id = strings.add(user_id);
+            EventType::UserRoleUpdated
+        }
+        E::ChannelViewed {
+            user_id,
+            channel_id,
+        } => {
+            r.user_id = strings.add(user_id);
+            r.channel_id = strings.add(channel_id);
+            EventType::ChannelViewed
+        }
+        E::ThreadUpdated {
+            thread_id,
+            channel_id,
+        } => {
+            r.target_id = strings.add(thread_id);
+            r.channel_id = strings.add(channel_id);
+            EventType::ThreadUpdated
+        }
+        E::ThreadReadChanged {
+            thread_id,
+            user_id,
+            channel_id,
+        } => {
+            r.target_id = strings.add(thread_id);
+            r.user_id = strings.add(user_id);
+            r.channel_id = strings.add(channel_id);
+            EventType::ThreadReadChanged
+        }
+        E::ThreadFollowChanged {
+            thread_id,
+            user_id,
+            channel_id,
+            following,
+        } => {
+            r.target_id = strings.add(thread_id);
+            r.user_id = strings.add(user_id);
+            r.channel_id = strings.add(channel_id);
+            r.flag = *following;
+            EventType::ThreadFollowChanged
+        }
+        E::PostUnread {
+            post_id,
+            channel_id,
+            user_id,
+        } => {
+            r.target_id = strings.add(post_id);
+            r.channel_id = strings.add(channel_id);
+            r.user_id = strings.add(user_id);
+            EventType::PostUnread
+        }
+        E::EmojiAdded {
+            emoji_id,
+            emoji_name,
+        } => {
+            r.target_id = strings.add(emoji_id);
+            r.name = strings.add(emoji_name);
+            EventType::EmojiAdded
+        }
+        E::AddedToTeam { team_id, user_id } => {
+            r.team_id = strings.add(team_id);
+            r.user_id = strings.add(user_id);
+            EventType::AddedToTeam
+        }
+        E::LeftTeam { team_id, user_id } => {
+            r.team_id = strings.add(team_id);
+            r.user_id = strings.add(user_id);
+            EventType::LeftTeam
+        }
+        E::ChannelConverted { channel_id } => {
+            r.channel_id = strings.add(channel_id);
+            EventType::ChannelConverted
+        }
+        E::SharedChannelRemoteUpdated {
+            channel_id,
+            remote_id,
+        } => {
+            r.channel_id = strings.add(channel_id);
+            r.target_id = strings.add(remote_id);
+            EventType::SharedChannelRemoteUpdated
+        }
+        E::ChannelMemberUpdated {
+            channel_id,
+            user_id,
+            user,
+            ..
+        } => {
+            r.channel_id = strings.add(channel_id);
+            r.user_id = strings.add(user_id);
+            r.name = strings.opt(user.as_ref().map(|user| user.display_name.as_str()));
+            EventType::ChannelMemberUpdated
+        }
+        E::TeamDeleted { team_id } => {
+            r.team_id = strings.add(team_id);
+            EventType::TeamDeleted
+        }
+        E::TeamUpdated { team_id } => {
+            r.team_id = strings.add(team_id);
+            EventType::TeamUpdated
+        }
+        E::MemberRoleUpdated {
+            channel_id,
+            user_id,
+            user,
+            ..
+        } => {
+            r.channel_id = strings.add(channel_id);
+            r.user_id = strings.add(user_id);
+            r.name = strings.opt(user.as_ref().map(|user| user.display_name.as_str()));
+            EventType::MemberRoleUpdated
+        }
+        E::PluginDisabled { plugin_id } => {
+            r.target_id = strings.add(plugin_id);
+            EventType::PluginDisabled
+        }
+        E::PluginEnabled { plugin_id } => {
+            r.target_id = strings.add(plugin_id);
+            EventType::PluginEnabled
+        }
+        E::DialogOpened { dialog_id } => {
+            r.target_id = strings.add(dialog_id);
+            EventType::DialogOpened
+        }
+        E::RoleUpdated { role_id } => {
+            r.target_id = strings.add(role_id);
+            EventType::RoleUpdated
+        }
+        E::PollUpdated(poll) => {
+            r.target_id = strings.add(&poll.id);
+            r.channel_id = strings.add(&poll.channel_id);
+            r.name = strings.add(&poll.question);
+            r.flag = poll.closed;
+            EventType::PollUpdated
+        }
+        E::ReminderDue(reminder) => {
+            r.target_id = strings.add(&reminder.id);
+            r.channel_id = strings.add(&reminder.channel_id);
+            r.name = strings.opt(reminder.note.as_deref());
+            r.value = strings.opt(reminder.message_id.as_deref());
+            EventType::ReminderDue
+        }
+        E::NotificationTriggered { message, reason } => {
+            r.target_id = strings.add(&message.id);
+            r.channel_id = strings.add(&message.channel_id);
+            r.user_id = strings.add(&message.sender_id);
+            r.name = strings.add(reason.name());
+            r.value = strings.opt(reason.keyword());
+            EventType::NotificationTriggered
+        }
+        E::CircuitStateChanged(state) => {
+            r.value = strings.name(state);
+            EventType::CircuitStateChanged
+        }
+        E::ServerEndpointChanged { server_url } => {
+            r.value = strings.add(server_url);
+            EventType::ServerEndpointChanged
+        }
+        E::MessageSendFailed {
+            outbox_id,
+            channel_id,
+            error,
+        } => {
+            r.target_id = strings.add(outbox_id);
+            r.channel_id = strings.add(channel_id);
+            r.value = strings.add(&error.message);
+            EventType::MessageSendFailed
+        }
+        E::SessionReady(info) => {
+            r.user_id = strings.add(&info.user_id);
+            r.team_id = strings.opt(info.team_id.as_deref());
+            r.name = strings.add(&info.user_display_name);
+            r.value = strings.add(&info.server);
+            r.flag = info.stale;
+            EventType::SessionReady
+        }
+        // Events with their own payload kind are built by build()
+        E::MessagePosted(_)
+        | E::MessageConfirmed { .. }
+        | E::MessageSendSucceeded { .. }
+        | E::MessageUpdated(_)
+        | E::MessagePinned(_)
+        | E::ChannelCreated(_)
+        | E::ChannelUpdated(_)
+        | E::ReactionAdded { .. }
+        | E::ReactionRemoved { .. }
+        | E::PreferenceChanged { .. }
+        | E::PreferencesDeleted { .. }
+        | E::Response { .. }
+        | E::SessionFailed(_)
+        | E::SessionExpired(_)
+        | E::ConfigChanged
+        | E::LicenseChanged
+        | E::PluginStatusesChanged
+        | E::ChannelListChanged
+        | E::UnreadsChanged
+        | E::ProfileCacheChanged => unreachable!("not a reference event"),
+    };
+    (event_type, r)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    fn text(pointer: *const c_char) -> &'static str {
+        unsafe { CStr::from_ptr(pointer) }.to_str().unwrap()
+    }
+
+    #[test]
+    fn test_message_event() {
+        let message = Message::new("post-1", "hello\0world", "user-1", "chan-1");
+        let event = PlatformEvent::MessageConfirmed {
+            pending_id: "pending-1".to_string(),
+            message,
+        };
+        let raw = OwnedEvent::new(&event, crate::event_json::event_to_json(&event)).into_raw();
+
+        let structured = unsafe { &*raw };
+        assert_eq!(structured.event_type, EventType::MessageConfirmed);
+        let payload = structured.payload(PayloadKind::Message).unwrap();
+        let message = unsafe { payload.message };
+        assert_eq!(text(message.id), "post-1");
+        assert_eq!(text(message.text), "helloworld");
+        assert_eq!(text(message.pending_id), "pending-1");
+        assert_eq!(message.edited_at, 0);
+        assert!(structured.payload(PayloadKind::Reference).is_none());
+
+        let json = unsafe { OwnedEvent::json_of(raw) };
+        assert_eq!(json["type"], "message_confirmed");
+        drop(unsafe { OwnedEvent::from_raw(raw) });
+    }
+
+    #[test]
+    fn test_reference_event() {
+        let event = PlatformEvent::UserStatusChanged {
+            user_id: "user-1".to_string(),
+            status: crate::types::user::UserStatus::Away,
+        };
+        let owned = OwnedEvent::new(&event, crate::event_json::event_to_json(&event));
+        assert_eq!(owned.event.payload_kind, PayloadKind::Reference);
+        let reference = unsafe { owned.event.payload.reference };
+        assert_eq!(text(reference.user_id), "user-1");
+        assert_eq!(text(reference.value), "away");
+        assert!(reference.channel_id.is_null());
+    }
+
+    #[test]
+    fn test_send_failed_event() {
+        let event = PlatformEvent::MessageSendFailed {
+            outbox_id: "outbox-1".to_string(),
+            channel_id: "chan-1".to_string(),
+            error: crate::error::Error::new(ErrorCode::PermissionDenied, "Forbidden"),
+        };
+        let owned = OwnedEvent::new(&event, crate::event_json::event_to_json(&event));
+        assert_eq!(owned.event.event_type, EventType::MessageSendFailed);
+        let reference = unsafe { owned.event.payload.reference };
+        assert_eq!(text(reference.target_id), "outbox-1");
+        assert_eq!(text(reference.channel_id), "chan-1");
+        assert_eq!(text(reference.value), "Forbidden");
+
+        let json = crate::event_json::event_to_json(&event);
+        assert_eq!(json["type"], "message_send_failed");
+        assert_eq!(json["code"], ErrorCode::PermissionDenied as i32);
+    }
+
+    #[test]
+    fn test_enriched_membership_event() {
+        let event = PlatformEvent::UserJoinedChannel {
+            user_id: "user-1".to_string(),
+            channel_id: "chan-1".to_string(),
+            user: Some(Box::new(crate::types::User::new(
+                "user-1", "alice", "Alice",
+            ))),
+            channel: None,
+        };
+        let owned = OwnedEvent::new(&event, crate::event_json::event_to_json(&event));
+        let reference = unsafe { owned.event.payload.reference };
+        assert_eq!(text(reference.name), "Alice");
+
+        let json = crate::event_json::event_to_json(&event);
+        assert_eq!(json["user"]["username"], "alice");
+        assert!(json.get("channel").is_none());
+    }
+}
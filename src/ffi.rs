@@ -0,0 +1,14666 @@
+//! C API
+//!
+//! The `extern "C"` functions declared in `include/communicator.h`. They block
+//! on the library's runtime (see [`crate::runtime`]) and exchange JSON strings
+//! and opaque handles with their callers. Built with the `ffi` feature, on by
+//! default; Rust programs can turn it off and use the async API directly.
+
+use std::ffi::CString;
+use std::os::raw::{c_char, c_void};
+
+use crate::event_json::event_to_json;
+use crate::pipeline::{PipelinePlatform, Polled};
+use crate::*;
+
+/// FFI function: Free a string allocated by this library
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        unsafe {
+            let _ = CString::from_raw(s);
+        }
+    }
+}
+
+// ============================================================================
+// Library Initialization Pattern
+// ============================================================================
+
+/// FFI function: Initialize the library
+/// This should be called once before using any other library functions
+/// Returns ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_init() -> ErrorCode {
+    error::clear_last_error();
+
+    // Initialize the async runtime
+    match runtime::init_runtime() {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Initialize the library with runtime options
+///
+/// Like communicator_init(), with control over the runtime's threads:
+/// {"worker_threads": 4, "thread_name_prefix": "communicator", "shared": false}
+/// (all fields optional). "shared" reuses the Tokio runtime the calling thread
+/// runs in, for Rust programs embedding the crate. Returns
+/// ErrorCode::InvalidState if the library is already initialized with other
+/// options.
+///
+/// # Arguments
+/// * `options_json` - JSON RuntimeOptions, or NULL for the defaults
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_init_with_options(options_json: *const c_char) -> ErrorCode {
+    error::clear_last_error();
+
+    let options = if options_json.is_null() {
+        runtime::RuntimeOptions::default()
+    } else {
+        let Ok(options_str) = std::ffi::CStr::from_ptr(options_json).to_str() else {
+            error::set_last_error(Error::invalid_utf8());
+            return ErrorCode::InvalidUtf8;
+        };
+        match json_input::parse(options_str, "options", "RuntimeOptions") {
+            Ok(options) => options,
+            Err(e) => {
+                error::set_last_error(e);
+                return ErrorCode::InvalidArgument;
+            }
+        }
+    };
+
+    match runtime::init_runtime_with_options(options) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Initialize the library for a host-driven event loop
+///
+/// Use instead of communicator_init() when embedding in a loop the host owns
+/// (game engines, single-threaded GUI toolkits). The library spawns no
+/// threads: WebSocket I/O, timers and other background work only progress
+/// while the host calls communicator_tick() or another library function, on
+/// the calling thread. Events are polled; event callbacks are not available.
+/// Returns ErrorCode::InvalidState if the library is already initialized with
+/// communicator_init().
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_init_host_driven() -> ErrorCode {
+    error::clear_last_error();
+
+    match runtime::init_host_driven_runtime() {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Run ready background work from the host's event loop
+///
+/// Never waits for I/O: call it every frame or loop iteration, then poll
+/// events. Only available after communicator_init_host_driven().
+///
+/// # Arguments
+/// * `max_work` - Maximum number of passes over ready tasks, I/O and timers
+///
+/// # Returns
+/// The number of background tasks still alive, or -1 on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_tick(max_work: u32) -> i64 {
+    error::clear_last_error();
+
+    match runtime::tick(max_work) {
+        Ok(alive) => alive as i64,
+        Err(e) => {
+            error::set_last_error(e);
+            -1
+        }
+    }
+}
+
+/// FFI function: Get the metrics of the library's runtime
+/// Returns a JSON RuntimeMetrics object:
+/// {"workers": 8, "alive_tasks": 3, "global_queue_depth": 0,
+///  "host_driven": false, "shared": false}
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error (ErrorCode::InvalidState before initialization)
+#[no_mangle]
+pub extern "C" fn communicator_runtime_metrics() -> *mut c_char {
+    error::clear_last_error();
+
+    let Some(metrics) = runtime::metrics() else {
+        error::set_last_error(Error::new(
+            ErrorCode::InvalidState,
+            "The library is not initialized",
+        ));
+        return std::ptr::null_mut();
+    };
+
+    match serde_json::to_string(&metrics)
+        .ok()
+        .and_then(|json| CString::new(json).ok())
+    {
+        Some(c_string) => c_string.into_raw(),
+        None => {
+            error::set_last_error(Error::new(
+                ErrorCode::OutOfMemory,
+                "Failed to allocate string",
+            ));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Cleanup the library
+/// This should be called once when done using the library
+/// Frees any global resources allocated by the library
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_cleanup() {
+    error::clear_last_error();
+
+    // Event callbacks need the runtime
+    event_callback::clear_all();
+
+    // Shutdown the async runtime
+    runtime::shutdown_runtime();
+}
+
+/// FFI function: Set the time budget of each library call
+///
+/// A call that makes several requests (e.g. connecting, or listing channels
+/// with the partners of direct message channels) gets one deadline for all of
+/// them instead of a timeout per request, and fails with ErrorCode::Timeout
+/// once it is spent (Mattermost). Requests are still limited to 30 seconds each.
+///
+/// # Arguments
+/// * `timeout_ms` - Budget per call in milliseconds, or 0 for no budget
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_set_call_timeout(timeout_ms: u64) {
+    error::clear_last_error();
+    runtime::set_call_timeout(
+        (timeout_ms > 0).then(|| std::time::Duration::from_millis(timeout_ms)),
+    );
+}
+
+// ============================================================================
+// Version Information
+// ============================================================================
+
+/// FFI function: Get the library version string
+/// Returns a static string, do NOT free this pointer
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), " (libcommunicator)\0").as_ptr() as *const c_char
+}
+
+/// FFI function: Get the major version number
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_version_major() -> u32 {
+    VERSION_MAJOR
+}
+
+/// FFI function: Get the minor version number
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_version_minor() -> u32 {
+    VERSION_MINOR
+}
+
+/// FFI function: Get the patch version number
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_version_patch() -> u32 {
+    VERSION_PATCH
+}
+
+// ============================================================================
+// Error Handling FFI
+// ============================================================================
+
+/// FFI function: Get the error code of the last error
+/// Returns ErrorCode::Success (0) if no error has occurred
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_last_error_code() -> ErrorCode {
+    error::get_last_error()
+        .map(|e| e.code)
+        .unwrap_or(ErrorCode::Success)
+}
+
+/// FFI function: Get the error message of the last error
+/// Returns a dynamically allocated string that must be freed with communicator_free_string()
+/// Returns NULL if no error has occurred
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_last_error_message() -> *mut c_char {
+    let error = match error::get_last_error() {
+        Some(e) => e,
+        None => return std::ptr::null_mut(),
+    };
+
+    match CString::new(error.message) {
+        Ok(c_string) => c_string.into_raw(),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// FFI function: Get the limit exceeded by the last error
+/// Returns the maximum message length (characters) or file size (bytes) for
+/// MessageTooLong and FileTooLarge errors, or -1 if the last error has no limit
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_last_error_limit() -> i64 {
+    error::get_last_error()
+        .and_then(|e| e.limit())
+        .map(|limit| limit.min(i64::MAX as u64) as i64)
+        .unwrap_or(-1)
+}
+
+/// FFI function: Get where the JSON input of the last call failed to parse
+/// Returns a JSON object with "pointer" (RFC 6901), "line", "column" and "schema",
+/// or NULL if the last error did not come from parsing JSON input
+/// The string must be freed with communicator_free_string()
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_last_error_details() -> *mut c_char {
+    let Some(location) = error::get_last_error().and_then(|e| e.json_location().cloned()) else {
+        return std::ptr::null_mut();
+    };
+
+    match serde_json::to_string(&location).map(CString::new) {
+        Ok(Ok(c_string)) => c_string.into_raw(),
+        _ => std::ptr::null_mut(),
+    }
+}
+
+/// FFI function: Get a human-readable description of an error code
+/// Returns a static string, do NOT free this pointer
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_error_code_string(code: ErrorCode) -> *const c_char {
+    let s = match code {
+        ErrorCode::Success => "Success\0",
+        ErrorCode::Unknown => "Unknown error\0",
+        ErrorCode::InvalidArgument => "Invalid argument\0",
+        ErrorCode::NullPointer => "Null pointer\0",
+        ErrorCode::OutOfMemory => "Out of memory\0",
+        ErrorCode::InvalidUtf8 => "Invalid UTF-8 string\0",
+        ErrorCode::NetworkError => "Network error\0",
+        ErrorCode::AuthenticationFailed => "Authentication failed\0",
+        ErrorCode::NotFound => "Not found\0",
+        ErrorCode::PermissionDenied => "Permission denied\0",
+        ErrorCode::Timeout => "Timeout\0",
+        ErrorCode::InvalidState => "Invalid state\0",
+        ErrorCode::Unsupported => "Feature not supported\0",
+        ErrorCode::RateLimited => "Rate limit exceeded\0",
+        ErrorCode::MessageTooLong => "Message too long\0",
+        ErrorCode::FileTooLarge => "File too large\0",
+        ErrorCode::ServiceUnavailable => "Service unavailable\0",
+        ErrorCode::ChecksumMismatch => "Checksum mismatch\0",
+        ErrorCode::Cancelled => "Operation cancelled\0",
+    };
+    s.as_ptr() as *const c_char
+}
+
+/// FFI function: Clear the last error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_clear_error() {
+    error::clear_last_error();
+}
+
+/// FFI function: Get the last error of a platform handle
+/// Unlike communicator_last_error_code(), which reports the last call on the
+/// calling thread, this reports the last failed call on the handle from any
+/// thread. The error stays until another call on the handle fails, or until
+/// communicator_platform_clear_error() is called.
+/// Returns ErrorCode::Success (0) if no call on the handle has failed
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `out_message` - Receives the error message, or NULL if there is no error
+///   (may be NULL); must be freed with communicator_free_string()
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_last_error(
+    handle: PlatformHandle,
+    out_message: *mut *mut c_char,
+) -> ErrorCode {
+    let error = if handle.is_null() {
+        None
+    } else {
+        error::get_handle_error(handle as usize)
+    };
+
+    if !out_message.is_null() {
+        *out_message = error
+            .as_ref()
+            .and_then(|e| CString::new(e.message.clone()).ok())
+            .map_or(std::ptr::null_mut(), CString::into_raw);
+    }
+
+    error.map(|e| e.code).unwrap_or(ErrorCode::Success)
+}
+
+/// FFI function: Clear the last error of a platform handle
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_clear_error(handle: PlatformHandle) {
+    if !handle.is_null() {
+        error::clear_handle_error(handle as usize);
+    }
+}
+
+// ============================================================================
+// Opaque Handle Pattern - Context Management
+// ============================================================================
+
+/// Opaque handle to a Context object
+/// This is an ID in the context registry, not a pointer
+pub type ContextHandle = *mut c_void;
+
+/// FFI function: Create a new context
+/// Returns an opaque handle to the context
+/// The handle must be freed with communicator_context_destroy()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_context_create(id: *const c_char) -> ContextHandle {
+    error::clear_last_error();
+
+    if id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let id_str = {
+        match std::ffi::CStr::from_ptr(id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    handles::CONTEXTS.insert(handles::Object::new(Context::new(id_str)))
+}
+
+/// FFI function: Initialize a context
+/// Returns ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_context_initialize(handle: ContextHandle) -> ErrorCode {
+    error::clear_last_error();
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::CONTEXTS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let mut context = match object.lock() {
+        Ok(context) => context,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            return code;
+        }
+    };
+
+    match context.initialize() {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Check if a context is initialized
+/// Returns 1 if initialized, 0 if not, -1 on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_context_is_initialized(handle: ContextHandle) -> i32 {
+    error::clear_last_error();
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return -1;
+    }
+
+    let Some(object) = handles::CONTEXTS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return -1;
+    };
+
+    let context = match object.lock() {
+        Ok(context) => context,
+        Err(e) => {
+            error::set_last_error(e);
+            return -1;
+        }
+    };
+    if context.is_initialized() {
+        1
+    } else {
+        0
+    }
+}
+
+/// FFI function: Set a configuration value on a context
+/// Returns ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_context_set_config(
+    handle: ContextHandle,
+    key: *const c_char,
+    value: *const c_char,
+) -> ErrorCode {
+    error::clear_last_error();
+
+    if handle.is_null() || key.is_null() || value.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::CONTEXTS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let key_str = {
+        match std::ffi::CStr::from_ptr(key).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let value_str = {
+        match std::ffi::CStr::from_ptr(value).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let mut context = match object.lock() {
+        Ok(context) => context,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            return code;
+        }
+    };
+    context.set_config(key_str, value_str);
+    ErrorCode::Success
+}
+
+/// FFI function: Get a configuration value from a context
+/// Returns a dynamically allocated string that must be freed with communicator_free_string()
+/// Returns NULL if the key doesn't exist or on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_context_get_config(
+    handle: ContextHandle,
+    key: *const c_char,
+) -> *mut c_char {
+    error::clear_last_error();
+
+    if handle.is_null() || key.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::CONTEXTS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let key_str = {
+        match std::ffi::CStr::from_ptr(key).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let context = match object.lock() {
+        Ok(context) => context,
+        Err(e) => {
+            error::set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    match context.get_config(key_str) {
+        Some(value) => match CString::new(value.as_str()) {
+            Ok(c_string) => c_string.into_raw(),
+            Err(_) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::OutOfMemory,
+                    "Failed to allocate string",
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        None => {
+            error::set_last_error(Error::new(ErrorCode::NotFound, "Key not found"));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Shutdown a context
+/// Returns ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_context_shutdown(handle: ContextHandle) -> ErrorCode {
+    error::clear_last_error();
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::CONTEXTS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let mut context = match object.lock() {
+        Ok(context) => context,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            return code;
+        }
+    };
+
+    match context.shutdown() {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Destroy a context and free its memory
+/// After calling this, the handle is invalid and must not be used
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_context_destroy(handle: ContextHandle) {
+    if !handle.is_null() {
+        handles::CONTEXTS.remove(handle);
+    }
+}
+
+// ============================================================================
+// Callback Pattern - Function Pointers
+// ============================================================================
+
+/// FFI function: Set a log callback on a context
+/// The callback will be called for logging events, with plain text messages
+/// user_data is an opaque pointer passed back to the callback
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_context_set_log_callback(
+    handle: ContextHandle,
+    callback: LogCallback,
+    user_data: *mut c_void,
+) -> ErrorCode {
+    error::clear_last_error();
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::CONTEXTS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let in_flight = match object.lock() {
+        Ok(mut context) => context.set_log_callback(callback, user_data),
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            return code;
+        }
+    };
+    // After unlocking the context, which a call in progress may be waiting for
+    in_flight.wait();
+    ErrorCode::Success
+}
+
+/// FFI function: Set a log callback on a context receiving JSON log records
+/// Like communicator_context_set_log_callback(), but each message is a JSON
+/// object with the target, message, fields and spans of the log event
+/// user_data is an opaque pointer passed back to the callback
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_context_set_structured_log_callback(
+    handle: ContextHandle,
+    callback: LogCallback,
+    user_data: *mut c_void,
+) -> ErrorCode {
+    error::clear_last_error();
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::CONTEXTS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let in_flight = match object.lock() {
+        Ok(mut context) => context.set_structured_log_callback(callback, user_data),
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            return code;
+        }
+    };
+    // After unlocking the context, which a call in progress may be waiting for
+    in_flight.wait();
+    ErrorCode::Success
+}
+
+/// FFI function: Clear the log callback on a context
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_context_clear_log_callback(
+    handle: ContextHandle,
+) -> ErrorCode {
+    error::clear_last_error();
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::CONTEXTS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let in_flight = match object.lock() {
+        Ok(mut context) => context.clear_log_callback(),
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            return code;
+        }
+    };
+    // After unlocking the context, which a call in progress may be waiting for
+    in_flight.wait();
+    ErrorCode::Success
+}
+
+/// FFI function: Set the least severe level passed to a context's log callback
+/// The default is COMMUNICATOR_LOG_INFO; the level may be changed at any time
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_context_set_log_level(
+    handle: ContextHandle,
+    level: std::os::raw::c_int,
+) -> ErrorCode {
+    error::clear_last_error();
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(level) = LogLevel::from_raw(level) else {
+        error::set_last_error(Error::invalid_argument(format!(
+            "Invalid log level: {level}"
+        )));
+        return ErrorCode::InvalidArgument;
+    };
+
+    let Some(object) = handles::CONTEXTS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let mut context = match object.lock() {
+        Ok(context) => context,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            return code;
+        }
+    };
+    context.set_log_level(level);
+    ErrorCode::Success
+}
+
+// ============================================================================
+// Platform FFI - Opaque Handle Pattern
+// ============================================================================
+
+/// Opaque handle to a Platform object
+/// This is an ID in the platform registry, not a pointer; the platform behind it
+/// is locked per call, so a handle may be used from several threads at once
+pub type PlatformHandle = *mut c_void;
+
+/// FFI function: Create a new Mattermost platform instance
+/// Returns an opaque handle to the platform
+/// The handle must be freed with communicator_platform_destroy()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_mattermost_create(
+    server_url: *const c_char,
+) -> PlatformHandle {
+    error::clear_last_error();
+
+    if server_url.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let url_str = {
+        match std::ffi::CStr::from_ptr(server_url).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    match platforms::mattermost::MattermostPlatform::new(url_str) {
+        Ok(platform) => {
+            let wrapped = PipelinePlatform::new(Box::new(platform));
+            handles::PLATFORMS.insert(tokio::sync::RwLock::new(wrapped))
+        }
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Create a new Matrix platform instance
+/// Returns an opaque handle to the platform
+/// The handle must be freed with communicator_platform_destroy()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_matrix_create(
+    homeserver_url: *const c_char,
+) -> PlatformHandle {
+    error::clear_last_error();
+
+    if homeserver_url.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let url_str = {
+        match std::ffi::CStr::from_ptr(homeserver_url).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    match platforms::matrix::MatrixPlatform::new(url_str) {
+        Ok(platform) => {
+            let wrapped = PipelinePlatform::new(Box::new(platform));
+            handles::PLATFORMS.insert(tokio::sync::RwLock::new(wrapped))
+        }
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Create a new XMPP platform instance
+/// server is "host[:port]", or "" to connect to the domain of the JID
+/// Returns an opaque handle to the platform
+/// The handle must be freed with communicator_platform_destroy()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_xmpp_create(server: *const c_char) -> PlatformHandle {
+    error::clear_last_error();
+
+    if server.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let server_str = {
+        match std::ffi::CStr::from_ptr(server).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    match platforms::xmpp::XmppPlatform::new(server_str) {
+        Ok(platform) => {
+            let wrapped = PipelinePlatform::new(Box::new(platform));
+            handles::PLATFORMS.insert(tokio::sync::RwLock::new(wrapped))
+        }
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get a Mattermost platform instance shared within the process
+/// Consumers passing the same connection_key get the same handle, and with it
+/// the same connection, caches and event queue. The handle is created on the
+/// first call; it is reference counted and freed once every consumer has called
+/// communicator_platform_destroy() on it.
+/// Check communicator_platform_is_connected() before connecting, since another
+/// consumer may already have done so.
+/// Returns NULL on error, including when the key is in use for another server
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_mattermost_create_shared(
+    connection_key: *const c_char,
+    server_url: *const c_char,
+) -> PlatformHandle {
+    error::clear_last_error();
+
+    if connection_key.is_null() || server_url.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let key_str = match std::ffi::CStr::from_ptr(connection_key).to_str() {
+        Ok(s) => s,
+        Err(_) => {
+            error::set_last_error(Error::invalid_utf8());
+            return std::ptr::null_mut();
+        }
+    };
+
+    let url_str = match std::ffi::CStr::from_ptr(server_url).to_str() {
+        Ok(s) => s,
+        Err(_) => {
+            error::set_last_error(Error::invalid_utf8());
+            return std::ptr::null_mut();
+        }
+    };
+
+    let create = || {
+        let platform = platforms::mattermost::MattermostPlatform::new(url_str)?;
+        let wrapped = PipelinePlatform::new(Box::new(platform));
+        Ok(handles::PLATFORMS.insert(tokio::sync::RwLock::new(wrapped)) as usize)
+    };
+
+    match shared::acquire(key_str, url_str, create) {
+        Ok(handle) => handle as PlatformHandle,
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get the number of consumers sharing a platform handle
+/// Returns 0 for handles not created by communicator_mattermost_create_shared(),
+/// or -1 on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_shared_count(handle: PlatformHandle) -> i32 {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return -1;
+    }
+
+    if !handles::PLATFORMS.contains(handle) {
+        error::set_last_error(handles::invalid_handle());
+        return -1;
+    }
+
+    shared::ref_count(handle as usize) as i32
+}
+
+/// FFI function: Connect to a platform
+/// config_json: JSON string with format:
+/// {
+///   "server": "https://mattermost.example.com",
+///   "credentials": {
+///     "token": "xxx" OR "login_id": "user@example.com", "password": "xxx"
+///   },
+///   "credentials_alias": "work",
+///   "team_id": "optional-team-id",
+///   "fallback_servers": ["https://optional-other-url.example.com"],
+///   "proxy": "socks5://127.0.0.1:9050",
+///   "tls": { "ca_bundle": "/path/to/ca.pem", "client_cert": "/path/to/cert.pem",
+///            "client_key": "/path/to/key.pem", "accept_invalid_certs": false },
+///   "hosts": { "chat.example.com": "10.0.0.5" },
+///   "startup_cache": "/path/to/session.json",
+///   "download_spill_threshold": 268435456,
+///   "outbox": "/path/to/outbox.json",
+///   "retry": { "max_attempts": 3, "initial_backoff_ms": 250, "max_backoff_ms": 5000,
+///              "backoff_multiplier": 2.0, "retry_on": [502, 503, 504], "retry_posts": false },
+///   "cache": { "enabled": true, "user_ttl_secs": 300, "channel_ttl_secs": 120,
+///              "team_ttl_secs": 600, "emoji_ttl_secs": 3600, "max_entries": null,
+///              "path": "/path/to/entities.json" },
+///   "resync_on_reconnect": true,
+///   "resync_on_gap": false
+/// }
+/// Returns ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_connect(
+    handle: PlatformHandle,
+    config_json: *const c_char,
+) -> ErrorCode {
+    communicator_platform_connect_ex(handle, config_json, std::ptr::null_mut())
+}
+
+/// FFI function: Connect to a platform, cancellably
+/// Like communicator_platform_connect(); cancelling `cancellation` from another
+/// thread makes the call return ErrorCode::Cancelled
+///
+/// # Arguments
+/// * `handle` - Platform handle
+/// * `config_json` - JSON configuration, as for communicator_platform_connect()
+/// * `cancellation` - Cancellation handle, or NULL
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_connect_ex(
+    handle: PlatformHandle,
+    config_json: *const c_char,
+    cancellation: CancellationHandle,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || config_json.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+    let Some(cancellation) = cancellation_arg(cancellation) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let config_str = {
+        match std::ffi::CStr::from_ptr(config_json).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    // Parse JSON into PlatformConfig
+    #[derive(serde::Deserialize)]
+    struct ConfigJson {
+        server: String,
+        #[serde(default)]
+        credentials: std::collections::HashMap<String, String>,
+        #[serde(default)]
+        credentials_alias: Option<String>,
+        team_id: Option<String>,
+        #[serde(default)]
+        fallback_servers: Vec<String>,
+        #[serde(default)]
+        proxy: Option<String>,
+        #[serde(default)]
+        tls: tls::TlsConfig,
+        #[serde(default)]
+        hosts: std::collections::HashMap<String, String>,
+        #[serde(default)]
+        startup_cache: Option<String>,
+        #[serde(default)]
+        download_spill_threshold: Option<u64>,
+        #[serde(default)]
+        outbox: Option<String>,
+        #[serde(default)]
+        retry: types::RetryPolicy,
+        #[serde(default)]
+        cache: types::CacheConfig,
+        #[serde(default)]
+        enrich_events: bool,
+        #[serde(default)]
+        resync_on_reconnect: Option<bool>,
+        #[serde(default)]
+        resync_on_gap: bool,
+    }
+
+    let config_data: ConfigJson = match json_input::parse(config_str, "config", "Config") {
+        Ok(c) => c,
+        Err(e) => {
+            error::set_last_error(e);
+            return ErrorCode::InvalidArgument;
+        }
+    };
+
+    let mut platform_config = PlatformConfig::new(config_data.server);
+    platform_config.credentials = config_data.credentials;
+    platform_config.team_id = config_data.team_id;
+    platform_config.fallback_servers = config_data.fallback_servers;
+    platform_config.proxy = config_data.proxy;
+    platform_config.tls = config_data.tls;
+    platform_config.hosts = config_data.hosts;
+    platform_config.startup_cache = config_data.startup_cache;
+    platform_config.download_spill_threshold = config_data.download_spill_threshold;
+    platform_config.outbox = config_data.outbox;
+    platform_config.retry = config_data.retry;
+    platform_config.cache = config_data.cache;
+    platform_config.enrich_events = config_data.enrich_events;
+    platform_config.resync_on_reconnect = config_data.resync_on_reconnect.unwrap_or(true);
+    platform_config.resync_on_gap = config_data.resync_on_gap;
+    platform_config.credentials_alias = config_data.credentials_alias;
+    if let Err(e) = credentials::resolve(&mut platform_config) {
+        let code = e.code;
+        error::set_last_error(e);
+        return code;
+    }
+
+    let mut platform = object.blocking_write();
+
+    // Run async connect in blocking mode
+    match runtime::block_on(cancellation::run(
+        cancellation.as_deref(),
+        platform.connect(platform_config),
+    )) {
+        Ok(_) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Connect to a platform with MFA (Multi-Factor Authentication)
+/// This is a convenience function for platforms that require MFA during login.
+///
+/// # Arguments
+/// * `handle` - Platform handle
+/// * `config_json` - JSON string with server, credentials (login_id, password, mfa_token), and optional team_id
+///
+/// # Returns
+/// ErrorCode indicating success or failure
+///
+/// # Example JSON
+/// ```json
+/// {
+///   "server": "https://mattermost.example.com",
+///   "credentials": {
+///     "login_id": "user@example.com",
+///     "password": "password123",
+///     "mfa_token": "123456"
+///   },
+///   "team_id": "optional-team-id"
+/// }
+/// ```
+///
+/// # Note
+/// If MFA is required but not provided, the error will indicate MFA is required.
+/// Check the error message for the Mattermost error ID to determine the specific issue.
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_connect_with_mfa(
+    handle: PlatformHandle,
+    config_json: *const c_char,
+) -> ErrorCode {
+    // This function is identical to communicator_platform_connect
+    // The MFA token is passed as part of the credentials map
+    // The Mattermost platform will use it if present
+    communicator_platform_connect(handle, config_json)
+}
+
+/// FFI function: Replace an expired session with one from new credentials
+///
+/// Call this after a session_expired event: requests fail until it succeeds.
+/// Sessions from a password login without MFA are renewed automatically and
+/// only expire if logging in again fails.
+///
+/// # Arguments
+/// * `handle` - Platform handle
+/// * `credentials_json` - JSON object with the same keys as the "credentials"
+///   of communicator_platform_connect(), for the same account
+///
+/// # Returns
+/// ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_renew_session(
+    handle: PlatformHandle,
+    credentials_json: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || credentials_json.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let credentials_str = {
+        match std::ffi::CStr::from_ptr(credentials_json).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let credentials: std::collections::HashMap<String, String> =
+        match json_input::parse(credentials_str, "credentials", "string map") {
+            Ok(c) => c,
+            Err(e) => {
+                error::set_last_error(e);
+                return ErrorCode::InvalidArgument;
+            }
+        };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.renew_session(credentials)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Start a single sign-on login through an OAuth 2.0 provider
+/// Returns a dynamically allocated string containing the URL to open in a browser
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - Platform handle (connecting is not needed)
+/// * `provider` - Name of the provider (Mattermost: "gitlab", "google",
+///   "office365" or "openid")
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_begin_sso_login(
+    handle: PlatformHandle,
+    provider: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || provider.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let provider_str = {
+        match std::ffi::CStr::from_ptr(provider).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.begin_sso_login(provider_str)) {
+        Ok(url) => match CString::new(url) {
+            Ok(c_string) => c_string.into_raw(),
+            Err(_) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    "Failed to convert URL to C string",
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Finish a single sign-on login
+/// Returns a dynamically allocated string containing the session token, to
+/// connect with as the "token" credential
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - Platform handle passed to communicator_platform_begin_sso_login()
+/// * `callback_url` - The URL the provider redirected the browser to
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_complete_sso_login(
+    handle: PlatformHandle,
+    callback_url: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || callback_url.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let callback_url_str = {
+        match std::ffi::CStr::from_ptr(callback_url).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.complete_sso_login(callback_url_str)) {
+        Ok(token) => match CString::new(token) {
+            Ok(c_string) => c_string.into_raw(),
+            Err(_) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    "Failed to convert token to C string",
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Disconnect from a platform
+/// Returns ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_disconnect(handle: PlatformHandle) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let mut platform = object.blocking_write();
+
+    match runtime::block_on(platform.disconnect()) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Check if platform is connected
+/// Returns 1 if connected, 0 if not, -1 on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_is_connected(handle: PlatformHandle) -> i32 {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return -1;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return -1;
+    };
+
+    let platform = object.blocking_read();
+    if platform.is_connected() {
+        1
+    } else {
+        0
+    }
+}
+
+/// FFI function: Get connection info as JSON
+/// Returns a dynamically allocated JSON string that must be freed with communicator_free_string()
+/// Returns NULL on error or if not connected
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_connection_info(
+    handle: PlatformHandle,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    match platform.connection_info() {
+        Some(info) => match serde_json::to_string(info) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize connection info: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        None => {
+            error::set_last_error(Error::new(ErrorCode::InvalidState, "Not connected"));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get platform capabilities as JSON
+/// Includes server limits (message length, file size, request rate) once connected
+/// Returns a dynamically allocated JSON string that must be freed with communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_capabilities(
+    handle: PlatformHandle,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    match serde_json::to_string(platform.capabilities()) {
+        Ok(json) => match CString::new(json) {
+            Ok(c_string) => c_string.into_raw(),
+            Err(_) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::OutOfMemory,
+                    "Failed to allocate string",
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(Error::new(
+                ErrorCode::Unknown,
+                format!("Failed to serialize capabilities: {e}"),
+            ));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Send a message to a channel
+/// Returns a JSON string representing the created Message
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_send_message(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    text: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() || text.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let text_str = {
+        match std::ffi::CStr::from_ptr(text).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.send_message(channel_id_str, text_str)) {
+        Ok(message) => match serde_json::to_string(&message) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize message: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Send a message, splitting it into numbered parts if it is too long
+/// Returns a JSON array of the created Message objects, in order
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `channel_id` - The channel ID to send the message to
+/// * `text` - The message text
+/// * `max_len` - Maximum length of a part in characters, or 0 for the server limit
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_send_message_chunked(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    text: *const c_char,
+    max_len: u32,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() || text.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let text_str = {
+        match std::ffi::CStr::from_ptr(text).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+    let max_len = (max_len > 0).then_some(max_len as usize);
+
+    // Hooks see the whole message, not its parts
+    let text_string = match platform.pipeline().outbound(channel_id_str, text_str) {
+        Ok(text) => text,
+        Err(e) => {
+            error::set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    match runtime::block_on(chunking::send_chunked(
+        platform.inner(),
+        channel_id_str,
+        &text_string,
+        max_len,
+    )) {
+        Ok(messages) => match serde_json::to_string(&messages) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize messages: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Enable or disable reassembly of split messages
+///
+/// While enabled, parts sent with communicator_platform_send_message_chunked()
+/// are held back by communicator_platform_poll_event() until all parts have
+/// arrived, and then delivered as a single message_posted event.
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `enabled` - Non-zero to enable reassembly, 0 to disable it
+///
+/// # Returns
+/// ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_set_chunk_reassembly(
+    handle: PlatformHandle,
+    enabled: std::os::raw::c_int,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let platform = object.blocking_read();
+    platform.pipeline().set_chunk_reassembly(enabled != 0);
+    ErrorCode::Success
+}
+
+/// FFI function: Enable or disable language detection for incoming messages
+///
+/// While enabled, messages of message_posted and message_updated events get
+/// the detected language (an ISO 639-1 code) in their "language" field.
+/// Channels without their own setting follow the setting for all channels.
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `channel_id` - The channel to configure (pass NULL for all channels)
+/// * `enabled` - Non-zero to enable detection, 0 to disable it
+///
+/// # Returns
+/// ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_set_language_detection(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    enabled: std::os::raw::c_int,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let channel_id_str = if channel_id.is_null() {
+        None
+    } else {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => Some(s),
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+    platform
+        .pipeline()
+        .set_language_detection(channel_id_str, enabled != 0);
+    ErrorCode::Success
+}
+
+/// FFI function: Enable or disable mention and keyword notifications
+///
+/// While enabled, message_posted events of messages that mention the current
+/// user, mention the whole channel, contain one of the user's keywords or were
+/// posted in a direct message channel are followed by a notification_triggered
+/// event with the reason.
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `enabled` - Non-zero to enable notifications, 0 to disable them
+/// * `keywords_json` - JSON array of keywords to add to the user's own
+///   (pass NULL for none)
+///
+/// # Returns
+/// ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_set_notifications(
+    handle: PlatformHandle,
+    enabled: std::os::raw::c_int,
+    keywords_json: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let keywords = if keywords_json.is_null() {
+        Vec::new()
+    } else {
+        let Ok(keywords_str) = std::ffi::CStr::from_ptr(keywords_json).to_str() else {
+            error::set_last_error(Error::invalid_utf8());
+            return ErrorCode::InvalidUtf8;
+        };
+        match json_input::parse(keywords_str, "keywords", "string array") {
+            Ok(keywords) => keywords,
+            Err(e) => {
+                let code = e.code;
+                error::set_last_error(e);
+                return code;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+    platform
+        .pipeline()
+        .set_mention_detection(enabled != 0, keywords);
+    ErrorCode::Success
+}
+
+/// FFI function: Enable or disable coarse invalidation hints
+///
+/// While enabled, channel_list_changed, unreads_changed and
+/// profile_cache_changed events are delivered next to the granular events,
+/// each at most once per interval however many events raised it.
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `interval_ms` - Coalescing interval in milliseconds, or 0 to disable hints
+///
+/// # Returns
+/// ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_set_invalidation_hints(
+    handle: PlatformHandle,
+    interval_ms: u64,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let interval = (interval_ms > 0).then(|| std::time::Duration::from_millis(interval_ms));
+    let platform = object.blocking_read();
+    platform.pipeline().set_invalidation_hints(interval);
+    ErrorCode::Success
+}
+
+/// FFI function: Enable or disable coalescing of presence and typing events
+///
+/// While enabled, user_status_changed and user_typing events are held back
+/// and delivered once per interval: the latest status of each user whose
+/// status changed, and each user still typing in a channel, once.
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `interval_ms` - Coalescing interval in milliseconds, or 0 to disable
+///   coalescing (events held at that moment are dropped)
+///
+/// # Returns
+/// ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_set_event_coalescing(
+    handle: PlatformHandle,
+    interval_ms: u64,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let interval = (interval_ms > 0).then(|| std::time::Duration::from_millis(interval_ms));
+    let platform = object.blocking_read();
+    platform.pipeline().set_coalescing(interval);
+    ErrorCode::Success
+}
+
+/// FFI function: Detect the language of a text
+///
+/// # Arguments
+/// * `text` - The text
+///
+/// # Returns
+/// JSON {"language": "de", "confidence": 0.8} with an ISO 639-1 code, or null
+/// if the text is too short or ambiguous (not an error) or on error
+/// The caller must free the returned string using communicator_free_string()
+///
+/// # Safety
+/// The caller must ensure all pointer arguments are valid.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_detect_language(text: *const c_char) -> *mut c_char {
+    error::clear_last_error();
+
+    if text.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let text_str = match std::ffi::CStr::from_ptr(text).to_str() {
+        Ok(s) => s,
+        Err(_) => {
+            error::set_last_error(Error::invalid_utf8());
+            return std::ptr::null_mut();
+        }
+    };
+
+    let Some(detection) = language::detect(text_str) else {
+        return std::ptr::null_mut();
+    };
+    match serde_json::to_string(&detection) {
+        Ok(json) => match CString::new(json) {
+            Ok(c_str) => c_str.into_raw(),
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(Error::new(
+                ErrorCode::Unknown,
+                format!("Failed to serialize language: {e}"),
+            ));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Post a code snippet with syntax highlighting metadata
+/// Short snippets are posted as a fenced code block; snippets that are too long
+/// for a message are uploaded as a file. Returns a JSON string representing the
+/// created Message
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `channel_id` - The channel to post the snippet to
+/// * `language` - Language of the code, e.g. "rust" (pass NULL if unknown)
+/// * `code` - The code to post
+/// * `filename` - File name of the snippet (pass NULL to derive one from the language)
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_send_code_snippet(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    language: *const c_char,
+    code: *const c_char,
+    filename: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() || code.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let code_str = {
+        match std::ffi::CStr::from_ptr(code).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let language_opt = if language.is_null() {
+        None
+    } else {
+        match std::ffi::CStr::from_ptr(language).to_str() {
+            Ok(s) => Some(s),
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let filename_opt = if filename.is_null() {
+        None
+    } else {
+        match std::ffi::CStr::from_ptr(filename).to_str() {
+            Ok(s) => Some(s),
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.send_code_snippet(
+        channel_id_str,
+        language_opt,
+        code_str,
+        filename_opt,
+    )) {
+        Ok(message) => match serde_json::to_string(&message) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize message: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Quote a message, with a comment, in the quoted message's channel
+/// The quote is a blockquote with an attribution line linking to the original;
+/// the quoted reference is stored in the "libcommunicator_quote" prop.
+/// Returns a JSON string representing the created Message
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `target_id` - The ID of the message to quote
+/// * `comment` - The comment to post below the quote (pass NULL for none)
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_quote_message(
+    handle: PlatformHandle,
+    target_id: *const c_char,
+    comment: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || target_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let target_id_str = {
+        match std::ffi::CStr::from_ptr(target_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let comment_str = if comment.is_null() {
+        ""
+    } else {
+        match std::ffi::CStr::from_ptr(comment).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.quote_message(target_id_str, comment_str)) {
+        Ok(message) => match serde_json::to_string(&message) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize message: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Run a slash command in a channel
+/// Returns a JSON string representing the CommandResponse
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `channel_id` - The channel the command is run in
+/// * `command` - The full command text, starting with `/` (e.g. "/away")
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_execute_command(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    command: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() || command.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let Ok(channel_id_str) = std::ffi::CStr::from_ptr(channel_id).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+    let Ok(command_str) = std::ffi::CStr::from_ptr(command).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.execute_command(channel_id_str, command_str)) {
+        Ok(response) => match serde_json::to_string(&response) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize command response: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: List the slash commands matching a partly typed command
+/// Returns a JSON array of SlashCommand objects, sorted by trigger
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `team_id` - The team to list commands for, or NULL (or "") for the current team
+/// * `prefix` - The start of the command; a leading `/` is ignored
+/// * `limit` - Maximum number of commands to return
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_autocomplete_commands(
+    handle: PlatformHandle,
+    team_id: *const c_char,
+    prefix: *const c_char,
+    limit: u32,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || prefix.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let team_id_str = if team_id.is_null() {
+        None
+    } else {
+        let Ok(s) = std::ffi::CStr::from_ptr(team_id).to_str() else {
+            error::set_last_error(Error::invalid_utf8());
+            return std::ptr::null_mut();
+        };
+        Some(s).filter(|s| !s.is_empty())
+    };
+    let Ok(prefix_str) = std::ffi::CStr::from_ptr(prefix).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.autocomplete_commands(team_id_str, prefix_str, limit as usize))
+    {
+        Ok(commands) => match serde_json::to_string(&commands) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize commands: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Create a poll in a channel
+/// Returns a JSON string representing the created Poll
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `channel_id` - The channel to post the poll in
+/// * `question` - The poll question
+/// * `options_json` - JSON array of option strings, e.g. ["Yes", "No"]
+/// * `settings_json` - JSON PollSettings: {"anonymous": bool, "show_progress": bool,
+///   "allow_new_options": bool} (all fields optional; pass NULL for defaults)
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_create_poll(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    question: *const c_char,
+    options_json: *const c_char,
+    settings_json: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() || question.is_null() || options_json.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let question_str = {
+        match std::ffi::CStr::from_ptr(question).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let options_str = {
+        match std::ffi::CStr::from_ptr(options_json).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let options: Vec<String> = match json_input::parse(options_str, "poll options", "string array")
+    {
+        Ok(options) => options,
+        Err(e) => {
+            error::set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    let settings: types::PollSettings = if settings_json.is_null() {
+        types::PollSettings::default()
+    } else {
+        let settings_str = match std::ffi::CStr::from_ptr(settings_json).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        };
+        match json_input::parse(settings_str, "poll settings", "PollSettings") {
+            Ok(settings) => settings,
+            Err(e) => {
+                error::set_last_error(e);
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.create_poll(channel_id_str, question_str, &options, &settings))
+    {
+        Ok(poll) => match serde_json::to_string(&poll) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize poll: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get the current state of a poll
+/// Returns a JSON string representing the Poll
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `poll_id` - The poll ID
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_poll(
+    handle: PlatformHandle,
+    poll_id: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || poll_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let poll_id_str = {
+        match std::ffi::CStr::from_ptr(poll_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_poll(poll_id_str)) {
+        Ok(poll) => match serde_json::to_string(&poll) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize poll: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Vote for an option of a poll
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `poll_id` - The poll ID
+/// * `option_index` - Position of the option in the poll's options array
+///
+/// # Returns
+/// ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_vote_poll(
+    handle: PlatformHandle,
+    poll_id: *const c_char,
+    option_index: u32,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || poll_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let poll_id_str = {
+        match std::ffi::CStr::from_ptr(poll_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.vote_poll(poll_id_str, option_index as usize)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Close a poll, ending voting
+/// Returns a JSON string representing the closed Poll with its results
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `poll_id` - The poll ID
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_close_poll(
+    handle: PlatformHandle,
+    poll_id: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || poll_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let poll_id_str = {
+        match std::ffi::CStr::from_ptr(poll_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.close_poll(poll_id_str)) {
+        Ok(poll) => match serde_json::to_string(&poll) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize poll: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get all channels for the current user
+/// Returns a JSON array string of Channel objects
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_channels(handle: PlatformHandle) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_channels()) {
+        Ok(channels) => match serde_json::to_string(&channels) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize channels: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get a specific channel by ID
+/// Returns a JSON string representing the Channel
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_channel(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_channel(channel_id_str)) {
+        Ok(channel) => match serde_json::to_string(&channel) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize channel: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get recent messages from a channel
+/// Returns a JSON array string of Message objects
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_messages(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    limit: u32,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_messages(channel_id_str, limit as usize)) {
+        Ok(messages) => match serde_json::to_string(&messages) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize messages: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get members of a channel
+/// Returns a JSON array string of User objects
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_channel_members(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_channel_members(channel_id_str)) {
+        Ok(users) => match serde_json::to_string(&users) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize users: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get a specific user by ID
+/// Returns a JSON string representing the User
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_user(
+    handle: PlatformHandle,
+    user_id: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || user_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let user_id_str = {
+        match std::ffi::CStr::from_ptr(user_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_user(user_id_str)) {
+        Ok(user) => match serde_json::to_string(&user) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize user: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get the current authenticated user
+/// Returns a JSON string representing the User
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_current_user(
+    handle: PlatformHandle,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_current_user()) {
+        Ok(user) => match serde_json::to_string(&user) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize user: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Update the current user's profile
+/// Returns a JSON string representing the updated User
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `update_json` - JSON ProfileUpdate; fields left out are not changed
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_update_profile(
+    handle: PlatformHandle,
+    update_json: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || update_json.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let Ok(update_str) = std::ffi::CStr::from_ptr(update_json).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+
+    let update: types::ProfileUpdate =
+        match json_input::parse(update_str, "profile update", "ProfileUpdate") {
+            Ok(update) => update,
+            Err(e) => {
+                error::set_last_error(e);
+                return std::ptr::null_mut();
+            }
+        };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.update_profile(&update)) {
+        Ok(user) => match serde_json::to_string(&user) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize user: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Upload a new profile image for the current user
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `image_data` - The image bytes (PNG, JPEG, GIF or BMP)
+/// * `image_size` - The number of image bytes
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid and that
+/// `image_data` points to `image_size` readable bytes.
+pub unsafe extern "C" fn communicator_platform_set_profile_image(
+    handle: PlatformHandle,
+    image_data: *const u8,
+    image_size: usize,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || image_data.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let image = std::slice::from_raw_parts(image_data, image_size).to_vec();
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.set_profile_image(image)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Get the user groups visible to the current user
+/// Returns a JSON array of Group objects
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_groups(handle: PlatformHandle) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_groups()) {
+        Ok(result) => match serde_json::to_string(&result) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize groups: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get the members of a user group
+/// Returns a JSON array of User objects
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `group_id` - The group ID
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_group_members(
+    handle: PlatformHandle,
+    group_id: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || group_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let Ok(group_id_str) = std::ffi::CStr::from_ptr(group_id).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_group_members(group_id_str)) {
+        Ok(result) => match serde_json::to_string(&result) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize group members: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Find the groups mentioned in a message, with their members
+/// Returns a JSON array of GroupMention objects ({"group": Group, "members": [User]})
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `text` - The message text
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_resolve_group_mentions(
+    handle: PlatformHandle,
+    text: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || text.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let Ok(text_str) = std::ffi::CStr::from_ptr(text).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.resolve_group_mentions(text_str)) {
+        Ok(result) => match serde_json::to_string(&result) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize group mentions: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Create a direct message channel with another user
+/// Returns a JSON string representing the created Channel
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_create_direct_channel(
+    handle: PlatformHandle,
+    user_id: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || user_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let user_id_str = {
+        match std::ffi::CStr::from_ptr(user_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.create_direct_channel(user_id_str)) {
+        Ok(channel) => match serde_json::to_string(&channel) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize channel: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Create a new regular channel (public or private)
+/// Returns a JSON string representing the created Channel
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Safety
+/// The caller must ensure that all pointer arguments are valid
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_create_channel(
+    handle: PlatformHandle,
+    team_id: *const c_char,
+    name: *const c_char,
+    display_name: *const c_char,
+    is_private: i32,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || team_id.is_null() || name.is_null() || display_name.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let team_id_str = {
+        match std::ffi::CStr::from_ptr(team_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let name_str = {
+        match std::ffi::CStr::from_ptr(name).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let display_name_str = {
+        match std::ffi::CStr::from_ptr(display_name).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+    let is_private_bool = is_private != 0;
+
+    match runtime::block_on(platform.create_channel(
+        team_id_str,
+        name_str,
+        display_name_str,
+        is_private_bool,
+    )) {
+        Ok(channel) => match serde_json::to_string(&channel) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize channel: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Update a channel's properties
+/// Returns a JSON string representing the updated Channel
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - Platform handle
+/// * `channel_id` - ID of the channel to update
+/// * `display_name` - New display name (NULL to keep unchanged)
+/// * `purpose` - New purpose (NULL to keep unchanged)
+/// * `header` - New header (NULL to keep unchanged)
+///
+/// # Safety
+/// The caller must ensure that all pointer arguments are valid
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_update_channel(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    display_name: *const c_char,
+    purpose: *const c_char,
+    header: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let display_name_opt = if display_name.is_null() {
+        None
+    } else {
+        match std::ffi::CStr::from_ptr(display_name).to_str() {
+            Ok(s) => Some(s),
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let purpose_opt = if purpose.is_null() {
+        None
+    } else {
+        match std::ffi::CStr::from_ptr(purpose).to_str() {
+            Ok(s) => Some(s),
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let header_opt = if header.is_null() {
+        None
+    } else {
+        match std::ffi::CStr::from_ptr(header).to_str() {
+            Ok(s) => Some(s),
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.update_channel(
+        channel_id_str,
+        display_name_opt,
+        purpose_opt,
+        header_opt,
+    )) {
+        Ok(channel) => match serde_json::to_string(&channel) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize channel: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Delete (archive) a channel
+/// Returns ErrorCode indicating success or failure
+///
+/// # Safety
+/// The caller must ensure that all pointer arguments are valid
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_delete_channel(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.delete_channel(channel_id_str)) {
+        Ok(_) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Get all teams the user belongs to
+/// Returns a JSON string representing an array of Teams
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Safety
+/// The caller must ensure that `handle` is a valid pointer
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_teams(handle: PlatformHandle) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_teams()) {
+        Ok(teams) => match serde_json::to_string(&teams) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize teams: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get a specific team by ID
+/// Returns a JSON string representing the Team
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_team(
+    handle: PlatformHandle,
+    team_id: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || team_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let team_id_str = {
+        match std::ffi::CStr::from_ptr(team_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_team(team_id_str)) {
+        Ok(team) => match serde_json::to_string(&team) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize team: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Set the current user's status
+/// Returns ErrorCode indicating success or failure
+///
+/// # Arguments
+/// * `handle` - Platform handle
+/// * `status` - Status string: "online", "away", "dnd", or "offline"
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_set_status(
+    handle: PlatformHandle,
+    status: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || status.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let status_str = {
+        match std::ffi::CStr::from_ptr(status).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    // Convert status string to UserStatus
+    let user_status = match status_str {
+        "online" => crate::types::user::UserStatus::Online,
+        "away" => crate::types::user::UserStatus::Away,
+        "dnd" => crate::types::user::UserStatus::DoNotDisturb,
+        "offline" => crate::types::user::UserStatus::Offline,
+        _ => {
+            error::set_last_error(Error::new(
+                ErrorCode::InvalidArgument,
+                "Invalid status. Must be one of: online, away, dnd, offline",
+            ));
+            return ErrorCode::InvalidArgument;
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.set_status(user_status, None)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Get a user's status
+/// Returns a JSON string representing the status: {"status": "online"}
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_user_status(
+    handle: PlatformHandle,
+    user_id: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || user_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let user_id_str = {
+        match std::ffi::CStr::from_ptr(user_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_user_status(user_id_str)) {
+        Ok(status) => {
+            // Convert UserStatus to JSON
+            let status_str = match status {
+                crate::types::user::UserStatus::Online => "online",
+                crate::types::user::UserStatus::Away => "away",
+                crate::types::user::UserStatus::DoNotDisturb => "dnd",
+                crate::types::user::UserStatus::Offline => "offline",
+                crate::types::user::UserStatus::Unknown => "unknown",
+            };
+
+            let json = serde_json::json!({"status": status_str});
+
+            match serde_json::to_string(&json) {
+                Ok(json_str) => match CString::new(json_str) {
+                    Ok(c_string) => c_string.into_raw(),
+                    Err(_) => {
+                        error::set_last_error(Error::new(
+                            ErrorCode::OutOfMemory,
+                            "Failed to allocate string",
+                        ));
+                        std::ptr::null_mut()
+                    }
+                },
+                Err(e) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::Unknown,
+                        format!("Failed to serialize status: {e}"),
+                    ));
+                    std::ptr::null_mut()
+                }
+            }
+        }
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Send typing indicator to a channel
+/// Returns ErrorCode indicating success or failure
+///
+/// # Arguments
+/// * `handle` - Platform handle
+/// * `channel_id` - The channel ID to send typing indicator to
+/// * `parent_id` - Optional parent post ID for thread typing (pass NULL for regular channel typing)
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_send_typing_indicator(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    parent_id: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    // parent_id is optional - NULL is allowed
+    let parent_id_str = if parent_id.is_null() {
+        None
+    } else {
+        unsafe {
+            match std::ffi::CStr::from_ptr(parent_id).to_str() {
+                Ok(s) => {
+                    if s.is_empty() {
+                        None
+                    } else {
+                        Some(s)
+                    }
+                }
+                Err(_) => {
+                    error::set_last_error(Error::invalid_utf8());
+                    return ErrorCode::InvalidUtf8;
+                }
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.send_typing_indicator(channel_id_str, parent_id_str)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Request statuses for all users via WebSocket
+/// Returns the sequence number on success, or -1 on error
+/// The actual status data will arrive as a Response event with matching seq_reply
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_request_all_statuses(handle: PlatformHandle) -> i64 {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return -1;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return -1;
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.request_all_statuses()) {
+        Ok(seq) => seq,
+        Err(e) => {
+            error::set_last_error(e);
+            -1
+        }
+    }
+}
+
+/// FFI function: Request statuses for specific users via WebSocket
+/// Returns the sequence number on success, or -1 on error
+/// The actual status data will arrive as a Response event with matching seq_reply
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `user_ids_json` - JSON array of user IDs (e.g., ["user1", "user2"])
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_request_users_statuses(
+    handle: PlatformHandle,
+    user_ids_json: *const c_char,
+) -> i64 {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || user_ids_json.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return -1;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return -1;
+    };
+
+    let user_ids_json_str = {
+        match std::ffi::CStr::from_ptr(user_ids_json).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return -1;
+            }
+        }
+    };
+
+    // Parse JSON array of user IDs
+    let user_ids: Vec<String> =
+        match json_input::parse(user_ids_json_str, "user IDs", "string array") {
+            Ok(ids) => ids,
+            Err(e) => {
+                error::set_last_error(e);
+                return -1;
+            }
+        };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.request_users_statuses(user_ids)) {
+        Ok(seq) => seq,
+        Err(e) => {
+            error::set_last_error(e);
+            -1
+        }
+    }
+}
+
+/// FFI function: Subscribe to real-time events
+/// Returns ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_subscribe_events(
+    handle: PlatformHandle,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let mut platform = object.blocking_write();
+
+    match runtime::block_on(platform.subscribe_events()) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Unsubscribe from real-time events
+/// Returns ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_unsubscribe_events(
+    handle: PlatformHandle,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let mut platform = object.blocking_write();
+
+    match runtime::block_on(platform.unsubscribe_events()) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Drop real-time events before they are queued
+/// filter_json: JSON object with optional "exclude" (event categories:
+/// "messages", "reactions", "typing", "presence", "channels", "read_state",
+/// "threads", "users", "teams", "preferences", "plugins", "system") and
+/// "channel_ids" (only deliver events of these channels) fields; pass NULL to
+/// deliver every event again
+/// Returns ErrorCode indicating success or failure
+///
+/// Events raised by the library itself (connection, session and outbox
+/// events, events_missed) are never filtered.
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_set_event_filter(
+    handle: PlatformHandle,
+    filter_json: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let filter = if filter_json.is_null() {
+        types::EventFilter::default()
+    } else {
+        let Ok(filter_str) = std::ffi::CStr::from_ptr(filter_json).to_str() else {
+            error::set_last_error(Error::invalid_utf8());
+            return ErrorCode::InvalidUtf8;
+        };
+        match json_input::parse(filter_str, "event filter", "EventFilter") {
+            Ok(filter) => filter,
+            Err(e) => {
+                error::set_last_error(e);
+                return ErrorCode::InvalidArgument;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.set_event_filter(filter)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// Take the next event of a platform handle as JSON
+///
+/// # Safety
+/// The handle must be a valid platform handle
+unsafe fn next_event(handle: PlatformHandle) -> Result<Polled<String>> {
+    next_event_as(handle, |_, json| {
+        serde_json::to_string(&json).map_err(|e| {
+            Error::new(
+                ErrorCode::Unknown,
+                format!("Failed to serialize event: {e}"),
+            )
+        })
+    })
+}
+
+/// Take the next event of a platform handle and run it through the event pipeline
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `convert` - Builds the delivered form from the event and its JSON
+///
+/// # Safety
+/// The handle must be a valid platform handle
+unsafe fn next_event_as<T>(
+    handle: PlatformHandle,
+    convert: impl FnOnce(PlatformEvent, serde_json::Value) -> Result<T>,
+) -> Result<Polled<T>> {
+    let object = handles::PLATFORMS
+        .get(handle)
+        .ok_or_else(handles::invalid_handle)?;
+
+    match runtime::block_on(PipelinePlatform::next_event_shared(&object))? {
+        Polled::Event(event) => {
+            let json = event_to_json(&event);
+            convert(event, json).map(Polled::Event)
+        }
+        Polled::Skipped => Ok(Polled::Skipped),
+        Polled::Empty => Ok(Polled::Empty),
+    }
+}
+
+/// Take the next event of the platform handle at an address (for delivery threads)
+fn next_event_for_key(handle_key: usize) -> Result<Polled<String>> {
+    // SAFETY: delivery threads are stopped before their handle is destroyed
+    unsafe { next_event(handle_key as PlatformHandle) }
+}
+
+/// FFI function: Poll for the next event
+/// Returns a JSON string representing the PlatformEvent, or NULL if no events are available
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL if no events or on error; polling a platform with an event
+/// callback fails with ErrorCode::InvalidState
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_poll_event(handle: PlatformHandle) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    if !handles::PLATFORMS.contains(handle) {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    }
+
+    if event_callback::is_registered(handle as usize) {
+        error::set_last_error(Error::new(
+            ErrorCode::InvalidState,
+            "Events are delivered to the event callback",
+        ));
+        return std::ptr::null_mut();
+    }
+
+    // Events held back or dropped by the pipeline don't end the polling loop
+    match event_callback::next_delivered(|| next_event(handle)) {
+        Ok(Some(json_str)) => match CString::new(json_str) {
+            Ok(c_string) => c_string.into_raw(),
+            Err(_) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::OutOfMemory,
+                    "Failed to allocate string",
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Ok(None) => {
+            // No events available, not an error
+            std::ptr::null_mut()
+        }
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Poll for the next event as a structured event
+/// Returns the same events as communicator_platform_poll_event(), as a tagged
+/// struct instead of JSON
+/// The caller must free the returned event using communicator_event_free()
+/// Returns NULL if no events or on error; polling a platform with an event
+/// callback fails with ErrorCode::InvalidState
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_poll_event_struct(
+    handle: PlatformHandle,
+) -> *mut event_struct::StructuredEvent {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    if !handles::PLATFORMS.contains(handle) {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    }
+
+    if event_callback::is_registered(handle as usize) {
+        error::set_last_error(Error::new(
+            ErrorCode::InvalidState,
+            "Events are delivered to the event callback",
+        ));
+        return std::ptr::null_mut();
+    }
+
+    let polled = event_callback::next_delivered(|| {
+        next_event_as(handle, |event, json| {
+            Ok(event_struct::OwnedEvent::new(&event, json))
+        })
+    });
+    match polled {
+        Ok(Some(event)) => event.into_raw(),
+        Ok(None) => {
+            // No events available, not an error
+            std::ptr::null_mut()
+        }
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Free a structured event
+/// Strings in the event's payload are invalid afterwards
+#[no_mangle]
+///
+/// # Safety
+/// The event must have been returned by communicator_platform_poll_event_struct()
+/// and not freed before. NULL is ignored.
+pub unsafe extern "C" fn communicator_event_free(event: *mut event_struct::StructuredEvent) {
+    if !event.is_null() {
+        drop(event_struct::OwnedEvent::from_raw(event));
+    }
+}
+
+/// FFI function: Get the JSON representation of a structured event
+/// Returns the event as communicator_platform_poll_event() would have, for
+/// fields without a struct member
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// The event must have been returned by communicator_platform_poll_event_struct()
+/// and not freed.
+pub unsafe extern "C" fn communicator_event_to_json(
+    event: *const event_struct::StructuredEvent,
+) -> *mut c_char {
+    error::clear_last_error();
+
+    if event.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    match serde_json::to_string(event_struct::OwnedEvent::json_of(event)) {
+        Ok(json) => match CString::new(json) {
+            Ok(c_string) => c_string.into_raw(),
+            Err(_) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::OutOfMemory,
+                    "Failed to allocate string",
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(Error::new(
+                ErrorCode::Unknown,
+                format!("Failed to serialize event: {e}"),
+            ));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Define an accessor for one payload kind of structured events
+macro_rules! event_payload_accessor {
+    ($name:ident, $kind:ident, $field:ident, $payload:ty, $doc:literal) => {
+        #[doc = $doc]
+        /// Returns NULL if the event has another payload kind
+        /// The payload is owned by the event; do NOT free it
+        #[no_mangle]
+        ///
+        /// # Safety
+        /// The event must have been returned by communicator_platform_poll_event_struct()
+        /// and not freed.
+        pub unsafe extern "C" fn $name(
+            event: *const event_struct::StructuredEvent,
+        ) -> *const $payload {
+            if event.is_null() {
+                return std::ptr::null();
+            }
+            match (*event).payload(event_struct::PayloadKind::$kind) {
+                Some(payload) => &payload.$field,
+                None => std::ptr::null(),
+            }
+        }
+    };
+}
+
+event_payload_accessor!(
+    communicator_event_message,
+    Message,
+    message,
+    event_struct::MessagePayload,
+    "FFI function: Get the message payload of a structured event"
+);
+event_payload_accessor!(
+    communicator_event_channel,
+    Channel,
+    channel,
+    event_struct::ChannelPayload,
+    "FFI function: Get the channel payload of a structured event"
+);
+event_payload_accessor!(
+    communicator_event_reaction,
+    Reaction,
+    reaction,
+    event_struct::ReactionPayload,
+    "FFI function: Get the reaction payload of a structured event"
+);
+event_payload_accessor!(
+    communicator_event_preference,
+    Preference,
+    preference,
+    event_struct::PreferencePayload,
+    "FFI function: Get the preference payload of a structured event"
+);
+event_payload_accessor!(
+    communicator_event_response,
+    Response,
+    response,
+    event_struct::ResponsePayload,
+    "FFI function: Get the response payload of a structured event"
+);
+event_payload_accessor!(
+    communicator_event_error,
+    Error,
+    error,
+    event_struct::ErrorPayload,
+    "FFI function: Get the error payload of a structured event"
+);
+event_payload_accessor!(
+    communicator_event_reference,
+    Reference,
+    reference,
+    event_struct::ReferencePayload,
+    "FFI function: Get the reference payload of a structured event"
+);
+
+/// FFI function: Deliver events to a callback instead of polling
+///
+/// The callback is invoked from a library thread with each event as JSON, as
+/// soon as the platform queues it. Events go through the same pipeline as
+/// communicator_platform_poll_event() (reminders, reassembly, script hooks,
+/// webhooks, rules), which must not be called while a callback is set.
+/// Replaces any previously set callback; pass NULL to go back to polling.
+/// Not available after communicator_init_host_driven().
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `callback` - Called with the event JSON and user_data (NULL to clear)
+/// * `user_data` - Opaque pointer passed back to the callback
+///
+/// # Returns
+/// ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid, and that user_data
+/// stays valid until the callback is cleared or the platform is destroyed.
+pub unsafe extern "C" fn communicator_platform_set_event_callback(
+    handle: PlatformHandle,
+    callback: Option<event_callback::EventCallback>,
+    user_data: *mut c_void,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    match callback {
+        Some(_) if runtime::is_host_driven() => {
+            error::set_last_error(Error::new(
+                ErrorCode::InvalidState,
+                "Event callbacks need library threads; poll events from the host loop instead",
+            ));
+            return ErrorCode::InvalidState;
+        }
+        Some(callback) => {
+            let signal = object.blocking_read().event_signal();
+            event_callback::register(
+                handle as usize,
+                callback,
+                user_data,
+                signal,
+                next_event_for_key,
+            );
+        }
+        None => event_callback::clear(handle as usize),
+    }
+    ErrorCode::Success
+}
+
+// ============================================================================
+// Extended Platform FFI Functions
+// ============================================================================
+
+/// FFI function: Send a message with uploaded files attached
+/// file_ids_json: JSON array of file IDs returned by communicator_platform_upload_file(),
+/// e.g. ["file-id-1", "file-id-2"]
+/// Returns a JSON string representing the created Message
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `channel_id` - The channel the files were uploaded to
+/// * `text` - The message text (may be empty)
+/// * `file_ids_json` - The files to attach
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_send_message_with_attachments(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    text: *const c_char,
+    file_ids_json: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() || text.is_null() || file_ids_json.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let (Ok(channel_id_str), Ok(text_str), Ok(file_ids_str)) = (
+        std::ffi::CStr::from_ptr(channel_id).to_str(),
+        std::ffi::CStr::from_ptr(text).to_str(),
+        std::ffi::CStr::from_ptr(file_ids_json).to_str(),
+    ) else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+
+    let file_ids: Vec<String> = match json_input::parse(file_ids_str, "file IDs", "string array") {
+        Ok(file_ids) => file_ids,
+        Err(e) => {
+            error::set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.send_message_with_attachments(
+        channel_id_str,
+        text_str,
+        file_ids,
+    )) {
+        Ok(message) => match serde_json::to_string(&message) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize message: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Send a message with options
+/// options_json: JSON object with any of "root_id", "props", "file_ids",
+/// "priority" ("standard", "important" or "urgent"), "requested_ack" and
+/// "scheduled_at" (RFC 3339); NULL sends a plain message
+/// Returns a JSON string representing the created Message
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `channel_id` - The channel to send the message to
+/// * `text` - The message text (may be empty if files are attached)
+/// * `options_json` - The send options, or NULL
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_send_message_ex(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    text: *const c_char,
+    options_json: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() || text.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let (Ok(channel_id_str), Ok(text_str)) = (
+        std::ffi::CStr::from_ptr(channel_id).to_str(),
+        std::ffi::CStr::from_ptr(text).to_str(),
+    ) else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+
+    let options: types::SendOptions = if options_json.is_null() {
+        types::SendOptions::default()
+    } else {
+        let Ok(options_str) = std::ffi::CStr::from_ptr(options_json).to_str() else {
+            error::set_last_error(Error::invalid_utf8());
+            return std::ptr::null_mut();
+        };
+        match json_input::parse(options_str, "send options", "SendOptions") {
+            Ok(options) => options,
+            Err(e) => {
+                error::set_last_error(e);
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.send_message_ex(channel_id_str, text_str, &options)) {
+        Ok(message) => match serde_json::to_string(&message) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize message: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Send a reply to a message (threaded conversation)
+/// Returns a JSON string representing the created Message
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_send_reply(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    text: *const c_char,
+    root_id: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() || text.is_null() || root_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let text_str = {
+        match std::ffi::CStr::from_ptr(text).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let root_id_str = {
+        match std::ffi::CStr::from_ptr(root_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.send_reply(channel_id_str, text_str, root_id_str)) {
+        Ok(message) => match serde_json::to_string(&message) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize message: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Send a message with an overridden sender name and/or avatar
+/// Returns a JSON string representing the created Message
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `channel_id` - The channel to send the message to
+/// * `text` - The message text
+/// * `username` - Display name override (pass NULL to keep the sender's name)
+/// * `icon_url` - Avatar URL override (pass NULL to keep the sender's avatar)
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_send_message_as(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    text: *const c_char,
+    username: *const c_char,
+    icon_url: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() || text.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let text_str = {
+        match std::ffi::CStr::from_ptr(text).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let username_opt = if username.is_null() {
+        None
+    } else {
+        match std::ffi::CStr::from_ptr(username).to_str() {
+            Ok(s) => Some(s),
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let icon_url_opt = if icon_url.is_null() {
+        None
+    } else {
+        match std::ffi::CStr::from_ptr(icon_url).to_str() {
+            Ok(s) => Some(s),
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.send_message_as(
+        channel_id_str,
+        text_str,
+        username_opt,
+        icon_url_opt,
+    )) {
+        Ok(message) => match serde_json::to_string(&message) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize message: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Update/edit a message
+/// Returns a JSON string representing the updated Message
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_update_message(
+    handle: PlatformHandle,
+    message_id: *const c_char,
+    new_text: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || message_id.is_null() || new_text.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let message_id_str = {
+        match std::ffi::CStr::from_ptr(message_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let text_str = {
+        match std::ffi::CStr::from_ptr(new_text).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.update_message(message_id_str, text_str)) {
+        Ok(message) => match serde_json::to_string(&message) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize message: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Delete a message
+/// Returns ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_delete_message(
+    handle: PlatformHandle,
+    message_id: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || message_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let message_id_str = {
+        match std::ffi::CStr::from_ptr(message_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.delete_message(message_id_str)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Get a specific message by ID
+/// Returns a JSON string representing the Message
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_message(
+    handle: PlatformHandle,
+    message_id: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || message_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let message_id_str = {
+        match std::ffi::CStr::from_ptr(message_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_message(message_id_str)) {
+        Ok(message) => match serde_json::to_string(&message) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize message: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get the messages of a channel around a date ("jump to date")
+/// Returns a JSON array string of Message objects, oldest first
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `channel_id` - The channel ID
+/// * `date` - A calendar date ("YYYY-MM-DD", start of day in UTC) or an RFC 3339 timestamp
+/// * `limit` - Maximum number of messages to retrieve
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_messages_at_date(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    date: *const c_char,
+    limit: u32,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() || date.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let date_str = {
+        match std::ffi::CStr::from_ptr(date).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let date = match parse_date_time(date_str) {
+        Some(date) => date,
+        None => {
+            error::set_last_error(Error::invalid_argument(
+                "Invalid date. Expected YYYY-MM-DD or an RFC 3339 timestamp",
+            ));
+            return std::ptr::null_mut();
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_messages_at_date(channel_id_str, date, limit as usize)) {
+        Ok(messages) => match serde_json::to_string(&messages) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize messages: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Parse a calendar date (start of day in UTC) or an RFC 3339 timestamp
+fn parse_date_time(value: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    if let Ok(date_time) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Some(date_time.with_timezone(&chrono::Utc));
+    }
+    chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|date_time| date_time.and_utc())
+}
+
+/// FFI function: Get messages before a specific message (pagination)
+/// Returns a JSON array string of Message objects
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_messages_before(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    before_id: *const c_char,
+    limit: u32,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() || before_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let before_id_str = {
+        match std::ffi::CStr::from_ptr(before_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_messages_before(
+        channel_id_str,
+        before_id_str,
+        limit as usize,
+    )) {
+        Ok(messages) => match serde_json::to_string(&messages) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize messages: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get messages after a specific message (pagination)
+/// Returns a JSON array string of Message objects
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_messages_after(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    after_id: *const c_char,
+    limit: u32,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() || after_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let after_id_str = {
+        match std::ffi::CStr::from_ptr(after_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_messages_after(
+        channel_id_str,
+        after_id_str,
+        limit as usize,
+    )) {
+        Ok(messages) => match serde_json::to_string(&messages) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize messages: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get the most recent messages of a channel as a page
+/// Returns a JSON string: {"items": [Message, ...], "next_cursor": "..." | null}
+/// Pass next_cursor to communicator_platform_next_page() to fetch older messages
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_messages_page(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    limit: u32,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    let page = match runtime::block_on(platform.get_messages_page(channel_id_str, limit as usize)) {
+        Ok(page) => page,
+        Err(e) => {
+            error::set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    match serde_json::to_string(&page) {
+        Ok(json) => match CString::new(json) {
+            Ok(c_string) => c_string.into_raw(),
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(Error::new(
+                ErrorCode::Unknown,
+                format!("Failed to serialize page: {e}"),
+            ));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Fetch the page a pagination cursor points to
+/// Works with the next_cursor of any paginated list
+/// Returns a JSON string: {"items": [...], "next_cursor": "..." | null}
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_next_page(
+    handle: PlatformHandle,
+    cursor: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || cursor.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let cursor_str = {
+        match std::ffi::CStr::from_ptr(cursor).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+    let cursor = types::Cursor::from_string(cursor_str);
+
+    let page = match runtime::block_on(platform.next_page(&cursor)) {
+        Ok(page) => page,
+        Err(e) => {
+            error::set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    match serde_json::to_string(&page) {
+        Ok(json) => match CString::new(json) {
+            Ok(c_string) => c_string.into_raw(),
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(Error::new(
+                ErrorCode::Unknown,
+                format!("Failed to serialize page: {e}"),
+            ));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Add a reaction to a message
+/// Returns error code indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_add_reaction(
+    handle: PlatformHandle,
+    message_id: *const c_char,
+    emoji_name: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || message_id.is_null() || emoji_name.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let message_id_str = {
+        match std::ffi::CStr::from_ptr(message_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let emoji_name_str = {
+        match std::ffi::CStr::from_ptr(emoji_name).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.add_reaction(message_id_str, emoji_name_str)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Remove a reaction from a message
+/// Returns error code indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_remove_reaction(
+    handle: PlatformHandle,
+    message_id: *const c_char,
+    emoji_name: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || message_id.is_null() || emoji_name.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let message_id_str = {
+        match std::ffi::CStr::from_ptr(message_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let emoji_name_str = {
+        match std::ffi::CStr::from_ptr(emoji_name).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.remove_reaction(message_id_str, emoji_name_str)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Get the reactions to a message
+/// Returns a JSON array of Reaction objects ("emoji", "user_id", "created_at"),
+/// oldest first
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `message_id` - The message ID
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_reactions(
+    handle: PlatformHandle,
+    message_id: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || message_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let Ok(message_id_str) = std::ffi::CStr::from_ptr(message_id).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_reactions(message_id_str)) {
+        Ok(reactions) => match serde_json::to_string(&reactions) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize reactions: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Pin a message/post to its channel
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_platform_pin_post(
+    handle: PlatformHandle,
+    message_id: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || message_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let message_id_str = {
+        match std::ffi::CStr::from_ptr(message_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.pin_post(message_id_str)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// Unpin a message/post from its channel
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_platform_unpin_post(
+    handle: PlatformHandle,
+    message_id: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || message_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let message_id_str = {
+        match std::ffi::CStr::from_ptr(message_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.unpin_post(message_id_str)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// Get all pinned messages/posts for a channel
+///
+/// Returns a JSON string containing an array of pinned messages.
+/// The returned string must be freed using `communicator_free_string()`.
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_platform_get_pinned_posts(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_pinned_posts(channel_id_str)) {
+        Ok(messages) => match serde_json::to_string(&messages) {
+            Ok(json) => match std::ffi::CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::Unknown,
+                        "Failed to convert JSON to C string".to_string(),
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize pinned posts: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Save a message for the current user
+/// Returns ErrorCode indicating success or failure
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `message_id` - The ID of the message
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_flag_post(
+    handle: PlatformHandle,
+    message_id: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || message_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let Ok(message_id_str) = std::ffi::CStr::from_ptr(message_id).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return ErrorCode::InvalidUtf8;
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.flag_post(message_id_str)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Remove a message from the current user's saved messages
+/// Returns ErrorCode indicating success or failure
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `message_id` - The ID of the message
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_unflag_post(
+    handle: PlatformHandle,
+    message_id: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || message_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let Ok(message_id_str) = std::ffi::CStr::from_ptr(message_id).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return ErrorCode::InvalidUtf8;
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.unflag_post(message_id_str)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Get the current user's saved messages
+/// Returns a JSON string: {"items": [Message, ...], "next_cursor": "..." | null}
+/// Pass next_cursor to communicator_platform_next_page() to fetch more messages
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `page` - Page number (0-indexed)
+/// * `per_page` - Number of messages per page
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_flagged_posts(
+    handle: PlatformHandle,
+    page: u32,
+    per_page: u32,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    let page = match runtime::block_on(platform.get_flagged_posts(page, per_page)) {
+        Ok(page) => page,
+        Err(e) => {
+            error::set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    match serde_json::to_string(&page) {
+        Ok(json) => match CString::new(json) {
+            Ok(c_string) => c_string.into_raw(),
+            Err(_) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::OutOfMemory,
+                    "Failed to allocate string",
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(Error::new(
+                ErrorCode::Unknown,
+                format!("Failed to serialize saved messages: {e}"),
+            ));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get a list of custom emojis
+/// Returns a JSON string representing a Vec<Emoji>
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_emojis(
+    handle: PlatformHandle,
+    page: u32,
+    per_page: u32,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_emojis(page, per_page)) {
+        Ok(emojis) => match serde_json::to_string(&emojis) {
+            Ok(json_str) => match CString::new(json_str) {
+                Ok(c_str) => c_str.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::invalid_utf8());
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize emojis: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get the first page of custom emojis
+/// Returns a JSON string: {"items": [Emoji, ...], "next_cursor": "..." | null}
+/// Pass next_cursor to communicator_platform_next_page() to fetch more emojis
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_emojis_page(
+    handle: PlatformHandle,
+    per_page: u32,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    let page = match runtime::block_on(platform.get_emojis_page(per_page)) {
+        Ok(page) => page,
+        Err(e) => {
+            error::set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    match serde_json::to_string(&page) {
+        Ok(json) => match CString::new(json) {
+            Ok(c_string) => c_string.into_raw(),
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(Error::new(
+                ErrorCode::Unknown,
+                format!("Failed to serialize page: {e}"),
+            ));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get the image for a custom emoji by name or ID
+///
+/// Images are cached by the library, so this can be called for every custom
+/// emoji occurrence when rendering messages.
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `name_or_id` - The emoji name (colons optional) or emoji ID
+/// * `out_data` - Output parameter for the image data (caller must free with communicator_free_file_data)
+/// * `out_size` - Output parameter for the size of the image data in bytes
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_emoji_image(
+    handle: PlatformHandle,
+    name_or_id: *const c_char,
+    out_data: *mut *mut u8,
+    out_size: *mut usize,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || name_or_id.is_null() || out_data.is_null() || out_size.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let name_or_id_str = {
+        match std::ffi::CStr::from_ptr(name_or_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_emoji_image(name_or_id_str)) {
+        Ok(data) => {
+            let size = data.len();
+            let boxed_data = data.into_boxed_slice();
+            let raw_ptr = Box::into_raw(boxed_data) as *mut u8;
+
+            *out_data = raw_ptr;
+            *out_size = size;
+            ErrorCode::Success
+        }
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Create a custom emoji
+/// Returns a JSON string representing the created Emoji
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `name` - The emoji name (without colons)
+/// * `image_data` - The image bytes (PNG, JPEG or GIF)
+/// * `image_size` - The number of image bytes
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid and that
+/// `image_data` points to `image_size` readable bytes.
+pub unsafe extern "C" fn communicator_platform_create_emoji(
+    handle: PlatformHandle,
+    name: *const c_char,
+    image_data: *const u8,
+    image_size: usize,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || name.is_null() || image_data.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let Ok(name_str) = std::ffi::CStr::from_ptr(name).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+    let image = std::slice::from_raw_parts(image_data, image_size).to_vec();
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.create_emoji(name_str, image)) {
+        Ok(emoji) => match serde_json::to_string(&emoji) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize emoji: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Delete a custom emoji
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `emoji_id` - The ID of the emoji
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_delete_emoji(
+    handle: PlatformHandle,
+    emoji_id: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || emoji_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let Ok(emoji_id_str) = std::ffi::CStr::from_ptr(emoji_id).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return ErrorCode::InvalidUtf8;
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.delete_emoji(emoji_id_str)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Suggest custom emojis for a partly typed name
+/// Returns a JSON array of Emoji objects; standard emojis are suggested by
+/// communicator_emoji_autocomplete()
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `prefix` - The start of the emoji name (a leading colon is ignored)
+/// * `limit` - Maximum number of suggestions
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_autocomplete_emojis(
+    handle: PlatformHandle,
+    prefix: *const c_char,
+    limit: u32,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || prefix.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let Ok(prefix_str) = std::ffi::CStr::from_ptr(prefix).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.autocomplete_emojis(prefix_str, limit as usize)) {
+        Ok(emojis) => match serde_json::to_string(&emojis) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize emojis: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Resolve a standard emoji shortcode to its emoji
+///
+/// # Arguments
+/// * `shortcode` - The shortcode, with or without surrounding colons
+///
+/// # Returns
+/// The emoji as a UTF-8 string, or NULL if the shortcode is unknown (not an
+/// error; it may name a custom emoji) or on error
+/// The caller must free the returned string using communicator_free_string()
+///
+/// # Safety
+/// The caller must ensure all pointer arguments are valid.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_emoji_resolve(shortcode: *const c_char) -> *mut c_char {
+    error::clear_last_error();
+
+    if shortcode.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Ok(shortcode_str) = std::ffi::CStr::from_ptr(shortcode).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+
+    match emoji::resolve(shortcode_str).map(CString::new) {
+        Some(Ok(c_string)) => c_string.into_raw(),
+        _ => std::ptr::null_mut(),
+    }
+}
+
+/// FFI function: Replace the standard emoji `:shortcodes:` in a text with
+/// their emojis
+///
+/// Shortcodes in inline code and code blocks are kept, as are unknown ones
+/// (such as custom emojis).
+///
+/// # Arguments
+/// * `text` - The text
+///
+/// # Returns
+/// The text with emojis, or NULL on error
+/// The caller must free the returned string using communicator_free_string()
+///
+/// # Safety
+/// The caller must ensure all pointer arguments are valid.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_emoji_replace_shortcodes(text: *const c_char) -> *mut c_char {
+    error::clear_last_error();
+
+    if text.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Ok(text_str) = std::ffi::CStr::from_ptr(text).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+
+    match CString::new(emoji::replace_shortcodes(text_str)) {
+        Ok(c_string) => c_string.into_raw(),
+        Err(_) => {
+            error::set_last_error(Error::new(
+                ErrorCode::OutOfMemory,
+                "Failed to allocate string",
+            ));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Suggest standard emojis for a partly typed shortcode
+///
+/// # Arguments
+/// * `prefix` - What was typed, with or without a leading colon
+/// * `limit` - Maximum number of suggestions
+///
+/// # Returns
+/// A JSON array [{"name": "thumbsup", "unicode": "👍"}, ...] of emojis whose
+/// shortcode starts with the prefix, then those containing it; NULL on error
+/// The caller must free the returned string using communicator_free_string()
+///
+/// # Safety
+/// The caller must ensure all pointer arguments are valid.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_emoji_autocomplete(
+    prefix: *const c_char,
+    limit: u32,
+) -> *mut c_char {
+    error::clear_last_error();
+
+    if prefix.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Ok(prefix_str) = std::ffi::CStr::from_ptr(prefix).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+
+    match serde_json::to_string(&emoji::autocomplete(prefix_str, limit as usize)) {
+        Ok(json) => match CString::new(json) {
+            Ok(c_string) => c_string.into_raw(),
+            Err(_) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::OutOfMemory,
+                    "Failed to allocate string",
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(Error::new(
+                ErrorCode::Unknown,
+                format!("Failed to serialize emojis: {e}"),
+            ));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get a channel by name
+/// Returns a JSON string representing the Channel
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_channel_by_name(
+    handle: PlatformHandle,
+    team_id: *const c_char,
+    channel_name: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || team_id.is_null() || channel_name.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let team_id_str = {
+        match std::ffi::CStr::from_ptr(team_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let channel_name_str = {
+        match std::ffi::CStr::from_ptr(channel_name).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_channel_by_name(team_id_str, channel_name_str)) {
+        Ok(channel) => match serde_json::to_string(&channel) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize channel: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Create a group direct message channel
+/// user_ids_json: JSON array of user IDs, e.g. ["user1", "user2", "user3"]
+/// Returns a JSON string representing the created Channel
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_create_group_channel(
+    handle: PlatformHandle,
+    user_ids_json: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || user_ids_json.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let user_ids_str = {
+        match std::ffi::CStr::from_ptr(user_ids_json).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    // Parse JSON array of user IDs
+    let user_ids: Vec<String> = match json_input::parse(user_ids_str, "user IDs", "string array") {
+        Ok(ids) => ids,
+        Err(e) => {
+            error::set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.create_group_channel(user_ids)) {
+        Ok(channel) => match serde_json::to_string(&channel) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize channel: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Add a user to a channel
+/// Returns ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_add_channel_member(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    user_id: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() || user_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let user_id_str = {
+        match std::ffi::CStr::from_ptr(user_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.add_channel_member(channel_id_str, user_id_str)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Remove a user from a channel
+/// Returns ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_remove_channel_member(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    user_id: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() || user_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let user_id_str = {
+        match std::ffi::CStr::from_ptr(user_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.remove_channel_member(channel_id_str, user_id_str)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Get a member's roles in a channel
+/// Returns a JSON string representing the ChannelMemberRoles
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `channel_id` - The channel ID
+/// * `user_id` - The user ID of the member
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_channel_member_roles(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    user_id: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() || user_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let Ok(channel_id_str) = std::ffi::CStr::from_ptr(channel_id).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+    let Ok(user_id_str) = std::ffi::CStr::from_ptr(user_id).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_channel_member_roles(channel_id_str, user_id_str)) {
+        Ok(roles) => match serde_json::to_string(&roles) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize channel member roles: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Make a channel member a channel admin, or revoke it
+/// Returns ErrorCode indicating success or failure
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `channel_id` - The channel ID
+/// * `user_id` - The user ID of the member
+/// * `is_admin` - 1 to make the member a channel admin, 0 to revoke it
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_set_channel_admin(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    user_id: *const c_char,
+    is_admin: i32,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() || user_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let Ok(channel_id_str) = std::ffi::CStr::from_ptr(channel_id).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return ErrorCode::InvalidUtf8;
+    };
+    let Ok(user_id_str) = std::ffi::CStr::from_ptr(user_id).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return ErrorCode::InvalidUtf8;
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.set_channel_admin(channel_id_str, user_id_str, is_admin != 0))
+    {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Convert a channel between public and private
+/// Returns a JSON string representing the updated Channel
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `channel_id` - The channel ID
+/// * `is_private` - 1 to make the channel private, 0 to make it public
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_set_channel_privacy(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    is_private: i32,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let Ok(channel_id_str) = std::ffi::CStr::from_ptr(channel_id).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.set_channel_privacy(channel_id_str, is_private != 0)) {
+        Ok(channel) => match serde_json::to_string(&channel) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize channel: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get a user by username
+/// Returns a JSON string representing the User
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_user_by_username(
+    handle: PlatformHandle,
+    username: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || username.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let username_str = {
+        match std::ffi::CStr::from_ptr(username).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_user_by_username(username_str)) {
+        Ok(user) => match serde_json::to_string(&user) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize user: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get a user by email
+/// Returns a JSON string representing the User
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_user_by_email(
+    handle: PlatformHandle,
+    email: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || email.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let email_str = {
+        match std::ffi::CStr::from_ptr(email).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_user_by_email(email_str)) {
+        Ok(user) => match serde_json::to_string(&user) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize user: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get multiple users by their IDs (batch operation)
+/// user_ids_json: JSON array of user IDs, e.g. ["user1", "user2", "user3"]
+/// Returns a JSON array string of User objects
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_users_by_ids(
+    handle: PlatformHandle,
+    user_ids_json: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || user_ids_json.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let user_ids_str = {
+        match std::ffi::CStr::from_ptr(user_ids_json).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    // Parse JSON array of user IDs
+    let user_ids: Vec<String> = match json_input::parse(user_ids_str, "user IDs", "string array") {
+        Ok(ids) => ids,
+        Err(e) => {
+            error::set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_users_by_ids(user_ids)) {
+        Ok(users) => match serde_json::to_string(&users) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize users: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get a user's profile image
+///
+/// Images are cached by the library and can be warmed for the visible
+/// channels with communicator_platform_prefetch_channel_profiles().
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `user_id` - The ID of the user
+/// * `out_data` - Output parameter for the image data (caller must free with communicator_free_file_data)
+/// * `out_size` - Output parameter for the size of the image data in bytes
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_user_avatar(
+    handle: PlatformHandle,
+    user_id: *const c_char,
+    out_data: *mut *mut u8,
+    out_size: *mut usize,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || user_id.is_null() || out_data.is_null() || out_size.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let user_id_str = match std::ffi::CStr::from_ptr(user_id).to_str() {
+        Ok(s) => s,
+        Err(_) => {
+            error::set_last_error(Error::invalid_utf8());
+            return ErrorCode::InvalidUtf8;
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_user_avatar(user_id_str)) {
+        Ok(data) => {
+            let size = data.len();
+            let boxed_data = data.into_boxed_slice();
+            let raw_ptr = Box::into_raw(boxed_data) as *mut u8;
+
+            *out_data = raw_ptr;
+            *out_size = size;
+            ErrorCode::Success
+        }
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Warm the profile caches for the channels visible in the UI
+/// channel_ids_json: JSON array of channel IDs, e.g. ["channel1", "channel2"];
+/// an empty array only cancels a running prefetch
+/// Returns ErrorCode indicating success or failure
+///
+/// The prefetch runs in the background; this returns immediately. Each call
+/// cancels the prefetch started by the previous one.
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_prefetch_channel_profiles(
+    handle: PlatformHandle,
+    channel_ids_json: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_ids_json.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let channel_ids_str = match std::ffi::CStr::from_ptr(channel_ids_json).to_str() {
+        Ok(s) => s,
+        Err(_) => {
+            error::set_last_error(Error::invalid_utf8());
+            return ErrorCode::InvalidUtf8;
+        }
+    };
+
+    let channel_ids: Vec<String> =
+        match json_input::parse(channel_ids_str, "channel IDs", "string array") {
+            Ok(ids) => ids,
+            Err(e) => {
+                error::set_last_error(e);
+                return ErrorCode::InvalidArgument;
+            }
+        };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.prefetch_channel_profiles(channel_ids)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Set a custom status message
+/// custom_status_json: JSON object with format:
+/// {
+///   "emoji": "optional-emoji",
+///   "text": "status text",
+///   "expires_at": 1234567890  // Optional Unix timestamp
+/// }
+/// Returns ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_set_custom_status(
+    handle: PlatformHandle,
+    custom_status_json: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || custom_status_json.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let status_str = {
+        match std::ffi::CStr::from_ptr(custom_status_json).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    // Parse custom status JSON
+    #[derive(serde::Deserialize)]
+    struct CustomStatusJson {
+        emoji: Option<String>,
+        text: String,
+        expires_at: Option<i64>,
+    }
+
+    let status_data: CustomStatusJson =
+        match json_input::parse(status_str, "custom status", "CustomStatus") {
+            Ok(s) => s,
+            Err(e) => {
+                error::set_last_error(e);
+                return ErrorCode::InvalidArgument;
+            }
+        };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.set_custom_status(
+        status_data.emoji.as_deref(),
+        &status_data.text,
+        status_data.expires_at,
+    )) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Remove/clear the current user's custom status
+/// Returns ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_remove_custom_status(
+    handle: PlatformHandle,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.remove_custom_status()) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Get status for multiple users (batch operation)
+/// user_ids_json: JSON array of user IDs, e.g. ["user1", "user2", "user3"]
+/// Returns a JSON object mapping user IDs to status strings: {"user1": "online", "user2": "away", ...}
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_users_status(
+    handle: PlatformHandle,
+    user_ids_json: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || user_ids_json.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let user_ids_str = {
+        match std::ffi::CStr::from_ptr(user_ids_json).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    // Parse JSON array of user IDs
+    let user_ids: Vec<String> = match json_input::parse(user_ids_str, "user IDs", "string array") {
+        Ok(ids) => ids,
+        Err(e) => {
+            error::set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_users_status(user_ids)) {
+        Ok(status_map) => {
+            // Convert UserStatus enum to strings
+            let status_strings: std::collections::HashMap<String, String> = status_map
+                .into_iter()
+                .map(|(id, status)| {
+                    let status_str = match status {
+                        crate::types::user::UserStatus::Online => "online",
+                        crate::types::user::UserStatus::Away => "away",
+                        crate::types::user::UserStatus::DoNotDisturb => "dnd",
+                        crate::types::user::UserStatus::Offline => "offline",
+                        crate::types::user::UserStatus::Unknown => "unknown",
+                    };
+                    (id, status_str.to_string())
+                })
+                .collect();
+
+            match serde_json::to_string(&status_strings) {
+                Ok(json) => match CString::new(json) {
+                    Ok(c_string) => c_string.into_raw(),
+                    Err(_) => {
+                        error::set_last_error(Error::new(
+                            ErrorCode::OutOfMemory,
+                            "Failed to allocate string",
+                        ));
+                        std::ptr::null_mut()
+                    }
+                },
+                Err(e) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::Unknown,
+                        format!("Failed to serialize status map: {e}"),
+                    ));
+                    std::ptr::null_mut()
+                }
+            }
+        }
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get the users currently typing in a channel
+/// Returns a JSON array of user IDs, e.g. ["user1", "user2"]
+/// Typing state is cleared when the event connection is re-established
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_typing_users(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_typing_users(channel_id_str)) {
+        Ok(user_ids) => match serde_json::to_string(&user_ids) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::Unknown,
+                        "Failed to convert JSON to C string".to_string(),
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize typing users: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get a team by name
+/// Returns a JSON string representing the Team
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Safety
+/// The caller must ensure that `handle` and `team_name` are valid pointers
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_team_by_name(
+    handle: PlatformHandle,
+    team_name: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || team_name.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let team_name_str = match std::ffi::CStr::from_ptr(team_name).to_str() {
+        Ok(s) => s,
+        Err(_) => {
+            error::set_last_error(Error::invalid_utf8());
+            return std::ptr::null_mut();
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_team_by_name(team_name_str)) {
+        Ok(team) => match serde_json::to_string(&team) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize team: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Set the active team/workspace ID
+/// team_id: The team ID to set as active (pass NULL to unset)
+/// Returns ErrorCode indicating success or failure
+///
+/// # Safety
+/// The caller must ensure that `handle` is a valid pointer.
+/// If `team_id` is not NULL, it must be a valid C string pointer.
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_set_team_id(
+    handle: PlatformHandle,
+    team_id: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    // team_id can be NULL (to unset the team ID)
+    let team_id_opt = if team_id.is_null() {
+        None
+    } else {
+        let team_id_str = match std::ffi::CStr::from_ptr(team_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        };
+        Some(team_id_str.to_string())
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.set_team_id(team_id_opt)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Configure the concurrent request limit and the circuit breaker
+/// config_json: JSON object with optional max_concurrent_requests, failure_threshold
+/// and cooldown_ms fields; missing fields keep their defaults (8, 5 and 30000)
+/// Returns ErrorCode indicating success or failure
+///
+/// While the circuit is open, requests fail with ServiceUnavailable. State
+/// changes are reported as "circuit_state_changed" events.
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_set_circuit_breaker(
+    handle: PlatformHandle,
+    config_json: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || config_json.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let config_str = match std::ffi::CStr::from_ptr(config_json).to_str() {
+        Ok(s) => s,
+        Err(_) => {
+            error::set_last_error(Error::invalid_utf8());
+            return ErrorCode::InvalidUtf8;
+        }
+    };
+
+    let config: types::CircuitBreakerConfig =
+        match json_input::parse(config_str, "circuit breaker config", "CircuitBreakerConfig") {
+            Ok(c) => c,
+            Err(e) => {
+                error::set_last_error(e);
+                return ErrorCode::InvalidArgument;
+            }
+        };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.set_circuit_breaker_config(config)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Get the state of the circuit breaker
+/// Returns a JSON string: {"state": "closed"}, "open" or "half_open"
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_circuit_state(
+    handle: PlatformHandle,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_circuit_state()) {
+        Ok(state) => {
+            let json = serde_json::json!({ "state": state });
+            match CString::new(json.to_string()) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            }
+        }
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get the health of a platform's real-time connection
+/// Returns a JSON ConnectionHealth object:
+/// {"state": "connected", "last_pong_ms_ago": 1200, "last_message_ms_ago": 300,
+///  "reconnect_attempts": 0, "reconnects": 1, "backoff_ms": null,
+///  "events_per_sec": 2.5}
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_connection_health(
+    handle: PlatformHandle,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.connection_health()) {
+        Ok(health) => match serde_json::to_string(&health)
+            .ok()
+            .and_then(|json| CString::new(json).ok())
+        {
+            Some(c_string) => c_string.into_raw(),
+            None => {
+                error::set_last_error(Error::new(
+                    ErrorCode::OutOfMemory,
+                    "Failed to allocate string",
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get statistics of requests queued for the server's rate limit
+/// Requests exceeding the rate limit wait until it resets instead of failing
+/// with RateLimited, and requests refused with 429 are retried with backoff
+/// Returns a JSON RequestStats object:
+/// {"queued": 0, "delayed": 0, "rate_limited": 0, "retried": 0,
+///  "limit": 10, "remaining": 9, "reset_in_ms": 1000}
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_request_stats(
+    handle: PlatformHandle,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_request_stats()) {
+        Ok(stats) => match serde_json::to_string(&stats)
+            .ok()
+            .and_then(|json| CString::new(json).ok())
+        {
+            Some(c_string) => c_string.into_raw(),
+            None => {
+                error::set_last_error(Error::new(
+                    ErrorCode::OutOfMemory,
+                    "Failed to allocate string",
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Configure the platform's caches of users, channels, teams and emojis
+/// config_json: JSON object with optional enabled, user_ttl_secs, channel_ttl_secs,
+/// team_ttl_secs, emoji_ttl_secs, max_entries and path fields; missing fields
+/// keep their defaults (true, 300, 120, 600, 3600, no limit and no file)
+/// Returns ErrorCode indicating success or failure
+///
+/// New TTLs apply to entries cached from now on; caches over the new size
+/// limit are shrunk at once. Disabling caching clears the caches. With a path,
+/// cached users, channels and teams are saved there on disconnect and loaded
+/// (revalidated by update_at) after the next login.
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_configure_cache(
+    handle: PlatformHandle,
+    config_json: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || config_json.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let config_str = match std::ffi::CStr::from_ptr(config_json).to_str() {
+        Ok(s) => s,
+        Err(_) => {
+            error::set_last_error(Error::invalid_utf8());
+            return ErrorCode::InvalidUtf8;
+        }
+    };
+
+    let config: types::CacheConfig =
+        match json_input::parse(config_str, "cache config", "CacheConfig") {
+            Ok(c) => c,
+            Err(e) => {
+                error::set_last_error(e);
+                return ErrorCode::InvalidArgument;
+            }
+        };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.configure_cache(config)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Get the usage statistics of the platform's caches
+/// Returns a JSON array of CacheStats objects, one per cache:
+/// [{"name": "user", "entries": 42, "expired": 3, "max_entries": 1000,
+///   "ttl_secs": 300, "hits": 120, "misses": 45, "evictions": 0}, ...]
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_cache_stats(
+    handle: PlatformHandle,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_cache_stats()) {
+        Ok(stats) => match serde_json::to_string(&stats)
+            .ok()
+            .and_then(|json| CString::new(json).ok())
+        {
+            Some(c_string) => c_string.into_raw(),
+            None => {
+                error::set_last_error(Error::new(
+                    ErrorCode::OutOfMemory,
+                    "Failed to allocate string",
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// ============================================================================
+// File Operations FFI Functions
+// ============================================================================
+
+/// FFI function: Upload a file to a channel
+/// Returns a dynamically allocated string containing the file ID
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `channel_id` - The channel ID where the file will be uploaded
+/// * `file_path` - Path to the file to upload
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_upload_file(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    file_path: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() || file_path.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let file_path_str = {
+        match std::ffi::CStr::from_ptr(file_path).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+    let path = std::path::Path::new(file_path_str);
+
+    match runtime::block_on(platform.upload_file(channel_id_str, path)) {
+        Ok(file_id) => match CString::new(file_id) {
+            Ok(c_string) => c_string.into_raw(),
+            Err(_) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    "Failed to convert file ID to C string",
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Callback receiving the progress of an upload
+/// Parameters: bytes the server has received, file size, user_data
+/// Returns 0 to continue, anything else to abort the upload
+pub type UploadProgressCallback = extern "C" fn(u64, u64, *mut c_void) -> i32;
+
+/// FFI function: Upload a file from disk in chunks, reporting progress
+/// Only one chunk is held in memory at a time; an upload that failed or was
+/// aborted resumes when the unchanged file is uploaded to the channel again
+/// The callback is invoked on the calling thread after each chunk
+/// Returns the file ID, or NULL on error
+/// The caller must free the returned string using communicator_free_string()
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `channel_id` - The channel ID where the file will be uploaded
+/// * `file_path` - Path to the file to upload
+/// * `callback` - Receives the progress, or NULL
+/// * `user_data` - Passed back to the callback
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_upload_file_with_progress(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    file_path: *const c_char,
+    callback: Option<UploadProgressCallback>,
+    user_data: *mut c_void,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() || file_path.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let (Ok(channel_id_str), Ok(file_path_str)) = (
+        std::ffi::CStr::from_ptr(channel_id).to_str(),
+        std::ffi::CStr::from_ptr(file_path).to_str(),
+    ) else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+
+    let user_data = user_data as usize;
+    let mut progress = |progress: types::TransferProgress| {
+        let Some(callback) = callback else {
+            return Ok(());
+        };
+        let total = progress.total.unwrap_or(progress.transferred);
+        if callback(progress.transferred, total, user_data as *mut c_void) != 0 {
+            return Err(Error::new(
+                ErrorCode::Unknown,
+                "Upload aborted by the callback",
+            ));
+        }
+        Ok(())
+    };
+
+    let platform = object.blocking_read();
+    let path = std::path::Path::new(file_path_str);
+
+    match runtime::block_on(platform.upload_file_with_progress(channel_id_str, path, &mut progress))
+    {
+        Ok(file_id) => match CString::new(file_id) {
+            Ok(c_string) => c_string.into_raw(),
+            Err(_) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    "Failed to convert file ID to C string",
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Download a file by its ID
+/// The file data is returned through the out_data and out_size parameters
+/// The caller must free the returned data using communicator_free_file_data()
+/// Returns ErrorCode indicating success or failure
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `file_id` - The ID of the file to download
+/// * `out_data` - Output parameter for the file data (caller must free with communicator_free_file_data)
+/// * `out_size` - Output parameter for the size of the file data in bytes
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_download_file(
+    handle: PlatformHandle,
+    file_id: *const c_char,
+    out_data: *mut *mut u8,
+    out_size: *mut usize,
+) -> ErrorCode {
+    communicator_platform_download_file_ex(
+        handle,
+        file_id,
+        out_data,
+        out_size,
+        std::ptr::null_mut(),
+    )
+}
+
+/// FFI function: Download a file by its ID, cancellably
+/// Like communicator_platform_download_file(); cancelling `cancellation` from
+/// another thread makes the call return ErrorCode::Cancelled
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `file_id` - The ID of the file to download
+/// * `out_data` - Output parameter for the file data (caller must free with communicator_free_file_data)
+/// * `out_size` - Output parameter for the size of the file data in bytes
+/// * `cancellation` - Cancellation handle, or NULL
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_download_file_ex(
+    handle: PlatformHandle,
+    file_id: *const c_char,
+    out_data: *mut *mut u8,
+    out_size: *mut usize,
+    cancellation: CancellationHandle,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || file_id.is_null() || out_data.is_null() || out_size.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+    let Some(cancellation) = cancellation_arg(cancellation) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let file_id_str = {
+        match std::ffi::CStr::from_ptr(file_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(cancellation::run(
+        cancellation.as_deref(),
+        platform.download_file(file_id_str),
+    )) {
+        Ok(data) => {
+            let size = data.len();
+            let boxed_data = data.into_boxed_slice();
+            let raw_ptr = Box::into_raw(boxed_data) as *mut u8;
+
+            *out_data = raw_ptr;
+            *out_size = size;
+            ErrorCode::Success
+        }
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Download a file to disk without buffering it in memory
+/// Returns the path of the downloaded file
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `file_id` - The ID of the file to download
+/// * `path` - The file to write, or NULL for a new file in the temp directory
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_download_file_to_path(
+    handle: PlatformHandle,
+    file_id: *const c_char,
+    path: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || file_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let file_id_str = {
+        match std::ffi::CStr::from_ptr(file_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let path = if path.is_null() {
+        None
+    } else {
+        match std::ffi::CStr::from_ptr(path).to_str() {
+            Ok(s) => Some(std::path::Path::new(s)),
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.download_file_to_path(file_id_str, path)) {
+        Ok(path) => match CString::new(path.to_string_lossy().into_owned()) {
+            Ok(c_string) => c_string.into_raw(),
+            Err(_) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    "Failed to convert path to C string",
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Callback receiving the chunks of a streamed download
+/// Parameters: data, length, bytes downloaded so far (including this chunk),
+/// file size or -1 if unknown, user_data
+/// Returns 0 to continue, anything else to abort the download
+pub type DownloadCallback = extern "C" fn(*const u8, usize, u64, i64, *mut c_void) -> i32;
+
+/// FFI function: Download a file in chunks handed to a callback, without buffering it
+/// The callback is invoked on the calling thread before this returns
+/// Returns ErrorCode indicating success or failure; the checksum is verified
+/// after the last chunk, so on ChecksumMismatch the data received must be discarded
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `file_id` - The ID of the file to download
+/// * `callback` - Receives each chunk and the progress
+/// * `user_data` - Passed back to the callback
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_download_file_streaming(
+    handle: PlatformHandle,
+    file_id: *const c_char,
+    callback: Option<DownloadCallback>,
+    user_data: *mut c_void,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    let Some(callback) = callback.filter(|_| !handle.is_null() && !file_id.is_null()) else {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    };
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let file_id_str = {
+        match std::ffi::CStr::from_ptr(file_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let user_data = user_data as usize;
+    let mut sink = |chunk: &[u8], progress: types::TransferProgress| {
+        let total = progress.total.map_or(-1, |total| total as i64);
+        let status = callback(
+            chunk.as_ptr(),
+            chunk.len(),
+            progress.transferred,
+            total,
+            user_data as *mut c_void,
+        );
+        if status != 0 {
+            return Err(Error::new(
+                ErrorCode::Unknown,
+                "Download aborted by the callback",
+            ));
+        }
+        Ok(())
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.download_file_streaming(file_id_str, &mut sink)) {
+        Ok(_) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Get file metadata without downloading the file
+/// Returns a JSON string representing the Attachment metadata
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `file_id` - The ID of the file
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_file_metadata(
+    handle: PlatformHandle,
+    file_id: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || file_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let file_id_str = {
+        match std::ffi::CStr::from_ptr(file_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_file_metadata(file_id_str)) {
+        Ok(attachment) => match serde_json::to_string(&attachment) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::Unknown,
+                        "Failed to convert metadata to C string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize metadata: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get file thumbnail
+/// The thumbnail data is returned through the out_data and out_size parameters
+/// The caller must free the returned data using communicator_free_file_data()
+/// Returns ErrorCode indicating success or failure
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `file_id` - The ID of the file
+/// * `out_data` - Output parameter for the thumbnail data (caller must free with communicator_free_file_data)
+/// * `out_size` - Output parameter for the size of the thumbnail data in bytes
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_file_thumbnail(
+    handle: PlatformHandle,
+    file_id: *const c_char,
+    out_data: *mut *mut u8,
+    out_size: *mut usize,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || file_id.is_null() || out_data.is_null() || out_size.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let file_id_str = {
+        match std::ffi::CStr::from_ptr(file_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_file_thumbnail(file_id_str)) {
+        Ok(data) => {
+            let size = data.len();
+            let boxed_data = data.into_boxed_slice();
+            let raw_ptr = Box::into_raw(boxed_data) as *mut u8;
+
+            *out_data = raw_ptr;
+            *out_size = size;
+            ErrorCode::Success
+        }
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Free file data allocated by download_file or get_file_thumbnail
+///
+/// # Arguments
+/// * `data` - Pointer to file data returned by communicator_platform_download_file or communicator_platform_get_file_thumbnail
+/// * `size` - Size of the data in bytes (as returned in out_size)
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure the data pointer was allocated by this library and has not been freed already.
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_free_file_data(data: *mut u8, size: usize) {
+    if !data.is_null() && size > 0 {
+        let _ = Box::from_raw(std::ptr::slice_from_raw_parts_mut(data, size));
+    }
+}
+
+/// FFI function: Get file preview (full-size image preview)
+///
+/// # Safety
+/// The caller must ensure all pointer arguments are valid.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_platform_get_file_preview(
+    handle: PlatformHandle,
+    file_id: *const c_char,
+    out_data: *mut *mut u8,
+    out_size: *mut usize,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || file_id.is_null() || out_data.is_null() || out_size.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let file_id_str = {
+        match std::ffi::CStr::from_ptr(file_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_file_preview(file_id_str)) {
+        Ok(data) => {
+            let size = data.len();
+            let boxed_data = data.into_boxed_slice();
+            let raw_ptr = Box::into_raw(boxed_data) as *mut u8;
+
+            *out_data = raw_ptr;
+            *out_size = size;
+            ErrorCode::Success
+        }
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Get a public link to a file
+///
+/// # Safety
+/// The caller must ensure all pointer arguments are valid.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_platform_get_file_link(
+    handle: PlatformHandle,
+    file_id: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || file_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let file_id_str = {
+        match std::ffi::CStr::from_ptr(file_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_file_link(file_id_str)) {
+        Ok(link) => match CString::new(link) {
+            Ok(c_string) => c_string.into_raw(),
+            Err(_) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    "Failed to convert result to C string",
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// ============================================================================
+// Thread Operations
+// ============================================================================
+
+/// FFI function: Get a thread (root post and all replies)
+/// Returns a JSON string containing an array of messages
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+/// The returned string must be freed using communicator_free_string.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_platform_get_thread(
+    handle: PlatformHandle,
+    post_id: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || post_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let post_id_str = {
+        match std::ffi::CStr::from_ptr(post_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_thread(post_id_str)) {
+        Ok(messages) => match serde_json::to_string(&messages) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::Unknown,
+                        "Failed to create C string from thread JSON",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize thread: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Start following a thread
+/// Returns error code indicating success or failure
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_platform_follow_thread(
+    handle: PlatformHandle,
+    thread_id: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || thread_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let thread_id_str = {
+        match std::ffi::CStr::from_ptr(thread_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.follow_thread(thread_id_str)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Stop following a thread
+/// Returns error code indicating success or failure
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_platform_unfollow_thread(
+    handle: PlatformHandle,
+    thread_id: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || thread_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let thread_id_str = {
+        match std::ffi::CStr::from_ptr(thread_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.unfollow_thread(thread_id_str)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Mark a thread as read
+/// Returns error code indicating success or failure
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_platform_mark_thread_read(
+    handle: PlatformHandle,
+    thread_id: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || thread_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let thread_id_str = {
+        match std::ffi::CStr::from_ptr(thread_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.mark_thread_read(thread_id_str)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Mark a thread as unread from a specific post
+/// Returns error code indicating success or failure
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_platform_mark_thread_unread(
+    handle: PlatformHandle,
+    thread_id: *const c_char,
+    post_id: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || thread_id.is_null() || post_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let thread_id_str = {
+        match std::ffi::CStr::from_ptr(thread_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let post_id_str = {
+        match std::ffi::CStr::from_ptr(post_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.mark_thread_unread(thread_id_str, post_id_str)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Get all threads for a user in a team
+///
+/// # Safety
+/// The caller must ensure all pointer arguments are valid.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_platform_get_user_threads(
+    handle: PlatformHandle,
+    user_id: *const c_char,
+    team_id: *const c_char,
+    since: u64,
+    deleted: std::os::raw::c_int,
+    unread: std::os::raw::c_int,
+    per_page: usize,
+    page: usize,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || user_id.is_null() || team_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let user_id_str = {
+        match std::ffi::CStr::from_ptr(user_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let team_id_str = {
+        match std::ffi::CStr::from_ptr(team_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_user_threads(
+        user_id_str,
+        team_id_str,
+        since,
+        deleted != 0,
+        unread != 0,
+        per_page,
+        page,
+    )) {
+        Ok(json) => match CString::new(json) {
+            Ok(c_string) => c_string.into_raw(),
+            Err(_) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    "Failed to convert result to C string",
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get a specific thread for a user
+///
+/// # Safety
+/// The caller must ensure all pointer arguments are valid.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_platform_get_user_thread(
+    handle: PlatformHandle,
+    user_id: *const c_char,
+    team_id: *const c_char,
+    thread_id: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || user_id.is_null() || team_id.is_null() || thread_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let user_id_str = {
+        match std::ffi::CStr::from_ptr(user_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let team_id_str = {
+        match std::ffi::CStr::from_ptr(team_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let thread_id_str = {
+        match std::ffi::CStr::from_ptr(thread_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_user_thread(user_id_str, team_id_str, thread_id_str)) {
+        Ok(json) => match CString::new(json) {
+            Ok(c_string) => c_string.into_raw(),
+            Err(_) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    "Failed to convert result to C string",
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get the threads the current user follows
+/// Returns a JSON string: {"items": [Thread, ...], "next_cursor": "..." | null}
+/// Pass next_cursor to communicator_platform_next_page() to fetch more threads
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `team_id` - The team to list threads of, or NULL for the current team
+/// * `page` - Page number (0-indexed)
+/// * `per_page` - Number of threads per page
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_followed_threads(
+    handle: PlatformHandle,
+    team_id: *const c_char,
+    page: u32,
+    per_page: u32,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let team_id_str = if team_id.is_null() {
+        None
+    } else {
+        let Ok(s) = std::ffi::CStr::from_ptr(team_id).to_str() else {
+            error::set_last_error(Error::invalid_utf8());
+            return std::ptr::null_mut();
+        };
+        Some(s)
+    };
+
+    let platform = object.blocking_read();
+
+    let page = match runtime::block_on(platform.get_followed_threads(team_id_str, page, per_page)) {
+        Ok(page) => page,
+        Err(e) => {
+            error::set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    match serde_json::to_string(&page) {
+        Ok(json) => match CString::new(json) {
+            Ok(c_string) => c_string.into_raw(),
+            Err(_) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::OutOfMemory,
+                    "Failed to allocate string",
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(Error::new(
+                ErrorCode::Unknown,
+                format!("Failed to serialize threads: {e}"),
+            ));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Mark all threads as read for a user in a team
+///
+/// # Safety
+/// The caller must ensure all pointer arguments are valid.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_platform_mark_all_threads_read(
+    handle: PlatformHandle,
+    user_id: *const c_char,
+    team_id: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || user_id.is_null() || team_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let user_id_str = {
+        match std::ffi::CStr::from_ptr(user_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let team_id_str = {
+        match std::ffi::CStr::from_ptr(team_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.mark_all_threads_as_read(user_id_str, team_id_str)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Search for messages
+///
+/// # Arguments
+/// * `handle` - Platform handle
+/// * `query` - Search query (supports operators like from:, in:, before:, after:)
+/// * `limit` - Maximum number of results
+///
+/// # Returns
+/// JSON array of messages on success, or null on error
+///
+/// # Safety
+/// The caller must ensure all pointer arguments are valid.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_platform_search_messages(
+    handle: PlatformHandle,
+    query: *const c_char,
+    limit: usize,
+) -> *mut c_char {
+    communicator_platform_search_messages_ex(handle, query, limit, std::ptr::null_mut())
+}
+
+/// FFI function: Search for messages, cancellably
+/// Like communicator_platform_search_messages(); cancelling `cancellation` from
+/// another thread makes the call return NULL with ErrorCode::Cancelled
+///
+/// # Arguments
+/// * `handle` - Platform handle
+/// * `query` - Search query (supports operators like from:, in:, before:, after:)
+/// * `limit` - Maximum number of results
+/// * `cancellation` - Cancellation handle, or NULL
+///
+/// # Returns
+/// JSON array of messages on success, or null on error
+///
+/// # Safety
+/// The caller must ensure all pointer arguments are valid.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_platform_search_messages_ex(
+    handle: PlatformHandle,
+    query: *const c_char,
+    limit: usize,
+    cancellation: CancellationHandle,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || query.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+    let Some(cancellation) = cancellation_arg(cancellation) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let query_str = {
+        match std::ffi::CStr::from_ptr(query).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(cancellation::run(
+        cancellation.as_deref(),
+        platform.search_messages(query_str, limit),
+    )) {
+        Ok(messages) => match serde_json::to_string(&messages) {
+            Ok(json) => match std::ffi::CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::Unknown,
+                        "Failed to convert result to C string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize messages: {}", e),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Search for messages with a structured query
+/// The platform translates the query into its native search syntax.
+///
+/// # Arguments
+/// * `handle` - Platform handle
+/// * `query_json` - JSON SearchQuery: {"terms": [...], "phrases": [...], "excluded_terms": [...],
+///   "from_users": [...], "to_users": [...], "in_channels": [...], "before": "YYYY-MM-DD",
+///   "after": "YYYY-MM-DD", "on": "YYYY-MM-DD", "has_attachment": bool} (all fields optional)
+/// * `limit` - Maximum number of results
+///
+/// # Returns
+/// JSON array of messages on success, or null on error
+///
+/// # Safety
+/// The caller must ensure all pointer arguments are valid.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_platform_search_messages_query(
+    handle: PlatformHandle,
+    query_json: *const c_char,
+    limit: u32,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || query_json.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let query_str = match std::ffi::CStr::from_ptr(query_json).to_str() {
+        Ok(s) => s,
+        Err(_) => {
+            error::set_last_error(Error::invalid_utf8());
+            return std::ptr::null_mut();
+        }
+    };
+
+    let query: types::SearchQuery =
+        match json_input::parse(query_str, "search query", "SearchQuery") {
+            Ok(q) => q,
+            Err(e) => {
+                error::set_last_error(e);
+                return std::ptr::null_mut();
+            }
+        };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.search_messages_query(&query, limit as usize)) {
+        Ok(messages) => match serde_json::to_string(&messages) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_str) => c_str.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::invalid_utf8());
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize messages: {}", e),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Parse a search string in the common search mini-language
+/// Supported syntax: words, "exact phrases", -excluded, from:user, to:user,
+/// in:channel, before:YYYY-MM-DD, after:YYYY-MM-DD, on:YYYY-MM-DD, has:attachment
+///
+/// # Arguments
+/// * `text` - The search string
+///
+/// # Returns
+/// JSON SearchQuery on success (for communicator_platform_search_messages_query), or null on error
+/// The caller must free the returned string using communicator_free_string()
+///
+/// # Safety
+/// The caller must ensure all pointer arguments are valid.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_search_query_parse(text: *const c_char) -> *mut c_char {
+    error::clear_last_error();
+
+    if text.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let text_str = match std::ffi::CStr::from_ptr(text).to_str() {
+        Ok(s) => s,
+        Err(_) => {
+            error::set_last_error(Error::invalid_utf8());
+            return std::ptr::null_mut();
+        }
+    };
+
+    match types::SearchQuery::parse(text_str) {
+        Ok(query) => match serde_json::to_string(&query) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_str) => c_str.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::invalid_utf8());
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize search query: {}", e),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// ============================================================================
+// Advanced Search Operations
+// ============================================================================
+
+/// FFI function: Search for users with advanced filtering
+///
+/// # Arguments
+/// * `handle` - Platform handle
+/// * `request_json` - JSON string with UserSearchRequest parameters
+///
+/// # Returns
+/// JSON array of users on success, or null on error
+///
+/// # Safety
+/// The caller must ensure all pointer arguments are valid.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_platform_search_users(
+    handle: PlatformHandle,
+    request_json: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || request_json.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let request_str = {
+        match std::ffi::CStr::from_ptr(request_json).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let request: platforms::mattermost::UserSearchRequest =
+        match json_input::parse(request_str, "search request", "UserSearchRequest") {
+            Ok(r) => r,
+            Err(e) => {
+                error::set_last_error(e);
+                return std::ptr::null_mut();
+            }
+        };
+
+    let platform = object.blocking_read();
+
+    // Extract term and limit for the simple trait method
+    let query = &request.term;
+    let limit = request.limit.unwrap_or(100) as usize;
+
+    match runtime::block_on(platform.search_users(query, limit)) {
+        Ok(users) => match serde_json::to_string(&users) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::Unknown,
+                        "Failed to convert result to C string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize users: {}", e),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Autocomplete users for mentions
+///
+/// # Safety
+/// The caller must ensure all pointer arguments are valid.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_platform_autocomplete_users(
+    handle: PlatformHandle,
+    name: *const c_char,
+    team_id: *const c_char,
+    channel_id: *const c_char,
+    limit: usize,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || name.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let name_str = {
+        match std::ffi::CStr::from_ptr(name).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let _team_id_opt = if team_id.is_null() {
+        None
+    } else {
+        match std::ffi::CStr::from_ptr(team_id).to_str() {
+            Ok(s) => Some(s),
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let channel_id_str = if channel_id.is_null() {
+        ""
+    } else {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    // Note: team_id is not used by the simple trait method
+    // For full advanced search support, the platform trait would need enhancement
+    match runtime::block_on(platform.autocomplete_users(channel_id_str, name_str, limit)) {
+        Ok(users) => match serde_json::to_string(&users) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::Unknown,
+                        "Failed to convert result to C string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize users: {}", e),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Search for channels by name or display name
+/// Returns a JSON array of up to 100 Channel objects
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `team_id` - The team to search in, or NULL (or "") for the current team
+/// * `term` - Search term
+///
+/// # Safety
+/// The caller must ensure all pointer arguments are valid.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_platform_search_channels(
+    handle: PlatformHandle,
+    team_id: *const c_char,
+    term: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || term.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let team_id_str = if team_id.is_null() {
+        None
+    } else {
+        let Ok(s) = std::ffi::CStr::from_ptr(team_id).to_str() else {
+            error::set_last_error(Error::invalid_utf8());
+            return std::ptr::null_mut();
+        };
+        Some(s).filter(|s| !s.is_empty())
+    };
+
+    let term_str = {
+        match std::ffi::CStr::from_ptr(term).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.search_channels(team_id_str, term_str, 100)) {
+        Ok(channels) => match serde_json::to_string(&channels) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::Unknown,
+                        "Failed to convert result to C string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize channels: {}", e),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Autocomplete channels for references
+/// Returns a JSON array of up to 100 Channel objects whose names start with `name`
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `team_id` - The team to search in, or NULL (or "") for the current team
+/// * `name` - Partial channel name
+///
+/// # Safety
+/// The caller must ensure all pointer arguments are valid.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_platform_autocomplete_channels(
+    handle: PlatformHandle,
+    team_id: *const c_char,
+    name: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || name.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let team_id_str = if team_id.is_null() {
+        None
+    } else {
+        let Ok(s) = std::ffi::CStr::from_ptr(team_id).to_str() else {
+            error::set_last_error(Error::invalid_utf8());
+            return std::ptr::null_mut();
+        };
+        Some(s).filter(|s| !s.is_empty())
+    };
+
+    let name_str = {
+        match std::ffi::CStr::from_ptr(name).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.autocomplete_channels(team_id_str, name_str, 100)) {
+        Ok(channels) => match serde_json::to_string(&channels) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::Unknown,
+                        "Failed to convert result to C string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize channels: {}", e),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Search for files with advanced filtering
+///
+/// # Safety
+/// The caller must ensure all pointer arguments are valid.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_platform_search_files(
+    handle: PlatformHandle,
+    request_json: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || request_json.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    if !handles::PLATFORMS.contains(handle) {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    }
+
+    let request_str = {
+        match std::ffi::CStr::from_ptr(request_json).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let _request: platforms::mattermost::FileSearchRequest =
+        match json_input::parse(request_str, "file search request", "FileSearchRequest") {
+            Ok(r) => r,
+            Err(e) => {
+                error::set_last_error(e);
+                return std::ptr::null_mut();
+            }
+        };
+
+    // TODO: File search requires Platform trait support - not yet implemented
+    // The Platform trait needs a search_files method added
+    error::set_last_error(Error::unsupported(
+        "Advanced file search not yet supported by Platform trait",
+    ));
+    std::ptr::null_mut()
+}
+
+/// FFI function: Search for posts with advanced filtering
+///
+/// # Safety
+/// The caller must ensure all pointer arguments are valid.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_platform_search_posts_advanced(
+    handle: PlatformHandle,
+    request_json: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || request_json.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    if !handles::PLATFORMS.contains(handle) {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    }
+
+    let request_str = {
+        match std::ffi::CStr::from_ptr(request_json).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let _request: platforms::mattermost::PostSearchOptions =
+        match json_input::parse(request_str, "post search request", "PostSearchOptions") {
+            Ok(r) => r,
+            Err(e) => {
+                error::set_last_error(e);
+                return std::ptr::null_mut();
+            }
+        };
+
+    // TODO: Advanced post search requires Platform trait support - not yet implemented
+    // The Platform trait has search_messages(query, limit) but not advanced options
+    // To support this properly, need to add search_posts_advanced to the trait
+    error::set_last_error(Error::unsupported(
+        "Advanced post search not yet supported by Platform trait",
+    ));
+    std::ptr::null_mut()
+}
+
+// ============================================================================
+// User Preferences and Notifications
+// ============================================================================
+
+/// FFI function: Get user preferences as JSON
+/// Returns a JSON string representing the user's preferences
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_user_preferences(
+    handle: PlatformHandle,
+    user_id: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || user_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let user_id_str = {
+        match std::ffi::CStr::from_ptr(user_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_user_preferences(user_id_str)) {
+        Ok(json) => match CString::new(json) {
+            Ok(c_string) => c_string.into_raw(),
+            Err(_) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::OutOfMemory,
+                    "Failed to allocate string",
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Set user preferences from JSON
+/// Returns error code indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_set_user_preferences(
+    handle: PlatformHandle,
+    user_id: *const c_char,
+    preferences_json: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || user_id.is_null() || preferences_json.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let user_id_str = {
+        match std::ffi::CStr::from_ptr(user_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let preferences_json_str = {
+        match std::ffi::CStr::from_ptr(preferences_json).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.set_user_preferences(user_id_str, preferences_json_str)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Mute a channel
+/// Returns error code indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_mute_channel(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.mute_channel(channel_id_str)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Unmute a channel
+/// Returns error code indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_unmute_channel(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.unmute_channel(channel_id_str)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Update channel notification properties from JSON
+/// Returns error code indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_update_channel_notify_props(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    notify_props_json: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() || notify_props_json.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let notify_props_json_str = {
+        match std::ffi::CStr::from_ptr(notify_props_json).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(
+        platform.update_channel_notify_props(channel_id_str, notify_props_json_str),
+    ) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+// ============================================================================
+// Channel Read State Management FFI
+// ============================================================================
+
+/// FFI function: Mark a channel as viewed (read)
+/// Returns error code indicating success or failure
+///
+/// Repeated views of the same channel are debounced and unread counts are
+/// updated locally right away, so this can be called on every channel switch.
+/// A reported view is also delivered as a "channel_viewed" event right away.
+/// The previously viewed channel is marked read too; use
+/// communicator_platform_view_channel_ex() to name it explicitly.
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `channel_id` - The ID of the channel being viewed
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_view_channel(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+) -> ErrorCode {
+    communicator_platform_view_channel_ex(handle, channel_id, std::ptr::null())
+}
+
+/// FFI function: Mark a channel as viewed (read), naming the channel switched away from
+/// Returns error code indicating success or failure
+///
+/// Repeated views of the same channel are debounced and unread counts are
+/// updated locally right away, so this can be called on every channel switch.
+/// A reported view is also delivered as a "channel_viewed" event right away.
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `channel_id` - The ID of the channel being viewed
+/// * `prev_channel_id` - The ID of the channel being switched away from
+///   (pass NULL to use the previously viewed channel)
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_view_channel_ex(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    prev_channel_id: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let prev_channel_id_opt = if prev_channel_id.is_null() {
+        None
+    } else {
+        match std::ffi::CStr::from_ptr(prev_channel_id).to_str() {
+            Ok(s) => Some(s),
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.view_channel(channel_id_str, prev_channel_id_opt)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Get unread information for a channel
+/// Returns a JSON string with unread counts or NULL on error
+/// The returned string must be freed with communicator_free_string()
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_channel_unread(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    let unread_info = match runtime::block_on(platform.get_channel_unread(channel_id_str)) {
+        Ok(info) => info,
+        Err(e) => {
+            error::set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    // Serialize to JSON
+    let json = match serde_json::to_string(&unread_info) {
+        Ok(j) => j,
+        Err(e) => {
+            error::set_last_error(Error::new(
+                ErrorCode::Unknown,
+                format!("Failed to serialize unread info: {e}"),
+            ));
+            return std::ptr::null_mut();
+        }
+    };
+
+    match CString::new(json) {
+        Ok(c_string) => c_string.into_raw(),
+        Err(_) => {
+            error::set_last_error(Error::invalid_utf8());
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get hourly message counts for a channel
+/// Returns a JSON string with the channel activity or NULL on error
+/// The returned string must be freed with communicator_free_string()
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_channel_activity(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    days: u32,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    let activity = match runtime::block_on(platform.get_channel_activity(channel_id_str, days)) {
+        Ok(activity) => activity,
+        Err(e) => {
+            error::set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    let json = match serde_json::to_string(&activity) {
+        Ok(j) => j,
+        Err(e) => {
+            error::set_last_error(Error::new(
+                ErrorCode::Unknown,
+                format!("Failed to serialize channel activity: {e}"),
+            ));
+            return std::ptr::null_mut();
+        }
+    };
+
+    match CString::new(json) {
+        Ok(c_string) => c_string.into_raw(),
+        Err(_) => {
+            error::set_last_error(Error::invalid_utf8());
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get the most recent messages of a channel from its in-memory window
+/// Returns a JSON array of Message objects, oldest first, or NULL on error
+/// The returned string must be freed with communicator_free_string()
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_window(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    let messages = match runtime::block_on(platform.get_window(channel_id_str)) {
+        Ok(messages) => messages,
+        Err(e) => {
+            error::set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    let json = match serde_json::to_string(&messages) {
+        Ok(j) => j,
+        Err(e) => {
+            error::set_last_error(Error::new(
+                ErrorCode::Unknown,
+                format!("Failed to serialize messages: {e}"),
+            ));
+            return std::ptr::null_mut();
+        }
+    };
+
+    match CString::new(json) {
+        Ok(c_string) => c_string.into_raw(),
+        Err(_) => {
+            error::set_last_error(Error::invalid_utf8());
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get unread counts for all channels in a team
+/// Returns a JSON string with array of unread info or NULL on error
+/// The returned string must be freed with communicator_free_string()
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_team_unreads(
+    handle: PlatformHandle,
+    team_id: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || team_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let team_id_str = {
+        match std::ffi::CStr::from_ptr(team_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    let unreads = match runtime::block_on(platform.get_team_unreads(team_id_str)) {
+        Ok(list) => list,
+        Err(e) => {
+            error::set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    // Serialize to JSON
+    let json = match serde_json::to_string(&unreads) {
+        Ok(j) => j,
+        Err(e) => {
+            error::set_last_error(Error::new(
+                ErrorCode::Unknown,
+                format!("Failed to serialize team unreads: {e}"),
+            ));
+            return std::ptr::null_mut();
+        }
+    };
+
+    match CString::new(json) {
+        Ok(c_string) => c_string.into_raw(),
+        Err(_) => {
+            error::set_last_error(Error::invalid_utf8());
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get unread counts for all channels across all teams
+///
+/// # Safety
+/// The caller must ensure all pointer arguments are valid.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_platform_get_all_unreads(
+    handle: PlatformHandle,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_all_unreads()) {
+        Ok(unreads) => match serde_json::to_string(&unreads) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::Unknown,
+                        "Failed to convert result to C string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize unreads: {}", e),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get unread counts for all channels in the current team
+/// Returns a JSON array of ChannelUnread objects, one per channel the user is a
+/// member of
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_channel_unreads(
+    handle: PlatformHandle,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_channel_unreads()) {
+        Ok(unreads) => match serde_json::to_string(&unreads) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize channel unreads: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Mark all messages in a channel as read
+/// Unlike communicator_platform_view_channel(), not debounced and without
+/// changing the channel the user is considered to be viewing
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `channel_id` - The channel ID
+///
+/// # Returns
+/// ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_mark_channel_read(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let Ok(channel_id_str) = std::ffi::CStr::from_ptr(channel_id).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return ErrorCode::InvalidUtf8;
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.mark_channel_read(channel_id_str)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Get the current user's sidebar categories in the current team
+/// Returns a JSON array of SidebarCategory objects in display order
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_sidebar_categories(
+    handle: PlatformHandle,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_sidebar_categories()) {
+        Ok(categories) => match serde_json::to_string(&categories) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize sidebar categories: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Move a channel into a sidebar category
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `channel_id` - The channel ID
+/// * `category_id` - The category ID
+/// * `index` - Position in the category, or -1 for its end; a position sorts
+///   the category manually
+///
+/// # Returns
+/// ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_move_channel_to_category(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    category_id: *const c_char,
+    index: i32,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() || category_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let Ok(channel_id_str) = std::ffi::CStr::from_ptr(channel_id).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return ErrorCode::InvalidUtf8;
+    };
+    let Ok(category_id_str) = std::ffi::CStr::from_ptr(category_id).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return ErrorCode::InvalidUtf8;
+    };
+    let index = usize::try_from(index).ok();
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.move_channel_to_category(
+        channel_id_str,
+        category_id_str,
+        index,
+    )) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Get unread posts in a channel
+///
+/// # Safety
+/// The caller must ensure all pointer arguments are valid.
+#[no_mangle]
+pub unsafe extern "C" fn communicator_platform_get_unread_posts(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    limit_after: usize,
+    limit_before: usize,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_unread_posts(channel_id_str, limit_after, limit_before)) {
+        Ok(json) => match CString::new(json) {
+            Ok(c_string) => c_string.into_raw(),
+            Err(_) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    "Failed to convert result to C string",
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// ============================================================================
+// Integration Management
+// ============================================================================
+
+/// FFI function: Register an OAuth 2.0 app
+/// Returns a JSON string with the registered app (including its client secret) or NULL on error
+/// The returned string must be freed with communicator_free_string()
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_register_oauth_app(
+    handle: PlatformHandle,
+    settings_json: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || settings_json.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let settings_str = {
+        match std::ffi::CStr::from_ptr(settings_json).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let settings: types::OAuthAppSettings =
+        match json_input::parse(settings_str, "OAuth app settings", "OAuthAppSettings") {
+            Ok(settings) => settings,
+            Err(e) => {
+                error::set_last_error(e);
+                return std::ptr::null_mut();
+            }
+        };
+
+    let platform = object.blocking_read();
+
+    let app = match runtime::block_on(platform.register_oauth_app(&settings)) {
+        Ok(app) => app,
+        Err(e) => {
+            error::set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    let json = match serde_json::to_string(&app) {
+        Ok(j) => j,
+        Err(e) => {
+            error::set_last_error(Error::new(
+                ErrorCode::Unknown,
+                format!("Failed to serialize OAuth app: {e}"),
+            ));
+            return std::ptr::null_mut();
+        }
+    };
+
+    match CString::new(json) {
+        Ok(c_string) => c_string.into_raw(),
+        Err(_) => {
+            error::set_last_error(Error::invalid_utf8());
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get the OAuth 2.0 apps visible to the current user
+/// Returns a JSON array of OAuth apps or NULL on error
+/// The returned string must be freed with communicator_free_string()
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_oauth_apps(
+    handle: PlatformHandle,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    let apps = match runtime::block_on(platform.get_oauth_apps()) {
+        Ok(apps) => apps,
+        Err(e) => {
+            error::set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    let json = match serde_json::to_string(&apps) {
+        Ok(j) => j,
+        Err(e) => {
+            error::set_last_error(Error::new(
+                ErrorCode::Unknown,
+                format!("Failed to serialize OAuth apps: {e}"),
+            ));
+            return std::ptr::null_mut();
+        }
+    };
+
+    match CString::new(json) {
+        Ok(c_string) => c_string.into_raw(),
+        Err(_) => {
+            error::set_last_error(Error::invalid_utf8());
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Delete an OAuth 2.0 app
+/// Returns error code indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_delete_oauth_app(
+    handle: PlatformHandle,
+    app_id: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || app_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let app_id_str = {
+        match std::ffi::CStr::from_ptr(app_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.delete_oauth_app(app_id_str)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Create a personal access token
+/// Returns a JSON string with the created token (including its value) or NULL on error
+/// The returned string must be freed with communicator_free_string()
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_create_access_token(
+    handle: PlatformHandle,
+    user_id: *const c_char,
+    description: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || user_id.is_null() || description.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let user_id_str = {
+        match std::ffi::CStr::from_ptr(user_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let description_str = {
+        match std::ffi::CStr::from_ptr(description).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    let token = match runtime::block_on(platform.create_access_token(user_id_str, description_str))
+    {
+        Ok(token) => token,
+        Err(e) => {
+            error::set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    let json = match serde_json::to_string(&token) {
+        Ok(j) => j,
+        Err(e) => {
+            error::set_last_error(Error::new(
+                ErrorCode::Unknown,
+                format!("Failed to serialize access token: {e}"),
+            ));
+            return std::ptr::null_mut();
+        }
+    };
+
+    match CString::new(json) {
+        Ok(c_string) => c_string.into_raw(),
+        Err(_) => {
+            error::set_last_error(Error::invalid_utf8());
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get a user's personal access tokens
+/// Returns a JSON array of access tokens (without their values) or NULL on error
+/// The returned string must be freed with communicator_free_string()
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_access_tokens(
+    handle: PlatformHandle,
+    user_id: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || user_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let user_id_str = {
+        match std::ffi::CStr::from_ptr(user_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    let tokens = match runtime::block_on(platform.get_access_tokens(user_id_str)) {
+        Ok(tokens) => tokens,
+        Err(e) => {
+            error::set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    let json = match serde_json::to_string(&tokens) {
+        Ok(j) => j,
+        Err(e) => {
+            error::set_last_error(Error::new(
+                ErrorCode::Unknown,
+                format!("Failed to serialize access tokens: {e}"),
+            ));
+            return std::ptr::null_mut();
+        }
+    };
+
+    match CString::new(json) {
+        Ok(c_string) => c_string.into_raw(),
+        Err(_) => {
+            error::set_last_error(Error::invalid_utf8());
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Revoke a personal access token
+/// Returns error code indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_revoke_access_token(
+    handle: PlatformHandle,
+    token_id: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || token_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let token_id_str = {
+        match std::ffi::CStr::from_ptr(token_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.revoke_access_token(token_id_str)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Create a bot account owned by the current user
+/// Returns a JSON string representing the created Bot
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `settings_json` - JSON BotSettings: {"username", "display_name", "description"}
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_create_bot(
+    handle: PlatformHandle,
+    settings_json: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || settings_json.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let Ok(settings_json_str) = std::ffi::CStr::from_ptr(settings_json).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+
+    let settings: types::BotSettings =
+        match json_input::parse(settings_json_str, "bot settings", "BotSettings") {
+            Ok(settings) => settings,
+            Err(e) => {
+                error::set_last_error(e);
+                return std::ptr::null_mut();
+            }
+        };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.create_bot(&settings)) {
+        Ok(result) => match serde_json::to_string(&result) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize bot: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get the bot accounts visible to the current user
+/// Returns a JSON array of Bot objects
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `include_disabled` - 1 to include disabled bots, 0 otherwise
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_bots(
+    handle: PlatformHandle,
+    include_disabled: i32,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.get_bots(include_disabled != 0)) {
+        Ok(result) => match serde_json::to_string(&result) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize bots: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Enable or disable a bot account
+/// Returns a JSON string representing the updated Bot
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `bot_user_id` - The bot's user ID
+/// * `enabled` - 1 to enable the bot, 0 to disable it
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_set_bot_enabled(
+    handle: PlatformHandle,
+    bot_user_id: *const c_char,
+    enabled: i32,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || bot_user_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let Ok(bot_user_id_str) = std::ffi::CStr::from_ptr(bot_user_id).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.set_bot_enabled(bot_user_id_str, enabled != 0)) {
+        Ok(result) => match serde_json::to_string(&result) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize bot: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Transfer a bot account to another owner
+/// Returns a JSON string representing the updated Bot
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `bot_user_id` - The bot's user ID
+/// * `owner_id` - The user ID of the new owner
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_assign_bot(
+    handle: PlatformHandle,
+    bot_user_id: *const c_char,
+    owner_id: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || bot_user_id.is_null() || owner_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let Ok(bot_user_id_str) = std::ffi::CStr::from_ptr(bot_user_id).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+    let Ok(owner_id_str) = std::ffi::CStr::from_ptr(owner_id).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.assign_bot(bot_user_id_str, owner_id_str)) {
+        Ok(result) => match serde_json::to_string(&result) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize bot: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Convert a user account into a bot account
+/// Returns a JSON string representing the Bot
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `user_id` - The user to convert
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_convert_user_to_bot(
+    handle: PlatformHandle,
+    user_id: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || user_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let Ok(user_id_str) = std::ffi::CStr::from_ptr(user_id).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.convert_user_to_bot(user_id_str)) {
+        Ok(result) => match serde_json::to_string(&result) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize bot: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Convert a bot account into a regular user account
+/// Returns a JSON string representing the User
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `bot_user_id` - The bot's user ID
+/// * `password` - The password the user will log in with
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_convert_bot_to_user(
+    handle: PlatformHandle,
+    bot_user_id: *const c_char,
+    password: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || bot_user_id.is_null() || password.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let Ok(bot_user_id_str) = std::ffi::CStr::from_ptr(bot_user_id).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+    let Ok(password_str) = std::ffi::CStr::from_ptr(password).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.convert_bot_to_user(bot_user_id_str, password_str)) {
+        Ok(result) => match serde_json::to_string(&result) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize user: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// ============================================================================
+// Event Webhooks
+// ============================================================================
+
+/// FFI function: Forward events to a local HTTP endpoint
+/// Every event returned by communicator_platform_poll_event() that matches the
+/// filter is also POSTed as JSON to the given URL in the background.
+/// Delivery is best-effort; failures do not affect polling.
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `url` - The endpoint URL; must be http(s) on a loopback address (localhost, 127.0.0.1, ::1)
+/// * `event_types_json` - JSON array of event types to forward (e.g. ["message_posted"]), or NULL for all events
+///
+/// # Returns
+/// ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_add_event_webhook(
+    handle: PlatformHandle,
+    url: *const c_char,
+    event_types_json: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || url.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let url_str = match std::ffi::CStr::from_ptr(url).to_str() {
+        Ok(s) => s,
+        Err(_) => {
+            error::set_last_error(Error::invalid_utf8());
+            return ErrorCode::InvalidUtf8;
+        }
+    };
+
+    let event_types = if event_types_json.is_null() {
+        None
+    } else {
+        let json_str = match std::ffi::CStr::from_ptr(event_types_json).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        };
+        match json_input::parse::<Vec<String>>(json_str, "event types", "string array") {
+            Ok(types) => Some(types),
+            Err(e) => {
+                error::set_last_error(e);
+                return ErrorCode::InvalidArgument;
+            }
+        }
+    };
+
+    match webhook::EventWebhook::new(url_str, event_types) {
+        Ok(hook) => {
+            let platform = object.blocking_read();
+            platform.pipeline().add_webhook(hook);
+            ErrorCode::Success
+        }
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Remove all event webhooks registered for a platform
+///
+/// # Arguments
+/// * `handle` - The platform handle
+///
+/// # Returns
+/// ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_clear_event_webhooks(
+    handle: PlatformHandle,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let platform = object.blocking_read();
+    platform.pipeline().clear_webhooks();
+    ErrorCode::Success
+}
+
+// ============================================================================
+// Automation Rules
+// ============================================================================
+
+/// FFI function: Install automation rules for a platform
+/// Rules are evaluated against every event returned by
+/// communicator_platform_poll_event(), and their actions are performed before
+/// the event is returned. Replaces any previously installed rules.
+/// The platform must be connected (the current user is needed for `mentions_me`).
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `rules_json` - JSON array of rules, e.g.
+///   [{"name": "watch", "trigger": {"event": "message_posted", "channel": "alerts", "mentions_me": true},
+///   "actions": [{"type": "add_reaction", "emoji": "eyes"}]}]
+///   Actions: {"type": "send_message", "text": "...", "channel_id": optional},
+///   {"type": "add_reaction", "emoji": "..."}, {"type": "callback"}
+/// * `callback` - Called for `callback` actions with the rule name and event JSON (may be NULL)
+/// * `user_data` - Opaque pointer passed back to the callback
+///
+/// # Returns
+/// ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_set_rules(
+    handle: PlatformHandle,
+    rules_json: *const c_char,
+    callback: Option<rules::RuleCallback>,
+    user_data: *mut c_void,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || rules_json.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let rules_json_str = match std::ffi::CStr::from_ptr(rules_json).to_str() {
+        Ok(s) => s,
+        Err(_) => {
+            error::set_last_error(Error::invalid_utf8());
+            return ErrorCode::InvalidUtf8;
+        }
+    };
+
+    let parsed = match rules::RulesEngine::parse_rules(rules_json_str) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            return code;
+        }
+    };
+
+    let platform = object.blocking_read();
+    let user = match runtime::block_on(platform.get_current_user()) {
+        Ok(user) => user,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            return code;
+        }
+    };
+
+    let mut engine = rules::RulesEngine::new(parsed, user.id);
+    if let Some(callback) = callback {
+        engine = engine.with_callback(callback, user_data);
+    }
+
+    platform.pipeline().set_rules(Some(engine));
+    ErrorCode::Success
+}
+
+/// FFI function: Remove all automation rules from a platform
+///
+/// # Arguments
+/// * `handle` - The platform handle
+///
+/// # Returns
+/// ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_clear_rules(handle: PlatformHandle) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let platform = object.blocking_read();
+    platform.pipeline().set_rules(None);
+    ErrorCode::Success
+}
+
+// ============================================================================
+// Script Hooks
+// ============================================================================
+
+/// FFI function: Add a script to the message pipeline
+/// The script is Lua source returning a function, which receives a message as
+/// a table and returns the transformed message (or nil to drop it). Scripts
+/// run in a sandbox without I/O.
+/// Inbound hooks receive and return Message objects for message_posted and
+/// message_updated events; outbound hooks receive and return
+/// {"channel_id": "...", "text": "..."} for communicator_platform_send_message()
+/// and communicator_platform_send_reply().
+/// Requires the library to be built with the `scripting` feature; otherwise
+/// returns COMMUNICATOR_ERROR_UNSUPPORTED.
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `stage` - "inbound" or "outbound"
+/// * `script` - Lua source of the hook
+/// * `timeout_ms` - Time limit per run in milliseconds (0 for the default of 500ms)
+/// * `memory_limit_mb` - Memory limit per run in MiB (0 for the default of 16 MiB)
+/// * `instruction_limit` - Lua instructions per run (0 for the default of 10 million)
+///
+/// # Returns
+/// ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_add_script_hook(
+    handle: PlatformHandle,
+    stage: *const c_char,
+    script: *const c_char,
+    timeout_ms: u64,
+    memory_limit_mb: u64,
+    instruction_limit: u64,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || stage.is_null() || script.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let stage_str = match std::ffi::CStr::from_ptr(stage).to_str() {
+        Ok(s) => s,
+        Err(_) => {
+            error::set_last_error(Error::invalid_utf8());
+            return ErrorCode::InvalidUtf8;
+        }
+    };
+
+    let script_str = match std::ffi::CStr::from_ptr(script).to_str() {
+        Ok(s) => s,
+        Err(_) => {
+            error::set_last_error(Error::invalid_utf8());
+            return ErrorCode::InvalidUtf8;
+        }
+    };
+
+    #[cfg(feature = "scripting")]
+    {
+        let stage = match scripting::HookStage::parse(stage_str) {
+            Some(stage) => stage,
+            None => {
+                error::set_last_error(Error::invalid_argument(
+                    "Invalid stage. Must be one of: inbound, outbound",
+                ));
+                return ErrorCode::InvalidArgument;
+            }
+        };
+
+        let mut hook = match scripting::ScriptHook::new(script_str, stage) {
+            Ok(hook) => hook,
+            Err(e) => {
+                let code = e.code;
+                error::set_last_error(e);
+                return code;
+            }
+        };
+        if timeout_ms > 0 {
+            hook = hook.with_timeout(std::time::Duration::from_millis(timeout_ms));
+        }
+        if memory_limit_mb > 0 {
+            hook = hook.with_memory_limit(memory_limit_mb.saturating_mul(1024 * 1024));
+        }
+        if instruction_limit > 0 {
+            hook = hook.with_instruction_limit(instruction_limit);
+        }
+
+        let platform = object.blocking_read();
+        platform.pipeline().add_script_hook(hook);
+        ErrorCode::Success
+    }
+
+    #[cfg(not(feature = "scripting"))]
+    {
+        let _ = (
+            object,
+            stage_str,
+            script_str,
+            timeout_ms,
+            memory_limit_mb,
+            instruction_limit,
+        );
+        error::set_last_error(Error::unsupported(
+            "Script hooks require the scripting feature",
+        ));
+        ErrorCode::Unsupported
+    }
+}
+
+/// FFI function: Remove all script hooks from a platform
+///
+/// # Arguments
+/// * `handle` - The platform handle
+///
+/// # Returns
+/// ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_clear_script_hooks(
+    handle: PlatformHandle,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    #[cfg(feature = "scripting")]
+    object.blocking_read().pipeline().clear_script_hooks();
+    #[cfg(not(feature = "scripting"))]
+    let _ = object;
+
+    ErrorCode::Success
+}
+
+/// FFI function: Get the original text of a message changed by inbound hooks
+/// Returns a JSON object {"message_id": "...", "original_text": "...",
+/// "transformations": [{"name": "...", "detail": "..."}, ...]}
+/// The original text of recently transformed messages is kept locally; for
+/// other messages it is fetched from the platform, which stores the text as sent.
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `message_id` - The message ID
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_original_text(
+    handle: PlatformHandle,
+    message_id: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || message_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let message_id_str = match std::ffi::CStr::from_ptr(message_id).to_str() {
+        Ok(s) => s,
+        Err(_) => {
+            error::set_last_error(Error::invalid_utf8());
+            return std::ptr::null_mut();
+        }
+    };
+
+    #[cfg(feature = "scripting")]
+    let kept = object
+        .blocking_read()
+        .pipeline()
+        .original_text(message_id_str);
+    #[cfg(not(feature = "scripting"))]
+    let kept: Option<(String, Vec<types::Transformation>)> = None;
+
+    let (original_text, transformations) = match kept {
+        Some(kept) => kept,
+        None => {
+            let platform = object.blocking_read();
+            match runtime::block_on(platform.get_message(message_id_str)) {
+                Ok(message) => (message.text, Vec::new()),
+                Err(e) => {
+                    error::set_last_error(e);
+                    return std::ptr::null_mut();
+                }
+            }
+        }
+    };
+
+    let json = serde_json::json!({
+        "message_id": message_id_str,
+        "original_text": original_text,
+        "transformations": transformations,
+    });
+
+    match CString::new(json.to_string()) {
+        Ok(c_string) => c_string.into_raw(),
+        Err(_) => {
+            error::set_last_error(Error::new(
+                ErrorCode::OutOfMemory,
+                "Failed to allocate string",
+            ));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// ============================================================================
+// Activity-Driven Presence
+// ============================================================================
+
+/// FFI function: Configure how reported activities map to the user's status
+/// Replaces any previous configuration and forgets reported activities.
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `config_json` - JSON object {"rules": [{"activity": "in_meeting", "status": "dnd", "message": "In a meeting"}, ...], "idle_status": "online"}
+///   Rules are ordered by precedence (first match wins). NULL uses the default
+///   rules for "presenting", "on_call" and "in_meeting".
+///
+/// # Returns
+/// ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_configure_presence(
+    handle: PlatformHandle,
+    config_json: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    if !handles::PLATFORMS.contains(handle) {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    }
+
+    let config = if config_json.is_null() {
+        presence::PresenceConfig::default()
+    } else {
+        let json_str = match std::ffi::CStr::from_ptr(config_json).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        };
+        match json_input::parse(json_str, "presence config", "PresenceConfig") {
+            Ok(config) => config,
+            Err(e) => {
+                error::set_last_error(e);
+                return ErrorCode::InvalidArgument;
+            }
+        }
+    };
+
+    presence::register(handle as usize, presence::PresenceManager::new(config));
+    ErrorCode::Success
+}
+
+/// FFI function: Report that an activity started or ended
+/// If the resulting status differs from the one last set, the platform status
+/// (and status message) is updated. Presence must be configured first with
+/// communicator_platform_configure_presence().
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `source` - Name of the reporting source (e.g. "calendar", "screen_share")
+/// * `activity` - The activity (e.g. "in_meeting", "presenting", "on_call")
+/// * `active` - 1 if the activity started, 0 if it ended
+///
+/// # Returns
+/// ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_report_activity(
+    handle: PlatformHandle,
+    source: *const c_char,
+    activity: *const c_char,
+    active: std::os::raw::c_int,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || source.is_null() || activity.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let source_str = match std::ffi::CStr::from_ptr(source).to_str() {
+        Ok(s) => s,
+        Err(_) => {
+            error::set_last_error(Error::invalid_utf8());
+            return ErrorCode::InvalidUtf8;
+        }
+    };
+
+    let activity_str = match std::ffi::CStr::from_ptr(activity).to_str() {
+        Ok(s) => s,
+        Err(_) => {
+            error::set_last_error(Error::invalid_utf8());
+            return ErrorCode::InvalidUtf8;
+        }
+    };
+
+    let manager = match presence::get(handle as usize) {
+        Some(manager) => manager,
+        None => {
+            error::set_last_error(Error::new(
+                ErrorCode::InvalidState,
+                "Presence is not configured for this platform",
+            ));
+            return ErrorCode::InvalidState;
+        }
+    };
+
+    let state = match manager.set_activity(source_str, activity_str, active != 0) {
+        Some(state) => state,
+        None => return ErrorCode::Success,
+    };
+
+    let platform = object.blocking_read();
+    match runtime::block_on(manager.apply(&*platform, state)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+// ============================================================================
+// Desktop Notifications
+// ============================================================================
+
+/// FFI function: Publish mentions and direct messages as desktop notifications
+/// Notifications are sent to org.freedesktop.Notifications on the D-Bus session
+/// bus for events returned by communicator_platform_poll_event().
+/// Requires the library to be built with the `dbus` feature on Linux; otherwise
+/// returns COMMUNICATOR_ERROR_UNSUPPORTED.
+/// The platform must be connected (the user's mention rules decide what notifies).
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `app_name` - Application name shown in notifications, or NULL for "libcommunicator"
+///
+/// # Returns
+/// ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_enable_desktop_notifications(
+    handle: PlatformHandle,
+    app_name: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let app_name_str = if app_name.is_null() {
+        None
+    } else {
+        match std::ffi::CStr::from_ptr(app_name).to_str() {
+            Ok(s) => Some(s),
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    #[cfg(all(feature = "dbus", target_os = "linux"))]
+    {
+        let platform = object.blocking_read();
+        match runtime::block_on(platform.get_mention_rules()) {
+            Ok(rules) => {
+                let mut notifier = dbus::DesktopNotifier::new(rules);
+                if let Some(name) = app_name_str {
+                    notifier = notifier.with_app_name(name);
+                }
+                platform.pipeline().set_desktop_notifier(Some(notifier));
+                ErrorCode::Success
+            }
+            Err(e) => {
+                let code = e.code;
+                error::set_last_error(e);
+                code
+            }
+        }
+    }
+
+    #[cfg(not(all(feature = "dbus", target_os = "linux")))]
+    {
+        let _ = (object, app_name_str);
+        error::set_last_error(Error::unsupported(
+            "Desktop notifications require the dbus feature on Linux",
+        ));
+        ErrorCode::Unsupported
+    }
+}
+
+/// FFI function: Stop publishing desktop notifications for a platform
+///
+/// # Arguments
+/// * `handle` - The platform handle
+///
+/// # Returns
+/// ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_disable_desktop_notifications(
+    handle: PlatformHandle,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    #[cfg(all(feature = "dbus", target_os = "linux"))]
+    object.blocking_read().pipeline().set_desktop_notifier(None);
+    #[cfg(not(all(feature = "dbus", target_os = "linux")))]
+    let _ = object;
+
+    ErrorCode::Success
+}
+
+// ============================================================================
+// Reminders
+// ============================================================================
+
+/// FFI function: Persist the reminders of a platform handle to a JSON file
+/// Reminders already stored in the file are loaded; reminders that fell due in
+/// the meantime are delivered by the next communicator_platform_poll_event() calls.
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `path` - Path of the JSON file (created on the first change)
+///
+/// # Returns
+/// ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_set_reminder_store(
+    handle: PlatformHandle,
+    path: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || path.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let path_str = match std::ffi::CStr::from_ptr(path).to_str() {
+        Ok(s) => s,
+        Err(_) => {
+            error::set_last_error(Error::invalid_utf8());
+            return ErrorCode::InvalidUtf8;
+        }
+    };
+
+    let platform = object.blocking_read();
+    match platform.pipeline().reminders().set_store(path_str) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Schedule a reminder about a message or channel
+/// When the reminder falls due, communicator_platform_poll_event() returns a
+/// "reminder_due" event carrying the Reminder.
+/// Returns a JSON string representing the created Reminder
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `channel_id` - The channel the reminder is about
+/// * `message_id` - The message the reminder is about (pass NULL for the channel)
+/// * `note` - Note shown with the reminder (pass NULL for none)
+/// * `due_at` - When the reminder is due (Unix timestamp in milliseconds)
+/// * `server_side` - Non-zero to let the server deliver reminders about messages
+///   where the platform supports it; falls back to a local reminder otherwise
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_add_reminder(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    message_id: *const c_char,
+    note: *const c_char,
+    due_at: i64,
+    server_side: std::os::raw::c_int,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let channel_id_str = {
+        match std::ffi::CStr::from_ptr(channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let message_id_opt = if message_id.is_null() {
+        None
+    } else {
+        match std::ffi::CStr::from_ptr(message_id).to_str() {
+            Ok(s) => Some(s),
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let note_opt = if note.is_null() {
+        None
+    } else {
+        match std::ffi::CStr::from_ptr(note).to_str() {
+            Ok(s) => Some(s),
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let due_at = match chrono::DateTime::from_timestamp_millis(due_at) {
+        Some(due_at) => due_at,
+        None => {
+            error::set_last_error(Error::invalid_argument("Reminder time out of range"));
+            return std::ptr::null_mut();
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    let server_reminder = match message_id_opt.filter(|_| server_side != 0) {
+        Some(message_id) => {
+            match runtime::block_on(platform.set_message_reminder(message_id, due_at)) {
+                Ok(()) => {
+                    let mut reminder = types::Reminder::new(
+                        format!("server-{message_id}-{}", due_at.timestamp()),
+                        channel_id_str,
+                        due_at,
+                    )
+                    .with_message(message_id);
+                    reminder.note = note_opt.map(|n| n.to_string());
+                    reminder.server_side = true;
+                    Some(reminder)
+                }
+                Err(e) if e.code == ErrorCode::Unsupported => None,
+                Err(e) => {
+                    error::set_last_error(e);
+                    return std::ptr::null_mut();
+                }
+            }
+        }
+        None => None,
+    };
+
+    let result = match server_reminder {
+        Some(reminder) => Ok(reminder),
+        None => {
+            platform
+                .pipeline()
+                .reminders()
+                .add(channel_id_str, message_id_opt, note_opt, due_at)
+        }
+    };
+
+    match result {
+        Ok(reminder) => match serde_json::to_string(&reminder) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize reminder: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get the pending local reminders of a platform handle
+/// Returns a JSON array of Reminder objects, earliest first
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_list_reminders(
+    handle: PlatformHandle,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let pending = object.blocking_read().pipeline().reminders().list();
+
+    match serde_json::to_string(&pending) {
+        Ok(json) => match CString::new(json) {
+            Ok(c_string) => c_string.into_raw(),
+            Err(_) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::OutOfMemory,
+                    "Failed to allocate string",
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(Error::new(
+                ErrorCode::Unknown,
+                format!("Failed to serialize reminders: {e}"),
+            ));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Cancel a pending local reminder
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `reminder_id` - The reminder ID
+///
+/// # Returns
+/// ErrorCode indicating success or failure (NotFound if no such reminder is pending)
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_cancel_reminder(
+    handle: PlatformHandle,
+    reminder_id: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || reminder_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let reminder_id_str = match std::ffi::CStr::from_ptr(reminder_id).to_str() {
+        Ok(s) => s,
+        Err(_) => {
+            error::set_last_error(Error::invalid_utf8());
+            return ErrorCode::InvalidUtf8;
+        }
+    };
+
+    let cancelled = object
+        .blocking_read()
+        .pipeline()
+        .reminders()
+        .cancel(reminder_id_str);
+
+    match cancelled {
+        Ok(true) => ErrorCode::Success,
+        Ok(false) => {
+            error::set_last_error(Error::new(
+                ErrorCode::NotFound,
+                format!("No pending reminder {reminder_id_str}"),
+            ));
+            ErrorCode::NotFound
+        }
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+// ============================================================================
+// Drafts
+// ============================================================================
+
+/// FFI function: Save the draft of a channel or thread
+/// draft_json: JSON object with "channel_id", "message" and optionally
+/// "root_id", "file_ids" and "props"; replaces the draft of the same channel
+/// and thread. Drafts are synced through the server where it supports them and
+/// kept by the library for the lifetime of the handle otherwise.
+/// Returns a JSON string representing the saved Draft ("server_side" tells
+/// where it is stored)
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `draft_json` - The draft
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_save_draft(
+    handle: PlatformHandle,
+    draft_json: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || draft_json.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let Ok(draft_str) = std::ffi::CStr::from_ptr(draft_json).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+
+    let draft: types::Draft = match json_input::parse(draft_str, "draft", "Draft") {
+        Ok(draft) => draft,
+        Err(e) => {
+            error::set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    let platform = object.blocking_read();
+    let result = runtime::block_on(platform.save_draft(&draft));
+
+    match result {
+        Ok(value) => match serde_json::to_string(&value) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize draft: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Get the draft of a channel or thread
+/// Returns a JSON string representing the Draft, or "null" if there is none
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `channel_id` - The channel of the draft
+/// * `root_id` - The thread of the draft (pass NULL for the channel draft)
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_draft(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    root_id: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let Ok(channel_id_str) = std::ffi::CStr::from_ptr(channel_id).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+
+    let root_id_opt = if root_id.is_null() {
+        None
+    } else {
+        match std::ffi::CStr::from_ptr(root_id).to_str() {
+            Ok(s) => Some(s),
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+    let result = runtime::block_on(drafts::find(&*platform, channel_id_str, root_id_opt));
+
+    match result {
+        Ok(value) => match serde_json::to_string(&value) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize draft: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: List the drafts of the current user
+/// On platforms syncing drafts, the drafts in the current team
+/// Returns a JSON array of Draft objects, most recently saved first
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_drafts(handle: PlatformHandle) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+    let result = runtime::block_on(platform.get_drafts());
+
+    match result {
+        Ok(value) => match serde_json::to_string(&value) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize drafts: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Delete the draft of a channel or thread
+/// Deleting a draft that does not exist succeeds.
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `channel_id` - The channel of the draft
+/// * `root_id` - The thread of the draft (pass NULL for the channel draft)
+///
+/// # Returns
+/// ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_delete_draft(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    root_id: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let Ok(channel_id_str) = std::ffi::CStr::from_ptr(channel_id).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return ErrorCode::InvalidUtf8;
+    };
+
+    let root_id_opt = if root_id.is_null() {
+        None
+    } else {
+        match std::ffi::CStr::from_ptr(root_id).to_str() {
+            Ok(s) => Some(s),
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.delete_draft(channel_id_str, root_id_opt)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+// ============================================================================
+// Scheduled Messages
+// ============================================================================
+
+/// FFI function: Schedule a message to be sent at a later time
+/// The server sends the message where the platform supports it (Mattermost
+/// 10.3+); otherwise the library sends it once due, checked whenever events
+/// are polled, so it is only sent while the application keeps polling.
+/// Returns a JSON string representing the ScheduledMessage ("server_side"
+/// tells which was used)
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `channel_id` - The channel to send the message to
+/// * `text` - The message text
+/// * `send_at` - When to send the message (Unix timestamp in milliseconds, in
+///   the future)
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_schedule_message(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    text: *const c_char,
+    send_at: i64,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() || text.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let (Ok(channel_id_str), Ok(text_str)) = (
+        std::ffi::CStr::from_ptr(channel_id).to_str(),
+        std::ffi::CStr::from_ptr(text).to_str(),
+    ) else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+
+    let Some(send_at) = chrono::DateTime::from_timestamp_millis(send_at) else {
+        error::set_last_error(Error::invalid_argument("Scheduled time out of range"));
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+    let result = runtime::block_on(platform.schedule_message(channel_id_str, text_str, send_at));
+
+    match result {
+        Ok(value) => match serde_json::to_string(&value) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize scheduled message: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: List the scheduled messages that have not been sent yet
+/// Covers messages scheduled on the server and by the library; messages that
+/// failed to send are included with their "error".
+/// Returns a JSON array of ScheduledMessage objects, earliest first
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `handle` - The platform handle
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_get_scheduled_messages(
+    handle: PlatformHandle,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+    let result = runtime::block_on(platform.get_scheduled_messages());
+
+    match result {
+        Ok(value) => match serde_json::to_string(&value) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize scheduled messages: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Cancel a scheduled message before it is sent
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `scheduled_id` - The ID of the scheduled message
+///
+/// # Returns
+/// ErrorCode indicating success or failure (NotFound if no such message is pending)
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_cancel_scheduled_message(
+    handle: PlatformHandle,
+    scheduled_id: *const c_char,
+) -> ErrorCode {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || scheduled_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let Ok(scheduled_id_str) = std::ffi::CStr::from_ptr(scheduled_id).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return ErrorCode::InvalidUtf8;
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(platform.cancel_scheduled_message(scheduled_id_str)) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+// ============================================================================
+// Bridging
+// ============================================================================
+
+/// Opaque handle to a Bridge object
+pub type BridgeHandle = *mut c_void;
+
+/// FFI function: Create a bridge for relaying messages between platforms
+/// Returns an opaque handle to the bridge
+/// The handle must be freed with communicator_bridge_destroy()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `bridge_id` - Unique identifier of this bridge (used to recognize its own messages)
+/// * `max_hops` - Maximum number of bridges a message may pass through (0 for the default of 1)
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_bridge_create(
+    bridge_id: *const c_char,
+    max_hops: u32,
+) -> BridgeHandle {
+    error::clear_last_error();
+
+    if bridge_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let bridge_id_str = {
+        match std::ffi::CStr::from_ptr(bridge_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let mut config = bridge::BridgeConfig::new(bridge_id_str);
+    if max_hops > 0 {
+        config = config.with_max_hops(max_hops);
+    }
+
+    handles::BRIDGES.insert(handles::Object::new(bridge::Bridge::new(config)))
+}
+
+/// FFI function: Relay a message from one platform to another
+/// The message is skipped if it is an echo of a message relayed by this bridge
+/// or if it already passed through too many bridges.
+/// Returns a JSON object: {"decision": "forward"|"echo"|"loop_detected", "message": Message|null}
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error
+///
+/// # Arguments
+/// * `bridge` - The bridge handle
+/// * `source` - The platform the message was received from
+/// * `target` - The platform to relay the message to
+/// * `target_channel_id` - The channel on the target platform
+/// * `message_json` - The received message as JSON (as returned by the library)
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_bridge_forward(
+    bridge: BridgeHandle,
+    source: PlatformHandle,
+    target: PlatformHandle,
+    target_channel_id: *const c_char,
+    message_json: *const c_char,
+) -> *mut c_char {
+    error::begin_call(target as usize);
+
+    if bridge.is_null()
+        || source.is_null()
+        || target.is_null()
+        || target_channel_id.is_null()
+        || message_json.is_null()
+    {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let target_channel_id_str = {
+        match std::ffi::CStr::from_ptr(target_channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let message_json_str = {
+        match std::ffi::CStr::from_ptr(message_json).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let message: Message = match json_input::parse(message_json_str, "message", "Message") {
+        Ok(m) => m,
+        Err(e) => {
+            error::set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    let (Some(bridge), Some(source), Some(target)) = (
+        handles::BRIDGES.get(bridge),
+        handles::PLATFORMS.get(source),
+        handles::PLATFORMS.get(target),
+    ) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+    // A platform bridged to itself is locked once, so a queued writer cannot
+    // block the second lock while the first is held
+    let source_guard = source.blocking_read();
+    let target_guard = (!std::sync::Arc::ptr_eq(&source, &target)).then(|| target.blocking_read());
+    let target_platform = target_guard.as_deref().unwrap_or(&*source_guard);
+    let bridge = match bridge.lock() {
+        Ok(bridge) => bridge,
+        Err(e) => {
+            error::set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+    let (source, target) = (&*source_guard, target_platform);
+
+    match runtime::block_on(bridge.forward(&message, source, target, target_channel_id_str)) {
+        Ok((decision, sent)) => {
+            let result = serde_json::json!({
+                "decision": decision,
+                "message": sent,
+            });
+            match CString::new(result.to_string()) {
+                Ok(c_str) => c_str.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::invalid_utf8());
+                    std::ptr::null_mut()
+                }
+            }
+        }
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Destroy a bridge and free its memory
+/// After calling this, the handle is invalid and must not be used
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_bridge_destroy(bridge: BridgeHandle) {
+    if !bridge.is_null() {
+        handles::BRIDGES.remove(bridge);
+    }
+}
+
+// ============================================================================
+// Event Bus
+// ============================================================================
+
+/// Opaque handle to an EventBus object
+pub type EventBusHandle = *mut c_void;
+
+/// FFI function: Create an event bus merging the events of several platforms
+/// Returns an opaque handle to the bus
+/// The handle must be freed with communicator_eventbus_destroy()
+#[no_mangle]
+pub extern "C" fn communicator_eventbus_create() -> EventBusHandle {
+    error::clear_last_error();
+    handles::EVENT_BUSES.insert(handles::Object::new(event_bus::EventBus::new()))
+}
+
+/// FFI function: Add a platform to an event bus
+/// Its events are then taken by communicator_eventbus_poll() and must not be
+/// polled from the platform directly. A platform may be on several buses, but
+/// each event is delivered to only one of them.
+/// Returns ErrorCode::Success on success; ErrorCode::InvalidArgument if the
+/// ID or the platform is already on the bus, ErrorCode::InvalidState if the
+/// platform has an event callback
+///
+/// # Arguments
+/// * `bus` - The event bus handle
+/// * `platform` - The platform handle
+/// * `platform_id` - Identifier the platform's events are tagged with
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_eventbus_add_platform(
+    bus: EventBusHandle,
+    platform: PlatformHandle,
+    platform_id: *const c_char,
+) -> ErrorCode {
+    error::begin_call(platform as usize);
+
+    if bus.is_null() || platform.is_null() || platform_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let platform_id_str = {
+        match std::ffi::CStr::from_ptr(platform_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let Some(bus) = handles::EVENT_BUSES.get(bus) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+    if !handles::PLATFORMS.contains(platform) {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    }
+
+    if event_callback::is_registered(platform as usize) {
+        error::set_last_error(Error::new(
+            ErrorCode::InvalidState,
+            "Events are delivered to the event callback",
+        ));
+        return ErrorCode::InvalidState;
+    }
+
+    match bus
+        .lock()
+        .and_then(|mut bus| bus.add(platform_id_str, platform as usize))
+    {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Remove a platform from an event bus
+/// Its events still queued on the bus are dropped
+/// Returns ErrorCode::Success on success, ErrorCode::NotFound if no platform
+/// was added under the ID
+///
+/// # Arguments
+/// * `bus` - The event bus handle
+/// * `platform_id` - Identifier the platform was added under
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_eventbus_remove_platform(
+    bus: EventBusHandle,
+    platform_id: *const c_char,
+) -> ErrorCode {
+    error::clear_last_error();
+
+    if bus.is_null() || platform_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let platform_id_str = {
+        match std::ffi::CStr::from_ptr(platform_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return ErrorCode::InvalidUtf8;
+            }
+        }
+    };
+
+    let Some(bus) = handles::EVENT_BUSES.get(bus) else {
+        error::set_last_error(handles::invalid_handle());
+        return ErrorCode::InvalidArgument;
+    };
+
+    let removed = match bus.lock() {
+        Ok(mut bus) => bus.remove(platform_id_str),
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            return code;
+        }
+    };
+    if removed {
+        ErrorCode::Success
+    } else {
+        error::set_last_error(Error::new(
+            ErrorCode::NotFound,
+            format!("No platform \"{platform_id_str}\" on the event bus"),
+        ));
+        ErrorCode::NotFound
+    }
+}
+
+/// FFI function: Poll for the next event of any platform on an event bus
+/// Events go through the same pipeline as communicator_platform_poll_event();
+/// platforms are polled in turn so a busy one cannot starve the others, and
+/// destroyed platforms leave the bus.
+/// Returns a JSON object: {"platform_id": "...", "sequence": N,
+/// "received_at": "...", "event": Event}
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL if no events or on error; an error is only reported when no
+/// platform had an event
+///
+/// # Arguments
+/// * `bus` - The event bus handle
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_eventbus_poll(bus: EventBusHandle) -> *mut c_char {
+    error::clear_last_error();
+
+    if bus.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(bus) = handles::EVENT_BUSES.get(bus) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let next = |key: usize| {
+        if event_callback::is_registered(key) {
+            return Err(Error::new(
+                ErrorCode::InvalidState,
+                "Events are delivered to the event callback",
+            ));
+        }
+        next_event_as(key as PlatformHandle, |_, json| Ok(json))
+    };
+    let alive = |key: usize| handles::PLATFORMS.contains(key as PlatformHandle);
+
+    match bus.lock().and_then(|mut bus| bus.poll(next, alive)) {
+        Ok(Some(event)) => match serde_json::to_string(&event) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_str) => c_str.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::invalid_utf8());
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize event: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        // No events available, not an error
+        Ok(None) => std::ptr::null_mut(),
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Destroy an event bus and free its memory
+/// The platforms on it are not destroyed
+/// After calling this, the handle is invalid and must not be used
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_eventbus_destroy(bus: EventBusHandle) {
+    if !bus.is_null() {
+        handles::EVENT_BUSES.remove(bus);
+    }
+}
+
+// ============================================================================
+// Copying Messages Between Accounts
+// ============================================================================
+
+/// FFI function: Copy a message to a channel of another account, optionally moving it
+/// Attachments are uploaded again and libcommunicator props carried over; the
+/// copy is tagged with its origin in the "libcommunicator_copied_from" prop
+/// Returns a JSON CopyReport {"message": Message|null, "items": [...]}
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error (e.g. the original could not be read)
+///
+/// # Arguments
+/// * `source` - The platform the message is on
+/// * `message_id` - The message to copy
+/// * `target` - The platform to copy it to (may be `source`)
+/// * `target_channel_id` - The channel on the target platform
+/// * `delete_source` - Non-zero to delete the original once everything was copied
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_copy_message(
+    source: PlatformHandle,
+    message_id: *const c_char,
+    target: PlatformHandle,
+    target_channel_id: *const c_char,
+    delete_source: std::os::raw::c_int,
+) -> *mut c_char {
+    error::begin_call(target as usize);
+
+    if source.is_null() || message_id.is_null() || target.is_null() || target_channel_id.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let (Some(source), Some(target)) = (
+        handles::PLATFORMS.get(source),
+        handles::PLATFORMS.get(target),
+    ) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let message_id_str = {
+        match std::ffi::CStr::from_ptr(message_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let target_channel_id_str = {
+        match std::ffi::CStr::from_ptr(target_channel_id).to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    // Copying within one account locks it once, as in communicator_bridge_forward()
+    let source_guard = source.blocking_read();
+    let target_guard = (!std::sync::Arc::ptr_eq(&source, &target)).then(|| target.blocking_read());
+    let target_platform = target_guard.as_deref().unwrap_or(&*source_guard);
+
+    match runtime::block_on(message_copy::copy_message(
+        &*source_guard,
+        message_id_str,
+        target_platform,
+        target_channel_id_str,
+        delete_source != 0,
+    )) {
+        Ok(report) => match serde_json::to_string(&report) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize copy report: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// ============================================================================
+// Importing History
+// ============================================================================
+
+/// FFI function: Import a JSONL export into a channel
+/// Lines are Message objects or Mattermost bulk export lines; messages are sent
+/// in batches, tagged with their origin in the "libcommunicator_imported_from" prop
+/// Returns a JSON ImportReport {"imported": n, "skipped": n, "failed": [...]}
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error (e.g. invalid options)
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `channel_id` - The channel to import into
+/// * `jsonl` - The export, one JSON object per line
+/// * `options_json` - JSON ImportOptions, or NULL for the defaults
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_import_history(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    jsonl: *const c_char,
+    options_json: *const c_char,
+) -> *mut c_char {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() || jsonl.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let Ok(channel_id_str) = std::ffi::CStr::from_ptr(channel_id).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+
+    let Ok(jsonl_str) = std::ffi::CStr::from_ptr(jsonl).to_str() else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+
+    let options = if options_json.is_null() {
+        import::ImportOptions::default()
+    } else {
+        let Ok(options_str) = std::ffi::CStr::from_ptr(options_json).to_str() else {
+            error::set_last_error(Error::invalid_utf8());
+            return std::ptr::null_mut();
+        };
+        match json_input::parse(options_str, "import options", "ImportOptions") {
+            Ok(options) => options,
+            Err(e) => {
+                error::set_last_error(e);
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let platform = object.blocking_read();
+
+    match runtime::block_on(import::import_history(
+        &*platform,
+        channel_id_str,
+        jsonl_str,
+        &options,
+    )) {
+        Ok(report) => match serde_json::to_string(&report) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_string) => c_string.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::new(
+                        ErrorCode::OutOfMemory,
+                        "Failed to allocate string",
+                    ));
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize import report: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// ============================================================================
+// Cached Entity Iterators
+// ============================================================================
+
+/// Opaque handle to an EntityIter object
+pub type EntityIterHandle = *mut c_void;
+
+/// Create an iterator over a snapshot of cached entities
+///
+/// # Safety
+/// `handle` must be NULL or a handle returned by this library
+unsafe fn iter_cached<T, F>(handle: PlatformHandle, fetch: F) -> EntityIterHandle
+where
+    T: serde::Serialize + Send,
+    F: for<'a> FnOnce(
+        &'a dyn Platform,
+    ) -> std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<Vec<T>>> + Send + 'a>,
+    >,
+{
+    error::begin_call(handle as usize);
+
+    if handle.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(object) = handles::PLATFORMS.get(handle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let platform = object.blocking_read();
+
+    let iter = runtime::block_on(fetch(&*platform)).and_then(entity_iter::EntityIter::new);
+    match iter {
+        Ok(iter) => handles::ENTITY_ITERS.insert(handles::Object::new(iter)),
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Iterate over the channels the platform has cached
+/// Makes no network calls: the iterator yields a snapshot of the cache taken
+/// now, ordered by channel ID, with channels fetched or updated earlier
+/// Returns an opaque iterator handle, or NULL on error
+/// The handle must be freed with communicator_iter_destroy()
+///
+/// # Arguments
+/// * `handle` - The platform handle
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_iter_channels(
+    handle: PlatformHandle,
+) -> EntityIterHandle {
+    iter_cached(handle, |platform| platform.cached_channels())
+}
+
+/// FFI function: Iterate over the users the platform has cached
+/// Makes no network calls: the iterator yields a snapshot of the cache taken
+/// now, ordered by user ID
+/// Returns an opaque iterator handle, or NULL on error
+/// The handle must be freed with communicator_iter_destroy()
+///
+/// # Arguments
+/// * `handle` - The platform handle
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_iter_users(
+    handle: PlatformHandle,
+) -> EntityIterHandle {
+    iter_cached(handle, |platform| platform.cached_users())
+}
+
+/// FFI function: Get the next entity of an iterator
+/// Returns a JSON string of the entity (Channel or User)
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL when the iterator is exhausted (no error) or on error
+///
+/// # Arguments
+/// * `iter` - The iterator handle
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_iter_next(iter: EntityIterHandle) -> *mut c_char {
+    error::clear_last_error();
+
+    if iter.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(iter) = handles::ENTITY_ITERS.get(iter) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+
+    let next = match iter.lock() {
+        Ok(mut iter) => iter.next(),
+        Err(e) => {
+            error::set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    match next {
+        Some(entity) => match CString::new(entity.to_string()) {
+            Ok(c_str) => c_str.into_raw(),
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                std::ptr::null_mut()
+            }
+        },
+        // Exhausted, not an error
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// FFI function: Get the number of entities an iterator has not yielded yet
+/// Returns the count, or -1 on error
+///
+/// # Arguments
+/// * `iter` - The iterator handle
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_iter_remaining(iter: EntityIterHandle) -> i64 {
+    error::clear_last_error();
+
+    if iter.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return -1;
+    }
+
+    match handles::ENTITY_ITERS.get(iter) {
+        Some(iter) => match iter.lock() {
+            Ok(iter) => iter.remaining() as i64,
+            Err(e) => {
+                error::set_last_error(e);
+                -1
+            }
+        },
+        None => {
+            error::set_last_error(handles::invalid_handle());
+            -1
+        }
+    }
+}
+
+/// FFI function: Destroy an iterator and free its memory
+/// After calling this, the handle is invalid and must not be used
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_iter_destroy(iter: EntityIterHandle) {
+    if !iter.is_null() {
+        handles::ENTITY_ITERS.remove(iter);
+    }
+}
+
+/// Opaque handle to a cursor over a channel's message history
+pub type MessageCursorHandle = *mut c_void;
+
+/// FFI function: Open a cursor walking a channel's message history
+/// Each communicator_message_cursor_next() continues where the previous batch
+/// ended, so callers don't track pagination themselves
+/// Returns an opaque cursor handle, or NULL on error
+/// The handle must be freed with communicator_message_cursor_close()
+///
+/// # Arguments
+/// * `handle` - The platform handle
+/// * `channel_id` - The channel
+/// * `from` - Message ID to start from (not included), or NULL for the newest message
+/// * `direction` - "older" or "newer"; "newer" needs `from`
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_open_message_cursor(
+    handle: PlatformHandle,
+    channel_id: *const c_char,
+    from: *const c_char,
+    direction: *const c_char,
+) -> MessageCursorHandle {
+    error::begin_call(handle as usize);
+
+    if handle.is_null() || channel_id.is_null() || direction.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    if !handles::PLATFORMS.contains(handle) {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    }
+
+    let (Ok(channel_id), Ok(direction)) = (
+        std::ffi::CStr::from_ptr(channel_id).to_str(),
+        std::ffi::CStr::from_ptr(direction).to_str(),
+    ) else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+    let from = if from.is_null() {
+        None
+    } else {
+        match std::ffi::CStr::from_ptr(from).to_str() {
+            Ok(s) => Some(s),
+            Err(_) => {
+                error::set_last_error(Error::invalid_utf8());
+                return std::ptr::null_mut();
+            }
+        }
+    };
+
+    let cursor = message_cursor::Direction::parse(direction).and_then(|direction| {
+        message_cursor::MessageCursor::new(handle as usize, channel_id, from, direction)
+    });
+    match cursor {
+        Ok(cursor) => handles::MESSAGE_CURSORS.insert(handles::Object::new(cursor)),
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Fetch the next batch of messages of a cursor
+/// Returns a JSON array of up to batch_size messages, oldest first; an empty
+/// array once the start (or end) of the history is reached
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error, e.g. if the cursor's platform was destroyed
+///
+/// # Arguments
+/// * `cursor` - The cursor handle
+/// * `batch_size` - Maximum number of messages to fetch
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_message_cursor_next(
+    cursor: MessageCursorHandle,
+    batch_size: u32,
+) -> *mut c_char {
+    error::clear_last_error();
+
+    if cursor.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Some(cursor) = handles::MESSAGE_CURSORS.get(cursor) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+    let mut cursor = match cursor.lock() {
+        Ok(cursor) => cursor,
+        Err(e) => {
+            error::set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+    error::begin_call(cursor.platform);
+
+    let Some(object) = handles::PLATFORMS.get(cursor.platform as PlatformHandle) else {
+        error::set_last_error(handles::invalid_handle());
+        return std::ptr::null_mut();
+    };
+    let platform = object.blocking_read();
+
+    let messages = match runtime::block_on(cursor.next_batch(&*platform, batch_size as usize)) {
+        Ok(messages) => messages,
+        Err(e) => {
+            error::set_last_error(e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    match serde_json::to_string(&messages) {
+        Ok(json) => match CString::new(json) {
+            Ok(c_str) => c_str.into_raw(),
+            Err(_) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::OutOfMemory,
+                    "Failed to allocate string",
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(Error::new(
+                ErrorCode::Unknown,
+                format!("Failed to serialize messages: {e}"),
+            ));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Close a message cursor and free its memory
+/// After calling this, the handle is invalid and must not be used
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_message_cursor_close(cursor: MessageCursorHandle) {
+    if !cursor.is_null() {
+        handles::MESSAGE_CURSORS.remove(cursor);
+    }
+}
+
+// ============================================================================
+// Cancellation
+// ============================================================================
+
+/// Opaque handle to a cancellation of long-running calls
+pub type CancellationHandle = *mut c_void;
+
+/// Look up the cancellation passed to an `_ex` call
+///
+/// # Returns
+/// Some(None) for NULL, Some(cancellation) for a live cancellation handle,
+/// None for any other handle
+fn cancellation_arg(
+    cancellation: CancellationHandle,
+) -> Option<Option<std::sync::Arc<cancellation::Cancellation>>> {
+    if cancellation.is_null() {
+        return Some(None);
+    }
+    handles::CANCELLATIONS.get(cancellation).map(Some)
+}
+
+/// FFI function: Create a cancellation for long-running calls
+/// Pass it to the _ex variant of an operation (connect, search, file download)
+/// and cancel it from another thread to make the call return
+/// ErrorCode::Cancelled. A cancelled handle stays cancelled.
+/// The handle must be freed with communicator_cancellation_destroy()
+#[no_mangle]
+pub extern "C" fn communicator_cancellation_create() -> CancellationHandle {
+    error::clear_last_error();
+    handles::CANCELLATIONS.insert(cancellation::Cancellation::new())
+}
+
+/// FFI function: Cancel the calls running with a cancellation, and the ones to come
+/// Safe to call from any thread, while the calls block
+/// Returns ErrorCode::Success, or ErrorCode::InvalidArgument for an invalid handle
+///
+/// # Arguments
+/// * `cancellation` - The cancellation handle
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_cancellation_cancel(
+    cancellation: CancellationHandle,
+) -> ErrorCode {
+    error::clear_last_error();
+
+    if cancellation.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+    match handles::CANCELLATIONS.get(cancellation) {
+        Some(cancellation) => {
+            cancellation.cancel();
+            ErrorCode::Success
+        }
+        None => {
+            error::set_last_error(handles::invalid_handle());
+            ErrorCode::InvalidArgument
+        }
+    }
+}
+
+/// FFI function: Destroy a cancellation and free its memory
+/// Calls still running with it are not cancelled
+/// After calling this, the handle is invalid and must not be used
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_cancellation_destroy(cancellation: CancellationHandle) {
+    if !cancellation.is_null() {
+        handles::CANCELLATIONS.remove(cancellation);
+    }
+}
+
+// ============================================================================
+// Credential Storage
+// ============================================================================
+
+/// Read a C string argument of the credential functions
+///
+/// # Safety
+/// `s` must be a valid NUL-terminated string
+unsafe fn credential_arg<'a>(s: *const c_char) -> std::result::Result<&'a str, ErrorCode> {
+    match std::ffi::CStr::from_ptr(s).to_str() {
+        Ok(s) => Ok(s),
+        Err(_) => {
+            error::set_last_error(Error::invalid_utf8());
+            Err(ErrorCode::InvalidUtf8)
+        }
+    }
+}
+
+/// FFI function: Choose where credentials are stored
+/// Credentials in the previous store are not carried over
+/// config_json: JSON string, one of:
+/// { "backend": "memory" } (the default)
+/// { "backend": "file", "path": "/path/to/credentials.json", "passphrase": "..." }
+/// { "backend": "keyring", "service": "my-app" } (`keyring` feature)
+/// Returns ErrorCode indicating success or failure; AuthenticationFailed if
+/// the passphrase of an existing file is wrong
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_credentials_configure(
+    config_json: *const c_char,
+) -> ErrorCode {
+    error::clear_last_error();
+
+    if config_json.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let config_str = match credential_arg(config_json) {
+        Ok(s) => s,
+        Err(code) => return code,
+    };
+
+    let config: credentials::StoreConfig =
+        match json_input::parse(config_str, "config", "CredentialStoreConfig") {
+            Ok(c) => c,
+            Err(e) => {
+                error::set_last_error(e);
+                return ErrorCode::InvalidArgument;
+            }
+        };
+
+    match config.open() {
+        Ok(store) => {
+            credentials::set_store(store);
+            ErrorCode::Success
+        }
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Store credentials under an alias
+/// Connect configurations can then pass "credentials_alias" instead of the
+/// credentials themselves
+/// Returns ErrorCode indicating success or failure
+///
+/// # Arguments
+/// * `alias` - Name to store the credentials under
+/// * `credentials_json` - JSON object as the "credentials" of
+///   communicator_platform_connect()
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_credentials_store(
+    alias: *const c_char,
+    credentials_json: *const c_char,
+) -> ErrorCode {
+    error::clear_last_error();
+
+    if alias.is_null() || credentials_json.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let (alias_str, credentials_str) =
+        match (credential_arg(alias), credential_arg(credentials_json)) {
+            (Ok(a), Ok(c)) => (a, c),
+            (Err(code), _) | (_, Err(code)) => return code,
+        };
+
+    let credentials: credentials::Credentials =
+        match json_input::parse(credentials_str, "credentials", "string map") {
+            Ok(c) => c,
+            Err(e) => {
+                error::set_last_error(e);
+                return ErrorCode::InvalidArgument;
+            }
+        };
+
+    match credentials::store().store(alias_str, &credentials) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Get the credentials stored under an alias
+/// Returns a JSON object of the credentials
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error (ErrorCode::NotFound if nothing is stored under the alias)
+///
+/// # Arguments
+/// * `alias` - Name the credentials were stored under
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_credentials_retrieve(alias: *const c_char) -> *mut c_char {
+    error::clear_last_error();
+
+    if alias.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let Ok(alias_str) = credential_arg(alias) else {
+        return std::ptr::null_mut();
+    };
+
+    match credentials::store().retrieve(alias_str) {
+        Ok(credentials) => match serde_json::to_string(&credentials) {
+            Ok(json) => match CString::new(json) {
+                Ok(c_str) => c_str.into_raw(),
+                Err(_) => {
+                    error::set_last_error(Error::invalid_utf8());
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                error::set_last_error(Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize credentials: {e}"),
+                ));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Delete the credentials stored under an alias
+/// Returns ErrorCode::Success, or ErrorCode::NotFound if nothing was stored under it
+///
+/// # Arguments
+/// * `alias` - Name the credentials were stored under
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_credentials_delete(alias: *const c_char) -> ErrorCode {
+    error::clear_last_error();
+
+    if alias.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return ErrorCode::NullPointer;
+    }
+
+    let alias_str = match credential_arg(alias) {
+        Ok(s) => s,
+        Err(code) => return code,
+    };
+
+    match credentials::store().delete(alias_str) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+// ============================================================================
+// Debug Capture
+// ============================================================================
+
+/// FFI function: Start capturing HTTP request summaries and WebSocket frames
+/// config_json: A JSON CaptureConfig, or NULL for the defaults:
+/// {"capacity": 1000, "path": null, "max_payload_bytes": 65536}
+/// Entries are kept in a ring buffer of `capacity` entries and, if `path` is
+/// set, appended to that file one JSON object per line; tokens and passwords
+/// are redacted. Starting a capture discards the entries of the last one.
+/// Returns ErrorCode::InvalidArgument if the file can't be opened
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_debug_capture_start(config_json: *const c_char) -> ErrorCode {
+    error::clear_last_error();
+
+    let config = if config_json.is_null() {
+        capture::CaptureConfig::default()
+    } else {
+        let Ok(config_str) = std::ffi::CStr::from_ptr(config_json).to_str() else {
+            error::set_last_error(Error::invalid_utf8());
+            return ErrorCode::InvalidUtf8;
+        };
+        match json_input::parse(config_str, "config", "CaptureConfig") {
+            Ok(config) => config,
+            Err(e) => {
+                error::set_last_error(e);
+                return ErrorCode::InvalidArgument;
+            }
+        }
+    };
+
+    match capture::start(config) {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// FFI function: Stop capturing
+/// The entries stay available until the next capture starts
+#[no_mangle]
+pub extern "C" fn communicator_debug_capture_stop() {
+    error::clear_last_error();
+    capture::stop();
+}
+
+/// FFI function: Get the entries of the running or last capture
+/// Returns a JSON array of CaptureEntry objects, oldest first:
+/// [{"kind": "http", "at": 1700000000000, "method": "GET", "url": "...",
+///   "status": 200, "request_id": "...", "elapsed_ms": 42, "error": null},
+///  {"kind": "frame", "at": 1700000000000, "direction": "received",
+///   "url": "...", "opcode": "text", "payload": "...", "truncated": false}]
+/// The caller must free the returned string using communicator_free_string()
+#[no_mangle]
+pub extern "C" fn communicator_debug_capture_entries() -> *mut c_char {
+    error::clear_last_error();
+    match serde_json::to_string(&capture::entries())
+        .ok()
+        .and_then(|json| CString::new(json).ok())
+    {
+        Some(c_string) => c_string.into_raw(),
+        None => {
+            error::set_last_error(Error::new(
+                ErrorCode::OutOfMemory,
+                "Failed to allocate string",
+            ));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// ============================================================================
+// Persisted State Migrations
+// ============================================================================
+
+/// Serialize a migration result for C callers
+fn migration_json<T: serde::Serialize>(value: &T) -> *mut c_char {
+    match serde_json::to_string(value)
+        .ok()
+        .and_then(|json| CString::new(json).ok())
+    {
+        Some(c_string) => c_string.into_raw(),
+        None => {
+            error::set_last_error(Error::new(
+                ErrorCode::OutOfMemory,
+                "Failed to allocate string",
+            ));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: Upgrade a persisted file to the current format
+/// Stores are upgraded when they are opened; this reports (or applies) the
+/// migrations ahead of time, e.g. for an installer
+/// store: "reminders", "outbox", "startup_cache" or "entity_cache"
+/// path: The file
+/// dry_run: Non-zero to only report the migrations, leaving the file unchanged
+/// Returns a JSON MigrationReport, or "null" if the file does not exist or is
+/// up to date; a migrated file's original is kept as <file>.v<version>.bak
+/// The caller must free the returned string using communicator_free_string()
+/// Returns NULL on error (InvalidState for a file written by a newer library)
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_migrate_store(
+    store: *const c_char,
+    path: *const c_char,
+    dry_run: i32,
+) -> *mut c_char {
+    error::clear_last_error();
+
+    if store.is_null() || path.is_null() {
+        error::set_last_error(Error::null_pointer());
+        return std::ptr::null_mut();
+    }
+
+    let (Ok(store_str), Ok(path_str)) = (
+        std::ffi::CStr::from_ptr(store).to_str(),
+        std::ffi::CStr::from_ptr(path).to_str(),
+    ) else {
+        error::set_last_error(Error::invalid_utf8());
+        return std::ptr::null_mut();
+    };
+
+    // Report the migrations to the log callbacks, as when a store is opened
+    let result = telemetry::scoped(|| {
+        migrations::format(store_str).and_then(|format| {
+            migrations::migrate(format, std::path::Path::new(path_str), dry_run != 0)
+        })
+    });
+    match result {
+        Ok(report) => migration_json(&report),
+        Err(e) => {
+            error::set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// FFI function: List the migrations applied since the library was loaded
+/// Each is also reported to the log callbacks of all contexts when applied
+/// Returns a JSON array of MigrationReport objects, oldest first
+/// The caller must free the returned string using communicator_free_string()
+#[no_mangle]
+pub extern "C" fn communicator_migrations_applied() -> *mut c_char {
+    error::clear_last_error();
+    migration_json(&migrations::applied())
+}
+
+// ============================================================================
+// Simulation
+// ============================================================================
+
+/// Report the result of a simulation call
+#[cfg(feature = "simulation")]
+fn simulation_result(result: Result<()>) -> ErrorCode {
+    match result {
+        Ok(()) => ErrorCode::Success,
+        Err(e) => {
+            let code = e.code;
+            error::set_last_error(e);
+            code
+        }
+    }
+}
+
+/// Fail a simulation call made without the `simulation` feature
+#[cfg(not(feature = "simulation"))]
+fn simulation_unsupported() -> ErrorCode {
+    error::set_last_error(Error::unsupported(
+        "Simulation requires the simulation feature",
+    ));
+    ErrorCode::Unsupported
+}
+
+/// Read the C string arguments of a simulation call
+///
+/// # Safety
+/// Each pointer must be NULL or a valid NUL-terminated string
+unsafe fn simulation_args<'a, const N: usize>(
+    args: [*const c_char; N],
+) -> std::result::Result<[&'a str; N], ErrorCode> {
+    if args.iter().any(|arg| arg.is_null()) {
+        error::set_last_error(Error::null_pointer());
+        return Err(ErrorCode::NullPointer);
+    }
+    let mut strs = [""; N];
+    for (s, arg) in strs.iter_mut().zip(args) {
+        *s = credential_arg(arg)?;
+    }
+    Ok(strs)
+}
+
+/// Look up the simulated server of a host
+#[cfg(feature = "simulation")]
+fn simulated_server(host: &str) -> Result<std::sync::Arc<simulation::SimulatedServer>> {
+    simulation::server(host)
+        .ok_or_else(|| Error::invalid_argument(format!("No simulated server for {host}")))
+}
+
+/// FFI function: Pause the clock for a deterministic simulation
+/// Timers (reconnect backoff, pings, timeouts) then only fire as
+/// communicator_simulation_advance() moves the clock
+/// Requires the `simulation` feature and communicator_init_host_driven()
+/// Returns ErrorCode indicating success or failure
+#[no_mangle]
+pub extern "C" fn communicator_simulation_enable() -> ErrorCode {
+    error::clear_last_error();
+
+    #[cfg(feature = "simulation")]
+    {
+        simulation_result(simulation::enable())
+    }
+
+    #[cfg(not(feature = "simulation"))]
+    {
+        simulation_unsupported()
+    }
+}
+
+/// FFI function: Advance the simulation clock
+/// Everything due meanwhile runs in order, e.g. each reconnect attempt of a
+/// backoff sequence
+/// Returns ErrorCode indicating success or failure (InvalidState if the clock
+/// is not paused)
+#[no_mangle]
+pub extern "C" fn communicator_simulation_advance(ms: u64) -> ErrorCode {
+    error::clear_last_error();
+
+    #[cfg(feature = "simulation")]
+    {
+        simulation_result(simulation::advance(std::time::Duration::from_millis(ms)))
+    }
+
+    #[cfg(not(feature = "simulation"))]
+    {
+        let _ = ms;
+        simulation_unsupported()
+    }
+}
+
+/// FFI function: Simulate a Mattermost server for a host name
+/// Platforms connecting to http://<host> then talk to it in memory; logins
+/// succeed and return user_json. Replaces any previous server of the host
+/// user_json: JSON Mattermost user, or NULL for a default one
+/// Returns ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_simulation_add_server(
+    host: *const c_char,
+    user_json: *const c_char,
+) -> ErrorCode {
+    error::clear_last_error();
+
+    let [host_str] = match simulation_args([host]) {
+        Ok(args) => args,
+        Err(code) => return code,
+    };
+    let user_str = if user_json.is_null() {
+        None
+    } else {
+        match credential_arg(user_json) {
+            Ok(s) => Some(s),
+            Err(code) => return code,
+        }
+    };
+
+    #[cfg(feature = "simulation")]
+    {
+        let user = match user_str.map(|s| json_input::parse::<serde_json::Value>(s, "user", "User"))
+        {
+            Some(Ok(user)) => Some(user),
+            Some(Err(e)) => {
+                error::set_last_error(e);
+                return ErrorCode::InvalidArgument;
+            }
+            None => None,
+        };
+        let server = simulation::add_server(host_str);
+        if let Some(user) = user {
+            server.set_user(user);
+        }
+        ErrorCode::Success
+    }
+
+    #[cfg(not(feature = "simulation"))]
+    {
+        let _ = (host_str, user_str);
+        simulation_unsupported()
+    }
+}
+
+/// FFI function: Answer requests to an API path of a simulated server
+/// method: HTTP method, e.g. "GET"
+/// path: API path without /api/v4, e.g. "/users/me/teams"
+/// status: HTTP status code
+/// body: Response body (JSON)
+/// Returns ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_simulation_set_route(
+    host: *const c_char,
+    method: *const c_char,
+    path: *const c_char,
+    status: u16,
+    body: *const c_char,
+) -> ErrorCode {
+    error::clear_last_error();
+
+    let [host_str, method_str, path_str, body_str] =
+        match simulation_args([host, method, path, body]) {
+            Ok(args) => args,
+            Err(code) => return code,
+        };
+
+    #[cfg(feature = "simulation")]
+    {
+        simulation_result(
+            simulated_server(host_str)
+                .map(|server| server.set_route(method_str, path_str, status, body_str)),
+        )
+    }
+
+    #[cfg(not(feature = "simulation"))]
+    {
+        let _ = (host_str, method_str, path_str, status, body_str);
+        simulation_unsupported()
+    }
+}
+
+/// FFI function: Send a WebSocket event from a simulated server
+/// event_json: JSON Mattermost WebSocket event, e.g.
+/// {"event": "posted", "data": {...}, "broadcast": {...}}; the server numbers
+/// events itself. Without an open connection, the event is sent once a
+/// client connects
+/// Returns ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_simulation_push_event(
+    host: *const c_char,
+    event_json: *const c_char,
+) -> ErrorCode {
+    error::clear_last_error();
+
+    let [host_str, event_str] = match simulation_args([host, event_json]) {
+        Ok(args) => args,
+        Err(code) => return code,
+    };
+
+    #[cfg(feature = "simulation")]
+    {
+        let event: serde_json::Value = match json_input::parse(event_str, "event", "WebSocketEvent")
+        {
+            Ok(event) => event,
+            Err(e) => {
+                error::set_last_error(e);
+                return ErrorCode::InvalidArgument;
+            }
+        };
+        simulation_result(simulated_server(host_str).map(|server| server.push_event(event)))
+    }
+
+    #[cfg(not(feature = "simulation"))]
+    {
+        let _ = (host_str, event_str);
+        simulation_unsupported()
+    }
+}
+
+/// FFI function: Take a simulated server off the network, or bring it back
+/// While unreachable, its WebSocket connection is dropped, new connections
+/// are refused and HTTP requests get 502 Bad Gateway
+/// reachable: Non-zero to bring the server back
+/// Returns ErrorCode indicating success or failure
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_simulation_set_reachable(
+    host: *const c_char,
+    reachable: i32,
+) -> ErrorCode {
+    error::clear_last_error();
+
+    let [host_str] = match simulation_args([host]) {
+        Ok(args) => args,
+        Err(code) => return code,
+    };
+
+    #[cfg(feature = "simulation")]
+    {
+        simulation_result(
+            simulated_server(host_str).map(|server| server.set_reachable(reachable != 0)),
+        )
+    }
+
+    #[cfg(not(feature = "simulation"))]
+    {
+        let _ = (host_str, reachable);
+        simulation_unsupported()
+    }
+}
+
+// ============================================================================
+// Platform Cleanup
+// ============================================================================
+
+/// FFI function: Destroy a platform and free its memory
+/// After calling this, the handle is invalid and must not be used
+/// A shared handle is only freed when its last consumer destroys it
+///
+/// # Safety
+/// The caller must ensure that `handle` is a valid pointer that was created by
+/// this library and has not been freed already.
+#[no_mangle]
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from C.
+/// The caller must ensure all pointer arguments are valid.
+pub unsafe extern "C" fn communicator_platform_destroy(handle: PlatformHandle) {
+    if handles::PLATFORMS.contains(handle) && shared::release(handle as usize) {
+        event_callback::clear(handle as usize);
+        presence::clear(handle as usize);
+        error::clear_handle_error(handle as usize);
+        handles::PLATFORMS.remove(handle);
+    }
+}
@@ -0,0 +1,248 @@
+//! Registry of the objects behind C handles
+//!
+//! Handles given to C callers are IDs into a registry rather than pointers to
+//! the objects. IDs are never reused and are unique across all kinds of
+//! objects, so a handle that was destroyed, or one of another kind (e.g. a
+//! context passed as a platform), is rejected with `ErrorCode::InvalidArgument`
+//! instead of being dereferenced. Lookups return an `Arc`, so an object
+//! destroyed while another thread is using it is freed when that call returns.
+//!
+//! Platforms are behind a [`PlatformLock`], so a platform handle may be used
+//! from several threads at once: calls that change the connection (connect,
+//! disconnect, subscribing, polling events) take the lock for writing and wait
+//! for the others, which share it. Contexts, bridges, event buses, entity
+//! iterators and message cursors are an [`Object`], locked for the duration of
+//! each call, so calls on one handle from several threads wait for each other.
+//! Cancellations are thread-safe, as they are meant to be cancelled from
+//! another thread.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use std::os::raw::c_void;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use lazy_static::lazy_static;
+use tokio::sync::RwLock;
+
+use crate::bridge::Bridge;
+use crate::cancellation::Cancellation;
+use crate::context::Context;
+use crate::entity_iter::EntityIter;
+use crate::error::{Error, ErrorCode};
+use crate::event_bus::EventBus;
+use crate::message_cursor::MessageCursor;
+use crate::pipeline::PipelinePlatform;
+
+/// Next handle ID, shared by all registries
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// A platform behind a C handle, with its event pipeline
+pub(crate) type PlatformLock = RwLock<PipelinePlatform>;
+
+/// An object behind a C handle, locked for the duration of each call
+pub(crate) struct Object<T> {
+    value: Mutex<T>,
+}
+
+thread_local! {
+    /// Addresses of the objects locked by calls on this thread
+    static HELD: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+}
+
+impl<T> Object<T> {
+    /// Wrap an object for a registry
+    pub fn new(object: T) -> Self {
+        Object {
+            value: Mutex::new(object),
+        }
+    }
+
+    /// Lock the object, waiting for calls on other threads using it
+    ///
+    /// A call from a callback of the same object (e.g. a log callback using
+    /// the context that is logging) can't wait for the call it runs in, so it
+    /// fails with `ErrorCode::InvalidState` instead.
+    pub fn lock(&self) -> Result<ObjectGuard<'_, T>, Error> {
+        let address = self as *const Self as usize;
+        if HELD.with(|held| held.borrow().contains(&address)) {
+            return Err(Error::new(
+                ErrorCode::InvalidState,
+                "Handle is in use by the call running this callback",
+            ));
+        }
+        // A panicking call is caught at the FFI boundary; the object is still
+        // as consistent as that call left it
+        let guard = self.value.lock().unwrap_or_else(PoisonError::into_inner);
+        HELD.with(|held| held.borrow_mut().push(address));
+        Ok(ObjectGuard { guard, address })
+    }
+}
+
+/// A locked object, see [`Object::lock`]
+pub(crate) struct ObjectGuard<'a, T> {
+    guard: MutexGuard<'a, T>,
+    address: usize,
+}
+
+impl<T> Deref for ObjectGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<T> DerefMut for ObjectGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.guard
+    }
+}
+
+impl<T> Drop for ObjectGuard<'_, T> {
+    fn drop(&mut self) {
+        HELD.with(|held| {
+            let mut held = held.borrow_mut();
+            if let Some(index) = held.iter().rposition(|address| *address == self.address) {
+                held.remove(index);
+            }
+        });
+    }
+}
+
+/// Objects of one kind, keyed by handle ID
+pub(crate) struct Registry<T> {
+    objects: Mutex<HashMap<u64, Arc<T>>>,
+}
+
+impl<T> Registry<T> {
+    fn new() -> Self {
+        Self {
+            objects: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Register an object and return its handle
+    pub fn insert(&self, object: T) -> *mut c_void {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        self.objects.lock().unwrap().insert(id, Arc::new(object));
+        id as usize as *mut c_void
+    }
+
+    /// Look up the object of a handle
+    ///
+    /// # Returns
+    /// The object, or None if the handle is not a live handle of this registry
+    pub fn get(&self, handle: *mut c_void) -> Option<Arc<T>> {
+        self.objects
+            .lock()
+            .unwrap()
+            .get(&(handle as usize as u64))
+            .cloned()
+    }
+
+    /// Whether a handle is a live handle of this registry
+    pub fn contains(&self, handle: *mut c_void) -> bool {
+        self.objects
+            .lock()
+            .unwrap()
+            .contains_key(&(handle as usize as u64))
+    }
+
+    /// Unregister the object of a handle
+    ///
+    /// The object is dropped once calls still using it have returned.
+    ///
+    /// # Returns
+    /// Whether the handle was a live handle of this registry
+    pub fn remove(&self, handle: *mut c_void) -> bool {
+        self.objects
+            .lock()
+            .unwrap()
+            .remove(&(handle as usize as u64))
+            .is_some()
+    }
+}
+
+lazy_static! {
+    /// Platforms, from communicator_*_create()
+    pub(crate) static ref PLATFORMS: Registry<PlatformLock> = Registry::new();
+    /// Contexts, from communicator_context_create()
+    pub(crate) static ref CONTEXTS: Registry<Object<Context>> = Registry::new();
+    /// Bridges, from communicator_bridge_create()
+    pub(crate) static ref BRIDGES: Registry<Object<Bridge>> = Registry::new();
+    /// Event buses, from communicator_eventbus_create()
+    pub(crate) static ref EVENT_BUSES: Registry<Object<EventBus>> = Registry::new();
+    /// Iterators over cached entities, from communicator_platform_iter_*()
+    pub(crate) static ref ENTITY_ITERS: Registry<Object<EntityIter>> = Registry::new();
+    /// Cursors over message history, from communicator_platform_open_message_cursor()
+    pub(crate) static ref MESSAGE_CURSORS: Registry<Object<MessageCursor>> = Registry::new();
+    /// Cancellations, from communicator_cancellation_create()
+    pub(crate) static ref CANCELLATIONS: Registry<Cancellation> = Registry::new();
+}
+
+/// The error for a handle that is not live or of the wrong kind
+pub(crate) fn invalid_handle() -> Error {
+    Error::invalid_argument("Invalid handle (destroyed, or of another kind)")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stale_and_foreign_handles() {
+        let contexts: Registry<String> = Registry::new();
+        let bridges: Registry<String> = Registry::new();
+
+        let handle = contexts.insert("first".to_string());
+        let other = bridges.insert("other".to_string());
+        assert_ne!(handle, other);
+        assert_eq!(*contexts.get(handle).unwrap(), "first");
+
+        // A handle of another registry is foreign
+        assert!(contexts.get(other).is_none());
+        assert!(!bridges.contains(handle));
+
+        // An object stays alive while a lookup holds it
+        let object = contexts.get(handle).unwrap();
+        assert!(contexts.remove(handle));
+        assert_eq!(*object, "first");
+
+        // A destroyed handle is stale, and its ID is not reused
+        assert!(contexts.get(handle).is_none());
+        assert!(!contexts.remove(handle));
+        assert_ne!(contexts.insert("second".to_string()), handle);
+    }
+
+    #[test]
+    fn test_object_lock() {
+        let object = Arc::new(Object::new(0u32));
+
+        // A call from a callback of the object fails instead of deadlocking
+        let guard = object.lock().unwrap();
+        let err = object.lock().err().unwrap();
+        assert_eq!(err.code, ErrorCode::InvalidState);
+        drop(guard);
+
+        // Calls from several threads wait for each other
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let object = object.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..1000 {
+                        let mut value = object.lock().unwrap();
+                        let read = *value;
+                        std::thread::yield_now();
+                        *value = read + 1;
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        assert_eq!(*object.lock().unwrap(), 4000);
+    }
+}
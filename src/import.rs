@@ -0,0 +1,454 @@
+//! Importing message history
+//!
+//! Replays a JSONL export into a channel, for migrations between servers and
+//! platforms. Two line formats are understood, and may be mixed:
+//!
+//! - A [`Message`] object per line, as returned by the message APIs. Replies
+//!   are recognized by a `root_id` in the message metadata.
+//! - Mattermost bulk export lines. `post` and `direct_post` lines are
+//!   imported, their `replies` as a thread; other line types (`version`,
+//!   `team`, `channel`, `user`, ...) are skipped.
+//!
+//! Messages are sent by the importing account in batches, pausing between
+//! batches and backing off when the server rate-limits. Platforms rarely allow
+//! posting as another user or in the past, so every message is tagged with
+//! its original author and time in [`IMPORT_ORIGIN_PROP`] and, optionally,
+//! prefixed with them.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, ErrorCode, Result};
+use crate::platforms::Platform;
+use crate::types::{Message, SendOptions};
+
+/// Message property holding the [`ImportOrigin`] of an imported message
+pub const IMPORT_ORIGIN_PROP: &str = "libcommunicator_imported_from";
+
+/// How to import a history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImportOptions {
+    /// Messages sent per batch
+    pub batch_size: usize,
+    /// Pause between batches, in milliseconds
+    pub batch_delay_ms: u64,
+    /// How often to retry a message the server rate-limited; the wait
+    /// doubles on each retry, starting at `batch_delay_ms`
+    pub max_retries: u32,
+    /// Prefix each text with its original author and time
+    pub attribution: bool,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            batch_size: 20,
+            batch_delay_ms: 1000,
+            max_retries: 5,
+            attribution: true,
+        }
+    }
+}
+
+impl ImportOptions {
+    /// Check that the options can be used
+    pub fn validate(&self) -> Result<()> {
+        if self.batch_size == 0 {
+            return Err(Error::invalid_argument("Batch size must be at least 1"));
+        }
+        Ok(())
+    }
+}
+
+/// Where an imported message came from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportOrigin {
+    /// ID of the message in the export, if it had one
+    pub message_id: Option<String>,
+    /// Author in the export (a user ID or a username)
+    pub author: String,
+    /// When the original was created, if known
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// A message read from an export
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportEntry {
+    /// One-based line of the export the message was read from
+    pub line: usize,
+    /// Identifies the message within the export, for replies to refer to
+    pub source_id: String,
+    /// The original message
+    pub origin: ImportOrigin,
+    /// The text
+    pub text: String,
+    /// `source_id` of the thread root, for replies
+    pub root_source_id: Option<String>,
+}
+
+/// A line or message that could not be imported
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportFailure {
+    /// One-based line of the export
+    pub line: usize,
+    /// Why it failed
+    pub error: String,
+}
+
+/// Outcome of an import
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportReport {
+    /// Messages sent
+    pub imported: usize,
+    /// Lines that do not hold messages (e.g. users or teams of a bulk export)
+    pub skipped: usize,
+    /// Lines that could not be read and messages that could not be sent
+    pub failed: Vec<ImportFailure>,
+}
+
+/// Messages read from an export
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParsedExport {
+    /// The messages, in export order, each reply after its root
+    pub entries: Vec<ImportEntry>,
+    /// Lines that do not hold messages
+    pub skipped: usize,
+    /// Lines that could not be read
+    pub failed: Vec<ImportFailure>,
+}
+
+/// A post of a Mattermost bulk export, or one of its replies
+#[derive(Debug, Deserialize)]
+struct BulkPost {
+    user: String,
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    create_at: Option<i64>,
+    #[serde(default)]
+    replies: Vec<BulkPost>,
+}
+
+/// Read the messages of a JSONL export
+///
+/// Blank lines are ignored. Lines that are neither a message nor a bulk
+/// export line are reported as failures; reading continues after them.
+pub fn parse_export(jsonl: &str) -> ParsedExport {
+    let mut parsed = ParsedExport::default();
+
+    for (index, line) in jsonl.lines().enumerate() {
+        let number = index + 1;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let fail = |error: String| ImportFailure {
+            line: number,
+            error,
+        };
+
+        let value: serde_json::Value = match serde_json::from_str(line) {
+            Ok(value) => value,
+            Err(e) => {
+                parsed.failed.push(fail(format!("Invalid JSON: {e}")));
+                continue;
+            }
+        };
+
+        // Bulk export lines name their type; messages have no "type" field
+        if let Some(line_type) = value.get("type").and_then(|t| t.as_str()) {
+            if !matches!(line_type, "post" | "direct_post") {
+                parsed.skipped += 1;
+                continue;
+            }
+            let post = value
+                .get(line_type)
+                .cloned()
+                .map(serde_json::from_value::<BulkPost>);
+            match post {
+                Some(Ok(post)) => push_bulk_post(&mut parsed.entries, number, post),
+                Some(Err(e)) => parsed
+                    .failed
+                    .push(fail(format!("Invalid {line_type}: {e}"))),
+                None => parsed
+                    .failed
+                    .push(fail(format!("Missing \"{line_type}\" object"))),
+            }
+            continue;
+        }
+
+        match serde_json::from_value::<Message>(value) {
+            Ok(message) => parsed.entries.push(entry_from_message(number, message)),
+            Err(e) => parsed.failed.push(fail(format!("Invalid message: {e}"))),
+        }
+    }
+
+    parsed
+}
+
+/// Add a bulk export post and its replies
+fn push_bulk_post(entries: &mut Vec<ImportEntry>, line: usize, post: BulkPost) {
+    // Bulk exports carry no post IDs; the line identifies the thread
+    let root_source_id = format!("line-{line}");
+    let entry = |post: &BulkPost, source_id: String, root: Option<&String>| ImportEntry {
+        line,
+        source_id,
+        origin: ImportOrigin {
+            message_id: None,
+            author: post.user.clone(),
+            created_at: post
+                .create_at
+                .and_then(chrono::DateTime::from_timestamp_millis),
+        },
+        text: post.message.clone(),
+        root_source_id: root.cloned(),
+    };
+
+    entries.push(entry(&post, root_source_id.clone(), None));
+    for (index, reply) in post.replies.iter().enumerate() {
+        let source_id = format!("{root_source_id}-{index}");
+        entries.push(entry(reply, source_id, Some(&root_source_id)));
+    }
+}
+
+/// An import entry for a message exported by this library
+fn entry_from_message(line: usize, message: Message) -> ImportEntry {
+    let root_source_id = message
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.get("root_id"))
+        .and_then(|root_id| root_id.as_str())
+        .filter(|root_id| !root_id.is_empty())
+        .map(str::to_string);
+    ImportEntry {
+        line,
+        source_id: message.id.clone(),
+        origin: ImportOrigin {
+            message_id: Some(message.id),
+            author: message.sender_id,
+            created_at: Some(message.created_at),
+        },
+        text: message.text,
+        root_source_id,
+    }
+}
+
+/// Import a JSONL export into a channel
+///
+/// # Arguments
+/// * `platform` - The account to send the messages with
+/// * `channel_id` - The channel to import into
+/// * `jsonl` - The export, one JSON object per line
+/// * `options` - Batching, pacing and attribution
+///
+/// # Returns
+/// The report, or an error if the options are invalid. Messages that cannot
+/// be sent are reported and skipped; replies to them are sent as top-level
+/// messages.
+pub async fn import_history(
+    platform: &dyn Platform,
+    channel_id: &str,
+    jsonl: &str,
+    options: &ImportOptions,
+) -> Result<ImportReport> {
+    options.validate()?;
+
+    let parsed = parse_export(jsonl);
+    let mut report = ImportReport {
+        imported: 0,
+        skipped: parsed.skipped,
+        failed: parsed.failed,
+    };
+
+    // Export IDs of the messages sent so far -> IDs of their copies
+    let mut sent_ids: HashMap<String, String> = HashMap::new();
+    let delay = Duration::from_millis(options.batch_delay_ms);
+
+    for (batch_index, batch) in parsed.entries.chunks(options.batch_size).enumerate() {
+        if batch_index > 0 {
+            tokio::time::sleep(delay).await;
+        }
+        for entry in batch {
+            let root_id = entry
+                .root_source_id
+                .as_ref()
+                .and_then(|root| sent_ids.get(root))
+                .cloned();
+            match send_entry(platform, channel_id, entry, root_id, options).await {
+                Ok(message) => {
+                    sent_ids.insert(entry.source_id.clone(), message.id);
+                    report.imported += 1;
+                }
+                Err(e) => report.failed.push(ImportFailure {
+                    line: entry.line,
+                    error: e.message,
+                }),
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// Send an entry, retrying while the server rate-limits
+async fn send_entry(
+    platform: &dyn Platform,
+    channel_id: &str,
+    entry: &ImportEntry,
+    root_id: Option<String>,
+    options: &ImportOptions,
+) -> Result<Message> {
+    let text = if options.attribution {
+        attributed_text(entry)
+    } else {
+        entry.text.clone()
+    };
+    let mut send_options = SendOptions {
+        root_id,
+        ..SendOptions::default()
+    };
+    send_options.props.insert(
+        IMPORT_ORIGIN_PROP.to_string(),
+        serde_json::to_value(&entry.origin).unwrap_or_default(),
+    );
+
+    let mut wait = Duration::from_millis(options.batch_delay_ms.max(1));
+    let mut retries = 0;
+    loop {
+        let result = match platform
+            .send_message_ex(channel_id, &text, &send_options)
+            .await
+        {
+            // Platforms without message properties still get the text
+            Err(e) if e.code == ErrorCode::Unsupported && !send_options.props.is_empty() => {
+                send_options.props.clear();
+                continue;
+            }
+            result => result,
+        };
+        match result {
+            Err(e) if e.code == ErrorCode::RateLimited && retries < options.max_retries => {
+                tokio::time::sleep(wait).await;
+                wait *= 2;
+                retries += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// The text of an entry, prefixed with its original author and time
+fn attributed_text(entry: &ImportEntry) -> String {
+    match entry.origin.created_at {
+        Some(created_at) => format!(
+            "**{}** ({}):\n{}",
+            entry.origin.author,
+            created_at.format("%Y-%m-%d %H:%M UTC"),
+            entry.text
+        ),
+        None => format!("**{}**:\n{}", entry.origin.author, entry.text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bulk_export() {
+        let export = r#"{"type":"version","version":1}
+{"type":"user","user":{"username":"alice"}}
+{"type":"post","post":{"team":"t","channel":"town-square","user":"alice","message":"hello","create_at":1700000000000,"replies":[{"user":"bob","message":"hi","create_at":1700000001000}]}}
+
+{"type":"direct_post","direct_post":{"channel_members":["alice","bob"],"user":"bob","message":"psst"}}
+not json
+{"type":"post"}"#;
+
+        let parsed = parse_export(export);
+        assert_eq!(parsed.skipped, 2);
+        let lines: Vec<usize> = parsed.failed.iter().map(|f| f.line).collect();
+        assert_eq!(lines, vec![6, 7]);
+
+        let texts: Vec<&str> = parsed.entries.iter().map(|e| e.text.as_str()).collect();
+        assert_eq!(texts, vec!["hello", "hi", "psst"]);
+        assert_eq!(parsed.entries[1].origin.author, "bob");
+        assert_eq!(
+            parsed.entries[1].root_source_id.as_deref(),
+            Some(parsed.entries[0].source_id.as_str())
+        );
+        assert_eq!(parsed.entries[2].origin.created_at, None);
+    }
+
+    #[test]
+    fn test_parse_message_export() {
+        let root = Message::new("m1", "question", "u1", "c1");
+        let reply = Message::new("m2", "answer", "u2", "c1")
+            .with_metadata(serde_json::json!({ "root_id": "m1" }));
+        let export = [root, reply]
+            .iter()
+            .map(|m| serde_json::to_string(m).unwrap())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let parsed = parse_export(&export);
+        assert!(parsed.failed.is_empty());
+        assert_eq!(parsed.entries[0].root_source_id, None);
+        assert_eq!(parsed.entries[1].root_source_id.as_deref(), Some("m1"));
+        assert_eq!(parsed.entries[1].origin.message_id.as_deref(), Some("m2"));
+
+        let text = attributed_text(&parsed.entries[1]);
+        assert!(text.starts_with("**u2** ("));
+        assert!(text.ends_with("):\nanswer"));
+    }
+
+    #[test]
+    fn test_options() {
+        let options: ImportOptions = serde_json::from_str(r#"{"batch_size": 5}"#).unwrap();
+        assert_eq!(options.batch_size, 5);
+        assert!(options.attribution);
+        assert!(options.validate().is_ok());
+
+        let options = ImportOptions {
+            batch_size: 0,
+            ..ImportOptions::default()
+        };
+        assert_eq!(
+            options.validate().unwrap_err().code,
+            ErrorCode::InvalidArgument
+        );
+    }
+
+    #[cfg(feature = "simulation")]
+    #[tokio::test]
+    async fn test_import_history() {
+        let server = crate::simulation::add_server("import.sim");
+        server.set_route(
+            "POST",
+            "/posts",
+            201,
+            r#"{"id":"new1","create_at":1,"update_at":1,"delete_at":0,"edit_at":0,
+                "user_id":"me","channel_id":"c1","message":"imported"}"#,
+        );
+
+        let platform =
+            crate::platforms::mattermost::MattermostPlatform::new("http://import.sim").unwrap();
+        let export = r#"{"type":"post","post":{"user":"alice","message":"one","replies":[{"user":"bob","message":"two"}]}}
+{"type":"post","post":{"user":"alice","message":"three"}}"#;
+        let options = ImportOptions {
+            batch_size: 2,
+            batch_delay_ms: 0,
+            ..ImportOptions::default()
+        };
+        let report = import_history(&platform, "c1", export, &options)
+            .await
+            .unwrap();
+        assert_eq!(report.imported, 3);
+        assert!(report.failed.is_empty());
+
+        crate::simulation::remove_server("import.sim");
+    }
+}
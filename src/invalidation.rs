@@ -0,0 +1,172 @@
+//! Coarse invalidation hints
+//!
+//! Minimal clients that redraw whole views instead of applying each change
+//! can ask for three hints next to the granular events: `channel_list_changed`,
+//! `unreads_changed` and `profile_cache_changed`. A hint is raised by the first
+//! event affecting its view and delivered once the coalescing interval has
+//! passed, however many more such events arrive meanwhile. Due hints are
+//! delivered before platform events, like due reminders.
+//!
+//! Status changes don't raise `profile_cache_changed`: they are frequent and
+//! already one event per user.
+
+use std::time::{Duration, Instant};
+
+use crate::platforms::PlatformEvent;
+
+/// Preference categories that change which channels the sidebar shows
+const CHANNEL_LIST_PREFERENCES: &[&str] = &[
+    "direct_channel_show",
+    "group_channel_show",
+    "favorite_channel",
+    "sidebar_settings",
+];
+
+/// A view to redraw
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Invalidation {
+    ChannelList,
+    Unreads,
+    ProfileCache,
+}
+
+impl Invalidation {
+    /// The hint event
+    pub fn event(self) -> PlatformEvent {
+        match self {
+            Self::ChannelList => PlatformEvent::ChannelListChanged,
+            Self::Unreads => PlatformEvent::UnreadsChanged,
+            Self::ProfileCache => PlatformEvent::ProfileCacheChanged,
+        }
+    }
+}
+
+/// Views an event affects
+pub fn invalidations(event: &PlatformEvent) -> &'static [Invalidation] {
+    use Invalidation::*;
+    use PlatformEvent as E;
+
+    match event {
+        E::ChannelCreated(_)
+        | E::ChannelDeleted { .. }
+        | E::ChannelConverted { .. }
+        | E::DirectChannelAdded { .. }
+        | E::GroupChannelAdded { .. }
+        | E::UserJoinedChannel { .. }
+        | E::UserLeftChannel { .. }
+        | E::AddedToTeam { .. }
+        | E::LeftTeam { .. }
+        | E::TeamDeleted { .. }
+        | E::TeamUpdated { .. } => &[ChannelList],
+        E::ChannelUpdated(_) => &[ChannelList, Unreads],
+        E::PreferenceChanged { category, .. } | E::PreferencesDeleted { category, .. }
+            if CHANNEL_LIST_PREFERENCES.contains(&category.as_str()) =>
+        {
+            &[ChannelList]
+        }
+        E::MessagePosted(_)
+        | E::MessageSendSucceeded { .. }
+        | E::MessageDeleted { .. }
+        | E::ChannelViewed { .. }
+        | E::PostUnread { .. }
+        | E::ThreadUpdated { .. }
+        | E::ThreadReadChanged { .. } => &[Unreads],
+        E::UserAdded { .. } | E::UserUpdated { .. } | E::UserRoleUpdated { .. } => &[ProfileCache],
+        // Live data replaces the data the session started from
+        E::SessionReady(_) => &[ChannelList, Unreads, ProfileCache],
+        _ => &[],
+    }
+}
+
+/// Coalesces the invalidations of one platform
+#[derive(Debug)]
+pub struct Coalescer {
+    interval: Duration,
+    /// Raised invalidations and when they were raised, oldest first
+    pending: Vec<(Invalidation, Instant)>,
+}
+
+impl Coalescer {
+    /// Create a coalescer
+    ///
+    /// # Arguments
+    /// * `interval` - How long after it is raised a hint is delivered
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Raise the invalidations of an event not raised yet
+    pub fn note(&mut self, event: &PlatformEvent, now: Instant) {
+        for &invalidation in invalidations(event) {
+            if !self
+                .pending
+                .iter()
+                .any(|(pending, _)| *pending == invalidation)
+            {
+                self.pending.push((invalidation, now));
+            }
+        }
+    }
+
+    /// Take the oldest invalidation raised at least the interval ago
+    pub fn pop_due(&mut self, now: Instant) -> Option<Invalidation> {
+        let (invalidation, raised_at) = *self.pending.first()?;
+        if now.duration_since(raised_at) < self.interval {
+            return None;
+        }
+        self.pending.remove(0);
+        Some(invalidation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Message;
+
+    #[test]
+    fn test_coalesces_until_interval() {
+        let start = Instant::now();
+        let interval = Duration::from_millis(100);
+        let mut coalescer = Coalescer::new(interval);
+        let posted = PlatformEvent::MessagePosted(Message::new("m1", "hi", "u1", "c1"));
+
+        coalescer.note(&posted, start);
+        coalescer.note(
+            &PlatformEvent::UserUpdated {
+                user_id: "u1".into(),
+            },
+            start,
+        );
+        coalescer.note(&posted, start + interval / 2);
+        coalescer.note(&PlatformEvent::ConfigChanged, start);
+        assert_eq!(coalescer.pop_due(start + interval / 2), None);
+
+        assert_eq!(
+            coalescer.pop_due(start + interval),
+            Some(Invalidation::Unreads)
+        );
+        assert_eq!(
+            coalescer.pop_due(start + interval),
+            Some(Invalidation::ProfileCache)
+        );
+        assert_eq!(coalescer.pop_due(start + interval * 10), None);
+    }
+
+    #[test]
+    fn test_channel_list_preferences() {
+        let preference = |category: &str| PlatformEvent::PreferenceChanged {
+            category: category.to_string(),
+            name: "c1".to_string(),
+            value: "true".to_string(),
+        };
+        assert_eq!(
+            invalidations(&preference("favorite_channel")),
+            &[Invalidation::ChannelList]
+        );
+        assert!(invalidations(&preference("display_settings")).is_empty());
+    }
+}
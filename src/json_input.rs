@@ -0,0 +1,233 @@
+//! Parsing JSON passed in by C callers
+//!
+//! C callers often assemble JSON by hand, so a bare "invalid type" message is
+//! hard to act on. Parse errors from this module point at the failing value:
+//! they carry a JSON pointer (RFC 6901), the line and column, and the name of
+//! the schema the input was parsed as. The details are attached to the error
+//! and exposed through `communicator_last_error_details()`.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::error::{Error, ErrorCode, Result};
+
+/// Where JSON input failed to parse
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JsonErrorLocation {
+    /// JSON pointer to the failing value ("" for the whole document)
+    pub pointer: String,
+    /// One-based line
+    pub line: usize,
+    /// One-based column (bytes); 0 if the error is at the start of the line
+    pub column: usize,
+    /// Name of the schema the input was parsed as
+    pub schema: String,
+}
+
+/// Parse JSON from a C caller
+///
+/// # Arguments
+/// * `json` - The JSON text
+/// * `what` - What the JSON describes, for the error message (e.g. "config")
+/// * `schema` - The schema name, as documented in the C header
+///
+/// # Returns
+/// The parsed value, or an InvalidArgument error carrying a [`JsonErrorLocation`]
+pub fn parse<T: DeserializeOwned>(json: &str, what: &str, schema: &str) -> Result<T> {
+    serde_json::from_str(json).map_err(|e| parse_error(json, what, schema, &e))
+}
+
+fn parse_error(json: &str, what: &str, schema: &str, error: &serde_json::Error) -> Error {
+    let (line, column) = (error.line(), error.column());
+    let pointer = pointer_at(json, byte_offset(json, line, column));
+
+    // serde_json appends the position to its message; it is reported separately
+    let message = error.to_string();
+    let suffix = format!(" at line {line} column {column}");
+    let reason = message.strip_suffix(&suffix).unwrap_or(&message);
+
+    let at = if pointer.is_empty() {
+        "at the document root".to_string()
+    } else {
+        format!("at {pointer}")
+    };
+    Error::new(
+        ErrorCode::InvalidArgument,
+        format!(
+            "Invalid {what} JSON {at} (line {line}, column {column}): {reason} (schema: {schema})"
+        ),
+    )
+    .with_json_location(JsonErrorLocation {
+        pointer,
+        line,
+        column,
+        schema: schema.to_string(),
+    })
+}
+
+/// Byte offset of the character serde_json stopped at
+///
+/// serde_json reports the one-based column of the last byte it read, so the
+/// offset is that of the byte before the column.
+fn byte_offset(json: &str, line: usize, column: usize) -> usize {
+    let line_start: usize = json
+        .split_inclusive('\n')
+        .take(line.saturating_sub(1))
+        .map(str::len)
+        .sum();
+    (line_start + column.saturating_sub(1)).min(json.len())
+}
+
+/// A container being scanned
+enum Frame {
+    /// An object, with the key of the current member once its ':' was read
+    Object(Option<String>),
+    /// An array, with the index of the current element
+    Array(usize),
+}
+
+/// JSON pointer to the value containing a byte offset
+///
+/// Scans the JSON up to (excluding) the offset, tracking the containers and
+/// the member or element being read. An offset at the end of a container
+/// (e.g. a missing field) points at the container. Works on malformed input
+/// too, as long as it is well-formed up to the offset.
+fn pointer_at(json: &str, offset: usize) -> String {
+    let bytes = &json.as_bytes()[..offset.min(json.len())];
+    let mut frames: Vec<Frame> = Vec::new();
+    // The last string read, which becomes the key if a ':' follows
+    let mut last_string: Option<String> = None;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => {
+                let start = i + 1;
+                i = start;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                if i >= bytes.len() {
+                    // The offset is inside this string
+                    break;
+                }
+                let raw = &json[start..i];
+                last_string = Some(
+                    serde_json::from_str(&json[start - 1..=i]).unwrap_or_else(|_| raw.to_string()),
+                );
+            }
+            b'{' => frames.push(Frame::Object(None)),
+            b'[' => frames.push(Frame::Array(0)),
+            b'}' | b']' => {
+                frames.pop();
+            }
+            b':' => {
+                if let Some(Frame::Object(key)) = frames.last_mut() {
+                    *key = last_string.take();
+                }
+            }
+            b',' => match frames.last_mut() {
+                Some(Frame::Object(key)) => *key = None,
+                Some(Frame::Array(index)) => *index += 1,
+                None => {}
+            },
+            _ => {}
+        }
+        i += 1;
+    }
+
+    if matches!(json.as_bytes().get(offset), Some(b'}' | b']')) {
+        frames.pop();
+        frames.push(Frame::Object(None));
+    }
+
+    let mut pointer = String::new();
+    for frame in &frames {
+        match frame {
+            Frame::Object(Some(key)) => {
+                pointer.push('/');
+                pointer.push_str(&key.replace('~', "~0").replace('/', "~1"));
+            }
+            // Between members, or at the end: the container is the location
+            Frame::Object(None) => break,
+            Frame::Array(index) => {
+                pointer.push('/');
+                pointer.push_str(&index.to_string());
+            }
+        }
+    }
+    pointer
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Config {
+        server: String,
+        ports: Vec<u16>,
+        #[serde(default)]
+        options: Option<Options>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Options {
+        retries: u8,
+    }
+
+    fn location(json: &str) -> JsonErrorLocation {
+        let error = parse::<Config>(json, "config", "Config").unwrap_err();
+        assert_eq!(error.code, ErrorCode::InvalidArgument);
+        error.json_location().cloned().unwrap()
+    }
+
+    #[test]
+    fn test_error_locations() {
+        let loc = location(r#"{"server": 5, "ports": []}"#);
+        assert_eq!(loc.pointer, "/server");
+        assert_eq!((loc.line, loc.column), (1, 12));
+        assert_eq!(loc.schema, "Config");
+
+        assert_eq!(
+            location(r#"{"server": "x", "ports": [1, "a"]}"#).pointer,
+            "/ports/1"
+        );
+        assert_eq!(
+            location(r#"{"server": "x", "ports": [], "options": {"retries": 300}}"#).pointer,
+            "/options/retries"
+        );
+
+        // Missing fields point at the object that lacks them
+        assert_eq!(location(r#"{"server": "x"}"#).pointer, "");
+        assert_eq!(
+            location(r#"{"server": "x", "ports": [], "options": {}}"#).pointer,
+            "/options"
+        );
+
+        // Syntax errors on later lines
+        let loc = location("{\n  \"server\": \"x\",\n  \"ports\": [1,, 2]\n}");
+        assert_eq!(loc.pointer, "/ports/1");
+        assert_eq!(loc.line, 3);
+    }
+
+    #[test]
+    fn test_error_message() {
+        let error = parse::<Vec<String>>(r#"["a", 1]"#, "user IDs", "string array").unwrap_err();
+        assert_eq!(
+            error.message,
+            "Invalid user IDs JSON at /1 (line 1, column 7): invalid type: integer `1`, \
+             expected a string (schema: string array)"
+        );
+    }
+
+    #[test]
+    fn test_pointer_escaping() {
+        let json = r#"{"a/b~c": {"k\"ey": [true]}}"#;
+        let offset = json.find("true").unwrap();
+        assert_eq!(pointer_at(json, offset), "/a~1b~0c/k\"ey/0");
+    }
+}
@@ -0,0 +1,419 @@
+//! Language detection for incoming messages
+//!
+//! A lightweight detector that needs no models: non-Latin scripts are told
+//! apart by their Unicode ranges (and a few letters specific to a language),
+//! Latin-script text by counting common words and letters of each language.
+//! It is meant for hints such as routing, translation or notification rules,
+//! not for linguistic accuracy, and gives up on short or ambiguous text.
+//!
+//! Detection is enabled per platform (see [`LanguageSettings`]), for all
+//! channels or for single channels. Detected languages are set as `Message::language` (an ISO 639-1
+//! code) on message_posted and message_updated events.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::platforms::PlatformEvent;
+use crate::types::Message;
+
+/// Fewest letters of a non-Latin script needed for a detection
+const MIN_SCRIPT_LETTERS: usize = 4;
+
+/// Fewest words needed to detect a Latin-script language
+const MIN_LATIN_WORDS: usize = 3;
+
+/// A detected language
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Detection {
+    /// ISO 639-1 code, e.g. "en"
+    pub language: &'static str,
+    /// Between 0 and 1
+    pub confidence: f32,
+}
+
+/// Common words and characteristic letters of a Latin-script language
+struct LatinProfile {
+    language: &'static str,
+    /// Space-separated
+    words: &'static str,
+    letters: &'static str,
+}
+
+const LATIN_PROFILES: &[LatinProfile] = &[
+    LatinProfile {
+        language: "en",
+        words: concat!(
+            "the and is are was to of in that it for you with on this have be not but what ",
+            "will can we they there just i'm it's don't my"
+        ),
+        letters: "",
+    },
+    LatinProfile {
+        language: "de",
+        words: concat!(
+            "der die das und ist nicht ich du wir sie ein eine mit auf für auch noch ja ",
+            "nein aber wie zu den dem des sind hast habe bitte schon"
+        ),
+        letters: "äöüß",
+    },
+    LatinProfile {
+        language: "fr",
+        words: concat!(
+            "le la les et est je tu nous vous il elle un une des du pas que qui pour avec ",
+            "sur dans mais c'est ce oui non merci très ça"
+        ),
+        letters: "éèêàçùœ",
+    },
+    LatinProfile {
+        language: "es",
+        words: concat!(
+            "el la los las y es yo tú usted nosotros un una de del que no en por para con ",
+            "pero como está muy sí gracias hola qué también hay"
+        ),
+        letters: "ñ¿¡áíóú",
+    },
+    LatinProfile {
+        language: "it",
+        words: concat!(
+            "il lo la gli le e è io tu noi voi un una di che non per con ma come sono ",
+            "questo anche grazie ciao molto perché della del nel"
+        ),
+        letters: "àèìòù",
+    },
+    LatinProfile {
+        language: "pt",
+        words: concat!(
+            "o a os as e é eu você nós um uma de do da que não em para com mas como está ",
+            "muito obrigado obrigada sim também isso no na"
+        ),
+        letters: "ãõçâê",
+    },
+    LatinProfile {
+        language: "nl",
+        words: concat!(
+            "de het een en is ik je jij wij we niet dat van op met voor maar ook nog wel ",
+            "zijn heb hebben dank bedankt graag naar dit er wat"
+        ),
+        letters: "",
+    },
+    LatinProfile {
+        language: "sv",
+        words: concat!(
+            "och är jag du vi det den en ett inte att som på med för men också har var tack ",
+            "hej kan ska till av om så nu här vad"
+        ),
+        letters: "åäö",
+    },
+    LatinProfile {
+        language: "pl",
+        words: concat!(
+            "i jest nie to ja ty my się na w z że do jak ale czy tak dziękuję cześć co ",
+            "jestem bardzo już tylko tego mam być dla od po"
+        ),
+        letters: "ąęłśżźćń",
+    },
+    LatinProfile {
+        language: "tr",
+        words: concat!(
+            "ve bir bu ben sen biz değil için ile ama çok evet hayır teşekkürler merhaba ne ",
+            "var yok da de gibi daha mi mı olarak şey nasıl şimdi sonra her"
+        ),
+        letters: "ğış",
+    },
+];
+
+/// Remove parts of a message that are not prose: code, links, mentions and
+/// emoji shortcodes
+fn prose(text: &str) -> String {
+    let mut out = String::new();
+    for (i, block) in text.split("```").enumerate() {
+        // Odd blocks are inside code fences
+        if i % 2 == 1 {
+            continue;
+        }
+        for (j, span) in block.split('`').enumerate() {
+            if j % 2 == 1 {
+                continue;
+            }
+            for word in span.split_whitespace() {
+                let is_markup = word.contains("://")
+                    || word.starts_with('@')
+                    || word.starts_with('#')
+                    || (word.len() > 2 && word.starts_with(':') && word.ends_with(':'));
+                if !is_markup {
+                    out.push_str(word);
+                    out.push(' ');
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Letter counts of the scripts detection tells apart
+#[derive(Debug, Default)]
+struct Scripts {
+    latin: usize,
+    han: usize,
+    kana: usize,
+    hangul: usize,
+    cyrillic: usize,
+    arabic: usize,
+    hebrew: usize,
+    greek: usize,
+    thai: usize,
+    devanagari: usize,
+}
+
+impl Scripts {
+    fn count(text: &str) -> Self {
+        let mut scripts = Scripts::default();
+        for c in text.chars() {
+            match c as u32 {
+                0x41..=0x5A | 0x61..=0x7A | 0xC0..=0x24F => scripts.latin += 1,
+                0x3040..=0x30FF => scripts.kana += 1,
+                0x4E00..=0x9FFF | 0x3400..=0x4DBF => scripts.han += 1,
+                0xAC00..=0xD7AF | 0x1100..=0x11FF => scripts.hangul += 1,
+                0x400..=0x4FF => scripts.cyrillic += 1,
+                0x600..=0x6FF => scripts.arabic += 1,
+                0x590..=0x5FF => scripts.hebrew += 1,
+                0x370..=0x3FF => scripts.greek += 1,
+                0xE00..=0xE7F => scripts.thai += 1,
+                0x900..=0x97F => scripts.devanagari += 1,
+                _ => {}
+            }
+        }
+        scripts
+    }
+}
+
+/// Detect the language of a text
+///
+/// # Returns
+/// The language, or None if the text is too short or ambiguous
+pub fn detect(text: &str) -> Option<Detection> {
+    let text = prose(text).to_lowercase();
+    let scripts = Scripts::count(&text);
+
+    let cjk = scripts.han + scripts.kana;
+    let candidates = [
+        (scripts.latin, ""),
+        (cjk, "cjk"),
+        (scripts.hangul, "ko"),
+        (scripts.cyrillic, "cyrillic"),
+        (scripts.arabic, "arabic"),
+        (scripts.hebrew, "he"),
+        (scripts.greek, "el"),
+        (scripts.thai, "th"),
+        (scripts.devanagari, "hi"),
+    ];
+    let total: usize = candidates.iter().map(|(count, _)| count).sum();
+    let &(count, script) = candidates.iter().max_by_key(|(count, _)| *count)?;
+    if count == 0 {
+        return None;
+    }
+    if script.is_empty() {
+        return detect_latin(&text);
+    }
+    if count < MIN_SCRIPT_LETTERS {
+        return None;
+    }
+
+    let language = match script {
+        // Japanese mixes kanji with kana; Chinese has no kana
+        "cjk" if scripts.kana * 10 >= cjk => "ja",
+        "cjk" => "zh",
+        "cyrillic" if text.contains(['і', 'ї', 'є', 'ґ']) => "uk",
+        "cyrillic" => "ru",
+        "arabic" if text.contains(['پ', 'چ', 'ژ', 'گ', 'ی']) => "fa",
+        "arabic" => "ar",
+        other => other,
+    };
+    Some(Detection {
+        language,
+        confidence: count as f32 / total as f32,
+    })
+}
+
+fn detect_latin(text: &str) -> Option<Detection> {
+    let words: Vec<&str> = text
+        .split(|c: char| !(c.is_alphabetic() || c == '\''))
+        .filter(|word| !word.is_empty())
+        .collect();
+    if words.len() < MIN_LATIN_WORDS {
+        return None;
+    }
+
+    let mut scores: Vec<(usize, &'static str)> = LATIN_PROFILES
+        .iter()
+        .map(|profile| {
+            let word_hits = words
+                .iter()
+                .filter(|word| profile.words.split(' ').any(|w| w == **word))
+                .count();
+            let letter_hits = text
+                .chars()
+                .filter(|c| profile.letters.contains(*c))
+                .count();
+            (word_hits * 2 + letter_hits, profile.language)
+        })
+        .collect();
+    scores.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+
+    let (best, language) = scores[0];
+    let second = scores[1].0;
+    if best < 2 || best == second {
+        return None;
+    }
+    let margin = (best - second) as f32 / best as f32;
+    let coverage = (best as f32 / (words.len() * 2) as f32).min(1.0);
+    Some(Detection {
+        language,
+        confidence: ((margin + coverage) / 2.0).min(1.0),
+    })
+}
+
+/// Channels language detection is enabled for
+#[derive(Debug, Default)]
+pub struct LanguageSettings {
+    /// Whether channels without an override are detected
+    all_channels: bool,
+    /// Per-channel overrides
+    channels: HashMap<String, bool>,
+}
+
+impl LanguageSettings {
+    /// Create settings with detection disabled everywhere
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable or disable detection
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel to configure, or None for channels without their own setting
+    /// * `enabled` - Whether to detect languages
+    pub fn set(&mut self, channel_id: Option<&str>, enabled: bool) {
+        match channel_id {
+            Some(channel_id) => {
+                self.channels.insert(channel_id.to_string(), enabled);
+            }
+            None => self.all_channels = enabled,
+        }
+        if self.is_off() {
+            *self = Self::default();
+        }
+    }
+
+    /// Whether detection is enabled for a channel
+    pub fn enabled(&self, channel_id: &str) -> bool {
+        self.channels
+            .get(channel_id)
+            .copied()
+            .unwrap_or(self.all_channels)
+    }
+
+    /// Whether detection is disabled for every channel
+    pub fn is_off(&self) -> bool {
+        !self.all_channels && !self.channels.values().any(|&enabled| enabled)
+    }
+
+    /// Set the language of the message of an event, if enabled for its channel
+    pub fn annotate(&self, event: &mut PlatformEvent) {
+        if let PlatformEvent::MessagePosted(message) | PlatformEvent::MessageUpdated(message) =
+            event
+        {
+            self.annotate_message(message);
+        }
+    }
+
+    fn annotate_message(&self, message: &mut Message) {
+        if message.language.is_none() && self.enabled(&message.channel_id) {
+            message.language = detect(&message.text).map(|d| d.language.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn language(text: &str) -> Option<&'static str> {
+        detect(text).map(|d| d.language)
+    }
+
+    #[test]
+    fn test_latin_languages() {
+        assert_eq!(
+            language("I think the build is broken, can you have a look?"),
+            Some("en")
+        );
+        assert_eq!(
+            language("Ich habe das noch nicht gesehen, aber es ist schön"),
+            Some("de")
+        );
+        assert_eq!(
+            language("Je ne sais pas, c'est très bizarre pour moi"),
+            Some("fr")
+        );
+        assert_eq!(
+            language("Hola, gracias por la ayuda con el despliegue"),
+            Some("es")
+        );
+        assert_eq!(
+            language("Dziękuję, to jest bardzo dobre rozwiązanie"),
+            Some("pl")
+        );
+        // Too short to tell
+        assert_eq!(language("ok thx"), None);
+    }
+
+    #[test]
+    fn test_scripts() {
+        assert_eq!(language("今日はいい天気ですね"), Some("ja"));
+        assert_eq!(language("今天天气很好"), Some("zh"));
+        assert_eq!(language("오늘 날씨가 좋네요"), Some("ko"));
+        assert_eq!(language("Привет, как дела?"), Some("ru"));
+        assert_eq!(language("Привіт, як справи? Все добре"), Some("uk"));
+        assert_eq!(language("مرحبا كيف حالك"), Some("ar"));
+    }
+
+    #[test]
+    fn test_markup_is_ignored() {
+        let text = "@alice the deploy is done, see https://example.com/le/la/les\n\
+                    ```\nlet la = le + les;\n```";
+        assert_eq!(language(text), Some("en"));
+    }
+
+    #[test]
+    fn test_per_channel_settings() {
+        let mut settings = LanguageSettings::new();
+        let mut event = PlatformEvent::MessagePosted(Message::new(
+            "m1",
+            "Where is the meeting today, and who is coming?",
+            "u1",
+            "ch-1",
+        ));
+        let language_of = |event: &PlatformEvent| match event {
+            PlatformEvent::MessagePosted(message) => message.language.clone(),
+            _ => None,
+        };
+
+        settings.annotate(&mut event);
+        assert_eq!(language_of(&event), None);
+
+        settings.set(None, true);
+        settings.set(Some("ch-1"), false);
+        settings.annotate(&mut event);
+        assert_eq!(language_of(&event), None);
+
+        settings.set(Some("ch-1"), true);
+        settings.annotate(&mut event);
+        assert_eq!(language_of(&event).as_deref(), Some("en"));
+
+        settings.set(None, false);
+        settings.set(Some("ch-1"), false);
+        assert!(settings.is_off());
+    }
+}
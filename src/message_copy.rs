@@ -0,0 +1,295 @@
+//! Copying and moving messages between accounts
+//!
+//! Copies a message from one platform handle (account) to a channel of
+//! another, for migration and triage tooling. Attachments are downloaded and
+//! uploaded again, and snippet and quote metadata are carried over. The copy
+//! is tagged with [`COPY_ORIGIN_PROP`] so it can be traced back to the original.
+//!
+//! Parts of a copy can fail independently, so the result reports each item
+//! (the text, every attachment, deleting the original) instead of failing
+//! the whole operation.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ErrorCode, Result};
+use crate::platforms::Platform;
+use crate::types::quote::QUOTE_PROP;
+use crate::types::snippet::SNIPPET_PROP;
+use crate::types::Message;
+
+/// Message property holding the [`CopyOrigin`] of a copied message
+pub const COPY_ORIGIN_PROP: &str = "libcommunicator_copied_from";
+
+/// Message properties carried over to copies
+///
+/// Chunk and bridge properties describe how the original was delivered and
+/// would confuse reassembly and loop detection if copied.
+const CARRIED_PROPS: &[&str] = &[SNIPPET_PROP, QUOTE_PROP];
+
+/// Where a copied message came from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CopyOrigin {
+    /// Name of the platform of the original
+    pub platform: String,
+    /// Channel of the original
+    pub channel_id: String,
+    /// ID of the original
+    pub message_id: String,
+    /// User ID of the original's sender
+    pub sender_id: String,
+    /// When the original was created
+    pub created_at: DateTime<Utc>,
+}
+
+impl CopyOrigin {
+    /// Read the copy origin of a received message, if it is a copy
+    pub fn from_message(message: &Message) -> Option<Self> {
+        let value = message
+            .metadata
+            .as_ref()?
+            .get("props")?
+            .get(COPY_ORIGIN_PROP)?;
+        serde_json::from_value(value.clone()).ok()
+    }
+}
+
+/// A part of a message copy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CopyItemKind {
+    /// The message itself, with its text
+    Text,
+    /// An attachment
+    Attachment,
+    /// Deleting the original, when moving
+    DeleteSource,
+}
+
+/// Outcome of copying a part of a message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CopyItem {
+    /// Which part this is
+    pub kind: CopyItemKind,
+    /// File name, for attachments
+    pub name: Option<String>,
+    /// Whether this part was copied (or the original deleted)
+    pub success: bool,
+    /// Why it failed
+    pub error: Option<String>,
+}
+
+impl CopyItem {
+    fn new(
+        kind: CopyItemKind,
+        name: Option<&str>,
+        outcome: std::result::Result<(), String>,
+    ) -> Self {
+        Self {
+            kind,
+            name: name.map(|n| n.to_string()),
+            success: outcome.is_ok(),
+            error: outcome.err(),
+        }
+    }
+}
+
+/// Outcome of copying a message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CopyReport {
+    /// The copy, if the message could be sent
+    pub message: Option<Message>,
+    /// Outcome of each part, the text first
+    pub items: Vec<CopyItem>,
+}
+
+impl CopyReport {
+    /// Whether every part was copied
+    pub fn is_complete(&self) -> bool {
+        self.items.iter().all(|item| item.success)
+    }
+}
+
+/// Copy a message to a channel of another account, optionally moving it
+///
+/// # Arguments
+/// * `source` - The platform (account) the message is on
+/// * `message_id` - The message to copy
+/// * `target` - The platform (account) to copy it to; may be `source`
+/// * `target_channel_id` - The channel on the target platform
+/// * `delete_source` - Delete the original once everything was copied (move)
+///
+/// # Returns
+/// The report of each part, or an error if the original could not be read.
+/// The original is only deleted if every other part succeeded.
+pub async fn copy_message(
+    source: &dyn Platform,
+    message_id: &str,
+    target: &dyn Platform,
+    target_channel_id: &str,
+    delete_source: bool,
+) -> Result<CopyReport> {
+    let original = source.get_message(message_id).await?;
+
+    let mut items = Vec::new();
+    let mut file_ids = Vec::new();
+    for attachment in &original.attachments {
+        let uploaded = match source.download_file(&attachment.id).await {
+            Ok(data) => {
+                target
+                    .upload_file_data(target_channel_id, &attachment.filename, data)
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        match uploaded {
+            Ok(file_id) => {
+                file_ids.push(file_id);
+                items.push(CopyItem::new(
+                    CopyItemKind::Attachment,
+                    Some(&attachment.filename),
+                    Ok(()),
+                ));
+            }
+            Err(e) => items.push(CopyItem::new(
+                CopyItemKind::Attachment,
+                Some(&attachment.filename),
+                Err(e.message),
+            )),
+        }
+    }
+
+    let props = copy_props(&original, &source.capabilities().platform_name);
+    let sent = send_copy(target, target_channel_id, &original.text, file_ids, props).await;
+    let (message, text_outcome) = match sent {
+        Ok(message) => (Some(message), Ok(())),
+        Err(e) => (None, Err(e.message)),
+    };
+    items.insert(0, CopyItem::new(CopyItemKind::Text, None, text_outcome));
+
+    if delete_source {
+        let outcome = if items.iter().all(|item| item.success) {
+            source
+                .delete_message(message_id)
+                .await
+                .map_err(|e| e.message)
+        } else {
+            Err("Not deleted because parts of the message were not copied".to_string())
+        };
+        items.push(CopyItem::new(CopyItemKind::DeleteSource, None, outcome));
+    }
+
+    Ok(CopyReport { message, items })
+}
+
+/// Properties of a copy: the carried-over ones and the copy origin
+fn copy_props(original: &Message, platform_name: &str) -> HashMap<String, serde_json::Value> {
+    let mut props: HashMap<String, serde_json::Value> = original
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.get("props"))
+        .and_then(|props| props.as_object())
+        .map(|props| {
+            props
+                .iter()
+                .filter(|(key, _)| CARRIED_PROPS.contains(&key.as_str()))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // A copy of a copy points at the first original
+    let origin = CopyOrigin::from_message(original).unwrap_or_else(|| CopyOrigin {
+        platform: platform_name.to_string(),
+        channel_id: original.channel_id.clone(),
+        message_id: original.id.clone(),
+        sender_id: original.sender_id.clone(),
+        created_at: original.created_at,
+    });
+    props.insert(
+        COPY_ORIGIN_PROP.to_string(),
+        serde_json::to_value(&origin).unwrap_or_default(),
+    );
+    props
+}
+
+/// Send the copy, dropping what the target platform does not support
+async fn send_copy(
+    target: &dyn Platform,
+    channel_id: &str,
+    text: &str,
+    file_ids: Vec<String>,
+    props: HashMap<String, serde_json::Value>,
+) -> Result<Message> {
+    if !file_ids.is_empty() {
+        return target
+            .send_message_with_files(channel_id, text, file_ids, props)
+            .await;
+    }
+    match target
+        .send_message_with_props(channel_id, text, props)
+        .await
+    {
+        Err(e) if e.code == ErrorCode::Unsupported => target.send_message(channel_id, text).await,
+        result => result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_props() {
+        let original =
+            Message::new("msg-1", "hello", "user-1", "ch-1").with_metadata(serde_json::json!({
+                "props": {
+                    "libcommunicator_snippet": {"language": "rust"},
+                    "libcommunicator_chunk": {"index": 1},
+                    "from_webhook": "true",
+                }
+            }));
+
+        let props = copy_props(&original, "mattermost");
+        assert_eq!(props["libcommunicator_snippet"]["language"], "rust");
+        assert!(!props.contains_key("libcommunicator_chunk"));
+        assert!(!props.contains_key("from_webhook"));
+
+        let copy = Message::new("msg-2", "hello", "user-2", "ch-2")
+            .with_metadata(serde_json::json!({ "props": props }));
+        let origin = CopyOrigin::from_message(&copy).unwrap();
+        assert_eq!(origin.message_id, "msg-1");
+        assert_eq!(origin.platform, "mattermost");
+
+        // Copying the copy keeps the first origin
+        let props = copy_props(&copy, "mattermost");
+        assert_eq!(props[COPY_ORIGIN_PROP]["message_id"], "msg-1");
+    }
+
+    #[test]
+    fn test_report_completeness() {
+        let mut report = CopyReport {
+            message: None,
+            items: vec![CopyItem::new(CopyItemKind::Text, None, Ok(()))],
+        };
+        assert!(report.is_complete());
+
+        report.items.push(CopyItem::new(
+            CopyItemKind::Attachment,
+            Some("a.png"),
+            Err("Not found".to_string()),
+        ));
+        assert!(!report.is_complete());
+        assert_eq!(
+            serde_json::to_value(&report.items[1]).unwrap(),
+            serde_json::json!({
+                "kind": "attachment",
+                "name": "a.png",
+                "success": false,
+                "error": "Not found",
+            })
+        );
+    }
+}
@@ -0,0 +1,169 @@
+//! Cursors walking a channel's message history
+//!
+//! Clients reading a channel's history page by page would otherwise keep track
+//! of the oldest (or newest) message they have seen and ask for the messages
+//! before (or after) it. A [`MessageCursor`] does that bookkeeping: it starts at
+//! a message, or at the newest message, and each batch continues where the
+//! previous one ended, however the platform paginates.
+
+use crate::error::{Error, Result};
+use crate::platforms::Platform;
+use crate::types::Message;
+
+/// Which way a cursor walks the history
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Towards older messages
+    Older,
+    /// Towards newer messages
+    Newer,
+}
+
+impl Direction {
+    /// Parse "older" or "newer"
+    pub fn parse(direction: &str) -> Result<Self> {
+        match direction {
+            "older" => Ok(Self::Older),
+            "newer" => Ok(Self::Newer),
+            other => Err(Error::invalid_argument(format!(
+                "Unknown direction '{other}' (expected \"older\" or \"newer\")"
+            ))),
+        }
+    }
+}
+
+/// Position in a channel's message history
+#[derive(Debug)]
+pub struct MessageCursor {
+    /// Key of the platform handle the cursor reads from, checked by the C API
+    #[cfg_attr(not(feature = "ffi"), allow(dead_code))]
+    pub(crate) platform: usize,
+    channel_id: String,
+    direction: Direction,
+    /// Message the next batch continues from; None before the first batch of
+    /// a cursor starting at the newest message
+    anchor: Option<String>,
+    exhausted: bool,
+}
+
+impl MessageCursor {
+    /// Create a cursor
+    ///
+    /// # Arguments
+    /// * `platform` - Key of the platform handle to read from
+    /// * `channel_id` - The channel
+    /// * `from` - Message to start from (not included), or None for the newest
+    /// * `direction` - Which way to walk
+    ///
+    /// # Returns
+    /// An InvalidArgument error for a cursor walking towards newer messages
+    /// from the newest one
+    pub fn new(
+        platform: usize,
+        channel_id: impl Into<String>,
+        from: Option<&str>,
+        direction: Direction,
+    ) -> Result<Self> {
+        if from.is_none() && direction == Direction::Newer {
+            return Err(Error::invalid_argument(
+                "A cursor towards newer messages needs a message to start from",
+            ));
+        }
+        Ok(Self {
+            platform,
+            channel_id: channel_id.into(),
+            direction,
+            anchor: from.map(str::to_string),
+            exhausted: false,
+        })
+    }
+
+    /// Whether the end of the history has been reached
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    /// Fetch the next batch of messages
+    ///
+    /// # Arguments
+    /// * `platform` - The platform to read from
+    /// * `batch_size` - Maximum number of messages
+    ///
+    /// # Returns
+    /// The messages, oldest first; empty once the cursor is exhausted
+    pub async fn next_batch(
+        &mut self,
+        platform: &dyn Platform,
+        batch_size: usize,
+    ) -> Result<Vec<Message>> {
+        if self.exhausted || batch_size == 0 {
+            return Ok(Vec::new());
+        }
+        let messages = match (self.direction, &self.anchor) {
+            (Direction::Older, None) => platform.get_messages(&self.channel_id, batch_size).await?,
+            (Direction::Older, Some(before_id)) => {
+                platform
+                    .get_messages_before(&self.channel_id, before_id, batch_size)
+                    .await?
+            }
+            (Direction::Newer, Some(after_id)) => {
+                platform
+                    .get_messages_after(&self.channel_id, after_id, batch_size)
+                    .await?
+            }
+            (Direction::Newer, None) => Vec::new(),
+        };
+        self.advance(&messages, batch_size);
+        Ok(messages)
+    }
+
+    /// Move past a fetched batch
+    fn advance(&mut self, messages: &[Message], batch_size: usize) {
+        if messages.len() < batch_size {
+            self.exhausted = true;
+        }
+        let edge = match self.direction {
+            Direction::Older => messages.iter().min_by_key(|m| m.created_at),
+            Direction::Newer => messages.iter().max_by_key(|m| m.created_at),
+        };
+        match edge {
+            Some(edge) => self.anchor = Some(edge.id.clone()),
+            None => self.exhausted = true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(id: &str, minute: u32) -> Message {
+        let mut message = Message::new(id, "hi", "u1", "c1");
+        message.created_at = chrono::DateTime::from_timestamp(minute as i64 * 60, 0).unwrap();
+        message
+    }
+
+    #[test]
+    fn test_advance() {
+        let mut cursor = MessageCursor::new(1, "c1", None, Direction::Older).unwrap();
+        cursor.advance(&[message("m2", 2), message("m3", 3)], 2);
+        assert_eq!(cursor.anchor.as_deref(), Some("m2"));
+        assert!(!cursor.is_exhausted());
+        cursor.advance(&[message("m1", 1)], 2);
+        assert_eq!(cursor.anchor.as_deref(), Some("m1"));
+        assert!(cursor.is_exhausted());
+
+        let mut cursor = MessageCursor::new(1, "c1", Some("m1"), Direction::Newer).unwrap();
+        cursor.advance(&[message("m2", 2), message("m3", 3)], 2);
+        assert_eq!(cursor.anchor.as_deref(), Some("m3"));
+        cursor.advance(&[], 2);
+        assert!(cursor.is_exhausted());
+    }
+
+    #[test]
+    fn test_newer_needs_start() {
+        assert!(MessageCursor::new(1, "c1", None, Direction::Newer).is_err());
+        assert_eq!(Direction::parse("older").unwrap(), Direction::Older);
+        assert!(Direction::parse("sideways").is_err());
+    }
+}
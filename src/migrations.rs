@@ -0,0 +1,357 @@
+//! Versioned migrations of persisted state
+//!
+//! Files the library persists (reminders, the offline outbox, the startup and
+//! entity caches) are JSON documents with a top-level `version`. Files written
+//! before versioning (a bare array, or an object without `version`) are
+//! version 0.
+//! When a store is opened, an older file is upgraded by applying each
+//! migration from its version on, after copying the original to
+//! `<file>.v<version>.bak`. Applied migrations are reported to the log
+//! callbacks of all contexts and kept for [`applied`].
+//!
+//! A file written by a newer library version is refused rather than read and
+//! overwritten, so downgrading cannot silently drop data. [`migrate`] with
+//! `dry_run` reports what opening a file would do without changing it.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::error::{Error, ErrorCode, Result};
+
+/// One step upgrading a document from the version before it
+pub struct Migration {
+    /// What the migration changes, for reports
+    pub description: &'static str,
+    /// Upgrade the document; the caller sets `version` afterwards
+    pub apply: fn(Value) -> Result<Value>,
+}
+
+/// A persisted file format and the migrations up to its current version
+pub struct StoreFormat {
+    /// Name of the store, for reports
+    pub name: &'static str,
+    /// Migration `i` upgrades version `i` to version `i + 1`
+    pub migrations: &'static [Migration],
+}
+
+impl StoreFormat {
+    /// Version written by this library
+    pub fn current_version(&self) -> u32 {
+        self.migrations.len() as u32
+    }
+
+    /// Set `version` on a document about to be saved
+    pub fn stamp(&self, mut document: Value) -> Value {
+        if let Some(object) = document.as_object_mut() {
+            object.insert("version".to_string(), self.current_version().into());
+        }
+        document
+    }
+}
+
+/// Reminders of `ReminderScheduler::set_store`
+pub static REMINDERS: StoreFormat = StoreFormat {
+    name: "reminders",
+    migrations: &[Migration {
+        description: "Move the reminder list into a versioned document",
+        apply: |document| wrap_list(document, "reminders"),
+    }],
+};
+
+/// Offline outbox of the Mattermost platform
+pub static OUTBOX: StoreFormat = StoreFormat {
+    name: "outbox",
+    migrations: &[Migration {
+        description: "Move the queued messages into a versioned document",
+        apply: |document| wrap_list(document, "entries"),
+    }],
+};
+
+/// Startup cache of the Mattermost platform
+pub static STARTUP_CACHE: StoreFormat = StoreFormat {
+    name: "startup_cache",
+    migrations: &[Migration {
+        description: "Add a version to the session snapshot",
+        apply: Ok,
+    }],
+};
+
+/// Persistent entity cache of the Mattermost platform
+pub static ENTITY_CACHE: StoreFormat = StoreFormat {
+    name: "entity_cache",
+    migrations: &[],
+};
+
+/// All store formats, by name
+static FORMATS: &[&StoreFormat] = &[&REMINDERS, &OUTBOX, &STARTUP_CACHE, &ENTITY_CACHE];
+
+/// Look up a store format by name
+pub fn format(name: &str) -> Result<&'static StoreFormat> {
+    FORMATS
+        .iter()
+        .copied()
+        .find(|format| format.name == name)
+        .ok_or_else(|| {
+            let names: Vec<_> = FORMATS.iter().map(|format| format.name).collect();
+            Error::invalid_argument(format!(
+                "Unknown store '{name}' (expected one of {})",
+                names.join(", ")
+            ))
+        })
+}
+
+/// Version 0 stored a bare list; put it under `key`
+fn wrap_list(document: Value, key: &str) -> Result<Value> {
+    if !document.is_array() {
+        return Err(Error::invalid_argument(format!("Expected a list of {key}")));
+    }
+    Ok(serde_json::json!({ key: document }))
+}
+
+/// Migrations applied to (or, in a dry run, pending for) one file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MigrationReport {
+    pub store: String,
+    pub path: String,
+    pub from_version: u32,
+    pub to_version: u32,
+    /// Descriptions of the migrations, in order
+    pub migrations: Vec<String>,
+    /// Copy of the file before the migrations, unless a dry run
+    pub backup: Option<String>,
+    pub dry_run: bool,
+}
+
+lazy_static! {
+    /// Migrations applied since the library was loaded
+    static ref APPLIED: Mutex<Vec<MigrationReport>> = Mutex::new(Vec::new());
+}
+
+/// Migrations applied since the library was loaded, oldest first
+pub fn applied() -> Vec<MigrationReport> {
+    APPLIED.lock().unwrap().clone()
+}
+
+/// Version of a document: its `version`, or 0 before versioning
+fn document_version(document: &Value) -> Result<u32> {
+    match document.get("version") {
+        None => Ok(0),
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| Error::invalid_argument(format!("Invalid version {version}"))),
+    }
+}
+
+fn read_error(format: &StoreFormat, path: &Path, message: impl std::fmt::Display) -> Error {
+    Error::new(
+        ErrorCode::Unknown,
+        format!(
+            "Failed to read {} {}: {message}",
+            format.name,
+            path.display()
+        ),
+    )
+}
+
+fn write_error(format: &StoreFormat, path: &Path, e: std::io::Error) -> Error {
+    Error::new(
+        ErrorCode::Unknown,
+        format!("Failed to migrate {} {}: {e}", format.name, path.display()),
+    )
+}
+
+/// File the original of a migrated file is copied to
+fn backup_path(path: &Path, version: u32) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".v{version}.bak"));
+    path.with_file_name(name)
+}
+
+/// Upgrade a file's document to the current version
+///
+/// # Returns
+/// The upgraded document, and a report if any migration was needed
+fn upgrade(
+    format: &StoreFormat,
+    path: &Path,
+    json: &str,
+    dry_run: bool,
+) -> Result<(Value, Option<MigrationReport>)> {
+    let mut document: Value =
+        serde_json::from_str(json).map_err(|e| read_error(format, path, e))?;
+    let from_version = document_version(&document).map_err(|e| read_error(format, path, e))?;
+    let to_version = format.current_version();
+    if from_version > to_version {
+        return Err(Error::new(
+            ErrorCode::InvalidState,
+            format!(
+                "{} {} has version {from_version}, written by a newer library (this one reads up to {to_version})",
+                format.name,
+                path.display()
+            ),
+        ));
+    }
+    if from_version == to_version {
+        return Ok((document, None));
+    }
+
+    let pending = &format.migrations[from_version as usize..];
+    for (version, migration) in (from_version..).zip(pending) {
+        document = (migration.apply)(document).map_err(|e| {
+            Error::new(
+                e.code,
+                format!(
+                    "Migration of {} {} from version {version} failed: {}",
+                    format.name,
+                    path.display(),
+                    e.message
+                ),
+            )
+        })?;
+    }
+    let document = format.stamp(document);
+
+    let mut report = MigrationReport {
+        store: format.name.to_string(),
+        path: path.display().to_string(),
+        from_version,
+        to_version,
+        migrations: pending
+            .iter()
+            .map(|migration| migration.description.to_string())
+            .collect(),
+        backup: None,
+        dry_run,
+    };
+    if dry_run {
+        return Ok((document, Some(report)));
+    }
+
+    let backup = backup_path(path, from_version);
+    std::fs::write(&backup, json).map_err(|e| write_error(format, path, e))?;
+    let migrated = serde_json::to_string(&document).map_err(|e| {
+        Error::new(
+            ErrorCode::Unknown,
+            format!("Failed to serialize {}: {e}", format.name),
+        )
+    })?;
+    let temp_path = path.with_extension("tmp");
+    std::fs::write(&temp_path, migrated).map_err(|e| write_error(format, path, e))?;
+    std::fs::rename(&temp_path, path).map_err(|e| write_error(format, path, e))?;
+
+    report.backup = Some(backup.display().to_string());
+    tracing::info!(
+        format = format.name,
+        path = %path.display(),
+        from_version,
+        to_version,
+        backup = %backup.display(),
+        "Migrated {} ({})",
+        format.name,
+        report.migrations.join("; "),
+    );
+    APPLIED.lock().unwrap().push(report.clone());
+    Ok((document, Some(report)))
+}
+
+/// Open a store file, upgrading it to the current version first
+///
+/// # Returns
+/// The document, or None if the file does not exist
+pub fn open(format: &StoreFormat, path: &Path) -> Result<Option<Value>> {
+    let json = match std::fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(read_error(format, path, e)),
+    };
+    let (document, _) = upgrade(format, path, &json, false)?;
+    Ok(Some(document))
+}
+
+/// Upgrade a store file to the current version without opening the store
+///
+/// # Arguments
+/// * `format` - The format of the file
+/// * `path` - The file
+/// * `dry_run` - Only report the migrations, leaving the file unchanged
+///
+/// # Returns
+/// The migrations applied (or pending), or None if the file does not exist or
+/// is up to date
+pub fn migrate(
+    format: &StoreFormat,
+    path: &Path,
+    dry_run: bool,
+) -> Result<Option<MigrationReport>> {
+    let json = match std::fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(read_error(format, path, e)),
+    };
+    let (_, report) = upgrade(format, path, &json, dry_run)?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "libcommunicator-migrations-{name}-{}.json",
+            std::process::id()
+        ));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_upgrade_with_backup() {
+        let path = temp_file("upgrade", r#"[{"id":"r1"}]"#);
+
+        let report = migrate(&REMINDERS, &path, true).unwrap().unwrap();
+        assert!(report.dry_run);
+        assert_eq!((report.from_version, report.to_version), (0, 1));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), r#"[{"id":"r1"}]"#);
+
+        let document = open(&REMINDERS, &path).unwrap().unwrap();
+        assert_eq!(
+            document,
+            serde_json::json!({ "version": 1, "reminders": [{ "id": "r1" }] })
+        );
+        let backup = backup_path(&path, 0);
+        assert_eq!(
+            std::fs::read_to_string(&backup).unwrap(),
+            r#"[{"id":"r1"}]"#
+        );
+        assert!(applied()
+            .iter()
+            .any(|r| r.path == path.display().to_string()));
+
+        // Up to date now
+        assert!(migrate(&REMINDERS, &path, true).unwrap().is_none());
+
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&backup);
+    }
+
+    #[test]
+    fn test_newer_version_refused() {
+        let path = temp_file("newer", r#"{"version":99,"entries":[]}"#);
+        let error = open(&OUTBOX, &path).unwrap_err();
+        assert_eq!(error.code, ErrorCode::InvalidState);
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            r#"{"version":99,"entries":[]}"#
+        );
+        let _ = std::fs::remove_file(&path);
+
+        assert!(format("outbox").is_ok());
+        assert!(format("unknown").is_err());
+    }
+}
@@ -0,0 +1,274 @@
+//! Client-side mention and keyword detection
+//!
+//! While enabled for a platform (see [`MentionDetector`]), each posted message is checked against
+//! the current user's mention rules (`Platform::get_mention_rules`, plus any
+//! keywords the client adds). A message that mentions the user, mentions the
+//! whole channel, contains one of their keywords or arrives in a direct message
+//! channel is followed by a `notification_triggered` event carrying the
+//! message and the reason. The user's own messages never notify.
+//!
+//! Rules are fetched with the first message and again after the current user
+//! was updated, so changed notification settings take effect.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use crate::platforms::{Platform, PlatformEvent};
+use crate::types::{ChannelType, MentionRules, Message, NotificationReason};
+
+/// Mentions addressing everyone in a channel
+const CHANNEL_WIDE_MENTIONS: &[&str] = &["@channel", "@all", "@here"];
+
+/// Whether a character continues a word (usernames contain '-' and '_')
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-'
+}
+
+/// Whether a text contains a word, case-insensitively and not as part of a
+/// longer word
+fn contains_word(text: &str, word: &str) -> bool {
+    let word = word.trim().to_lowercase();
+    if word.is_empty() {
+        return false;
+    }
+    let text = text.to_lowercase();
+    text.match_indices(&word).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + word.len()..].chars().next();
+        let starts_word = before.is_none_or(|c| !is_word_char(c));
+        let ends_word = after.is_none_or(|c| !is_word_char(c));
+        starts_word && ends_word
+    })
+}
+
+/// Check a message against mention rules
+///
+/// # Arguments
+/// * `rules` - The current user's mention rules
+/// * `message` - The posted message
+/// * `is_direct` - Whether the message was posted in a direct message channel
+///
+/// # Returns
+/// Why the message notifies the user, or None if it does not
+pub fn evaluate(
+    rules: &MentionRules,
+    message: &Message,
+    is_direct: bool,
+) -> Option<NotificationReason> {
+    if message.sender_id == rules.user_id {
+        return None;
+    }
+    if is_direct {
+        return Some(NotificationReason::DirectMessage);
+    }
+
+    let text = &message.text;
+    if !rules.username.is_empty() {
+        let mention = format!("@{}", rules.username);
+        if contains_word(text, &mention) {
+            return Some(NotificationReason::Mention { keyword: mention });
+        }
+    }
+    if rules.channel_wide {
+        if let Some(mention) = CHANNEL_WIDE_MENTIONS
+            .iter()
+            .find(|mention| contains_word(text, mention))
+        {
+            return Some(NotificationReason::ChannelWide {
+                keyword: mention.to_string(),
+            });
+        }
+    }
+    rules
+        .first_name
+        .iter()
+        .chain(&rules.keywords)
+        .find(|keyword| contains_word(text, keyword))
+        .map(|keyword| NotificationReason::Keyword {
+            keyword: keyword.trim().to_string(),
+        })
+}
+
+/// Whether a channel is a direct message channel, false if it cannot be fetched
+pub(crate) async fn is_direct(platform: &dyn Platform, channel_id: &str) -> bool {
+    platform
+        .get_channel(channel_id)
+        .await
+        .is_ok_and(|channel| channel.channel_type == ChannelType::DirectMessage)
+}
+
+/// Detection state while enabled
+#[derive(Debug, Default)]
+struct Notifier {
+    /// Keywords added by the client to the platform's rules
+    extra_keywords: Vec<String>,
+    /// The rules in effect, None until fetched
+    rules: Option<MentionRules>,
+    /// Notifications not delivered yet
+    pending: VecDeque<PlatformEvent>,
+}
+
+/// Detects mentions and keywords in the events of one platform
+#[derive(Debug, Default)]
+pub struct MentionDetector {
+    /// None while detection is disabled
+    notifier: Mutex<Option<Notifier>>,
+}
+
+impl MentionDetector {
+    /// Create a detector, disabled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable or disable detection
+    ///
+    /// # Arguments
+    /// * `enabled` - Whether to detect mentions
+    /// * `keywords` - Keywords to add to the platform's rules
+    pub fn set(&self, enabled: bool, keywords: Vec<String>) {
+        *self.notifier.lock().unwrap() = enabled.then(|| Notifier {
+            extra_keywords: keywords,
+            ..Default::default()
+        });
+    }
+
+    /// Whether detection is enabled
+    pub fn is_enabled(&self) -> bool {
+        self.notifier.lock().unwrap().is_some()
+    }
+
+    /// The rules in effect, fetching them if needed
+    async fn rules(&self, platform: &dyn Platform) -> Option<MentionRules> {
+        let extra_keywords = {
+            let notifier = self.notifier.lock().unwrap();
+            let notifier = notifier.as_ref()?;
+            if let Some(rules) = &notifier.rules {
+                return Some(rules.clone());
+            }
+            notifier.extra_keywords.clone()
+        };
+
+        // Not fetched yet; messages arriving before the rules can be fetched
+        // are not checked
+        let mut rules = platform.get_mention_rules().await.ok()?;
+        rules.keywords.extend(extra_keywords);
+        if let Some(notifier) = self.notifier.lock().unwrap().as_mut() {
+            notifier.rules = Some(rules.clone());
+        }
+        Some(rules)
+    }
+
+    /// Check a delivered event, queueing a notification for it if it triggers one
+    pub async fn note(&self, platform: &dyn Platform, event: &PlatformEvent) {
+        match event {
+            PlatformEvent::MessagePosted(message) => {
+                let Some(rules) = self.rules(platform).await else {
+                    return;
+                };
+                if message.sender_id == rules.user_id {
+                    return;
+                }
+                let is_direct = is_direct(platform, &message.channel_id).await;
+                let Some(reason) = evaluate(&rules, message, is_direct) else {
+                    return;
+                };
+                if let Some(notifier) = self.notifier.lock().unwrap().as_mut() {
+                    notifier
+                        .pending
+                        .push_back(PlatformEvent::NotificationTriggered {
+                            message: message.clone(),
+                            reason,
+                        });
+                }
+            }
+            // The user's notification settings may have changed
+            PlatformEvent::UserUpdated { user_id } => {
+                if let Some(notifier) = self.notifier.lock().unwrap().as_mut() {
+                    if notifier
+                        .rules
+                        .as_ref()
+                        .is_some_and(|rules| &rules.user_id == user_id)
+                    {
+                        notifier.rules = None;
+                    }
+                }
+            }
+            PlatformEvent::SessionReady(_) => {
+                if let Some(notifier) = self.notifier.lock().unwrap().as_mut() {
+                    notifier.rules = None;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Take the next notification
+    pub fn pop(&self) -> Option<PlatformEvent> {
+        self.notifier.lock().unwrap().as_mut()?.pending.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> MentionRules {
+        let mut rules = MentionRules::new("me", "alice");
+        rules.first_name = Some("Alice".to_string());
+        rules.keywords = vec!["deploy".to_string()];
+        rules
+    }
+
+    fn check(text: &str) -> Option<NotificationReason> {
+        evaluate(&rules(), &Message::new("m1", text, "bob", "c1"), false)
+    }
+
+    #[test]
+    fn test_mentions() {
+        assert_eq!(
+            check("hey @Alice, look"),
+            Some(NotificationReason::Mention {
+                keyword: "@alice".to_string()
+            })
+        );
+        assert_eq!(
+            check("@here standup"),
+            Some(NotificationReason::ChannelWide {
+                keyword: "@here".to_string()
+            })
+        );
+        assert_eq!(
+            check("Deploy is done"),
+            Some(NotificationReason::Keyword {
+                keyword: "deploy".to_string()
+            })
+        );
+        assert_eq!(check("alice said hi").unwrap().name(), "keyword");
+    }
+
+    #[test]
+    fn test_word_boundaries() {
+        assert_eq!(check("@alice-bot is broken"), None);
+        assert_eq!(check("redeployment"), None);
+        assert_eq!(check("mail alice_w"), None);
+        assert!(check("(@alice)").is_some());
+    }
+
+    #[test]
+    fn test_direct_and_own_messages() {
+        let rules = rules();
+        let dm = Message::new("m1", "hi", "bob", "dm1");
+        assert_eq!(
+            evaluate(&rules, &dm, true),
+            Some(NotificationReason::DirectMessage)
+        );
+        let own = Message::new("m2", "@alice deploy", "me", "c1");
+        assert_eq!(evaluate(&rules, &own, true), None);
+
+        let mut quiet = rules.clone();
+        quiet.channel_wide = false;
+        let all = Message::new("m3", "@all lunch", "bob", "c1");
+        assert_eq!(evaluate(&quiet, &all, false), None);
+    }
+}
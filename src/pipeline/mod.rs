@@ -0,0 +1,616 @@
+//! Event pipeline of a platform
+//!
+//! [`PipelinePlatform`] wraps a platform and runs the events it polls through
+//! the stages configured on its [`Pipeline`], in this order:
+//!
+//! 1. Locally scheduled messages that are due are sent, and due reminders,
+//!    mention notifications, invalidation hints and coalesced presence and
+//!    typing summaries are delivered before platform events
+//! 2. Presence and typing events are held back for coalescing
+//! 3. Split messages are reassembled
+//! 4. The language of messages is detected
+//! 5. Inbound script hooks transform or drop messages (`scripting` feature)
+//! 6. Desktop notifications are published (`dbus` feature, Linux),
+//!    invalidation hints raised and mentions detected
+//! 7. The event is posted to webhooks and evaluated by the rules
+//!
+//! Every stage is off until configured. Sent messages go through the outbound
+//! script hooks, and scheduled messages and drafts are kept locally for
+//! platforms that cannot store them. The C API and the daemon serve their
+//! platforms through this wrapper; Rust programs wrap a platform to get the
+//! same behaviour:
+//!
+//! ```no_run
+//! use communicator::pipeline::PipelinePlatform;
+//! use communicator::platforms::mattermost::MattermostPlatform;
+//! use communicator::webhook::EventWebhook;
+//! use communicator::{Platform, PlatformConfig};
+//!
+//! # async fn run() -> communicator::Result<()> {
+//! let mut platform =
+//!     PipelinePlatform::new(Box::new(MattermostPlatform::new("https://chat.example.com")?));
+//! platform.pipeline().add_webhook(EventWebhook::new("http://localhost:8080/events", None)?);
+//!
+//! let config = PlatformConfig::new("https://chat.example.com").with_credential("token", "...");
+//! platform.connect(config).await?;
+//! platform.subscribe_events().await?;
+//! while let Some(event) = platform.poll_event().await? {
+//!     // Events have been posted to the webhook by now
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+mod platform_impl;
+#[cfg(test)]
+pub(crate) mod test_platform;
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use tokio::sync::RwLock;
+
+use crate::chunking::ChunkReassembler;
+#[cfg(all(feature = "dbus", target_os = "linux"))]
+use crate::dbus::DesktopNotifier;
+use crate::drafts::DraftStore;
+use crate::error::Result;
+#[cfg(feature = "scripting")]
+use crate::error::{Error, ErrorCode};
+use crate::event_json::event_to_json;
+use crate::language::LanguageSettings;
+use crate::notifications::MentionDetector;
+use crate::platforms::{Platform, PlatformEvent};
+use crate::reminders::ReminderScheduler;
+use crate::rules::RulesEngine;
+use crate::scheduled_messages::{self, ScheduledQueue};
+#[cfg(feature = "scripting")]
+use crate::scripting::{HookStage, KeptOriginals, OutgoingMessage, ScriptHook, ScriptPipeline};
+#[cfg(feature = "scripting")]
+use crate::types::{Message, Transformation};
+use crate::webhook::{self, EventWebhook};
+use crate::{coalescing, invalidation};
+
+/// Result of taking the next event from a platform
+#[derive(Debug)]
+pub enum Polled<T = PlatformEvent> {
+    /// An event, in the form requested by the caller
+    Event(T),
+    /// An event was taken but is not delivered (e.g. part of a split message)
+    Skipped,
+    /// No event is available
+    Empty,
+}
+
+/// State of the event pipeline of one platform
+///
+/// All stages are configured through shared references, so the pipeline can
+/// be changed while events are polled.
+#[derive(Default)]
+pub struct Pipeline {
+    scheduled: ScheduledQueue,
+    drafts: DraftStore,
+    reminders: ReminderScheduler,
+    mentions: MentionDetector,
+    /// None while invalidation hints are disabled
+    invalidation: Mutex<Option<invalidation::Coalescer>>,
+    /// None while coalescing is disabled
+    coalescing: Mutex<Option<coalescing::Coalescer>>,
+    /// None while reassembly is disabled
+    chunks: Mutex<Option<ChunkReassembler>>,
+    language: Mutex<LanguageSettings>,
+    #[cfg(feature = "scripting")]
+    hooks: Mutex<Arc<ScriptPipeline>>,
+    /// Original texts of messages changed by inbound hooks
+    #[cfg(feature = "scripting")]
+    originals: Mutex<KeptOriginals>,
+    #[cfg(all(feature = "dbus", target_os = "linux"))]
+    desktop: Mutex<Option<Arc<DesktopNotifier>>>,
+    webhooks: Mutex<Vec<Arc<EventWebhook>>>,
+    rules: Mutex<Option<Arc<RulesEngine>>>,
+}
+
+impl Pipeline {
+    /// Create a pipeline with every stage off
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reminders delivered as reminder_due events
+    pub fn reminders(&self) -> &ReminderScheduler {
+        &self.reminders
+    }
+
+    /// Messages scheduled locally because the platform cannot schedule them
+    pub fn scheduled_messages(&self) -> &ScheduledQueue {
+        &self.scheduled
+    }
+
+    /// Drafts saved locally because the platform cannot store them
+    pub fn drafts(&self) -> &DraftStore {
+        &self.drafts
+    }
+
+    /// Enable or disable mention_notification events
+    ///
+    /// # Arguments
+    /// * `enabled` - Whether to detect mentions
+    /// * `keywords` - Keywords to add to the platform's mention rules
+    pub fn set_mention_detection(&self, enabled: bool, keywords: Vec<String>) {
+        self.mentions.set(enabled, keywords);
+    }
+
+    /// Enable invalidation hints, raised at most once per interval, or disable
+    /// them with None
+    pub fn set_invalidation_hints(&self, interval: Option<Duration>) {
+        *self.invalidation.lock().unwrap() = interval.map(invalidation::Coalescer::new);
+    }
+
+    /// Enable coalescing of presence and typing events, summarized once per
+    /// interval, or disable it with None
+    ///
+    /// Events held back when coalescing is changed are dropped.
+    pub fn set_coalescing(&self, interval: Option<Duration>) {
+        *self.coalescing.lock().unwrap() = interval.map(coalescing::Coalescer::new);
+    }
+
+    /// Enable or disable reassembly of messages split by
+    /// [`crate::chunking::send_chunked`]
+    ///
+    /// Parts already received are kept while reassembly stays enabled.
+    pub fn set_chunk_reassembly(&self, enabled: bool) {
+        let mut chunks = self.chunks.lock().unwrap();
+        if !enabled {
+            *chunks = None;
+        } else if chunks.is_none() {
+            *chunks = Some(ChunkReassembler::new());
+        }
+    }
+
+    /// Enable or disable language detection for a channel, or for all
+    /// channels with None
+    pub fn set_language_detection(&self, channel_id: Option<&str>, enabled: bool) {
+        self.language.lock().unwrap().set(channel_id, enabled);
+    }
+
+    /// Post events to a webhook, in addition to the webhooks added before
+    pub fn add_webhook(&self, webhook: EventWebhook) {
+        self.webhooks.lock().unwrap().push(Arc::new(webhook));
+    }
+
+    /// Stop posting events to webhooks
+    pub fn clear_webhooks(&self) {
+        self.webhooks.lock().unwrap().clear();
+    }
+
+    /// Evaluate events against rules, or stop with None
+    pub fn set_rules(&self, engine: Option<RulesEngine>) {
+        *self.rules.lock().unwrap() = engine.map(Arc::new);
+    }
+
+    /// Publish desktop notifications for events, or stop with None
+    #[cfg(all(feature = "dbus", target_os = "linux"))]
+    pub fn set_desktop_notifier(&self, notifier: Option<DesktopNotifier>) {
+        *self.desktop.lock().unwrap() = notifier.map(Arc::new);
+    }
+
+    /// Run a script hook after the hooks added before
+    #[cfg(feature = "scripting")]
+    pub fn add_script_hook(&self, hook: ScriptHook) {
+        let mut hooks = self.hooks.lock().unwrap();
+        let mut updated = (**hooks).clone();
+        updated.add(hook);
+        *hooks = Arc::new(updated);
+    }
+
+    /// Remove all script hooks
+    #[cfg(feature = "scripting")]
+    pub fn clear_script_hooks(&self) {
+        *self.hooks.lock().unwrap() = Arc::default();
+    }
+
+    /// The text of a message before inbound hooks changed it, with the
+    /// transformations they applied
+    #[cfg(feature = "scripting")]
+    pub fn original_text(&self, message_id: &str) -> Option<(String, Vec<Transformation>)> {
+        self.originals.lock().unwrap().get(message_id)
+    }
+
+    /// Run outbound script hooks on a message about to be sent
+    ///
+    /// # Returns
+    /// The text to send, or an error if a hook failed or dropped the message
+    #[cfg(feature = "scripting")]
+    pub fn outbound(&self, channel_id: &str, text: &str) -> Result<String> {
+        let hooks = self.hooks.lock().unwrap().clone();
+        if !hooks.has_stage(HookStage::Outbound) {
+            return Ok(text.to_string());
+        }
+
+        let outgoing = OutgoingMessage {
+            channel_id: channel_id.to_string(),
+            text: text.to_string(),
+        };
+        match hooks.process_outbound(outgoing)? {
+            Some(outgoing) => Ok(outgoing.text),
+            None => Err(Error::new(
+                ErrorCode::InvalidState,
+                "Message was dropped by an outbound script hook",
+            )),
+        }
+    }
+
+    /// Run outbound script hooks on a message about to be sent
+    ///
+    /// # Returns
+    /// The text to send, unchanged without the `scripting` feature
+    #[cfg(not(feature = "scripting"))]
+    pub fn outbound(&self, _channel_id: &str, text: &str) -> Result<String> {
+        Ok(text.to_string())
+    }
+
+    /// Send due scheduled messages and take the next event raised by the
+    /// pipeline itself
+    async fn due_event(&self, platform: &dyn Platform) -> Option<PlatformEvent> {
+        // Their echoes arrive as ordinary events
+        if self.scheduled.has_due(Utc::now()) {
+            scheduled_messages::send_due(&self.scheduled, platform).await;
+        }
+
+        let now = Instant::now();
+        self.reminders
+            .pop_due(Utc::now())
+            .map(PlatformEvent::ReminderDue)
+            .or_else(|| self.mentions.pop())
+            .or_else(|| {
+                let mut invalidation = self.invalidation.lock().unwrap();
+                invalidation.as_mut()?.pop_due(now).map(|i| i.event())
+            })
+            .or_else(|| self.coalescing.lock().unwrap().as_mut()?.pop_due(now))
+    }
+
+    /// Hold back a polled event for coalescing
+    ///
+    /// # Returns
+    /// The event if it is to be delivered now, None if it was held back
+    fn hold(&self, event: PlatformEvent) -> Option<PlatformEvent> {
+        match self.coalescing.lock().unwrap().as_mut() {
+            Some(coalescer) => coalescer.hold(event, Instant::now()),
+            None => Some(event),
+        }
+    }
+
+    /// Run an event through the stages after polling
+    ///
+    /// # Returns
+    /// The event to deliver, None if a stage held it back or dropped it
+    async fn process(
+        &self,
+        platform: &dyn Platform,
+        event: PlatformEvent,
+    ) -> Option<PlatformEvent> {
+        let mut event = self.reassemble(event)?;
+        self.language.lock().unwrap().annotate(&mut event);
+        let event = self.inbound(event)?;
+
+        #[cfg(all(feature = "dbus", target_os = "linux"))]
+        {
+            let notifier = self.desktop.lock().unwrap().clone();
+            if let Some(notifier) = notifier {
+                notifier.dispatch(platform, &event).await;
+            }
+        }
+        if let Some(invalidation) = self.invalidation.lock().unwrap().as_mut() {
+            invalidation.note(&event, Instant::now());
+        }
+        if self.mentions.is_enabled() {
+            self.mentions.note(platform, &event).await;
+        }
+
+        // Webhooks and rules work on the JSON representation
+        let json = event_to_json(&event);
+        let webhooks = self.webhooks.lock().unwrap().clone();
+        webhook::dispatch(&webhooks, &json);
+
+        let engine = self.rules.lock().unwrap().clone();
+        if let Some(engine) = engine {
+            // Rule actions are best-effort and must not affect event polling
+            let _ = engine.run(platform, &json).await;
+        }
+
+        Some(event)
+    }
+
+    /// Pass a message through the reassembler, if enabled
+    fn reassemble(&self, event: PlatformEvent) -> Option<PlatformEvent> {
+        match (event, self.chunks.lock().unwrap().as_mut()) {
+            (PlatformEvent::MessagePosted(message), Some(reassembler)) => {
+                reassembler.add(message).map(PlatformEvent::MessagePosted)
+            }
+            (event, _) => Some(event),
+        }
+    }
+
+    /// Run inbound script hooks on a message event
+    ///
+    /// Returns None if a hook dropped the message. If a hook fails, the event
+    /// is delivered unchanged.
+    #[cfg(feature = "scripting")]
+    fn inbound(&self, event: PlatformEvent) -> Option<PlatformEvent> {
+        let hooks = self.hooks.lock().unwrap().clone();
+        if !hooks.has_stage(HookStage::Inbound) {
+            return Some(event);
+        }
+
+        let process = |message: Message| match hooks.process_inbound(message.clone()) {
+            Ok(Some(processed)) => {
+                self.originals.lock().unwrap().keep(&processed);
+                Some(processed)
+            }
+            Ok(None) => None,
+            Err(_) => Some(message),
+        };
+
+        match event {
+            PlatformEvent::MessagePosted(message) => {
+                process(message).map(PlatformEvent::MessagePosted)
+            }
+            PlatformEvent::MessageUpdated(message) => {
+                process(message).map(PlatformEvent::MessageUpdated)
+            }
+            PlatformEvent::MessagePinned(message) => {
+                process(message).map(PlatformEvent::MessagePinned)
+            }
+            PlatformEvent::MessageConfirmed {
+                pending_id,
+                message,
+            } => process(message).map(|message| PlatformEvent::MessageConfirmed {
+                pending_id,
+                message,
+            }),
+            PlatformEvent::MessageSendSucceeded { outbox_id, message } => process(message)
+                .map(|message| PlatformEvent::MessageSendSucceeded { outbox_id, message }),
+            other => Some(other),
+        }
+    }
+
+    #[cfg(not(feature = "scripting"))]
+    fn inbound(&self, event: PlatformEvent) -> Option<PlatformEvent> {
+        Some(event)
+    }
+}
+
+/// A platform whose events run through a [`Pipeline`]
+///
+/// Implements [`Platform`] by delegating to the wrapped platform, except that
+/// [`Platform::poll_event`] runs the pipeline, sends go through outbound script
+/// hooks, and scheduled messages and drafts fall back to the pipeline's local
+/// stores.
+pub struct PipelinePlatform {
+    platform: Box<dyn Platform>,
+    pipeline: Arc<Pipeline>,
+}
+
+impl PipelinePlatform {
+    /// Wrap a platform with a pipeline that has every stage off
+    pub fn new(platform: Box<dyn Platform>) -> Self {
+        Self {
+            platform,
+            pipeline: Arc::new(Pipeline::new()),
+        }
+    }
+
+    /// The pipeline, to configure its stages
+    pub fn pipeline(&self) -> &Arc<Pipeline> {
+        &self.pipeline
+    }
+
+    /// The wrapped platform
+    pub fn inner(&self) -> &dyn Platform {
+        &*self.platform
+    }
+
+    /// Take the next event and run it through the pipeline
+    ///
+    /// Unlike [`Platform::poll_event`], returns [`Polled::Skipped`] for an
+    /// event the pipeline held back or dropped instead of polling again.
+    pub async fn next_event(&mut self) -> Result<Polled> {
+        let event = match self.pipeline.due_event(&*self.platform).await {
+            Some(event) => event,
+            None => match self.platform.poll_event().await? {
+                Some(event) => match self.pipeline.hold(event) {
+                    Some(event) => event,
+                    // Delivered with the next summary
+                    None => return Ok(Polled::Skipped),
+                },
+                None => return Ok(Polled::Empty),
+            },
+        };
+
+        Ok(match self.pipeline.process(&*self.platform, event).await {
+            Some(event) => Polled::Event(event),
+            None => Polled::Skipped,
+        })
+    }
+
+    /// Take the next event of a shared platform and run it through the pipeline
+    ///
+    /// Like [`PipelinePlatform::next_event`], but holds the write lock only
+    /// while polling the wrapped platform, so that other tasks (including rule
+    /// actions) can use the platform while the rest of the pipeline runs.
+    pub async fn next_event_shared(platform: &RwLock<Self>) -> Result<Polled> {
+        let due_event = {
+            let platform = platform.read().await;
+            platform.pipeline.due_event(&*platform.platform).await
+        };
+        let event = match due_event {
+            Some(event) => event,
+            None => {
+                let mut platform = platform.write().await;
+                match platform.platform.poll_event().await? {
+                    Some(event) => match platform.pipeline.hold(event) {
+                        Some(event) => event,
+                        None => return Ok(Polled::Skipped),
+                    },
+                    None => return Ok(Polled::Empty),
+                }
+            }
+        };
+
+        let platform = platform.read().await;
+        Ok(
+            match platform.pipeline.process(&*platform.platform, event).await {
+                Some(event) => Polled::Event(event),
+                None => Polled::Skipped,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use super::test_platform::{posted, QueuedPlatform};
+    use crate::types::ScheduledMessage;
+
+    #[tokio::test]
+    async fn test_poll_event_runs_pipeline() {
+        let typing = PlatformEvent::UserTyping {
+            user_id: "user-2".to_string(),
+            channel_id: "ch-1".to_string(),
+        };
+        let mut platform =
+            PipelinePlatform::new(Box::new(QueuedPlatform::new([typing, posted("m1")])));
+        let pipeline = Arc::clone(platform.pipeline());
+        pipeline.set_coalescing(Some(Duration::from_secs(60)));
+        pipeline
+            .reminders()
+            .add(
+                "ch-1",
+                None,
+                None,
+                Utc::now() - chrono::Duration::minutes(1),
+            )
+            .unwrap();
+
+        // Due reminders come first, and the held typing event is skipped
+        assert!(matches!(
+            platform.poll_event().await.unwrap(),
+            Some(PlatformEvent::ReminderDue(_))
+        ));
+        match platform.poll_event().await.unwrap() {
+            Some(PlatformEvent::MessagePosted(message)) => assert_eq!(message.id, "m1"),
+            other => panic!("expected the message, got {other:?}"),
+        }
+        assert!(platform.poll_event().await.unwrap().is_none());
+
+        // The shared variant reports the skipped event instead of polling again
+        let typing = PlatformEvent::UserTyping {
+            user_id: "user-2".to_string(),
+            channel_id: "ch-1".to_string(),
+        };
+        let platform = PipelinePlatform::new(Box::new(QueuedPlatform::new([typing])));
+        platform
+            .pipeline()
+            .set_coalescing(Some(Duration::from_secs(60)));
+        let shared = RwLock::new(platform);
+        assert!(matches!(
+            PipelinePlatform::next_event_shared(&shared).await.unwrap(),
+            Polled::Skipped
+        ));
+        assert!(matches!(
+            PipelinePlatform::next_event_shared(&shared).await.unwrap(),
+            Polled::Empty
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_local_fallbacks() {
+        let platform = PipelinePlatform::new(Box::new(QueuedPlatform::new([])));
+        let send_at = Utc::now() + chrono::Duration::hours(1);
+
+        let scheduled: ScheduledMessage = platform
+            .schedule_message("ch-1", "later", send_at)
+            .await
+            .unwrap();
+        assert_eq!(
+            platform.get_scheduled_messages().await.unwrap(),
+            vec![scheduled.clone()]
+        );
+        platform
+            .cancel_scheduled_message(&scheduled.id)
+            .await
+            .unwrap();
+        assert!(platform.get_scheduled_messages().await.unwrap().is_empty());
+
+        let draft = crate::types::Draft::new("ch-1", "unsent");
+        platform.save_draft(&draft).await.unwrap();
+        assert_eq!(platform.get_drafts().await.unwrap().len(), 1);
+        platform.delete_draft("ch-1", None).await.unwrap();
+        assert!(platform.get_drafts().await.unwrap().is_empty());
+    }
+
+    /// Accept one HTTP request and answer it with 200, returning its body
+    async fn receive_post(listener: &tokio::net::TcpListener) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0u8; 1024];
+        loop {
+            let n = stream.read(&mut buf).await.unwrap();
+            assert!(n > 0, "connection closed before the request was complete");
+            request.extend_from_slice(&buf[..n]);
+
+            let text = String::from_utf8_lossy(&request);
+            let Some(header_end) = text.find("\r\n\r\n") else {
+                continue;
+            };
+            let length = text[..header_end]
+                .lines()
+                .find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("content-length")
+                        .then(|| value.trim().parse::<usize>().unwrap())
+                })
+                .unwrap_or(0);
+            if request.len() >= header_end + 4 + length {
+                stream
+                    .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+                    .await
+                    .unwrap();
+                return text[header_end + 4..].to_string();
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_poll_event_posts_to_webhooks() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/events", listener.local_addr().unwrap());
+
+        let typing = PlatformEvent::UserTyping {
+            user_id: "user-2".to_string(),
+            channel_id: "ch-1".to_string(),
+        };
+        let mut platform =
+            PipelinePlatform::new(Box::new(QueuedPlatform::new([typing, posted("m1")])));
+        let webhook = EventWebhook::new(&url, Some(vec!["message_posted".to_string()])).unwrap();
+        platform.pipeline().add_webhook(webhook);
+
+        assert!(platform.poll_event().await.unwrap().is_some());
+        assert!(platform.poll_event().await.unwrap().is_some());
+
+        // Only the event type the webhook forwards is posted
+        let body: serde_json::Value = serde_json::from_str(&receive_post(&listener).await).unwrap();
+        assert_eq!(body["type"], "message_posted");
+        assert_eq!(body["data"]["id"], "m1");
+        assert!(
+            tokio::time::timeout(Duration::from_millis(200), listener.accept())
+                .await
+                .is_err()
+        );
+    }
+}
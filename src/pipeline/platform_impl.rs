@@ -0,0 +1,954 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Notify;
+
+use crate::drafts;
+use crate::error::Result;
+use crate::platforms::{ChunkSink, Platform, PlatformConfig, PlatformEvent, ProgressSink};
+use crate::scheduled_messages;
+use crate::types::user::UserStatus;
+use crate::types::{
+    CacheConfig, Channel, ConnectionInfo, Draft, EventFilter, Message, PlatformCapabilities,
+    ScheduledMessage, SendOptions, Team, User,
+};
+
+use super::{PipelinePlatform, Polled};
+
+#[async_trait]
+impl Platform for PipelinePlatform {
+    async fn poll_event(&mut self) -> Result<Option<PlatformEvent>> {
+        loop {
+            match self.next_event().await? {
+                Polled::Event(event) => return Ok(Some(event)),
+                Polled::Skipped => {}
+                Polled::Empty => return Ok(None),
+            }
+        }
+    }
+
+    async fn send_message(&self, channel_id: &str, text: &str) -> Result<Message> {
+        let text = self.pipeline.outbound(channel_id, text)?;
+        self.platform.send_message(channel_id, &text).await
+    }
+
+    async fn send_reply(&self, channel_id: &str, text: &str, root_id: &str) -> Result<Message> {
+        let text = self.pipeline.outbound(channel_id, text)?;
+        self.platform.send_reply(channel_id, &text, root_id).await
+    }
+
+    async fn send_message_with_attachments(
+        &self,
+        channel_id: &str,
+        text: &str,
+        file_ids: Vec<String>,
+    ) -> Result<Message> {
+        let text = self.pipeline.outbound(channel_id, text)?;
+        self.platform
+            .send_message_with_attachments(channel_id, &text, file_ids)
+            .await
+    }
+
+    async fn send_message_ex(
+        &self,
+        channel_id: &str,
+        text: &str,
+        options: &SendOptions,
+    ) -> Result<Message> {
+        let text = self.pipeline.outbound(channel_id, text)?;
+        self.platform
+            .send_message_ex(channel_id, &text, options)
+            .await
+    }
+
+    async fn schedule_message(
+        &self,
+        channel_id: &str,
+        text: &str,
+        send_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<ScheduledMessage> {
+        let text = self.pipeline.outbound(channel_id, text)?;
+        scheduled_messages::schedule(
+            &self.pipeline.scheduled,
+            &*self.platform,
+            channel_id,
+            &text,
+            send_at,
+        )
+        .await
+    }
+
+    async fn get_scheduled_messages(&self) -> Result<Vec<ScheduledMessage>> {
+        scheduled_messages::list(&self.pipeline.scheduled, &*self.platform).await
+    }
+
+    async fn cancel_scheduled_message(&self, scheduled_id: &str) -> Result<()> {
+        scheduled_messages::cancel(&self.pipeline.scheduled, &*self.platform, scheduled_id).await
+    }
+
+    async fn save_draft(&self, draft: &Draft) -> Result<Draft> {
+        drafts::save(&self.pipeline.drafts, &*self.platform, draft).await
+    }
+
+    async fn get_drafts(&self) -> Result<Vec<Draft>> {
+        drafts::list(&self.pipeline.drafts, &*self.platform).await
+    }
+
+    async fn delete_draft(&self, channel_id: &str, root_id: Option<&str>) -> Result<()> {
+        drafts::delete(&self.pipeline.drafts, &*self.platform, channel_id, root_id).await
+    }
+
+    // Everything else is delegated to the wrapped platform
+
+    fn capabilities(&self) -> &PlatformCapabilities {
+        self.platform.capabilities()
+    }
+
+    async fn connect(&mut self, config: PlatformConfig) -> Result<ConnectionInfo> {
+        self.platform.connect(config).await
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        self.platform.disconnect().await
+    }
+
+    async fn renew_session(&self, credentials: HashMap<String, String>) -> Result<()> {
+        self.platform.renew_session(credentials).await
+    }
+
+    async fn begin_sso_login(&self, provider: &str) -> Result<String> {
+        self.platform.begin_sso_login(provider).await
+    }
+
+    async fn complete_sso_login(&self, callback_url: &str) -> Result<String> {
+        self.platform.complete_sso_login(callback_url).await
+    }
+
+    fn connection_info(&self) -> Option<&ConnectionInfo> {
+        self.platform.connection_info()
+    }
+
+    fn is_connected(&self) -> bool {
+        self.platform.is_connected()
+    }
+
+    async fn get_channels(&self) -> Result<Vec<Channel>> {
+        self.platform.get_channels().await
+    }
+
+    async fn get_channel(&self, channel_id: &str) -> Result<Channel> {
+        self.platform.get_channel(channel_id).await
+    }
+
+    async fn get_messages(&self, channel_id: &str, limit: usize) -> Result<Vec<Message>> {
+        self.platform.get_messages(channel_id, limit).await
+    }
+
+    async fn get_channel_members(&self, channel_id: &str) -> Result<Vec<User>> {
+        self.platform.get_channel_members(channel_id).await
+    }
+
+    async fn get_user(&self, user_id: &str) -> Result<User> {
+        self.platform.get_user(user_id).await
+    }
+
+    async fn get_current_user(&self) -> Result<User> {
+        self.platform.get_current_user().await
+    }
+
+    async fn get_mention_rules(&self) -> Result<crate::types::MentionRules> {
+        self.platform.get_mention_rules().await
+    }
+
+    async fn update_profile(&self, update: &crate::types::ProfileUpdate) -> Result<User> {
+        self.platform.update_profile(update).await
+    }
+
+    async fn set_profile_image(&self, image: Vec<u8>) -> Result<()> {
+        self.platform.set_profile_image(image).await
+    }
+
+    async fn get_groups(&self) -> Result<Vec<crate::types::Group>> {
+        self.platform.get_groups().await
+    }
+
+    async fn get_group_members(&self, group_id: &str) -> Result<Vec<User>> {
+        self.platform.get_group_members(group_id).await
+    }
+
+    async fn resolve_group_mentions(&self, text: &str) -> Result<Vec<crate::types::GroupMention>> {
+        self.platform.resolve_group_mentions(text).await
+    }
+
+    async fn cached_channels(&self) -> Result<Vec<Channel>> {
+        self.platform.cached_channels().await
+    }
+
+    async fn cached_users(&self) -> Result<Vec<User>> {
+        self.platform.cached_users().await
+    }
+
+    async fn create_direct_channel(&self, user_id: &str) -> Result<Channel> {
+        self.platform.create_direct_channel(user_id).await
+    }
+
+    async fn create_channel(
+        &self,
+        team_id: &str,
+        name: &str,
+        display_name: &str,
+        is_private: bool,
+    ) -> Result<Channel> {
+        self.platform
+            .create_channel(team_id, name, display_name, is_private)
+            .await
+    }
+
+    async fn update_channel(
+        &self,
+        channel_id: &str,
+        display_name: Option<&str>,
+        purpose: Option<&str>,
+        header: Option<&str>,
+    ) -> Result<Channel> {
+        self.platform
+            .update_channel(channel_id, display_name, purpose, header)
+            .await
+    }
+
+    async fn delete_channel(&self, channel_id: &str) -> Result<()> {
+        self.platform.delete_channel(channel_id).await
+    }
+
+    async fn get_teams(&self) -> Result<Vec<Team>> {
+        self.platform.get_teams().await
+    }
+
+    async fn get_team(&self, team_id: &str) -> Result<Team> {
+        self.platform.get_team(team_id).await
+    }
+
+    async fn set_status(&self, status: UserStatus, custom_message: Option<&str>) -> Result<()> {
+        self.platform.set_status(status, custom_message).await
+    }
+
+    async fn get_user_status(&self, user_id: &str) -> Result<UserStatus> {
+        self.platform.get_user_status(user_id).await
+    }
+
+    async fn subscribe_events(&mut self) -> Result<()> {
+        self.platform.subscribe_events().await
+    }
+
+    async fn unsubscribe_events(&mut self) -> Result<()> {
+        self.platform.unsubscribe_events().await
+    }
+
+    fn event_signal(&self) -> Option<Arc<Notify>> {
+        self.platform.event_signal()
+    }
+
+    async fn send_message_as(
+        &self,
+        channel_id: &str,
+        text: &str,
+        username: Option<&str>,
+        icon_url: Option<&str>,
+    ) -> Result<Message> {
+        self.platform
+            .send_message_as(channel_id, text, username, icon_url)
+            .await
+    }
+
+    async fn send_message_with_props(
+        &self,
+        channel_id: &str,
+        text: &str,
+        props: HashMap<String, serde_json::Value>,
+    ) -> Result<Message> {
+        self.platform
+            .send_message_with_props(channel_id, text, props)
+            .await
+    }
+
+    async fn send_message_with_files(
+        &self,
+        channel_id: &str,
+        text: &str,
+        file_ids: Vec<String>,
+        props: HashMap<String, serde_json::Value>,
+    ) -> Result<Message> {
+        self.platform
+            .send_message_with_files(channel_id, text, file_ids, props)
+            .await
+    }
+
+    async fn send_code_snippet(
+        &self,
+        channel_id: &str,
+        language: Option<&str>,
+        code: &str,
+        filename: Option<&str>,
+    ) -> Result<Message> {
+        self.platform
+            .send_code_snippet(channel_id, language, code, filename)
+            .await
+    }
+
+    async fn message_permalink(&self, message: &Message) -> Result<String> {
+        self.platform.message_permalink(message).await
+    }
+
+    async fn quote_message(&self, target_id: &str, comment: &str) -> Result<Message> {
+        self.platform.quote_message(target_id, comment).await
+    }
+
+    async fn execute_command(
+        &self,
+        channel_id: &str,
+        command: &str,
+    ) -> Result<crate::types::CommandResponse> {
+        self.platform.execute_command(channel_id, command).await
+    }
+
+    async fn autocomplete_commands(
+        &self,
+        team_id: Option<&str>,
+        prefix: &str,
+        limit: usize,
+    ) -> Result<Vec<crate::types::SlashCommand>> {
+        self.platform
+            .autocomplete_commands(team_id, prefix, limit)
+            .await
+    }
+
+    async fn create_poll(
+        &self,
+        channel_id: &str,
+        question: &str,
+        options: &[String],
+        settings: &crate::types::PollSettings,
+    ) -> Result<crate::types::Poll> {
+        self.platform
+            .create_poll(channel_id, question, options, settings)
+            .await
+    }
+
+    async fn get_poll(&self, poll_id: &str) -> Result<crate::types::Poll> {
+        self.platform.get_poll(poll_id).await
+    }
+
+    async fn vote_poll(&self, poll_id: &str, option_index: usize) -> Result<()> {
+        self.platform.vote_poll(poll_id, option_index).await
+    }
+
+    async fn close_poll(&self, poll_id: &str) -> Result<crate::types::Poll> {
+        self.platform.close_poll(poll_id).await
+    }
+
+    async fn update_message(&self, message_id: &str, new_text: &str) -> Result<Message> {
+        self.platform.update_message(message_id, new_text).await
+    }
+
+    async fn delete_message(&self, message_id: &str) -> Result<()> {
+        self.platform.delete_message(message_id).await
+    }
+
+    async fn get_message(&self, message_id: &str) -> Result<Message> {
+        self.platform.get_message(message_id).await
+    }
+
+    async fn search_messages(&self, query: &str, limit: usize) -> Result<Vec<Message>> {
+        self.platform.search_messages(query, limit).await
+    }
+
+    async fn search_messages_query(
+        &self,
+        query: &crate::types::SearchQuery,
+        limit: usize,
+    ) -> Result<Vec<Message>> {
+        self.platform.search_messages_query(query, limit).await
+    }
+
+    async fn get_messages_at_date(
+        &self,
+        channel_id: &str,
+        date: chrono::DateTime<chrono::Utc>,
+        limit: usize,
+    ) -> Result<Vec<Message>> {
+        self.platform
+            .get_messages_at_date(channel_id, date, limit)
+            .await
+    }
+
+    async fn get_messages_before(
+        &self,
+        channel_id: &str,
+        before_id: &str,
+        limit: usize,
+    ) -> Result<Vec<Message>> {
+        self.platform
+            .get_messages_before(channel_id, before_id, limit)
+            .await
+    }
+
+    async fn get_messages_after(
+        &self,
+        channel_id: &str,
+        after_id: &str,
+        limit: usize,
+    ) -> Result<Vec<Message>> {
+        self.platform
+            .get_messages_after(channel_id, after_id, limit)
+            .await
+    }
+
+    async fn get_messages_page(
+        &self,
+        channel_id: &str,
+        limit: usize,
+    ) -> Result<crate::types::Page<Message>> {
+        self.platform.get_messages_page(channel_id, limit).await
+    }
+
+    async fn next_page(
+        &self,
+        cursor: &crate::types::Cursor,
+    ) -> Result<crate::types::Page<serde_json::Value>> {
+        self.platform.next_page(cursor).await
+    }
+
+    async fn add_reaction(&self, message_id: &str, emoji: &str) -> Result<()> {
+        self.platform.add_reaction(message_id, emoji).await
+    }
+
+    async fn remove_reaction(&self, message_id: &str, emoji: &str) -> Result<()> {
+        self.platform.remove_reaction(message_id, emoji).await
+    }
+
+    async fn get_reactions(&self, message_id: &str) -> Result<Vec<crate::types::Reaction>> {
+        self.platform.get_reactions(message_id).await
+    }
+
+    async fn pin_post(&self, message_id: &str) -> Result<()> {
+        self.platform.pin_post(message_id).await
+    }
+
+    async fn set_message_reminder(
+        &self,
+        message_id: &str,
+        due_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<()> {
+        self.platform.set_message_reminder(message_id, due_at).await
+    }
+
+    async fn unpin_post(&self, message_id: &str) -> Result<()> {
+        self.platform.unpin_post(message_id).await
+    }
+
+    async fn get_pinned_posts(&self, channel_id: &str) -> Result<Vec<Message>> {
+        self.platform.get_pinned_posts(channel_id).await
+    }
+
+    async fn flag_post(&self, message_id: &str) -> Result<()> {
+        self.platform.flag_post(message_id).await
+    }
+
+    async fn unflag_post(&self, message_id: &str) -> Result<()> {
+        self.platform.unflag_post(message_id).await
+    }
+
+    async fn get_flagged_posts(
+        &self,
+        page: u32,
+        per_page: u32,
+    ) -> Result<crate::types::Page<Message>> {
+        self.platform.get_flagged_posts(page, per_page).await
+    }
+
+    async fn get_emojis(&self, page: u32, per_page: u32) -> Result<Vec<crate::types::Emoji>> {
+        self.platform.get_emojis(page, per_page).await
+    }
+
+    async fn get_emojis_page(
+        &self,
+        per_page: u32,
+    ) -> Result<crate::types::Page<crate::types::Emoji>> {
+        self.platform.get_emojis_page(per_page).await
+    }
+
+    async fn get_emoji_image(&self, name_or_id: &str) -> Result<Vec<u8>> {
+        self.platform.get_emoji_image(name_or_id).await
+    }
+
+    async fn create_emoji(&self, name: &str, image: Vec<u8>) -> Result<crate::types::Emoji> {
+        self.platform.create_emoji(name, image).await
+    }
+
+    async fn delete_emoji(&self, emoji_id: &str) -> Result<()> {
+        self.platform.delete_emoji(emoji_id).await
+    }
+
+    async fn autocomplete_emojis(
+        &self,
+        prefix: &str,
+        limit: usize,
+    ) -> Result<Vec<crate::types::Emoji>> {
+        self.platform.autocomplete_emojis(prefix, limit).await
+    }
+
+    async fn get_channel_by_name(&self, team_id: &str, channel_name: &str) -> Result<Channel> {
+        self.platform
+            .get_channel_by_name(team_id, channel_name)
+            .await
+    }
+
+    async fn create_group_channel(&self, user_ids: Vec<String>) -> Result<Channel> {
+        self.platform.create_group_channel(user_ids).await
+    }
+
+    async fn add_channel_member(&self, channel_id: &str, user_id: &str) -> Result<()> {
+        self.platform.add_channel_member(channel_id, user_id).await
+    }
+
+    async fn remove_channel_member(&self, channel_id: &str, user_id: &str) -> Result<()> {
+        self.platform
+            .remove_channel_member(channel_id, user_id)
+            .await
+    }
+
+    async fn get_channel_member_roles(
+        &self,
+        channel_id: &str,
+        user_id: &str,
+    ) -> Result<crate::types::ChannelMemberRoles> {
+        self.platform
+            .get_channel_member_roles(channel_id, user_id)
+            .await
+    }
+
+    async fn set_channel_admin(
+        &self,
+        channel_id: &str,
+        user_id: &str,
+        is_admin: bool,
+    ) -> Result<()> {
+        self.platform
+            .set_channel_admin(channel_id, user_id, is_admin)
+            .await
+    }
+
+    async fn set_channel_privacy(&self, channel_id: &str, is_private: bool) -> Result<Channel> {
+        self.platform
+            .set_channel_privacy(channel_id, is_private)
+            .await
+    }
+
+    async fn get_user_by_username(&self, username: &str) -> Result<User> {
+        self.platform.get_user_by_username(username).await
+    }
+
+    async fn get_user_by_email(&self, email: &str) -> Result<User> {
+        self.platform.get_user_by_email(email).await
+    }
+
+    async fn get_users_by_ids(&self, user_ids: Vec<String>) -> Result<Vec<User>> {
+        self.platform.get_users_by_ids(user_ids).await
+    }
+
+    async fn get_user_avatar(&self, user_id: &str) -> Result<Vec<u8>> {
+        self.platform.get_user_avatar(user_id).await
+    }
+
+    async fn prefetch_channel_profiles(&self, channel_ids: Vec<String>) -> Result<()> {
+        self.platform.prefetch_channel_profiles(channel_ids).await
+    }
+
+    async fn set_custom_status(
+        &self,
+        emoji: Option<&str>,
+        text: &str,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        self.platform
+            .set_custom_status(emoji, text, expires_at)
+            .await
+    }
+
+    async fn remove_custom_status(&self) -> Result<()> {
+        self.platform.remove_custom_status().await
+    }
+
+    async fn get_users_status(
+        &self,
+        user_ids: Vec<String>,
+    ) -> Result<std::collections::HashMap<String, UserStatus>> {
+        self.platform.get_users_status(user_ids).await
+    }
+
+    async fn get_typing_users(&self, channel_id: &str) -> Result<Vec<String>> {
+        self.platform.get_typing_users(channel_id).await
+    }
+
+    async fn request_all_statuses(&self) -> Result<i64> {
+        self.platform.request_all_statuses().await
+    }
+
+    async fn request_users_statuses(&self, user_ids: Vec<String>) -> Result<i64> {
+        self.platform.request_users_statuses(user_ids).await
+    }
+
+    async fn send_typing_indicator(&self, channel_id: &str, parent_id: Option<&str>) -> Result<()> {
+        self.platform
+            .send_typing_indicator(channel_id, parent_id)
+            .await
+    }
+
+    async fn get_team_by_name(&self, team_name: &str) -> Result<Team> {
+        self.platform.get_team_by_name(team_name).await
+    }
+
+    async fn set_team_id(&self, team_id: Option<String>) -> Result<()> {
+        self.platform.set_team_id(team_id).await
+    }
+
+    async fn set_circuit_breaker_config(
+        &self,
+        config: crate::types::CircuitBreakerConfig,
+    ) -> Result<()> {
+        self.platform.set_circuit_breaker_config(config).await
+    }
+
+    async fn get_circuit_state(&self) -> Result<crate::types::CircuitState> {
+        self.platform.get_circuit_state().await
+    }
+
+    async fn configure_cache(&self, config: CacheConfig) -> Result<()> {
+        self.platform.configure_cache(config).await
+    }
+
+    async fn get_cache_stats(&self) -> Result<Vec<crate::types::CacheStats>> {
+        self.platform.get_cache_stats().await
+    }
+
+    async fn set_event_filter(&self, filter: EventFilter) -> Result<()> {
+        self.platform.set_event_filter(filter).await
+    }
+
+    async fn connection_health(&self) -> Result<crate::types::ConnectionHealth> {
+        self.platform.connection_health().await
+    }
+
+    async fn get_request_stats(&self) -> Result<crate::types::RequestStats> {
+        self.platform.get_request_stats().await
+    }
+
+    async fn upload_file(&self, channel_id: &str, file_path: &std::path::Path) -> Result<String> {
+        self.platform.upload_file(channel_id, file_path).await
+    }
+
+    async fn upload_file_with_progress(
+        &self,
+        channel_id: &str,
+        file_path: &std::path::Path,
+        progress: &mut ProgressSink<'_>,
+    ) -> Result<String> {
+        self.platform
+            .upload_file_with_progress(channel_id, file_path, progress)
+            .await
+    }
+
+    async fn upload_file_data(
+        &self,
+        channel_id: &str,
+        filename: &str,
+        data: Vec<u8>,
+    ) -> Result<String> {
+        self.platform
+            .upload_file_data(channel_id, filename, data)
+            .await
+    }
+
+    async fn download_file(&self, file_id: &str) -> Result<Vec<u8>> {
+        self.platform.download_file(file_id).await
+    }
+
+    async fn download_file_to_path(&self, file_id: &str, path: Option<&Path>) -> Result<PathBuf> {
+        self.platform.download_file_to_path(file_id, path).await
+    }
+
+    async fn download_file_streaming(
+        &self,
+        file_id: &str,
+        sink: &mut ChunkSink<'_>,
+    ) -> Result<u64> {
+        self.platform.download_file_streaming(file_id, sink).await
+    }
+
+    async fn get_file_metadata(&self, file_id: &str) -> Result<crate::types::Attachment> {
+        self.platform.get_file_metadata(file_id).await
+    }
+
+    async fn get_file_thumbnail(&self, file_id: &str) -> Result<Vec<u8>> {
+        self.platform.get_file_thumbnail(file_id).await
+    }
+
+    async fn get_file_preview(&self, file_id: &str) -> Result<Vec<u8>> {
+        self.platform.get_file_preview(file_id).await
+    }
+
+    async fn get_file_link(&self, file_id: &str) -> Result<String> {
+        self.platform.get_file_link(file_id).await
+    }
+
+    async fn get_thread(&self, post_id: &str) -> Result<Vec<Message>> {
+        self.platform.get_thread(post_id).await
+    }
+
+    async fn follow_thread(&self, thread_id: &str) -> Result<()> {
+        self.platform.follow_thread(thread_id).await
+    }
+
+    async fn unfollow_thread(&self, thread_id: &str) -> Result<()> {
+        self.platform.unfollow_thread(thread_id).await
+    }
+
+    async fn mark_thread_read(&self, thread_id: &str) -> Result<()> {
+        self.platform.mark_thread_read(thread_id).await
+    }
+
+    async fn mark_thread_unread(&self, thread_id: &str, post_id: &str) -> Result<()> {
+        self.platform.mark_thread_unread(thread_id, post_id).await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn get_user_threads(
+        &self,
+        user_id: &str,
+        team_id: &str,
+        since: u64,
+        deleted: bool,
+        unread: bool,
+        per_page: usize,
+        page: usize,
+    ) -> Result<String> {
+        self.platform
+            .get_user_threads(user_id, team_id, since, deleted, unread, per_page, page)
+            .await
+    }
+
+    async fn get_user_thread(
+        &self,
+        user_id: &str,
+        team_id: &str,
+        thread_id: &str,
+    ) -> Result<String> {
+        self.platform
+            .get_user_thread(user_id, team_id, thread_id)
+            .await
+    }
+
+    async fn get_followed_threads(
+        &self,
+        team_id: Option<&str>,
+        page: u32,
+        per_page: u32,
+    ) -> Result<crate::types::Page<crate::types::Thread>> {
+        self.platform
+            .get_followed_threads(team_id, page, per_page)
+            .await
+    }
+
+    async fn mark_all_threads_as_read(&self, user_id: &str, team_id: &str) -> Result<()> {
+        self.platform
+            .mark_all_threads_as_read(user_id, team_id)
+            .await
+    }
+
+    async fn search_users(&self, query: &str, limit: usize) -> Result<Vec<User>> {
+        self.platform.search_users(query, limit).await
+    }
+
+    async fn autocomplete_users(
+        &self,
+        channel_id: &str,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<User>> {
+        self.platform
+            .autocomplete_users(channel_id, query, limit)
+            .await
+    }
+
+    async fn search_channels(
+        &self,
+        team_id: Option<&str>,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<Channel>> {
+        self.platform.search_channels(team_id, query, limit).await
+    }
+
+    async fn autocomplete_channels(
+        &self,
+        team_id: Option<&str>,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<Channel>> {
+        self.platform
+            .autocomplete_channels(team_id, query, limit)
+            .await
+    }
+
+    async fn get_user_preferences(&self, user_id: &str) -> Result<String> {
+        self.platform.get_user_preferences(user_id).await
+    }
+
+    async fn set_user_preferences(&self, user_id: &str, preferences_json: &str) -> Result<()> {
+        self.platform
+            .set_user_preferences(user_id, preferences_json)
+            .await
+    }
+
+    async fn get_sidebar_categories(&self) -> Result<Vec<crate::types::SidebarCategory>> {
+        self.platform.get_sidebar_categories().await
+    }
+
+    async fn move_channel_to_category(
+        &self,
+        channel_id: &str,
+        category_id: &str,
+        index: Option<usize>,
+    ) -> Result<()> {
+        self.platform
+            .move_channel_to_category(channel_id, category_id, index)
+            .await
+    }
+
+    async fn mute_channel(&self, channel_id: &str) -> Result<()> {
+        self.platform.mute_channel(channel_id).await
+    }
+
+    async fn unmute_channel(&self, channel_id: &str) -> Result<()> {
+        self.platform.unmute_channel(channel_id).await
+    }
+
+    async fn update_channel_notify_props(
+        &self,
+        channel_id: &str,
+        notify_props_json: &str,
+    ) -> Result<()> {
+        self.platform
+            .update_channel_notify_props(channel_id, notify_props_json)
+            .await
+    }
+
+    async fn view_channel(&self, channel_id: &str, prev_channel_id: Option<&str>) -> Result<()> {
+        self.platform
+            .view_channel(channel_id, prev_channel_id)
+            .await
+    }
+
+    async fn get_channel_unread(&self, channel_id: &str) -> Result<crate::types::ChannelUnread> {
+        self.platform.get_channel_unread(channel_id).await
+    }
+
+    async fn get_channel_activity(
+        &self,
+        channel_id: &str,
+        days: u32,
+    ) -> Result<crate::types::ChannelActivity> {
+        self.platform.get_channel_activity(channel_id, days).await
+    }
+
+    async fn get_window(&self, channel_id: &str) -> Result<Vec<Message>> {
+        self.platform.get_window(channel_id).await
+    }
+
+    async fn get_team_unreads(&self, team_id: &str) -> Result<Vec<crate::types::ChannelUnread>> {
+        self.platform.get_team_unreads(team_id).await
+    }
+
+    async fn get_channel_unreads(&self) -> Result<Vec<crate::types::ChannelUnread>> {
+        self.platform.get_channel_unreads().await
+    }
+
+    async fn mark_channel_read(&self, channel_id: &str) -> Result<()> {
+        self.platform.mark_channel_read(channel_id).await
+    }
+
+    async fn get_all_unreads(&self) -> Result<Vec<crate::types::TeamUnread>> {
+        self.platform.get_all_unreads().await
+    }
+
+    async fn get_unread_posts(
+        &self,
+        channel_id: &str,
+        limit_after: usize,
+        limit_before: usize,
+    ) -> Result<String> {
+        self.platform
+            .get_unread_posts(channel_id, limit_after, limit_before)
+            .await
+    }
+
+    async fn register_oauth_app(
+        &self,
+        settings: &crate::types::OAuthAppSettings,
+    ) -> Result<crate::types::OAuthApp> {
+        self.platform.register_oauth_app(settings).await
+    }
+
+    async fn get_oauth_apps(&self) -> Result<Vec<crate::types::OAuthApp>> {
+        self.platform.get_oauth_apps().await
+    }
+
+    async fn delete_oauth_app(&self, app_id: &str) -> Result<()> {
+        self.platform.delete_oauth_app(app_id).await
+    }
+
+    async fn create_access_token(
+        &self,
+        user_id: &str,
+        description: &str,
+    ) -> Result<crate::types::AccessToken> {
+        self.platform
+            .create_access_token(user_id, description)
+            .await
+    }
+
+    async fn get_access_tokens(&self, user_id: &str) -> Result<Vec<crate::types::AccessToken>> {
+        self.platform.get_access_tokens(user_id).await
+    }
+
+    async fn revoke_access_token(&self, token_id: &str) -> Result<()> {
+        self.platform.revoke_access_token(token_id).await
+    }
+
+    async fn create_bot(&self, settings: &crate::types::BotSettings) -> Result<crate::types::Bot> {
+        self.platform.create_bot(settings).await
+    }
+
+    async fn get_bots(&self, include_disabled: bool) -> Result<Vec<crate::types::Bot>> {
+        self.platform.get_bots(include_disabled).await
+    }
+
+    async fn set_bot_enabled(&self, bot_user_id: &str, enabled: bool) -> Result<crate::types::Bot> {
+        self.platform.set_bot_enabled(bot_user_id, enabled).await
+    }
+
+    async fn assign_bot(&self, bot_user_id: &str, owner_id: &str) -> Result<crate::types::Bot> {
+        self.platform.assign_bot(bot_user_id, owner_id).await
+    }
+
+    async fn convert_user_to_bot(&self, user_id: &str) -> Result<crate::types::Bot> {
+        self.platform.convert_user_to_bot(user_id).await
+    }
+
+    async fn convert_bot_to_user(&self, bot_user_id: &str, password: &str) -> Result<User> {
+        self.platform
+            .convert_bot_to_user(bot_user_id, password)
+            .await
+    }
+}
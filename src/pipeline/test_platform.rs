@@ -0,0 +1,95 @@
+//! A platform for tests of the pipeline and the services built on it
+
+use std::collections::VecDeque;
+
+use async_trait::async_trait;
+
+use crate::error::{Error, Result};
+use crate::platforms::{Platform, PlatformConfig, PlatformEvent};
+use crate::types::user::UserStatus;
+use crate::types::{Channel, ConnectionInfo, Message, PlatformCapabilities, Team, User};
+
+/// A platform that returns queued events, echoes sent messages and supports
+/// nothing else
+pub(crate) struct QueuedPlatform {
+    capabilities: PlatformCapabilities,
+    events: VecDeque<PlatformEvent>,
+}
+
+impl QueuedPlatform {
+    pub(crate) fn new(events: impl IntoIterator<Item = PlatformEvent>) -> Self {
+        Self {
+            capabilities: PlatformCapabilities::new("queued"),
+            events: events.into_iter().collect(),
+        }
+    }
+}
+
+fn unsupported<T>() -> Result<T> {
+    Err(Error::unsupported("Not supported by the queued platform"))
+}
+
+#[async_trait]
+impl Platform for QueuedPlatform {
+    fn capabilities(&self) -> &PlatformCapabilities {
+        &self.capabilities
+    }
+    async fn connect(&mut self, _config: PlatformConfig) -> Result<ConnectionInfo> {
+        unsupported()
+    }
+    async fn disconnect(&mut self) -> Result<()> {
+        Ok(())
+    }
+    fn connection_info(&self) -> Option<&ConnectionInfo> {
+        None
+    }
+    async fn send_message(&self, channel_id: &str, text: &str) -> Result<Message> {
+        Ok(Message::new("sent-1", text, "me", channel_id))
+    }
+    async fn get_channels(&self) -> Result<Vec<Channel>> {
+        unsupported()
+    }
+    async fn get_channel(&self, _channel_id: &str) -> Result<Channel> {
+        unsupported()
+    }
+    async fn get_messages(&self, _channel_id: &str, _limit: usize) -> Result<Vec<Message>> {
+        unsupported()
+    }
+    async fn get_channel_members(&self, _channel_id: &str) -> Result<Vec<User>> {
+        unsupported()
+    }
+    async fn get_user(&self, _user_id: &str) -> Result<User> {
+        unsupported()
+    }
+    async fn get_current_user(&self) -> Result<User> {
+        unsupported()
+    }
+    async fn create_direct_channel(&self, _user_id: &str) -> Result<Channel> {
+        unsupported()
+    }
+    async fn get_teams(&self) -> Result<Vec<Team>> {
+        unsupported()
+    }
+    async fn get_team(&self, _team_id: &str) -> Result<Team> {
+        unsupported()
+    }
+    async fn set_status(&self, _status: UserStatus, _message: Option<&str>) -> Result<()> {
+        unsupported()
+    }
+    async fn get_user_status(&self, _user_id: &str) -> Result<UserStatus> {
+        unsupported()
+    }
+    async fn subscribe_events(&mut self) -> Result<()> {
+        Ok(())
+    }
+    async fn unsubscribe_events(&mut self) -> Result<()> {
+        Ok(())
+    }
+    async fn poll_event(&mut self) -> Result<Option<PlatformEvent>> {
+        Ok(self.events.pop_front())
+    }
+}
+
+pub(crate) fn posted(id: &str) -> PlatformEvent {
+    PlatformEvent::MessagePosted(Message::new(id, "hi", "user-1", "ch-1"))
+}
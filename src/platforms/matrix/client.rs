@@ -0,0 +1,514 @@
+use reqwest::Client;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::sync::RwLock;
+use url::Url;
+
+use crate::error::{Error, ErrorCode, Result};
+
+use super::types::{
+    CreateRoomResponse, JoinedMembersResponse, JoinedRoomsResponse, LoginResponse,
+    MatrixErrorResponse, MessagesResponse, PresenceResponse, ProfileResponse, RoomEvent,
+    SendResponse, SyncResponse, WhoAmIResponse,
+};
+
+/// Matrix client-server API client
+pub struct MatrixClient {
+    /// HTTP client for making requests
+    http_client: Client,
+    /// Base URL of the homeserver (e.g., https://matrix.example.com)
+    homeserver: Url,
+    /// Access token of the current session
+    access_token: RwLock<Option<String>>,
+    /// Fully qualified ID of the logged in user (e.g., @alice:example.com)
+    user_id: RwLock<Option<String>>,
+    /// Counter making transaction IDs unique within this client
+    txn_counter: AtomicU64,
+}
+
+impl MatrixClient {
+    /// Create a new Matrix client
+    ///
+    /// # Arguments
+    /// * `homeserver_url` - The base URL of the homeserver (e.g., "https://matrix.example.com")
+    ///
+    /// # Returns
+    /// A Result containing the client or an Error if the URL is invalid
+    pub fn new(homeserver_url: &str) -> Result<Self> {
+        let homeserver = Url::parse(homeserver_url).map_err(|e| {
+            Error::new(
+                ErrorCode::InvalidArgument,
+                format!("Invalid homeserver URL: {e}"),
+            )
+        })?;
+        if homeserver.cannot_be_a_base() {
+            return Err(Error::invalid_argument(
+                "Invalid homeserver URL: not a base URL",
+            ));
+        }
+
+        let http_client = Client::builder()
+            .timeout(Duration::from_secs(90))
+            .build()
+            .map_err(|e| {
+                Error::new(
+                    ErrorCode::NetworkError,
+                    format!("Failed to create HTTP client: {e}"),
+                )
+            })?;
+
+        Ok(Self {
+            http_client,
+            homeserver,
+            access_token: RwLock::new(None),
+            user_id: RwLock::new(None),
+            txn_counter: AtomicU64::new(0),
+        })
+    }
+
+    /// Get the homeserver base URL
+    pub fn homeserver(&self) -> &Url {
+        &self.homeserver
+    }
+
+    /// Get the access token of the current session
+    pub async fn get_token(&self) -> Option<String> {
+        self.access_token.read().await.clone()
+    }
+
+    /// Get the ID of the logged in user
+    pub async fn get_user_id(&self) -> Option<String> {
+        self.user_id.read().await.clone()
+    }
+
+    /// Get the ID of the logged in user, or an error if not logged in
+    pub async fn require_user_id(&self) -> Result<String> {
+        self.get_user_id().await.ok_or_else(|| {
+            Error::new(
+                ErrorCode::InvalidState,
+                "User ID not set - ensure you're authenticated",
+            )
+        })
+    }
+
+    /// Build a client-server API URL from path segments
+    ///
+    /// Segments are percent-encoded, so room and event IDs can be passed as is.
+    pub fn api_url(&self, segments: &[&str]) -> Url {
+        let mut url = self.homeserver.clone();
+        url.path_segments_mut()
+            .expect("homeserver URL is a base URL")
+            .pop_if_empty()
+            .extend(["_matrix", "client", "v3"])
+            .extend(segments);
+        url
+    }
+
+    /// Generate a transaction ID for an idempotent PUT request
+    fn next_txn_id(&self) -> String {
+        let counter = self.txn_counter.fetch_add(1, Ordering::Relaxed);
+        format!("lc{}.{counter}", chrono::Utc::now().timestamp_millis())
+    }
+
+    async fn authorize(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match self.get_token().await {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+        self.authorize(request).await.send().await.map_err(|e| {
+            let code = if e.is_timeout() {
+                ErrorCode::Timeout
+            } else {
+                ErrorCode::NetworkError
+            };
+            Error::new(code, format!("Request failed: {e}"))
+        })
+    }
+
+    /// Make a GET request to the client-server API
+    pub async fn get(&self, url: Url) -> Result<reqwest::Response> {
+        self.send(self.http_client.get(url)).await
+    }
+
+    /// Make a POST request with a JSON body to the client-server API
+    pub async fn post<T: serde::Serialize>(&self, url: Url, body: &T) -> Result<reqwest::Response> {
+        self.send(self.http_client.post(url).json(body)).await
+    }
+
+    /// Make a PUT request with a JSON body to the client-server API
+    pub async fn put<T: serde::Serialize>(&self, url: Url, body: &T) -> Result<reqwest::Response> {
+        self.send(self.http_client.put(url).json(body)).await
+    }
+
+    /// Handle an API response, parsing the body or the Matrix error
+    pub async fn handle_response<T: serde::de::DeserializeOwned>(
+        &self,
+        response: reqwest::Response,
+    ) -> Result<T> {
+        let status = response.status();
+        if status.is_success() {
+            return response.json::<T>().await.map_err(|e| {
+                Error::new(ErrorCode::Unknown, format!("Failed to parse response: {e}"))
+            });
+        }
+
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+
+        let error = match serde_json::from_str::<MatrixErrorResponse>(&error_text) {
+            Ok(matrix_error) => Error::new(
+                Self::map_errcode(&matrix_error.errcode, status.as_u16()),
+                format!("{}: {}", matrix_error.errcode, matrix_error.error),
+            ),
+            Err(_) => Error::new(
+                Self::map_errcode("", status.as_u16()),
+                format!("API request failed with status {status}: {error_text}"),
+            ),
+        };
+        Err(error.with_http_status(status.as_u16()))
+    }
+
+    /// Map a Matrix error code (falling back to the HTTP status) to an ErrorCode
+    fn map_errcode(errcode: &str, status: u16) -> ErrorCode {
+        match errcode {
+            "M_FORBIDDEN" => ErrorCode::PermissionDenied,
+            "M_UNKNOWN_TOKEN" | "M_MISSING_TOKEN" | "M_USER_DEACTIVATED" => {
+                ErrorCode::AuthenticationFailed
+            }
+            "M_NOT_FOUND" => ErrorCode::NotFound,
+            "M_LIMIT_EXCEEDED" => ErrorCode::RateLimited,
+            "M_TOO_LARGE" => ErrorCode::FileTooLarge,
+            "M_BAD_JSON" | "M_NOT_JSON" | "M_INVALID_PARAM" | "M_MISSING_PARAM" => {
+                ErrorCode::InvalidArgument
+            }
+            "M_UNRECOGNIZED" => ErrorCode::Unsupported,
+            _ => match status {
+                401 => ErrorCode::AuthenticationFailed,
+                403 => ErrorCode::PermissionDenied,
+                404 => ErrorCode::NotFound,
+                413 => ErrorCode::FileTooLarge,
+                429 => ErrorCode::RateLimited,
+                500..=599 => ErrorCode::NetworkError,
+                _ => ErrorCode::Unknown,
+            },
+        }
+    }
+
+    // ========================================================================
+    // Authentication
+    // ========================================================================
+
+    /// Log in with a user ID (or localpart) and password
+    ///
+    /// # API Endpoint
+    /// POST /_matrix/client/v3/login
+    pub async fn login(&self, user: &str, password: &str) -> Result<LoginResponse> {
+        let body = serde_json::json!({
+            "type": "m.login.password",
+            "identifier": { "type": "m.id.user", "user": user },
+            "password": password,
+            "initial_device_display_name": "libcommunicator",
+        });
+        let response = self.post(self.api_url(&["login"]), &body).await?;
+        let login: LoginResponse = self.handle_response(response).await.map_err(|e| {
+            if e.code == ErrorCode::PermissionDenied {
+                Error::new(ErrorCode::AuthenticationFailed, e.message)
+            } else {
+                e
+            }
+        })?;
+
+        *self.access_token.write().await = Some(login.access_token.clone());
+        *self.user_id.write().await = Some(login.user_id.clone());
+        Ok(login)
+    }
+
+    /// Use an existing access token, verifying it with the homeserver
+    ///
+    /// # API Endpoint
+    /// GET /_matrix/client/v3/account/whoami
+    pub async fn login_with_token(&self, token: &str) -> Result<WhoAmIResponse> {
+        *self.access_token.write().await = Some(token.to_string());
+        let result = async {
+            let response = self.get(self.api_url(&["account", "whoami"])).await?;
+            self.handle_response::<WhoAmIResponse>(response).await
+        }
+        .await;
+
+        match result {
+            Ok(whoami) => {
+                *self.user_id.write().await = Some(whoami.user_id.clone());
+                Ok(whoami)
+            }
+            Err(e) => {
+                *self.access_token.write().await = None;
+                Err(e)
+            }
+        }
+    }
+
+    /// Invalidate the access token and forget the session
+    ///
+    /// # API Endpoint
+    /// POST /_matrix/client/v3/logout
+    pub async fn logout(&self) -> Result<()> {
+        if self.get_token().await.is_some() {
+            let response = self
+                .post(self.api_url(&["logout"]), &serde_json::json!({}))
+                .await?;
+            self.handle_response::<serde_json::Value>(response).await?;
+        }
+        *self.access_token.write().await = None;
+        *self.user_id.write().await = None;
+        Ok(())
+    }
+
+    // ========================================================================
+    // Rooms
+    // ========================================================================
+
+    /// Get the IDs of all rooms the user has joined
+    ///
+    /// # API Endpoint
+    /// GET /_matrix/client/v3/joined_rooms
+    pub async fn get_joined_rooms(&self) -> Result<Vec<String>> {
+        let response = self.get(self.api_url(&["joined_rooms"])).await?;
+        let rooms: JoinedRoomsResponse = self.handle_response(response).await?;
+        Ok(rooms.joined_rooms)
+    }
+
+    /// Get the current state events of a room
+    ///
+    /// # API Endpoint
+    /// GET /_matrix/client/v3/rooms/{roomId}/state
+    pub async fn get_room_state(&self, room_id: &str) -> Result<Vec<RoomEvent>> {
+        let response = self.get(self.api_url(&["rooms", room_id, "state"])).await?;
+        self.handle_response(response).await
+    }
+
+    /// Get the joined members of a room
+    ///
+    /// # API Endpoint
+    /// GET /_matrix/client/v3/rooms/{roomId}/joined_members
+    pub async fn get_joined_members(&self, room_id: &str) -> Result<JoinedMembersResponse> {
+        let response = self
+            .get(self.api_url(&["rooms", room_id, "joined_members"]))
+            .await?;
+        self.handle_response(response).await
+    }
+
+    /// Get the user's direct chats, keyed by the other user's ID
+    ///
+    /// # API Endpoint
+    /// GET /_matrix/client/v3/user/{userId}/account_data/m.direct
+    pub async fn get_direct_rooms(&self) -> Result<std::collections::HashMap<String, Vec<String>>> {
+        let user_id = self.require_user_id().await?;
+        let response = self
+            .get(self.api_url(&["user", &user_id, "account_data", "m.direct"]))
+            .await?;
+        match self.handle_response(response).await {
+            Ok(direct) => Ok(direct),
+            // Users without direct chats have no m.direct account data
+            Err(e) if e.code == ErrorCode::NotFound => Ok(Default::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Replace the user's direct chats
+    ///
+    /// # API Endpoint
+    /// PUT /_matrix/client/v3/user/{userId}/account_data/m.direct
+    pub async fn set_direct_rooms(
+        &self,
+        direct: &std::collections::HashMap<String, Vec<String>>,
+    ) -> Result<()> {
+        let user_id = self.require_user_id().await?;
+        let response = self
+            .put(
+                self.api_url(&["user", &user_id, "account_data", "m.direct"]),
+                direct,
+            )
+            .await?;
+        self.handle_response::<serde_json::Value>(response).await?;
+        Ok(())
+    }
+
+    /// Create a room
+    ///
+    /// # Arguments
+    /// * `body` - The createRoom request (name, preset, invite, is_direct, ...)
+    ///
+    /// # API Endpoint
+    /// POST /_matrix/client/v3/createRoom
+    pub async fn create_room(&self, body: &serde_json::Value) -> Result<String> {
+        let response = self.post(self.api_url(&["createRoom"]), body).await?;
+        let created: CreateRoomResponse = self.handle_response(response).await?;
+        Ok(created.room_id)
+    }
+
+    // ========================================================================
+    // Messages
+    // ========================================================================
+
+    /// Send a message event to a room
+    ///
+    /// # Arguments
+    /// * `room_id` - The room to send to
+    /// * `content` - The m.room.message content
+    ///
+    /// # Returns
+    /// The ID of the sent event
+    ///
+    /// # API Endpoint
+    /// PUT /_matrix/client/v3/rooms/{roomId}/send/m.room.message/{txnId}
+    pub async fn send_message(&self, room_id: &str, content: &serde_json::Value) -> Result<String> {
+        let txn_id = self.next_txn_id();
+        let url = self.api_url(&["rooms", room_id, "send", "m.room.message", &txn_id]);
+        let response = self.put(url, content).await?;
+        let sent: SendResponse = self.handle_response(response).await?;
+        Ok(sent.event_id)
+    }
+
+    /// Get the most recent events of a room, newest first
+    ///
+    /// # API Endpoint
+    /// GET /_matrix/client/v3/rooms/{roomId}/messages?dir=b
+    pub async fn get_latest_events(&self, room_id: &str, limit: usize) -> Result<MessagesResponse> {
+        let mut url = self.api_url(&["rooms", room_id, "messages"]);
+        url.query_pairs_mut()
+            .append_pair("dir", "b")
+            .append_pair("limit", &limit.to_string());
+        let response = self.get(url).await?;
+        self.handle_response(response).await
+    }
+
+    /// Notify a room that the user is typing
+    ///
+    /// # API Endpoint
+    /// PUT /_matrix/client/v3/rooms/{roomId}/typing/{userId}
+    pub async fn send_typing(&self, room_id: &str, timeout_ms: u64) -> Result<()> {
+        let user_id = self.require_user_id().await?;
+        let url = self.api_url(&["rooms", room_id, "typing", &user_id]);
+        let body = serde_json::json!({ "typing": true, "timeout": timeout_ms });
+        let response = self.put(url, &body).await?;
+        self.handle_response::<serde_json::Value>(response).await?;
+        Ok(())
+    }
+
+    // ========================================================================
+    // Users and presence
+    // ========================================================================
+
+    /// Get a user's profile
+    ///
+    /// # API Endpoint
+    /// GET /_matrix/client/v3/profile/{userId}
+    pub async fn get_profile(&self, user_id: &str) -> Result<ProfileResponse> {
+        let response = self.get(self.api_url(&["profile", user_id])).await?;
+        self.handle_response(response).await
+    }
+
+    /// Get a user's presence
+    ///
+    /// # API Endpoint
+    /// GET /_matrix/client/v3/presence/{userId}/status
+    pub async fn get_presence(&self, user_id: &str) -> Result<PresenceResponse> {
+        let response = self
+            .get(self.api_url(&["presence", user_id, "status"]))
+            .await?;
+        self.handle_response(response).await
+    }
+
+    /// Set the user's presence
+    ///
+    /// # API Endpoint
+    /// PUT /_matrix/client/v3/presence/{userId}/status
+    pub async fn set_presence(&self, presence: &str, status_msg: Option<&str>) -> Result<()> {
+        let user_id = self.require_user_id().await?;
+        let mut body = serde_json::json!({ "presence": presence });
+        if let Some(status_msg) = status_msg {
+            body["status_msg"] = serde_json::json!(status_msg);
+        }
+        let response = self
+            .put(self.api_url(&["presence", &user_id, "status"]), &body)
+            .await?;
+        self.handle_response::<serde_json::Value>(response).await?;
+        Ok(())
+    }
+
+    // ========================================================================
+    // Sync
+    // ========================================================================
+
+    /// Fetch updates since a sync token
+    ///
+    /// # Arguments
+    /// * `since` - The `next_batch` token of the previous sync (None for the first sync)
+    /// * `timeout_ms` - How long the server may wait for new events
+    /// * `filter` - Optional inline filter definition
+    ///
+    /// # API Endpoint
+    /// GET /_matrix/client/v3/sync
+    pub async fn sync(
+        &self,
+        since: Option<&str>,
+        timeout_ms: u64,
+        filter: Option<&str>,
+    ) -> Result<SyncResponse> {
+        let mut url = self.api_url(&["sync"]);
+        {
+            let mut query = url.query_pairs_mut();
+            query.append_pair("timeout", &timeout_ms.to_string());
+            if let Some(since) = since {
+                query.append_pair("since", since);
+            }
+            if let Some(filter) = filter {
+                query.append_pair("filter", filter);
+            }
+        }
+        let response = self.get(url).await?;
+        self.handle_response(response).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_url_encodes_segments() {
+        let client = MatrixClient::new("https://matrix.example.com/").unwrap();
+        let url = client.api_url(&["rooms", "!abc:example.com", "state"]);
+        assert_eq!(
+            url.as_str(),
+            "https://matrix.example.com/_matrix/client/v3/rooms/!abc:example.com/state"
+        );
+        let url = client.api_url(&["profile", "@alice/bob:example.com"]);
+        assert!(url.as_str().ends_with("/profile/@alice%2Fbob:example.com"));
+    }
+
+    #[test]
+    fn test_errcode_mapping() {
+        assert_eq!(
+            MatrixClient::map_errcode("M_FORBIDDEN", 403),
+            ErrorCode::PermissionDenied
+        );
+        assert_eq!(
+            MatrixClient::map_errcode("M_UNKNOWN_TOKEN", 401),
+            ErrorCode::AuthenticationFailed
+        );
+        assert_eq!(
+            MatrixClient::map_errcode("M_LIMIT_EXCEEDED", 429),
+            ErrorCode::RateLimited
+        );
+        assert_eq!(
+            MatrixClient::map_errcode("M_UNKNOWN", 502),
+            ErrorCode::NetworkError
+        );
+    }
+}
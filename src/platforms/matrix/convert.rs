@@ -0,0 +1,636 @@
+//! Conversions from Matrix events to the platform-agnostic types
+
+use chrono::{DateTime, Utc};
+use url::Url;
+
+use crate::platforms::PlatformEvent;
+use crate::types::user::UserStatus;
+use crate::types::{Attachment, Channel, ChannelType, Message};
+
+use super::types::{BasicEvent, RoomEvent, SyncResponse};
+
+/// Context needed to convert Matrix events
+#[derive(Debug, Clone)]
+pub struct ConversionContext {
+    /// Base URL of the homeserver, for media download URLs
+    pub homeserver: Url,
+    /// ID of the logged in user
+    pub user_id: String,
+}
+
+impl ConversionContext {
+    /// Create a new conversion context
+    pub fn new(homeserver: Url, user_id: impl Into<String>) -> Self {
+        Self {
+            homeserver,
+            user_id: user_id.into(),
+        }
+    }
+
+    /// Server name of the logged in user
+    fn server_name(&self) -> &str {
+        server_name(&self.user_id).unwrap_or_default()
+    }
+}
+
+/// Server name part of a Matrix ID (e.g. "example.com" for "@alice:example.com")
+pub fn server_name(id: &str) -> Option<&str> {
+    id.split_once(':').map(|(_, server)| server)
+}
+
+/// Local part of a user ID (e.g. "alice" for "@alice:example.com")
+pub fn localpart(user_id: &str) -> &str {
+    let id = user_id.strip_prefix('@').unwrap_or(user_id);
+    id.split_once(':').map(|(local, _)| local).unwrap_or(id)
+}
+
+/// Convert an `mxc://` content URI to a download URL on the homeserver
+pub fn mxc_to_http(homeserver: &Url, mxc: &str) -> Option<String> {
+    let (server, media_id) = mxc.strip_prefix("mxc://")?.split_once('/')?;
+    let mut url = homeserver.clone();
+    url.path_segments_mut()
+        .ok()?
+        .pop_if_empty()
+        .extend(["_matrix", "media", "v3", "download", server, media_id]);
+    Some(url.to_string())
+}
+
+/// Map a Matrix presence state to a UserStatus
+pub fn presence_to_status(presence: &str) -> UserStatus {
+    match presence {
+        "online" => UserStatus::Online,
+        "unavailable" => UserStatus::Away,
+        "offline" => UserStatus::Offline,
+        _ => UserStatus::Unknown,
+    }
+}
+
+/// Map a UserStatus to a Matrix presence state
+///
+/// Matrix has no do-not-disturb state; it is reported as unavailable.
+pub fn status_to_presence(status: UserStatus) -> &'static str {
+    match status {
+        UserStatus::Online | UserStatus::Unknown => "online",
+        UserStatus::Away | UserStatus::DoNotDisturb => "unavailable",
+        UserStatus::Offline => "offline",
+    }
+}
+
+fn timestamp_to_datetime(timestamp: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp_millis(timestamp).unwrap_or_else(Utc::now)
+}
+
+/// Relation of an event to another event (`content["m.relates_to"]`)
+fn relation(content: &serde_json::Value) -> Option<(&str, &str)> {
+    let relates_to = content.get("m.relates_to")?;
+    Some((
+        relates_to.get("rel_type")?.as_str()?,
+        relates_to.get("event_id")?.as_str()?,
+    ))
+}
+
+/// Convert an m.room.message event to a Message
+///
+/// Edits (m.replace relations) are not messages of their own and return None;
+/// use `event_to_edit` for them. If the server bundled the latest edit with
+/// the event, the edited text is used.
+pub fn event_to_message(
+    room_id: &str,
+    event: &RoomEvent,
+    ctx: &ConversionContext,
+) -> Option<Message> {
+    if event.event_type != "m.room.message" {
+        return None;
+    }
+    let content = &event.content;
+    // Redacted messages have no body
+    let body = content.get("body")?.as_str()?;
+    let relation = relation(content);
+    if matches!(relation, Some(("m.replace", _))) {
+        return None;
+    }
+
+    let mut message = Message::new(&event.event_id, body, &event.sender, room_id);
+    message.created_at = timestamp_to_datetime(event.origin_server_ts);
+
+    // Latest edit bundled by the server
+    if let Some(replacement) = event
+        .unsigned
+        .as_ref()
+        .and_then(|unsigned| unsigned.get("m.relations"))
+        .and_then(|relations| relations.get("m.replace"))
+    {
+        if let Some(new_body) = replacement
+            .pointer("/content/m.new_content/body")
+            .and_then(|body| body.as_str())
+        {
+            message.text = new_body.to_string();
+        }
+        if let Some(ts) = replacement
+            .get("origin_server_ts")
+            .and_then(|ts| ts.as_i64())
+        {
+            message.edited_at = Some(timestamp_to_datetime(ts));
+        }
+    }
+
+    let msgtype = content
+        .get("msgtype")
+        .and_then(|t| t.as_str())
+        .unwrap_or("m.text");
+    if let Some(attachment) = content_attachment(&event.event_id, content, ctx) {
+        message = message.with_attachment(attachment);
+    }
+
+    let sender_server = server_name(&event.sender).unwrap_or_default();
+    if !sender_server.is_empty() && sender_server != ctx.server_name() {
+        message = message.with_origin(sender_server);
+    }
+
+    let mut metadata = serde_json::json!({ "msgtype": msgtype });
+    if let Some(formatted) = content.get("formatted_body") {
+        metadata["formatted_body"] = formatted.clone();
+    }
+    match relation {
+        Some(("m.thread", root_id)) => metadata["root_id"] = serde_json::json!(root_id),
+        _ => {
+            if let Some(reply_to) = content.pointer("/m.relates_to/m.in_reply_to/event_id") {
+                metadata["in_reply_to"] = reply_to.clone();
+            }
+        }
+    }
+
+    Some(message.with_metadata(metadata))
+}
+
+/// Convert an edit (m.replace relation) to the edited Message
+pub fn event_to_edit(room_id: &str, event: &RoomEvent) -> Option<Message> {
+    if event.event_type != "m.room.message" {
+        return None;
+    }
+    let Some(("m.replace", target_id)) = relation(&event.content) else {
+        return None;
+    };
+    let new_body = event
+        .content
+        .pointer("/m.new_content/body")
+        .and_then(|body| body.as_str())?;
+
+    let mut message = Message::new(target_id, new_body, &event.sender, room_id);
+    message.edited_at = Some(timestamp_to_datetime(event.origin_server_ts));
+    Some(message)
+}
+
+/// Attachment of a media message (m.image, m.file, m.audio, m.video)
+fn content_attachment(
+    event_id: &str,
+    content: &serde_json::Value,
+    ctx: &ConversionContext,
+) -> Option<Attachment> {
+    let url = mxc_to_http(&ctx.homeserver, content.get("url")?.as_str()?)?;
+    let info = content.get("info");
+    let filename = content
+        .get("filename")
+        .or_else(|| content.get("body"))
+        .and_then(|name| name.as_str())
+        .unwrap_or_default();
+    let mime_type = info
+        .and_then(|info| info.get("mimetype"))
+        .and_then(|mime| mime.as_str())
+        .unwrap_or("application/octet-stream");
+    let size = info
+        .and_then(|info| info.get("size"))
+        .and_then(|size| size.as_u64())
+        .unwrap_or(0);
+
+    let mut attachment = Attachment::new(event_id, filename, mime_type, size, url);
+    if let Some(thumbnail) = info
+        .and_then(|info| info.get("thumbnail_url"))
+        .and_then(|thumbnail| thumbnail.as_str())
+        .and_then(|mxc| mxc_to_http(&ctx.homeserver, mxc))
+    {
+        attachment = attachment.with_thumbnail(thumbnail);
+    }
+    Some(attachment)
+}
+
+/// Convert the state of a room to a Channel
+///
+/// # Arguments
+/// * `room_id` - The room ID
+/// * `state` - The current state events of the room
+/// * `is_direct` - Whether the room is listed in the user's m.direct account data
+/// * `ctx` - Conversion context
+pub fn room_to_channel(
+    room_id: &str,
+    state: &[RoomEvent],
+    is_direct: bool,
+    ctx: &ConversionContext,
+) -> Channel {
+    let state_content = |event_type: &str| {
+        state
+            .iter()
+            .find(|event| event.event_type == event_type && event.state_key.as_deref() == Some(""))
+            .map(|event| &event.content)
+    };
+    let content_str = |event_type: &str, key: &str| {
+        state_content(event_type)
+            .and_then(|content| content.get(key))
+            .and_then(|value| value.as_str())
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+
+    let members: Vec<&RoomEvent> = state
+        .iter()
+        .filter(|event| {
+            event.event_type == "m.room.member"
+                && event.content.get("membership").and_then(|m| m.as_str()) == Some("join")
+        })
+        .collect();
+    let member_ids: Vec<String> = members
+        .iter()
+        .filter_map(|event| event.state_key.clone())
+        .collect();
+
+    let channel_type = if is_direct {
+        if member_ids.len() <= 2 {
+            ChannelType::DirectMessage
+        } else {
+            ChannelType::GroupMessage
+        }
+    } else if content_str("m.room.join_rules", "join_rule").as_deref() == Some("public") {
+        ChannelType::Public
+    } else {
+        ChannelType::Private
+    };
+
+    let alias = content_str("m.room.canonical_alias", "alias");
+    let display_name = content_str("m.room.name", "name")
+        .or_else(|| alias.clone())
+        .or_else(|| {
+            // Name unnamed rooms after the other members
+            let names: Vec<String> = members
+                .iter()
+                .filter(|event| event.state_key.as_deref() != Some(ctx.user_id.as_str()))
+                .filter_map(|event| {
+                    event
+                        .content
+                        .get("displayname")
+                        .and_then(|name| name.as_str())
+                        .map(str::to_string)
+                        .or_else(|| {
+                            event
+                                .state_key
+                                .as_deref()
+                                .map(|id| localpart(id).to_string())
+                        })
+                })
+                .collect();
+            (!names.is_empty()).then(|| names.join(", "))
+        })
+        .unwrap_or_else(|| room_id.to_string());
+
+    let mut channel = Channel::new(
+        room_id,
+        alias.unwrap_or_else(|| room_id.to_string()),
+        display_name,
+        channel_type,
+    )
+    .with_members(member_ids.clone());
+
+    if let Some(create) = state
+        .iter()
+        .find(|event| event.event_type == "m.room.create")
+    {
+        channel.created_at = timestamp_to_datetime(create.origin_server_ts);
+    }
+    if let Some(topic) = content_str("m.room.topic", "topic") {
+        channel = channel.with_topic(topic);
+    }
+    // A tombstone means the room was replaced by a new one
+    if state_content("m.room.tombstone").is_some() {
+        channel = channel.archived();
+    }
+    // Rooms with members from other homeservers are federated
+    let own_server = ctx.server_name();
+    if member_ids
+        .iter()
+        .any(|id| server_name(id).is_some_and(|server| server != own_server))
+    {
+        channel = channel.shared();
+    }
+
+    let mut metadata = serde_json::json!({
+        "encrypted": state_content("m.room.encryption").is_some(),
+    });
+    if let Some(avatar) =
+        content_str("m.room.avatar", "url").and_then(|mxc| mxc_to_http(&ctx.homeserver, &mxc))
+    {
+        metadata["avatar_url"] = serde_json::json!(avatar);
+    }
+    if channel_type == ChannelType::DirectMessage {
+        if let Some(partner) = member_ids.iter().find(|id| **id != ctx.user_id) {
+            metadata["dm_partner_id"] = serde_json::json!(partner);
+        }
+    }
+    channel.with_metadata(metadata)
+}
+
+/// Convert a timeline event to platform events
+fn timeline_event_to_events(
+    room_id: &str,
+    event: &RoomEvent,
+    ctx: &ConversionContext,
+) -> Vec<PlatformEvent> {
+    match event.event_type.as_str() {
+        "m.room.message" => event_to_edit(room_id, event)
+            .map(PlatformEvent::MessageUpdated)
+            .or_else(|| event_to_message(room_id, event, ctx).map(PlatformEvent::MessagePosted))
+            .into_iter()
+            .collect(),
+        "m.room.redaction" => event
+            .redacts
+            .as_deref()
+            .or_else(|| event.content.get("redacts").and_then(|r| r.as_str()))
+            .map(|message_id| PlatformEvent::MessageDeleted {
+                message_id: message_id.to_string(),
+                channel_id: room_id.to_string(),
+            })
+            .into_iter()
+            .collect(),
+        "m.reaction" => match event.content.get("m.relates_to") {
+            Some(relates_to)
+                if relates_to.get("rel_type").and_then(|t| t.as_str()) == Some("m.annotation") =>
+            {
+                let message_id = relates_to.get("event_id").and_then(|id| id.as_str());
+                let key = relates_to.get("key").and_then(|key| key.as_str());
+                match (message_id, key) {
+                    (Some(message_id), Some(key)) => vec![PlatformEvent::ReactionAdded {
+                        message_id: message_id.to_string(),
+                        user_id: event.sender.clone(),
+                        emoji_name: key.to_string(),
+                        channel_id: room_id.to_string(),
+                    }],
+                    _ => Vec::new(),
+                }
+            }
+            _ => Vec::new(),
+        },
+        "m.room.member" => {
+            let Some(user_id) = event.state_key.clone() else {
+                return Vec::new();
+            };
+            let membership = event.content.get("membership").and_then(|m| m.as_str());
+            let previous = event
+                .unsigned
+                .as_ref()
+                .and_then(|unsigned| unsigned.pointer("/prev_content/membership"))
+                .and_then(|m| m.as_str());
+            let channel_id = room_id.to_string();
+            match (membership, previous) {
+                // Display name or avatar change
+                (Some("join"), Some("join")) => vec![PlatformEvent::UserUpdated { user_id }],
+                (Some("join"), _) => vec![PlatformEvent::UserJoinedChannel {
+                    user_id,
+                    channel_id,
+                    user: None,
+                    channel: None,
+                }],
+                (Some("leave" | "ban"), Some("join")) => vec![PlatformEvent::UserLeftChannel {
+                    user_id,
+                    channel_id,
+                    user: None,
+                    channel: None,
+                }],
+                _ => Vec::new(),
+            }
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Convert an ephemeral room event to platform events
+fn ephemeral_event_to_events(
+    room_id: &str,
+    event: &BasicEvent,
+    ctx: &ConversionContext,
+) -> Vec<PlatformEvent> {
+    if event.event_type != "m.typing" {
+        return Vec::new();
+    }
+    event
+        .content
+        .get("user_ids")
+        .and_then(|ids| ids.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|id| id.as_str())
+        .filter(|id| *id != ctx.user_id)
+        .map(|user_id| PlatformEvent::UserTyping {
+            user_id: user_id.to_string(),
+            channel_id: room_id.to_string(),
+        })
+        .collect()
+}
+
+/// Convert a sync response to platform events
+pub fn sync_to_events(sync: &SyncResponse, ctx: &ConversionContext) -> Vec<PlatformEvent> {
+    let mut events = Vec::new();
+
+    for (room_id, room) in &sync.rooms.join {
+        for event in &room.timeline.events {
+            events.extend(timeline_event_to_events(room_id, event, ctx));
+        }
+        for event in &room.ephemeral.events {
+            events.extend(ephemeral_event_to_events(room_id, event, ctx));
+        }
+    }
+    for (room_id, room) in &sync.rooms.leave {
+        for event in &room.timeline.events {
+            events.extend(timeline_event_to_events(room_id, event, ctx));
+        }
+    }
+
+    for event in &sync.presence.events {
+        if event.event_type != "m.presence" {
+            continue;
+        }
+        let (Some(user_id), Some(presence)) = (
+            event.sender.as_ref(),
+            event.content.get("presence").and_then(|p| p.as_str()),
+        ) else {
+            continue;
+        };
+        events.push(PlatformEvent::UserStatusChanged {
+            user_id: user_id.clone(),
+            status: presence_to_status(presence),
+        });
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx() -> ConversionContext {
+        ConversionContext::new(
+            Url::parse("https://matrix.example.com").unwrap(),
+            "@alice:example.com",
+        )
+    }
+
+    fn event(value: serde_json::Value) -> RoomEvent {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_ids() {
+        assert_eq!(localpart("@alice:example.com"), "alice");
+        assert_eq!(server_name("@alice:example.com"), Some("example.com"));
+        assert_eq!(
+            server_name("!room:matrix.org:8448"),
+            Some("matrix.org:8448")
+        );
+    }
+
+    #[test]
+    fn test_mxc_to_http() {
+        let homeserver = Url::parse("https://matrix.example.com").unwrap();
+        assert_eq!(
+            mxc_to_http(&homeserver, "mxc://example.org/abc123").as_deref(),
+            Some("https://matrix.example.com/_matrix/media/v3/download/example.org/abc123")
+        );
+        assert_eq!(mxc_to_http(&homeserver, "https://example.org/x"), None);
+    }
+
+    #[test]
+    fn test_message_conversion() {
+        let message = event_to_message(
+            "!room:example.com",
+            &event(serde_json::json!({
+                "type": "m.room.message",
+                "event_id": "$ev1",
+                "sender": "@bob:matrix.org",
+                "origin_server_ts": 1_700_000_000_000i64,
+                "content": {
+                    "msgtype": "m.image",
+                    "body": "cat.png",
+                    "url": "mxc://matrix.org/cat",
+                    "info": { "mimetype": "image/png", "size": 1234 },
+                    "m.relates_to": { "rel_type": "m.thread", "event_id": "$root" }
+                },
+                "unsigned": { "m.relations": { "m.replace": {
+                    "origin_server_ts": 1_700_000_060_000i64,
+                    "content": { "m.new_content": { "body": "a cat" } }
+                } } }
+            })),
+            &ctx(),
+        )
+        .unwrap();
+
+        assert_eq!(message.id, "$ev1");
+        assert_eq!(message.text, "a cat");
+        assert!(message.edited_at.is_some());
+        assert_eq!(message.origin.as_deref(), Some("matrix.org"));
+        assert_eq!(message.attachments[0].mime_type, "image/png");
+        assert_eq!(message.attachments[0].size, 1234);
+        let metadata = message.metadata.unwrap();
+        assert_eq!(metadata["root_id"], "$root");
+        assert_eq!(metadata["msgtype"], "m.image");
+    }
+
+    #[test]
+    fn test_room_to_channel() {
+        let member = |user_id: &str, name: &str| {
+            event(serde_json::json!({
+                "type": "m.room.member", "state_key": user_id, "sender": user_id,
+                "content": { "membership": "join", "displayname": name }
+            }))
+        };
+        let state = vec![
+            event(serde_json::json!({
+                "type": "m.room.create", "state_key": "", "origin_server_ts": 1000,
+                "content": {}
+            })),
+            member("@alice:example.com", "Alice"),
+            member("@bob:example.com", "Bob"),
+        ];
+
+        let dm = room_to_channel("!dm:example.com", &state, true, &ctx());
+        assert_eq!(dm.channel_type, ChannelType::DirectMessage);
+        assert_eq!(dm.display_name, "Bob");
+        assert!(!dm.is_shared);
+        assert_eq!(dm.metadata.unwrap()["dm_partner_id"], "@bob:example.com");
+
+        let mut state = state;
+        state.push(event(serde_json::json!({
+            "type": "m.room.join_rules", "state_key": "", "content": { "join_rule": "public" }
+        })));
+        state.push(event(serde_json::json!({
+            "type": "m.room.name", "state_key": "", "content": { "name": "Lobby" }
+        })));
+        state.push(member("@carol:matrix.org", "Carol"));
+        let room = room_to_channel("!lobby:example.com", &state, false, &ctx());
+        assert_eq!(room.channel_type, ChannelType::Public);
+        assert_eq!(room.display_name, "Lobby");
+        assert_eq!(room.member_ids.unwrap().len(), 3);
+        assert!(room.is_shared);
+    }
+
+    #[test]
+    fn test_sync_to_events() {
+        let sync: SyncResponse = serde_json::from_value(serde_json::json!({
+            "next_batch": "s2",
+            "rooms": { "join": { "!room:example.com": {
+                "timeline": { "events": [
+                    { "type": "m.room.message", "event_id": "$1", "sender": "@bob:example.com",
+                      "origin_server_ts": 1, "content": { "msgtype": "m.text", "body": "hi" } },
+                    { "type": "m.room.message", "event_id": "$2", "sender": "@bob:example.com",
+                      "origin_server_ts": 2, "content": { "msgtype": "m.text", "body": "* hello",
+                        "m.new_content": { "msgtype": "m.text", "body": "hello" },
+                        "m.relates_to": { "rel_type": "m.replace", "event_id": "$1" } } },
+                    { "type": "m.room.redaction", "event_id": "$3", "sender": "@bob:example.com",
+                      "origin_server_ts": 3, "redacts": "$1", "content": {} },
+                    { "type": "m.room.member", "event_id": "$4", "sender": "@carol:example.com",
+                      "state_key": "@carol:example.com", "content": { "membership": "join" } }
+                ] },
+                "ephemeral": { "events": [
+                    { "type": "m.typing",
+                      "content": { "user_ids": ["@alice:example.com", "@bob:example.com"] } }
+                ] }
+            } } },
+            "presence": { "events": [
+                { "type": "m.presence", "sender": "@bob:example.com",
+                  "content": { "presence": "unavailable" } }
+            ] }
+        }))
+        .unwrap();
+
+        let events = sync_to_events(&sync, &ctx());
+        assert_eq!(events.len(), 6);
+        assert!(matches!(&events[0], PlatformEvent::MessagePosted(m) if m.text == "hi"));
+        assert!(
+            matches!(&events[1], PlatformEvent::MessageUpdated(m) if m.id == "$1" && m.text == "hello")
+        );
+        assert!(
+            matches!(&events[2], PlatformEvent::MessageDeleted { message_id, .. } if message_id == "$1")
+        );
+        assert!(
+            matches!(&events[3], PlatformEvent::UserJoinedChannel { user_id, .. } if user_id == "@carol:example.com")
+        );
+        // The user's own typing notification is skipped
+        assert!(
+            matches!(&events[4], PlatformEvent::UserTyping { user_id, .. } if user_id == "@bob:example.com")
+        );
+        assert!(matches!(
+            &events[5],
+            PlatformEvent::UserStatusChanged {
+                status: UserStatus::Away,
+                ..
+            }
+        ));
+    }
+}
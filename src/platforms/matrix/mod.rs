@@ -0,0 +1,18 @@
+//! Matrix platform adapter
+//!
+//! This module implements the communication layer for Matrix homeservers
+//! using the client-server API (v3). Rooms are mapped to channels; Matrix has
+//! no teams, so the workspace methods are unsupported. Real-time events are
+//! delivered by a `/sync` loop. End-to-end encrypted rooms are listed, but
+//! their messages cannot be decrypted.
+
+mod client;
+mod convert;
+mod platform_impl;
+mod sync;
+mod types;
+
+pub use client::MatrixClient;
+pub use convert::{presence_to_status, status_to_presence};
+pub use platform_impl::MatrixPlatform;
+pub use types::*;
@@ -0,0 +1,333 @@
+use async_trait::async_trait;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::error::{Error, ErrorCode, Result};
+use crate::platforms::platform_trait::{Platform, PlatformConfig, PlatformEvent};
+use crate::types::user::UserStatus;
+use crate::types::{Channel, ConnectionInfo, Message, PlatformCapabilities, Team, User};
+
+use super::client::MatrixClient;
+use super::convert::{
+    event_to_message, localpart, mxc_to_http, presence_to_status, room_to_channel,
+    status_to_presence, ConversionContext,
+};
+use super::sync::SyncManager;
+
+/// How long a typing notification lasts unless renewed
+const TYPING_TIMEOUT_MS: u64 = 5_000;
+
+/// Wrapper struct that implements the Platform trait for Matrix
+pub struct MatrixPlatform {
+    client: Arc<MatrixClient>,
+    connection_info: Option<ConnectionInfo>,
+    sync: Option<SyncManager>,
+    homeserver_url: String,
+    capabilities: PlatformCapabilities,
+}
+
+impl MatrixPlatform {
+    /// Create a new Matrix platform instance
+    pub fn new(homeserver_url: &str) -> Result<Self> {
+        let client = Arc::new(MatrixClient::new(homeserver_url)?);
+        Ok(Self {
+            client,
+            connection_info: None,
+            sync: None,
+            homeserver_url: homeserver_url.to_string(),
+            capabilities: PlatformCapabilities::matrix(),
+        })
+    }
+
+    /// Get the underlying client (for accessing Matrix-specific methods)
+    pub fn client(&self) -> &MatrixClient {
+        &self.client
+    }
+
+    async fn context(&self) -> Result<ConversionContext> {
+        let user_id = self.client.require_user_id().await?;
+        Ok(ConversionContext::new(
+            self.client.homeserver().clone(),
+            user_id,
+        ))
+    }
+
+    /// IDs of the rooms listed as direct chats in the user's account data
+    async fn direct_room_ids(&self) -> Result<HashSet<String>> {
+        Ok(self
+            .client
+            .get_direct_rooms()
+            .await?
+            .into_values()
+            .flatten()
+            .collect())
+    }
+
+    async fn room_channel(
+        &self,
+        room_id: &str,
+        direct_rooms: &HashSet<String>,
+        ctx: &ConversionContext,
+    ) -> Result<Channel> {
+        let state = self.client.get_room_state(room_id).await?;
+        Ok(room_to_channel(
+            room_id,
+            &state,
+            direct_rooms.contains(room_id),
+            ctx,
+        ))
+    }
+
+    async fn send_content(
+        &self,
+        room_id: &str,
+        text: &str,
+        content: serde_json::Value,
+    ) -> Result<Message> {
+        let user_id = self.client.require_user_id().await?;
+        let event_id = self.client.send_message(room_id, &content).await?;
+        Ok(Message::new(event_id, text, user_id, room_id))
+    }
+}
+
+#[async_trait]
+impl Platform for MatrixPlatform {
+    fn capabilities(&self) -> &PlatformCapabilities {
+        &self.capabilities
+    }
+
+    async fn connect(&mut self, config: PlatformConfig) -> Result<ConnectionInfo> {
+        let user_id = if let Some(token) = config.credentials.get("token") {
+            // Use an existing access token
+            self.client.login_with_token(token).await?.user_id
+        } else if let (Some(login_id), Some(password)) = (
+            config.credentials.get("login_id"),
+            config.credentials.get("password"),
+        ) {
+            self.client.login(login_id, password).await?.user_id
+        } else {
+            return Err(Error::new(
+                ErrorCode::InvalidArgument,
+                "Missing authentication credentials (provide 'token' or 'login_id'+'password')",
+            ));
+        };
+
+        // The profile is optional; fall back to the user ID's local part
+        let display_name = self
+            .client
+            .get_profile(&user_id)
+            .await
+            .ok()
+            .and_then(|profile| profile.displayname)
+            .unwrap_or_else(|| localpart(&user_id).to_string());
+
+        let conn_info = ConnectionInfo::new("matrix", &self.homeserver_url, user_id, display_name);
+        self.connection_info = Some(conn_info.clone());
+
+        Ok(conn_info)
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        self.sync = None;
+        self.client.logout().await?;
+        self.connection_info = None;
+        Ok(())
+    }
+
+    fn connection_info(&self) -> Option<&ConnectionInfo> {
+        self.connection_info.as_ref()
+    }
+
+    async fn send_message(&self, channel_id: &str, text: &str) -> Result<Message> {
+        let content = serde_json::json!({ "msgtype": "m.text", "body": text });
+        self.send_content(channel_id, text, content).await
+    }
+
+    async fn get_channels(&self) -> Result<Vec<Channel>> {
+        let ctx = self.context().await?;
+        let direct_rooms = self.direct_room_ids().await?;
+
+        let mut channels = Vec::new();
+        for room_id in self.client.get_joined_rooms().await? {
+            channels.push(self.room_channel(&room_id, &direct_rooms, &ctx).await?);
+        }
+
+        Ok(channels)
+    }
+
+    async fn get_channel(&self, channel_id: &str) -> Result<Channel> {
+        let ctx = self.context().await?;
+        let direct_rooms = self.direct_room_ids().await?;
+        self.room_channel(channel_id, &direct_rooms, &ctx).await
+    }
+
+    async fn get_messages(&self, channel_id: &str, limit: usize) -> Result<Vec<Message>> {
+        let ctx = self.context().await?;
+        let response = self.client.get_latest_events(channel_id, limit).await?;
+
+        // Events are returned newest first; edits are folded into their messages
+        let mut messages: Vec<Message> = response
+            .chunk
+            .iter()
+            .filter_map(|event| event_to_message(channel_id, event, &ctx))
+            .collect();
+        messages.reverse();
+
+        Ok(messages)
+    }
+
+    async fn get_channel_members(&self, channel_id: &str) -> Result<Vec<User>> {
+        let members = self.client.get_joined_members(channel_id).await?;
+
+        let mut users: Vec<User> = members
+            .joined
+            .into_iter()
+            .map(|(user_id, member)| {
+                let display_name = member
+                    .display_name
+                    .unwrap_or_else(|| localpart(&user_id).to_string());
+                let mut user = User::new(&user_id, localpart(&user_id), display_name);
+                if let Some(avatar) = member
+                    .avatar_url
+                    .and_then(|mxc| mxc_to_http(self.client.homeserver(), &mxc))
+                {
+                    user = user.with_avatar(avatar);
+                }
+                user
+            })
+            .collect();
+        users.sort_by(|a, b| a.id.cmp(&b.id));
+
+        Ok(users)
+    }
+
+    async fn get_user(&self, user_id: &str) -> Result<User> {
+        let profile = self.client.get_profile(user_id).await?;
+        let display_name = profile
+            .displayname
+            .unwrap_or_else(|| localpart(user_id).to_string());
+        let mut user = User::new(user_id, localpart(user_id), display_name);
+        if let Some(avatar) = profile
+            .avatar_url
+            .and_then(|mxc| mxc_to_http(self.client.homeserver(), &mxc))
+        {
+            user = user.with_avatar(avatar);
+        }
+        Ok(user)
+    }
+
+    async fn get_current_user(&self) -> Result<User> {
+        let user_id = self.client.require_user_id().await?;
+        self.get_user(&user_id).await
+    }
+
+    async fn create_direct_channel(&self, user_id: &str) -> Result<Channel> {
+        let room_id = self
+            .client
+            .create_room(&serde_json::json!({
+                "is_direct": true,
+                "invite": [user_id],
+                "preset": "trusted_private_chat",
+            }))
+            .await?;
+
+        // Record the room as a direct chat, as other clients expect
+        let mut direct = self.client.get_direct_rooms().await?;
+        direct
+            .entry(user_id.to_string())
+            .or_default()
+            .push(room_id.clone());
+        self.client.set_direct_rooms(&direct).await?;
+
+        self.get_channel(&room_id).await
+    }
+
+    async fn create_channel(
+        &self,
+        _team_id: &str,
+        name: &str,
+        display_name: &str,
+        is_private: bool,
+    ) -> Result<Channel> {
+        let (preset, visibility) = if is_private {
+            ("private_chat", "private")
+        } else {
+            ("public_chat", "public")
+        };
+        let room_id = self
+            .client
+            .create_room(&serde_json::json!({
+                "name": display_name,
+                "room_alias_name": name,
+                "preset": preset,
+                "visibility": visibility,
+            }))
+            .await?;
+        self.get_channel(&room_id).await
+    }
+
+    async fn get_teams(&self) -> Result<Vec<Team>> {
+        Err(Error::unsupported("Matrix has no teams/workspaces"))
+    }
+
+    async fn get_team(&self, team_id: &str) -> Result<Team> {
+        let _ = team_id;
+        Err(Error::unsupported("Matrix has no teams/workspaces"))
+    }
+
+    async fn set_status(&self, status: UserStatus, custom_message: Option<&str>) -> Result<()> {
+        self.client
+            .set_presence(status_to_presence(status), custom_message)
+            .await
+    }
+
+    async fn get_user_status(&self, user_id: &str) -> Result<UserStatus> {
+        let presence = self.client.get_presence(user_id).await?;
+        Ok(presence_to_status(&presence.presence))
+    }
+
+    async fn subscribe_events(&mut self) -> Result<()> {
+        let ctx = self.context().await?;
+        self.sync = Some(SyncManager::start(self.client.clone(), ctx).await?);
+        Ok(())
+    }
+
+    async fn unsubscribe_events(&mut self) -> Result<()> {
+        self.sync = None;
+        Ok(())
+    }
+
+    async fn poll_event(&mut self) -> Result<Option<PlatformEvent>> {
+        match &self.sync {
+            Some(sync) => Ok(sync.poll_event().await),
+            None => Ok(None),
+        }
+    }
+
+    // ========================================================================
+    // Extended Platform Methods Implementation
+    // ========================================================================
+
+    async fn send_reply(&self, channel_id: &str, text: &str, root_id: &str) -> Result<Message> {
+        // Replies are sent as thread messages, with a reply fallback for
+        // clients that do not support threads
+        let content = serde_json::json!({
+            "msgtype": "m.text",
+            "body": text,
+            "m.relates_to": {
+                "rel_type": "m.thread",
+                "event_id": root_id,
+                "is_falling_back": true,
+                "m.in_reply_to": { "event_id": root_id },
+            },
+        });
+        let message = self.send_content(channel_id, text, content).await?;
+        Ok(message.with_metadata(serde_json::json!({ "root_id": root_id })))
+    }
+
+    async fn send_typing_indicator(&self, channel_id: &str, parent_id: Option<&str>) -> Result<()> {
+        // Matrix typing notifications are per room
+        let _ = parent_id;
+        self.client.send_typing(channel_id, TYPING_TIMEOUT_MS).await
+    }
+}
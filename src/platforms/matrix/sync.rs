@@ -0,0 +1,127 @@
+//! Sync loop delivering real-time events
+//!
+//! Long-polls `/sync` in a background task and converts the responses to
+//! platform events. The initial sync only establishes the position in the
+//! event stream, so existing history is not replayed as new events. Failed
+//! syncs are retried with exponential backoff; the loop reports this through
+//! `ConnectionStateChanged` events and stops once the access token is rejected.
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, Mutex};
+use tokio::task::JoinHandle;
+
+use crate::error::{ErrorCode, Result};
+use crate::platforms::PlatformEvent;
+use crate::types::ConnectionState;
+
+use super::client::MatrixClient;
+use super::convert::{sync_to_events, ConversionContext};
+
+/// How long the server may hold a sync request open
+const SYNC_TIMEOUT_MS: u64 = 30_000;
+
+/// Filter for the initial sync, which is only needed for its `next_batch` token
+const INITIAL_SYNC_FILTER: &str = r#"{"room":{"timeline":{"limit":1},"state":{"lazy_load_members":true}},"presence":{"not_types":["*"]}}"#;
+
+/// Maximum number of events waiting to be polled
+const EVENT_QUEUE_SIZE: usize = 1000;
+
+/// First and longest delay between failed syncs
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Runs the sync loop and queues its events for polling
+pub struct SyncManager {
+    event_rx: Mutex<mpsc::Receiver<PlatformEvent>>,
+    task: JoinHandle<()>,
+}
+
+impl SyncManager {
+    /// Perform the initial sync and start the sync loop
+    ///
+    /// # Returns
+    /// An error if the initial sync fails
+    pub async fn start(client: Arc<MatrixClient>, ctx: ConversionContext) -> Result<Self> {
+        let initial = client.sync(None, 0, Some(INITIAL_SYNC_FILTER)).await?;
+
+        let (event_tx, event_rx) = mpsc::channel(EVENT_QUEUE_SIZE);
+        let task = tokio::spawn(run(client, ctx, initial.next_batch, event_tx));
+
+        Ok(Self {
+            event_rx: Mutex::new(event_rx),
+            task,
+        })
+    }
+
+    /// Take the next queued event, if any
+    pub async fn poll_event(&self) -> Option<PlatformEvent> {
+        self.event_rx.lock().await.try_recv().ok()
+    }
+}
+
+impl Drop for SyncManager {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn run(
+    client: Arc<MatrixClient>,
+    ctx: ConversionContext,
+    mut since: String,
+    event_tx: mpsc::Sender<PlatformEvent>,
+) {
+    let mut backoff = MIN_BACKOFF;
+    let mut failing = false;
+
+    loop {
+        match client.sync(Some(&since), SYNC_TIMEOUT_MS, None).await {
+            Ok(sync) => {
+                if failing {
+                    failing = false;
+                    backoff = MIN_BACKOFF;
+                    let event = PlatformEvent::ConnectionStateChanged {
+                        state: ConnectionState::Connected,
+                        attempt: None,
+                        reason: None,
+                    };
+                    if event_tx.send(event).await.is_err() {
+                        return;
+                    }
+                }
+                for event in sync_to_events(&sync, &ctx) {
+                    if event_tx.send(event).await.is_err() {
+                        return;
+                    }
+                }
+                since = sync.next_batch;
+            }
+            Err(e) if e.code == ErrorCode::AuthenticationFailed => {
+                let _ = event_tx
+                    .send(PlatformEvent::ConnectionStateChanged {
+                        state: ConnectionState::Error,
+                        attempt: None,
+                        reason: Some(e.message.clone()),
+                    })
+                    .await;
+                return;
+            }
+            Err(_) => {
+                if !failing {
+                    failing = true;
+                    let event = PlatformEvent::ConnectionStateChanged {
+                        state: ConnectionState::Reconnecting,
+                        attempt: None,
+                        reason: None,
+                    };
+                    if event_tx.send(event).await.is_err() {
+                        return;
+                    }
+                }
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+        }
+    }
+}
@@ -0,0 +1,175 @@
+//! Matrix client-server API types
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Error response returned by the homeserver
+#[derive(Debug, Clone, Deserialize)]
+pub struct MatrixErrorResponse {
+    pub errcode: String,
+    #[serde(default)]
+    pub error: String,
+    /// Milliseconds to wait before retrying (M_LIMIT_EXCEEDED)
+    #[serde(default)]
+    pub retry_after_ms: Option<u64>,
+}
+
+/// Response to POST /login
+#[derive(Debug, Clone, Deserialize)]
+pub struct LoginResponse {
+    pub user_id: String,
+    pub access_token: String,
+    #[serde(default)]
+    pub device_id: Option<String>,
+}
+
+/// Response to GET /account/whoami
+#[derive(Debug, Clone, Deserialize)]
+pub struct WhoAmIResponse {
+    pub user_id: String,
+    #[serde(default)]
+    pub device_id: Option<String>,
+}
+
+/// Response to GET /joined_rooms
+#[derive(Debug, Clone, Deserialize)]
+pub struct JoinedRoomsResponse {
+    pub joined_rooms: Vec<String>,
+}
+
+/// A room event (timeline or state)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoomEvent {
+    #[serde(rename = "type")]
+    pub event_type: String,
+    #[serde(default)]
+    pub event_id: String,
+    #[serde(default)]
+    pub sender: String,
+    #[serde(default)]
+    pub origin_server_ts: i64,
+    #[serde(default)]
+    pub room_id: Option<String>,
+    #[serde(default)]
+    pub state_key: Option<String>,
+    /// Event ID of the redacted event (m.room.redaction, room versions < 11)
+    #[serde(default)]
+    pub redacts: Option<String>,
+    #[serde(default)]
+    pub content: serde_json::Value,
+    #[serde(default)]
+    pub unsigned: Option<serde_json::Value>,
+}
+
+/// An event without room context (presence, typing, account data)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BasicEvent {
+    #[serde(rename = "type")]
+    pub event_type: String,
+    #[serde(default)]
+    pub sender: Option<String>,
+    #[serde(default)]
+    pub content: serde_json::Value,
+}
+
+/// Response to GET /rooms/{roomId}/messages
+#[derive(Debug, Clone, Deserialize)]
+pub struct MessagesResponse {
+    pub chunk: Vec<RoomEvent>,
+    #[serde(default)]
+    pub start: Option<String>,
+    #[serde(default)]
+    pub end: Option<String>,
+}
+
+/// Response to PUT /rooms/{roomId}/send/{eventType}/{txnId}
+#[derive(Debug, Clone, Deserialize)]
+pub struct SendResponse {
+    pub event_id: String,
+}
+
+/// Response to POST /createRoom
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateRoomResponse {
+    pub room_id: String,
+}
+
+/// Response to GET /profile/{userId}
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ProfileResponse {
+    #[serde(default)]
+    pub displayname: Option<String>,
+    #[serde(default)]
+    pub avatar_url: Option<String>,
+}
+
+/// Response to GET /presence/{userId}/status
+#[derive(Debug, Clone, Deserialize)]
+pub struct PresenceResponse {
+    pub presence: String,
+    #[serde(default)]
+    pub status_msg: Option<String>,
+}
+
+/// A joined member in GET /rooms/{roomId}/joined_members
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct JoinedMember {
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub avatar_url: Option<String>,
+}
+
+/// Response to GET /rooms/{roomId}/joined_members
+#[derive(Debug, Clone, Deserialize)]
+pub struct JoinedMembersResponse {
+    pub joined: HashMap<String, JoinedMember>,
+}
+
+/// Response to GET /sync
+#[derive(Debug, Clone, Deserialize)]
+pub struct SyncResponse {
+    pub next_batch: String,
+    #[serde(default)]
+    pub rooms: SyncRooms,
+    #[serde(default)]
+    pub presence: EventList<BasicEvent>,
+}
+
+/// Rooms section of a sync response
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SyncRooms {
+    #[serde(default)]
+    pub join: HashMap<String, JoinedRoomUpdate>,
+    #[serde(default)]
+    pub leave: HashMap<String, LeftRoomUpdate>,
+}
+
+/// Updates to a room the user has joined
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct JoinedRoomUpdate {
+    #[serde(default)]
+    pub timeline: EventList<RoomEvent>,
+    #[serde(default)]
+    pub ephemeral: EventList<BasicEvent>,
+}
+
+/// Updates to a room the user has left
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LeftRoomUpdate {
+    #[serde(default)]
+    pub timeline: EventList<RoomEvent>,
+}
+
+/// A list of events
+#[derive(Debug, Clone, Deserialize)]
+pub struct EventList<T> {
+    #[serde(default = "Vec::new")]
+    pub events: Vec<T>,
+}
+
+impl<T> Default for EventList<T> {
+    fn default() -> Self {
+        Self { events: Vec::new() }
+    }
+}
@@ -0,0 +1,171 @@
+//! Per-channel hourly activity tracking
+//!
+//! Keeps hourly message counts per channel so clients can render activity
+//! sparklines and heatmaps without scanning the full history every time.
+//! Counts for a period are backfilled from the server once, and then kept up to
+//! date from `posted` WebSocket events.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::types::ChannelActivity;
+
+/// Longest period, in days, that activity is kept for
+pub const MAX_ACTIVITY_DAYS: u32 = 90;
+
+const HOUR_MS: i64 = ChannelActivity::HOUR_MS;
+
+/// Round a timestamp down to the start of its hour
+pub fn hour_start(timestamp: i64) -> i64 {
+    timestamp.div_euclid(HOUR_MS) * HOUR_MS
+}
+
+#[derive(Debug, Default)]
+struct ChannelCounts {
+    /// Message counts keyed by hour start
+    hours: BTreeMap<i64, u32>,
+    /// Counts are complete from this hour onwards (None until backfilled)
+    covered_since: Option<i64>,
+}
+
+/// Tracks hourly message counts per channel
+#[derive(Debug, Clone, Default)]
+pub struct ChannelActivityTracker {
+    channels: Arc<RwLock<HashMap<String, ChannelCounts>>>,
+}
+
+impl ChannelActivityTracker {
+    /// Create an empty tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count a newly posted message
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel the message was posted in
+    /// * `create_at` - When the message was posted (milliseconds since epoch)
+    pub async fn record(&self, channel_id: &str, create_at: i64) {
+        let mut channels = self.channels.write().await;
+        let counts = channels.entry(channel_id.to_string()).or_default();
+        *counts.hours.entry(hour_start(create_at)).or_default() += 1;
+
+        // Drop hours that can no longer be queried
+        let oldest = hour_start(create_at) - i64::from(MAX_ACTIVITY_DAYS) * 24 * HOUR_MS;
+        counts.hours = counts.hours.split_off(&oldest);
+        if counts.covered_since.is_some_and(|since| since < oldest) {
+            counts.covered_since = Some(oldest);
+        }
+    }
+
+    /// Check whether counts for a channel are complete from `since` onwards
+    pub async fn is_covered(&self, channel_id: &str, since: i64) -> bool {
+        let channels = self.channels.read().await;
+        channels
+            .get(channel_id)
+            .and_then(|counts| counts.covered_since)
+            .is_some_and(|covered| covered <= since)
+    }
+
+    /// Replace the counts from `since` onwards with history fetched from the server
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel the history belongs to
+    /// * `since` - Start of the fetched period (aligned to the hour)
+    /// * `timestamps` - Creation times of all messages posted since then
+    pub async fn backfill(&self, channel_id: &str, since: i64, timestamps: &[i64]) {
+        let mut channels = self.channels.write().await;
+        let counts = channels.entry(channel_id.to_string()).or_default();
+
+        counts.hours.split_off(&since);
+        for &create_at in timestamps.iter().filter(|&&t| t >= since) {
+            *counts.hours.entry(hour_start(create_at)).or_default() += 1;
+        }
+        counts.covered_since = Some(since);
+    }
+
+    /// Get the hourly counts of a channel
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel ID
+    /// * `start` - Start of the first hour (aligned to the hour)
+    /// * `hours` - Number of hours to return
+    pub async fn activity(&self, channel_id: &str, start: i64, hours: usize) -> ChannelActivity {
+        let mut activity = ChannelActivity::new(channel_id, start, hours);
+        let channels = self.channels.read().await;
+        if let Some(counts) = channels.get(channel_id) {
+            let end = start + hours as i64 * HOUR_MS;
+            for (&hour, &count) in counts.hours.range(start..end) {
+                activity.hourly_counts[((hour - start) / HOUR_MS) as usize] = count;
+            }
+        }
+        activity
+    }
+
+    /// Forget all tracked activity (e.g. on disconnect)
+    pub async fn reset(&self) {
+        self.channels.write().await.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START: i64 = 1_700_000_000_000 / HOUR_MS * HOUR_MS;
+
+    #[tokio::test]
+    async fn test_backfill_and_live_events() {
+        let tracker = ChannelActivityTracker::new();
+        assert!(!tracker.is_covered("ch1", START).await);
+
+        tracker
+            .backfill(
+                "ch1",
+                START,
+                &[START - 1, START + 5, START + 10, START + HOUR_MS],
+            )
+            .await;
+        assert!(tracker.is_covered("ch1", START).await);
+        assert!(!tracker.is_covered("ch1", START - HOUR_MS).await);
+
+        tracker.record("ch1", START + 2 * HOUR_MS + 1).await;
+
+        let activity = tracker.activity("ch1", START, 4).await;
+        assert_eq!(activity.hourly_counts, vec![2, 1, 1, 0]);
+        assert_eq!(activity.start, START);
+    }
+
+    #[tokio::test]
+    async fn test_backfill_replaces_live_counts() {
+        let tracker = ChannelActivityTracker::new();
+        tracker.record("ch1", START + 1).await;
+        tracker.record("ch1", START + 2).await;
+
+        // The fetched history already contains the live messages
+        tracker
+            .backfill("ch1", START, &[START + 1, START + 2])
+            .await;
+        assert_eq!(
+            tracker.activity("ch1", START, 1).await.hourly_counts,
+            vec![2]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reset_and_unknown_channel() {
+        let tracker = ChannelActivityTracker::new();
+        tracker.backfill("ch1", START, &[START]).await;
+        tracker.reset().await;
+
+        assert!(!tracker.is_covered("ch1", START).await);
+        assert_eq!(tracker.activity("ch1", START, 2).await.total(), 0);
+    }
+
+    #[test]
+    fn test_hour_start() {
+        assert_eq!(hour_start(START + HOUR_MS - 1), START);
+        assert_eq!(hour_start(-1), -HOUR_MS);
+    }
+}
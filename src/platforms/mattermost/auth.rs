@@ -75,13 +75,13 @@ impl MattermostClient {
         };
 
         let url = self.api_url("/users/login");
-        let response = self
-            .http_client
-            .post(&url)
-            .json(&login_request)
-            .send()
+        let request = self.http_client().post(&url).json(&login_request);
+        let response = super::client::send(super::client::apply_deadline(request)?)
             .await
             .map_err(|e| {
+                if e.is_timeout() && crate::deadline::remaining().is_err() {
+                    return crate::deadline::expired();
+                }
                 Error::new(
                     ErrorCode::AuthenticationFailed,
                     format!("Login request failed: {e}"),
@@ -184,6 +184,8 @@ impl MattermostClient {
     /// and clear the stored token locally.
     pub async fn logout(&self) -> Result<()> {
         self.set_state(ConnectionState::Disconnecting).await;
+        self.forget_pending_posts().await;
+        self.forget_session_credentials();
 
         // Only call the logout endpoint if we have a token
         if self.get_token().await.is_some() {
@@ -0,0 +1,183 @@
+//! Bot accounts
+//!
+//! Bots are user accounts without a password that act through personal access
+//! tokens, created with `create_user_access_token` for the bot's user ID.
+//! Whether these endpoints are usable depends on the server configuration
+//! (`EnableBotAccountCreation`, `EnableUserAccessTokens`) and the caller's
+//! permissions; the server rejects requests otherwise.
+
+use crate::error::Result;
+use crate::types::BotSettings;
+
+use super::client::MattermostClient;
+use super::types::{MattermostBot, MattermostUser};
+
+/// Page size used when listing all bots
+const LIST_PAGE_SIZE: usize = 200;
+
+impl MattermostClient {
+    /// Create a bot account owned by the current user
+    ///
+    /// # Arguments
+    /// * `settings` - The bot's username, display name and description
+    ///
+    /// # Returns
+    /// A Result containing the created bot or an Error
+    ///
+    /// # API Endpoint
+    /// `POST /api/v4/bots`
+    pub async fn create_bot(&self, settings: &BotSettings) -> Result<MattermostBot> {
+        let response = self.post("/bots", settings).await?;
+        self.handle_response(response).await
+    }
+
+    /// Get one page of bots
+    ///
+    /// # Arguments
+    /// * `page` - The page number (0-based)
+    /// * `per_page` - Number of bots per page
+    /// * `include_deleted` - Whether to include disabled bots
+    ///
+    /// # Returns
+    /// A Result containing the bots or an Error
+    ///
+    /// # Notes
+    /// With `read_others_bots` all bots are returned; otherwise only the bots
+    /// owned by the current user.
+    pub async fn get_bots(
+        &self,
+        page: usize,
+        per_page: usize,
+        include_deleted: bool,
+    ) -> Result<Vec<MattermostBot>> {
+        let endpoint =
+            format!("/bots?page={page}&per_page={per_page}&include_deleted={include_deleted}");
+        let response = self.get(&endpoint).await?;
+        self.handle_response(response).await
+    }
+
+    /// Get all bots visible to the current user
+    pub async fn get_all_bots(&self, include_deleted: bool) -> Result<Vec<MattermostBot>> {
+        let mut bots = Vec::new();
+        for page in 0.. {
+            let batch = self.get_bots(page, LIST_PAGE_SIZE, include_deleted).await?;
+            let done = batch.len() < LIST_PAGE_SIZE;
+            bots.extend(batch);
+            if done {
+                break;
+            }
+        }
+        Ok(bots)
+    }
+
+    /// Enable or disable a bot
+    ///
+    /// # Arguments
+    /// * `bot_user_id` - The user ID of the bot
+    /// * `enabled` - Whether the bot should be enabled
+    ///
+    /// # Returns
+    /// A Result containing the updated bot or an Error
+    pub async fn set_bot_enabled(&self, bot_user_id: &str, enabled: bool) -> Result<MattermostBot> {
+        let action = if enabled { "enable" } else { "disable" };
+        let endpoint = format!("/bots/{bot_user_id}/{action}");
+        let response = self.post(&endpoint, &serde_json::json!({})).await?;
+        self.handle_response(response).await
+    }
+
+    /// Transfer a bot to another owner
+    ///
+    /// # Arguments
+    /// * `bot_user_id` - The user ID of the bot
+    /// * `owner_id` - The user ID of the new owner
+    ///
+    /// # Returns
+    /// A Result containing the updated bot or an Error
+    ///
+    /// # API Endpoint
+    /// `POST /api/v4/bots/{bot_user_id}/assign/{user_id}`
+    pub async fn assign_bot(&self, bot_user_id: &str, owner_id: &str) -> Result<MattermostBot> {
+        let endpoint = format!("/bots/{bot_user_id}/assign/{owner_id}");
+        let response = self.post(&endpoint, &serde_json::json!({})).await?;
+        self.handle_response(response).await
+    }
+
+    /// Convert a user account into a bot
+    ///
+    /// # Arguments
+    /// * `user_id` - The ID of the user
+    ///
+    /// # Returns
+    /// A Result containing the bot or an Error
+    ///
+    /// # Notes
+    /// Requires `manage_system`. The user's sessions are revoked and the
+    /// account can no longer log in with a password.
+    pub async fn convert_user_to_bot(&self, user_id: &str) -> Result<MattermostBot> {
+        let endpoint = format!("/users/{user_id}/convert_to_bot");
+        let response = self.post(&endpoint, &serde_json::json!({})).await?;
+        let bot = self.handle_response(response).await?;
+        self.invalidate_user_cache(user_id).await;
+        Ok(bot)
+    }
+
+    /// Convert a bot into a regular user account
+    ///
+    /// # Arguments
+    /// * `bot_user_id` - The user ID of the bot
+    /// * `password` - The password the user will log in with
+    ///
+    /// # Returns
+    /// A Result containing the user or an Error
+    ///
+    /// # Notes
+    /// Requires `manage_system`.
+    pub async fn convert_bot_to_user(
+        &self,
+        bot_user_id: &str,
+        password: &str,
+    ) -> Result<MattermostUser> {
+        let endpoint = format!("/bots/{bot_user_id}/convert_to_user");
+        let body = serde_json::json!({ "password": password });
+        let response = self.post(&endpoint, &body).await?;
+        let user = self.handle_response(response).await?;
+        self.invalidate_user_cache(bot_user_id).await;
+        Ok(user)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bot_conversion() {
+        let mm_bot: MattermostBot = serde_json::from_str(
+            r#"{"user_id":"b1","username":"deploy","owner_id":"u1","create_at":1,"update_at":2,"delete_at":2}"#,
+        )
+        .unwrap();
+        let bot: crate::types::Bot = mm_bot.into();
+        assert_eq!(bot.user_id, "b1");
+        assert!(bot.is_disabled);
+    }
+
+    #[cfg(feature = "simulation")]
+    #[tokio::test]
+    async fn test_get_all_bots() {
+        let server = crate::simulation::add_server("bots.sim");
+        server.set_route(
+            "GET",
+            "/bots",
+            200,
+            r#"[{"user_id":"b1","username":"deploy","owner_id":"u1","create_at":1,"update_at":1,"delete_at":0},
+                {"user_id":"b2","username":"old","owner_id":"u1","create_at":1,"update_at":2,"delete_at":2}]"#,
+        );
+
+        let client = MattermostClient::new("http://bots.sim").unwrap();
+        let bots = client.get_all_bots(true).await.unwrap();
+        assert_eq!(bots.len(), 2);
+        assert_eq!(bots[0].username, "deploy");
+
+        crate::simulation::remove_server("bots.sim");
+    }
+}
@@ -4,10 +4,13 @@
 //! and improve performance. Caches are automatically invalidated via WebSocket events.
 
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
+use crate::types::CacheStats;
+
 /// A cache entry with TTL expiration
 #[derive(Debug, Clone)]
 struct CacheEntry<T> {
@@ -40,12 +43,22 @@ impl<T> CacheEntry<T> {
 /// - TTL-based expiration: Entries automatically expire after configured duration
 /// - Automatic cleanup: Expired entries are removed on access
 /// - Memory efficient: Only stores unexpired entries
+/// - Bounded: With a maximum size, the entry closest to expiring is evicted
+///   to make room for a new one
 #[derive(Debug, Clone)]
 pub struct Cache<T: Clone> {
     /// Storage for cache entries
     entries: Arc<RwLock<HashMap<String, CacheEntry<T>>>>,
-    /// Time-to-live for cache entries
-    ttl: Duration,
+    /// Time-to-live for new cache entries
+    ttl: Arc<std::sync::RwLock<Duration>>,
+    /// Maximum number of entries; 0 for no limit
+    max_entries: Arc<AtomicUsize>,
+    /// Lookups answered from the cache
+    hits: Arc<AtomicU64>,
+    /// Lookups of missing or expired entries
+    misses: Arc<AtomicU64>,
+    /// Entries removed to stay within `max_entries`
+    evictions: Arc<AtomicU64>,
 }
 
 impl<T: Clone> Cache<T> {
@@ -62,7 +75,62 @@ impl<T: Clone> Cache<T> {
     pub fn new(ttl: Duration) -> Self {
         Self {
             entries: Arc::new(RwLock::new(HashMap::new())),
-            ttl,
+            ttl: Arc::new(std::sync::RwLock::new(ttl)),
+            max_entries: Arc::new(AtomicUsize::new(0)),
+            hits: Arc::new(AtomicU64::new(0)),
+            misses: Arc::new(AtomicU64::new(0)),
+            evictions: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Limit the number of entries of a new cache
+    ///
+    /// # Arguments
+    /// * `max_entries` - Maximum number of entries, None for no limit
+    pub fn with_max_entries(self, max_entries: Option<usize>) -> Self {
+        self.max_entries
+            .store(max_entries.unwrap_or(0), Ordering::Relaxed);
+        self
+    }
+
+    /// Change the TTL and size limit of the cache
+    ///
+    /// The TTL applies to entries stored from now on. If the cache holds more
+    /// than `max_entries` entries, the excess is evicted right away.
+    ///
+    /// # Arguments
+    /// * `ttl` - Time-to-live duration for new cache entries
+    /// * `max_entries` - Maximum number of entries, None for no limit
+    pub async fn configure(&self, ttl: Duration, max_entries: Option<usize>) {
+        *self.ttl.write().unwrap() = ttl;
+        let max_entries = max_entries.unwrap_or(0);
+        self.max_entries.store(max_entries, Ordering::Relaxed);
+
+        if max_entries > 0 {
+            let mut entries = self.entries.write().await;
+            while entries.len() > max_entries {
+                self.evict_one(&mut entries);
+            }
+        }
+    }
+
+    /// The TTL of new cache entries
+    fn ttl(&self) -> Duration {
+        *self.ttl.read().unwrap()
+    }
+
+    /// Remove an expired entry or, if there is none, the one closest to expiring
+    fn evict_one(&self, entries: &mut HashMap<String, CacheEntry<T>>) {
+        let victim = entries
+            .iter()
+            .min_by_key(|(_, entry)| entry.expires_at)
+            .map(|(key, _)| key.clone());
+        if let Some(key) = victim {
+            let expired = entries.get(&key).is_some_and(|entry| entry.is_expired());
+            entries.remove(&key);
+            if !expired {
+                self.evictions.fetch_add(1, Ordering::Relaxed);
+            }
         }
     }
 
@@ -84,12 +152,14 @@ impl<T: Clone> Cache<T> {
 
         if let Some(entry) = entries.get(key) {
             if !entry.is_expired() {
+                self.hits.fetch_add(1, Ordering::Relaxed);
                 return Some(entry.value.clone());
             }
             // Entry is expired, will be removed in cleanup
         }
 
         drop(entries);
+        self.misses.fetch_add(1, Ordering::Relaxed);
 
         // Remove expired entry if found
         self.remove_if_expired(key).await;
@@ -100,14 +170,82 @@ impl<T: Clone> Cache<T> {
     /// Set a value in the cache
     ///
     /// Stores the value with the configured TTL. If a value already exists
-    /// for this key, it will be replaced.
+    /// for this key, it will be replaced. If the cache is full, an entry is
+    /// evicted first.
     ///
     /// # Arguments
     /// * `key` - The cache key
     /// * `value` - The value to cache
     pub async fn set(&self, key: String, value: T) {
+        let ttl = self.ttl();
+        let max_entries = self.max_entries.load(Ordering::Relaxed);
+        let mut entries = self.entries.write().await;
+        if max_entries > 0 && !entries.contains_key(&key) {
+            while entries.len() >= max_entries {
+                self.evict_one(&mut entries);
+            }
+        }
+        entries.insert(key, CacheEntry::new(value, ttl));
+    }
+
+    /// Get a value even if it has expired
+    ///
+    /// An expired value can still be revalidated with the server (e.g. by its
+    /// ETag) instead of fetched again. Unlike [`get`](Self::get), this does not
+    /// remove expired entries.
+    ///
+    /// # Arguments
+    /// * `key` - The cache key to look up
+    ///
+    /// # Returns
+    /// The cached value and whether it is still fresh, None if the key does not exist
+    pub async fn peek(&self, key: &str) -> Option<(T, bool)> {
+        let entries = self.entries.read().await;
+        let found = entries
+            .get(key)
+            .map(|entry| (entry.value.clone(), !entry.is_expired()));
+        let counter = match found {
+            Some((_, true)) => &self.hits,
+            _ => &self.misses,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        found
+    }
+
+    /// Mark an entry as expired without removing it
+    ///
+    /// The value stays available to [`peek`](Self::peek) for revalidation.
+    ///
+    /// # Arguments
+    /// * `key` - The cache key to expire
+    ///
+    /// # Returns
+    /// true if an entry was expired, false if key didn't exist
+    pub async fn expire(&self, key: &str) -> bool {
         let mut entries = self.entries.write().await;
-        entries.insert(key, CacheEntry::new(value, self.ttl));
+        match entries.get_mut(key) {
+            Some(entry) => {
+                entry.expires_at = Instant::now();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Get all unexpired values, ordered by key
+    ///
+    /// # Returns
+    /// The cached values, in the same order for the same contents
+    pub async fn values(&self) -> Vec<T> {
+        let entries = self.entries.read().await;
+        let mut live: Vec<(&String, &CacheEntry<T>)> = entries
+            .iter()
+            .filter(|(_, entry)| !entry.is_expired())
+            .collect();
+        live.sort_by(|a, b| a.0.cmp(b.0));
+        live.into_iter()
+            .map(|(_, entry)| entry.value.clone())
+            .collect()
     }
 
     /// Invalidate (remove) a specific cache entry
@@ -201,6 +339,27 @@ impl<T: Clone> Cache<T> {
         let expired = entries.values().filter(|e| e.is_expired()).count();
         (total, expired)
     }
+
+    /// Get the usage statistics of the cache
+    ///
+    /// # Arguments
+    /// * `name` - The name the cache is reported under
+    pub async fn usage(&self, name: &str) -> CacheStats {
+        let (entries, expired) = self.stats().await;
+        CacheStats {
+            name: name.to_string(),
+            entries,
+            expired,
+            max_entries: match self.max_entries.load(Ordering::Relaxed) {
+                0 => None,
+                max => Some(max),
+            },
+            ttl_secs: self.ttl().as_secs(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -226,6 +385,21 @@ mod tests {
         assert_eq!(value, None);
     }
 
+    #[tokio::test]
+    async fn test_cache_peek_expired() {
+        let cache = Cache::new(Duration::from_secs(300));
+        cache.set("key1".to_string(), "value1".to_string()).await;
+        assert_eq!(cache.peek("key1").await, Some(("value1".to_string(), true)));
+
+        assert!(cache.expire("key1").await);
+        assert!(!cache.expire("missing").await);
+        assert_eq!(
+            cache.peek("key1").await,
+            Some(("value1".to_string(), false))
+        );
+        assert_eq!(cache.peek("missing").await, None);
+    }
+
     #[tokio::test]
     async fn test_cache_expiration() {
         // Create cache with very short TTL
@@ -243,6 +417,17 @@ mod tests {
         assert_eq!(cache.get("key1").await, None);
     }
 
+    #[tokio::test]
+    async fn test_cache_values_ordered_by_key() {
+        let cache = Cache::new(Duration::from_secs(300));
+
+        cache.set("b".to_string(), 2).await;
+        cache.set("c".to_string(), 3).await;
+        cache.set("a".to_string(), 1).await;
+
+        assert_eq!(cache.values().await, vec![1, 2, 3]);
+    }
+
     #[tokio::test]
     async fn test_cache_invalidate() {
         let cache = Cache::new(Duration::from_secs(300));
@@ -325,6 +510,47 @@ mod tests {
         assert_eq!(expired, 2);
     }
 
+    #[tokio::test]
+    async fn test_cache_usage() {
+        let cache = Cache::new(Duration::from_secs(300));
+        cache.set("key1".to_string(), 1).await;
+
+        assert_eq!(cache.get("key1").await, Some(1));
+        assert_eq!(cache.get("key2").await, None);
+        assert_eq!(cache.peek("key1").await, Some((1, true)));
+
+        let usage = cache.usage("numbers").await;
+        assert_eq!(usage.name, "numbers");
+        assert_eq!(usage.entries, 1);
+        assert_eq!((usage.hits, usage.misses), (2, 1));
+        assert_eq!(usage.max_entries, None);
+        assert_eq!(usage.ttl_secs, 300);
+    }
+
+    #[tokio::test]
+    async fn test_cache_max_entries() {
+        let cache = Cache::new(Duration::from_secs(300));
+        cache.configure(Duration::from_secs(300), Some(2)).await;
+
+        cache.set("key1".to_string(), 1).await;
+        cache.set("key2".to_string(), 2).await;
+        // Replacing an entry does not evict another one
+        cache.set("key2".to_string(), 2).await;
+        assert_eq!(cache.len().await, 2);
+
+        // key1 expires first, so it makes room for key3
+        cache.set("key3".to_string(), 3).await;
+        assert_eq!(cache.len().await, 2);
+        assert_eq!(cache.get("key1").await, None);
+        assert_eq!(cache.get("key3").await, Some(3));
+        assert_eq!(cache.usage("numbers").await.evictions, 1);
+
+        // Shrinking the cache evicts the excess right away
+        cache.configure(Duration::from_secs(60), Some(1)).await;
+        assert_eq!(cache.len().await, 1);
+        assert_eq!(cache.get("key3").await, Some(3));
+    }
+
     #[tokio::test]
     async fn test_cache_replace_existing() {
         let cache = Cache::new(Duration::from_secs(300));
@@ -0,0 +1,302 @@
+//! Persistent entity cache
+//!
+//! With a cache file configured (`CacheConfig::path`), the cached users,
+//! channels and teams are saved when disconnecting and loaded after the next
+//! login to the same server as the same user, so a restarted client doesn't
+//! fetch hundreds of profiles again.
+//!
+//! Saved entities are revalidated by their `update_at` before use: users
+//! modified since the save are fetched in one request, and channels and teams
+//! are compared with the user's current lists. Entities with a newer version
+//! on the server are replaced, and entities the user can no longer see are
+//! dropped.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, ErrorCode, Result};
+use crate::migrations;
+
+use super::client::MattermostClient;
+use super::types::{MattermostChannel, MattermostTeam, MattermostUser};
+
+/// Cached entities of a session, saved for the next startup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheSnapshot {
+    /// Server URL the entities were fetched from
+    pub server: String,
+    /// The user the entities were fetched as
+    pub user_id: String,
+    /// When the snapshot was saved, in milliseconds since the Unix epoch
+    pub saved_at: i64,
+    #[serde(default)]
+    pub users: Vec<MattermostUser>,
+    #[serde(default)]
+    pub channels: Vec<MattermostChannel>,
+    #[serde(default)]
+    pub teams: Vec<MattermostTeam>,
+}
+
+impl CacheSnapshot {
+    /// Load a snapshot
+    ///
+    /// # Returns
+    /// The snapshot, or None if the file is missing, unreadable or can't be
+    /// migrated to the current format (it is only a cache, so a broken file
+    /// means fetching everything again)
+    pub fn load(path: &Path) -> Option<Self> {
+        let document = migrations::open(&migrations::ENTITY_CACHE, path).ok()??;
+        serde_json::from_value(document).ok()
+    }
+
+    /// Save the snapshot, replacing the file atomically
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_value(self)
+            .map(|document| migrations::ENTITY_CACHE.stamp(document))
+            .and_then(|document| serde_json::to_string(&document))
+            .map_err(|e| {
+                Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize entity cache: {e}"),
+                )
+            })?;
+        let write_error = |e: std::io::Error| {
+            Error::new(
+                ErrorCode::Unknown,
+                format!("Failed to write entity cache {}: {e}", path.display()),
+            )
+        };
+        let temp_path = path.with_extension("tmp");
+        std::fs::write(&temp_path, json).map_err(write_error)?;
+        std::fs::rename(&temp_path, path).map_err(write_error)
+    }
+}
+
+/// An entity with an ID and the time of its last change
+trait Versioned {
+    fn id(&self) -> &str;
+    fn update_at(&self) -> i64;
+}
+
+impl Versioned for MattermostUser {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn update_at(&self) -> i64 {
+        self.update_at
+    }
+}
+
+impl Versioned for MattermostChannel {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn update_at(&self) -> i64 {
+        self.update_at
+    }
+}
+
+impl Versioned for MattermostTeam {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn update_at(&self) -> i64 {
+        self.update_at
+    }
+}
+
+/// Revalidate saved entities against their current versions
+///
+/// # Arguments
+/// * `saved` - The saved entities
+/// * `current` - Current versions of (some of) the entities
+/// * `complete` - Whether `current` lists every entity still visible, so saved
+///   entities missing from it are dropped
+///
+/// # Returns
+/// The saved entities, replaced by their current version where it is newer
+fn revalidate<T: Versioned + Clone>(saved: Vec<T>, current: &[T], complete: bool) -> Vec<T> {
+    let current: HashMap<&str, &T> = current.iter().map(|entity| (entity.id(), entity)).collect();
+    saved
+        .into_iter()
+        .filter_map(|entity| match current.get(entity.id()) {
+            Some(newer) if newer.update_at() > entity.update_at() => Some((*newer).clone()),
+            Some(_) => Some(entity),
+            None if complete => None,
+            None => Some(entity),
+        })
+        .collect()
+}
+
+impl MattermostClient {
+    /// Save the cached users, channels and teams to a file
+    ///
+    /// # Arguments
+    /// * `path` - The file to save to
+    /// * `server` - The server URL the session was configured with
+    ///
+    /// # Returns
+    /// A Result indicating success or failure
+    pub async fn save_entity_cache(&self, path: &Path, server: &str) -> Result<()> {
+        let snapshot = CacheSnapshot {
+            server: server.to_string(),
+            user_id: self.current_user_id().await?,
+            saved_at: chrono::Utc::now().timestamp_millis(),
+            users: self.cached_users().await,
+            channels: self.cached_channels().await,
+            teams: self.cached_teams().await,
+        };
+        snapshot.save(path)
+    }
+
+    /// Fill the caches from a file saved by `save_entity_cache()`
+    ///
+    /// The file is ignored unless it was saved for the same server and user.
+    /// Its entities are revalidated with three requests: users modified since
+    /// the save, and the user's current channels and teams.
+    ///
+    /// # Arguments
+    /// * `path` - The file to load
+    /// * `server` - The server URL the session was configured with
+    ///
+    /// # Returns
+    /// A Result containing the number of entities loaded or an Error
+    pub async fn warm_entity_cache(&self, path: &Path, server: &str) -> Result<usize> {
+        let Some(snapshot) = CacheSnapshot::load(path) else {
+            return Ok(0);
+        };
+        if snapshot.server != server || snapshot.user_id != self.current_user_id().await? {
+            return Ok(0);
+        }
+
+        let user_ids: Vec<String> = snapshot.users.iter().map(|user| user.id.clone()).collect();
+        let modified = if user_ids.is_empty() {
+            Vec::new()
+        } else {
+            self.get_users_modified_since(&user_ids, snapshot.saved_at)
+                .await?
+        };
+        let users = revalidate(snapshot.users, &modified, false);
+
+        let channels = if snapshot.channels.is_empty() {
+            Vec::new()
+        } else {
+            let current = self.get_all_channels_for_user().await?;
+            revalidate(snapshot.channels, &current, true)
+        };
+
+        let teams = if snapshot.teams.is_empty() {
+            Vec::new()
+        } else {
+            let current = self.get_teams().await?;
+            revalidate(snapshot.teams, &current, true)
+        };
+
+        let loaded = users.len() + channels.len() + teams.len();
+        self.warm_caches(users, channels, teams).await;
+        Ok(loaded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn team(id: &str, update_at: i64) -> MattermostTeam {
+        serde_json::from_value(serde_json::json!({
+            "id": id, "create_at": 1, "update_at": update_at, "delete_at": 0,
+            "display_name": id, "name": id, "type": "O"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_revalidate() {
+        let saved = vec![team("a", 1), team("b", 1), team("c", 5)];
+        let current = [team("a", 1), team("b", 3), team("c", 2)];
+
+        let kept = revalidate(saved.clone(), &current[..2], true);
+        let versions: Vec<(&str, i64)> = kept.iter().map(|t| (t.id(), t.update_at)).collect();
+        assert_eq!(versions, vec![("a", 1), ("b", 3)]);
+
+        // An incomplete list only replaces outdated entities
+        let kept = revalidate(saved, &current[1..], false);
+        let versions: Vec<(&str, i64)> = kept.iter().map(|t| (t.id(), t.update_at)).collect();
+        assert_eq!(versions, vec![("a", 1), ("b", 3), ("c", 5)]);
+    }
+
+    #[cfg(feature = "simulation")]
+    #[tokio::test]
+    async fn test_warm_entity_cache() {
+        let server = crate::simulation::add_server("entitycache.sim");
+        server.set_route(
+            "POST",
+            "/users/ids",
+            200,
+            r#"[{"id":"u2","username":"bob2","create_at":1,"update_at":9,"delete_at":0}]"#,
+        );
+        server.set_route(
+            "GET",
+            "/users/me/teams",
+            200,
+            r#"[{"id":"t1","create_at":1,"update_at":1,"delete_at":0,"display_name":"T","name":"t","type":"O"}]"#,
+        );
+
+        let path = std::env::temp_dir().join(format!(
+            "communicator-entity-cache-{}.json",
+            std::process::id()
+        ));
+        let user = |id: &str, name: &str| -> MattermostUser {
+            serde_json::from_value(serde_json::json!({
+                "id": id, "username": name, "create_at": 1, "update_at": 1, "delete_at": 0
+            }))
+            .unwrap()
+        };
+        CacheSnapshot {
+            server: "http://entitycache.sim".to_string(),
+            user_id: "me".to_string(),
+            saved_at: 5,
+            users: vec![user("u1", "alice"), user("u2", "bob")],
+            channels: Vec::new(),
+            teams: vec![team("t1", 1), team("t2", 1)],
+        }
+        .save(&path)
+        .unwrap();
+
+        let client = MattermostClient::new("http://entitycache.sim").unwrap();
+        client.set_user_id(Some("me".to_string())).await;
+        let loaded = client
+            .warm_entity_cache(&path, "http://entitycache.sim")
+            .await
+            .unwrap();
+        assert_eq!(loaded, 3);
+
+        let names: Vec<String> = client
+            .cached_users()
+            .await
+            .into_iter()
+            .map(|user| user.username)
+            .collect();
+        assert_eq!(names, vec!["alice", "bob2"]);
+        assert_eq!(client.cached_teams().await.len(), 1);
+
+        // Another server's cache is ignored
+        let other = MattermostClient::new("http://entitycache.sim").unwrap();
+        other.set_user_id(Some("me".to_string())).await;
+        assert_eq!(
+            other
+                .warm_entity_cache(&path, "http://other.sim")
+                .await
+                .unwrap(),
+            0
+        );
+
+        std::fs::remove_file(&path).unwrap();
+        crate::simulation::remove_server("entitycache.sim");
+    }
+}
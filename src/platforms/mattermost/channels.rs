@@ -59,6 +59,18 @@ impl MattermostClient {
         self.handle_response(response).await
     }
 
+    /// Get the current user's channels in all teams, including DMs and group messages
+    ///
+    /// # Returns
+    /// A Result containing a list of channels or an Error
+    ///
+    /// # API Endpoint
+    /// `GET /api/v4/users/me/channels`
+    pub async fn get_all_channels_for_user(&self) -> Result<Vec<MattermostChannel>> {
+        let response = self.get("/users/me/channels").await?;
+        self.handle_response(response).await
+    }
+
     /// Get a channel by ID
     ///
     /// # Arguments
@@ -140,6 +152,24 @@ impl MattermostClient {
         self.handle_response(response).await
     }
 
+    /// Get all channel memberships of a user in a team
+    ///
+    /// # Arguments
+    /// * `user_id` - The ID of the user
+    /// * `team_id` - The ID of the team
+    ///
+    /// # Returns
+    /// A Result containing the user's channel members or an Error
+    pub async fn get_channel_members_for_user(
+        &self,
+        user_id: &str,
+        team_id: &str,
+    ) -> Result<Vec<ChannelMember>> {
+        let endpoint = format!("/users/{user_id}/teams/{team_id}/channels/members");
+        let response = self.get(&endpoint).await?;
+        self.handle_response(response).await
+    }
+
     /// Get a specific channel member
     ///
     /// # Arguments
@@ -202,6 +232,67 @@ impl MattermostClient {
         }
     }
 
+    /// Grant or revoke a member's channel admin role
+    ///
+    /// # Arguments
+    /// * `channel_id` - The ID of the channel
+    /// * `user_id` - The ID of the member
+    /// * `is_admin` - Whether the member should be a channel admin
+    ///
+    /// # Returns
+    /// A Result indicating success or failure
+    ///
+    /// # API Endpoint
+    /// `PUT /api/v4/channels/{channel_id}/members/{user_id}/schemeRoles`
+    pub async fn update_channel_member_scheme_roles(
+        &self,
+        channel_id: &str,
+        user_id: &str,
+        is_admin: bool,
+    ) -> Result<()> {
+        let body = serde_json::json!({
+            "scheme_admin": is_admin,
+            "scheme_user": true,
+        });
+
+        let endpoint = format!("/channels/{channel_id}/members/{user_id}/schemeRoles");
+        let response = self.put(&endpoint, &body).await?;
+        let _: serde_json::Value = self.handle_response(response).await?;
+        if self.get_user_id().await.as_deref() == Some(user_id) {
+            self.invalidate_channel_permissions(channel_id).await;
+        }
+        Ok(())
+    }
+
+    /// Convert a channel between public and private
+    ///
+    /// # Arguments
+    /// * `channel_id` - The ID of the channel
+    /// * `is_private` - Whether the channel should become private
+    ///
+    /// # Returns
+    /// A Result containing the updated channel or an Error
+    ///
+    /// # API Endpoint
+    /// `PUT /api/v4/channels/{channel_id}/privacy`
+    pub async fn update_channel_privacy(
+        &self,
+        channel_id: &str,
+        is_private: bool,
+    ) -> Result<MattermostChannel> {
+        let body = serde_json::json!({
+            "privacy": if is_private { "P" } else { "O" },
+        });
+
+        let endpoint = format!("/channels/{channel_id}/privacy");
+        let response = self.put(&endpoint, &body).await?;
+        let channel: MattermostChannel = self.handle_response(response).await?;
+        self.update_channel_cache(&channel).await;
+        // Posting and membership permissions differ between public and private
+        self.invalidate_channel_permissions(channel_id).await;
+        Ok(channel)
+    }
+
     // ========================================================================
     // Channel Read State Management
     // ========================================================================
@@ -280,18 +371,25 @@ impl MattermostClient {
             )
         })?;
 
-        let endpoint = format!("/users/{user_id}/teams/{team_id}/channels/members");
-        let response = self.get(&endpoint).await?;
-
-        // The API returns ChannelMember objects, which we need to convert to ChannelUnreadInfo
-        let members: Vec<ChannelMember> = self.handle_response(response).await?;
+        // The API returns ChannelMember objects, which we need to convert to ChannelUnreadInfo.
+        // A member's msg_count is the number of messages read; the unread count is
+        // the channel's total minus that.
+        let members = self.get_channel_members_for_user(&user_id, team_id).await?;
+        let totals: std::collections::HashMap<String, i64> = self
+            .get_channels_for_team(team_id)
+            .await?
+            .into_iter()
+            .map(|c| (c.id, c.total_msg_count))
+            .collect();
 
         Ok(members
             .into_iter()
             .map(|m| ChannelUnreadInfo {
                 team_id: team_id.to_string(),
+                msg_count: totals
+                    .get(&m.channel_id)
+                    .map_or(0, |total| (total - m.msg_count).max(0)),
                 channel_id: m.channel_id,
-                msg_count: m.msg_count,
                 mention_count: m.mention_count,
                 last_viewed_at: m.last_viewed_at,
             })
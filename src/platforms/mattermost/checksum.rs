@@ -0,0 +1,285 @@
+//! File checksums
+//!
+//! Downloads are verified against a SHA-256 checksum when one is known: from
+//! the server's `Repr-Digest` (RFC 9530) or `Digest` (RFC 3230) header, or
+//! recorded when this client uploaded or completely downloaded the file.
+//! A mismatch fails with `ErrorCode::ChecksumMismatch`. Checksums are
+//! lowercase hex.
+//!
+//! Uploads are also indexed by checksum, so uploading the same content to the
+//! same channel again reuses the file while it is not attached to a post.
+
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::sync::Mutex;
+
+use base64::Engine;
+use reqwest::header::HeaderMap;
+
+use crate::error::{Error, ErrorCode, Result};
+
+/// Maximum number of recorded checksums; the oldest are forgotten first
+pub const MAX_CHECKSUMS: usize = 10_000;
+
+/// Incremental SHA-256
+pub struct Hasher(ring::digest::Context);
+
+impl Hasher {
+    pub fn new() -> Self {
+        Hasher(ring::digest::Context::new(&ring::digest::SHA256))
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    /// The hex checksum of the data so far
+    pub fn finish(self) -> String {
+        hex(self.0.finish().as_ref())
+    }
+}
+
+impl Default for Hasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The hex SHA-256 checksum of some data
+pub fn sha256_hex(data: &[u8]) -> String {
+    hex(ring::digest::digest(&ring::digest::SHA256, data).as_ref())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// The SHA-256 checksum a response announces for the whole file, if any
+pub fn from_headers(headers: &HeaderMap) -> Option<String> {
+    // Repr-Digest: sha-256=:<base64>:, sha-512=:...:
+    let repr = headers
+        .get("repr-digest")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| find_digest(value, true));
+    // Digest: SHA-256=<base64>,MD5=...
+    repr.or_else(|| {
+        headers
+            .get("digest")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| find_digest(value, false))
+    })
+}
+
+fn find_digest(value: &str, byte_sequence: bool) -> Option<String> {
+    value.split(',').find_map(|member| {
+        let (algorithm, digest) = member.trim().split_once('=')?;
+        if !algorithm.trim().eq_ignore_ascii_case("sha-256") {
+            return None;
+        }
+        let digest = digest.trim();
+        let digest = if byte_sequence {
+            digest.strip_prefix(':')?.strip_suffix(':')?
+        } else {
+            digest
+        };
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(digest)
+            .ok()?;
+        (bytes.len() == 32).then(|| hex(&bytes))
+    })
+}
+
+/// Check downloaded data against the expected checksum, if one is known
+pub fn verify(file_id: &str, expected: Option<&str>, actual: &str) -> Result<()> {
+    match expected {
+        Some(expected) if !expected.eq_ignore_ascii_case(actual) => Err(Error::new(
+            ErrorCode::ChecksumMismatch,
+            format!("Checksum mismatch for file {file_id}: expected {expected}, got {actual}"),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Map forgetting its oldest entries beyond `MAX_CHECKSUMS`
+#[derive(Debug)]
+struct Bounded<K> {
+    values: HashMap<K, String>,
+    /// Keys in the order they were recorded
+    order: VecDeque<K>,
+}
+
+impl<K> Default for Bounded<K> {
+    fn default() -> Self {
+        Bounded {
+            values: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash> Bounded<K> {
+    fn insert(&mut self, key: K, value: String) {
+        if self.values.insert(key.clone(), value).is_none() {
+            self.order.push_back(key);
+        }
+        while self.order.len() > MAX_CHECKSUMS {
+            if let Some(oldest) = self.order.pop_front() {
+                self.values.remove(&oldest);
+            }
+        }
+    }
+
+    fn remove(&mut self, key: &K) {
+        if self.values.remove(key).is_some() {
+            self.order.retain(|k| k != key);
+        }
+    }
+}
+
+/// Checksums of files uploaded or downloaded by this client
+#[derive(Debug, Default)]
+pub struct ChecksumStore {
+    state: Mutex<Bounded<String>>,
+}
+
+impl ChecksumStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the checksum of a file
+    pub fn record(&self, file_id: &str, checksum: String) {
+        self.state
+            .lock()
+            .unwrap()
+            .insert(file_id.to_string(), checksum);
+    }
+
+    /// The recorded checksum of a file
+    pub fn get(&self, file_id: &str) -> Option<String> {
+        self.state.lock().unwrap().values.get(file_id).cloned()
+    }
+}
+
+/// Channel, file name and checksum of an upload
+type UploadKey = (String, String, String);
+
+/// Files uploaded by this client, for reusing them instead of uploading
+/// identical content again
+///
+/// Mattermost attaches a file to one post only, so an entry is only reusable
+/// until the file is sent; the caller checks that with the server.
+#[derive(Debug, Default)]
+pub struct UploadIndex {
+    state: Mutex<Bounded<UploadKey>>,
+}
+
+impl UploadIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn key(channel_id: &str, filename: &str, checksum: &str) -> UploadKey {
+        (
+            channel_id.to_string(),
+            filename.to_string(),
+            checksum.to_string(),
+        )
+    }
+
+    /// Record an upload
+    pub fn record(&self, channel_id: &str, filename: &str, checksum: &str, file_id: &str) {
+        self.state.lock().unwrap().insert(
+            Self::key(channel_id, filename, checksum),
+            file_id.to_string(),
+        );
+    }
+
+    /// The file ID of an earlier upload of the same content
+    pub fn find(&self, channel_id: &str, filename: &str, checksum: &str) -> Option<String> {
+        self.state
+            .lock()
+            .unwrap()
+            .values
+            .get(&Self::key(channel_id, filename, checksum))
+            .cloned()
+    }
+
+    /// Forget an upload that can no longer be reused
+    pub fn forget(&self, channel_id: &str, filename: &str, checksum: &str) {
+        self.state
+            .lock()
+            .unwrap()
+            .remove(&Self::key(channel_id, filename, checksum));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HELLO_SHA256: &str = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+    #[test]
+    fn test_hashing() {
+        assert_eq!(sha256_hex(b"hello"), HELLO_SHA256);
+        let mut hasher = Hasher::new();
+        hasher.update(b"he");
+        hasher.update(b"llo");
+        assert_eq!(hasher.finish(), HELLO_SHA256);
+    }
+
+    #[test]
+    fn test_from_headers() {
+        let digest = "LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ=";
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "digest",
+            format!("MD5=XUFAKrxLKna5cZ2REBfFkg==, SHA-256={digest}")
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(from_headers(&headers).as_deref(), Some(HELLO_SHA256));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "repr-digest",
+            format!("sha-256=:{digest}:").parse().unwrap(),
+        );
+        assert_eq!(from_headers(&headers).as_deref(), Some(HELLO_SHA256));
+
+        let mut headers = HeaderMap::new();
+        headers.insert("repr-digest", "sha-512=:AAAA:".parse().unwrap());
+        assert_eq!(from_headers(&headers), None);
+    }
+
+    #[test]
+    fn test_verify_and_store() {
+        let store = ChecksumStore::new();
+        store.record("file1", HELLO_SHA256.to_string());
+        let expected = store.get("file1");
+
+        assert!(verify("file1", expected.as_deref(), HELLO_SHA256).is_ok());
+        assert!(verify("file1", None, "anything").is_ok());
+        let error = verify("file1", expected.as_deref(), &sha256_hex(b"other")).unwrap_err();
+        assert_eq!(error.code, ErrorCode::ChecksumMismatch);
+        assert_eq!(store.get("file2"), None);
+    }
+
+    #[test]
+    fn test_upload_index() {
+        let index = UploadIndex::new();
+        index.record("chan1", "a.txt", HELLO_SHA256, "file1");
+        assert_eq!(
+            index.find("chan1", "a.txt", HELLO_SHA256).as_deref(),
+            Some("file1")
+        );
+        // Another channel, name or content is a different upload
+        assert_eq!(index.find("chan2", "a.txt", HELLO_SHA256), None);
+        assert_eq!(index.find("chan1", "b.txt", HELLO_SHA256), None);
+        assert_eq!(index.find("chan1", "a.txt", &sha256_hex(b"other")), None);
+
+        index.forget("chan1", "a.txt", HELLO_SHA256);
+        assert_eq!(index.find("chan1", "a.txt", HELLO_SHA256), None);
+    }
+}
@@ -0,0 +1,243 @@
+//! Circuit breaker for REST requests
+//!
+//! Counts consecutive server errors (5xx) and transport failures (timeouts,
+//! refused connections). Once `failure_threshold` is reached the circuit opens
+//! and requests fail fast with `ErrorCode::ServiceUnavailable` for the cooldown.
+//! After that a single probe request is let through: success closes the
+//! circuit, failure opens it for another cooldown. State changes are queued so
+//! the platform can report them as `circuit_state_changed` events.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::error::{Error, ErrorCode, Result};
+use crate::types::{CircuitBreakerConfig, CircuitState};
+
+#[derive(Debug)]
+struct BreakerState {
+    config: CircuitBreakerConfig,
+    state: CircuitState,
+    consecutive_failures: u32,
+    /// When the circuit last opened
+    opened_at: Option<Instant>,
+    /// When the current half-open probe was let through
+    probe_started_at: Option<Instant>,
+    /// State changes not yet reported as events
+    changes: VecDeque<CircuitState>,
+}
+
+impl BreakerState {
+    fn cooldown(&self) -> Duration {
+        Duration::from_millis(self.config.cooldown_ms)
+    }
+
+    fn transition(&mut self, state: CircuitState) {
+        if self.state != state {
+            self.state = state;
+            self.changes.push_back(state);
+        }
+    }
+
+    fn open(&mut self, now: Instant) {
+        self.opened_at = Some(now);
+        self.probe_started_at = None;
+        self.transition(CircuitState::Open);
+    }
+}
+
+/// Tracks server health and decides whether requests may be sent
+#[derive(Debug)]
+pub(crate) struct CircuitBreaker {
+    inner: Mutex<BreakerState>,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            inner: Mutex::new(BreakerState {
+                config,
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+                probe_started_at: None,
+                changes: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// Replace the configuration, keeping the current state
+    pub(crate) fn set_config(&self, config: CircuitBreakerConfig) {
+        self.inner.lock().unwrap().config = config;
+    }
+
+    /// Get the current configuration
+    pub(crate) fn config(&self) -> CircuitBreakerConfig {
+        self.inner.lock().unwrap().config.clone()
+    }
+
+    /// Get the current state
+    pub(crate) fn state(&self) -> CircuitState {
+        self.inner.lock().unwrap().state
+    }
+
+    /// Check whether a request may be sent now
+    ///
+    /// Lets the first request after the cooldown through as the probe. A probe
+    /// that never reports back (e.g. because its future was dropped) is
+    /// replaced after another cooldown.
+    ///
+    /// # Returns
+    /// A ServiceUnavailable error if the circuit is open
+    pub(crate) fn check(&self, now: Instant) -> Result<()> {
+        let mut inner = self.inner.lock().unwrap();
+        let cooldown = inner.cooldown();
+        let waiting_since = match inner.state {
+            CircuitState::Closed => return Ok(()),
+            CircuitState::Open => inner.opened_at,
+            CircuitState::HalfOpen => inner.probe_started_at,
+        };
+        let elapsed = waiting_since
+            .map(|t| now.saturating_duration_since(t))
+            .unwrap_or(cooldown);
+
+        if elapsed >= cooldown {
+            inner.probe_started_at = Some(now);
+            inner.transition(CircuitState::HalfOpen);
+            return Ok(());
+        }
+
+        let retry_in = (cooldown - elapsed).as_secs().max(1);
+        Err(Error::new(
+            ErrorCode::ServiceUnavailable,
+            format!("Server is unavailable; requests are paused for another {retry_in}s"),
+        ))
+    }
+
+    /// Whether the circuit is open and the cooldown has passed
+    pub(crate) fn probe_due(&self, now: Instant) -> bool {
+        let inner = self.inner.lock().unwrap();
+        inner.state == CircuitState::Open
+            && inner
+                .opened_at
+                .map(|t| now.saturating_duration_since(t) >= inner.cooldown())
+                .unwrap_or(true)
+    }
+
+    /// Record a request the server answered without a server error
+    pub(crate) fn record_success(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.consecutive_failures = 0;
+        inner.opened_at = None;
+        inner.probe_started_at = None;
+        inner.transition(CircuitState::Closed);
+    }
+
+    /// Record a server error or transport failure
+    pub(crate) fn record_failure(&self, now: Instant) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.consecutive_failures = inner.consecutive_failures.saturating_add(1);
+        match inner.state {
+            CircuitState::HalfOpen => inner.open(now),
+            CircuitState::Closed
+                if inner.consecutive_failures >= inner.config.failure_threshold.max(1) =>
+            {
+                inner.open(now)
+            }
+            _ => {}
+        }
+    }
+
+    /// Take the oldest state change not yet reported
+    pub(crate) fn take_change(&self) -> Option<CircuitState> {
+        self.inner.lock().unwrap().changes.pop_front()
+    }
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new(CircuitBreakerConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker() -> CircuitBreaker {
+        CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 3,
+            cooldown_ms: 1000,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_opens_after_threshold() {
+        let breaker = breaker();
+        let now = Instant::now();
+
+        breaker.record_failure(now);
+        breaker.record_failure(now);
+        assert!(breaker.check(now).is_ok());
+        assert_eq!(breaker.take_change(), None);
+
+        breaker.record_failure(now);
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert_eq!(breaker.take_change(), Some(CircuitState::Open));
+        let err = breaker.check(now).unwrap_err();
+        assert_eq!(err.code, ErrorCode::ServiceUnavailable);
+    }
+
+    #[test]
+    fn test_success_resets_failures() {
+        let breaker = breaker();
+        let now = Instant::now();
+
+        breaker.record_failure(now);
+        breaker.record_failure(now);
+        breaker.record_success();
+        breaker.record_failure(now);
+        breaker.record_failure(now);
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_half_open_probe() {
+        let breaker = breaker();
+        let now = Instant::now();
+        for _ in 0..3 {
+            breaker.record_failure(now);
+        }
+
+        let later = now + Duration::from_millis(1000);
+        assert!(breaker.probe_due(later));
+        // Only one probe is let through
+        assert!(breaker.check(later).is_ok());
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert!(breaker.check(later).is_err());
+
+        // A failed probe opens the circuit again
+        breaker.record_failure(later);
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(!breaker.probe_due(later));
+
+        // A successful probe closes it
+        let much_later = later + Duration::from_millis(1000);
+        assert!(breaker.check(much_later).is_ok());
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+
+        let changes: Vec<_> = std::iter::from_fn(|| breaker.take_change()).collect();
+        assert_eq!(
+            changes,
+            vec![
+                CircuitState::Open,
+                CircuitState::HalfOpen,
+                CircuitState::Open,
+                CircuitState::HalfOpen,
+                CircuitState::Closed,
+            ]
+        );
+    }
+}
@@ -1,48 +1,32 @@
 use reqwest::Client;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
-use tokio::sync::RwLock;
-use url::Url;
+use std::time::{Duration, Instant};
+use tokio::sync::{RwLock, Semaphore};
+use tokio_rustls::rustls::ClientConfig;
 
+use crate::dns::Resolver;
 use crate::error::{Error, ErrorCode, Result};
-use crate::types::{ConnectionInfo, ConnectionState};
+use crate::proxy::{is_onion_url, Proxy, ProxyKind};
+use crate::tls::TlsConfig;
+use crate::types::{
+    CacheConfig, CacheStats, ChannelPermissions, CircuitBreakerConfig, CircuitState,
+    ConnectionInfo, ConnectionState, PlatformLimits, RequestStats, RetryPolicy,
+};
 
 use super::cache::Cache;
-use super::types::{MattermostChannel, MattermostTeam, MattermostUser};
-
-/// Configuration for caching API responses
-#[derive(Debug, Clone)]
-pub struct CacheConfig {
-    /// Time-to-live for user cache entries (default: 5 minutes)
-    pub user_ttl: Duration,
-    /// Time-to-live for channel cache entries (default: 2 minutes)
-    pub channel_ttl: Duration,
-    /// Time-to-live for team cache entries (default: 10 minutes)
-    pub team_ttl: Duration,
-    /// Enable caching (default: true)
-    pub enable_cache: bool,
-}
-
-impl Default for CacheConfig {
-    fn default() -> Self {
-        Self {
-            user_ttl: Duration::from_secs(300),    // 5 minutes
-            channel_ttl: Duration::from_secs(120), // 2 minutes
-            team_ttl: Duration::from_secs(600),    // 10 minutes
-            enable_cache: true,
-        }
-    }
-}
-
-impl CacheConfig {
-    /// Create a configuration with caching disabled
-    pub fn disabled() -> Self {
-        Self {
-            enable_cache: false,
-            ..Default::default()
-        }
-    }
-}
+use super::checksum::{ChecksumStore, UploadIndex};
+use super::circuit::CircuitBreaker;
+use super::echo::PendingPosts;
+use super::endpoints::Endpoints;
+use super::files::UploadSessions;
+use super::rate_limit::RateLimitScheduler;
+use super::session::SessionRenewal;
+use super::sso::PendingSso;
+use super::types::{
+    MattermostChannel, MattermostEmoji, MattermostRole, MattermostTeam, MattermostUser,
+};
+use super::users::UserImage;
 
 /// Rate limit information from Mattermost API response headers
 #[derive(Debug, Clone)]
@@ -55,12 +39,27 @@ pub struct RateLimitInfo {
     pub reset_at: u64,
 }
 
+/// Maximum post length (in characters) of Mattermost servers that do not report one
+const DEFAULT_MAX_POST_SIZE: usize = 16383;
+
+/// Maximum upload size of Mattermost servers that do not report one (100 MB)
+const DEFAULT_MAX_FILE_SIZE: u64 = 100 * 1024 * 1024;
+
+/// Timeout of a single request
+const REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
 /// Mattermost client for interacting with Mattermost servers
 pub struct MattermostClient {
-    /// HTTP client for REST API calls
-    pub(crate) http_client: Client,
-    /// Base URL for the Mattermost server (e.g., "https://mattermost.example.com")
-    base_url: Url,
+    /// HTTP client for REST API calls; rebuilt when the proxy or resolver changes
+    http_client: std::sync::RwLock<Client>,
+    /// HTTP or SOCKS5 proxy all connections go through, if any
+    proxy: std::sync::RwLock<Option<Proxy>>,
+    /// TLS configuration of all connections, if not the default
+    tls: std::sync::RwLock<Option<Arc<ClientConfig>>>,
+    /// Resolver for direct connections, with the configured host overrides
+    resolver: std::sync::RwLock<Resolver>,
+    /// Server URLs (e.g., "https://mattermost.example.com") and the one in use
+    endpoints: Arc<Endpoints>,
     /// Authentication token (session token or Personal Access Token)
     token: Arc<RwLock<Option<String>>>,
     /// Current connection state
@@ -71,14 +70,48 @@ pub struct MattermostClient {
     user_id: Arc<RwLock<Option<String>>>,
     /// Rate limit information from last API response
     rate_limit_info: Arc<RwLock<Option<RateLimitInfo>>>,
+    /// Server-imposed limits, loaded on connect
+    limits: Arc<RwLock<PlatformLimits>>,
     /// Cache for user objects
     user_cache: Cache<MattermostUser>,
     /// Cache for channel objects
     channel_cache: Cache<MattermostChannel>,
     /// Cache for team objects
     team_cache: Cache<MattermostTeam>,
-    /// Cache configuration
-    cache_config: CacheConfig,
+    /// Cache for custom emoji metadata, keyed by emoji name
+    emoji_cache: Cache<MattermostEmoji>,
+    /// Cache for custom emoji image bytes, keyed by emoji ID
+    emoji_image_cache: Cache<Vec<u8>>,
+    /// Cache for profile image bytes, keyed by user ID
+    avatar_cache: Cache<UserImage>,
+    /// Cache for role definitions, keyed by role name
+    role_cache: Cache<MattermostRole>,
+    /// Cache for the current user's computed channel permissions, keyed by channel ID
+    permission_cache: Cache<ChannelPermissions>,
+    /// Cache configuration; changed by configure_caches()
+    cache_config: std::sync::RwLock<CacheConfig>,
+    /// Pending post IDs of sent posts whose WebSocket echo has not arrived yet
+    pending_posts: PendingPosts,
+    /// Circuit breaker failing requests fast while the server is unhealthy
+    circuit_breaker: CircuitBreaker,
+    /// Limits the number of requests in flight; replaced when reconfigured
+    request_slots: std::sync::Mutex<Arc<Semaphore>>,
+    /// Queues requests while the server's rate limit is used up
+    rate_limiter: RateLimitScheduler,
+    /// Retries of requests failing with transient errors
+    retry_policy: std::sync::RwLock<RetryPolicy>,
+    /// Largest file (bytes) download_file() buffers in memory
+    download_spill_threshold: AtomicU64,
+    /// Checksums of files uploaded or downloaded by this client
+    checksums: ChecksumStore,
+    /// Files uploaded by this client, reused for identical uploads
+    uploads: UploadIndex,
+    /// Interrupted resumable uploads, continued by the next upload of the file
+    upload_sessions: UploadSessions,
+    /// Renewal of expired sessions
+    session: SessionRenewal,
+    /// SSO login started with begin_sso_login(), waiting for its callback
+    pending_sso: std::sync::Mutex<Option<PendingSso>>,
 }
 
 impl MattermostClient {
@@ -102,31 +135,51 @@ impl MattermostClient {
     /// # Returns
     /// A Result containing the MattermostClient or an Error
     pub fn with_cache_config(base_url: &str, cache_config: CacheConfig) -> Result<Self> {
-        let base_url = Url::parse(base_url)
-            .map_err(|e| Error::new(ErrorCode::InvalidArgument, format!("Invalid URL: {e}")))?;
-
-        let http_client = Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .build()
-            .map_err(|e| {
-                Error::new(
-                    ErrorCode::NetworkError,
-                    format!("Failed to create HTTP client: {e}"),
-                )
-            })?;
+        let resolver = Resolver::default();
+        let http_client = build_http_client(None, &resolver, None)?;
+        let endpoints = Arc::new(Endpoints::new(base_url, http_client.clone())?);
+        let user_ttl = Duration::from_secs(cache_config.user_ttl_secs);
+        let channel_ttl = Duration::from_secs(cache_config.channel_ttl_secs);
+        let team_ttl = Duration::from_secs(cache_config.team_ttl_secs);
+        let emoji_ttl = Duration::from_secs(cache_config.emoji_ttl_secs);
+        let max_entries = cache_config.max_entries;
 
         Ok(Self {
-            http_client,
-            base_url,
+            http_client: std::sync::RwLock::new(http_client),
+            proxy: std::sync::RwLock::new(None),
+            tls: std::sync::RwLock::new(None),
+            resolver: std::sync::RwLock::new(resolver),
+            endpoints,
             token: Arc::new(RwLock::new(None)),
             state: Arc::new(RwLock::new(ConnectionState::Disconnected)),
             team_id: Arc::new(RwLock::new(None)),
             user_id: Arc::new(RwLock::new(None)),
             rate_limit_info: Arc::new(RwLock::new(None)),
-            user_cache: Cache::new(cache_config.user_ttl),
-            channel_cache: Cache::new(cache_config.channel_ttl),
-            team_cache: Cache::new(cache_config.team_ttl),
-            cache_config,
+            limits: Arc::new(RwLock::new(PlatformLimits::default())),
+            user_cache: Cache::new(user_ttl).with_max_entries(max_entries),
+            channel_cache: Cache::new(channel_ttl).with_max_entries(max_entries),
+            team_cache: Cache::new(team_ttl).with_max_entries(max_entries),
+            emoji_cache: Cache::new(emoji_ttl).with_max_entries(max_entries),
+            emoji_image_cache: Cache::new(emoji_ttl).with_max_entries(max_entries),
+            avatar_cache: Cache::new(user_ttl).with_max_entries(max_entries),
+            role_cache: Cache::new(team_ttl).with_max_entries(max_entries),
+            permission_cache: Cache::new(channel_ttl).with_max_entries(max_entries),
+            cache_config: std::sync::RwLock::new(cache_config),
+            pending_posts: PendingPosts::new(),
+            circuit_breaker: CircuitBreaker::default(),
+            request_slots: std::sync::Mutex::new(Arc::new(Semaphore::new(
+                CircuitBreakerConfig::default().max_concurrent_requests,
+            ))),
+            rate_limiter: RateLimitScheduler::default(),
+            retry_policy: std::sync::RwLock::new(RetryPolicy::default()),
+            download_spill_threshold: AtomicU64::new(
+                super::files::DEFAULT_DOWNLOAD_SPILL_THRESHOLD,
+            ),
+            checksums: ChecksumStore::new(),
+            uploads: UploadIndex::new(),
+            upload_sessions: UploadSessions::default(),
+            session: SessionRenewal::new(),
+            pending_sso: std::sync::Mutex::new(None),
         })
     }
 
@@ -163,6 +216,44 @@ impl MattermostClient {
         self.user_id.read().await.clone()
     }
 
+    /// Set the largest file (bytes) download_file() buffers in memory
+    ///
+    /// Larger files must be downloaded with download_file_to_path().
+    pub fn set_download_spill_threshold(&self, bytes: u64) {
+        self.download_spill_threshold
+            .store(bytes, Ordering::Relaxed);
+    }
+
+    /// Get the largest file (bytes) download_file() buffers in memory
+    pub fn download_spill_threshold(&self) -> u64 {
+        self.download_spill_threshold.load(Ordering::Relaxed)
+    }
+
+    /// Checksums of files uploaded or downloaded by this client
+    pub(crate) fn checksums(&self) -> &ChecksumStore {
+        &self.checksums
+    }
+
+    /// Files uploaded by this client, reused for identical uploads
+    pub(crate) fn uploads(&self) -> &UploadIndex {
+        &self.uploads
+    }
+
+    /// Interrupted resumable uploads
+    pub(crate) fn upload_sessions(&self) -> &UploadSessions {
+        &self.upload_sessions
+    }
+
+    /// Renewal of expired sessions
+    pub(crate) fn session(&self) -> &SessionRenewal {
+        &self.session
+    }
+
+    /// SSO login started with begin_sso_login(), waiting for its callback
+    pub(crate) fn pending_sso(&self) -> &std::sync::Mutex<Option<PendingSso>> {
+        &self.pending_sso
+    }
+
     /// Get the current user ID, returning an error if not authenticated
     pub async fn current_user_id(&self) -> Result<String> {
         self.get_user_id().await.ok_or_else(|| {
@@ -173,9 +264,129 @@ impl MattermostClient {
         })
     }
 
-    /// Get the base URL of the Mattermost server
-    pub fn get_base_url(&self) -> &str {
-        self.base_url.as_str()
+    /// Get the base URL of the Mattermost server currently in use
+    pub fn get_base_url(&self) -> String {
+        self.endpoints.active()
+    }
+
+    /// Set the URLs to try when the primary server URL is unreachable
+    ///
+    /// # Arguments
+    /// * `urls` - Fallback URLs in order of preference
+    pub fn set_fallback_urls(&self, urls: &[String]) -> Result<()> {
+        self.endpoints.set_fallbacks(urls)
+    }
+
+    /// Probe the server URLs and switch to the first reachable one
+    ///
+    /// # Returns
+    /// The URL in use afterwards
+    pub async fn select_endpoint(&self) -> String {
+        self.endpoints.select().await
+    }
+
+    /// Take the oldest change of the server URL in use that has not been reported yet
+    pub fn take_endpoint_change(&self) -> Option<String> {
+        self.endpoints.take_change()
+    }
+
+    /// Route all connections through a proxy
+    ///
+    /// # Arguments
+    /// * `proxy_url` - "http://host:port", "socks5://host:port" or
+    ///   "socks5h://host:port" (host names are resolved by the proxy either
+    ///   way), optionally with "user:password@", or None for direct connections
+    ///
+    /// # Returns
+    /// An error if the proxy URL is invalid, or if a server URL is a Tor onion
+    /// service and no SOCKS5 proxy is set
+    pub fn set_proxy(&self, proxy_url: Option<&str>) -> Result<()> {
+        let proxy = proxy_url.map(Proxy::parse).transpose()?;
+        if proxy.as_ref().map(Proxy::kind) != Some(ProxyKind::Socks5) {
+            if let Some(url) = self.endpoints.urls().iter().find(|url| is_onion_url(url)) {
+                return Err(Error::invalid_argument(format!(
+                    "{url} is an onion service; configure a SOCKS5 proxy (e.g. Tor) to reach it"
+                )));
+            }
+        }
+
+        let http_client = build_http_client(proxy.as_ref(), &self.resolver(), self.tls().as_ref())?;
+        self.endpoints.set_http_client(http_client.clone());
+        *self.http_client.write().unwrap() = http_client;
+        *self.proxy.write().unwrap() = proxy;
+        Ok(())
+    }
+
+    /// The proxy connections go through, if any
+    pub fn proxy(&self) -> Option<Proxy> {
+        self.proxy.read().unwrap().clone()
+    }
+
+    /// Use custom TLS settings for all connections
+    ///
+    /// # Arguments
+    /// * `config` - CA bundle, client certificate and whether to accept
+    ///   invalid server certificates
+    ///
+    /// # Returns
+    /// An error if a certificate or key file can't be used
+    pub fn set_tls(&self, config: &TlsConfig) -> Result<()> {
+        let tls = if config.is_default() {
+            None
+        } else {
+            Some(config.client_config()?)
+        };
+        let http_client = build_http_client(self.proxy().as_ref(), &self.resolver(), tls.as_ref())?;
+        self.endpoints.set_http_client(http_client.clone());
+        *self.http_client.write().unwrap() = http_client;
+        *self.tls.write().unwrap() = tls;
+        Ok(())
+    }
+
+    /// The TLS configuration of all connections, if not the default
+    pub fn tls(&self) -> Option<Arc<ClientConfig>> {
+        self.tls.read().unwrap().clone()
+    }
+
+    /// Connect to fixed IP addresses instead of resolving some host names
+    ///
+    /// # Arguments
+    /// * `hosts` - Host names mapped to the IP address to use for them
+    ///
+    /// # Returns
+    /// An error if one of the addresses is not an IP address
+    pub fn set_host_overrides(
+        &self,
+        hosts: &std::collections::HashMap<String, String>,
+    ) -> Result<()> {
+        let resolver = Resolver::new(hosts)?;
+        let http_client = build_http_client(self.proxy().as_ref(), &resolver, self.tls().as_ref())?;
+        self.endpoints.set_http_client(http_client.clone());
+        *self.http_client.write().unwrap() = http_client;
+        *self.resolver.write().unwrap() = resolver;
+        Ok(())
+    }
+
+    /// The resolver for direct connections
+    pub fn resolver(&self) -> Resolver {
+        self.resolver.read().unwrap().clone()
+    }
+
+    /// The HTTP client for REST API calls
+    pub(crate) fn http_client(&self) -> Client {
+        self.http_client.read().unwrap().clone()
+    }
+
+    /// HTTP client that returns redirects instead of following them
+    pub(crate) fn http_client_without_redirects(&self) -> Result<Client> {
+        let builder =
+            http_client_builder(self.proxy().as_ref(), &self.resolver(), self.tls().as_ref())?;
+        build(builder.redirect(reqwest::redirect::Policy::none()))
+    }
+
+    /// The server URLs, shared with the WebSocket connection for reconnects
+    pub(crate) fn endpoints(&self) -> Arc<Endpoints> {
+        self.endpoints.clone()
     }
 
     /// Update the connection state
@@ -217,6 +428,45 @@ impl MattermostClient {
         self.rate_limit_info.read().await.clone()
     }
 
+    /// Load the server's limits from its client configuration
+    ///
+    /// Falls back to Mattermost's defaults for limits the server does not
+    /// report. The request rate comes from the rate limit headers, if the
+    /// server has rate limiting enabled.
+    ///
+    /// # Returns
+    /// A Result containing the loaded limits or an Error
+    pub async fn load_server_limits(&self) -> Result<PlatformLimits> {
+        let response = self.get("/config/client?format=old").await?;
+        let config: std::collections::HashMap<String, serde_json::Value> =
+            self.handle_response(response).await?;
+
+        let number = |key: &str| {
+            config.get(key).and_then(|v| match v {
+                serde_json::Value::String(s) => s.parse::<u64>().ok(),
+                _ => v.as_u64(),
+            })
+        };
+
+        let limits = PlatformLimits {
+            max_message_length: Some(
+                number("MaxPostSize")
+                    .map(|n| n as usize)
+                    .unwrap_or(DEFAULT_MAX_POST_SIZE),
+            ),
+            max_file_size: Some(number("MaxFileSize").unwrap_or(DEFAULT_MAX_FILE_SIZE)),
+            max_requests_per_second: self.get_rate_limit_info().await.map(|info| info.limit),
+        };
+
+        *self.limits.write().await = limits.clone();
+        Ok(limits)
+    }
+
+    /// Get the server limits loaded by [`load_server_limits`](Self::load_server_limits)
+    pub async fn server_limits(&self) -> PlatformLimits {
+        self.limits.read().await.clone()
+    }
+
     /// Extract rate limit information from response headers
     ///
     /// # Arguments
@@ -288,6 +538,271 @@ impl MattermostClient {
         }
     }
 
+    /// Configure the concurrent request limit and the circuit breaker
+    ///
+    /// Requests already waiting for a slot keep the previous limit.
+    pub fn set_circuit_breaker_config(&self, config: CircuitBreakerConfig) {
+        if config.max_concurrent_requests != self.circuit_breaker.config().max_concurrent_requests {
+            *self.request_slots.lock().unwrap() =
+                Arc::new(Semaphore::new(config.max_concurrent_requests.max(1)));
+        }
+        self.circuit_breaker.set_config(config);
+    }
+
+    /// Get the current circuit breaker configuration
+    pub fn circuit_breaker_config(&self) -> CircuitBreakerConfig {
+        self.circuit_breaker.config()
+    }
+
+    /// Set the retry policy of requests failing with transient errors
+    pub fn set_retry_policy(&self, policy: RetryPolicy) {
+        *self.retry_policy.write().unwrap() = policy;
+    }
+
+    /// Get the current retry policy
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry_policy.read().unwrap().clone()
+    }
+
+    /// Get the current circuit breaker state
+    pub fn circuit_state(&self) -> CircuitState {
+        self.circuit_breaker.state()
+    }
+
+    /// Issue a pending post ID for a post about to be created
+    ///
+    /// # Returns
+    /// None if no user is logged in
+    pub(crate) async fn issue_pending_post_id(&self) -> Option<String> {
+        let user_id = self.get_user_id().await?;
+        Some(
+            self.pending_posts
+                .issue(&user_id, chrono::Utc::now().timestamp_millis())
+                .await,
+        )
+    }
+
+    /// Check whether a post's pending ID was issued by this client
+    ///
+    /// # Returns
+    /// true the first time the echo of a post sent by this client is seen
+    pub async fn take_pending_post(&self, pending_post_id: &str) -> bool {
+        self.pending_posts.take(pending_post_id).await
+    }
+
+    /// Forget the pending IDs of all sent posts
+    pub(crate) async fn forget_pending_posts(&self) {
+        self.pending_posts.reset().await;
+    }
+
+    /// Statistics of requests queued for the server's rate limit
+    pub fn request_stats(&self) -> RequestStats {
+        self.rate_limiter.stats()
+    }
+
+    /// Take the oldest circuit state change that has not been reported yet
+    pub fn take_circuit_state_change(&self) -> Option<CircuitState> {
+        self.circuit_breaker.take_change()
+    }
+
+    /// Probe the server if the circuit is open and its cooldown has passed
+    ///
+    /// Lets the circuit close again once the server recovers, even if the
+    /// application is not making requests of its own.
+    pub async fn probe_if_due(&self) {
+        if self.circuit_breaker.probe_due(Instant::now()) {
+            // The outcome is recorded by the circuit breaker
+            let _ = self.get("/system/ping").await;
+        }
+    }
+
+    /// Send a request through the circuit breaker and the concurrent request limit
+    ///
+    /// An authenticated request rejected with 401 Unauthorized renews the
+    /// session if possible and is sent again with the new token.
+    ///
+    /// # Arguments
+    /// * `request` - The request to send, with authentication already applied
+    /// * `method` - Name of the request used in error messages (e.g. "GET")
+    ///
+    /// # Returns
+    /// The response (including error responses), a ServiceUnavailable error if
+    /// the circuit is open, or a NetworkError if the request could not be sent
+    #[tracing::instrument(level = "debug", name = "request", skip_all, fields(method = %method))]
+    pub(crate) async fn send_request(
+        &self,
+        request: reqwest::RequestBuilder,
+        method: &str,
+    ) -> Result<reqwest::Response> {
+        let retry = request.try_clone();
+        let token = self.get_token().await;
+        let response = self.send_retrying(request, method).await?;
+        if response.status() != reqwest::StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+
+        let (Some(retry), Some(token)) = (retry, token) else {
+            return Ok(response);
+        };
+        let Some(token) = self.renew_session(&token).await else {
+            return Ok(response);
+        };
+        tracing::debug!("Session renewed after 401 Unauthorized, sending again");
+        match super::session::with_token(retry, &token) {
+            Some(retry) => self.send_retrying(retry, method).await,
+            None => Ok(response),
+        }
+    }
+
+    /// Send a request, retrying transient failures as the retry policy allows
+    ///
+    /// A retry that would not finish before the deadline is not attempted; the
+    /// last response or error is returned instead.
+    async fn send_retrying(
+        &self,
+        mut request: reqwest::RequestBuilder,
+        method: &str,
+    ) -> Result<reqwest::Response> {
+        let policy = self.retry_policy();
+        if !policy.retries_method(method) {
+            return self.send_scheduled(request, method).await;
+        }
+
+        let mut attempt = 1;
+        loop {
+            let retry = request.try_clone();
+            let result = self.send_scheduled(request, method).await;
+            let transient = match &result {
+                Ok(response) => policy.retry_on.contains(&response.status().as_u16()),
+                Err(e) => e.code == ErrorCode::NetworkError,
+            };
+            let Some(retry) = retry.filter(|_| transient && attempt < policy.max_attempts) else {
+                return result;
+            };
+            let backoff = policy.backoff(attempt - 1);
+            match crate::deadline::remaining() {
+                Ok(Some(remaining)) if remaining <= backoff => return result,
+                Err(_) => return result,
+                _ => {}
+            }
+            tracing::debug!(
+                attempt,
+                backoff_ms = backoff.as_millis() as u64,
+                "Transient failure, retrying"
+            );
+            tokio::time::sleep(backoff).await;
+            attempt += 1;
+            request = retry;
+        }
+    }
+
+    /// Send a request within the server's rate limit
+    ///
+    /// A request refused with 429 Too Many Requests is sent again once the
+    /// limit resets, a few times at most; the last 429 response is returned.
+    async fn send_scheduled(
+        &self,
+        mut request: reqwest::RequestBuilder,
+        method: &str,
+    ) -> Result<reqwest::Response> {
+        let mut attempt = 0;
+        loop {
+            let retry = request.try_clone();
+            let response = self.send_once(request, method).await?;
+            let refused = self.rate_limiter.record(&response, attempt);
+            match retry {
+                Some(retry) if refused => {
+                    tracing::debug!(attempt, "Rate limited, sending again once the limit resets");
+                    attempt += 1;
+                    request = retry;
+                }
+                _ => return Ok(response),
+            }
+        }
+    }
+
+    async fn send_once(
+        &self,
+        request: reqwest::RequestBuilder,
+        method: &str,
+    ) -> Result<reqwest::Response> {
+        crate::deadline::remaining()?;
+        self.circuit_breaker.check(Instant::now())?;
+
+        // Requests queued for the rate limit don't hold a slot meanwhile
+        match crate::deadline::remaining()? {
+            Some(remaining) => tokio::time::timeout(remaining, self.rate_limiter.acquire())
+                .await
+                .map_err(|_| crate::deadline::expired())?,
+            None => self.rate_limiter.acquire().await,
+        }
+
+        let slots = self.request_slots.lock().unwrap().clone();
+        let acquire = slots.acquire();
+        let permit = match crate::deadline::remaining()? {
+            Some(remaining) => tokio::time::timeout(remaining, acquire)
+                .await
+                .map_err(|_| crate::deadline::expired())?,
+            None => acquire.await,
+        };
+        let _permit = permit
+            .map_err(|_| Error::new(ErrorCode::InvalidState, "Request limiter has been closed"))?;
+
+        let started = Instant::now();
+        match send(apply_deadline(request)?).await {
+            Ok(response) => {
+                let status = response.status().as_u16();
+                let elapsed_ms = started.elapsed().as_millis() as u64;
+                crate::capture::record_http(
+                    method,
+                    response.url().as_str(),
+                    Some(status),
+                    response
+                        .headers()
+                        .get("X-Request-Id")
+                        .and_then(|v| v.to_str().ok()),
+                    started.elapsed(),
+                    None,
+                );
+                let path = response.url().path();
+                if response.status().is_server_error() {
+                    tracing::warn!(path, status, elapsed_ms, "Server error");
+                    self.circuit_breaker.record_failure(Instant::now());
+                } else {
+                    tracing::debug!(path, status, elapsed_ms, "Response");
+                    self.circuit_breaker.record_success();
+                }
+                Ok(response)
+            }
+            Err(e) => {
+                // The source leaves out the URL, which may carry a token
+                let cause = std::error::Error::source(&e)
+                    .map_or_else(|| "request failed".to_string(), |cause| cause.to_string());
+                crate::capture::record_http(
+                    method,
+                    e.url().map_or("", |url| url.as_str()),
+                    None,
+                    None,
+                    started.elapsed(),
+                    Some(cause),
+                );
+                // Running out of the caller's time budget says nothing about the server
+                if e.is_timeout() && crate::deadline::remaining().is_err() {
+                    return Err(crate::deadline::expired());
+                }
+                // A request that could not be built says nothing about the server
+                if !e.is_builder() {
+                    self.circuit_breaker.record_failure(Instant::now());
+                }
+                tracing::warn!(error = %e, "Request failed");
+                Err(Error::new(
+                    ErrorCode::NetworkError,
+                    format!("{method} request failed: {e}"),
+                ))
+            }
+        }
+    }
+
     /// Build the full API URL for a given endpoint
     ///
     /// # Arguments
@@ -297,7 +812,7 @@ impl MattermostClient {
     /// The full URL string
     pub fn api_url(&self, endpoint: &str) -> String {
         let endpoint = endpoint.trim_start_matches('/');
-        let base = self.base_url.as_str().trim_end_matches('/');
+        let base = self.endpoints.active();
         format!("{base}/api/v4/{endpoint}")
     }
 
@@ -310,16 +825,13 @@ impl MattermostClient {
     /// A Result containing the reqwest::Response or an Error
     pub async fn get(&self, endpoint: &str) -> Result<reqwest::Response> {
         let url = self.api_url(endpoint);
-        let mut request = self.http_client.get(&url);
+        let mut request = self.http_client().get(&url);
 
         if let Some(token) = self.get_token().await {
             request = request.bearer_auth(token);
         }
 
-        request
-            .send()
-            .await
-            .map_err(|e| Error::new(ErrorCode::NetworkError, format!("GET request failed: {e}")))
+        self.send_request(request, "GET").await
     }
 
     /// Make a POST request to the Mattermost API
@@ -336,17 +848,13 @@ impl MattermostClient {
         body: &T,
     ) -> Result<reqwest::Response> {
         let url = self.api_url(endpoint);
-        let mut request = self.http_client.post(&url);
+        let mut request = self.http_client().post(&url);
 
         if let Some(token) = self.get_token().await {
             request = request.bearer_auth(token);
         }
 
-        request
-            .json(body)
-            .send()
-            .await
-            .map_err(|e| Error::new(ErrorCode::NetworkError, format!("POST request failed: {e}")))
+        self.send_request(request.json(body), "POST").await
     }
 
     /// Make a PUT request to the Mattermost API
@@ -363,17 +871,13 @@ impl MattermostClient {
         body: &T,
     ) -> Result<reqwest::Response> {
         let url = self.api_url(endpoint);
-        let mut request = self.http_client.put(&url);
+        let mut request = self.http_client().put(&url);
 
         if let Some(token) = self.get_token().await {
             request = request.bearer_auth(token);
         }
 
-        request
-            .json(body)
-            .send()
-            .await
-            .map_err(|e| Error::new(ErrorCode::NetworkError, format!("PUT request failed: {e}")))
+        self.send_request(request.json(body), "PUT").await
     }
 
     /// Make a DELETE request to the Mattermost API
@@ -385,18 +889,13 @@ impl MattermostClient {
     /// A Result containing the reqwest::Response or an Error
     pub async fn delete(&self, endpoint: &str) -> Result<reqwest::Response> {
         let url = self.api_url(endpoint);
-        let mut request = self.http_client.delete(&url);
+        let mut request = self.http_client().delete(&url);
 
         if let Some(token) = self.get_token().await {
             request = request.bearer_auth(token);
         }
 
-        request.send().await.map_err(|e| {
-            Error::new(
-                ErrorCode::NetworkError,
-                format!("DELETE request failed: {e}"),
-            )
-        })
+        self.send_request(request, "DELETE").await
     }
 
     /// Map Mattermost error ID to appropriate ErrorCode
@@ -408,12 +907,11 @@ impl MattermostClient {
     /// The appropriate ErrorCode for this error ID
     fn map_mattermost_error_id(error_id: &str) -> ErrorCode {
         // Based on common Mattermost error ID patterns
-        // Check MFA-specific errors first (before general login errors)
-        if error_id.contains("mfa_required") {
-            ErrorCode::AuthenticationFailed
-        } else if error_id.contains("invalid_mfa") || error_id.contains("mfa") {
-            ErrorCode::AuthenticationFailed
-        } else if error_id.contains("invalid_credentials") || error_id.contains("login") {
+        // MFA errors (mfa_required, invalid_mfa) and login errors are all auth failures
+        if error_id.contains("mfa")
+            || error_id.contains("invalid_credentials")
+            || error_id.contains("login")
+        {
             ErrorCode::AuthenticationFailed
         } else if error_id.contains("not_found") {
             ErrorCode::NotFound
@@ -423,6 +921,8 @@ impl MattermostClient {
             ErrorCode::RateLimited
         } else if error_id.contains("timeout") {
             ErrorCode::Timeout
+        } else if error_id.contains("too_large") {
+            ErrorCode::FileTooLarge
         } else if error_id.contains("invalid_param") || error_id.contains("invalid_") {
             ErrorCode::InvalidArgument
         } else {
@@ -459,6 +959,7 @@ impl MattermostClient {
                 Error::new(ErrorCode::Unknown, format!("Failed to parse response: {e}"))
             })
         } else {
+            tracing::debug!(status = status.as_u16(), request_id, "Error response");
             // Error case - try to parse as Mattermost error response
             let error_text = response
                 .text()
@@ -485,6 +986,9 @@ impl MattermostClient {
                 let error_code = match status.as_u16() {
                     401 | 403 => ErrorCode::AuthenticationFailed,
                     404 => ErrorCode::NotFound,
+                    // A body over a server or proxy limit; uploads report
+                    // this as FileTooLarge
+                    413 => ErrorCode::InvalidArgument,
                     429 => ErrorCode::RateLimited,
                     500..=599 => ErrorCode::NetworkError,
                     _ => ErrorCode::Unknown,
@@ -505,6 +1009,22 @@ impl MattermostClient {
         }
     }
 
+    /// Check the response to an upload and extract the JSON body
+    ///
+    /// Like [`handle_response`](Self::handle_response), except that a request
+    /// rejected with HTTP 413 is reported as `FileTooLarge`.
+    pub(crate) async fn handle_upload_response<T: serde::de::DeserializeOwned>(
+        &self,
+        response: reqwest::Response,
+    ) -> Result<T> {
+        self.handle_response(response).await.map_err(|mut error| {
+            if error.http_status() == Some(413) {
+                error.code = ErrorCode::FileTooLarge;
+            }
+            error
+        })
+    }
+
     /// Get a list of custom emojis
     ///
     /// # Arguments
@@ -554,6 +1074,44 @@ impl MattermostClient {
         self.handle_response(response).await
     }
 
+    /// Download the image for a custom emoji
+    ///
+    /// # Arguments
+    /// * `emoji_id` - The ID of the emoji
+    ///
+    /// # Returns
+    /// A Result containing the raw image bytes or an Error
+    pub async fn get_emoji_image(&self, emoji_id: &str) -> Result<Vec<u8>> {
+        let endpoint = format!("/emoji/{}/image", emoji_id);
+        let response = self.get(&endpoint).await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_code = match status.as_u16() {
+                404 => ErrorCode::NotFound,
+                401 => ErrorCode::AuthenticationFailed,
+                403 => ErrorCode::PermissionDenied,
+                _ => ErrorCode::NetworkError,
+            };
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(Error::new(
+                error_code,
+                format!("Failed to download emoji image: {error_text}"),
+            )
+            .with_http_status(status.as_u16()));
+        }
+
+        response.bytes().await.map(|b| b.to_vec()).map_err(|e| {
+            Error::new(
+                ErrorCode::NetworkError,
+                format!("Failed to read emoji image data: {e}"),
+            )
+        })
+    }
+
     // ========================================================================
     // Cached API Methods
     // ========================================================================
@@ -570,7 +1128,7 @@ impl MattermostClient {
     /// A Result containing the user information or an Error
     pub async fn get_user_cached(&self, user_id: &str) -> Result<MattermostUser> {
         // Return early if caching is disabled
-        if !self.cache_config.enable_cache {
+        if !self.cache_enabled() {
             return self.get_user(user_id).await;
         }
 
@@ -600,7 +1158,7 @@ impl MattermostClient {
     /// A Result containing the channel information or an Error
     pub async fn get_channel_cached(&self, channel_id: &str) -> Result<MattermostChannel> {
         // Return early if caching is disabled
-        if !self.cache_config.enable_cache {
+        if !self.cache_enabled() {
             return self.get_channel(channel_id).await;
         }
 
@@ -632,7 +1190,7 @@ impl MattermostClient {
     /// A Result containing the team information or an Error
     pub async fn get_team_cached(&self, team_id: &str) -> Result<MattermostTeam> {
         // Return early if caching is disabled
-        if !self.cache_config.enable_cache {
+        if !self.cache_enabled() {
             return self.get_team(team_id).await;
         }
 
@@ -672,7 +1230,7 @@ impl MattermostClient {
         user_ids: &[String],
     ) -> Result<Vec<MattermostUser>> {
         // Return early if caching is disabled
-        if !self.cache_config.enable_cache {
+        if !self.cache_enabled() {
             return self.get_users_by_ids(user_ids).await;
         }
 
@@ -711,6 +1269,234 @@ impl MattermostClient {
         Ok(ordered_result)
     }
 
+    /// Get roles by name with caching
+    ///
+    /// Only roles missing from the cache are fetched, in one batch request.
+    ///
+    /// # Arguments
+    /// * `names` - The role names to retrieve
+    ///
+    /// # Returns
+    /// A Result containing the roles that exist, in no particular order
+    pub async fn get_roles_by_names_cached(&self, names: &[String]) -> Result<Vec<MattermostRole>> {
+        if !self.cache_enabled() {
+            return self.get_roles_by_names(names).await;
+        }
+
+        let mut result = Vec::with_capacity(names.len());
+        let mut uncached_names = Vec::new();
+        for name in names {
+            if let Some(role) = self.role_cache.get(name).await {
+                result.push(role);
+            } else {
+                uncached_names.push(name.clone());
+            }
+        }
+
+        if !uncached_names.is_empty() {
+            for role in self.get_roles_by_names(&uncached_names).await? {
+                self.role_cache.set(role.name.clone(), role.clone()).await;
+                result.push(role);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Get the current user's permissions in channels with caching
+    ///
+    /// # Arguments
+    /// * `channels` - The channels to get permissions for
+    ///
+    /// # Returns
+    /// A Result containing the permissions keyed by channel ID
+    pub async fn get_channels_permissions_cached(
+        &self,
+        channels: &[MattermostChannel],
+    ) -> Result<std::collections::HashMap<String, ChannelPermissions>> {
+        if !self.cache_enabled() {
+            return self.get_channels_permissions(channels).await;
+        }
+
+        let mut result = std::collections::HashMap::with_capacity(channels.len());
+        let mut uncached = Vec::new();
+        for channel in channels {
+            if let Some(permissions) = self.permission_cache.get(&channel.id).await {
+                result.insert(channel.id.clone(), permissions);
+            } else {
+                uncached.push(channel.clone());
+            }
+        }
+
+        if !uncached.is_empty() {
+            for (channel_id, permissions) in self.get_channels_permissions(&uncached).await? {
+                self.permission_cache
+                    .set(channel_id.clone(), permissions)
+                    .await;
+                result.insert(channel_id, permissions);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Get a custom emoji by name with caching
+    ///
+    /// Checks the cache first. If not found or expired, fetches from the API
+    /// and stores in cache before returning.
+    ///
+    /// # Arguments
+    /// * `emoji_name` - The name of the emoji (without colons)
+    ///
+    /// # Returns
+    /// A Result containing the MattermostEmoji or an Error
+    pub async fn get_emoji_by_name_cached(&self, emoji_name: &str) -> Result<MattermostEmoji> {
+        // Return early if caching is disabled
+        if !self.cache_enabled() {
+            return self.get_emoji_by_name(emoji_name).await;
+        }
+
+        // Check cache first
+        if let Some(emoji) = self.emoji_cache.get(emoji_name).await {
+            return Ok(emoji);
+        }
+
+        // Cache miss - fetch from API
+        let emoji = self.get_emoji_by_name(emoji_name).await?;
+
+        // Store in cache before returning
+        self.emoji_cache
+            .set(emoji_name.to_string(), emoji.clone())
+            .await;
+
+        Ok(emoji)
+    }
+
+    /// Get a custom emoji image by emoji name or ID with caching
+    ///
+    /// Resolves the emoji to its ID (if a name was given) and downloads the
+    /// image, caching both the name lookup and the image bytes.
+    ///
+    /// # Arguments
+    /// * `name_or_id` - The emoji name (with or without surrounding colons) or emoji ID
+    ///
+    /// # Returns
+    /// A Result containing the raw image bytes or an Error
+    ///
+    /// # Notes
+    /// Names are tried first since that is what appears in message text
+    /// (e.g. `:party_parrot:`). If no emoji has that name and the value looks
+    /// like a Mattermost ID, it is used as an emoji ID instead.
+    pub async fn get_emoji_image_cached(&self, name_or_id: &str) -> Result<Vec<u8>> {
+        let key = name_or_id.trim().trim_matches(':');
+        if key.is_empty() {
+            return Err(Error::invalid_argument("Emoji name or ID cannot be empty"));
+        }
+
+        let emoji_id = match self.get_emoji_by_name_cached(key).await {
+            Ok(emoji) => emoji.id,
+            Err(e) if e.code == ErrorCode::NotFound && is_mattermost_id(key) => key.to_string(),
+            Err(e) => return Err(e),
+        };
+
+        // Return early if caching is disabled
+        if !self.cache_enabled() {
+            return self.get_emoji_image(&emoji_id).await;
+        }
+
+        if let Some(image) = self.emoji_image_cache.get(&emoji_id).await {
+            return Ok(image);
+        }
+
+        let image = self.get_emoji_image(&emoji_id).await?;
+        self.emoji_image_cache.set(emoji_id, image.clone()).await;
+
+        Ok(image)
+    }
+
+    /// Get a user's profile image with caching
+    ///
+    /// Once a cached image expires (or its user was updated), it is
+    /// revalidated by its ETag, so an unchanged image is not downloaded again.
+    ///
+    /// # Arguments
+    /// * `user_id` - The ID of the user
+    ///
+    /// # Returns
+    /// A Result containing the raw image bytes or an Error
+    pub async fn get_user_image_cached(&self, user_id: &str) -> Result<Vec<u8>> {
+        // Return early if caching is disabled
+        if !self.cache_enabled() {
+            return self.get_user_image(user_id).await;
+        }
+
+        let cached = match self.avatar_cache.peek(user_id).await {
+            Some((image, true)) => return Ok(image.data),
+            Some((image, false)) => Some(image),
+            None => None,
+        };
+
+        let etag = cached.as_ref().and_then(|image| image.etag.as_deref());
+        let image = match self.get_user_image_if_modified(user_id, etag).await? {
+            Some(image) => image,
+            // Not modified: keep the cached image for another TTL
+            None => cached.ok_or_else(|| {
+                Error::new(
+                    ErrorCode::Unknown,
+                    "Profile image not modified but not cached",
+                )
+            })?,
+        };
+        self.avatar_cache
+            .set(user_id.to_string(), image.clone())
+            .await;
+
+        Ok(image.data)
+    }
+
+    /// Get a user from the cache, without a request
+    pub async fn cached_user(&self, user_id: &str) -> Option<MattermostUser> {
+        self.user_cache.get(user_id).await
+    }
+
+    /// Get all cached users, ordered by ID, without a request
+    pub async fn cached_users(&self) -> Vec<MattermostUser> {
+        self.user_cache.values().await
+    }
+
+    /// Get all cached channels, ordered by ID, without a request
+    pub async fn cached_channels(&self) -> Vec<MattermostChannel> {
+        self.channel_cache.values().await
+    }
+
+    /// Get all cached teams, ordered by ID, without a request
+    pub async fn cached_teams(&self) -> Vec<MattermostTeam> {
+        self.team_cache.values().await
+    }
+
+    /// Fill the caches with entities loaded from elsewhere, e.g. a cache file
+    ///
+    /// Does nothing if caching is disabled.
+    pub(crate) async fn warm_caches(
+        &self,
+        users: Vec<MattermostUser>,
+        channels: Vec<MattermostChannel>,
+        teams: Vec<MattermostTeam>,
+    ) {
+        if !self.cache_enabled() {
+            return;
+        }
+        for user in users {
+            self.user_cache.set(user.id.clone(), user).await;
+        }
+        for channel in channels {
+            self.channel_cache.set(channel.id.clone(), channel).await;
+        }
+        for team in teams {
+            self.team_cache.set(team.id.clone(), team).await;
+        }
+    }
+
     /// Invalidate a user in the cache
     ///
     /// This is typically called when a WebSocket event indicates
@@ -720,6 +1506,8 @@ impl MattermostClient {
     /// * `user_id` - The ID of the user to invalidate
     pub async fn invalidate_user_cache(&self, user_id: &str) {
         self.user_cache.invalidate(user_id).await;
+        // Kept for revalidation: most user updates leave the image unchanged
+        self.avatar_cache.expire(user_id).await;
     }
 
     /// Invalidate a channel in the cache
@@ -733,6 +1521,26 @@ impl MattermostClient {
         self.channel_cache.invalidate(channel_id).await;
     }
 
+    /// Invalidate the current user's permissions in a channel
+    ///
+    /// This is typically called when a WebSocket event indicates that the
+    /// channel or the user's channel membership has changed.
+    ///
+    /// # Arguments
+    /// * `channel_id` - The ID of the channel to invalidate
+    pub async fn invalidate_channel_permissions(&self, channel_id: &str) {
+        self.permission_cache.invalidate(channel_id).await;
+    }
+
+    /// Invalidate all roles and computed channel permissions
+    ///
+    /// This is typically called when a role definition or the current user's
+    /// system roles change, which may affect every channel.
+    pub async fn invalidate_permission_caches(&self) {
+        self.role_cache.clear().await;
+        self.permission_cache.clear().await;
+    }
+
     /// Invalidate a team in the cache
     ///
     /// This is typically called when a WebSocket event indicates
@@ -767,6 +1575,17 @@ impl MattermostClient {
         self.channel_cache.invalidate(channel_id).await;
     }
 
+    /// Remove a custom emoji and its image from the caches
+    ///
+    /// This is typically called after deleting an emoji.
+    ///
+    /// # Arguments
+    /// * `emoji` - The emoji to remove from cache
+    pub async fn remove_emoji_from_cache(&self, emoji: &MattermostEmoji) {
+        self.emoji_cache.invalidate(&emoji.name).await;
+        self.emoji_image_cache.invalidate(&emoji.id).await;
+    }
+
     /// Clear all caches
     ///
     /// This is useful when major changes occur (e.g., user logout/login,
@@ -775,35 +1594,133 @@ impl MattermostClient {
         self.user_cache.clear().await;
         self.channel_cache.clear().await;
         self.team_cache.clear().await;
+        self.emoji_cache.clear().await;
+        self.emoji_image_cache.clear().await;
+        self.avatar_cache.clear().await;
+        self.role_cache.clear().await;
+        self.permission_cache.clear().await;
     }
 
-    /// Get cache statistics
-    ///
-    /// Returns statistics for all caches: (cache_name, total_entries, expired_entries)
+    /// Whether API responses are cached
+    fn cache_enabled(&self) -> bool {
+        self.cache_config.read().unwrap().enabled
+    }
+
+    /// Get the current cache configuration
+    pub fn cache_config(&self) -> CacheConfig {
+        self.cache_config.read().unwrap().clone()
+    }
+
+    /// Change the TTLs and size limit of all caches
     ///
-    /// # Returns
-    /// A vector of tuples containing cache statistics
-    pub async fn get_cache_stats(&self) -> Vec<(&'static str, usize, usize)> {
+    /// New TTLs apply to entries cached from now on. Disabling the cache
+    /// clears it, so no outdated entries are used if it is enabled again.
+    pub async fn configure_caches(&self, config: CacheConfig) {
+        let user_ttl = Duration::from_secs(config.user_ttl_secs);
+        let channel_ttl = Duration::from_secs(config.channel_ttl_secs);
+        let team_ttl = Duration::from_secs(config.team_ttl_secs);
+        let emoji_ttl = Duration::from_secs(config.emoji_ttl_secs);
+        let max_entries = config.max_entries;
+
+        self.user_cache.configure(user_ttl, max_entries).await;
+        self.channel_cache.configure(channel_ttl, max_entries).await;
+        self.team_cache.configure(team_ttl, max_entries).await;
+        self.emoji_cache.configure(emoji_ttl, max_entries).await;
+        self.emoji_image_cache
+            .configure(emoji_ttl, max_entries)
+            .await;
+        self.avatar_cache.configure(user_ttl, max_entries).await;
+        self.role_cache.configure(team_ttl, max_entries).await;
+        self.permission_cache
+            .configure(channel_ttl, max_entries)
+            .await;
+
+        let enabled = config.enabled;
+        *self.cache_config.write().unwrap() = config;
+        if !enabled {
+            self.clear_all_caches().await;
+        }
+    }
+
+    /// Get the usage statistics of all caches
+    pub async fn get_cache_stats(&self) -> Vec<CacheStats> {
         vec![
-            (
-                "user",
-                self.user_cache.stats().await.0,
-                self.user_cache.stats().await.1,
-            ),
-            (
-                "channel",
-                self.channel_cache.stats().await.0,
-                self.channel_cache.stats().await.1,
-            ),
-            (
-                "team",
-                self.team_cache.stats().await.0,
-                self.team_cache.stats().await.1,
-            ),
+            self.user_cache.usage("user").await,
+            self.channel_cache.usage("channel").await,
+            self.team_cache.usage("team").await,
+            self.emoji_cache.usage("emoji").await,
+            self.emoji_image_cache.usage("emoji_image").await,
+            self.avatar_cache.usage("avatar").await,
+            self.role_cache.usage("role").await,
+            self.permission_cache.usage("channel_permissions").await,
         ]
     }
 }
 
+/// Send a request, to the simulated server of its host if there is one
+/// (`simulation` feature)
+pub(crate) async fn send(request: reqwest::RequestBuilder) -> reqwest::Result<reqwest::Response> {
+    #[cfg(feature = "simulation")]
+    return crate::simulation::send(request).await;
+    #[cfg(not(feature = "simulation"))]
+    request.send().await
+}
+
+/// Shorten a request's timeout to the time left until the current deadline
+///
+/// # Returns
+/// The request, or a Timeout error if the deadline has already passed
+pub(crate) fn apply_deadline(request: reqwest::RequestBuilder) -> Result<reqwest::RequestBuilder> {
+    Ok(match crate::deadline::remaining()? {
+        Some(remaining) if remaining < REQUEST_TIMEOUT => request.timeout(remaining),
+        _ => request,
+    })
+}
+
+/// Build the HTTP client for REST API calls
+fn build_http_client(
+    proxy: Option<&Proxy>,
+    resolver: &Resolver,
+    tls: Option<&Arc<ClientConfig>>,
+) -> Result<Client> {
+    build(http_client_builder(proxy, resolver, tls)?)
+}
+
+fn http_client_builder(
+    proxy: Option<&Proxy>,
+    resolver: &Resolver,
+    tls: Option<&Arc<ClientConfig>>,
+) -> Result<reqwest::ClientBuilder> {
+    let mut builder = Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .dns_resolver(Arc::new(resolver.clone()));
+    if let Some(proxy) = proxy {
+        builder = builder.proxy(proxy.to_reqwest()?);
+    }
+    if let Some(tls) = tls {
+        builder = builder.use_preconfigured_tls(ClientConfig::clone(tls));
+    }
+    Ok(builder)
+}
+
+fn build(builder: reqwest::ClientBuilder) -> Result<Client> {
+    builder.build().map_err(|e| {
+        Error::new(
+            ErrorCode::NetworkError,
+            format!("Failed to create HTTP client: {e}"),
+        )
+    })
+}
+
+/// Check whether a string has the shape of a Mattermost ID
+/// (26 lowercase alphanumeric characters)
+fn is_mattermost_id(value: &str) -> bool {
+    value.len() == 26
+        && value
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -814,6 +1731,20 @@ mod tests {
         assert!(client.is_ok());
     }
 
+    #[test]
+    fn test_set_tls() {
+        let client = MattermostClient::new("https://mattermost.example.com").unwrap();
+        let config = TlsConfig {
+            accept_invalid_certs: true,
+            ..Default::default()
+        };
+        client.set_tls(&config).unwrap();
+        assert!(client.tls().is_some());
+
+        client.set_tls(&TlsConfig::default()).unwrap();
+        assert!(client.tls().is_none());
+    }
+
     #[test]
     fn test_invalid_url() {
         let client = MattermostClient::new("not a url");
@@ -938,6 +1869,14 @@ mod tests {
             ErrorCode::Timeout
         );
 
+        // Test upload size errors
+        assert_eq!(
+            MattermostClient::map_mattermost_error_id(
+                "api.file.upload_file.too_large_detailed.app_error"
+            ),
+            ErrorCode::FileTooLarge
+        );
+
         // Test invalid argument errors
         assert_eq!(
             MattermostClient::map_mattermost_error_id("api.post.invalid_param.message"),
@@ -954,4 +1893,86 @@ mod tests {
             ErrorCode::Unknown
         );
     }
+    #[test]
+    fn test_is_mattermost_id() {
+        assert!(is_mattermost_id("abcdefghijklmnopqrstuvwxyz"));
+        assert!(is_mattermost_id("8x3k1q9r7w5m2n4p6t8v0y1z3a"));
+        assert!(!is_mattermost_id("party_parrot"));
+        assert!(!is_mattermost_id("ABCDEFGHIJKLMNOPQRSTUVWXYZ"));
+        assert!(!is_mattermost_id(""));
+    }
+
+    #[tokio::test]
+    async fn test_get_emoji_image_cached_rejects_empty_name() {
+        let client = MattermostClient::new("https://mattermost.example.com").unwrap();
+        let err = client.get_emoji_image_cached("::").await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidArgument);
+    }
+
+    #[cfg(feature = "simulation")]
+    #[tokio::test]
+    async fn test_user_image_cached_until_user_updated() {
+        let server = crate::simulation::add_server("avatar.sim");
+        server.set_route("GET", "/users/user1/image", 200, "old");
+
+        let client = MattermostClient::new("http://avatar.sim").unwrap();
+        assert_eq!(client.get_user_image_cached("user1").await.unwrap(), b"old");
+
+        server.set_route("GET", "/users/user1/image", 200, "new");
+        assert_eq!(client.get_user_image_cached("user1").await.unwrap(), b"old");
+
+        client.invalidate_user_cache("user1").await;
+        assert_eq!(client.get_user_image_cached("user1").await.unwrap(), b"new");
+
+        crate::simulation::remove_server("avatar.sim");
+    }
+
+    #[cfg(feature = "simulation")]
+    #[tokio::test]
+    async fn test_configure_caches() {
+        let server = crate::simulation::add_server("cacheconfig.sim");
+        server.set_route(
+            "GET",
+            "/teams/t1",
+            200,
+            r#"{"id":"t1","create_at":1,"update_at":1,"delete_at":0,"name":"team","display_name":"Team","type":"O"}"#,
+        );
+
+        let client = MattermostClient::new("http://cacheconfig.sim").unwrap();
+        client.get_team_cached("t1").await.unwrap();
+        client.get_team_cached("t1").await.unwrap();
+        let stats = client.get_cache_stats().await;
+        let teams = stats.iter().find(|s| s.name == "team").unwrap();
+        assert_eq!((teams.entries, teams.hits, teams.misses), (1, 1, 1));
+
+        // Disabling the cache clears it and sends every lookup to the server
+        client.configure_caches(CacheConfig::disabled()).await;
+        client.get_team_cached("t1").await.unwrap();
+        let stats = client.get_cache_stats().await;
+        assert!(stats.iter().all(|s| s.entries == 0));
+
+        client
+            .configure_caches(CacheConfig {
+                team_ttl_secs: 30,
+                max_entries: Some(10),
+                ..CacheConfig::default()
+            })
+            .await;
+        let stats = client.get_cache_stats().await;
+        let teams = stats.iter().find(|s| s.name == "team").unwrap();
+        assert_eq!((teams.ttl_secs, teams.max_entries), (30, Some(10)));
+
+        crate::simulation::remove_server("cacheconfig.sim");
+    }
+
+    #[tokio::test]
+    async fn test_requests_fail_after_deadline() {
+        let client = MattermostClient::new("https://mattermost.example.com").unwrap();
+        let err = crate::deadline::with_timeout(std::time::Duration::ZERO, client.get("/users/me"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, ErrorCode::Timeout);
+        // Not counted against the server
+        assert_eq!(client.circuit_state(), CircuitState::Closed);
+    }
 }
@@ -0,0 +1,129 @@
+//! Slash commands
+//!
+//! Built-in commands (`/away`, `/invite`, ...), custom integration commands and
+//! plugin commands all run through the same endpoint. The server performs the
+//! command's effects itself, posting any messages over the WebSocket, so the
+//! response mostly matters for ephemeral text and navigation.
+
+use crate::error::Result;
+use crate::types::SlashCommand;
+
+use super::client::MattermostClient;
+use super::types::{MattermostCommand, MattermostCommandResponse};
+
+/// Keep the commands whose trigger starts with a typed prefix
+///
+/// A leading slash in the prefix is ignored and matching is case-insensitive.
+/// Results are sorted by trigger.
+pub(crate) fn filter_commands(
+    commands: Vec<SlashCommand>,
+    prefix: &str,
+    limit: usize,
+) -> Vec<SlashCommand> {
+    let prefix = prefix.trim().trim_start_matches('/').to_lowercase();
+    let mut matches: Vec<SlashCommand> = commands
+        .into_iter()
+        .filter(|command| command.trigger.to_lowercase().starts_with(&prefix))
+        .collect();
+    matches.sort_by(|a, b| a.trigger.cmp(&b.trigger));
+    matches.dedup_by(|a, b| a.trigger == b.trigger);
+    matches.truncate(limit);
+    matches
+}
+
+impl MattermostClient {
+    /// Execute a slash command in a channel
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel the command is run in
+    /// * `command` - The full command text, starting with `/`
+    ///
+    /// # Returns
+    /// A Result containing the command response or an Error
+    ///
+    /// # API Endpoint
+    /// `POST /api/v4/commands/execute`
+    pub async fn execute_command(
+        &self,
+        channel_id: &str,
+        command: &str,
+    ) -> Result<MattermostCommandResponse> {
+        let body = serde_json::json!({
+            "channel_id": channel_id,
+            "command": command,
+        });
+        let response = self.post("/commands/execute", &body).await?;
+        self.handle_response(response).await
+    }
+
+    /// List the commands available for autocomplete in a team
+    ///
+    /// # Arguments
+    /// * `team_id` - The ID of the team
+    ///
+    /// # Returns
+    /// A Result containing the built-in, custom and plugin commands or an Error
+    ///
+    /// # API Endpoint
+    /// `GET /api/v4/teams/{team_id}/commands/autocomplete`
+    pub async fn list_autocomplete_commands(
+        &self,
+        team_id: &str,
+    ) -> Result<Vec<MattermostCommand>> {
+        let endpoint = format!("/teams/{team_id}/commands/autocomplete");
+        let response = self.get(&endpoint).await?;
+        self.handle_response(response).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(trigger: &str) -> SlashCommand {
+        SlashCommand {
+            trigger: trigger.to_string(),
+            description: String::new(),
+            hint: String::new(),
+            display_name: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_filter_commands() {
+        let commands = vec![command("invite"), command("away"), command("invite_people")];
+        let found = filter_commands(commands.clone(), "/inv", 10);
+        let triggers: Vec<&str> = found.iter().map(|c| c.trigger.as_str()).collect();
+        assert_eq!(triggers, vec!["invite", "invite_people"]);
+
+        assert_eq!(filter_commands(commands.clone(), "", 10).len(), 3);
+        assert_eq!(filter_commands(commands.clone(), "AW", 10).len(), 1);
+        assert_eq!(filter_commands(commands, "", 1)[0].trigger, "away");
+    }
+
+    #[cfg(feature = "simulation")]
+    #[tokio::test]
+    async fn test_execute_command() {
+        let server = crate::simulation::add_server("commands.sim");
+        server.set_route(
+            "POST",
+            "/commands/execute",
+            200,
+            r#"{"response_type":"ephemeral","text":"You are now away","goto_location":""}"#,
+        );
+
+        let client = MattermostClient::new("http://commands.sim").unwrap();
+        let response = client.execute_command("c1", "/away").await.unwrap();
+        assert_eq!(response.text, "You are now away");
+
+        server.set_route(
+            "POST",
+            "/commands/execute",
+            404,
+            r#"{"id":"api.command.execute_command.not_found.app_error","message":"Command with a trigger of 'nope' not found.","status_code":404}"#,
+        );
+        assert!(client.execute_command("c1", "/nope").await.is_err());
+
+        crate::simulation::remove_server("commands.sim");
+    }
+}
@@ -1,10 +1,18 @@
 use chrono::{DateTime, Utc};
 
 use crate::types::user::UserStatus;
-use crate::types::{Attachment, Channel, ChannelType, Message, Team, TeamType, User};
+use crate::types::{
+    Attachment, CategorySorting, Channel, ChannelMemberRoles, ChannelType, Draft, MentionRules,
+    Message, Reaction, ScheduledMessage, SidebarCategory, SidebarCategoryType, Team, TeamType,
+    Thread, User,
+};
 
 use super::channels::get_dm_partner_id;
-use super::types::{FileInfo, MattermostChannel, MattermostPost, MattermostTeam, MattermostUser};
+use super::types::{
+    ChannelMember, FileInfo, MattermostChannel, MattermostDraft, MattermostPost,
+    MattermostScheduledPost, MattermostSidebarCategory, MattermostTeam, MattermostUser,
+    Reaction as MattermostReaction, UserThread,
+};
 
 /// Context for converting Mattermost types to generic types
 /// Provides necessary information like server URL and current user ID
@@ -38,6 +46,29 @@ fn timestamp_to_datetime(timestamp_ms: i64) -> DateTime<Utc> {
 }
 
 impl MattermostUser {
+    /// The mention rules of the user's notification settings
+    pub fn mention_rules(&self) -> MentionRules {
+        let mut rules = MentionRules::new(self.id.clone(), self.username.clone());
+        let enabled = |key: &str| self.notify_props.get(key).map(String::as_str) == Some("true");
+        if enabled("first_name") && !self.first_name.is_empty() {
+            rules.first_name = Some(self.first_name.clone());
+        }
+        // Channel-wide mentions are on unless turned off
+        rules.channel_wide = self.notify_props.get("channel").map(String::as_str) != Some("false");
+        rules.keywords = self
+            .notify_props
+            .get("mention_keys")
+            .map(|keys| {
+                keys.split(',')
+                    .map(str::trim)
+                    .filter(|key| !key.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        rules
+    }
+
     /// Convert to User with context for proper URL construction
     pub fn to_user_with_context(&self, ctx: &ConversionContext) -> User {
         // Determine display name from available fields
@@ -119,6 +150,9 @@ impl From<MattermostPost> for Message {
             "hashtags": mm_post.hashtags,
             "update_at": mm_post.update_at,
             "delete_at": mm_post.delete_at,
+            "remote_id": mm_post.remote_id,
+            "pending_post_id": mm_post.pending_post_id,
+            "is_pinned": mm_post.is_pinned,
         });
 
         let mut message = Message::new(
@@ -134,10 +168,115 @@ impl From<MattermostPost> for Message {
         message.attachments = attachments;
         message = message.with_metadata(metadata);
 
+        // Posts synced from a remote cluster (shared channels) carry a remote_id
+        if let Some(remote_id) = mm_post.remote_id.filter(|id| !id.is_empty()) {
+            message = message.with_origin(remote_id);
+        }
+
         message
     }
 }
 
+impl From<MattermostReaction> for Reaction {
+    fn from(mm_reaction: MattermostReaction) -> Self {
+        Reaction {
+            emoji: mm_reaction.emoji_name,
+            user_id: mm_reaction.user_id,
+            created_at: timestamp_to_datetime(mm_reaction.create_at),
+        }
+    }
+}
+
+impl From<MattermostDraft> for Draft {
+    fn from(mm_draft: MattermostDraft) -> Self {
+        let mut draft = Draft::new(mm_draft.channel_id, mm_draft.message);
+        if !mm_draft.root_id.is_empty() {
+            draft.root_id = Some(mm_draft.root_id);
+        }
+        draft.file_ids = mm_draft.file_ids.unwrap_or_default();
+        draft.props = mm_draft.props.unwrap_or_default();
+        draft.updated_at = timestamp_to_datetime(mm_draft.update_at);
+        draft.server_side = true;
+        draft
+    }
+}
+
+impl From<MattermostSidebarCategory> for SidebarCategory {
+    fn from(mm_category: MattermostSidebarCategory) -> Self {
+        let category_type = match mm_category.category_type.as_str() {
+            "favorites" => SidebarCategoryType::Favorites,
+            "channels" => SidebarCategoryType::Channels,
+            "direct_messages" => SidebarCategoryType::DirectMessages,
+            _ => SidebarCategoryType::Custom,
+        };
+        let sorting = match mm_category.sorting.as_str() {
+            "alpha" => CategorySorting::Alphabetical,
+            "recent" => CategorySorting::Recent,
+            "manual" => CategorySorting::Manual,
+            _ => CategorySorting::Default,
+        };
+        SidebarCategory {
+            id: mm_category.id,
+            team_id: mm_category.team_id,
+            display_name: mm_category.display_name,
+            category_type,
+            sorting,
+            channel_ids: mm_category.channel_ids,
+            collapsed: mm_category.collapsed,
+            muted: mm_category.muted,
+        }
+    }
+}
+
+impl From<UserThread> for Thread {
+    fn from(mm_thread: UserThread) -> Self {
+        // Participants are user IDs, or user objects when requested extended
+        let participants = mm_thread
+            .participants
+            .iter()
+            .filter_map(|participant| {
+                participant
+                    .as_str()
+                    .or_else(|| participant.get("id").and_then(|id| id.as_str()))
+                    .map(str::to_string)
+            })
+            .collect();
+        let optional_time =
+            |timestamp_ms: i64| (timestamp_ms > 0).then(|| timestamp_to_datetime(timestamp_ms));
+        Thread {
+            id: mm_thread.id,
+            channel_id: mm_thread.post.channel_id.clone(),
+            root: mm_thread.post.into(),
+            reply_count: mm_thread.reply_count.max(0) as u32,
+            participants,
+            last_reply_at: optional_time(mm_thread.last_reply_at),
+            last_viewed_at: optional_time(mm_thread.last_viewed_at),
+            unread_replies: mm_thread.unread_replies.max(0) as u32,
+            unread_mentions: mm_thread.unread_mentions.max(0) as u32,
+        }
+    }
+}
+
+impl From<MattermostScheduledPost> for ScheduledMessage {
+    fn from(mm_scheduled: MattermostScheduledPost) -> Self {
+        let mut scheduled = ScheduledMessage::new(
+            mm_scheduled.id,
+            mm_scheduled.channel_id,
+            mm_scheduled.message,
+            timestamp_to_datetime(mm_scheduled.scheduled_at),
+        );
+        if !mm_scheduled.root_id.is_empty() {
+            scheduled.root_id = Some(mm_scheduled.root_id);
+        }
+        scheduled.created_at = timestamp_to_datetime(mm_scheduled.create_at);
+        scheduled.server_side = true;
+        if !mm_scheduled.error_code.is_empty() {
+            scheduled.error = Some(mm_scheduled.error_code);
+        }
+        scheduled
+    }
+}
+
 impl FileInfo {
     /// Convert to Attachment with context for proper URL construction
     pub fn to_attachment_with_context(&self, ctx: &ConversionContext) -> Attachment {
@@ -236,6 +375,10 @@ impl MattermostChannel {
             channel = channel.archived();
         }
 
+        if self.shared.unwrap_or(false) {
+            channel = channel.shared();
+        }
+
         channel.with_metadata(metadata)
     }
 }
@@ -249,6 +392,21 @@ impl From<MattermostChannel> for Channel {
     }
 }
 
+/// Convert a Mattermost channel member to its channel roles
+impl From<ChannelMember> for ChannelMemberRoles {
+    fn from(member: ChannelMember) -> Self {
+        let roles: Vec<String> = member.roles.split_whitespace().map(String::from).collect();
+        // Members of channels without a scheme only have the explicit role
+        let is_admin = member.scheme_admin || roles.iter().any(|role| role == "channel_admin");
+        ChannelMemberRoles {
+            channel_id: member.channel_id,
+            user_id: member.user_id,
+            is_admin,
+            roles,
+        }
+    }
+}
+
 /// Convert Mattermost Team to our internal Team type
 impl From<MattermostTeam> for Team {
     fn from(mm_team: MattermostTeam) -> Self {
@@ -335,6 +493,7 @@ mod tests {
             timezone: Default::default(),
             props: Default::default(),
             is_bot: false,
+            notify_props: Default::default(),
             create_at: 1234567890000,
             update_at: 1234567890000,
             delete_at: 0,
@@ -366,6 +525,7 @@ mod tests {
             last_post_at: 0,
             total_msg_count: 42,
             creator_id: "user1".to_string(),
+            shared: None,
         };
 
         let channel: Channel = mm_channel.into();
@@ -374,6 +534,60 @@ mod tests {
         assert_eq!(channel.channel_type, ChannelType::Public);
         assert_eq!(channel.topic, Some("Welcome!".to_string()));
         assert_eq!(channel.purpose, Some("General discussion".to_string()));
+        assert!(!channel.is_shared);
+    }
+
+    #[test]
+    fn test_shared_channel_and_remote_post_conversion() {
+        let mm_channel: MattermostChannel = serde_json::from_str(
+            r#"{
+                "id": "ch123",
+                "create_at": 0,
+                "update_at": 0,
+                "delete_at": 0,
+                "team_id": "team1",
+                "type": "O",
+                "display_name": "Partners",
+                "name": "partners",
+                "shared": true
+            }"#,
+        )
+        .unwrap();
+        let channel: Channel = mm_channel.into();
+        assert!(channel.is_shared);
+
+        let mm_post: MattermostPost = serde_json::from_str(
+            r#"{
+                "id": "post123",
+                "create_at": 0,
+                "update_at": 0,
+                "delete_at": 0,
+                "edit_at": 0,
+                "user_id": "user1",
+                "channel_id": "ch123",
+                "message": "hello from remote",
+                "remote_id": "remote456"
+            }"#,
+        )
+        .unwrap();
+        let message: Message = mm_post.into();
+        assert_eq!(message.origin, Some("remote456".to_string()));
+
+        let local_post: MattermostPost = serde_json::from_str(
+            r#"{
+                "id": "post124",
+                "create_at": 0,
+                "update_at": 0,
+                "delete_at": 0,
+                "edit_at": 0,
+                "user_id": "user1",
+                "channel_id": "ch123",
+                "message": "local"
+            }"#,
+        )
+        .unwrap();
+        let message: Message = local_post.into();
+        assert!(message.origin.is_none());
     }
 
     #[test]
@@ -395,6 +609,102 @@ mod tests {
         assert_eq!(dt.timestamp(), 1234567890);
     }
 
+    #[test]
+    fn test_mention_rules() {
+        let mm_user: MattermostUser = serde_json::from_value(serde_json::json!({
+            "id": "user1",
+            "username": "alice",
+            "first_name": "Alice",
+            "notify_props": {"first_name": "true", "channel": "false", "mention_keys": "alice,@alice, deploy"},
+            "create_at": 0,
+            "update_at": 0,
+            "delete_at": 0
+        }))
+        .unwrap();
+        let rules = mm_user.mention_rules();
+        assert_eq!(rules.username, "alice");
+        assert_eq!(rules.first_name.as_deref(), Some("Alice"));
+        assert!(!rules.channel_wide);
+        assert_eq!(rules.keywords, vec!["alice", "@alice", "deploy"]);
+    }
+
+    #[test]
+    fn test_channel_member_roles_conversion() {
+        let member: ChannelMember = serde_json::from_value(serde_json::json!({
+            "channel_id": "c1",
+            "user_id": "u1",
+            "roles": "channel_user channel_admin",
+            "last_viewed_at": 0,
+            "msg_count": 0,
+            "mention_count": 0,
+            "notify_props": {},
+            "last_update_at": 0
+        }))
+        .unwrap();
+        let roles: ChannelMemberRoles = member.into();
+        assert!(roles.is_admin);
+        assert_eq!(roles.roles, vec!["channel_user", "channel_admin"]);
+
+        let member: ChannelMember = serde_json::from_value(serde_json::json!({
+            "channel_id": "c1",
+            "user_id": "u2",
+            "roles": "channel_user",
+            "scheme_admin": false,
+            "last_viewed_at": 0,
+            "msg_count": 0,
+            "mention_count": 0,
+            "notify_props": {},
+            "last_update_at": 0
+        }))
+        .unwrap();
+        assert!(!ChannelMemberRoles::from(member).is_admin);
+    }
+
+    #[test]
+    fn test_thread_conversion() {
+        let mm_thread: UserThread = serde_json::from_value(serde_json::json!({
+            "id": "root1",
+            "reply_count": 3,
+            "last_reply_at": 1234567890000i64,
+            "last_viewed_at": 0,
+            "participants": ["user1", {"id": "user2", "username": "bob"}],
+            "post": {
+                "id": "root1",
+                "channel_id": "channel1",
+                "user_id": "user1",
+                "message": "Release plan",
+                "create_at": 1234567800000i64,
+                "update_at": 0,
+                "delete_at": 0,
+                "edit_at": 0
+            },
+            "unread_replies": 2,
+            "unread_mentions": 1
+        }))
+        .unwrap();
+        let thread: Thread = mm_thread.into();
+        assert_eq!(thread.channel_id, "channel1");
+        assert_eq!(thread.root.text, "Release plan");
+        assert_eq!(thread.participants, vec!["user1", "user2"]);
+        assert_eq!(thread.last_reply_at.unwrap().timestamp(), 1234567890);
+        assert!(thread.last_viewed_at.is_none());
+        assert!(thread.is_unread());
+    }
+
+    #[test]
+    fn test_reaction_conversion() {
+        let mm_reaction = MattermostReaction {
+            user_id: "user1".to_string(),
+            post_id: "post1".to_string(),
+            emoji_name: "thumbsup".to_string(),
+            create_at: 1234567890000,
+        };
+        let reaction: Reaction = mm_reaction.into();
+        assert_eq!(reaction.emoji, "thumbsup");
+        assert_eq!(reaction.user_id, "user1");
+        assert_eq!(reaction.created_at.timestamp(), 1234567890);
+    }
+
     #[test]
     fn test_team_conversion() {
         let mm_team = MattermostTeam {
@@ -0,0 +1,118 @@
+use crate::error::{Error, Result};
+
+use super::client::MattermostClient;
+use super::types::MattermostDraft;
+
+/// Turn the errors of servers without drafts into Unsupported errors
+///
+/// Servers with synced drafts turned off answer 501; servers older than 7.7
+/// have no drafts endpoints and answer 404.
+fn drafts_unavailable(e: Error) -> Error {
+    match e.http_status {
+        Some(404) | Some(501) => Error::unsupported("Drafts are not available on this server"),
+        _ => e,
+    }
+}
+
+impl MattermostClient {
+    /// Create or update the draft of a channel or thread
+    ///
+    /// # Arguments
+    /// * `draft` - The draft; `user_id` is filled in with the current user
+    ///
+    /// # Returns
+    /// A Result containing the saved draft or an Error (Unsupported if the
+    /// server has no synced drafts)
+    pub async fn upsert_draft(&self, mut draft: MattermostDraft) -> Result<MattermostDraft> {
+        draft.user_id = self.current_user_id().await?;
+        let response = self.post("/drafts", &draft).await?;
+        self.handle_response(response)
+            .await
+            .map_err(drafts_unavailable)
+    }
+
+    /// Get the current user's drafts in a team
+    ///
+    /// # Arguments
+    /// * `team_id` - The ID of the team
+    ///
+    /// # Returns
+    /// A Result containing the drafts or an Error (Unsupported if the server
+    /// has no synced drafts)
+    pub async fn get_team_drafts(&self, team_id: &str) -> Result<Vec<MattermostDraft>> {
+        let user_id = self.current_user_id().await?;
+        let endpoint = format!("/users/{user_id}/teams/{team_id}/drafts");
+        let response = self.get(&endpoint).await?;
+        self.handle_response(response)
+            .await
+            .map_err(drafts_unavailable)
+    }
+
+    /// Delete the draft of a channel or thread
+    ///
+    /// # Arguments
+    /// * `channel_id` - The ID of the channel
+    /// * `root_id` - The ID of the thread's root post, or None for the channel draft
+    ///
+    /// # Returns
+    /// A Result indicating success or failure (Unsupported if the server has
+    /// no synced drafts)
+    pub async fn delete_draft(&self, channel_id: &str, root_id: Option<&str>) -> Result<()> {
+        let user_id = self.current_user_id().await?;
+        let endpoint = match root_id {
+            Some(root_id) => format!("/users/{user_id}/channels/{channel_id}/drafts/{root_id}"),
+            None => format!("/users/{user_id}/channels/{channel_id}/drafts"),
+        };
+        let response = self.delete(&endpoint).await?;
+        let _: serde_json::Value = self
+            .handle_response(response)
+            .await
+            .map_err(drafts_unavailable)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drafts_unavailable() {
+        let disabled =
+            Error::new(crate::error::ErrorCode::Unknown, "disabled").with_http_status(501);
+        assert_eq!(
+            drafts_unavailable(disabled).code,
+            crate::error::ErrorCode::Unsupported
+        );
+        let denied =
+            Error::new(crate::error::ErrorCode::PermissionDenied, "denied").with_http_status(403);
+        assert_eq!(
+            drafts_unavailable(denied).code,
+            crate::error::ErrorCode::PermissionDenied
+        );
+    }
+
+    #[cfg(feature = "simulation")]
+    #[tokio::test]
+    async fn test_drafts_disabled() {
+        let server = crate::simulation::add_server("drafts.sim");
+        server.set_route(
+            "POST",
+            "/drafts",
+            501,
+            r#"{"id":"api.drafts.disabled","message":"Drafts are disabled","status_code":501}"#,
+        );
+
+        let client = MattermostClient::new("http://drafts.sim").unwrap();
+        client.set_user_id(Some("user1".to_string())).await;
+        let draft = MattermostDraft {
+            channel_id: "ch1".to_string(),
+            message: "hello".to_string(),
+            ..Default::default()
+        };
+        let err = client.upsert_draft(draft).await.unwrap_err();
+        assert_eq!(err.code, crate::error::ErrorCode::Unsupported);
+
+        crate::simulation::remove_server("drafts.sim");
+    }
+}
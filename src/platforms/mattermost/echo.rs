@@ -0,0 +1,111 @@
+//! Local echo reconciliation
+//!
+//! Every post created through the client carries a `pending_post_id`, which the
+//! server echoes back in the `posted` WebSocket event for that post. Clients that
+//! render sent messages optimistically would otherwise show the message twice:
+//! once from the send call and once from the event. Tracking the pending IDs we
+//! issued lets the platform report the echo as a confirmation instead.
+//!
+//! Memory stays bounded: at most `MAX_PENDING_POSTS` IDs are tracked, and IDs
+//! whose echo has not arrived within `PENDING_POST_TIMEOUT` are dropped.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+/// Maximum number of sent posts awaiting their echo
+pub const MAX_PENDING_POSTS: usize = 1000;
+
+/// How long a sent post waits for its echo before it is forgotten
+pub const PENDING_POST_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Default)]
+struct PendingState {
+    /// Pending post IDs and when they were issued
+    issued: HashMap<String, Instant>,
+    /// Timestamp (ms) used for the last issued ID, kept strictly increasing
+    last_timestamp: i64,
+}
+
+/// Tracks the pending post IDs of posts sent by this client
+#[derive(Debug, Clone, Default)]
+pub struct PendingPosts {
+    state: Arc<RwLock<PendingState>>,
+}
+
+impl PendingPosts {
+    /// Create an empty tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Issue and track a new pending post ID
+    ///
+    /// # Arguments
+    /// * `user_id` - The ID of the sending user
+    /// * `now_ms` - The current time in milliseconds since the epoch
+    ///
+    /// # Returns
+    /// An ID in the `user_id:timestamp` format used by the Mattermost web app
+    pub async fn issue(&self, user_id: &str, now_ms: i64) -> String {
+        let now = Instant::now();
+        let mut state = self.state.write().await;
+        state
+            .issued
+            .retain(|_, issued| now.duration_since(*issued) < PENDING_POST_TIMEOUT);
+        if state.issued.len() >= MAX_PENDING_POSTS {
+            if let Some(oldest) = state
+                .issued
+                .iter()
+                .min_by_key(|(_, issued)| **issued)
+                .map(|(id, _)| id.clone())
+            {
+                state.issued.remove(&oldest);
+            }
+        }
+
+        // Two posts sent within the same millisecond still get distinct IDs
+        let timestamp = now_ms.max(state.last_timestamp + 1);
+        state.last_timestamp = timestamp;
+
+        let pending_id = format!("{user_id}:{timestamp}");
+        state.issued.insert(pending_id.clone(), now);
+        pending_id
+    }
+
+    /// Stop tracking a pending post ID
+    ///
+    /// # Returns
+    /// true if the ID was issued by this tracker and had not been taken yet
+    pub async fn take(&self, pending_id: &str) -> bool {
+        self.state.write().await.issued.remove(pending_id).is_some()
+    }
+
+    /// Forget all pending post IDs (e.g. on logout)
+    pub async fn reset(&self) {
+        *self.state.write().await = PendingState::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_issued_ids_are_unique_and_taken_once() {
+        let pending = PendingPosts::new();
+        let first = pending.issue("user1", 1000).await;
+        let second = pending.issue("user1", 1000).await;
+
+        assert_eq!(first, "user1:1000");
+        assert_eq!(second, "user1:1001");
+
+        assert!(pending.take(&first).await);
+        assert!(!pending.take(&first).await);
+        assert!(!pending.take("other:1000").await);
+
+        pending.reset().await;
+        assert!(!pending.take(&second).await);
+    }
+}
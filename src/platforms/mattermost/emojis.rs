@@ -0,0 +1,136 @@
+use reqwest::multipart;
+
+use crate::error::{Error, Result};
+
+use super::client::MattermostClient;
+use super::types::MattermostEmoji;
+
+/// Longest custom emoji name the server accepts
+const MAX_EMOJI_NAME_LEN: usize = 64;
+
+/// Check a custom emoji name against the server's rules
+///
+/// Names are lowercase letters, digits, '_', '-' and '+', at most 64 long.
+fn validate_emoji_name(name: &str) -> Result<()> {
+    let valid_char =
+        |c: char| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-' || c == '+';
+    if name.is_empty() || name.len() > MAX_EMOJI_NAME_LEN || !name.chars().all(valid_char) {
+        return Err(Error::invalid_argument(format!(
+            "Invalid emoji name '{name}': use up to {MAX_EMOJI_NAME_LEN} lowercase letters, digits, '_', '-' or '+'"
+        )));
+    }
+    if crate::emoji::resolve(name).is_some() {
+        return Err(Error::invalid_argument(format!(
+            "Emoji name '{name}' is taken by a standard emoji"
+        )));
+    }
+    Ok(())
+}
+
+impl MattermostClient {
+    /// Create a custom emoji
+    ///
+    /// # Arguments
+    /// * `name` - The emoji name (without colons)
+    /// * `image` - The image bytes (PNG, JPEG or GIF)
+    ///
+    /// # Returns
+    /// A Result containing the created MattermostEmoji or an Error
+    ///
+    /// # API Endpoint
+    /// `POST /api/v4/emoji`
+    pub async fn create_emoji(&self, name: &str, image: Vec<u8>) -> Result<MattermostEmoji> {
+        let name = name.trim().trim_matches(':');
+        validate_emoji_name(name)?;
+        let creator_id = self.current_user_id().await?;
+
+        let emoji = serde_json::json!({ "name": name, "creator_id": creator_id });
+        let form = multipart::Form::new()
+            .part(
+                "image",
+                multipart::Part::bytes(image).file_name(name.to_string()),
+            )
+            .text("emoji", emoji.to_string());
+
+        let url = self.api_url("/emoji");
+        let mut request = self.http_client().post(&url);
+        if let Some(token) = self.get_token().await {
+            request = request.bearer_auth(token);
+        }
+
+        let response = self.send_request(request.multipart(form), "POST").await?;
+        self.handle_upload_response(response).await
+    }
+
+    /// Delete a custom emoji
+    ///
+    /// # Arguments
+    /// * `emoji_id` - The ID of the emoji
+    ///
+    /// # Returns
+    /// A Result indicating success or failure
+    ///
+    /// # API Endpoint
+    /// `DELETE /api/v4/emoji/{emoji_id}`
+    pub async fn delete_emoji(&self, emoji_id: &str) -> Result<()> {
+        let endpoint = format!("/emoji/{emoji_id}");
+        let response = self.delete(&endpoint).await?;
+        let emoji: MattermostEmoji = self.handle_response(response).await?;
+        self.remove_emoji_from_cache(&emoji).await;
+        Ok(())
+    }
+
+    /// Autocomplete custom emoji names
+    ///
+    /// # Arguments
+    /// * `name` - The start of the emoji name
+    ///
+    /// # Returns
+    /// A Result containing up to 100 matching emojis or an Error
+    ///
+    /// # API Endpoint
+    /// `GET /api/v4/emoji/autocomplete?name={name}`
+    pub async fn autocomplete_emojis(&self, name: &str) -> Result<Vec<MattermostEmoji>> {
+        let name: String = url::form_urlencoded::byte_serialize(name.as_bytes()).collect();
+        let endpoint = format!("/emoji/autocomplete?name={name}");
+        let response = self.get(&endpoint).await?;
+        self.handle_response(response).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_emoji_name() {
+        assert!(validate_emoji_name("party_parrot").is_ok());
+        assert!(validate_emoji_name("ship-it+1").is_ok());
+        assert!(validate_emoji_name("").is_err());
+        assert!(validate_emoji_name("Party").is_err());
+        assert!(validate_emoji_name("a b").is_err());
+        assert!(validate_emoji_name(&"x".repeat(65)).is_err());
+        // Standard emoji names cannot be reused
+        assert!(validate_emoji_name("thumbsup").is_err());
+    }
+
+    #[cfg(feature = "simulation")]
+    #[tokio::test]
+    async fn test_delete_emoji() {
+        let server = crate::simulation::add_server("emojis.sim");
+        server.set_route(
+            "DELETE",
+            "/emoji/emoji1",
+            200,
+            r#"{"id":"emoji1","creator_id":"user1","name":"party_parrot","create_at":1,"update_at":2,"delete_at":2}"#,
+        );
+
+        let client = MattermostClient::new("http://emojis.sim").unwrap();
+        client.delete_emoji("emoji1").await.unwrap();
+
+        server.set_route("DELETE", "/emoji/emoji2", 403, r#"{"message":"no"}"#);
+        assert!(client.delete_emoji("emoji2").await.is_err());
+
+        crate::simulation::remove_server("emojis.sim");
+    }
+}
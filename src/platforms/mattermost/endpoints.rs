@@ -0,0 +1,225 @@
+//! Server endpoint selection
+//!
+//! A server may be reachable under several URLs, e.g. an internal address on the
+//! corporate network and an external one elsewhere. The endpoints are kept in
+//! order of preference; when the client connects or the WebSocket reconnects,
+//! all of them are probed "happy eyeballs" style: probes start `PROBE_STAGGER`
+//! apart in order of preference, and the first endpoint to answer becomes the
+//! active one. With a single endpoint no probing is done.
+
+use futures::stream::{FuturesUnordered, StreamExt};
+use reqwest::Client;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+use url::Url;
+
+use crate::error::{Error, ErrorCode, Result};
+
+/// Delay between starting the probes of consecutive endpoints
+pub const PROBE_STAGGER: Duration = Duration::from_millis(250);
+
+/// How long a single probe may take
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Parse and normalize a server URL
+///
+/// # Returns
+/// The URL without a trailing slash, or an error if it is not a valid URL
+pub fn normalize_url(url: &str) -> Result<String> {
+    Url::parse(url)
+        .map_err(|e| Error::new(ErrorCode::InvalidArgument, format!("Invalid URL: {e}")))?;
+    Ok(url.trim_end_matches('/').to_string())
+}
+
+/// Run `probe` for `count` endpoints, starting them `stagger` apart
+///
+/// # Returns
+/// The index of the first endpoint whose probe succeeded, or None if all failed
+async fn race<F, Fut>(count: usize, stagger: Duration, probe: F) -> Option<usize>
+where
+    F: Fn(usize) -> Fut,
+    Fut: Future<Output = bool>,
+{
+    let mut attempts: FuturesUnordered<_> = (0..count)
+        .map(|index| {
+            let attempt = probe(index);
+            async move {
+                tokio::time::sleep(stagger * index as u32).await;
+                (index, attempt.await)
+            }
+        })
+        .collect();
+
+    while let Some((index, reachable)) = attempts.next().await {
+        if reachable {
+            return Some(index);
+        }
+    }
+    None
+}
+
+/// The URLs a server is reachable under and the one currently in use
+#[derive(Debug)]
+pub struct Endpoints {
+    /// Normalized URLs in order of preference; never empty
+    urls: RwLock<Vec<String>>,
+    /// Index of the active URL
+    active: RwLock<usize>,
+    /// Active URLs that have not been reported yet
+    changes: Mutex<VecDeque<String>>,
+    /// HTTP client used for probing
+    http_client: RwLock<Client>,
+}
+
+impl Endpoints {
+    /// Create an endpoint set with a single (primary) URL
+    pub fn new(primary: &str, http_client: Client) -> Result<Self> {
+        Ok(Self {
+            urls: RwLock::new(vec![normalize_url(primary)?]),
+            active: RwLock::new(0),
+            changes: Mutex::new(VecDeque::new()),
+            http_client: RwLock::new(http_client),
+        })
+    }
+
+    /// The URL currently in use, without a trailing slash
+    pub fn active(&self) -> String {
+        let urls = self.urls.read().unwrap();
+        urls[*self.active.read().unwrap()].clone()
+    }
+
+    /// Replace the fallback URLs tried after the primary URL
+    ///
+    /// The primary URL becomes active again.
+    pub fn set_fallbacks(&self, fallbacks: &[String]) -> Result<()> {
+        let fallbacks = fallbacks
+            .iter()
+            .map(|url| normalize_url(url))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut urls = self.urls.write().unwrap();
+        urls.truncate(1);
+        for url in fallbacks {
+            if !urls.contains(&url) {
+                urls.push(url);
+            }
+        }
+        self.activate_locked(&urls, 0);
+        Ok(())
+    }
+
+    /// All URLs, in order of preference
+    pub fn urls(&self) -> Vec<String> {
+        self.urls.read().unwrap().clone()
+    }
+
+    /// Replace the HTTP client used for probing (e.g. to use a proxy)
+    pub fn set_http_client(&self, http_client: Client) {
+        *self.http_client.write().unwrap() = http_client;
+    }
+
+    /// Whether there is more than one URL to choose from
+    pub fn has_fallbacks(&self) -> bool {
+        self.urls.read().unwrap().len() > 1
+    }
+
+    /// Probe the endpoints and make the first reachable one active
+    ///
+    /// # Returns
+    /// The active URL. If no endpoint is reachable, the active URL is kept.
+    pub async fn select(&self) -> String {
+        let urls = self.urls.read().unwrap().clone();
+        if urls.len() > 1 {
+            let http_client = self.http_client.read().unwrap().clone();
+            let probe = |index: usize| {
+                let request = http_client
+                    .get(format!("{}/api/v4/system/ping", urls[index]))
+                    .timeout(PROBE_TIMEOUT);
+                async move {
+                    matches!(super::client::send(request).await, Ok(response) if response.status().is_success())
+                }
+            };
+            if let Some(index) = race(urls.len(), PROBE_STAGGER, probe).await {
+                let current = self.urls.read().unwrap();
+                // The URLs may have been replaced while probing
+                if current.get(index) == Some(&urls[index]) {
+                    self.activate_locked(&current, index);
+                }
+            }
+        }
+        self.active()
+    }
+
+    /// Take the oldest active URL change that has not been reported yet
+    pub fn take_change(&self) -> Option<String> {
+        self.changes.lock().unwrap().pop_front()
+    }
+
+    fn activate_locked(&self, urls: &[String], index: usize) {
+        let mut active = self.active.write().unwrap();
+        if *active != index {
+            *active = index;
+            self.changes.lock().unwrap().push_back(urls[index].clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_race_prefers_earlier_endpoint() {
+        // The primary answers after 20ms, before the fallback's probe even starts
+        let winner = race(2, Duration::from_millis(100), |index| async move {
+            if index == 0 {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+            true
+        })
+        .await;
+        assert_eq!(winner, Some(0));
+    }
+
+    #[tokio::test]
+    async fn test_race_falls_back() {
+        let winner = race(
+            3,
+            Duration::from_millis(5),
+            |index| async move { index == 2 },
+        )
+        .await;
+        assert_eq!(winner, Some(2));
+
+        let none = race(2, Duration::from_millis(5), |_| async { false }).await;
+        assert_eq!(none, None);
+    }
+
+    #[test]
+    fn test_fallbacks_and_changes() {
+        let endpoints = Endpoints::new("https://internal.example.com/", Client::new()).unwrap();
+        assert_eq!(endpoints.active(), "https://internal.example.com");
+        assert!(!endpoints.has_fallbacks());
+
+        endpoints
+            .set_fallbacks(&[
+                "https://external.example.com".to_string(),
+                "https://internal.example.com".to_string(),
+            ])
+            .unwrap();
+        assert!(endpoints.has_fallbacks());
+        assert_eq!(endpoints.urls.read().unwrap().len(), 2);
+
+        endpoints.activate_locked(&endpoints.urls.read().unwrap().clone(), 1);
+        assert_eq!(endpoints.active(), "https://external.example.com");
+        assert_eq!(
+            endpoints.take_change().as_deref(),
+            Some("https://external.example.com")
+        );
+        assert_eq!(endpoints.take_change(), None);
+
+        assert!(endpoints.set_fallbacks(&["not a url".to_string()]).is_err());
+    }
+}
@@ -3,14 +3,170 @@
 //! This module provides functions for uploading, downloading, and managing files
 //! on a Mattermost server.
 
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
 
-use reqwest::multipart;
+use reqwest::header::{HeaderMap, CONTENT_RANGE, RANGE};
+use reqwest::{multipart, StatusCode};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
 use crate::error::{Error, ErrorCode, Result};
+use crate::platforms::{ChunkSink, ProgressSink};
+use crate::types::TransferProgress;
 
+use super::checksum::{self, Hasher};
 use super::client::MattermostClient;
-use super::types::FileInfo;
+use super::types::{FileInfo, UploadSession};
+
+/// Default largest file (bytes) `download_file` buffers in memory (256 MB)
+pub const DEFAULT_DOWNLOAD_SPILL_THRESHOLD: u64 = 256 * 1024 * 1024;
+
+/// Upper bound on the duration of a download to disk
+const STREAM_TIMEOUT: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Time without data after which a download to disk is abandoned
+const STREAM_IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Largest chunk of a resumable upload sent in one request (8 MB)
+const UPLOAD_CHUNK_SIZE: u64 = 8 * 1024 * 1024;
+
+/// A file on disk, as it was when its upload started
+type UploadKey = (String, PathBuf, u64, Option<SystemTime>);
+
+/// Upload sessions of interrupted resumable uploads
+///
+/// An upload is continued only if the file has the same size and modification
+/// time as when it started; otherwise a new session is created.
+#[derive(Debug, Default)]
+pub(crate) struct UploadSessions {
+    sessions: Mutex<HashMap<UploadKey, String>>,
+}
+
+impl UploadSessions {
+    fn key(channel_id: &str, path: &Path, metadata: &std::fs::Metadata) -> UploadKey {
+        (
+            channel_id.to_string(),
+            path.to_path_buf(),
+            metadata.len(),
+            metadata.modified().ok(),
+        )
+    }
+
+    fn get(&self, key: &UploadKey) -> Option<String> {
+        self.sessions.lock().unwrap().get(key).cloned()
+    }
+
+    fn insert(&self, key: UploadKey, upload_id: &str) {
+        self.sessions
+            .lock()
+            .unwrap()
+            .insert(key, upload_id.to_string());
+    }
+
+    fn remove(&self, key: &UploadKey) {
+        self.sessions.lock().unwrap().remove(key);
+    }
+}
+
+/// Read until `buffer` is full or the file ends
+async fn read_chunk(file: &mut tokio::fs::File, buffer: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        let n = file.read(&mut buffer[filled..]).await?;
+        if n == 0 {
+            break;
+        }
+        filled += n;
+    }
+    Ok(filled)
+}
+
+/// A new path in the temp directory for downloading a file to
+pub(crate) fn temp_download_path(file_id: &str) -> PathBuf {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+    // File IDs come from the server; keep only characters safe in file names
+    let id: String = file_id
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .collect();
+    std::env::temp_dir().join(format!("libcommunicator-{}-{n}-{id}", std::process::id()))
+}
+
+/// The file a download to `path` is written to until it is complete
+pub(crate) fn partial_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".part");
+    PathBuf::from(name)
+}
+
+/// Total size from a `Content-Range: bytes <start>-<end>/<total>` header
+fn content_range_total(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(CONTENT_RANGE)?
+        .to_str()
+        .ok()?
+        .rsplit_once('/')?
+        .1
+        .parse()
+        .ok()
+}
+
+/// Feed the contents of a file to a hasher
+async fn hash_file(path: &Path, hasher: &mut Hasher) -> std::io::Result<()> {
+    let mut file = tokio::fs::File::open(path).await?;
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let n = file.read(&mut buffer).await?;
+        if n == 0 {
+            return Ok(());
+        }
+        hasher.update(&buffer[..n]);
+    }
+}
+
+/// Turn an error response to a download into an error
+async fn check_download(response: reqwest::Response) -> Result<reqwest::Response> {
+    if response.status().is_success() {
+        return Ok(response);
+    }
+    let error_text = response
+        .text()
+        .await
+        .unwrap_or_else(|_| "Unknown error".to_string());
+    Err(Error::new(
+        ErrorCode::NetworkError,
+        format!("Failed to download file: {error_text}"),
+    ))
+}
+
+fn read_error(e: reqwest::Error) -> Error {
+    Error::new(
+        ErrorCode::NetworkError,
+        format!("Failed to read file data: {e}"),
+    )
+}
+
+/// Whether an uploaded file can be attached to a new post in a channel
+fn is_reusable(info: &FileInfo, channel_id: &str, size: usize) -> bool {
+    info.delete_at == 0
+        && info.post_id.is_empty()
+        && (info.channel_id.is_empty() || info.channel_id == channel_id)
+        && info.size == size as i64
+}
+
+fn too_large_for_memory(threshold: u64) -> Error {
+    Error::new(
+        ErrorCode::FileTooLarge,
+        format!(
+            "File is larger than the in-memory download limit of {threshold} bytes; download it to a path instead"
+        ),
+    )
+    .with_limit(threshold)
+}
 
 impl MattermostClient {
     /// Upload a file to a channel
@@ -39,6 +195,11 @@ impl MattermostClient {
         file_path: &Path,
         client_id: Option<&str>,
     ) -> Result<FileInfo> {
+        // Reject files over the server limit before reading them into memory
+        if let Ok(metadata) = tokio::fs::metadata(file_path).await {
+            self.server_limits().await.check_file_size(metadata.len())?;
+        }
+
         // Read the file from disk
         let file_data = tokio::fs::read(file_path).await.map_err(|e| {
             Error::new(
@@ -68,6 +229,10 @@ impl MattermostClient {
     ///
     /// # Returns
     /// A Result containing the FileInfo metadata for the uploaded file
+    ///
+    /// If this client already uploaded the same content under the same name
+    /// to the channel, and that file is not attached to a post yet, its
+    /// FileInfo is returned instead of uploading the data again.
     pub async fn upload_file_bytes(
         &self,
         channel_id: &str,
@@ -75,6 +240,18 @@ impl MattermostClient {
         file_data: Vec<u8>,
         client_id: Option<&str>,
     ) -> Result<FileInfo> {
+        self.server_limits()
+            .await
+            .check_file_size(file_data.len() as u64)?;
+
+        let checksum = checksum::sha256_hex(&file_data);
+        if let Some(file_info) = self
+            .reusable_upload(channel_id, filename, &checksum, file_data.len())
+            .await
+        {
+            return Ok(file_info);
+        }
+
         // Build the multipart form
         let file_part = multipart::Part::bytes(file_data).file_name(filename.to_string());
 
@@ -88,17 +265,13 @@ impl MattermostClient {
 
         // Send the request
         let url = self.api_url("/files");
-        let mut request = self.http_client.post(&url);
+        let mut request = self.http_client().post(&url);
 
         if let Some(token) = self.get_token().await {
             request = request.bearer_auth(token);
         }
 
-        let response = request
-            .multipart(form)
-            .send()
-            .await
-            .map_err(|e| Error::new(ErrorCode::NetworkError, format!("Upload failed: {e}")))?;
+        let response = self.send_request(request.multipart(form), "Upload").await?;
 
         // Parse the response
         #[derive(serde::Deserialize)]
@@ -108,13 +281,182 @@ impl MattermostClient {
             client_ids: Option<Vec<String>>,
         }
 
-        let upload_response: UploadResponse = self.handle_response(response).await?;
+        let upload_response: UploadResponse = self.handle_upload_response(response).await?;
 
-        upload_response
+        let file_info = upload_response
             .file_infos
             .into_iter()
             .next()
-            .ok_or_else(|| Error::new(ErrorCode::Unknown, "No file info returned from upload"))
+            .ok_or_else(|| Error::new(ErrorCode::Unknown, "No file info returned from upload"))?;
+        self.uploads()
+            .record(channel_id, filename, &checksum, &file_info.id);
+        self.checksums().record(&file_info.id, checksum);
+        Ok(file_info)
+    }
+
+    /// Upload a file from disk in chunks, reporting progress
+    ///
+    /// Uses the server's resumable uploads: only one chunk of the file is in
+    /// memory at a time. If the upload fails or `progress` aborts it, the
+    /// next upload of the unchanged file to the same channel continues from
+    /// the data the server already has.
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel ID where the file will be uploaded
+    /// * `file_path` - Path to the file to upload
+    /// * `progress` - Called after each chunk with the bytes the server has
+    ///
+    /// # Returns
+    /// A Result containing the FileInfo metadata for the uploaded file
+    pub async fn upload_file_resumable(
+        &self,
+        channel_id: &str,
+        file_path: &Path,
+        progress: &mut ProgressSink<'_>,
+    ) -> Result<FileInfo> {
+        let read_error = |e: std::io::Error| {
+            Error::new(
+                ErrorCode::InvalidArgument,
+                format!("Failed to read file: {e}"),
+            )
+        };
+        let metadata = tokio::fs::metadata(file_path).await.map_err(read_error)?;
+        let size = metadata.len();
+        self.server_limits().await.check_file_size(size)?;
+        if size == 0 {
+            // Upload sessions need data; nothing to report progress on either
+            return self.upload_file(channel_id, file_path, None).await;
+        }
+        let filename = file_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| Error::new(ErrorCode::InvalidArgument, "Invalid file path"))?;
+
+        let key = UploadSessions::key(channel_id, file_path, &metadata);
+        let resumed = match self.upload_sessions().get(&key) {
+            Some(upload_id) => match self.get_upload_session(&upload_id).await {
+                Ok(session) => Some(session),
+                Err(e) if e.code == ErrorCode::NotFound => None,
+                Err(e) => return Err(e),
+            },
+            None => None,
+        };
+        let session = match resumed {
+            Some(session) => session,
+            None => {
+                let session = self
+                    .create_upload_session(channel_id, filename, size)
+                    .await?;
+                self.upload_sessions().insert(key.clone(), &session.id);
+                session
+            }
+        };
+
+        let mut file = tokio::fs::File::open(file_path).await.map_err(read_error)?;
+        let mut hasher = Hasher::new();
+        let mut offset = (session.file_offset.max(0) as u64).min(size);
+        let mut buffer = vec![0; UPLOAD_CHUNK_SIZE.min(size) as usize];
+        // The server has the data before the offset; it is only hashed
+        let mut skipped = 0;
+        while skipped < offset {
+            let want = (offset - skipped).min(buffer.len() as u64) as usize;
+            let n = read_chunk(&mut file, &mut buffer[..want])
+                .await
+                .map_err(read_error)?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buffer[..n]);
+            skipped += n as u64;
+        }
+
+        let file_info = loop {
+            let n = read_chunk(&mut file, &mut buffer)
+                .await
+                .map_err(read_error)?;
+            if n == 0 {
+                self.upload_sessions().remove(&key);
+                return Err(Error::new(
+                    ErrorCode::InvalidState,
+                    format!("{} changed during the upload", file_path.display()),
+                ));
+            }
+            hasher.update(&buffer[..n]);
+
+            let url = self.api_url(&format!("/uploads/{}", session.id));
+            let mut request = self.http_client().post(&url).body(buffer[..n].to_vec());
+            if let Some(token) = self.get_token().await {
+                request = request.bearer_auth(token);
+            }
+            let response = self.send_request(request, "Upload").await?;
+            offset += n as u64;
+            let reported = TransferProgress {
+                transferred: offset,
+                total: Some(size),
+            };
+            if response.status() != StatusCode::NO_CONTENT {
+                let file_info: FileInfo = self.handle_upload_response(response).await?;
+                // Complete; there is nothing left to abort
+                let _ = progress(reported);
+                break file_info;
+            }
+            progress(reported)?;
+        };
+
+        self.upload_sessions().remove(&key);
+        let checksum = hasher.finish();
+        self.uploads()
+            .record(channel_id, filename, &checksum, &file_info.id);
+        self.checksums().record(&file_info.id, checksum);
+        Ok(file_info)
+    }
+
+    /// Start a resumable upload
+    async fn create_upload_session(
+        &self,
+        channel_id: &str,
+        filename: &str,
+        size: u64,
+    ) -> Result<UploadSession> {
+        let body = serde_json::json!({
+            "channel_id": channel_id,
+            "filename": filename,
+            "file_size": size,
+        });
+        let response = self.post("/uploads", &body).await?;
+        self.handle_upload_response(response).await
+    }
+
+    /// Get a resumable upload, with the bytes the server has received
+    async fn get_upload_session(&self, upload_id: &str) -> Result<UploadSession> {
+        let response = self.get(&format!("/uploads/{upload_id}")).await?;
+        self.handle_response(response).await
+    }
+
+    /// An earlier upload of the same content that can be attached instead
+    ///
+    /// The server is asked whether the file still exists unattached; if it
+    /// can't be reached, the data is uploaded again.
+    async fn reusable_upload(
+        &self,
+        channel_id: &str,
+        filename: &str,
+        checksum: &str,
+        size: usize,
+    ) -> Option<FileInfo> {
+        let file_id = self.uploads().find(channel_id, filename, checksum)?;
+        match self.get_file_info(&file_id).await {
+            Ok(info) if is_reusable(&info, channel_id, size) => Some(info),
+            Ok(_)
+            | Err(Error {
+                code: ErrorCode::NotFound,
+                ..
+            }) => {
+                self.uploads().forget(channel_id, filename, checksum);
+                None
+            }
+            Err(_) => None,
+        }
     }
 
     /// Download a file by its ID
@@ -135,28 +477,198 @@ impl MattermostClient {
     /// # Ok(())
     /// # }
     /// ```
+    ///
+    /// # Notes
+    /// Files larger than the download spill threshold (see
+    /// `set_download_spill_threshold`) fail with `ErrorCode::FileTooLarge`,
+    /// with the threshold as the error's limit. Download them with
+    /// `download_file_to_path` instead. Data that does not match the file's
+    /// checksum fails with `ErrorCode::ChecksumMismatch`.
     pub async fn download_file(&self, file_id: &str) -> Result<Vec<u8>> {
+        let threshold = self.download_spill_threshold();
         let endpoint = format!("/files/{file_id}");
-        let response = self.get(&endpoint).await?;
+        let mut response = check_download(self.get(&endpoint).await?).await?;
 
-        let status = response.status();
-        if !status.is_success() {
-            let error_text = response
-                .text()
+        if response.content_length().is_some_and(|len| len > threshold) {
+            return Err(too_large_for_memory(threshold));
+        }
+
+        let expected =
+            checksum::from_headers(response.headers()).or_else(|| self.checksums().get(file_id));
+        let mut data = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(read_error)? {
+            // The announced length may be missing or wrong
+            if (data.len() + chunk.len()) as u64 > threshold {
+                return Err(too_large_for_memory(threshold));
+            }
+            data.extend_from_slice(&chunk);
+        }
+
+        let actual = checksum::sha256_hex(&data);
+        checksum::verify(file_id, expected.as_deref(), &actual)?;
+        self.checksums().record(file_id, actual);
+        Ok(data)
+    }
+
+    /// Download a file straight to disk, without buffering it in memory
+    ///
+    /// The data is written to `<path>.part`, which is renamed to `path` once it
+    /// is complete and verified. An interrupted download keeps the `.part`
+    /// file, and the next download to the same path resumes where it stopped
+    /// with a Range request (or starts over if the server ignores the range).
+    ///
+    /// # Arguments
+    /// * `file_id` - The ID of the file to download
+    /// * `path` - The file to write; replaced if it exists
+    ///
+    /// # Returns
+    /// The size of the file. If the data does not match the file's checksum,
+    /// the partial file is removed and the error is `ErrorCode::ChecksumMismatch`.
+    pub async fn download_file_to_path(&self, file_id: &str, path: &Path) -> Result<u64> {
+        let part_path = partial_path(path);
+        let write_error = |e: std::io::Error| {
+            Error::new(
+                ErrorCode::Unknown,
+                format!("Failed to write {}: {e}", part_path.display()),
+            )
+        };
+
+        let mut offset = tokio::fs::metadata(&part_path)
+            .await
+            .map(|metadata| metadata.len())
+            .unwrap_or(0);
+        let mut response = self.request_download(file_id, offset).await?;
+        if offset > 0 && response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+            // The partial file is not a prefix of the file; start over
+            offset = 0;
+            response = self.request_download(file_id, 0).await?;
+        }
+        let mut response = check_download(response).await?;
+
+        let resumed = offset > 0 && response.status() == StatusCode::PARTIAL_CONTENT;
+        let expected =
+            checksum::from_headers(response.headers()).or_else(|| self.checksums().get(file_id));
+        let total = if resumed {
+            content_range_total(response.headers())
+        } else {
+            response.content_length()
+        };
+
+        let mut hasher = Hasher::new();
+        let mut size = 0u64;
+        let mut file = if resumed {
+            hash_file(&part_path, &mut hasher)
                 .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
+                .map_err(write_error)?;
+            size = offset;
+            tokio::fs::OpenOptions::new()
+                .append(true)
+                .open(&part_path)
+                .await
+        } else {
+            tokio::fs::File::create(&part_path).await
+        }
+        .map_err(write_error)?;
+
+        // On failure the partial file is kept for resuming
+        loop {
+            let chunk = tokio::time::timeout(STREAM_IDLE_TIMEOUT, response.chunk())
+                .await
+                .map_err(|_| Error::new(ErrorCode::Timeout, "File download stalled"))?
+                .map_err(read_error)?;
+            let Some(chunk) = chunk else { break };
+            hasher.update(&chunk);
+            file.write_all(&chunk).await.map_err(write_error)?;
+            size += chunk.len() as u64;
+        }
+        file.flush().await.map_err(write_error)?;
+        drop(file);
+
+        if let Some(total) = total.filter(|total| *total != size) {
             return Err(Error::new(
                 ErrorCode::NetworkError,
-                format!("Failed to download file: {error_text}"),
+                format!("Download of file {file_id} ended after {size} of {total} bytes"),
             ));
         }
 
-        response.bytes().await.map(|b| b.to_vec()).map_err(|e| {
-            Error::new(
+        let actual = hasher.finish();
+        if let Err(e) = checksum::verify(file_id, expected.as_deref(), &actual) {
+            let _ = tokio::fs::remove_file(&part_path).await;
+            return Err(e);
+        }
+        tokio::fs::rename(&part_path, path)
+            .await
+            .map_err(write_error)?;
+        self.checksums().record(file_id, actual);
+        Ok(size)
+    }
+
+    /// Download a file in chunks handed to a sink, without buffering it
+    ///
+    /// # Arguments
+    /// * `file_id` - The ID of the file to download
+    /// * `sink` - Called with each chunk and the progress including it; an
+    ///   error from it aborts the download and is returned
+    ///
+    /// # Returns
+    /// The size of the file. The checksum is verified after the last chunk
+    /// was handed over, so on `ErrorCode::ChecksumMismatch` the caller must
+    /// discard the data it received.
+    pub async fn download_file_streaming(
+        &self,
+        file_id: &str,
+        sink: &mut ChunkSink<'_>,
+    ) -> Result<u64> {
+        let mut response = check_download(self.request_download(file_id, 0).await?).await?;
+        let expected =
+            checksum::from_headers(response.headers()).or_else(|| self.checksums().get(file_id));
+        let total = response.content_length();
+
+        let mut hasher = Hasher::new();
+        let mut size = 0u64;
+        loop {
+            let chunk = tokio::time::timeout(STREAM_IDLE_TIMEOUT, response.chunk())
+                .await
+                .map_err(|_| Error::new(ErrorCode::Timeout, "File download stalled"))?
+                .map_err(read_error)?;
+            let Some(chunk) = chunk else { break };
+            hasher.update(&chunk);
+            size += chunk.len() as u64;
+            sink(
+                &chunk,
+                TransferProgress {
+                    transferred: size,
+                    total,
+                },
+            )?;
+        }
+
+        if let Some(total) = total.filter(|total| *total != size) {
+            return Err(Error::new(
                 ErrorCode::NetworkError,
-                format!("Failed to read file data: {e}"),
-            )
-        })
+                format!("Download of file {file_id} ended after {size} of {total} bytes"),
+            ));
+        }
+
+        let actual = hasher.finish();
+        checksum::verify(file_id, expected.as_deref(), &actual)?;
+        self.checksums().record(file_id, actual);
+        Ok(size)
+    }
+
+    /// Request a file, from `offset` on if it is not 0
+    async fn request_download(&self, file_id: &str, offset: u64) -> Result<reqwest::Response> {
+        let url = self.api_url(&format!("/files/{file_id}"));
+        // Large files take longer than the timeout of other requests; stalls
+        // are caught per chunk instead
+        let mut request = self.http_client().get(&url).timeout(STREAM_TIMEOUT);
+        if offset > 0 {
+            request = request.header(RANGE, format!("bytes={offset}-"));
+        }
+        if let Some(token) = self.get_token().await {
+            request = request.bearer_auth(token);
+        }
+        self.send_request(request, "GET").await
     }
 
     /// Get file metadata without downloading the file
@@ -287,4 +799,153 @@ mod tests {
         // This test just ensures the module compiles and basic types exist
         // Integration tests would require a real Mattermost server
     }
+
+    #[test]
+    fn test_temp_download_path() {
+        let first = temp_download_path("../abc/123");
+        let second = temp_download_path("../abc/123");
+        assert_ne!(first, second);
+        assert_eq!(first.parent(), Some(std::env::temp_dir().as_path()));
+        let name = first.file_name().unwrap().to_string_lossy();
+        assert!(name.starts_with("libcommunicator-"));
+        assert!(name.ends_with("-abc123"));
+    }
+
+    #[test]
+    fn test_resume_helpers() {
+        assert_eq!(
+            partial_path(Path::new("/tmp/report.pdf")),
+            PathBuf::from("/tmp/report.pdf.part")
+        );
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(content_range_total(&headers), None);
+        headers.insert(CONTENT_RANGE, "bytes 100-199/200".parse().unwrap());
+        assert_eq!(content_range_total(&headers), Some(200));
+        headers.insert(CONTENT_RANGE, "bytes 100-199/*".parse().unwrap());
+        assert_eq!(content_range_total(&headers), None);
+    }
+
+    #[test]
+    fn test_is_reusable() {
+        let info: FileInfo = serde_json::from_value(serde_json::json!({
+            "id": "file1", "user_id": "user1", "post_id": "", "channel_id": "chan1",
+            "create_at": 0, "update_at": 0, "delete_at": 0, "name": "a.txt",
+            "extension": "txt", "size": 5, "mime_type": "text/plain"
+        }))
+        .unwrap();
+        assert!(is_reusable(&info, "chan1", 5));
+        assert!(!is_reusable(&info, "chan2", 5));
+        assert!(!is_reusable(&info, "chan1", 6));
+
+        // A file attached to a post can't be attached to another one
+        let attached = FileInfo {
+            post_id: "post1".to_string(),
+            ..info.clone()
+        };
+        assert!(!is_reusable(&attached, "chan1", 5));
+        let deleted = FileInfo {
+            delete_at: 1,
+            ..info
+        };
+        assert!(!is_reusable(&deleted, "chan1", 5));
+    }
+
+    #[cfg(feature = "simulation")]
+    #[tokio::test]
+    async fn test_download_file_streaming() {
+        let server = crate::simulation::add_server("download.sim");
+        server.set_route("GET", "/files/file1", 200, "hello world");
+        let client = MattermostClient::new("http://download.sim").unwrap();
+
+        let mut data = Vec::new();
+        let mut last = None;
+        let size = client
+            .download_file_streaming("file1", &mut |chunk, progress| {
+                data.extend_from_slice(chunk);
+                last = Some(progress);
+                Ok(())
+            })
+            .await
+            .unwrap();
+        assert_eq!(size, 11);
+        assert_eq!(data, b"hello world");
+        assert_eq!(last.map(|progress| progress.transferred), Some(11));
+
+        let error = client
+            .download_file_streaming("file1", &mut |_, _| Err(Error::invalid_argument("stop")))
+            .await
+            .unwrap_err();
+        assert_eq!(error.message, "stop");
+        crate::simulation::remove_server("download.sim");
+    }
+
+    #[cfg(feature = "simulation")]
+    #[tokio::test]
+    async fn test_payload_too_large() {
+        let server = crate::simulation::add_server("too-large.sim");
+        server.set_route("POST", "/files", 413, "Request Entity Too Large");
+        server.set_route("GET", "/users/me", 413, "Request Entity Too Large");
+        let client = MattermostClient::new("http://too-large.sim").unwrap();
+
+        let error = client
+            .upload_file_bytes("chan1", "a.txt", b"hello".to_vec(), None)
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::FileTooLarge);
+        assert_eq!(error.http_status(), Some(413));
+
+        // Only uploads are about files
+        let error = client.get_current_user().await.unwrap_err();
+        assert_eq!(error.code, ErrorCode::InvalidArgument);
+        crate::simulation::remove_server("too-large.sim");
+    }
+
+    #[cfg(feature = "simulation")]
+    #[tokio::test]
+    async fn test_upload_file_resumable() {
+        let server = crate::simulation::add_server("upload.sim");
+        server.set_route(
+            "POST",
+            "/uploads",
+            201,
+            r#"{"id":"up1","file_offset":0,"file_size":5}"#,
+        );
+        server.set_route(
+            "POST",
+            "/uploads/up1",
+            201,
+            serde_json::json!({
+                "id": "file1", "user_id": "user1", "post_id": "", "channel_id": "chan1",
+                "create_at": 0, "update_at": 0, "delete_at": 0, "name": "a.txt",
+                "extension": "txt", "size": 5, "mime_type": "text/plain"
+            })
+            .to_string(),
+        );
+        let path =
+            std::env::temp_dir().join(format!("libcommunicator-upload-{}.txt", std::process::id()));
+        std::fs::write(&path, "hello").unwrap();
+        let client = MattermostClient::new("http://upload.sim").unwrap();
+
+        let mut reported = Vec::new();
+        let file_info = client
+            .upload_file_resumable("chan1", &path, &mut |progress| {
+                reported.push(progress);
+                Ok(())
+            })
+            .await
+            .unwrap();
+        let _ = std::fs::remove_file(&path);
+        crate::simulation::remove_server("upload.sim");
+
+        assert_eq!(file_info.id, "file1");
+        assert_eq!(
+            reported,
+            vec![TransferProgress {
+                transferred: 5,
+                total: Some(5)
+            }]
+        );
+        assert!(client.checksums().get("file1").is_some());
+    }
 }
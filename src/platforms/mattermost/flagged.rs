@@ -0,0 +1,117 @@
+//! Flagged ("saved") posts
+//!
+//! Mattermost stores saved posts as preferences of the `flagged_post`
+//! category, named after the post ID. They are private to the user.
+
+use crate::error::Result;
+
+use super::client::MattermostClient;
+use super::types::{MattermostPost, PostList, UserPreference};
+
+/// Preference category of flagged posts
+const FLAGGED_POST_CATEGORY: &str = "flagged_post";
+
+impl MattermostClient {
+    /// Flag (save) a post for a user
+    ///
+    /// # Arguments
+    /// * `user_id` - The ID of the user
+    /// * `post_id` - The ID of the post to flag
+    ///
+    /// # Returns
+    /// A Result indicating success or failure
+    pub async fn flag_post(&self, user_id: &str, post_id: &str) -> Result<()> {
+        let preference = flag_preference(user_id, post_id);
+        self.set_user_preferences(user_id, &[preference]).await
+    }
+
+    /// Remove a post from a user's flagged posts
+    ///
+    /// # Arguments
+    /// * `user_id` - The ID of the user
+    /// * `post_id` - The ID of the post to unflag
+    ///
+    /// # Returns
+    /// A Result indicating success or failure
+    pub async fn unflag_post(&self, user_id: &str, post_id: &str) -> Result<()> {
+        let preference = flag_preference(user_id, post_id);
+        self.delete_user_preferences(user_id, &[preference]).await
+    }
+
+    /// Get one page of a user's flagged posts
+    ///
+    /// # Arguments
+    /// * `user_id` - The ID of the user
+    /// * `page` - The page number (0-based)
+    /// * `per_page` - Number of posts per page
+    ///
+    /// # Returns
+    /// A Result containing the flagged posts, most recent first, or an Error
+    ///
+    /// # API Endpoint
+    /// `GET /api/v4/users/{user_id}/posts/flagged`
+    pub async fn get_flagged_posts(
+        &self,
+        user_id: &str,
+        page: u32,
+        per_page: u32,
+    ) -> Result<Vec<MattermostPost>> {
+        let endpoint = format!("/users/{user_id}/posts/flagged?page={page}&per_page={per_page}");
+        let response = self.get(&endpoint).await?;
+        let post_list: PostList = self.handle_response(response).await?;
+
+        let mut posts = Vec::new();
+        for post_id in &post_list.order {
+            if let Some(post) = post_list.posts.get(post_id) {
+                posts.push(post.clone());
+            }
+        }
+
+        Ok(posts)
+    }
+}
+
+/// The preference marking a post as flagged
+fn flag_preference(user_id: &str, post_id: &str) -> UserPreference {
+    UserPreference::new(
+        user_id.to_string(),
+        FLAGGED_POST_CATEGORY.to_string(),
+        post_id.to_string(),
+        "true".to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flagged_endpoints() {
+        let client = MattermostClient::new("https://mattermost.example.com").unwrap();
+        assert_eq!(
+            client.api_url("/users/user1/posts/flagged"),
+            "https://mattermost.example.com/api/v4/users/user1/posts/flagged"
+        );
+    }
+
+    #[cfg(feature = "simulation")]
+    #[tokio::test]
+    async fn test_get_flagged_posts() {
+        let server = crate::simulation::add_server("flagged.sim");
+        server.set_route(
+            "GET",
+            "/users/u1/posts/flagged",
+            200,
+            r#"{"order":["p2","p1"],"posts":{
+                "p1":{"id":"p1","create_at":1,"update_at":1,"delete_at":0,"edit_at":0,"user_id":"u2","channel_id":"c1","message":"first"},
+                "p2":{"id":"p2","create_at":2,"update_at":2,"delete_at":0,"edit_at":0,"user_id":"u2","channel_id":"c1","message":"second"}}}"#,
+        );
+
+        let client = MattermostClient::new("http://flagged.sim").unwrap();
+        let posts = client.get_flagged_posts("u1", 0, 60).await.unwrap();
+        let ids: Vec<&str> = posts.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec!["p2", "p1"]);
+
+        crate::simulation::remove_server("flagged.sim");
+    }
+}
@@ -0,0 +1,121 @@
+//! User groups
+//!
+//! LDAP groups are synced by the server; custom groups are managed by users.
+//! Only groups with `allow_reference` set can be mentioned.
+
+use crate::error::Result;
+
+use super::client::MattermostClient;
+use super::types::{GroupMembersResponse, MattermostGroup, MattermostUser};
+
+/// Page size used when listing all groups or members
+const LIST_PAGE_SIZE: usize = 200;
+
+impl MattermostClient {
+    /// Get one page of groups
+    ///
+    /// # Arguments
+    /// * `page` - The page number (0-based)
+    /// * `per_page` - Number of groups per page
+    /// * `referenceable_only` - Whether to only list groups that can be mentioned
+    ///
+    /// # Returns
+    /// A Result containing the groups, with member counts, or an Error
+    ///
+    /// # API Endpoint
+    /// `GET /api/v4/groups`
+    pub async fn get_groups(
+        &self,
+        page: usize,
+        per_page: usize,
+        referenceable_only: bool,
+    ) -> Result<Vec<MattermostGroup>> {
+        let mut endpoint =
+            format!("/groups?page={page}&per_page={per_page}&include_member_count=true");
+        if referenceable_only {
+            endpoint.push_str("&filter_allow_reference=true");
+        }
+        let response = self.get(&endpoint).await?;
+        self.handle_response(response).await
+    }
+
+    /// Get all groups visible to the current user
+    pub async fn get_all_groups(&self, referenceable_only: bool) -> Result<Vec<MattermostGroup>> {
+        let mut groups = Vec::new();
+        for page in 0.. {
+            let batch = self
+                .get_groups(page, LIST_PAGE_SIZE, referenceable_only)
+                .await?;
+            let done = batch.len() < LIST_PAGE_SIZE;
+            groups.extend(batch);
+            if done {
+                break;
+            }
+        }
+        Ok(groups)
+    }
+
+    /// Get one page of a group's members
+    ///
+    /// # Arguments
+    /// * `group_id` - The ID of the group
+    /// * `page` - The page number (0-based)
+    /// * `per_page` - Number of members per page
+    ///
+    /// # Returns
+    /// A Result containing the members and the total member count or an Error
+    ///
+    /// # API Endpoint
+    /// `GET /api/v4/groups/{group_id}/members`
+    pub async fn get_group_members(
+        &self,
+        group_id: &str,
+        page: usize,
+        per_page: usize,
+    ) -> Result<GroupMembersResponse> {
+        let endpoint = format!("/groups/{group_id}/members?page={page}&per_page={per_page}");
+        let response = self.get(&endpoint).await?;
+        self.handle_response(response).await
+    }
+
+    /// Get all members of a group
+    pub async fn get_all_group_members(&self, group_id: &str) -> Result<Vec<MattermostUser>> {
+        let mut members = Vec::new();
+        for page in 0.. {
+            let batch = self
+                .get_group_members(group_id, page, LIST_PAGE_SIZE)
+                .await?
+                .members;
+            let done = batch.len() < LIST_PAGE_SIZE;
+            members.extend(batch);
+            if done {
+                break;
+            }
+        }
+        Ok(members)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Group;
+
+    #[test]
+    fn test_group_conversion() {
+        let mm_group: MattermostGroup = serde_json::from_str(
+            r#"{"id":"g1","name":"developers","display_name":"Developers","source":"custom",
+                "member_count":2,"allow_reference":true,"delete_at":0}"#,
+        )
+        .unwrap();
+        let group: Group = mm_group.into();
+        assert_eq!(group.name.as_deref(), Some("developers"));
+
+        // LDAP groups keep their name but cannot be mentioned until allowed
+        let mm_group: MattermostGroup = serde_json::from_str(
+            r#"{"id":"g2","name":"ops","display_name":"Ops","source":"ldap","allow_reference":false}"#,
+        )
+        .unwrap();
+        assert_eq!(Group::from(mm_group).name, None);
+    }
+}
@@ -0,0 +1,160 @@
+//! OAuth 2.0 apps and personal access tokens
+//!
+//! Whether these endpoints are usable depends on the server configuration
+//! (`EnableOAuthServiceProvider`, `EnableUserAccessTokens`) and the caller's
+//! permissions; the server rejects requests otherwise.
+
+use crate::error::Result;
+
+use super::client::MattermostClient;
+use super::types::{CreateOAuthAppRequest, MattermostOAuthApp, UserAccessToken};
+
+/// Page size used when listing all apps or tokens
+const LIST_PAGE_SIZE: usize = 200;
+
+impl MattermostClient {
+    /// Register an OAuth 2.0 app
+    ///
+    /// # Arguments
+    /// * `request` - The app's name, homepage, callback URLs and options
+    ///
+    /// # Returns
+    /// A Result containing the registered app, including its client secret
+    ///
+    /// # Notes
+    /// Requires `manage_oauth` permission; trusted apps require
+    /// `manage_system_wide_oauth`.
+    pub async fn create_oauth_app(
+        &self,
+        request: &CreateOAuthAppRequest,
+    ) -> Result<MattermostOAuthApp> {
+        let response = self.post("/oauth/apps", request).await?;
+        self.handle_response(response).await
+    }
+
+    /// Get one page of OAuth 2.0 apps
+    ///
+    /// # Arguments
+    /// * `page` - The page number (0-based)
+    /// * `per_page` - Number of apps per page
+    ///
+    /// # Returns
+    /// A Result containing the apps or an Error
+    ///
+    /// # Notes
+    /// With `manage_system_wide_oauth` all apps are returned; otherwise only the
+    /// apps registered by the current user.
+    pub async fn get_oauth_apps(
+        &self,
+        page: usize,
+        per_page: usize,
+    ) -> Result<Vec<MattermostOAuthApp>> {
+        let endpoint = format!("/oauth/apps?page={page}&per_page={per_page}");
+        let response = self.get(&endpoint).await?;
+        self.handle_response(response).await
+    }
+
+    /// Get all OAuth 2.0 apps visible to the current user
+    pub async fn get_all_oauth_apps(&self) -> Result<Vec<MattermostOAuthApp>> {
+        let mut apps = Vec::new();
+        for page in 0.. {
+            let batch = self.get_oauth_apps(page, LIST_PAGE_SIZE).await?;
+            let done = batch.len() < LIST_PAGE_SIZE;
+            apps.extend(batch);
+            if done {
+                break;
+            }
+        }
+        Ok(apps)
+    }
+
+    /// Delete an OAuth 2.0 app
+    ///
+    /// # Arguments
+    /// * `app_id` - The ID of the app
+    ///
+    /// # Returns
+    /// A Result indicating success or failure
+    pub async fn delete_oauth_app(&self, app_id: &str) -> Result<()> {
+        let endpoint = format!("/oauth/apps/{app_id}");
+        let response = self.delete(&endpoint).await?;
+        let _: serde_json::Value = self.handle_response(response).await?;
+        Ok(())
+    }
+
+    /// Create a personal access token for a user
+    ///
+    /// # Arguments
+    /// * `user_id` - The ID of the user the token acts as
+    /// * `description` - A description of what the token is used for
+    ///
+    /// # Returns
+    /// A Result containing the token, including its value
+    ///
+    /// # Notes
+    /// Requires `create_user_access_token` permission, and
+    /// `edit_other_users` for tokens of other users.
+    pub async fn create_user_access_token(
+        &self,
+        user_id: &str,
+        description: &str,
+    ) -> Result<UserAccessToken> {
+        let endpoint = format!("/users/{user_id}/tokens");
+        let body = serde_json::json!({ "description": description });
+        let response = self.post(&endpoint, &body).await?;
+        self.handle_response(response).await
+    }
+
+    /// Get one page of a user's personal access tokens
+    ///
+    /// # Arguments
+    /// * `user_id` - The ID of the user
+    /// * `page` - The page number (0-based)
+    /// * `per_page` - Number of tokens per page
+    ///
+    /// # Returns
+    /// A Result containing the tokens (without their values) or an Error
+    pub async fn get_user_access_tokens(
+        &self,
+        user_id: &str,
+        page: usize,
+        per_page: usize,
+    ) -> Result<Vec<UserAccessToken>> {
+        let endpoint = format!("/users/{user_id}/tokens?page={page}&per_page={per_page}");
+        let response = self.get(&endpoint).await?;
+        self.handle_response(response).await
+    }
+
+    /// Get all personal access tokens of a user
+    pub async fn get_all_user_access_tokens(&self, user_id: &str) -> Result<Vec<UserAccessToken>> {
+        let mut tokens = Vec::new();
+        for page in 0.. {
+            let batch = self
+                .get_user_access_tokens(user_id, page, LIST_PAGE_SIZE)
+                .await?;
+            let done = batch.len() < LIST_PAGE_SIZE;
+            tokens.extend(batch);
+            if done {
+                break;
+            }
+        }
+        Ok(tokens)
+    }
+
+    /// Revoke a personal access token
+    ///
+    /// # Arguments
+    /// * `token_id` - The ID of the token (not the token value)
+    ///
+    /// # Returns
+    /// A Result indicating success or failure
+    ///
+    /// # Notes
+    /// Sessions created with the token are revoked as well.
+    pub async fn revoke_user_access_token(&self, token_id: &str) -> Result<()> {
+        let body = serde_json::json!({ "token_id": token_id });
+        let response = self.post("/users/tokens/revoke", &body).await?;
+        let _: serde_json::Value = self.handle_response(response).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,205 @@
+//! Typing indicators, user statuses and pinned messages as last seen in events
+//!
+//! All only stay correct while events keep arriving. After a reconnect, events
+//! from the outage are lost: a "typing" event is never followed by the post
+//! that would have ended it, and status and pin changes are missed. The
+//! platform therefore clears the typing and pin trackers and marks all statuses
+//! stale when the WebSocket reconnects, then refreshes the statuses that are
+//! on screen.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::types::user::UserStatus;
+
+/// How long a user counts as typing after their last typing event
+pub const TYPING_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Users currently typing, per channel
+#[derive(Debug, Default)]
+pub struct TypingTracker {
+    /// Channel ID -> user ID -> time of the last typing event
+    channels: Mutex<HashMap<String, HashMap<String, Instant>>>,
+}
+
+impl TypingTracker {
+    /// Record a typing event
+    pub fn record(&self, channel_id: &str, user_id: &str, now: Instant) {
+        self.channels
+            .lock()
+            .unwrap()
+            .entry(channel_id.to_string())
+            .or_default()
+            .insert(user_id.to_string(), now);
+    }
+
+    /// Forget that a user is typing (e.g. because their message arrived)
+    pub fn remove(&self, channel_id: &str, user_id: &str) {
+        let mut channels = self.channels.lock().unwrap();
+        if let Some(users) = channels.get_mut(channel_id) {
+            users.remove(user_id);
+            if users.is_empty() {
+                channels.remove(channel_id);
+            }
+        }
+    }
+
+    /// Users typing in a channel, dropping those whose indicator timed out
+    pub fn typing_users(&self, channel_id: &str, now: Instant) -> Vec<String> {
+        let mut channels = self.channels.lock().unwrap();
+        let Some(users) = channels.get_mut(channel_id) else {
+            return Vec::new();
+        };
+        users.retain(|_, last_seen| now.duration_since(*last_seen) < TYPING_TIMEOUT);
+        let mut typing: Vec<String> = users.keys().cloned().collect();
+        if typing.is_empty() {
+            channels.remove(channel_id);
+        }
+        typing.sort();
+        typing
+    }
+
+    /// Forget all typing users
+    pub fn clear(&self) {
+        self.channels.lock().unwrap().clear();
+    }
+}
+
+/// User statuses, and whether they may be outdated
+#[derive(Debug, Default)]
+pub struct StatusTracker {
+    /// User ID -> (status, stale)
+    statuses: Mutex<HashMap<String, (UserStatus, bool)>>,
+}
+
+impl StatusTracker {
+    /// Record a user's current status
+    pub fn set(&self, user_id: &str, status: UserStatus) {
+        self.statuses
+            .lock()
+            .unwrap()
+            .insert(user_id.to_string(), (status, false));
+    }
+
+    /// Get a user's status, unless it is unknown or stale
+    pub fn get_fresh(&self, user_id: &str) -> Option<UserStatus> {
+        match self.statuses.lock().unwrap().get(user_id) {
+            Some((status, false)) => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// Mark all statuses as possibly outdated
+    pub fn mark_all_stale(&self) {
+        for (_, stale) in self.statuses.lock().unwrap().values_mut() {
+            *stale = true;
+        }
+    }
+
+    /// Forget all statuses
+    pub fn clear(&self) {
+        self.statuses.lock().unwrap().clear();
+    }
+}
+
+/// Pinned messages of the channels whose pinned list was fetched
+#[derive(Debug, Default)]
+pub struct PinTracker {
+    /// Channel ID -> IDs of the pinned messages
+    channels: Mutex<HashMap<String, HashSet<String>>>,
+}
+
+impl PinTracker {
+    /// Record the complete list of pinned messages in a channel
+    pub fn set_channel(&self, channel_id: &str, message_ids: impl IntoIterator<Item = String>) {
+        self.channels
+            .lock()
+            .unwrap()
+            .insert(channel_id.to_string(), message_ids.into_iter().collect());
+    }
+
+    /// Record whether a message is pinned
+    ///
+    /// Returns whether it was pinned before, if the channel's pinned list is known.
+    pub fn update(&self, channel_id: &str, message_id: &str, pinned: bool) -> Option<bool> {
+        let mut channels = self.channels.lock().unwrap();
+        let pinned_ids = channels.get_mut(channel_id)?;
+        let was_pinned = if pinned {
+            !pinned_ids.insert(message_id.to_string())
+        } else {
+            pinned_ids.remove(message_id)
+        };
+        Some(was_pinned)
+    }
+
+    /// Forget a deleted message
+    pub fn remove(&self, channel_id: &str, message_id: &str) {
+        if let Some(pinned_ids) = self.channels.lock().unwrap().get_mut(channel_id) {
+            pinned_ids.remove(message_id);
+        }
+    }
+
+    /// Forget all pinned lists
+    pub fn clear(&self) {
+        self.channels.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_typing_expires_and_clears() {
+        let tracker = TypingTracker::default();
+        let start = Instant::now();
+        tracker.record("chan", "alice", start);
+        tracker.record("chan", "bob", start + Duration::from_secs(3));
+        assert_eq!(tracker.typing_users("chan", start), vec!["alice", "bob"]);
+
+        assert_eq!(
+            tracker.typing_users("chan", start + Duration::from_secs(6)),
+            vec!["bob"]
+        );
+
+        tracker.remove("chan", "bob");
+        assert!(tracker.typing_users("chan", start).is_empty());
+
+        tracker.record("chan", "alice", start);
+        tracker.clear();
+        assert!(tracker.typing_users("chan", start).is_empty());
+    }
+
+    #[test]
+    fn test_stale_statuses() {
+        let tracker = StatusTracker::default();
+        tracker.set("alice", UserStatus::Online);
+        assert_eq!(tracker.get_fresh("alice"), Some(UserStatus::Online));
+
+        tracker.mark_all_stale();
+        assert_eq!(tracker.get_fresh("alice"), None);
+
+        tracker.set("alice", UserStatus::Away);
+        assert_eq!(tracker.get_fresh("alice"), Some(UserStatus::Away));
+        assert_eq!(tracker.get_fresh("bob"), None);
+    }
+
+    #[test]
+    fn test_pin_changes() {
+        let tracker = PinTracker::default();
+        assert_eq!(tracker.update("chan", "post1", true), None);
+
+        tracker.set_channel("chan", vec!["post1".to_string()]);
+        assert_eq!(tracker.update("chan", "post1", true), Some(true));
+        assert_eq!(tracker.update("chan", "post2", true), Some(false));
+        assert_eq!(tracker.update("chan", "post1", false), Some(true));
+        assert_eq!(tracker.update("chan", "post1", false), Some(false));
+
+        tracker.remove("chan", "post2");
+        assert_eq!(tracker.update("chan", "post2", false), Some(false));
+
+        tracker.clear();
+        assert_eq!(tracker.update("chan", "post2", true), None);
+    }
+}
@@ -4,31 +4,60 @@
 //! The OpenAPI specification for the Mattermost API is available in
 //! `api-spec.yaml` in this directory.
 
+mod activity;
 mod auth;
+mod bots;
 mod cache;
+mod cache_store;
 mod channels;
+mod checksum;
+mod circuit;
 mod client;
+mod commands;
 mod convert;
+mod drafts;
+mod echo;
+mod emojis;
+mod endpoints;
 mod files;
+mod flagged;
+mod groups;
+mod integrations;
+mod live_state;
+mod outbox;
+mod permissions;
 mod pinned;
 mod platform_impl;
+mod polls;
 mod posts;
 mod preferences;
+mod prefetch;
+mod rate_limit;
 mod reactions;
+mod read_state;
+mod resync;
+mod scheduled;
 mod search;
+mod session;
+mod sidebar;
+mod sso;
+mod startup;
 mod status;
 mod teams;
 mod threads;
 mod types;
 mod users;
-mod websocket;
+pub(crate) mod websocket;
+mod window;
 
 pub use cache::Cache;
 pub use client::{MattermostClient, RateLimitInfo};
 pub use convert::{status_string_to_user_status, user_status_to_status_string};
 pub use platform_impl::MattermostPlatform;
+pub use polls::poll_from_message;
 pub use search::{
-    ChannelSearchRequest, FileSearchRequest, FileSearchResponse, FileSearchResult,
-    PostSearchOptions, UserSearchRequest,
+    to_mattermost_terms, ChannelSearchRequest, FileSearchRequest, FileSearchResponse,
+    FileSearchResult, PostSearchOptions, UserSearchRequest,
 };
+pub use startup::StartupSnapshot;
 pub use types::*;
@@ -0,0 +1,286 @@
+//! Offline outbox
+//!
+//! Messages sent while the WebSocket is down, or whose send fails for lack of
+//! connectivity, are queued instead of failing. The send call returns a
+//! placeholder message whose `outbox_id` metadata identifies the queued entry.
+//! The queue is flushed in order when the WebSocket reconnects, and each
+//! outcome is reported as `PlatformEvent::MessageSendSucceeded` or
+//! `PlatformEvent::MessageSendFailed` carrying the same ID.
+//!
+//! With a configured file, the queue is saved on every change, so messages
+//! queued before the application quit are sent after the next connect.
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, ErrorCode, Result};
+use crate::migrations;
+use crate::types::Message;
+
+/// Time between attempts to flush the outbox while the WebSocket stays up
+pub const OUTBOX_RETRY_INTERVAL: Duration = Duration::from_secs(15);
+
+/// A message waiting to be sent
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutboxEntry {
+    /// ID reported in the placeholder message and the send outcome event
+    pub outbox_id: String,
+    pub channel_id: String,
+    pub text: String,
+    /// Thread root, for replies
+    pub root_id: Option<String>,
+    /// When the message was queued
+    pub queued_at: DateTime<Utc>,
+}
+
+impl OutboxEntry {
+    /// The message returned by the send call that queued this entry
+    pub fn placeholder(&self, sender_id: &str) -> Message {
+        let mut message = Message::new(&self.outbox_id, &self.text, sender_id, &self.channel_id)
+            .with_metadata(serde_json::json!({ "outbox_id": self.outbox_id }));
+        message.created_at = self.queued_at;
+        if let Some(root_id) = &self.root_id {
+            message.metadata.as_mut().unwrap()["root_id"] = root_id.clone().into();
+        }
+        message
+    }
+}
+
+#[derive(Debug, Default)]
+struct OutboxState {
+    entries: VecDeque<OutboxEntry>,
+    /// File the entries are saved to, if configured
+    path: Option<PathBuf>,
+    /// Earliest time of the next flush, unless a reconnect comes first
+    retry_at: Option<Instant>,
+}
+
+/// Queue of messages waiting for connectivity
+#[derive(Debug, Default)]
+pub struct Outbox {
+    state: Mutex<OutboxState>,
+    next_id: AtomicU64,
+}
+
+impl Outbox {
+    /// Create an empty, in-memory outbox
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Save the outbox to a file, loading the entries saved there
+    ///
+    /// Entries already queued in memory are kept after the loaded ones. A file
+    /// that can't be read or migrated to the current format is left alone, and
+    /// the outbox stays in memory.
+    pub fn set_path(&self, path: Option<PathBuf>) {
+        let mut state = self.state.lock().unwrap();
+        let path = path.filter(|path| match load(path) {
+            Ok(entries) => {
+                restore(&mut state, entries);
+                true
+            }
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e.message, "Outbox not saved to file");
+                false
+            }
+        });
+        state.path = path;
+        save(&state);
+    }
+
+    /// Queue a message
+    ///
+    /// # Returns
+    /// The queued entry
+    pub fn push(&self, channel_id: &str, text: &str, root_id: Option<&str>) -> OutboxEntry {
+        let now = Utc::now();
+        let n = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let entry = OutboxEntry {
+            outbox_id: format!("outbox-{}-{n}", now.timestamp_millis()),
+            channel_id: channel_id.to_string(),
+            text: text.to_string(),
+            root_id: root_id.map(str::to_string),
+            queued_at: now,
+        };
+
+        let mut state = self.state.lock().unwrap();
+        state.entries.push_back(entry.clone());
+        if state.retry_at.is_none() {
+            state.retry_at = Some(Instant::now() + OUTBOX_RETRY_INTERVAL);
+        }
+        save(&state);
+        entry
+    }
+
+    /// The oldest queued message, which is sent next
+    pub fn front(&self) -> Option<OutboxEntry> {
+        self.state.lock().unwrap().entries.front().cloned()
+    }
+
+    /// Remove a message that was sent or failed for good
+    pub fn remove(&self, outbox_id: &str) {
+        let mut state = self.state.lock().unwrap();
+        state.entries.retain(|entry| entry.outbox_id != outbox_id);
+        save(&state);
+    }
+
+    /// Number of queued messages
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().entries.len()
+    }
+
+    /// Whether no messages are queued
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether messages are queued and may be retried now
+    pub fn is_due(&self) -> bool {
+        let state = self.state.lock().unwrap();
+        !state.entries.is_empty() && state.retry_at.is_none_or(|at| Instant::now() >= at)
+    }
+
+    /// Retry at once, e.g. after a reconnect
+    pub fn make_due(&self) {
+        self.state.lock().unwrap().retry_at = None;
+    }
+
+    /// Wait `OUTBOX_RETRY_INTERVAL` before retrying, after a failed attempt
+    pub fn defer(&self) {
+        self.state.lock().unwrap().retry_at = Some(Instant::now() + OUTBOX_RETRY_INTERVAL);
+    }
+}
+
+/// Whether a failed send should be queued and retried later
+///
+/// Errors the server returned (permissions, validation) would fail again.
+pub fn is_connectivity_error(error: &Error) -> bool {
+    matches!(
+        error.code,
+        ErrorCode::NetworkError | ErrorCode::Timeout | ErrorCode::ServiceUnavailable
+    )
+}
+
+/// Load the entries saved in a file, migrating it to the current format
+fn load(path: &Path) -> Result<Vec<OutboxEntry>> {
+    let Some(mut document) = migrations::open(&migrations::OUTBOX, path)? else {
+        return Ok(Vec::new());
+    };
+    serde_json::from_value(document["entries"].take())
+        .map_err(|e| Error::invalid_argument(format!("Invalid outbox {}: {e}", path.display())))
+}
+
+/// Queue loaded entries before the ones already in memory
+fn restore(state: &mut OutboxState, mut entries: Vec<OutboxEntry>) {
+    entries.retain(|saved| {
+        !state
+            .entries
+            .iter()
+            .any(|entry| entry.outbox_id == saved.outbox_id)
+    });
+    for entry in entries.into_iter().rev() {
+        state.entries.push_front(entry);
+    }
+}
+
+/// Save the entries, if a file is configured
+///
+/// Best-effort: the entries stay queued in memory if the file can't be written.
+fn save(state: &OutboxState) {
+    if let Some(path) = &state.path {
+        let _ = write(path, &state.entries);
+    }
+}
+
+/// Write the entries, replacing the file atomically
+fn write(path: &Path, entries: &VecDeque<OutboxEntry>) -> Result<()> {
+    let document = migrations::OUTBOX.stamp(serde_json::json!({ "entries": entries }));
+    let json = serde_json::to_string(&document).map_err(|e| {
+        Error::new(
+            ErrorCode::Unknown,
+            format!("Failed to serialize outbox: {e}"),
+        )
+    })?;
+    let write_error = |e: std::io::Error| {
+        Error::new(
+            ErrorCode::Unknown,
+            format!("Failed to write outbox {}: {e}", path.display()),
+        )
+    };
+    let temp_path = path.with_extension("tmp");
+    std::fs::write(&temp_path, json).map_err(write_error)?;
+    std::fs::rename(&temp_path, path).map_err(write_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue_order_and_placeholder() {
+        let outbox = Outbox::new();
+        let first = outbox.push("chan1", "hello", None);
+        let second = outbox.push("chan1", "reply", Some("root1"));
+        assert_ne!(first.outbox_id, second.outbox_id);
+        assert_eq!(outbox.len(), 2);
+
+        // Not due until the retry interval passed or a reconnect happened
+        assert!(!outbox.is_due());
+        outbox.make_due();
+        assert!(outbox.is_due());
+
+        assert_eq!(outbox.front(), Some(first.clone()));
+        outbox.remove(&first.outbox_id);
+        assert_eq!(outbox.front(), Some(second.clone()));
+
+        let placeholder = second.placeholder("user1");
+        assert_eq!(placeholder.id, second.outbox_id);
+        let metadata = placeholder.metadata.unwrap();
+        assert_eq!(metadata["outbox_id"], second.outbox_id.as_str());
+        assert_eq!(metadata["root_id"], "root1");
+    }
+
+    #[test]
+    fn test_persistence() {
+        let path = std::env::temp_dir().join(format!(
+            "libcommunicator-outbox-test-{}.json",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        let outbox = Outbox::new();
+        let early = outbox.push("chan1", "queued before the file was set", None);
+        outbox.set_path(Some(path.clone()));
+        let entry = outbox.push("chan1", "hello", None);
+
+        // A new session loads the saved entries in order
+        let restored = Outbox::new();
+        restored.set_path(Some(path.clone()));
+        assert_eq!(restored.len(), 2);
+        assert_eq!(restored.front(), Some(early.clone()));
+
+        restored.remove(&early.outbox_id);
+        restored.remove(&entry.outbox_id);
+        assert!(load(&path).unwrap().is_empty());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_connectivity_errors() {
+        assert!(is_connectivity_error(&Error::new(
+            ErrorCode::NetworkError,
+            "Connection refused"
+        )));
+        assert!(!is_connectivity_error(&Error::new(
+            ErrorCode::PermissionDenied,
+            "Forbidden"
+        )));
+    }
+}
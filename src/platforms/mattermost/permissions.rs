@@ -0,0 +1,228 @@
+//! Channel permissions
+//!
+//! Mattermost grants permissions through roles: system roles on the user,
+//! team roles on the team membership and channel roles on the channel
+//! membership. What a user may do in a channel is the union of the
+//! permissions of all these roles. Channel moderation is covered as well,
+//! since the server reports the roles of a moderated channel's scheme in the
+//! channel membership.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::error::{Error, ErrorCode, Result};
+use crate::types::ChannelPermissions;
+
+use super::client::MattermostClient;
+use super::types::{MattermostChannel, MattermostChannelType, MattermostRole};
+
+impl MattermostClient {
+    /// Get roles by name
+    ///
+    /// # Arguments
+    /// * `names` - The role names to retrieve
+    ///
+    /// # Returns
+    /// A Result containing the roles that exist
+    ///
+    /// # API Endpoint
+    /// POST /roles/names
+    pub async fn get_roles_by_names(&self, names: &[String]) -> Result<Vec<MattermostRole>> {
+        let response = self.post("/roles/names", &names).await?;
+        self.handle_response(response).await
+    }
+
+    /// Get the current user's permissions in channels
+    ///
+    /// Channels the user is not a member of get the permissions of their
+    /// system and team roles only.
+    ///
+    /// # Arguments
+    /// * `channels` - The channels to get permissions for
+    ///
+    /// # Returns
+    /// A Result containing the permissions keyed by channel ID
+    pub async fn get_channels_permissions(
+        &self,
+        channels: &[MattermostChannel],
+    ) -> Result<HashMap<String, ChannelPermissions>> {
+        if channels.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let user_id = self.get_user_id().await.ok_or_else(|| {
+            Error::new(
+                ErrorCode::InvalidState,
+                "User ID not set - ensure you're authenticated",
+            )
+        })?;
+        let system_roles = self.get_user_cached(&user_id).await?.roles;
+
+        let mut channels_per_team: HashMap<&str, usize> = HashMap::new();
+        for channel in channels {
+            *channels_per_team
+                .entry(channel.team_id.as_str())
+                .or_default() += 1;
+        }
+
+        // Team roles, and channel roles in bulk for teams with several channels
+        let mut team_roles = HashMap::new();
+        let mut channel_roles = HashMap::new();
+        for (&team_id, &count) in &channels_per_team {
+            if team_id.is_empty() {
+                continue;
+            }
+            if let Ok(member) = self.get_team_member(team_id, &user_id).await {
+                team_roles.insert(team_id, member.roles);
+            }
+            if count > 1 {
+                if let Ok(members) = self.get_channel_members_for_user(&user_id, team_id).await {
+                    for member in members {
+                        channel_roles.insert(member.channel_id, member.roles);
+                    }
+                }
+            }
+        }
+
+        let mut result = HashMap::with_capacity(channels.len());
+        for channel in channels {
+            let channel_role_names = match channel_roles.get(&channel.id) {
+                Some(roles) => roles.clone(),
+                None => self
+                    .get_channel_member(&channel.id, &user_id)
+                    .await
+                    .map(|member| member.roles)
+                    .unwrap_or_default(),
+            };
+
+            let role_names = role_names([
+                system_roles.as_str(),
+                team_roles
+                    .get(channel.team_id.as_str())
+                    .map(String::as_str)
+                    .unwrap_or_default(),
+                channel_role_names.as_str(),
+            ]);
+            let granted: HashSet<String> = self
+                .get_roles_by_names_cached(&role_names)
+                .await?
+                .into_iter()
+                .flat_map(|role| role.permissions)
+                .collect();
+
+            result.insert(
+                channel.id.clone(),
+                compute_channel_permissions(channel, &granted),
+            );
+        }
+
+        Ok(result)
+    }
+}
+
+/// Distinct role names in space-separated role lists
+fn role_names<'a>(role_lists: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    role_lists
+        .into_iter()
+        .flat_map(str::split_whitespace)
+        .filter(|name| seen.insert(*name))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Derive the channel permissions from the permissions granted by the user's roles
+fn compute_channel_permissions(
+    channel: &MattermostChannel,
+    granted: &HashSet<String>,
+) -> ChannelPermissions {
+    // Nothing can be changed in an archived channel
+    if channel.delete_at > 0 {
+        return ChannelPermissions::default();
+    }
+
+    let has = |permission: &str| granted.contains(permission);
+    let can_post = has("create_post");
+    let can_manage_members = match channel.channel_type {
+        MattermostChannelType::Open => has("manage_public_channel_members"),
+        MattermostChannelType::Private => has("manage_private_channel_members"),
+        // The members of direct and group messages are fixed
+        MattermostChannelType::Direct | MattermostChannelType::Group => false,
+    };
+
+    ChannelPermissions {
+        can_post,
+        can_react: has("add_reaction"),
+        can_upload: can_post && has("upload_file"),
+        can_manage_members,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel(channel_type: &str, delete_at: i64) -> MattermostChannel {
+        serde_json::from_value(serde_json::json!({
+            "id": "ch1", "create_at": 0, "update_at": 0, "delete_at": delete_at,
+            "team_id": "team1", "type": channel_type, "display_name": "Town Square",
+            "name": "town-square"
+        }))
+        .unwrap()
+    }
+
+    fn granted(permissions: &[&str]) -> HashSet<String> {
+        permissions.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn test_role_names() {
+        assert_eq!(
+            role_names([
+                "system_user",
+                "team_user team_admin",
+                " channel_user system_user "
+            ]),
+            vec!["system_user", "team_user", "team_admin", "channel_user"]
+        );
+    }
+
+    #[test]
+    fn test_compute_channel_permissions() {
+        let member = granted(&[
+            "create_post",
+            "add_reaction",
+            "upload_file",
+            "manage_public_channel_members",
+        ]);
+
+        let public = compute_channel_permissions(&channel("O", 0), &member);
+        assert_eq!(
+            public,
+            ChannelPermissions {
+                can_post: true,
+                can_react: true,
+                can_upload: true,
+                can_manage_members: true,
+            }
+        );
+
+        // Managing private channel members needs its own permission
+        let private = compute_channel_permissions(&channel("P", 0), &member);
+        assert!(!private.can_manage_members);
+        assert!(!compute_channel_permissions(&channel("D", 0), &member).can_manage_members);
+
+        // Uploading is part of posting
+        let read_only = compute_channel_permissions(
+            &channel("O", 0),
+            &granted(&["add_reaction", "upload_file"]),
+        );
+        assert!(!read_only.can_post);
+        assert!(!read_only.can_upload);
+        assert!(read_only.can_react);
+
+        assert_eq!(
+            compute_channel_permissions(&channel("O", 1), &member),
+            ChannelPermissions::default()
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::error::Result;
+use crate::types::Message;
 
 use super::client::MattermostClient;
 use super::types::{MattermostPost, PostList};
@@ -87,10 +88,29 @@ impl MattermostClient {
     }
 }
 
+/// Whether a message converted from a Mattermost post is pinned
+///
+/// # Returns
+/// The pinned flag, or None if the message does not carry one
+pub fn pinned_state(message: &Message) -> Option<bool> {
+    message.metadata.as_ref()?.get("is_pinned")?.as_bool()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_pinned_state() {
+        let post: MattermostPost = serde_json::from_str(
+            r#"{"id":"p1","create_at":1,"update_at":2,"delete_at":0,"edit_at":0,
+                "user_id":"u1","channel_id":"c1","message":"hi","is_pinned":true}"#,
+        )
+        .unwrap();
+        assert_eq!(pinned_state(&post.into()), Some(true));
+        assert_eq!(pinned_state(&Message::new("p1", "hi", "u1", "c1")), None);
+    }
+
     #[test]
     fn test_pinned_endpoints() {
         let client = MattermostClient::new("https://mattermost.example.com").unwrap();
@@ -1,44 +1,497 @@
 use async_trait::async_trait;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, Notify};
+use tracing::Instrument;
 
 use crate::error::{Error, ErrorCode, Result};
-use crate::platforms::platform_trait::{Platform, PlatformConfig, PlatformEvent};
+use crate::platforms::platform_trait::{
+    ChunkSink, Platform, PlatformConfig, PlatformEvent, ProgressSink,
+};
 use crate::types::{
-    Attachment, Channel, ConnectionInfo, Message, PlatformCapabilities, Team, User,
+    snippet, Attachment, Channel, CircuitBreakerConfig, CircuitState, ConnectionInfo, Draft,
+    EventFilter, MentionRules, Message, Page, PlatformCapabilities, PlatformLimits, Poll,
+    PollSettings, ScheduledMessage, SendOptions, SidebarCategory, Team, Thread, User,
 };
 
+use super::activity::{hour_start, ChannelActivityTracker, MAX_ACTIVITY_DAYS};
 use super::client::MattermostClient;
 use super::convert::ConversionContext;
-use super::websocket::WebSocketManager;
+use super::files;
+use super::live_state::{PinTracker, StatusTracker, TypingTracker};
+use super::outbox::{self, Outbox};
+use super::pinned::pinned_state;
+use super::polls::poll_from_message;
+use super::read_state::{ChannelViewTracker, ViewDecision};
+use super::resync::{missed_events, RESYNC_MARGIN_MS};
+use super::startup::StartupSnapshot;
+use super::types::{
+    MattermostChannelType, MattermostDraft, MattermostScheduledPost, MattermostUser,
+};
+use super::websocket::{ConnectionState as WsState, WebSocketManager};
+use super::window::{MessageWindows, WINDOW_SIZE};
 
 /// Wrapper struct that implements the Platform trait for Mattermost
 pub struct MattermostPlatform {
-    client: MattermostClient,
+    client: Arc<MattermostClient>,
     connection_info: Option<ConnectionInfo>,
     websocket: Arc<Mutex<Option<WebSocketManager>>>,
-    server_url: String,
     capabilities: PlatformCapabilities,
+    view_tracker: ChannelViewTracker,
+    activity_tracker: ChannelActivityTracker,
+    message_windows: MessageWindows,
+    /// Events derived from a polled event, returned by the next poll_event calls
+    pending_events: VecDeque<PlatformEvent>,
+    /// Events raised by calls on a shared reference (e.g. view_channel),
+    /// returned by the next poll_event calls
+    local_events: std::sync::Mutex<VecDeque<PlatformEvent>>,
+    /// Background profile prefetch for the visible channels, if running
+    prefetch_task: std::sync::Mutex<Option<tokio::task::JoinHandle<()>>>,
+    /// Notified when the WebSocket queues an event
+    event_signal: Arc<Notify>,
+    /// File the session snapshot is saved to, if configured
+    startup_cache: Option<PathBuf>,
+    /// Server URL passed to connect(), which identifies the saved session
+    configured_server: String,
+    /// Snapshot of the session, served while a cached-data-first connect is pending
+    snapshot: std::sync::Mutex<Option<StartupSnapshot>>,
+    /// Connection being established in the background after a cached-data-first connect
+    startup_task: Option<tokio::task::JoinHandle<Result<Established>>>,
+    /// Users typing in each channel, as reported by events
+    typing: TypingTracker,
+    /// User statuses, as fetched or reported by events
+    statuses: StatusTracker,
+    /// Pinned messages of the channels whose pinned list was fetched
+    pins: PinTracker,
+    /// Authors in the visible channels, found by the last profile prefetch
+    visible_users: Arc<std::sync::Mutex<Vec<String>>>,
+    /// WebSocket reconnections already handled
+    seen_reconnects: u64,
+    /// Messages sent while disconnected, waiting to be sent
+    outbox: Outbox,
+    /// Whether membership events are enriched with users and channels
+    enrich_events: bool,
+    /// Whether posts changed while the WebSocket was down are reported after a reconnect
+    resync_on_reconnect: bool,
+    /// Whether posts are resynced when the WebSocket skips events
+    resync_on_gap: bool,
+    /// Real-time events dropped before they are queued, shared with the WebSocket
+    event_filter: Arc<std::sync::RwLock<EventFilter>>,
 }
 
+/// Outcome of establishing a session
+type Established = (MattermostUser, Option<PlatformLimits>);
+
+/// Most pages of search results fetched to fill a search filtered locally
+const MAX_FILTERED_SEARCH_PAGES: u32 = 10;
+
 impl MattermostPlatform {
     /// Create a new Mattermost platform instance
     pub fn new(server_url: &str) -> Result<Self> {
-        let client = MattermostClient::new(server_url)?;
+        let client = Arc::new(MattermostClient::new(server_url)?);
         Ok(Self {
             client,
             connection_info: None,
             websocket: Arc::new(Mutex::new(None)),
-            server_url: server_url.to_string(),
             capabilities: PlatformCapabilities::mattermost(),
+            view_tracker: ChannelViewTracker::default(),
+            activity_tracker: ChannelActivityTracker::new(),
+            message_windows: MessageWindows::new(),
+            pending_events: VecDeque::new(),
+            local_events: std::sync::Mutex::new(VecDeque::new()),
+            prefetch_task: std::sync::Mutex::new(None),
+            event_signal: Arc::new(Notify::new()),
+            startup_cache: None,
+            configured_server: server_url.to_string(),
+            snapshot: std::sync::Mutex::new(None),
+            startup_task: None,
+            typing: TypingTracker::default(),
+            statuses: StatusTracker::default(),
+            pins: PinTracker::default(),
+            visible_users: Arc::new(std::sync::Mutex::new(Vec::new())),
+            seen_reconnects: 0,
+            outbox: Outbox::new(),
+            enrich_events: false,
+            resync_on_reconnect: true,
+            resync_on_gap: false,
+            event_filter: Arc::default(),
         })
     }
 
+    /// Drop typing and status state that may be outdated after a reconnect
+    ///
+    /// Typing indicators are cleared and statuses marked stale, then the
+    /// statuses of the users in the visible channels are refreshed in one
+    /// request and reported as `UserStatusChanged` events.
+    async fn prune_live_state(&mut self) {
+        self.typing.clear();
+        self.pins.clear();
+        self.statuses.mark_all_stale();
+
+        let user_ids = self.visible_users.lock().unwrap().clone();
+        if user_ids.is_empty() {
+            return;
+        }
+        // Best-effort: stale statuses are fetched again when requested
+        let Ok(mm_statuses) = self.client.get_users_status_by_ids(&user_ids).await else {
+            return;
+        };
+        for mm_status in mm_statuses {
+            let status = super::status_string_to_user_status(&mm_status.status);
+            self.statuses.set(&mm_status.user_id, status);
+            self.pending_events
+                .push_back(PlatformEvent::UserStatusChanged {
+                    user_id: mm_status.user_id,
+                    status,
+                });
+        }
+    }
+
+    /// Report the posts changed while events may have been lost
+    ///
+    /// The channels with a message window are resynced, one request each.
+    /// Best effort: a channel whose posts can't be fetched is skipped.
+    ///
+    /// # Arguments
+    /// * `since` - When the server was last heard from before events were
+    ///   lost (milliseconds since epoch, client clock)
+    async fn resync_missed_events(&mut self, since: i64) {
+        let since = since - RESYNC_MARGIN_MS;
+
+        for channel_id in self.message_windows.channel_ids().await {
+            let Ok(posts) = self.client.get_posts_since(&channel_id, since).await else {
+                continue;
+            };
+            let known = self.message_windows.update_times(&channel_id).await;
+            for event in missed_events(posts, since, &known) {
+                match &event {
+                    PlatformEvent::MessagePosted(message) => {
+                        self.activity_tracker
+                            .record(&message.channel_id, message.created_at.timestamp_millis())
+                            .await;
+                        self.message_windows.record_posted(message).await;
+                    }
+                    PlatformEvent::MessageUpdated(message) => {
+                        self.message_windows.record_updated(message).await;
+                    }
+                    PlatformEvent::MessageDeleted {
+                        message_id,
+                        channel_id,
+                    } => {
+                        self.pins.remove(channel_id, message_id);
+                        self.message_windows
+                            .record_deleted(channel_id, message_id)
+                            .await;
+                    }
+                    _ => {}
+                }
+                self.pending_events.push_back(event);
+            }
+        }
+    }
+
+    /// Fill in the user and channel of a membership event
+    ///
+    /// Users and channels come from the cache, or are fetched and cached on a
+    /// miss; an object that can't be fetched is left out.
+    async fn enrich_event(&self, mut event: PlatformEvent) -> PlatformEvent {
+        match &mut event {
+            PlatformEvent::UserJoinedChannel {
+                user_id,
+                channel_id,
+                user,
+                channel,
+            }
+            | PlatformEvent::UserLeftChannel {
+                user_id,
+                channel_id,
+                user,
+                channel,
+            }
+            | PlatformEvent::ChannelMemberUpdated {
+                user_id,
+                channel_id,
+                user,
+                channel,
+            }
+            | PlatformEvent::MemberRoleUpdated {
+                user_id,
+                channel_id,
+                user,
+                channel,
+            } => {
+                if let Ok(mm_user) = self.client.get_user_cached(user_id).await {
+                    *user = Some(Box::new(mm_user.into()));
+                }
+                if let Ok(resolved) = self.get_channel(channel_id).await {
+                    *channel = Some(Box::new(resolved));
+                }
+            }
+            _ => {}
+        }
+        event
+    }
+
+    /// Report that the current user viewed a channel without waiting for the
+    /// server's `channel_viewed` event, which follows as a duplicate
+    async fn emit_channel_viewed(&self, channel_id: &str) {
+        let Some(user_id) = self.client.get_user_id().await else {
+            return;
+        };
+        self.local_events
+            .lock()
+            .unwrap()
+            .push_back(PlatformEvent::ChannelViewed {
+                user_id,
+                channel_id: channel_id.to_string(),
+            });
+        self.event_signal.notify_one();
+    }
+
+    /// State of the WebSocket, or None if events are not subscribed
+    async fn websocket_state(&self) -> Option<WsState> {
+        match self.websocket.lock().await.as_ref() {
+            Some(ws) => Some(ws.get_connection_state().await),
+            None => None,
+        }
+    }
+
+    /// Send a message or reply, queueing it in the outbox while disconnected
+    ///
+    /// Messages are queued while events are subscribed and the WebSocket is
+    /// down, or when the send fails for lack of connectivity. Once messages
+    /// are queued, later ones queue behind them to keep their order.
+    async fn send_or_queue(
+        &self,
+        channel_id: &str,
+        text: &str,
+        root_id: Option<&str>,
+    ) -> Result<Message> {
+        let Some(ws_state) = self.websocket_state().await else {
+            return self.send_now(channel_id, text, root_id).await;
+        };
+
+        if ws_state == WsState::Connected && self.outbox.is_empty() {
+            match self.send_now(channel_id, text, root_id).await {
+                Err(e) if outbox::is_connectivity_error(&e) => {}
+                result => return result,
+            }
+        } else {
+            // Fail now on what would fail when sent
+            self.client.server_limits().await.check_message(text)?;
+        }
+
+        let entry = self.outbox.push(channel_id, text, root_id);
+        let sender_id = self.client.get_user_id().await.unwrap_or_default();
+        Ok(entry.placeholder(&sender_id))
+    }
+
+    async fn send_now(
+        &self,
+        channel_id: &str,
+        text: &str,
+        root_id: Option<&str>,
+    ) -> Result<Message> {
+        let mm_post = match root_id {
+            Some(root_id) => self.client.send_reply(channel_id, text, root_id).await?,
+            None => self.client.send_message(channel_id, text).await?,
+        };
+        Ok(mm_post.into())
+    }
+
+    /// Send the queued messages in order, reporting each outcome as an event
+    ///
+    /// Stops at the first connectivity failure and retries later.
+    async fn flush_outbox(&mut self) {
+        while let Some(entry) = self.outbox.front() {
+            let result = self
+                .send_now(&entry.channel_id, &entry.text, entry.root_id.as_deref())
+                .await;
+            let event = match result {
+                Err(e) if outbox::is_connectivity_error(&e) => {
+                    self.outbox.defer();
+                    return;
+                }
+                Ok(message) => PlatformEvent::MessageSendSucceeded {
+                    outbox_id: entry.outbox_id.clone(),
+                    message,
+                },
+                Err(error) => PlatformEvent::MessageSendFailed {
+                    outbox_id: entry.outbox_id.clone(),
+                    channel_id: entry.channel_id.clone(),
+                    error,
+                },
+            };
+            self.outbox.remove(&entry.outbox_id);
+            self.pending_events.push_back(event);
+        }
+    }
+
+    /// Authenticate and load the current user and the server limits
+    async fn establish(client: &MattermostClient, config: PlatformConfig) -> Result<Established> {
+        // Use the first reachable server URL
+        client.select_endpoint().await;
+
+        client.login_with_credentials(&config.credentials).await?;
+
+        // Set team ID if provided
+        if let Some(team_id) = config.team_id {
+            client.set_team_id(Some(team_id)).await;
+        }
+
+        // Get the current user to build connection info
+        let current_user = client.get_current_user().await?;
+
+        // Server limits are advisory; keep the defaults if the config is unavailable
+        let limits = client.load_server_limits().await.ok();
+
+        Ok((current_user, limits))
+    }
+
+    /// Record an established session and save it to the startup cache
+    async fn finish_connect(&mut self, established: Established) -> ConnectionInfo {
+        let (current_user, limits) = established;
+        if let Some(limits) = limits {
+            self.capabilities.limits = limits;
+        }
+
+        let conn_info = self
+            .client
+            .connection_info(&self.client.get_base_url(), &current_user.username)
+            .await;
+        self.connection_info = Some(conn_info.clone());
+
+        // Warming is best effort; entities are fetched as needed otherwise
+        if let Some(path) = self.client.cache_config().path {
+            let _ = self
+                .client
+                .warm_entity_cache(Path::new(&path), &self.configured_server)
+                .await;
+        }
+
+        let user: User = current_user.into();
+        let team_id = self.client.get_team_id().await;
+        self.update_snapshot(|snapshot| {
+            // Teams and channels of another user or team are of no use
+            if snapshot.user.id != user.id || snapshot.team_id != team_id {
+                snapshot.teams.clear();
+                snapshot.channels.clear();
+            }
+            snapshot.user = user.clone();
+            snapshot.team_id = team_id.clone();
+        });
+        conn_info
+    }
+
+    /// Collect the result of a background connect
+    ///
+    /// Waits for the connect to finish if it is still running.
+    async fn complete_startup(&mut self) -> Option<Result<ConnectionInfo>> {
+        let task = self.startup_task.take()?;
+        let established = task.await.unwrap_or_else(|e| {
+            Err(Error::new(
+                ErrorCode::Unknown,
+                format!("Background connect failed: {e}"),
+            ))
+        });
+        Some(match established {
+            Ok(established) => Ok(self.finish_connect(established).await),
+            Err(e) => {
+                self.connection_info = None;
+                *self.snapshot.lock().unwrap() = None;
+                Err(e)
+            }
+        })
+    }
+
+    /// Get data from the session snapshot while a background connect is pending
+    fn cached_startup_data<T>(&self, get: impl FnOnce(&StartupSnapshot) -> T) -> Option<T> {
+        let pending = self
+            .startup_task
+            .as_ref()
+            .is_some_and(|task| !task.is_finished());
+        if !pending {
+            return None;
+        }
+        self.snapshot.lock().unwrap().as_ref().map(get)
+    }
+
+    /// Update the session snapshot and save it to the startup cache
+    ///
+    /// Saving is best effort; the cache only speeds up the next startup.
+    fn update_snapshot(&self, update: impl FnOnce(&mut StartupSnapshot)) {
+        let Some(path) = &self.startup_cache else {
+            return;
+        };
+        let Some(info) = &self.connection_info else {
+            return;
+        };
+        let mut snapshot = self.snapshot.lock().unwrap();
+        let snapshot = snapshot.get_or_insert_with(|| {
+            StartupSnapshot::new(
+                &self.configured_server,
+                User::new(
+                    &info.user_id,
+                    &info.user_display_name,
+                    &info.user_display_name,
+                ),
+                info.team_id.clone(),
+            )
+        });
+        update(snapshot);
+        let _ = snapshot.save(path);
+    }
+
+    /// Stop the running profile prefetch, if any
+    fn cancel_prefetch(&self) {
+        if let Some(task) = self.prefetch_task.lock().unwrap().take() {
+            task.abort();
+        }
+    }
+
     /// Get the underlying client (for accessing Mattermost-specific methods)
     pub fn client(&self) -> &MattermostClient {
         &self.client
     }
 
+    /// Report the echo of a post sent by this client as `MessageConfirmed`
+    async fn confirm_own_post(&self, event: PlatformEvent) -> PlatformEvent {
+        let PlatformEvent::MessagePosted(message) = event else {
+            return event;
+        };
+
+        let pending_id = message
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get("pending_post_id"))
+            .and_then(|id| id.as_str())
+            .filter(|id| !id.is_empty())
+            .map(str::to_string);
+        match pending_id {
+            Some(pending_id) if self.client.take_pending_post(&pending_id).await => {
+                PlatformEvent::MessageConfirmed {
+                    pending_id,
+                    message,
+                }
+            }
+            _ => PlatformEvent::MessagePosted(message),
+        }
+    }
+
+    /// The given team ID, or the current team's if None
+    async fn team_id_or_current(&self, team_id: Option<&str>) -> Result<String> {
+        match team_id {
+            Some(team_id) => Ok(team_id.to_string()),
+            None => self.client.get_team_id().await.ok_or_else(|| {
+                Error::new(
+                    ErrorCode::InvalidState,
+                    "Team ID not set - call connect() with a team_id or set it manually",
+                )
+            }),
+        }
+    }
+
     /// Convert a Mattermost channel to our Channel type with proper DM/GM handling
     async fn convert_channel_with_context(
         &self,
@@ -46,10 +499,39 @@ impl MattermostPlatform {
         current_user_id: Option<&str>,
     ) -> Result<Channel> {
         use super::channels::get_dm_partner_id;
+
+        // For DM channels, fetch the other user to name the channel after
+        // Note: DM channel "name" field contains user IDs in format "user1id__user2id"
+        let partner = match current_user_id {
+            Some(user_id) if mm_channel.channel_type.is_direct() => {
+                match get_dm_partner_id(&mm_channel.name, user_id) {
+                    Some(partner_id) if partner_id != user_id => {
+                        self.client.get_user(&partner_id).await.ok()
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+
+        Ok(self.channel_with_partner(mm_channel, current_user_id, partner.as_ref()))
+    }
+
+    /// Convert a Mattermost channel, naming a DM after the other user
+    ///
+    /// # Arguments
+    /// * `partner` - The other user of a DM channel, if known
+    fn channel_with_partner(
+        &self,
+        mm_channel: super::types::MattermostChannel,
+        current_user_id: Option<&str>,
+        partner: Option<&MattermostUser>,
+    ) -> Channel {
+        use super::channels::get_dm_partner_id;
         use super::convert::ConversionContext;
 
         // Create conversion context with server URL and current user
-        let mut ctx = ConversionContext::new(self.server_url.clone());
+        let mut ctx = ConversionContext::new(self.client.get_base_url());
         if let Some(user_id) = current_user_id {
             ctx = ctx.with_current_user(user_id.to_string());
         }
@@ -57,37 +539,29 @@ impl MattermostPlatform {
         // Convert the channel with context
         let mut channel = mm_channel.to_channel_with_context(&ctx);
 
-        // For DM channels, fetch the other user's name and use it as display name
-        // Note: DM channel "name" field contains user IDs in format "user1id__user2id"
         if mm_channel.channel_type.is_direct() {
             if let Some(user_id) = current_user_id {
                 // Check if this is a self-DM (saved messages) - both user IDs are the same
                 if mm_channel.name == format!("{user_id}__{user_id}") {
                     // This is a DM with yourself
                     channel.display_name = "You (Saved Messages)".to_string();
-                } else if let Some(partner_id) = get_dm_partner_id(&mm_channel.name, user_id) {
-                    // Regular DM with another user - use the "name" field which contains user IDs
-                    match self.client.get_user(&partner_id).await {
-                        Ok(partner_user) => {
-                            // Build display name from partner's information
-                            let display_name = if !partner_user.first_name.is_empty()
-                                || !partner_user.last_name.is_empty()
-                            {
-                                format!("{} {}", partner_user.first_name, partner_user.last_name)
-                                    .trim()
-                                    .to_string()
-                            } else if !partner_user.nickname.is_empty() {
-                                partner_user.nickname.clone()
-                            } else {
-                                partner_user.username.clone()
-                            };
-                            channel.display_name = display_name;
-                        }
-                        Err(_) => {
-                            // Fall back to a generic name
-                            channel.display_name = "Direct Message".to_string();
-                        }
-                    }
+                } else if let Some(partner_user) = partner {
+                    // Build display name from partner's information
+                    let display_name = if !partner_user.first_name.is_empty()
+                        || !partner_user.last_name.is_empty()
+                    {
+                        format!("{} {}", partner_user.first_name, partner_user.last_name)
+                            .trim()
+                            .to_string()
+                    } else if !partner_user.nickname.is_empty() {
+                        partner_user.nickname.clone()
+                    } else {
+                        partner_user.username.clone()
+                    };
+                    channel.display_name = display_name;
+                } else if get_dm_partner_id(&mm_channel.name, user_id).is_some() {
+                    // Fall back to a generic name
+                    channel.display_name = "Direct Message".to_string();
                 }
             }
         }
@@ -103,7 +577,42 @@ impl MattermostPlatform {
             }
         }
 
-        Ok(channel)
+        channel
+    }
+
+    /// Search posts in the current team, one page of results at a time
+    ///
+    /// # Arguments
+    /// * `terms` - Search terms in Mattermost's syntax
+    /// * `page` - Page of results, starting at 0
+    /// * `per_page` - Number of results per page
+    async fn search_page(&self, terms: &str, page: u32, per_page: u32) -> Result<Vec<Message>> {
+        let team_id = self
+            .client
+            .get_team_id()
+            .await
+            .ok_or_else(|| Error::new(ErrorCode::InvalidArgument, "Team ID not set"))?;
+
+        let options = crate::platforms::mattermost::PostSearchOptions {
+            is_or_search: false,
+            include_deleted_channels: false,
+            time_zone_offset: 0,
+            page,
+            per_page,
+        };
+
+        let post_list = self
+            .client
+            .search_posts_advanced(&team_id, terms, options)
+            .await?;
+
+        // Convert posts to messages
+        Ok(post_list
+            .order
+            .iter()
+            .filter_map(|post_id| post_list.posts.get(post_id))
+            .map(|post| post.clone().into())
+            .collect())
     }
 }
 
@@ -113,60 +622,102 @@ impl Platform for MattermostPlatform {
         &self.capabilities
     }
 
+    #[tracing::instrument(skip_all, fields(server = %config.server))]
     async fn connect(&mut self, config: PlatformConfig) -> Result<ConnectionInfo> {
-        // Determine authentication method from credentials
-        if let Some(token) = config.credentials.get("token") {
-            // Use Personal Access Token or existing session token
-            self.client.login_with_token(token).await?;
-        } else if let (Some(login_id), Some(password)) = (
-            config.credentials.get("login_id"),
-            config.credentials.get("password"),
-        ) {
-            // Check if MFA token is provided
-            if let Some(mfa_token) = config.credentials.get("mfa_token") {
-                // Use email/username, password, and MFA token
-                self.client
-                    .login_with_mfa(login_id, password, mfa_token)
-                    .await?;
-            } else {
-                // Use email/username and password
-                self.client.login(login_id, password).await?;
-            }
-        } else {
-            return Err(Error::new(
-                ErrorCode::InvalidArgument,
-                "Missing authentication credentials (provide 'token' or 'login_id'+'password')",
+        self.client.set_fallback_urls(&config.fallback_servers)?;
+        self.client.set_proxy(config.proxy.as_deref())?;
+        self.client.set_tls(&config.tls)?;
+        self.client.set_host_overrides(&config.hosts)?;
+        self.client.set_retry_policy(config.retry.clone());
+        self.client.configure_caches(config.cache.clone()).await;
+        if let Some(threshold) = config.download_spill_threshold {
+            self.client.set_download_spill_threshold(threshold);
+        }
+        self.outbox
+            .set_path(config.outbox.as_ref().map(PathBuf::from));
+        self.startup_cache = config.startup_cache.as_ref().map(PathBuf::from);
+        self.configured_server = config.server.clone();
+        self.enrich_events = config.enrich_events;
+        self.resync_on_reconnect = config.resync_on_reconnect;
+        self.resync_on_gap = config.resync_on_gap;
+
+        // Serve the previous session's data while connecting in the background
+        let snapshot = self
+            .startup_cache
+            .as_deref()
+            .and_then(StartupSnapshot::load)
+            .filter(|snapshot| snapshot.server == config.server);
+        if let Some(snapshot) = snapshot {
+            tracing::info!("Serving the startup snapshot while connecting in the background");
+            let conn_info = snapshot.connection_info();
+            *self.snapshot.lock().unwrap() = Some(snapshot);
+            self.connection_info = Some(conn_info.clone());
+
+            let client = Arc::clone(&self.client);
+            self.startup_task = Some(tokio::spawn(
+                async move { Self::establish(&client, config).await }.in_current_span(),
             ));
+            return Ok(conn_info);
         }
 
-        // Set team ID if provided
-        if let Some(team_id) = config.team_id {
-            self.client.set_team_id(Some(team_id)).await;
-        }
+        let established = Self::establish(&self.client, config)
+            .await
+            .inspect_err(|e| tracing::warn!(error = %e, "Connection failed"))?;
+        let info = self.finish_connect(established).await;
+        tracing::info!(user_id = %info.user_id, "Connected");
+        Ok(info)
+    }
 
-        // Get the current user to build connection info
-        let current_user = self.client.get_current_user().await?;
+    async fn renew_session(&self, credentials: HashMap<String, String>) -> Result<()> {
+        self.client.login_with_credentials(&credentials).await?;
+        // The WebSocket reconnects with the new token on the next poll
+        self.client.session().mark_renewed();
+        Ok(())
+    }
 
-        // Get connection info
-        let conn_info = self
-            .client
-            .connection_info(&self.server_url, &current_user.username)
-            .await;
-        self.connection_info = Some(conn_info.clone());
+    async fn begin_sso_login(&self, provider: &str) -> Result<String> {
+        self.client.begin_sso_login(provider).await
+    }
 
-        Ok(conn_info)
+    async fn complete_sso_login(&self, callback_url: &str) -> Result<String> {
+        self.client.complete_sso_login(callback_url).await
     }
 
+    #[tracing::instrument(skip_all)]
     async fn disconnect(&mut self) -> Result<()> {
+        if let Some(task) = self.startup_task.take() {
+            task.abort();
+        }
+        *self.snapshot.lock().unwrap() = None;
+
         // Disconnect WebSocket if connected
         if let Some(ws) = self.websocket.lock().await.as_mut() {
             ws.disconnect().await;
         }
 
+        // Save the caches for the next session while the user is still known
+        if let Some(path) = self.client.cache_config().path {
+            let _ = self
+                .client
+                .save_entity_cache(Path::new(&path), &self.configured_server)
+                .await;
+        }
+
         // Logout from Mattermost
         self.client.logout().await?;
 
+        self.view_tracker.reset().await;
+        self.activity_tracker.reset().await;
+        self.message_windows.reset().await;
+        self.pending_events.clear();
+        self.local_events.lock().unwrap().clear();
+        self.cancel_prefetch();
+        self.typing.clear();
+        self.statuses.clear();
+        self.pins.clear();
+        self.visible_users.lock().unwrap().clear();
         self.connection_info = None;
+        tracing::info!("Disconnected");
         Ok(())
     }
 
@@ -175,11 +726,32 @@ impl Platform for MattermostPlatform {
     }
 
     async fn send_message(&self, channel_id: &str, text: &str) -> Result<Message> {
-        let mm_post = self.client.send_message(channel_id, text).await?;
+        self.send_or_queue(channel_id, text, None).await
+    }
+
+    async fn send_message_ex(
+        &self,
+        channel_id: &str,
+        text: &str,
+        options: &SendOptions,
+    ) -> Result<Message> {
+        if options.scheduled_at.is_some() {
+            return Err(Error::unsupported(
+                "Scheduled messages are sent with schedule_message()",
+            ));
+        }
+        let mm_post = self
+            .client
+            .send_message_with_options(channel_id, text, options)
+            .await?;
         Ok(mm_post.into())
     }
 
     async fn get_channels(&self) -> Result<Vec<Channel>> {
+        if let Some(channels) = self.cached_startup_data(|snapshot| snapshot.channels.clone()) {
+            return Ok(channels);
+        }
+
         // Get team ID from connection info or client state
         let team_id = self.client.get_team_id().await.ok_or_else(|| {
             Error::new(
@@ -190,26 +762,73 @@ impl Platform for MattermostPlatform {
 
         let mm_channels = self.client.get_channels_for_team(&team_id).await?;
 
+        // Permissions are best effort; channels are listed without them on failure
+        let permissions = self
+            .client
+            .get_channels_permissions_cached(&mm_channels)
+            .await
+            .unwrap_or_default();
+
         // Get current user ID for DM channel context
         let current_user_id = self.client.get_user_id().await;
 
         // Convert channels with proper DM handling
         let mut channels = Vec::new();
         for mm_channel in mm_channels {
-            let channel = self
+            self.client.update_channel_cache(&mm_channel).await;
+            let mut channel = self
                 .convert_channel_with_context(mm_channel, current_user_id.as_deref())
                 .await?;
+            channel.permissions = permissions.get(&channel.id).copied();
             channels.push(channel);
         }
 
+        self.update_snapshot(|snapshot| {
+            if snapshot.team_id.as_deref() == Some(team_id.as_str()) {
+                snapshot.channels = channels.clone();
+            }
+        });
+        Ok(channels)
+    }
+
+    async fn cached_channels(&self) -> Result<Vec<Channel>> {
+        use super::channels::get_dm_partner_id;
+
+        let current_user_id = self.client.get_user_id().await;
+        let mut channels = Vec::new();
+        for mm_channel in self.client.cached_channels().await {
+            // DMs are named after the other user only if that user is cached too
+            let partner_id = current_user_id
+                .as_deref()
+                .filter(|_| mm_channel.channel_type.is_direct())
+                .and_then(|user_id| get_dm_partner_id(&mm_channel.name, user_id));
+            let partner = match partner_id {
+                Some(partner_id) => self.client.cached_user(&partner_id).await,
+                None => None,
+            };
+            channels.push(self.channel_with_partner(
+                mm_channel,
+                current_user_id.as_deref(),
+                partner.as_ref(),
+            ));
+        }
         Ok(channels)
     }
 
     async fn get_channel(&self, channel_id: &str) -> Result<Channel> {
         let mm_channel = self.client.get_channel_cached(channel_id).await?;
-        let current_user_id = self.client.get_user_id().await;
-        self.convert_channel_with_context(mm_channel, current_user_id.as_deref())
+        let permissions = self
+            .client
+            .get_channels_permissions_cached(std::slice::from_ref(&mm_channel))
             .await
+            .ok()
+            .and_then(|mut permissions| permissions.remove(channel_id));
+        let current_user_id = self.client.get_user_id().await;
+        let mut channel = self
+            .convert_channel_with_context(mm_channel, current_user_id.as_deref())
+            .await?;
+        channel.permissions = permissions;
+        Ok(channel)
     }
 
     async fn get_messages(&self, channel_id: &str, limit: usize) -> Result<Vec<Message>> {
@@ -229,6 +848,8 @@ impl Platform for MattermostPlatform {
         // Reverse to get most recent first
         messages.reverse();
 
+        self.message_windows.merge(channel_id, &messages).await;
+
         Ok(messages)
     }
 
@@ -252,11 +873,65 @@ impl Platform for MattermostPlatform {
         Ok(mm_user.into())
     }
 
+    async fn cached_users(&self) -> Result<Vec<User>> {
+        let mm_users = self.client.cached_users().await;
+        Ok(mm_users.into_iter().map(User::from).collect())
+    }
+
     async fn get_current_user(&self) -> Result<User> {
+        if let Some(user) = self.cached_startup_data(|snapshot| snapshot.user.clone()) {
+            return Ok(user);
+        }
         let mm_user = self.client.get_current_user().await?;
         Ok(mm_user.into())
     }
 
+    async fn get_mention_rules(&self) -> Result<MentionRules> {
+        // The cached profile may predate changed notification settings
+        let mm_user = self.client.get_current_user().await?;
+        Ok(mm_user.mention_rules())
+    }
+
+    async fn update_profile(&self, update: &crate::types::ProfileUpdate) -> Result<User> {
+        if update.is_empty() {
+            return Err(Error::invalid_argument("Profile update changes no fields"));
+        }
+        if update
+            .username
+            .as_deref()
+            .is_some_and(|username| username.trim().is_empty())
+        {
+            return Err(Error::invalid_argument("Username cannot be empty"));
+        }
+        let user_id = self.client.current_user_id().await?;
+        let mm_user = self.client.patch_user(&user_id, update).await?;
+        let user: User = mm_user.into();
+        self.update_snapshot(|snapshot| snapshot.user = user.clone());
+        Ok(user)
+    }
+
+    async fn set_profile_image(&self, image: Vec<u8>) -> Result<()> {
+        if image.is_empty() {
+            return Err(Error::invalid_argument("Profile image is empty"));
+        }
+        let user_id = self.client.current_user_id().await?;
+        self.client.set_user_image(&user_id, image).await
+    }
+
+    async fn get_groups(&self) -> Result<Vec<crate::types::Group>> {
+        let mm_groups = self.client.get_all_groups(false).await?;
+        Ok(mm_groups
+            .into_iter()
+            .filter(|group| group.delete_at == 0)
+            .map(|group| group.into())
+            .collect())
+    }
+
+    async fn get_group_members(&self, group_id: &str) -> Result<Vec<User>> {
+        let mm_users = self.client.get_all_group_members(group_id).await?;
+        Ok(mm_users.into_iter().map(|user| user.into()).collect())
+    }
+
     async fn create_direct_channel(&self, user_id: &str) -> Result<Channel> {
         let mm_channel = self.client.create_direct_channel(user_id).await?;
         let current_user_id = self.client.get_user_id().await;
@@ -301,8 +976,13 @@ impl Platform for MattermostPlatform {
     }
 
     async fn get_teams(&self) -> Result<Vec<Team>> {
+        if let Some(teams) = self.cached_startup_data(|snapshot| snapshot.teams.clone()) {
+            return Ok(teams);
+        }
         let mm_teams = self.client.get_teams().await?;
-        Ok(mm_teams.into_iter().map(|t| t.into()).collect())
+        let teams: Vec<Team> = mm_teams.into_iter().map(|t| t.into()).collect();
+        self.update_snapshot(|snapshot| snapshot.teams = teams.clone());
+        Ok(teams)
     }
 
     async fn get_team(&self, team_id: &str) -> Result<Team> {
@@ -327,8 +1007,13 @@ impl Platform for MattermostPlatform {
     }
 
     async fn get_user_status(&self, user_id: &str) -> Result<crate::types::user::UserStatus> {
+        if let Some(status) = self.statuses.get_fresh(user_id) {
+            return Ok(status);
+        }
         let mm_status = self.client.get_user_status(user_id).await?;
-        Ok(super::status_string_to_user_status(&mm_status.status))
+        let status = super::status_string_to_user_status(&mm_status.status);
+        self.statuses.set(user_id, status);
+        Ok(status)
     }
 
     async fn send_typing_indicator(&self, channel_id: &str, parent_id: Option<&str>) -> Result<()> {
@@ -343,7 +1028,18 @@ impl Platform for MattermostPlatform {
         }
     }
 
+    #[tracing::instrument(skip_all)]
     async fn subscribe_events(&mut self) -> Result<()> {
+        // Events need the session; wait for a background connect to finish
+        if let Some(result) = self.complete_startup().await {
+            let event = match &result {
+                Ok(info) => PlatformEvent::SessionReady(info.clone()),
+                Err(e) => PlatformEvent::SessionFailed(e.clone()),
+            };
+            self.pending_events.push_back(event);
+            result?;
+        }
+
         let token = self.client.get_token().await.ok_or_else(|| {
             Error::new(
                 ErrorCode::InvalidState,
@@ -351,14 +1047,23 @@ impl Platform for MattermostPlatform {
             )
         })?;
 
-        // Use the stored server URL
-        let server_url = &self.server_url;
-
-        let mut ws_manager = WebSocketManager::new(server_url, token);
+        // Connect to the server URL in use; reconnects may switch to another one
+        let mut ws_manager = WebSocketManager::new(&self.client.get_base_url(), token)
+            .with_event_enrichment(self.enrich_events)
+            .with_resync(self.resync_on_reconnect)
+            .with_event_filter(Arc::clone(&self.event_filter))
+            .with_endpoints(self.client.endpoints())
+            .with_proxy(self.client.proxy())
+            .with_resolver(self.client.resolver())
+            .with_tls(self.client.tls())
+            .with_event_signal(Arc::clone(&self.event_signal));
         ws_manager.connect().await?;
 
         let mut ws_lock = self.websocket.lock().await;
         *ws_lock = Some(ws_manager);
+        self.seen_reconnects = 0;
+        // Send what was queued before, possibly in an earlier session
+        self.outbox.make_due();
 
         Ok(())
     }
@@ -372,11 +1077,80 @@ impl Platform for MattermostPlatform {
         Ok(())
     }
 
+    fn event_signal(&self) -> Option<Arc<Notify>> {
+        Some(Arc::clone(&self.event_signal))
+    }
+
     async fn poll_event(&mut self) -> Result<Option<PlatformEvent>> {
+        if self
+            .startup_task
+            .as_ref()
+            .is_some_and(|task| task.is_finished())
+        {
+            if let Some(result) = self.complete_startup().await {
+                return Ok(Some(match result {
+                    Ok(info) => PlatformEvent::SessionReady(info),
+                    Err(e) => PlatformEvent::SessionFailed(e),
+                }));
+            }
+        }
+        self.client.probe_if_due().await;
+        if let Some(state) = self.client.take_circuit_state_change() {
+            return Ok(Some(PlatformEvent::CircuitStateChanged(state)));
+        }
+        if let Some(server_url) = self.client.take_endpoint_change() {
+            if let Some(info) = self.connection_info.as_mut() {
+                info.server = server_url.clone();
+            }
+            return Ok(Some(PlatformEvent::ServerEndpointChanged { server_url }));
+        }
+
+        let reconnects = match self.websocket.lock().await.as_ref() {
+            Some(ws) => ws.reconnect_count(),
+            None => self.seen_reconnects,
+        };
+        if reconnects != self.seen_reconnects {
+            self.seen_reconnects = reconnects;
+            self.prune_live_state().await;
+            let since = match self.websocket.lock().await.as_ref() {
+                Some(ws) if ws.resyncs_on_reconnect() => ws.resync_since(),
+                _ => None,
+            };
+            if let Some(since) = since {
+                self.resync_missed_events(since).await;
+            }
+            self.outbox.make_due();
+            // The WebSocket can't tell an expired session apart from a lost
+            // connection; a request renews the session if it expired
+            self.client.verify_session().await;
+        }
+        if let Some(error) = self.client.session().take_expiry() {
+            return Ok(Some(PlatformEvent::SessionExpired(error)));
+        }
+        if self.client.session().take_renewed() && self.websocket.lock().await.is_some() {
+            // The WebSocket authenticates with the token it was opened with
+            self.unsubscribe_events().await?;
+            if let Err(e) = self.subscribe_events().await {
+                self.client.session().defer_renewed();
+                return Err(e);
+            }
+        }
+        if self.outbox.is_due() && self.websocket_state().await == Some(WsState::Connected) {
+            self.flush_outbox().await;
+        }
+
+        let local_events = std::mem::take(&mut *self.local_events.lock().unwrap());
+        self.pending_events.extend(local_events);
+        if let Some(event) = self.pending_events.pop_front() {
+            return Ok(Some(event));
+        }
+
         let ws_lock = self.websocket.lock().await;
         if let Some(ws) = ws_lock.as_ref() {
             // Poll from the WebSocket manager
             if let Some(event) = ws.poll_event().await {
+                let event = self.confirm_own_post(event).await;
+
                 // Invalidate caches based on event type
                 match &event {
                     // User events - invalidate user cache
@@ -385,6 +1159,9 @@ impl Platform for MattermostPlatform {
                     }
                     PlatformEvent::UserRoleUpdated { user_id } => {
                         self.client.invalidate_user_cache(user_id).await;
+                        if self.client.get_user_id().await.as_deref() == Some(user_id.as_str()) {
+                            self.client.invalidate_permission_caches().await;
+                        }
                     }
 
                     // Channel events - invalidate channel cache
@@ -393,9 +1170,96 @@ impl Platform for MattermostPlatform {
                     }
                     PlatformEvent::ChannelUpdated(channel) => {
                         self.client.invalidate_channel_cache(&channel.id).await;
+                        self.client
+                            .invalidate_channel_permissions(&channel.id)
+                            .await;
                     }
                     PlatformEvent::ChannelDeleted { channel_id } => {
                         self.client.invalidate_channel_cache(channel_id).await;
+                        self.message_windows.remove(channel_id).await;
+                    }
+                    PlatformEvent::SharedChannelRemoteUpdated { channel_id, .. } => {
+                        self.client.invalidate_channel_cache(channel_id).await;
+                    }
+
+                    // Permission events - recompute permissions on next access
+                    PlatformEvent::ChannelMemberUpdated { channel_id, .. }
+                    | PlatformEvent::MemberRoleUpdated { channel_id, .. } => {
+                        self.client.invalidate_channel_permissions(channel_id).await;
+                    }
+                    PlatformEvent::RoleUpdated { .. } => {
+                        self.client.invalidate_permission_caches().await;
+                    }
+
+                    // Read state - the server confirmed a view, drop the optimistic entry
+                    PlatformEvent::ChannelViewed {
+                        user_id,
+                        channel_id,
+                    } if self.client.get_user_id().await.as_deref() == Some(user_id.as_str()) => {
+                        self.view_tracker.confirm_view(channel_id).await;
+                    }
+
+                    // Live state - track typing and statuses
+                    PlatformEvent::UserTyping {
+                        user_id,
+                        channel_id,
+                    } => {
+                        self.typing
+                            .record(channel_id, user_id, std::time::Instant::now());
+                    }
+                    PlatformEvent::UserStatusChanged { user_id, status } => {
+                        self.statuses.set(user_id, *status);
+                    }
+
+                    // Activity - count new messages and keep windows current
+                    PlatformEvent::MessagePosted(message)
+                    | PlatformEvent::MessageConfirmed { message, .. } => {
+                        self.typing.remove(&message.channel_id, &message.sender_id);
+                        self.activity_tracker
+                            .record(&message.channel_id, message.created_at.timestamp_millis())
+                            .await;
+                        self.message_windows.record_posted(message).await;
+                        if let Some(poll) = poll_from_message(message) {
+                            self.pending_events
+                                .push_back(PlatformEvent::PollUpdated(poll));
+                        }
+                    }
+
+                    // Pins and polls - report the new state after the message event
+                    PlatformEvent::MessageUpdated(message) => {
+                        let previous = self.message_windows.record_updated(message).await;
+                        if let Some(pinned) = pinned_state(message) {
+                            let was_pinned = self
+                                .pins
+                                .update(&message.channel_id, &message.id, pinned)
+                                .or_else(|| previous.as_ref().and_then(pinned_state));
+                            match (was_pinned, pinned) {
+                                (Some(false), true) => self
+                                    .pending_events
+                                    .push_back(PlatformEvent::MessagePinned(message.clone())),
+                                (Some(true), false) => {
+                                    self.pending_events
+                                        .push_back(PlatformEvent::MessageUnpinned {
+                                            message_id: message.id.clone(),
+                                            channel_id: message.channel_id.clone(),
+                                        })
+                                }
+                                _ => {}
+                            }
+                        }
+                        if let Some(poll) = poll_from_message(message) {
+                            self.pending_events
+                                .push_back(PlatformEvent::PollUpdated(poll));
+                        }
+                    }
+                    PlatformEvent::MessageDeleted {
+                        message_id,
+                        channel_id,
+                    } => {
+                        self.pins.remove(channel_id, message_id);
+                        self.message_windows
+                            .record_deleted(channel_id, message_id)
+                            .await;
                     }
 
                     // Team events - clear team cache (structural changes)
@@ -406,10 +1270,25 @@ impl Platform for MattermostPlatform {
                         self.client.invalidate_team_cache(team_id).await;
                     }
 
+                    // Sequence gaps - report the missed posts after the gap
+                    PlatformEvent::EventsMissed { .. } if self.resync_on_gap => {
+                        let since = ws.take_gap_since().await;
+                        drop(ws_lock);
+                        if let Some(since) = since {
+                            self.resync_missed_events(since).await;
+                        }
+                        return Ok(Some(event));
+                    }
+
                     // Other events don't require cache invalidation
                     _ => {}
                 }
 
+                if ws.enriches_events() {
+                    // Lookups may take a request; don't hold up the WebSocket
+                    drop(ws_lock);
+                    return Ok(Some(self.enrich_event(event).await));
+                }
                 return Ok(Some(event));
             }
         }
@@ -421,10 +1300,253 @@ impl Platform for MattermostPlatform {
     // ========================================================================
 
     async fn send_reply(&self, channel_id: &str, text: &str, root_id: &str) -> Result<Message> {
-        let mm_post = self.client.send_reply(channel_id, text, root_id).await?;
+        self.send_or_queue(channel_id, text, Some(root_id)).await
+    }
+
+    async fn send_message_as(
+        &self,
+        channel_id: &str,
+        text: &str,
+        username: Option<&str>,
+        icon_url: Option<&str>,
+    ) -> Result<Message> {
+        let mm_post = self
+            .client
+            .send_message_with_overrides(channel_id, text, username, icon_url)
+            .await?;
+        Ok(mm_post.into())
+    }
+
+    async fn send_message_with_props(
+        &self,
+        channel_id: &str,
+        text: &str,
+        props: std::collections::HashMap<String, serde_json::Value>,
+    ) -> Result<Message> {
+        let mm_post = self
+            .client
+            .send_message_with_props(channel_id, text, props)
+            .await?;
+        Ok(mm_post.into())
+    }
+
+    async fn send_message_with_files(
+        &self,
+        channel_id: &str,
+        text: &str,
+        file_ids: Vec<String>,
+        props: std::collections::HashMap<String, serde_json::Value>,
+    ) -> Result<Message> {
+        let mm_post = self
+            .client
+            .send_message_with_files(channel_id, text, file_ids, props)
+            .await?;
+        Ok(mm_post.into())
+    }
+
+    async fn send_code_snippet(
+        &self,
+        channel_id: &str,
+        language: Option<&str>,
+        code: &str,
+        filename: Option<&str>,
+    ) -> Result<Message> {
+        let text = snippet::fenced_code_block(language, code);
+        let limits = self.client.server_limits().await;
+        let uploaded = snippet::should_upload_snippet(code, text.chars().count(), &limits);
+
+        let info = snippet::SnippetInfo {
+            language: language.map(|l| l.to_string()),
+            filename: filename.map(|f| f.to_string()),
+            uploaded,
+        };
+        let mut props = std::collections::HashMap::new();
+        props.insert(
+            snippet::SNIPPET_PROP.to_string(),
+            serde_json::to_value(&info).unwrap_or_default(),
+        );
+
+        let mm_post = if uploaded {
+            // Mattermost previews code files with highlighting based on the extension
+            let name = snippet::snippet_filename(language, filename);
+            let file_info = self
+                .client
+                .upload_file_bytes(channel_id, &name, code.as_bytes().to_vec(), None)
+                .await?;
+            self.client
+                .send_message_with_files(channel_id, "", vec![file_info.id], props)
+                .await?
+        } else {
+            self.client
+                .send_message_with_props(channel_id, &text, props)
+                .await?
+        };
         Ok(mm_post.into())
     }
 
+    async fn message_permalink(&self, message: &Message) -> Result<String> {
+        // Permalinks are team-scoped; direct and group channels have no team,
+        // and open in any team the user belongs to
+        let channel = self.client.get_channel_cached(&message.channel_id).await?;
+        let team_id = if channel.team_id.is_empty() {
+            self.client
+                .get_team_id()
+                .await
+                .ok_or_else(|| Error::new(ErrorCode::InvalidArgument, "Team ID not set"))?
+        } else {
+            channel.team_id
+        };
+        let team = self.client.get_team_cached(&team_id).await?;
+        Ok(format!(
+            "{}/{}/pl/{}",
+            self.client.get_base_url(),
+            team.name,
+            message.id
+        ))
+    }
+
+    async fn execute_command(
+        &self,
+        channel_id: &str,
+        command: &str,
+    ) -> Result<crate::types::CommandResponse> {
+        let command = command.trim();
+        if !command.starts_with('/') || command.len() < 2 {
+            return Err(Error::invalid_argument(
+                "Slash commands must start with '/' followed by the command",
+            ));
+        }
+        let response = self.client.execute_command(channel_id, command).await?;
+        Ok(response.into())
+    }
+
+    async fn autocomplete_commands(
+        &self,
+        team_id: Option<&str>,
+        prefix: &str,
+        limit: usize,
+    ) -> Result<Vec<crate::types::SlashCommand>> {
+        let team_id = self.team_id_or_current(team_id).await?;
+        let commands = self
+            .client
+            .list_autocomplete_commands(&team_id)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        Ok(super::commands::filter_commands(commands, prefix, limit))
+    }
+
+    async fn create_poll(
+        &self,
+        channel_id: &str,
+        question: &str,
+        options: &[String],
+        settings: &PollSettings,
+    ) -> Result<Poll> {
+        self.client
+            .create_poll(channel_id, question, options, settings)
+            .await
+    }
+
+    async fn get_poll(&self, poll_id: &str) -> Result<Poll> {
+        self.client.get_poll(poll_id).await
+    }
+
+    async fn vote_poll(&self, poll_id: &str, option_index: usize) -> Result<()> {
+        self.client.vote_poll(poll_id, option_index).await
+    }
+
+    async fn close_poll(&self, poll_id: &str) -> Result<Poll> {
+        self.client.close_poll(poll_id).await
+    }
+
+    async fn set_message_reminder(
+        &self,
+        message_id: &str,
+        due_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<()> {
+        self.client
+            .set_post_reminder(message_id, due_at.timestamp())
+            .await
+    }
+
+    async fn schedule_message(
+        &self,
+        channel_id: &str,
+        text: &str,
+        send_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<ScheduledMessage> {
+        self.client.server_limits().await.check_message(text)?;
+        let mm_scheduled = MattermostScheduledPost {
+            channel_id: channel_id.to_string(),
+            message: text.to_string(),
+            scheduled_at: send_at.timestamp_millis(),
+            ..Default::default()
+        };
+        Ok(self
+            .client
+            .create_scheduled_post(mm_scheduled)
+            .await?
+            .into())
+    }
+
+    async fn get_scheduled_messages(&self) -> Result<Vec<ScheduledMessage>> {
+        let team_id = self.client.get_team_id().await.ok_or_else(|| {
+            Error::new(
+                ErrorCode::InvalidState,
+                "Team ID not set - call connect() with a team_id or set it manually",
+            )
+        })?;
+        let mut scheduled: Vec<ScheduledMessage> = self
+            .client
+            .get_scheduled_posts(&team_id)
+            .await?
+            .into_iter()
+            .map(ScheduledMessage::from)
+            .collect();
+        scheduled.sort_by_key(|s| s.scheduled_at);
+        Ok(scheduled)
+    }
+
+    async fn cancel_scheduled_message(&self, scheduled_id: &str) -> Result<()> {
+        self.client.delete_scheduled_post(scheduled_id).await
+    }
+
+    async fn save_draft(&self, draft: &Draft) -> Result<Draft> {
+        let mm_draft = MattermostDraft {
+            channel_id: draft.channel_id.clone(),
+            root_id: draft.root_id.clone().unwrap_or_default(),
+            message: draft.message.clone(),
+            props: Some(draft.props.clone()),
+            file_ids: Some(draft.file_ids.clone()),
+            ..Default::default()
+        };
+        Ok(self.client.upsert_draft(mm_draft).await?.into())
+    }
+
+    async fn get_drafts(&self) -> Result<Vec<Draft>> {
+        let team_id = self.client.get_team_id().await.ok_or_else(|| {
+            Error::new(
+                ErrorCode::InvalidState,
+                "Team ID not set - call connect() with a team_id or set it manually",
+            )
+        })?;
+        let mut drafts: Vec<Draft> = self
+            .client
+            .get_team_drafts(&team_id)
+            .await?
+            .into_iter()
+            .map(Draft::from)
+            .collect();
+        drafts.sort_by_key(|draft| std::cmp::Reverse(draft.updated_at));
+        Ok(drafts)
+    }
+
+    async fn delete_draft(&self, channel_id: &str, root_id: Option<&str>) -> Result<()> {
+        self.client.delete_draft(channel_id, root_id).await
+    }
+
     async fn update_message(&self, message_id: &str, new_text: &str) -> Result<Message> {
         let mm_post = self.client.update_post(message_id, new_text).await?;
         Ok(mm_post.into())
@@ -440,40 +1562,57 @@ impl Platform for MattermostPlatform {
     }
 
     async fn search_messages(&self, query: &str, limit: usize) -> Result<Vec<Message>> {
-        let team_id = self
-            .client
-            .get_team_id()
-            .await
-            .ok_or_else(|| Error::new(ErrorCode::InvalidArgument, "Team ID not set"))?;
+        let mut messages = self.search_page(query, 0, limit as u32).await?;
 
-        // Use advanced search with pagination
-        let options = crate::platforms::mattermost::PostSearchOptions {
-            is_or_search: false,
-            include_deleted_channels: false,
-            time_zone_offset: 0,
-            page: 0,
-            per_page: limit as u32,
-        };
+        // Limit to requested number
+        messages.truncate(limit);
 
-        let post_list = self
-            .client
-            .search_posts_advanced(&team_id, query, options)
-            .await?;
+        Ok(messages)
+    }
 
-        // Convert posts to messages
-        let mut messages: Vec<Message> = post_list
-            .order
-            .iter()
-            .filter_map(|post_id| post_list.posts.get(post_id))
-            .map(|post| post.clone().into())
-            .collect();
+    async fn search_messages_query(
+        &self,
+        query: &crate::types::SearchQuery,
+        limit: usize,
+    ) -> Result<Vec<Message>> {
+        let terms = crate::platforms::mattermost::to_mattermost_terms(query);
+        if !query.has_attachment {
+            return self.search_messages(&terms, limit).await;
+        }
 
-        // Limit to requested number
+        // Mattermost has no attachment filter for post search, so pages of
+        // results are filtered until enough messages with files are found
+        let mut messages = Vec::new();
+        for page in 0..MAX_FILTERED_SEARCH_PAGES {
+            if messages.len() >= limit {
+                break;
+            }
+            let results = self.search_page(&terms, page, limit as u32).await?;
+            let last_page = results.len() < limit;
+            messages.extend(results.into_iter().filter(|m| !m.attachments.is_empty()));
+            if last_page {
+                break;
+            }
+        }
         messages.truncate(limit);
 
         Ok(messages)
     }
 
+    async fn get_messages_at_date(
+        &self,
+        channel_id: &str,
+        date: chrono::DateTime<chrono::Utc>,
+        limit: usize,
+    ) -> Result<Vec<Message>> {
+        let posts = self
+            .client
+            .get_posts_around_time(channel_id, date.timestamp_millis(), limit as u32)
+            .await?;
+
+        Ok(posts.into_iter().map(|post| post.into()).collect())
+    }
+
     async fn get_messages_before(
         &self,
         channel_id: &str,
@@ -533,23 +1672,90 @@ impl Platform for MattermostPlatform {
         self.client.remove_reaction(message_id, emoji).await
     }
 
+    async fn get_reactions(&self, message_id: &str) -> Result<Vec<crate::types::Reaction>> {
+        let mut reactions: Vec<crate::types::Reaction> = self
+            .client
+            .get_reactions(message_id)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        reactions.sort_by_key(|r| r.created_at);
+        Ok(reactions)
+    }
+
     async fn pin_post(&self, message_id: &str) -> Result<()> {
         self.client.pin_post(message_id).await
     }
 
-    async fn unpin_post(&self, message_id: &str) -> Result<()> {
-        self.client.unpin_post(message_id).await
+    async fn unpin_post(&self, message_id: &str) -> Result<()> {
+        self.client.unpin_post(message_id).await
+    }
+
+    async fn get_pinned_posts(&self, channel_id: &str) -> Result<Vec<Message>> {
+        let mm_posts = self.client.get_pinned_posts(channel_id).await?;
+        let messages: Vec<Message> = mm_posts.into_iter().map(|p| p.into()).collect();
+        self.pins
+            .set_channel(channel_id, messages.iter().map(|m| m.id.clone()));
+        Ok(messages)
+    }
+
+    async fn flag_post(&self, message_id: &str) -> Result<()> {
+        let user_id = self.client.current_user_id().await?;
+        self.client.flag_post(&user_id, message_id).await
+    }
+
+    async fn unflag_post(&self, message_id: &str) -> Result<()> {
+        let user_id = self.client.current_user_id().await?;
+        self.client.unflag_post(&user_id, message_id).await
+    }
+
+    async fn get_flagged_posts(&self, page: u32, per_page: u32) -> Result<Page<Message>> {
+        let user_id = self.client.current_user_id().await?;
+        let mm_posts = self
+            .client
+            .get_flagged_posts(&user_id, page, per_page)
+            .await?;
+        let next_cursor =
+            crate::types::pagination::flagged_posts_cursor(page, per_page, mm_posts.len());
+        Ok(Page::new(
+            mm_posts.into_iter().map(Message::from).collect(),
+            next_cursor,
+        ))
+    }
+
+    async fn get_emojis(&self, page: u32, per_page: u32) -> Result<Vec<crate::types::Emoji>> {
+        let mm_emojis = self.client.get_emojis(page, per_page, "name").await?;
+        Ok(mm_emojis.into_iter().map(|e| e.into()).collect())
+    }
+
+    async fn get_emoji_image(&self, name_or_id: &str) -> Result<Vec<u8>> {
+        self.client.get_emoji_image_cached(name_or_id).await
+    }
+
+    async fn create_emoji(&self, name: &str, image: Vec<u8>) -> Result<crate::types::Emoji> {
+        Ok(self.client.create_emoji(name, image).await?.into())
     }
 
-    async fn get_pinned_posts(&self, channel_id: &str) -> Result<Vec<Message>> {
-        let mm_posts = self.client.get_pinned_posts(channel_id).await?;
-        let messages: Vec<Message> = mm_posts.into_iter().map(|p| p.into()).collect();
-        Ok(messages)
+    async fn delete_emoji(&self, emoji_id: &str) -> Result<()> {
+        self.client.delete_emoji(emoji_id).await
     }
 
-    async fn get_emojis(&self, page: u32, per_page: u32) -> Result<Vec<crate::types::Emoji>> {
-        let mm_emojis = self.client.get_emojis(page, per_page, "name").await?;
-        Ok(mm_emojis.into_iter().map(|e| e.into()).collect())
+    async fn autocomplete_emojis(
+        &self,
+        prefix: &str,
+        limit: usize,
+    ) -> Result<Vec<crate::types::Emoji>> {
+        let prefix = prefix.trim().trim_start_matches(':');
+        if prefix.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mm_emojis = self.client.autocomplete_emojis(prefix).await?;
+        Ok(mm_emojis
+            .into_iter()
+            .take(limit)
+            .map(|e| e.into())
+            .collect())
     }
 
     async fn get_channel_by_name(&self, team_id: &str, channel_name: &str) -> Result<Channel> {
@@ -578,6 +1784,45 @@ impl Platform for MattermostPlatform {
         self.client.remove_channel_member(channel_id, user_id).await
     }
 
+    async fn get_channel_member_roles(
+        &self,
+        channel_id: &str,
+        user_id: &str,
+    ) -> Result<crate::types::ChannelMemberRoles> {
+        let member = self.client.get_channel_member(channel_id, user_id).await?;
+        Ok(member.into())
+    }
+
+    async fn set_channel_admin(
+        &self,
+        channel_id: &str,
+        user_id: &str,
+        is_admin: bool,
+    ) -> Result<()> {
+        self.client
+            .update_channel_member_scheme_roles(channel_id, user_id, is_admin)
+            .await
+    }
+
+    async fn set_channel_privacy(&self, channel_id: &str, is_private: bool) -> Result<Channel> {
+        let current = self.client.get_channel_cached(channel_id).await?;
+        if !matches!(
+            current.channel_type,
+            MattermostChannelType::Open | MattermostChannelType::Private
+        ) {
+            return Err(Error::invalid_argument(
+                "Only public and private channels can change privacy",
+            ));
+        }
+        let mm_channel = self
+            .client
+            .update_channel_privacy(channel_id, is_private)
+            .await?;
+        let current_user_id = self.client.get_user_id().await;
+        self.convert_channel_with_context(mm_channel, current_user_id.as_deref())
+            .await
+    }
+
     async fn get_user_by_username(&self, username: &str) -> Result<User> {
         let mm_user = self.client.get_user_by_username(username).await?;
         Ok(mm_user.into())
@@ -589,10 +1834,34 @@ impl Platform for MattermostPlatform {
     }
 
     async fn get_users_by_ids(&self, user_ids: Vec<String>) -> Result<Vec<User>> {
-        let mm_users = self.client.get_users_by_ids(&user_ids).await?;
+        let mm_users = self.client.get_users_by_ids_cached(&user_ids).await?;
         Ok(mm_users.into_iter().map(|u| u.into()).collect())
     }
 
+    async fn get_user_avatar(&self, user_id: &str) -> Result<Vec<u8>> {
+        self.client.get_user_image_cached(user_id).await
+    }
+
+    async fn prefetch_channel_profiles(&self, channel_ids: Vec<String>) -> Result<()> {
+        let mut prefetch_task = self.prefetch_task.lock().unwrap();
+        if let Some(previous) = prefetch_task.take() {
+            previous.abort();
+        }
+        if channel_ids.is_empty() {
+            return Ok(());
+        }
+
+        let client = Arc::clone(&self.client);
+        let visible_users = Arc::clone(&self.visible_users);
+        *prefetch_task = Some(tokio::spawn(async move {
+            // Prefetching is best-effort; lookups fall back to the API
+            if let Ok(user_ids) = client.prefetch_channel_profiles(&channel_ids).await {
+                *visible_users.lock().unwrap() = user_ids;
+            }
+        }));
+        Ok(())
+    }
+
     async fn set_custom_status(
         &self,
         emoji: Option<&str>,
@@ -633,12 +1902,19 @@ impl Platform for MattermostPlatform {
         let mut status_map = std::collections::HashMap::new();
         for status in mm_statuses {
             let user_status = super::status_string_to_user_status(&status.status);
+            self.statuses.set(&status.user_id, user_status);
             status_map.insert(status.user_id, user_status);
         }
 
         Ok(status_map)
     }
 
+    async fn get_typing_users(&self, channel_id: &str) -> Result<Vec<String>> {
+        Ok(self
+            .typing
+            .typing_users(channel_id, std::time::Instant::now()))
+    }
+
     async fn request_all_statuses(&self) -> Result<i64> {
         let ws_lock = self.websocket.lock().await;
         if let Some(ws) = ws_lock.as_ref() {
@@ -673,6 +1949,40 @@ impl Platform for MattermostPlatform {
         Ok(())
     }
 
+    async fn set_circuit_breaker_config(&self, config: CircuitBreakerConfig) -> Result<()> {
+        self.client.set_circuit_breaker_config(config);
+        Ok(())
+    }
+
+    async fn get_circuit_state(&self) -> Result<CircuitState> {
+        Ok(self.client.circuit_state())
+    }
+
+    async fn configure_cache(&self, config: crate::types::CacheConfig) -> Result<()> {
+        self.client.configure_caches(config).await;
+        Ok(())
+    }
+
+    async fn get_cache_stats(&self) -> Result<Vec<crate::types::CacheStats>> {
+        Ok(self.client.get_cache_stats().await)
+    }
+
+    async fn set_event_filter(&self, filter: EventFilter) -> Result<()> {
+        *self.event_filter.write().unwrap() = filter;
+        Ok(())
+    }
+
+    async fn connection_health(&self) -> Result<crate::types::ConnectionHealth> {
+        Ok(match self.websocket.lock().await.as_ref() {
+            Some(ws) => ws.health().await,
+            None => crate::types::ConnectionHealth::default(),
+        })
+    }
+
+    async fn get_request_stats(&self) -> Result<crate::types::RequestStats> {
+        Ok(self.client.request_stats())
+    }
+
     // ========================================================================
     // File Operations
     // ========================================================================
@@ -682,18 +1992,68 @@ impl Platform for MattermostPlatform {
         Ok(file_info.id)
     }
 
+    async fn upload_file_with_progress(
+        &self,
+        channel_id: &str,
+        file_path: &std::path::Path,
+        progress: &mut ProgressSink<'_>,
+    ) -> Result<String> {
+        let file_info = self
+            .client
+            .upload_file_resumable(channel_id, file_path, progress)
+            .await?;
+        Ok(file_info.id)
+    }
+
+    async fn upload_file_data(
+        &self,
+        channel_id: &str,
+        filename: &str,
+        data: Vec<u8>,
+    ) -> Result<String> {
+        let file_info = self
+            .client
+            .upload_file_bytes(channel_id, filename, data, None)
+            .await?;
+        Ok(file_info.id)
+    }
+
     async fn download_file(&self, file_id: &str) -> Result<Vec<u8>> {
         self.client.download_file(file_id).await
     }
 
+    async fn download_file_to_path(&self, file_id: &str, path: Option<&Path>) -> Result<PathBuf> {
+        let Some(path) = path else {
+            let path = files::temp_download_path(file_id);
+            let result = self.client.download_file_to_path(file_id, &path).await;
+            if result.is_err() {
+                // Nobody can resume a download to a temp path
+                let _ = tokio::fs::remove_file(files::partial_path(&path)).await;
+            }
+            return result.map(|_| path);
+        };
+        self.client.download_file_to_path(file_id, path).await?;
+        Ok(path.to_path_buf())
+    }
+
+    async fn download_file_streaming(
+        &self,
+        file_id: &str,
+        sink: &mut ChunkSink<'_>,
+    ) -> Result<u64> {
+        self.client.download_file_streaming(file_id, sink).await
+    }
+
     async fn get_file_metadata(&self, file_id: &str) -> Result<Attachment> {
         let file_info = self.client.get_file_info(file_id).await?;
         // Convert FileInfo to Attachment using context
         let ctx = ConversionContext {
-            server_url: self.client.get_base_url().to_string(),
+            server_url: self.client.get_base_url(),
             current_user_id: self.client.get_user_id().await,
         };
-        Ok(file_info.to_attachment_with_context(&ctx))
+        let mut attachment = file_info.to_attachment_with_context(&ctx);
+        attachment.checksum = self.client.checksums().get(file_id);
+        Ok(attachment)
     }
 
     async fn get_file_thumbnail(&self, file_id: &str) -> Result<Vec<u8>> {
@@ -776,6 +2136,40 @@ impl Platform for MattermostPlatform {
             .await
     }
 
+    async fn get_followed_threads(
+        &self,
+        team_id: Option<&str>,
+        page: u32,
+        per_page: u32,
+    ) -> Result<Page<Thread>> {
+        let current_team_id = self.team_id_or_current(team_id).await?;
+
+        let threads = self
+            .client
+            .get_user_threads(
+                "me",
+                &current_team_id,
+                None,
+                false,
+                false,
+                true,
+                page,
+                per_page,
+            )
+            .await?
+            .threads;
+        let next_cursor = crate::types::pagination::followed_threads_cursor(
+            team_id,
+            page,
+            per_page,
+            threads.len(),
+        );
+        Ok(Page::new(
+            threads.into_iter().map(Thread::from).collect(),
+            next_cursor,
+        ))
+    }
+
     async fn search_users(&self, query: &str, limit: usize) -> Result<Vec<User>> {
         let team_id = self
             .client
@@ -811,12 +2205,13 @@ impl Platform for MattermostPlatform {
         Ok(mm_users.into_iter().map(|u| u.into()).collect())
     }
 
-    async fn search_channels(&self, query: &str, limit: usize) -> Result<Vec<Channel>> {
-        let team_id = self
-            .client
-            .get_team_id()
-            .await
-            .ok_or_else(|| Error::new(ErrorCode::InvalidArgument, "Team ID not set"))?;
+    async fn search_channels(
+        &self,
+        team_id: Option<&str>,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<Channel>> {
+        let team_id = self.team_id_or_current(team_id).await?;
 
         let request = crate::platforms::mattermost::ChannelSearchRequest::new(query.to_string());
 
@@ -838,12 +2233,13 @@ impl Platform for MattermostPlatform {
         Ok(channels)
     }
 
-    async fn autocomplete_channels(&self, query: &str, limit: usize) -> Result<Vec<Channel>> {
-        let team_id = self
-            .client
-            .get_team_id()
-            .await
-            .ok_or_else(|| Error::new(ErrorCode::InvalidArgument, "Team ID not set"))?;
+    async fn autocomplete_channels(
+        &self,
+        team_id: Option<&str>,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<Channel>> {
+        let team_id = self.team_id_or_current(team_id).await?;
 
         let mm_channels = self.client.autocomplete_channels(&team_id, query).await?;
 
@@ -933,37 +2329,240 @@ impl Platform for MattermostPlatform {
             .await
     }
 
-    async fn view_channel(&self, channel_id: &str) -> Result<()> {
-        self.client.view_channel(channel_id, None).await?;
+    async fn view_channel(&self, channel_id: &str, prev_channel_id: Option<&str>) -> Result<()> {
+        let prev_channel_id = match self
+            .view_tracker
+            .record_view(channel_id, prev_channel_id)
+            .await
+        {
+            ViewDecision::Report { prev_channel_id } => prev_channel_id,
+            ViewDecision::Debounced => return Ok(()),
+        };
+
+        if let Err(e) = self
+            .client
+            .view_channel(channel_id, prev_channel_id.clone())
+            .await
+        {
+            self.view_tracker
+                .view_failed(channel_id, prev_channel_id.as_deref())
+                .await;
+            return Err(e);
+        }
+        self.emit_channel_viewed(channel_id).await;
         Ok(())
     }
 
     async fn get_channel_unread(&self, channel_id: &str) -> Result<crate::types::ChannelUnread> {
         let mm_unread = self.client.get_channel_unread(channel_id).await?;
 
-        Ok(crate::types::ChannelUnread {
+        let unread = crate::types::ChannelUnread {
             channel_id: mm_unread.channel_id,
             team_id: Some(mm_unread.team_id),
             msg_count: mm_unread.msg_count,
             mention_count: mm_unread.mention_count,
             last_viewed_at: mm_unread.last_viewed_at,
-        })
+        };
+        Ok(self.view_tracker.apply(unread).await)
+    }
+
+    async fn get_channel_activity(
+        &self,
+        channel_id: &str,
+        days: u32,
+    ) -> Result<crate::types::ChannelActivity> {
+        if days == 0 || days > MAX_ACTIVITY_DAYS {
+            return Err(Error::invalid_argument(format!(
+                "days must be between 1 and {MAX_ACTIVITY_DAYS}"
+            )));
+        }
+
+        let hours = days as usize * 24;
+        let start = hour_start(chrono::Utc::now().timestamp_millis())
+            - (hours as i64 - 1) * crate::types::ChannelActivity::HOUR_MS;
+
+        if !self.activity_tracker.is_covered(channel_id, start).await {
+            let times = self.client.get_post_times_since(channel_id, start).await?;
+            self.activity_tracker
+                .backfill(channel_id, start, &times)
+                .await;
+        }
+
+        Ok(self
+            .activity_tracker
+            .activity(channel_id, start, hours)
+            .await)
+    }
+
+    async fn get_window(&self, channel_id: &str) -> Result<Vec<Message>> {
+        let now = std::time::Instant::now();
+        if let Some(messages) = self.message_windows.get(channel_id, now).await {
+            return Ok(messages);
+        }
+
+        let messages = self.get_messages(channel_id, WINDOW_SIZE).await?;
+        self.message_windows.fill(channel_id, &messages, now).await;
+        Ok(messages)
     }
 
     async fn get_team_unreads(&self, team_id: &str) -> Result<Vec<crate::types::ChannelUnread>> {
         let mm_unreads = self.client.get_team_unreads(team_id).await?;
 
-        Ok(mm_unreads
-            .into_iter()
-            .map(|mm_unread| crate::types::ChannelUnread {
+        let mut unreads = Vec::with_capacity(mm_unreads.len());
+        for mm_unread in mm_unreads {
+            let unread = crate::types::ChannelUnread {
                 channel_id: mm_unread.channel_id,
                 team_id: Some(mm_unread.team_id),
                 msg_count: mm_unread.msg_count,
                 mention_count: mm_unread.mention_count,
                 last_viewed_at: mm_unread.last_viewed_at,
+            };
+            unreads.push(self.view_tracker.apply(unread).await);
+        }
+        Ok(unreads)
+    }
+
+    async fn get_channel_unreads(&self) -> Result<Vec<crate::types::ChannelUnread>> {
+        let team_id = self.client.get_team_id().await.ok_or_else(|| {
+            Error::new(
+                ErrorCode::InvalidState,
+                "Team ID not set - call connect() with a team_id or set it manually",
+            )
+        })?;
+        self.get_team_unreads(&team_id).await
+    }
+
+    async fn get_all_unreads(&self) -> Result<Vec<crate::types::TeamUnread>> {
+        let mm_unreads = self.client.get_all_unreads().await?;
+        Ok(mm_unreads
+            .into_iter()
+            .map(|mm_unread| crate::types::TeamUnread {
+                team_id: mm_unread.team_id,
+                msg_count: mm_unread.msg_count,
+                mention_count: mm_unread.mention_count,
             })
             .collect())
     }
+
+    async fn get_sidebar_categories(&self) -> Result<Vec<SidebarCategory>> {
+        let team_id = self.client.get_team_id().await.ok_or_else(|| {
+            Error::new(
+                ErrorCode::InvalidState,
+                "Team ID not set - call connect() with a team_id or set it manually",
+            )
+        })?;
+        let categories = self.client.get_sidebar_categories(&team_id).await?;
+        Ok(categories.into_iter().map(SidebarCategory::from).collect())
+    }
+
+    async fn move_channel_to_category(
+        &self,
+        channel_id: &str,
+        category_id: &str,
+        index: Option<usize>,
+    ) -> Result<()> {
+        let team_id = self.client.get_team_id().await.ok_or_else(|| {
+            Error::new(
+                ErrorCode::InvalidState,
+                "Team ID not set - call connect() with a team_id or set it manually",
+            )
+        })?;
+        self.client
+            .move_channel_to_category(&team_id, channel_id, category_id, index)
+            .await
+    }
+
+    async fn mark_channel_read(&self, channel_id: &str) -> Result<()> {
+        self.view_tracker.record_read(channel_id).await;
+        if let Err(e) = self.client.view_channel(channel_id, None).await {
+            self.view_tracker.read_failed(channel_id).await;
+            return Err(e);
+        }
+        self.emit_channel_viewed(channel_id).await;
+        Ok(())
+    }
+
+    // ========================================================================
+    // Integration Management
+    // ========================================================================
+
+    async fn register_oauth_app(
+        &self,
+        settings: &crate::types::OAuthAppSettings,
+    ) -> Result<crate::types::OAuthApp> {
+        settings.validate()?;
+        let mm_app = self.client.create_oauth_app(&settings.into()).await?;
+        Ok(mm_app.into())
+    }
+
+    async fn get_oauth_apps(&self) -> Result<Vec<crate::types::OAuthApp>> {
+        let mm_apps = self.client.get_all_oauth_apps().await?;
+        Ok(mm_apps.into_iter().map(|app| app.into()).collect())
+    }
+
+    async fn delete_oauth_app(&self, app_id: &str) -> Result<()> {
+        self.client.delete_oauth_app(app_id).await
+    }
+
+    async fn create_access_token(
+        &self,
+        user_id: &str,
+        description: &str,
+    ) -> Result<crate::types::AccessToken> {
+        let mm_token = self
+            .client
+            .create_user_access_token(user_id, description)
+            .await?;
+        Ok(mm_token.into())
+    }
+
+    async fn get_access_tokens(&self, user_id: &str) -> Result<Vec<crate::types::AccessToken>> {
+        let mm_tokens = self.client.get_all_user_access_tokens(user_id).await?;
+        Ok(mm_tokens.into_iter().map(|token| token.into()).collect())
+    }
+
+    async fn revoke_access_token(&self, token_id: &str) -> Result<()> {
+        self.client.revoke_user_access_token(token_id).await
+    }
+
+    async fn create_bot(&self, settings: &crate::types::BotSettings) -> Result<crate::types::Bot> {
+        settings.validate()?;
+        let mm_bot = self.client.create_bot(settings).await?;
+        Ok(mm_bot.into())
+    }
+
+    async fn get_bots(&self, include_disabled: bool) -> Result<Vec<crate::types::Bot>> {
+        let mm_bots = self.client.get_all_bots(include_disabled).await?;
+        Ok(mm_bots.into_iter().map(|bot| bot.into()).collect())
+    }
+
+    async fn set_bot_enabled(&self, bot_user_id: &str, enabled: bool) -> Result<crate::types::Bot> {
+        let mm_bot = self.client.set_bot_enabled(bot_user_id, enabled).await?;
+        Ok(mm_bot.into())
+    }
+
+    async fn assign_bot(&self, bot_user_id: &str, owner_id: &str) -> Result<crate::types::Bot> {
+        let mm_bot = self.client.assign_bot(bot_user_id, owner_id).await?;
+        Ok(mm_bot.into())
+    }
+
+    async fn convert_user_to_bot(&self, user_id: &str) -> Result<crate::types::Bot> {
+        let mm_bot = self.client.convert_user_to_bot(user_id).await?;
+        Ok(mm_bot.into())
+    }
+
+    async fn convert_bot_to_user(&self, bot_user_id: &str, password: &str) -> Result<User> {
+        if password.is_empty() {
+            return Err(Error::invalid_argument(
+                "A password is needed to convert a bot to a user",
+            ));
+        }
+        let mm_user = self
+            .client
+            .convert_bot_to_user(bot_user_id, password)
+            .await?;
+        Ok(mm_user.into())
+    }
 }
 
 #[cfg(test)]
@@ -993,4 +2592,269 @@ mod tests {
         assert!(config.credentials.contains_key("login_id"));
         assert_eq!(config.team_id, Some("team-abc".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_reconnect_prunes_live_state() {
+        let mut platform = MattermostPlatform::new("http://127.0.0.1:1").unwrap();
+        platform
+            .typing
+            .record("chan-1", "user-2", std::time::Instant::now());
+        platform
+            .statuses
+            .set("user-2", crate::types::user::UserStatus::Online);
+        assert_eq!(
+            platform.get_typing_users("chan-1").await.unwrap(),
+            vec!["user-2"]
+        );
+
+        platform.prune_live_state().await;
+        assert!(platform
+            .get_typing_users("chan-1")
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(platform.statuses.get_fresh("user-2"), None);
+        assert!(platform.pending_events.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_connect_from_startup_cache() {
+        // Nothing listens on port 1, so the background connect fails
+        let server = "http://127.0.0.1:1";
+        let path = std::env::temp_dir().join(format!(
+            "communicator-startup-connect-{}.json",
+            std::process::id()
+        ));
+        let mut snapshot = StartupSnapshot::new(
+            server,
+            User::new("user-1", "alice", "Alice"),
+            Some("team-1".to_string()),
+        );
+        snapshot.channels.push(Channel::new(
+            "chan-1",
+            "town-square",
+            "Town Square",
+            crate::types::ChannelType::Public,
+        ));
+        snapshot.save(&path).unwrap();
+
+        let mut platform = MattermostPlatform::new(server).unwrap();
+        let config = PlatformConfig::new(server)
+            .with_credential("token", "token")
+            .with_startup_cache(path.to_string_lossy());
+        let info = platform.connect(config).await.unwrap();
+        assert!(info.stale);
+        assert_eq!(info.user_id, "user-1");
+
+        let result = platform.subscribe_events().await;
+        std::fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+        assert!(matches!(
+            platform.poll_event().await.unwrap(),
+            Some(PlatformEvent::SessionFailed(_))
+        ));
+        assert!(platform.connection_info().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_channel_viewed_is_emitted_locally() {
+        let mut platform = MattermostPlatform::new("http://127.0.0.1:1").unwrap();
+        platform
+            .client
+            .set_user_id(Some("user-1".to_string()))
+            .await;
+
+        platform.emit_channel_viewed("chan-1").await;
+        assert!(matches!(
+            platform.poll_event().await.unwrap(),
+            Some(PlatformEvent::ChannelViewed { user_id, channel_id })
+                if user_id == "user-1" && channel_id == "chan-1"
+        ));
+        assert!(platform.poll_event().await.unwrap().is_none());
+    }
+
+    #[cfg(feature = "simulation")]
+    #[tokio::test]
+    async fn test_enrich_membership_event() {
+        let server = crate::simulation::add_server("enrich.sim");
+        server.set_route(
+            "GET",
+            "/users/user-2",
+            200,
+            r#"{"id":"user-2","username":"bob","create_at":0,"update_at":0,"delete_at":0}"#,
+        );
+
+        let platform = MattermostPlatform::new("http://enrich.sim").unwrap();
+        let event = platform
+            .enrich_event(PlatformEvent::UserLeftChannel {
+                user_id: "user-2".to_string(),
+                channel_id: "chan-1".to_string(),
+                user: None,
+                channel: None,
+            })
+            .await;
+        // The unknown channel is left out
+        assert!(matches!(
+            event,
+            PlatformEvent::UserLeftChannel { user: Some(user), channel: None, .. }
+                if user.username == "bob"
+        ));
+
+        crate::simulation::remove_server("enrich.sim");
+    }
+
+    #[cfg(feature = "simulation")]
+    #[tokio::test]
+    async fn test_search_with_attachments_fills_the_limit() {
+        let server = crate::simulation::add_server("attachmentsearch.sim");
+        let post = |id: &str, with_file: bool| {
+            let files = if with_file {
+                serde_json::json!([{
+                    "id": format!("file-{id}"), "user_id": "u1", "post_id": id,
+                    "channel_id": "c1", "create_at": 0, "update_at": 0, "delete_at": 0,
+                    "name": "a.txt", "extension": "txt", "size": 5, "mime_type": "text/plain"
+                }])
+            } else {
+                serde_json::json!([])
+            };
+            serde_json::json!({
+                "id": id, "create_at": 0, "update_at": 0, "delete_at": 0, "edit_at": 0,
+                "user_id": "u1", "channel_id": "c1", "message": "report",
+                "metadata": {"files": files}
+            })
+        };
+        let page = |posts: &[(&str, bool)]| {
+            serde_json::json!({
+                "order": posts.iter().map(|(id, _)| id).collect::<Vec<_>>(),
+                "posts": posts
+                    .iter()
+                    .map(|(id, with_file)| (id.to_string(), post(id, *with_file)))
+                    .collect::<serde_json::Map<_, _>>(),
+            })
+            .to_string()
+        };
+        let path = "/teams/team-1/posts/search";
+        server.queue_route("POST", path, 200, page(&[("p1", true), ("p2", false)]));
+        server.queue_route("POST", path, 200, page(&[("p3", false), ("p4", true)]));
+        server.queue_route("POST", path, 200, page(&[]));
+
+        let platform = MattermostPlatform::new("http://attachmentsearch.sim").unwrap();
+        platform
+            .set_team_id(Some("team-1".to_string()))
+            .await
+            .unwrap();
+        let query = crate::types::SearchQuery::parse("report has:attachment").unwrap();
+        let found = platform.search_messages_query(&query, 2).await.unwrap();
+        crate::simulation::remove_server("attachmentsearch.sim");
+
+        // The first page alone has one message with a file
+        let ids: Vec<_> = found.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, ["p1", "p4"]);
+    }
+
+    #[cfg(feature = "simulation")]
+    #[tokio::test]
+    async fn test_search_channels_in_team() {
+        let server = crate::simulation::add_server("channelsearch.sim");
+        let channels = r#"[
+            {"id":"c1","create_at":0,"update_at":0,"delete_at":0,"team_id":"team-2",
+             "type":"O","display_name":"Town Square","name":"town-square"},
+            {"id":"c2","create_at":0,"update_at":0,"delete_at":0,"team_id":"team-2",
+             "type":"P","display_name":"Town Planning","name":"town-planning"}
+        ]"#;
+        server.set_route("POST", "/teams/team-2/channels/search", 200, channels);
+        server.set_route("GET", "/teams/team-2/channels/autocomplete", 200, channels);
+
+        let platform = MattermostPlatform::new("http://channelsearch.sim").unwrap();
+        let found = platform
+            .search_channels(Some("team-2"), "town", 1)
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].display_name, "Town Square");
+
+        let found = platform
+            .autocomplete_channels(Some("team-2"), "town p", 10)
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 2);
+
+        // Without a team, the current team is needed
+        let err = platform
+            .search_channels(None, "town", 10)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidState);
+
+        crate::simulation::remove_server("channelsearch.sim");
+    }
+
+    #[cfg(feature = "simulation")]
+    #[tokio::test]
+    async fn test_set_channel_privacy() {
+        let server = crate::simulation::add_server("privacy.sim");
+        server.set_route(
+            "GET",
+            "/channels/c1",
+            200,
+            r#"{"id":"c1","create_at":0,"update_at":0,"delete_at":0,"team_id":"t1",
+                "type":"O","display_name":"Plans","name":"plans"}"#,
+        );
+        server.set_route(
+            "PUT",
+            "/channels/c1/privacy",
+            200,
+            r#"{"id":"c1","create_at":0,"update_at":1,"delete_at":0,"team_id":"t1",
+                "type":"P","display_name":"Plans","name":"plans"}"#,
+        );
+        server.set_route(
+            "GET",
+            "/channels/dm",
+            200,
+            r#"{"id":"dm","create_at":0,"update_at":0,"delete_at":0,"team_id":"",
+                "type":"D","display_name":"","name":"u1__u2"}"#,
+        );
+
+        let platform = MattermostPlatform::new("http://privacy.sim").unwrap();
+        let channel = platform.set_channel_privacy("c1", true).await.unwrap();
+        assert_eq!(channel.channel_type, crate::types::ChannelType::Private);
+
+        let err = platform.set_channel_privacy("dm", true).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidArgument);
+
+        crate::simulation::remove_server("privacy.sim");
+    }
+
+    #[cfg(feature = "simulation")]
+    #[tokio::test]
+    async fn test_resolve_group_mentions() {
+        let server = crate::simulation::add_server("groups.sim");
+        server.set_route(
+            "GET",
+            "/groups",
+            200,
+            r#"[{"id":"g1","name":"developers","display_name":"Developers","source":"custom",
+                 "member_count":1,"allow_reference":true,"delete_at":0},
+                {"id":"g2","name":"ops","display_name":"Ops","source":"ldap",
+                 "allow_reference":false,"delete_at":0}]"#,
+        );
+        server.set_route(
+            "GET",
+            "/groups/g1/members",
+            200,
+            r#"{"members":[{"id":"u1","username":"alice","create_at":0,"update_at":0,"delete_at":0}],
+                "total_member_count":1}"#,
+        );
+
+        let platform = MattermostPlatform::new("http://groups.sim").unwrap();
+        let mentions = platform
+            .resolve_group_mentions("@developers and @ops, ping @alice")
+            .await
+            .unwrap();
+        assert_eq!(mentions.len(), 1);
+        assert_eq!(mentions[0].group.id, "g1");
+        assert_eq!(mentions[0].members[0].username, "alice");
+
+        crate::simulation::remove_server("groups.sim");
+    }
 }
@@ -0,0 +1,385 @@
+//! Polls backed by the Matterpoll plugin
+//!
+//! Mattermost has no native polls. The Matterpoll plugin adds a `/poll` slash
+//! command that posts a message with one interactive button per option plus
+//! admin buttons ("End Poll", ...). Votes update the post in place, and ending
+//! the poll replaces the buttons with a results table. Polls are read back from
+//! the post's message attachment, and voting/closing triggers the buttons
+//! through the post action API.
+
+use serde_json::Value;
+
+use crate::error::{Error, ErrorCode, Result};
+use crate::types::{Message, Poll, PollOption, PollSettings};
+
+use super::client::MattermostClient;
+
+/// Slash command registered by the Matterpoll plugin
+const POLL_COMMAND: &str = "/poll";
+
+/// Matterpoll buttons that are not answer options
+const ADMIN_ACTIONS: &[&str] = &[
+    "add option",
+    "delete poll",
+    "end poll",
+    "reset votes",
+    "reset my votes",
+];
+
+/// Build the Matterpoll slash command creating a poll
+///
+/// # Returns
+/// The command text, or an InvalidArgument error if the question or an option
+/// cannot be expressed (Matterpoll arguments are double-quoted and cannot
+/// contain quotes)
+pub(crate) fn poll_command(
+    question: &str,
+    options: &[String],
+    settings: &PollSettings,
+) -> Result<String> {
+    if question.trim().is_empty() {
+        return Err(Error::invalid_argument("Poll question cannot be empty"));
+    }
+
+    let mut command = POLL_COMMAND.to_string();
+    for arg in std::iter::once(question).chain(options.iter().map(|o| o.as_str())) {
+        if arg.contains('"') {
+            return Err(Error::invalid_argument(
+                "Poll questions and options cannot contain double quotes",
+            ));
+        }
+        if arg.trim().is_empty() {
+            return Err(Error::invalid_argument("Poll options cannot be empty"));
+        }
+        command.push_str(&format!(" \"{arg}\""));
+    }
+
+    if settings.anonymous {
+        command.push_str(" --anonymous");
+    }
+    if settings.show_progress {
+        command.push_str(" --progress");
+    }
+    if settings.allow_new_options {
+        command.push_str(" --public-add-option");
+    }
+    Ok(command)
+}
+
+/// Get the Matterpoll attachment of a message, if it is a poll
+fn poll_attachment(message: &Message) -> Option<&Value> {
+    let props = message.metadata.as_ref()?.get("props")?;
+    props.get("poll_id")?.as_str()?;
+    props.get("attachments")?.get(0)
+}
+
+/// Get the buttons of a poll attachment
+fn actions(attachment: &Value) -> impl Iterator<Item = &Value> {
+    attachment
+        .get("actions")
+        .and_then(|a| a.as_array())
+        .into_iter()
+        .flatten()
+}
+
+fn action_name(action: &Value) -> &str {
+    action.get("name").and_then(|n| n.as_str()).unwrap_or("")
+}
+
+fn is_admin_action(action: &Value) -> bool {
+    ADMIN_ACTIONS.contains(&action_name(action).to_lowercase().as_str())
+}
+
+/// Split a trailing vote count like "Pizza (3)" or "Pizza (3 votes)" off a label
+fn split_vote_count(label: &str) -> (&str, Option<u32>) {
+    let parsed = label
+        .strip_suffix(')')
+        .and_then(|rest| rest.rsplit_once(" ("))
+        .and_then(|(text, count)| {
+            let count = count.trim_end_matches(" votes").trim_end_matches(" vote");
+            count.parse().ok().map(|n| (text, Some(n)))
+        });
+    parsed.unwrap_or((label, None))
+}
+
+/// Read a poll from a message posted by the Matterpoll plugin
+///
+/// # Returns
+/// The poll, or None if the message is not a Matterpoll poll
+pub fn poll_from_message(message: &Message) -> Option<Poll> {
+    let attachment = poll_attachment(message)?;
+    let question = attachment
+        .get("title")
+        .and_then(|t| t.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let text = attachment
+        .get("text")
+        .and_then(|t| t.as_str())
+        .unwrap_or_default()
+        .to_lowercase();
+
+    let mut settings = PollSettings {
+        anonymous: text.contains("anonymous"),
+        show_progress: text.contains("progress"),
+        allow_new_options: text.contains("public-add-option"),
+    };
+
+    let buttons: Vec<&Value> = actions(attachment).collect();
+    let closed = buttons.is_empty();
+
+    let options: Vec<PollOption> = if closed {
+        // Results: one field per option, titled "Option (n votes)", listing voters
+        attachment
+            .get("fields")
+            .and_then(|f| f.as_array())
+            .into_iter()
+            .flatten()
+            .map(|field| {
+                let title = field.get("title").and_then(|t| t.as_str()).unwrap_or("");
+                let (option_text, votes) = split_vote_count(title);
+                let voters = field
+                    .get("value")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .split(',')
+                    .map(|v| v.trim().trim_start_matches('@'))
+                    .filter(|v| !v.is_empty())
+                    .map(|v| v.to_string())
+                    .collect();
+                PollOption {
+                    text: option_text.to_string(),
+                    votes,
+                    voters,
+                }
+            })
+            .collect()
+    } else {
+        settings.allow_new_options |= buttons
+            .iter()
+            .any(|a| action_name(a).eq_ignore_ascii_case("add option"));
+        buttons
+            .iter()
+            .filter(|a| !is_admin_action(a))
+            .map(|action| {
+                let (option_text, votes) = split_vote_count(action_name(action));
+                PollOption {
+                    text: option_text.to_string(),
+                    votes,
+                    voters: Vec::new(),
+                }
+            })
+            .collect()
+    };
+    settings.show_progress |= !closed && options.iter().any(|o| o.votes.is_some());
+
+    Some(Poll {
+        id: message.id.clone(),
+        channel_id: message.channel_id.clone(),
+        question,
+        options,
+        settings,
+        closed,
+    })
+}
+
+/// Find the ID of the button voting for an option
+fn vote_action_id(message: &Message, option_index: usize) -> Result<String> {
+    let attachment =
+        poll_attachment(message).ok_or_else(|| Error::invalid_argument("Message is not a poll"))?;
+    let mut buttons = actions(attachment).peekable();
+    if buttons.peek().is_none() {
+        return Err(Error::new(ErrorCode::InvalidState, "Poll has ended"));
+    }
+    buttons
+        .filter(|a| !is_admin_action(a))
+        .nth(option_index)
+        .and_then(|a| a.get("id")?.as_str())
+        .map(|id| id.to_string())
+        .ok_or_else(|| Error::invalid_argument(format!("Poll has no option {option_index}")))
+}
+
+/// Find the ID of the button ending a poll
+fn end_action_id(message: &Message) -> Result<String> {
+    let attachment =
+        poll_attachment(message).ok_or_else(|| Error::invalid_argument("Message is not a poll"))?;
+    let mut buttons = actions(attachment).peekable();
+    if buttons.peek().is_none() {
+        return Err(Error::new(ErrorCode::InvalidState, "Poll has ended"));
+    }
+    buttons
+        .find(|a| action_name(a).eq_ignore_ascii_case("end poll"))
+        .and_then(|a| a.get("id")?.as_str())
+        .map(|id| id.to_string())
+        .ok_or_else(|| Error::new(ErrorCode::PermissionDenied, "Poll cannot be ended"))
+}
+
+impl MattermostClient {
+    /// Trigger an interactive button of a post
+    ///
+    /// # Arguments
+    /// * `post_id` - The post the button belongs to
+    /// * `action_id` - The ID of the button
+    ///
+    /// # Returns
+    /// A Result indicating success or failure
+    pub async fn do_post_action(&self, post_id: &str, action_id: &str) -> Result<()> {
+        let endpoint = format!("/posts/{post_id}/actions/{action_id}");
+        let response = self.post(&endpoint, &serde_json::json!({})).await?;
+        let _: Value = self.handle_response(response).await?;
+        Ok(())
+    }
+
+    /// Get a poll by the ID of its post
+    pub async fn get_poll(&self, post_id: &str) -> Result<Poll> {
+        let message: Message = self.get_post(post_id).await?.into();
+        poll_from_message(&message)
+            .ok_or_else(|| Error::invalid_argument(format!("Post {post_id} is not a poll")))
+    }
+
+    /// Create a poll with the Matterpoll plugin
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel to post the poll in
+    /// * `question` - The poll question
+    /// * `options` - The answer options (Matterpoll defaults to Yes/No if empty)
+    /// * `settings` - Poll settings
+    ///
+    /// # Returns
+    /// A Result containing the created poll or an Error
+    ///
+    /// # Notes
+    /// The plugin posts the poll itself, so the new post is looked up among the
+    /// latest posts of the channel once the command returns.
+    pub async fn create_poll(
+        &self,
+        channel_id: &str,
+        question: &str,
+        options: &[String],
+        settings: &PollSettings,
+    ) -> Result<Poll> {
+        let command = poll_command(question, options, settings)?;
+        self.execute_command(channel_id, &command).await?;
+
+        let post_list = self.get_latest_posts(channel_id, 20).await?;
+        post_list
+            .order
+            .iter()
+            .filter_map(|id| post_list.posts.get(id))
+            .filter_map(|post| poll_from_message(&post.clone().into()))
+            .find(|poll| poll.question == question && !poll.closed)
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorCode::Unsupported,
+                    "Poll was not created; is the Matterpoll plugin installed?",
+                )
+            })
+    }
+
+    /// Vote for an option of a Matterpoll poll
+    ///
+    /// # Arguments
+    /// * `post_id` - The ID of the poll post
+    /// * `option_index` - Position of the option in `Poll::options`
+    pub async fn vote_poll(&self, post_id: &str, option_index: usize) -> Result<()> {
+        let message: Message = self.get_post(post_id).await?.into();
+        let action_id = vote_action_id(&message, option_index)?;
+        self.do_post_action(post_id, &action_id).await
+    }
+
+    /// End a Matterpoll poll
+    ///
+    /// # Returns
+    /// A Result containing the closed poll with its results, or an Error
+    pub async fn close_poll(&self, post_id: &str) -> Result<Poll> {
+        let message: Message = self.get_post(post_id).await?.into();
+        let action_id = end_action_id(&message)?;
+        self.do_post_action(post_id, &action_id).await?;
+        self.get_poll(post_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poll_message(attachment: Value) -> Message {
+        Message::new("post1", "", "bot", "ch1").with_metadata(serde_json::json!({
+            "props": {
+                "poll_id": "poll1",
+                "attachments": [attachment],
+            }
+        }))
+    }
+
+    #[test]
+    fn test_poll_command() {
+        let settings = PollSettings {
+            anonymous: true,
+            show_progress: true,
+            allow_new_options: false,
+        };
+        assert_eq!(
+            poll_command(
+                "Lunch?",
+                &["Pizza".to_string(), "Sushi".to_string()],
+                &settings
+            )
+            .unwrap(),
+            r#"/poll "Lunch?" "Pizza" "Sushi" --anonymous --progress"#
+        );
+        assert!(poll_command("", &[], &settings).is_err());
+        assert!(poll_command("Say \"hi\"?", &[], &settings).is_err());
+    }
+
+    #[test]
+    fn test_open_poll() {
+        let message = poll_message(serde_json::json!({
+            "title": "Lunch?",
+            "text": "Total votes: 3",
+            "actions": [
+                {"id": "a1", "name": "Pizza (2)"},
+                {"id": "a2", "name": "Sushi (1)"},
+                {"id": "a3", "name": "Add Option"},
+                {"id": "a4", "name": "End Poll"},
+            ],
+        }));
+
+        let poll = poll_from_message(&message).unwrap();
+        assert_eq!(poll.question, "Lunch?");
+        assert!(!poll.closed);
+        assert_eq!(poll.options.len(), 2);
+        assert_eq!(poll.options[0].text, "Pizza");
+        assert_eq!(poll.total_votes(), Some(3));
+        assert!(poll.settings.show_progress);
+        assert!(poll.settings.allow_new_options);
+
+        assert_eq!(vote_action_id(&message, 1).unwrap(), "a2");
+        assert!(vote_action_id(&message, 2).is_err());
+        assert_eq!(end_action_id(&message).unwrap(), "a4");
+    }
+
+    #[test]
+    fn test_closed_poll() {
+        let message = poll_message(serde_json::json!({
+            "title": "Lunch?",
+            "text": "This poll has ended. The results are:",
+            "fields": [
+                {"title": "Pizza (2 votes)", "value": "@alice, @bob", "short": true},
+                {"title": "Sushi (0 votes)", "value": "", "short": true},
+            ],
+        }));
+
+        let poll = poll_from_message(&message).unwrap();
+        assert!(poll.closed);
+        assert_eq!(poll.options[0].voters, vec!["alice", "bob"]);
+        assert_eq!(poll.options[1].votes, Some(0));
+        assert!(vote_action_id(&message, 0).is_err());
+    }
+
+    #[test]
+    fn test_not_a_poll() {
+        assert!(poll_from_message(&Message::new("p", "hi", "u", "c")).is_none());
+        assert_eq!(split_vote_count("Plan (B)"), ("Plan (B)", None));
+    }
+}
@@ -1,9 +1,103 @@
+use std::collections::HashMap;
+use std::future::Future;
+
 use crate::error::Result;
+use crate::types::{MessagePriority, SendOptions};
 
 use super::client::MattermostClient;
 use super::types::{CreatePostRequest, MattermostPost, PostList};
 
+/// Page size used when locating posts by date
+const DATE_SEARCH_PAGE_SIZE: u32 = 200;
+
+/// Find the first page whose oldest post was created at or before `target`
+///
+/// Pages are numbered from the newest posts, so the oldest `create_at` of a page
+/// decreases as the page number grows. `oldest_on_page` returns the oldest
+/// `create_at` of a page, or None if the page is empty (past the end of the
+/// channel). An exponential probe bounds the search, then a binary search finds
+/// the page, so only O(log n) pages are fetched.
+async fn find_page_at<F, Fut>(mut oldest_on_page: F, target: i64) -> Result<u32>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<Option<i64>>>,
+{
+    let reaches_target = |oldest: Option<i64>| oldest.map(|t| t <= target).unwrap_or(true);
+
+    if reaches_target(oldest_on_page(0).await?) {
+        return Ok(0);
+    }
+
+    // Invariant: page `lo` does not reach the target, page `hi` does
+    let mut lo = 0u32;
+    let mut hi = 1u32;
+    while !reaches_target(oldest_on_page(hi).await?) {
+        lo = hi;
+        hi = hi.saturating_mul(2);
+    }
+
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if reaches_target(oldest_on_page(mid).await?) {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+
+    Ok(hi)
+}
+
+/// Get the posts on a page of a PostList
+///
+/// `posts` also holds the roots and parents of threads replied to on the page,
+/// which may be much older; only the posts listed in `order` are on the page.
+fn posts_on_page(list: &PostList) -> impl Iterator<Item = &MattermostPost> {
+    list.order.iter().filter_map(|id| list.posts.get(id))
+}
+
+/// Get the oldest `create_at` of the posts on a page, or None if it is empty
+fn oldest_on_page(list: &PostList) -> Option<i64> {
+    posts_on_page(list).map(|p| p.create_at).min()
+}
+
+/// Get the posts on a page of a PostList ordered from newest to oldest
+fn posts_newest_first(list: &PostList) -> Vec<&MattermostPost> {
+    let mut posts: Vec<&MattermostPost> = posts_on_page(list).collect();
+    posts.sort_by_key(|p| std::cmp::Reverse(p.create_at));
+    posts
+}
+
+/// Move the posts on a page of a PostList out as (ID, post) pairs
+fn take_posts_on_page(mut list: PostList) -> impl Iterator<Item = (String, MattermostPost)> {
+    list.order
+        .into_iter()
+        .filter_map(move |id| list.posts.remove(&id).map(|post| (id, post)))
+}
+
 impl MattermostClient {
+    /// Create a post, tagging it with a pending post ID
+    ///
+    /// The ID is echoed back in the post's `posted` WebSocket event, which lets
+    /// the platform recognize the echo of a post sent by this client.
+    async fn create_post(&self, request: CreatePostRequest) -> Result<MattermostPost> {
+        let pending_post_id = self.issue_pending_post_id().await;
+        let request = match &pending_post_id {
+            Some(id) => request.with_pending_post_id(id.clone()),
+            None => request,
+        };
+
+        let result = match self.post("/posts", &request).await {
+            Ok(response) => self.handle_response(response).await,
+            Err(e) => Err(e),
+        };
+        if let (Err(_), Some(id)) = (&result, &pending_post_id) {
+            // No echo will arrive for a post that was not created
+            self.take_pending_post(id).await;
+        }
+        result
+    }
+
     /// Send a message (post) to a channel
     ///
     /// # Arguments
@@ -13,10 +107,136 @@ impl MattermostClient {
     /// # Returns
     /// A Result containing the created post or an Error
     pub async fn send_message(&self, channel_id: &str, message: &str) -> Result<MattermostPost> {
+        self.server_limits().await.check_message(message)?;
+
         let request = CreatePostRequest::new(channel_id.to_string(), message.to_string());
 
-        let response = self.post("/posts", &request).await?;
-        self.handle_response(response).await
+        self.create_post(request).await
+    }
+
+    /// Send a message with an overridden username and/or profile picture
+    ///
+    /// # Arguments
+    /// * `channel_id` - The ID of the channel to send the message to
+    /// * `message` - The message text to send
+    /// * `override_username` - Optional username to display instead of the sender's
+    /// * `override_icon_url` - Optional URL of an image to display as the profile picture
+    ///
+    /// # Returns
+    /// A Result containing the created post or an Error
+    ///
+    /// # Notes
+    /// Overrides are only honored when the token belongs to a bot or integration and
+    /// the server has `EnablePostUsernameOverride` / `EnablePostIconOverride` enabled.
+    /// Otherwise the server ignores them and the post shows the real sender.
+    pub async fn send_message_with_overrides(
+        &self,
+        channel_id: &str,
+        message: &str,
+        override_username: Option<&str>,
+        override_icon_url: Option<&str>,
+    ) -> Result<MattermostPost> {
+        self.server_limits().await.check_message(message)?;
+
+        let request = CreatePostRequest::new(channel_id.to_string(), message.to_string())
+            .with_overrides(
+                override_username.map(|s| s.to_string()),
+                override_icon_url.map(|s| s.to_string()),
+            );
+
+        self.create_post(request).await
+    }
+
+    /// Send a message with custom post properties
+    ///
+    /// # Arguments
+    /// * `channel_id` - The ID of the channel to send the message to
+    /// * `message` - The message text to send
+    /// * `props` - Custom properties stored in the post's `props`
+    ///
+    /// # Returns
+    /// A Result containing the created post or an Error
+    pub async fn send_message_with_props(
+        &self,
+        channel_id: &str,
+        message: &str,
+        props: HashMap<String, serde_json::Value>,
+    ) -> Result<MattermostPost> {
+        self.server_limits().await.check_message(message)?;
+
+        let request =
+            CreatePostRequest::new(channel_id.to_string(), message.to_string()).with_props(props);
+
+        self.create_post(request).await
+    }
+
+    /// Send a message with uploaded files attached
+    ///
+    /// # Arguments
+    /// * `channel_id` - The ID of the channel to send the message to
+    /// * `message` - The message text to send (may be empty)
+    /// * `file_ids` - IDs of files uploaded to the channel
+    /// * `props` - Custom properties stored in the post's `props`
+    ///
+    /// # Returns
+    /// A Result containing the created post or an Error
+    pub async fn send_message_with_files(
+        &self,
+        channel_id: &str,
+        message: &str,
+        file_ids: Vec<String>,
+        props: HashMap<String, serde_json::Value>,
+    ) -> Result<MattermostPost> {
+        self.server_limits().await.check_message(message)?;
+
+        let request = CreatePostRequest::new(channel_id.to_string(), message.to_string())
+            .with_files(file_ids)
+            .with_props(props);
+
+        self.create_post(request).await
+    }
+
+    /// Send a message with options
+    ///
+    /// # Arguments
+    /// * `channel_id` - The ID of the channel to send the message to
+    /// * `message` - The message text to send (may be empty if files are attached)
+    /// * `options` - Thread, props, files and priority of the post
+    ///
+    /// # Returns
+    /// A Result containing the created post or an Error
+    ///
+    /// # Notes
+    /// Priorities need Mattermost 9.0 or later (Professional); older servers
+    /// ignore them. A scheduled time is not handled here.
+    pub async fn send_message_with_options(
+        &self,
+        channel_id: &str,
+        message: &str,
+        options: &SendOptions,
+    ) -> Result<MattermostPost> {
+        self.server_limits().await.check_message(message)?;
+
+        let mut request = CreatePostRequest::new(channel_id.to_string(), message.to_string());
+        if let Some(root_id) = &options.root_id {
+            request = request.with_root_id(root_id.clone());
+        }
+        if !options.file_ids.is_empty() {
+            request = request.with_files(options.file_ids.clone());
+        }
+        if !options.props.is_empty() {
+            request = request.with_props(options.props.clone());
+        }
+        let priority = match options.priority {
+            MessagePriority::Standard => "",
+            MessagePriority::Important => "important",
+            MessagePriority::Urgent => "urgent",
+        };
+        if !priority.is_empty() || options.requested_ack {
+            request = request.with_priority(priority, options.requested_ack);
+        }
+
+        self.create_post(request).await
     }
 
     /// Send a message as a reply to another post
@@ -34,11 +254,32 @@ impl MattermostClient {
         message: &str,
         root_id: &str,
     ) -> Result<MattermostPost> {
+        self.server_limits().await.check_message(message)?;
+
         let request = CreatePostRequest::new(channel_id.to_string(), message.to_string())
             .with_root_id(root_id.to_string());
 
-        let response = self.post("/posts", &request).await?;
-        self.handle_response(response).await
+        self.create_post(request).await
+    }
+
+    /// Set a server-side reminder for a post
+    ///
+    /// # Arguments
+    /// * `post_id` - The ID of the post
+    /// * `target_time` - When to remind the user (seconds since epoch)
+    ///
+    /// # Returns
+    /// A Result indicating success or failure
+    ///
+    /// # Notes
+    /// The server sends the reminder as a system message. Requires server 7.2+.
+    pub async fn set_post_reminder(&self, post_id: &str, target_time: i64) -> Result<()> {
+        let user_id = self.current_user_id().await?;
+        let endpoint = format!("/users/{user_id}/posts/{post_id}/reminder");
+        let body = serde_json::json!({ "target_time": target_time });
+        let response = self.post(&endpoint, &body).await?;
+        let _: serde_json::Value = self.handle_response(response).await?;
+        Ok(())
     }
 
     /// Get a specific post by ID
@@ -74,6 +315,31 @@ impl MattermostClient {
         self.handle_response(response).await
     }
 
+    /// Get the posts of a channel created, edited or deleted since a point in time
+    ///
+    /// # Arguments
+    /// * `channel_id` - The ID of the channel
+    /// * `since` - The point in time (milliseconds since epoch)
+    ///
+    /// # Returns
+    /// A Result containing the posts, oldest first, or an Error
+    ///
+    /// # Notes
+    /// Deleted posts are included with `delete_at` set.
+    pub async fn get_posts_since(
+        &self,
+        channel_id: &str,
+        since: i64,
+    ) -> Result<Vec<MattermostPost>> {
+        let endpoint = format!("/channels/{channel_id}/posts?since={since}");
+        let response = self.get(&endpoint).await?;
+        let post_list: PostList = self.handle_response(response).await?;
+
+        let mut posts: Vec<MattermostPost> = post_list.posts.into_values().collect();
+        posts.sort_by(|a, b| (a.create_at, &a.id).cmp(&(b.create_at, &b.id)));
+        Ok(posts)
+    }
+
     /// Get the latest posts for a channel
     ///
     /// # Arguments
@@ -95,6 +361,8 @@ impl MattermostClient {
     /// # Returns
     /// A Result containing the updated post or an Error
     pub async fn update_post(&self, post_id: &str, message: &str) -> Result<MattermostPost> {
+        self.server_limits().await.check_message(message)?;
+
         let body = serde_json::json!({
             "id": post_id,
             "message": message,
@@ -184,12 +452,189 @@ impl MattermostClient {
         let response = self.get(&endpoint).await?;
         self.handle_response(response).await
     }
+
+    /// Get the posts of a channel around a point in time ("jump to date")
+    ///
+    /// Locates the newest post created at or before `timestamp` with a binary
+    /// search over the channel's pages, then returns posts before and after it.
+    /// If the channel has no posts that old, the oldest posts are returned.
+    ///
+    /// # Arguments
+    /// * `channel_id` - The ID of the channel
+    /// * `timestamp` - The point in time (milliseconds since epoch)
+    /// * `limit` - Number of posts to retrieve
+    ///
+    /// # Returns
+    /// A Result containing the posts ordered oldest first, or an Error
+    pub async fn get_posts_around_time(
+        &self,
+        channel_id: &str,
+        timestamp: i64,
+        limit: u32,
+    ) -> Result<Vec<MattermostPost>> {
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let page = find_page_at(
+            |page| async move {
+                let list = self
+                    .get_posts_for_channel(channel_id, page, DATE_SEARCH_PAGE_SIZE)
+                    .await?;
+                Ok(oldest_on_page(&list))
+            },
+            timestamp,
+        )
+        .await?;
+
+        let list = self
+            .get_posts_for_channel(channel_id, page, DATE_SEARCH_PAGE_SIZE)
+            .await?;
+        let pivot = posts_newest_first(&list)
+            .into_iter()
+            .find(|p| p.create_at <= timestamp)
+            .cloned();
+
+        let (pivot, before_count) = match pivot {
+            Some(pivot) => (pivot, limit / 2),
+            None if page == 0 => return Ok(Vec::new()),
+            None => {
+                // Nothing that old: start from the oldest post of the channel
+                let newer = self
+                    .get_posts_for_channel(channel_id, page - 1, DATE_SEARCH_PAGE_SIZE)
+                    .await?;
+                match posts_newest_first(&newer).last() {
+                    Some(oldest) => ((*oldest).clone(), 0),
+                    None => return Ok(Vec::new()),
+                }
+            }
+        };
+
+        let mut posts: HashMap<String, MattermostPost> = HashMap::new();
+        if before_count > 0 {
+            let before = self
+                .get_posts_before(channel_id, &pivot.id, before_count)
+                .await?;
+            posts.extend(take_posts_on_page(before));
+        }
+        let after_count = limit - before_count - 1;
+        if after_count > 0 {
+            let after = self
+                .get_posts_after(channel_id, &pivot.id, after_count)
+                .await?;
+            posts.extend(take_posts_on_page(after));
+        }
+        posts.insert(pivot.id.clone(), pivot);
+
+        let mut posts: Vec<MattermostPost> = posts.into_values().collect();
+        posts.sort_by_key(|p| p.create_at);
+        Ok(posts)
+    }
+
+    /// Get the creation times of all posts in a channel since a point in time
+    ///
+    /// Pages through the channel from the newest posts until a page reaches
+    /// `since`. Only posts listed in each page's order are counted, not the
+    /// thread roots that the server includes alongside them.
+    ///
+    /// # Arguments
+    /// * `channel_id` - The ID of the channel
+    /// * `since` - The point in time (milliseconds since epoch)
+    ///
+    /// # Returns
+    /// A Result containing the creation times (newest first), or an Error
+    pub async fn get_post_times_since(&self, channel_id: &str, since: i64) -> Result<Vec<i64>> {
+        let mut times = Vec::new();
+        let mut page = 0;
+
+        loop {
+            let list = self
+                .get_posts_for_channel(channel_id, page, DATE_SEARCH_PAGE_SIZE)
+                .await?;
+            let page_times: Vec<i64> = list
+                .order
+                .iter()
+                .filter_map(|id| list.posts.get(id))
+                .map(|post| post.create_at)
+                .collect();
+
+            let reached_since = page_times.iter().any(|&t| t < since);
+            times.extend(page_times.iter().copied().filter(|&t| t >= since));
+
+            if reached_since || page_times.len() < DATE_SEARCH_PAGE_SIZE as usize {
+                return Ok(times);
+            }
+            page += 1;
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Oldest create_at of each page for a channel with one post per second
+    fn fake_pages(total_posts: i64, per_page: i64, page: u32) -> Option<i64> {
+        let newest_index = page as i64 * per_page;
+        if newest_index >= total_posts {
+            return None;
+        }
+        // Post i (0 = newest) was created at (total_posts - 1 - i) * 1000
+        let oldest_index = (newest_index + per_page - 1).min(total_posts - 1);
+        Some((total_posts - 1 - oldest_index) * 1000)
+    }
+
+    #[tokio::test]
+    async fn test_find_page_at() {
+        let fetches = std::cell::Cell::new(0);
+        let fetch = |page: u32| {
+            fetches.set(fetches.get() + 1);
+            async move { Ok(fake_pages(10_000, 100, page)) }
+        };
+
+        // Newest posts: page 0
+        assert_eq!(find_page_at(fetch, 9_999_000).await.unwrap(), 0);
+
+        // Post 5_000 (created at 5_000_000) is the 4_999th newest: page 49
+        fetches.set(0);
+        assert_eq!(find_page_at(fetch, 5_000_000).await.unwrap(), 49);
+        assert!(fetches.get() <= 16, "fetched {} pages", fetches.get());
+
+        // Before the first post: the first empty page
+        assert_eq!(find_page_at(fetch, -1).await.unwrap(), 100);
+    }
+
+    fn post(id: &str, create_at: i64) -> serde_json::Value {
+        serde_json::json!({
+            "id": id, "create_at": create_at, "update_at": create_at, "delete_at": 0,
+            "edit_at": 0, "user_id": "user", "channel_id": "channel", "message": id
+        })
+    }
+
+    #[test]
+    fn test_page_ignores_thread_roots_outside_order() {
+        // A reply on the page brings along its root from long before
+        let list: PostList = serde_json::from_value(serde_json::json!({
+            "order": ["reply", "recent"],
+            "posts": {
+                "reply": post("reply", 5_000),
+                "recent": post("recent", 4_000),
+                "root": post("root", 10),
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(oldest_on_page(&list), Some(4_000));
+        let ids: Vec<_> = posts_newest_first(&list)
+            .iter()
+            .map(|p| p.id.as_str())
+            .collect();
+        assert_eq!(ids, ["reply", "recent"]);
+        let mut taken: Vec<_> = take_posts_on_page(list).map(|(id, _)| id).collect();
+        taken.sort();
+        assert_eq!(taken, ["recent", "reply"]);
+    }
+
     #[test]
     fn test_post_endpoints() {
         let client = MattermostClient::new("https://mattermost.example.com").unwrap();
@@ -208,4 +653,26 @@ mod tests {
             "https://mattermost.example.com/api/v4/channels/channel123/posts?page=0&per_page=60"
         );
     }
+
+    #[test]
+    fn test_create_post_request_overrides() {
+        let request = CreatePostRequest::new("channel123".to_string(), "hello".to_string())
+            .with_overrides(
+                Some("remote-alice".to_string()),
+                Some("https://example.com/alice.png".to_string()),
+            );
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["props"]["override_username"], "remote-alice");
+        assert_eq!(
+            json["props"]["override_icon_url"],
+            "https://example.com/alice.png"
+        );
+
+        // No overrides means no props at all
+        let request = CreatePostRequest::new("channel123".to_string(), "hello".to_string())
+            .with_overrides(None, None);
+        let json = serde_json::to_value(&request).unwrap();
+        assert!(json.get("props").is_none());
+    }
 }
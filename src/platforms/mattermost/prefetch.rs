@@ -0,0 +1,99 @@
+//! Profile prefetching for visible channels
+//!
+//! Rendering a channel needs the name, avatar and custom status of every
+//! message author. Looking these up lazily per message sends a burst of
+//! requests whenever the user scrolls. Instead, the UI reports which channels
+//! are visible, and the authors of their recent messages are fetched in one
+//! batch before their profile images are downloaded. Custom statuses are part
+//! of the user profile, so they are warmed along with it.
+
+use std::collections::HashSet;
+
+use crate::error::Result;
+
+use super::client::MattermostClient;
+use super::types::PostList;
+
+/// Number of recent posts per channel whose authors are prefetched
+const PREFETCH_POSTS_PER_CHANNEL: u32 = 60;
+
+impl MattermostClient {
+    /// Warm the user and profile image caches for the given channels
+    ///
+    /// # Arguments
+    /// * `channel_ids` - The channels currently visible in the UI
+    ///
+    /// # Returns
+    /// The IDs of the authors, or an error if they could not be fetched.
+    /// Channels whose posts cannot be loaded and missing profile images are
+    /// skipped.
+    pub async fn prefetch_channel_profiles(&self, channel_ids: &[String]) -> Result<Vec<String>> {
+        let mut seen = HashSet::new();
+        let mut user_ids = Vec::new();
+        for channel_id in channel_ids {
+            let Ok(posts) = self
+                .get_latest_posts(channel_id, PREFETCH_POSTS_PER_CHANNEL)
+                .await
+            else {
+                continue;
+            };
+            for user_id in post_authors(&posts) {
+                if seen.insert(user_id.clone()) {
+                    user_ids.push(user_id);
+                }
+            }
+        }
+
+        if user_ids.is_empty() {
+            return Ok(user_ids);
+        }
+
+        self.get_users_by_ids_cached(&user_ids).await?;
+
+        // One at a time, so prefetching does not take all request slots from
+        // requests the user is waiting for
+        for user_id in &user_ids {
+            let _ = self.get_user_image_cached(user_id).await;
+        }
+
+        Ok(user_ids)
+    }
+}
+
+/// Distinct authors of the posts in a post list, newest post first
+fn post_authors(posts: &PostList) -> Vec<String> {
+    let mut seen = HashSet::new();
+    posts
+        .order
+        .iter()
+        .filter_map(|id| posts.posts.get(id))
+        .map(|post| post.user_id.clone())
+        .filter(|user_id| !user_id.is_empty() && seen.insert(user_id.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_post_authors() {
+        let post = |id: &str, user_id: &str| {
+            serde_json::json!({
+                "id": id, "create_at": 0, "update_at": 0, "delete_at": 0, "edit_at": 0,
+                "user_id": user_id, "channel_id": "ch1", "message": "hi"
+            })
+        };
+        let posts: PostList = serde_json::from_value(serde_json::json!({
+            "order": ["p3", "p2", "p1"],
+            "posts": {
+                "p1": post("p1", "alice"),
+                "p2": post("p2", "bob"),
+                "p3": post("p3", "alice")
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(post_authors(&posts), vec!["alice", "bob"]);
+    }
+}
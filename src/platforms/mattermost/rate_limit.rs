@@ -0,0 +1,227 @@
+//! Rate-limit-aware scheduling of REST requests
+//!
+//! Mattermost servers with rate limiting report each client's budget in the
+//! `X-Ratelimit-Limit`, `X-Ratelimit-Remaining` and `X-Ratelimit-Reset`
+//! headers. The scheduler spends that budget as requests are sent; once it is
+//! used up, further requests queue until the window resets instead of failing
+//! with `ErrorCode::RateLimited`. A request the server still refuses with
+//! 429 Too Many Requests is queued again until the reset (or `Retry-After`),
+//! with exponential backoff, up to [`MAX_RETRIES`] times.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::types::RequestStats;
+
+/// Retries of a request refused with 429 Too Many Requests
+const MAX_RETRIES: u32 = 3;
+
+/// Backoff before the first retry when the server gives no reset time
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Longest wait before a retry
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// `X-Ratelimit-Reset` values from here on are epoch seconds, not a delay
+const EPOCH_THRESHOLD: u64 = 1_000_000_000;
+
+#[derive(Debug, Default)]
+struct SchedulerState {
+    limit: Option<u32>,
+    remaining: Option<u32>,
+    /// When the budget is replenished
+    reset_at: Option<Instant>,
+    queued: usize,
+    delayed: u64,
+    rate_limited: u64,
+    retried: u64,
+}
+
+/// Queues requests while the server's rate limit is used up
+#[derive(Debug, Default)]
+pub(crate) struct RateLimitScheduler {
+    inner: Mutex<SchedulerState>,
+}
+
+/// Counts a request as queued until dropped, even if its wait is cancelled
+struct Queued<'a>(&'a RateLimitScheduler);
+
+impl Drop for Queued<'_> {
+    fn drop(&mut self) {
+        self.0.inner.lock().unwrap().queued -= 1;
+    }
+}
+
+impl RateLimitScheduler {
+    /// Wait until the budget allows another request, and spend it
+    pub(crate) async fn acquire(&self) {
+        let mut queued = None;
+        loop {
+            let wait_until = {
+                let mut state = self.inner.lock().unwrap();
+                let now = Instant::now();
+                if state.reset_at.is_some_and(|reset_at| reset_at <= now) {
+                    // A new window, whose budget the next response reports
+                    state.remaining = state.limit;
+                    state.reset_at = None;
+                }
+                match (state.remaining, state.reset_at) {
+                    (Some(0), Some(reset_at)) => {
+                        if queued.is_none() {
+                            state.queued += 1;
+                            state.delayed += 1;
+                        }
+                        reset_at
+                    }
+                    (Some(remaining), _) => {
+                        state.remaining = Some(remaining.saturating_sub(1));
+                        return;
+                    }
+                    (None, _) => return,
+                }
+            };
+            queued.get_or_insert(Queued(self));
+            tokio::time::sleep_until(wait_until).await;
+        }
+    }
+
+    /// Update the budget from a response
+    ///
+    /// # Arguments
+    /// * `response` - The response
+    /// * `attempt` - Retries of the request so far
+    ///
+    /// # Returns
+    /// Whether to send the request again: it was refused with 429 Too Many
+    /// Requests and has retries left. The retry waits in [`acquire`](Self::acquire).
+    pub(crate) fn record(&self, response: &reqwest::Response, attempt: u32) -> bool {
+        let header = |name: &str| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse::<u64>().ok())
+        };
+        let now = Instant::now();
+        let reset_in = header("X-Ratelimit-Reset").map(|reset| {
+            if reset >= EPOCH_THRESHOLD {
+                let epoch = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                Duration::from_secs(reset.saturating_sub(epoch))
+            } else {
+                Duration::from_secs(reset)
+            }
+        });
+
+        let mut state = self.inner.lock().unwrap();
+        if let Some(limit) = header("X-Ratelimit-Limit") {
+            state.limit = Some(limit.min(u32::MAX as u64) as u32);
+        }
+        if let Some(remaining) = header("X-Ratelimit-Remaining") {
+            state.remaining = Some(remaining.min(u32::MAX as u64) as u32);
+            state.reset_at = reset_in.map(|reset_in| now + reset_in);
+        }
+
+        if response.status() != reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return false;
+        }
+        state.rate_limited += 1;
+        let backoff = INITIAL_BACKOFF
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(MAX_BACKOFF);
+        let wait = header("Retry-After")
+            .map(Duration::from_secs)
+            .or(reset_in)
+            .unwrap_or_default()
+            .max(backoff)
+            .min(MAX_BACKOFF);
+        state.remaining = Some(0);
+        state.reset_at = Some(now + wait);
+        if attempt >= MAX_RETRIES {
+            return false;
+        }
+        state.retried += 1;
+        true
+    }
+
+    /// Statistics of the queue and the budget
+    pub(crate) fn stats(&self) -> RequestStats {
+        let state = self.inner.lock().unwrap();
+        RequestStats {
+            queued: state.queued,
+            delayed: state.delayed,
+            rate_limited: state.rate_limited,
+            retried: state.retried,
+            limit: state.limit,
+            remaining: state.remaining,
+            reset_in_ms: state.reset_at.map(|reset_at| {
+                reset_at
+                    .saturating_duration_since(Instant::now())
+                    .as_millis() as u64
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(status: u16, headers: &[(&str, &str)]) -> reqwest::Response {
+        let mut builder = http::Response::builder().status(status);
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        reqwest::Response::from(builder.body("").unwrap())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_queues_until_reset() {
+        let scheduler = RateLimitScheduler::default();
+        scheduler.acquire().await;
+        assert!(!scheduler.record(
+            &response(
+                200,
+                &[
+                    ("X-Ratelimit-Limit", "10"),
+                    ("X-Ratelimit-Remaining", "1"),
+                    ("X-Ratelimit-Reset", "2"),
+                ],
+            ),
+            0,
+        ));
+
+        let start = Instant::now();
+        scheduler.acquire().await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+        assert_eq!(scheduler.stats().remaining, Some(0));
+
+        // The budget is spent: the next request waits for the reset
+        scheduler.acquire().await;
+        assert!(start.elapsed() >= Duration::from_secs(2));
+        let stats = scheduler.stats();
+        assert_eq!((stats.queued, stats.delayed), (0, 1));
+        assert_eq!(stats.remaining, Some(9));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_too_many_requests_retried_with_backoff() {
+        let scheduler = RateLimitScheduler::default();
+        let refused = response(429, &[("Retry-After", "3")]);
+
+        for attempt in 0..MAX_RETRIES {
+            assert!(scheduler.record(&refused, attempt));
+        }
+        assert!(!scheduler.record(&refused, MAX_RETRIES));
+
+        let start = Instant::now();
+        scheduler.acquire().await;
+        assert!(start.elapsed() >= Duration::from_secs(3));
+        let stats = scheduler.stats();
+        assert_eq!((stats.rate_limited, stats.retried), (4, 3));
+    }
+}
@@ -66,6 +66,8 @@ impl MattermostClient {
     pub async fn get_reactions(&self, post_id: &str) -> Result<Vec<Reaction>> {
         let endpoint = format!("/posts/{post_id}/reactions");
         let response = self.get(&endpoint).await?;
-        self.handle_response(response).await
+        // Posts without reactions may come back as null
+        let reactions: Option<Vec<Reaction>> = self.handle_response(response).await?;
+        Ok(reactions.unwrap_or_default())
     }
 }
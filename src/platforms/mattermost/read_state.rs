@@ -0,0 +1,330 @@
+//! Local channel read-state tracking
+//!
+//! Clients tend to report channel views in bursts (switching channels quickly,
+//! focus/blur events, re-rendering the same channel). This module debounces
+//! those reports and keeps an optimistic view of the user's read state so unread
+//! counts drop to zero immediately, before the server confirms the view with a
+//! `channel_viewed` WebSocket event.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+use crate::types::ChannelUnread;
+
+/// Default window during which repeated views of the same channel are coalesced
+pub const DEFAULT_VIEW_DEBOUNCE: Duration = Duration::from_millis(1000);
+
+/// Outcome of recording a channel view
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ViewDecision {
+    /// The view should be reported to the server
+    Report {
+        /// The channel the user switched away from, if any
+        prev_channel_id: Option<String>,
+    },
+    /// The same channel was reported recently; no request is needed
+    Debounced,
+}
+
+#[derive(Debug, Default)]
+struct ViewState {
+    /// Channel the user is currently viewing (last reported view)
+    current_channel_id: Option<String>,
+    /// Channel the user was viewing before the last reported view, restored
+    /// if that report fails
+    replaced_channel_id: Option<String>,
+    /// Last channel reported to the server and when
+    last_reported: Option<(String, Instant)>,
+    /// Locally recorded view times (ms since epoch) awaiting server confirmation
+    optimistic_views: HashMap<String, i64>,
+}
+
+/// Tracks channel views for debouncing and optimistic unread state
+#[derive(Debug, Clone)]
+pub struct ChannelViewTracker {
+    state: Arc<RwLock<ViewState>>,
+    debounce: Duration,
+}
+
+impl Default for ChannelViewTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_VIEW_DEBOUNCE)
+    }
+}
+
+impl ChannelViewTracker {
+    /// Create a new tracker
+    ///
+    /// # Arguments
+    /// * `debounce` - Window during which repeated views of the same channel are coalesced
+    pub fn new(debounce: Duration) -> Self {
+        Self {
+            state: Arc::new(RwLock::new(ViewState::default())),
+            debounce,
+        }
+    }
+
+    /// Record that the user viewed a channel
+    ///
+    /// Optimistically marks the channel (and the previous channel, which the
+    /// server also marks as read) as viewed, and decides whether the view needs
+    /// to be reported.
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel being viewed
+    /// * `prev_channel_id` - The channel being switched away from. If None, the
+    ///   previously viewed channel tracked by this instance is used.
+    ///
+    /// # Returns
+    /// Whether to report the view, and with which previous channel
+    pub async fn record_view(
+        &self,
+        channel_id: &str,
+        prev_channel_id: Option<&str>,
+    ) -> ViewDecision {
+        let now = Instant::now();
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let mut state = self.state.write().await;
+
+        let prev_channel_id = prev_channel_id
+            .map(|s| s.to_string())
+            .or_else(|| state.current_channel_id.clone())
+            .filter(|prev| !prev.is_empty() && prev != channel_id);
+
+        state
+            .optimistic_views
+            .insert(channel_id.to_string(), now_ms);
+        if let Some(prev) = &prev_channel_id {
+            state.optimistic_views.insert(prev.clone(), now_ms);
+        }
+
+        if let Some((last_id, at)) = &state.last_reported {
+            if last_id == channel_id && now.duration_since(*at) < self.debounce {
+                return ViewDecision::Debounced;
+            }
+        }
+
+        state.replaced_channel_id = state.current_channel_id.replace(channel_id.to_string());
+        state.last_reported = Some((channel_id.to_string(), now));
+
+        ViewDecision::Report { prev_channel_id }
+    }
+
+    /// Record that a channel was marked read without the user switching to it
+    ///
+    /// Unlike `record_view`, this neither debounces nor changes the channel the
+    /// user is viewing.
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel marked read
+    pub async fn record_read(&self, channel_id: &str) {
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let mut state = self.state.write().await;
+        state
+            .optimistic_views
+            .insert(channel_id.to_string(), now_ms);
+    }
+
+    /// Roll back an optimistic view after the server rejected it
+    ///
+    /// The channel and the previous channel reported with it count as unread
+    /// again, and the channel viewed before is current again.
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel whose view report failed
+    /// * `prev_channel_id` - The previous channel reported with the view
+    pub async fn view_failed(&self, channel_id: &str, prev_channel_id: Option<&str>) {
+        let mut state = self.state.write().await;
+        state.optimistic_views.remove(channel_id);
+        if let Some(prev) = prev_channel_id {
+            state.optimistic_views.remove(prev);
+        }
+        if matches!(&state.last_reported, Some((id, _)) if id == channel_id) {
+            state.last_reported = None;
+        }
+        // Unless another view was recorded meanwhile
+        if state.current_channel_id.as_deref() == Some(channel_id) {
+            state.current_channel_id = state.replaced_channel_id.take();
+        }
+    }
+
+    /// Roll back an optimistic read after the server rejected it
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel whose read report failed
+    pub async fn read_failed(&self, channel_id: &str) {
+        let mut state = self.state.write().await;
+        state.optimistic_views.remove(channel_id);
+    }
+
+    /// Reconcile with a server-confirmed view (e.g. a `channel_viewed` event)
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel the server reports as viewed
+    pub async fn confirm_view(&self, channel_id: &str) {
+        let mut state = self.state.write().await;
+        state.optimistic_views.remove(channel_id);
+    }
+
+    /// Apply optimistic read state to unread information fetched from the server
+    ///
+    /// If the channel was viewed locally after the server's `last_viewed_at`,
+    /// the counts are cleared. Once the server catches up, the local entry is dropped.
+    ///
+    /// # Arguments
+    /// * `unread` - Unread information as reported by the server
+    ///
+    /// # Returns
+    /// The unread information adjusted for local views
+    pub async fn apply(&self, mut unread: ChannelUnread) -> ChannelUnread {
+        let local_viewed_at = {
+            let state = self.state.read().await;
+            state.optimistic_views.get(&unread.channel_id).copied()
+        };
+
+        match local_viewed_at {
+            Some(viewed_at) if viewed_at > unread.last_viewed_at => {
+                unread.msg_count = 0;
+                unread.mention_count = 0;
+                unread.last_viewed_at = viewed_at;
+            }
+            Some(_) => {
+                // Server already reflects the view
+                self.confirm_view(&unread.channel_id).await;
+            }
+            None => {}
+        }
+
+        unread
+    }
+
+    /// Forget all tracked state (e.g. on disconnect)
+    pub async fn reset(&self) {
+        let mut state = self.state.write().await;
+        *state = ViewState::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_repeated_view_is_debounced() {
+        let tracker = ChannelViewTracker::new(Duration::from_secs(60));
+
+        assert_eq!(
+            tracker.record_view("ch1", None).await,
+            ViewDecision::Report {
+                prev_channel_id: None
+            }
+        );
+        assert_eq!(
+            tracker.record_view("ch1", None).await,
+            ViewDecision::Debounced
+        );
+    }
+
+    #[tokio::test]
+    async fn test_previous_channel_is_tracked() {
+        let tracker = ChannelViewTracker::new(Duration::from_secs(60));
+
+        tracker.record_view("ch1", None).await;
+        assert_eq!(
+            tracker.record_view("ch2", None).await,
+            ViewDecision::Report {
+                prev_channel_id: Some("ch1".to_string())
+            }
+        );
+
+        // An explicit previous channel takes precedence
+        assert_eq!(
+            tracker.record_view("ch3", Some("other")).await,
+            ViewDecision::Report {
+                prev_channel_id: Some("other".to_string())
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_view_is_not_debounced() {
+        let tracker = ChannelViewTracker::new(Duration::from_secs(60));
+
+        tracker.record_view("ch1", None).await;
+        tracker.view_failed("ch1", None).await;
+        assert!(matches!(
+            tracker.record_view("ch1", None).await,
+            ViewDecision::Report { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_failed_view_is_rolled_back() {
+        let tracker = ChannelViewTracker::new(Duration::from_secs(60));
+        let server_unread = |channel_id| {
+            ChannelUnread::new(channel_id)
+                .with_counts(5, 2)
+                .with_last_viewed(1000)
+        };
+
+        tracker.record_view("ch1", None).await;
+        tracker.confirm_view("ch1").await;
+        let ViewDecision::Report { prev_channel_id } = tracker.record_view("ch2", None).await
+        else {
+            panic!("view was debounced");
+        };
+        assert_eq!(prev_channel_id.as_deref(), Some("ch1"));
+        tracker.view_failed("ch2", prev_channel_id.as_deref()).await;
+
+        // Both channels are unread again
+        assert_eq!(tracker.apply(server_unread("ch1")).await.msg_count, 5);
+        assert_eq!(tracker.apply(server_unread("ch2")).await.msg_count, 5);
+
+        // And the user is back in the previous channel
+        assert_eq!(
+            tracker.record_view("ch3", None).await,
+            ViewDecision::Report {
+                prev_channel_id: Some("ch1".to_string())
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_optimistic_unread_until_confirmed() {
+        let tracker = ChannelViewTracker::default();
+        let server_unread = ChannelUnread::new("ch1")
+            .with_counts(5, 2)
+            .with_last_viewed(1000);
+
+        tracker.record_view("ch1", None).await;
+        let unread = tracker.apply(server_unread.clone()).await;
+        assert_eq!(unread.msg_count, 0);
+        assert_eq!(unread.mention_count, 0);
+        assert!(unread.last_viewed_at > 1000);
+
+        tracker.confirm_view("ch1").await;
+        let unread = tracker.apply(server_unread).await;
+        assert_eq!(unread.msg_count, 5);
+        assert_eq!(unread.mention_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_marked_read_keeps_current_channel() {
+        let tracker = ChannelViewTracker::new(Duration::from_secs(60));
+        let server_unread = ChannelUnread::new("ch2").with_counts(3, 1);
+
+        tracker.record_view("ch1", None).await;
+        tracker.record_read("ch2").await;
+        assert_eq!(tracker.apply(server_unread).await.msg_count, 0);
+
+        // ch1 is still the channel being viewed
+        assert_eq!(
+            tracker.record_view("ch3", None).await,
+            ViewDecision::Report {
+                prev_channel_id: Some("ch1".to_string())
+            }
+        );
+    }
+}
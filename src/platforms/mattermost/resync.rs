@@ -0,0 +1,146 @@
+//! Delta sync after a WebSocket reconnect
+//!
+//! Events sent while the WebSocket was down are lost. After a reconnect, the
+//! posts of the channels with a message window that changed since the server
+//! was last heard from are fetched (`GET /channels/{id}/posts?since=`) and
+//! reported as the events that were missed: new posts as `MessagePosted`,
+//! edits as `MessageUpdated` and deletions as `MessageDeleted`. Channel
+//! membership changes are recovered from the system posts Mattermost adds for
+//! them, as `UserJoinedChannel` and `UserLeftChannel`.
+
+use std::collections::HashMap;
+
+use crate::platforms::platform_trait::PlatformEvent;
+use crate::types::Message;
+
+use super::types::MattermostPost;
+
+/// How far before the gap posts are fetched, to allow for clock differences
+/// between client and server; posts seen before are not reported again
+pub const RESYNC_MARGIN_MS: i64 = 30_000;
+
+/// The events a client missed for a channel's changed posts
+///
+/// # Arguments
+/// * `posts` - Posts changed since `since`, oldest first
+/// * `since` - Start of the gap (milliseconds since epoch)
+/// * `known` - Last update time of each post the client already has, by ID
+///
+/// # Returns
+/// The missed events, in the order the posts were created
+pub fn missed_events(
+    posts: Vec<MattermostPost>,
+    since: i64,
+    known: &HashMap<String, i64>,
+) -> Vec<PlatformEvent> {
+    let mut events = Vec::new();
+    for post in posts {
+        if post.delete_at > 0 {
+            if post.delete_at >= since {
+                events.push(PlatformEvent::MessageDeleted {
+                    message_id: post.id,
+                    channel_id: post.channel_id,
+                });
+            }
+            continue;
+        }
+
+        match known.get(&post.id) {
+            Some(&update_at) if update_at >= post.update_at => {}
+            Some(_) => events.push(PlatformEvent::MessageUpdated(post.into())),
+            None if post.create_at >= since => {
+                let membership = membership_event(&post);
+                events.push(PlatformEvent::MessagePosted(Message::from(post)));
+                events.extend(membership);
+            }
+            None => events.push(PlatformEvent::MessageUpdated(post.into())),
+        }
+    }
+    events
+}
+
+/// The membership change a system post records, if any
+fn membership_event(post: &MattermostPost) -> Option<PlatformEvent> {
+    let prop = |key: &str| {
+        post.props
+            .get(key)
+            .and_then(|value| value.as_str())
+            .map(str::to_string)
+    };
+    let (joined, user_id) = match post.post_type.as_str() {
+        "system_join_channel" => (true, post.user_id.clone()),
+        "system_add_to_channel" => (true, prop("addedUserId")?),
+        "system_leave_channel" => (false, post.user_id.clone()),
+        "system_remove_from_channel" => (false, prop("removedUserId")?),
+        _ => return None,
+    };
+    let channel_id = post.channel_id.clone();
+    Some(if joined {
+        PlatformEvent::UserJoinedChannel {
+            user_id,
+            channel_id,
+            user: None,
+            channel: None,
+        }
+    } else {
+        PlatformEvent::UserLeftChannel {
+            user_id,
+            channel_id,
+            user: None,
+            channel: None,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn post(id: &str, create_at: i64, update_at: i64, delete_at: i64) -> MattermostPost {
+        serde_json::from_value(serde_json::json!({
+            "id": id, "create_at": create_at, "update_at": update_at,
+            "delete_at": delete_at, "edit_at": 0, "user_id": "u1",
+            "channel_id": "c1", "message": id
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_missed_events() {
+        let mut joined = post("join", 120, 120, 0);
+        joined.post_type = "system_add_to_channel".to_string();
+        joined
+            .props
+            .insert("addedUserId".to_string(), serde_json::json!("u2"));
+        let posts = vec![
+            post("seen", 90, 95, 0),
+            post("edited", 50, 110, 0),
+            post("removed", 60, 115, 115),
+            post("new", 105, 105, 0),
+            joined,
+        ];
+        let known = HashMap::from([("seen".to_string(), 95), ("edited".to_string(), 50)]);
+
+        let events = missed_events(posts, 100, &known);
+        let kinds: Vec<String> = events
+            .iter()
+            .map(|event| match event {
+                PlatformEvent::MessagePosted(m) => format!("posted {}", m.id),
+                PlatformEvent::MessageUpdated(m) => format!("updated {}", m.id),
+                PlatformEvent::MessageDeleted { message_id, .. } => format!("deleted {message_id}"),
+                PlatformEvent::UserJoinedChannel { user_id, .. } => format!("joined {user_id}"),
+                other => format!("{other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "updated edited",
+                "deleted removed",
+                "posted new",
+                "posted join",
+                "joined u2"
+            ]
+        );
+    }
+}
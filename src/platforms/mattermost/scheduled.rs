@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use crate::error::{Error, Result};
+
+use super::client::MattermostClient;
+use super::types::MattermostScheduledPost;
+
+/// Turn the errors of servers without scheduled posts into Unsupported errors
+///
+/// Servers with scheduled posts turned off answer 501; servers older than 10.3
+/// have no scheduled post endpoints and answer 404.
+fn scheduled_posts_unavailable(e: Error) -> Error {
+    match e.http_status {
+        Some(404) | Some(501) => {
+            Error::unsupported("Scheduled posts are not available on this server")
+        }
+        _ => e,
+    }
+}
+
+impl MattermostClient {
+    /// Schedule a post
+    ///
+    /// # Arguments
+    /// * `scheduled_post` - The post with `scheduled_at` set; `user_id` is
+    ///   filled in with the current user
+    ///
+    /// # Returns
+    /// A Result containing the scheduled post or an Error (Unsupported if the
+    /// server has no scheduled posts)
+    pub async fn create_scheduled_post(
+        &self,
+        mut scheduled_post: MattermostScheduledPost,
+    ) -> Result<MattermostScheduledPost> {
+        scheduled_post.user_id = self.current_user_id().await?;
+        let response = self.post("/posts/schedule", &scheduled_post).await?;
+        self.handle_response(response)
+            .await
+            .map_err(scheduled_posts_unavailable)
+    }
+
+    /// Get the current user's scheduled posts in a team and in direct channels
+    ///
+    /// # Arguments
+    /// * `team_id` - The ID of the team
+    ///
+    /// # Returns
+    /// A Result containing the scheduled posts or an Error (Unsupported if the
+    /// server has no scheduled posts)
+    pub async fn get_scheduled_posts(&self, team_id: &str) -> Result<Vec<MattermostScheduledPost>> {
+        let endpoint = format!("/posts/scheduled/team/{team_id}?includeDirectChannels=true");
+        let response = self.get(&endpoint).await?;
+        // Grouped by team ID, with direct channels under "directChannels"
+        let grouped: HashMap<String, Vec<MattermostScheduledPost>> = self
+            .handle_response(response)
+            .await
+            .map_err(scheduled_posts_unavailable)?;
+        Ok(grouped.into_values().flatten().collect())
+    }
+
+    /// Delete a scheduled post before it is sent
+    ///
+    /// # Arguments
+    /// * `scheduled_post_id` - The ID of the scheduled post
+    ///
+    /// # Returns
+    /// A Result indicating success or failure
+    pub async fn delete_scheduled_post(&self, scheduled_post_id: &str) -> Result<()> {
+        let endpoint = format!("/posts/schedule/{scheduled_post_id}");
+        let response = self.delete(&endpoint).await?;
+        let _: serde_json::Value = self
+            .handle_response(response)
+            .await
+            .map_err(scheduled_posts_unavailable)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scheduled_post_serialization() {
+        let post = MattermostScheduledPost {
+            channel_id: "ch1".to_string(),
+            message: "later".to_string(),
+            scheduled_at: 1_700_000_000_000,
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&post).unwrap();
+        assert_eq!(json["scheduled_at"], 1_700_000_000_000i64);
+        assert!(json.get("error_code").is_none());
+    }
+
+    #[cfg(feature = "simulation")]
+    #[tokio::test]
+    async fn test_get_scheduled_posts() {
+        let server = crate::simulation::add_server("scheduled.sim");
+        server.set_route(
+            "GET",
+            "/posts/scheduled/team/team1",
+            200,
+            r#"{"team1":[{"id":"s1","channel_id":"ch1","message":"a","scheduled_at":2000}],
+                "directChannels":[{"id":"s2","channel_id":"dm1","message":"b","scheduled_at":1000}]}"#,
+        );
+
+        let client = MattermostClient::new("http://scheduled.sim").unwrap();
+        let mut posts = client.get_scheduled_posts("team1").await.unwrap();
+        posts.sort_by_key(|p| p.scheduled_at);
+        assert_eq!(posts.len(), 2);
+        assert_eq!(posts[0].id, "s2");
+
+        crate::simulation::remove_server("scheduled.sim");
+    }
+}
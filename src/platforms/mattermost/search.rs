@@ -1,4 +1,5 @@
 use crate::error::Result;
+use crate::types::SearchQuery;
 use serde::{Deserialize, Serialize};
 
 use super::client::MattermostClient;
@@ -187,6 +188,31 @@ pub struct PostSearchOptions {
     pub per_page: u32,
 }
 
+/// Translate a structured search query into Mattermost search terms
+///
+/// Mattermost has no attachment filter for post search, so `has_attachment`
+/// is not part of the terms and must be applied to the results. Direct
+/// messages with a user are searched with `in:@username`.
+pub fn to_mattermost_terms(query: &SearchQuery) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    parts.extend(query.terms.iter().cloned());
+    parts.extend(query.phrases.iter().map(|p| format!("\"{p}\"")));
+    parts.extend(query.excluded_terms.iter().map(|t| format!("-{t}")));
+    parts.extend(query.from_users.iter().map(|u| format!("from:{u}")));
+    parts.extend(query.in_channels.iter().map(|c| format!("in:{c}")));
+    parts.extend(query.to_users.iter().map(|u| format!("in:@{u}")));
+    if let Some(date) = query.after {
+        parts.push(format!("after:{}", date.format("%Y-%m-%d")));
+    }
+    if let Some(date) = query.before {
+        parts.push(format!("before:{}", date.format("%Y-%m-%d")));
+    }
+    if let Some(date) = query.on {
+        parts.push(format!("on:{}", date.format("%Y-%m-%d")));
+    }
+    parts.join(" ")
+}
+
 // ============================================================================
 // Search API Implementation
 // ============================================================================
@@ -202,10 +228,16 @@ impl MattermostClient {
     ///
     /// # Example
     /// ```no_run
+    /// # use communicator::platforms::mattermost::{MattermostClient, UserSearchRequest};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = MattermostClient::new("https://example.com")?;
+    /// # let team_id = "team_id";
     /// let request = UserSearchRequest::new("john".to_string())
-    ///     .with_team_id(team_id)
+    ///     .with_team_id(team_id.to_string())
     ///     .with_limit(50);
     /// let users = client.search_users(&request).await?;
+    /// # Ok(())
+    /// # }
     /// ```
     pub async fn search_users(&self, request: &UserSearchRequest) -> Result<Vec<MattermostUser>> {
         let response = self.post("/users/search", request).await?;
@@ -268,8 +300,14 @@ impl MattermostClient {
     ///
     /// # Example
     /// ```no_run
+    /// # use communicator::platforms::mattermost::{MattermostClient, ChannelSearchRequest};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = MattermostClient::new("https://example.com")?;
+    /// # let team_id = "team_id";
     /// let request = ChannelSearchRequest::new("general".to_string());
     /// let channels = client.search_channels(team_id, &request).await?;
+    /// # Ok(())
+    /// # }
     /// ```
     pub async fn search_channels(
         &self,
@@ -294,6 +332,7 @@ impl MattermostClient {
         team_id: &str,
         name: &str,
     ) -> Result<Vec<MattermostChannel>> {
+        let name: String = url::form_urlencoded::byte_serialize(name.as_bytes()).collect();
         let endpoint = format!("/teams/{}/channels/autocomplete?name={}", team_id, name);
         let response = self.get(&endpoint).await?;
         self.handle_response(response).await
@@ -310,9 +349,15 @@ impl MattermostClient {
     ///
     /// # Example
     /// ```no_run
+    /// # use communicator::platforms::mattermost::{MattermostClient, FileSearchRequest};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = MattermostClient::new("https://example.com")?;
+    /// # let team_id = "team_id";
     /// let request = FileSearchRequest::new("report".to_string())
     ///     .with_extensions(vec!["pdf".to_string(), "doc".to_string()]);
     /// let results = client.search_files(team_id, &request).await?;
+    /// # Ok(())
+    /// # }
     /// ```
     pub async fn search_files(
         &self,
@@ -345,9 +390,15 @@ impl MattermostClient {
     ///
     /// # Example
     /// ```no_run
+    /// # use communicator::platforms::mattermost::{MattermostClient, PostSearchOptions};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = MattermostClient::new("https://example.com")?;
+    /// # let team_id = "team_id";
     /// // Search for posts from john in town-square containing "project"
     /// let terms = "from:john in:town-square project";
     /// let results = client.search_posts_advanced(team_id, terms, PostSearchOptions::default()).await?;
+    /// # Ok(())
+    /// # }
     /// ```
     pub async fn search_posts_advanced(
         &self,
@@ -378,6 +429,19 @@ impl MattermostClient {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_to_mattermost_terms() {
+        let query = SearchQuery::parse(
+            r#"deploy "release notes" -draft from:alice to:bob in:town-square on:2024-01-15 has:attachment"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            to_mattermost_terms(&query),
+            r#"deploy "release notes" -draft from:alice in:town-square in:@bob on:2024-01-15"#
+        );
+    }
+
     #[test]
     fn test_user_search_request_builder() {
         let request = UserSearchRequest::new("john".to_string())
@@ -416,8 +480,8 @@ mod tests {
     #[test]
     fn test_post_search_options_default() {
         let options = PostSearchOptions::default();
-        assert_eq!(options.is_or_search, false);
-        assert_eq!(options.include_deleted_channels, false);
+        assert!(!options.is_or_search);
+        assert!(!options.include_deleted_channels);
         assert_eq!(options.time_zone_offset, 0);
         assert_eq!(options.page, 0);
         assert_eq!(options.per_page, 0);
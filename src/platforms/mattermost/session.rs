@@ -0,0 +1,273 @@
+//! Session renewal
+//!
+//! Mattermost session tokens expire (after 30 days by default, or when an
+//! administrator revokes them). A request rejected with 401 Unauthorized
+//! renews the session by logging in again with the password given at connect
+//! and is then retried once with the new token. Concurrent requests wait for
+//! one renewal instead of logging in several times.
+//!
+//! Sessions from a token or an MFA login can't be renewed this way. When
+//! renewal is impossible or fails, the expiry is reported once as
+//! `PlatformEvent::SessionExpired`, and requests fail with
+//! `ErrorCode::AuthenticationFailed` until new credentials are supplied with
+//! `Platform::renew_session`.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use reqwest::header::{HeaderValue, AUTHORIZATION};
+use reqwest::RequestBuilder;
+
+use crate::error::{Error, ErrorCode, Result};
+
+use super::client::MattermostClient;
+use super::types::MattermostUser;
+
+#[derive(Debug, Default)]
+struct SessionState {
+    /// Login ID and password to log in again with
+    credentials: Option<(String, String)>,
+    /// The session expired and could not be renewed
+    expired: bool,
+    /// Expiry that has not been reported yet
+    unreported: Option<Error>,
+    /// Renewed since the last `take_renewed`
+    renewed: bool,
+}
+
+/// State of session renewal
+#[derive(Debug, Default)]
+pub struct SessionRenewal {
+    state: Mutex<SessionState>,
+    /// Held while logging in again, so concurrent 401s renew once
+    renewing: tokio::sync::Mutex<()>,
+}
+
+impl SessionRenewal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep the password credentials of a login, or forget them (None)
+    fn set_credentials(&self, credentials: Option<(String, String)>) {
+        self.state.lock().unwrap().credentials = credentials;
+    }
+
+    fn credentials(&self) -> Option<(String, String)> {
+        self.state.lock().unwrap().credentials.clone()
+    }
+
+    /// Whether the session expired and could not be renewed
+    pub fn is_expired(&self) -> bool {
+        self.state.lock().unwrap().expired
+    }
+
+    fn mark_expired(&self, error: Error) {
+        let mut state = self.state.lock().unwrap();
+        if !state.expired {
+            state.expired = true;
+            state.unreported = Some(error);
+        }
+    }
+
+    /// End an expiry after a login
+    fn clear_expiry(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.expired = false;
+        state.unreported = None;
+    }
+
+    /// Record that the session of a connected client was replaced
+    pub fn mark_renewed(&self) {
+        self.clear_expiry();
+        self.state.lock().unwrap().renewed = true;
+    }
+
+    /// Take the expiry that has not been reported yet
+    pub fn take_expiry(&self) -> Option<Error> {
+        self.state.lock().unwrap().unreported.take()
+    }
+
+    /// Whether the session was renewed since the last call
+    pub fn take_renewed(&self) -> bool {
+        std::mem::take(&mut self.state.lock().unwrap().renewed)
+    }
+
+    /// Ask again for the WebSocket to reconnect with the new token
+    pub fn defer_renewed(&self) {
+        self.state.lock().unwrap().renewed = true;
+    }
+}
+
+/// Rebuild an authenticated request with another token
+///
+/// # Returns
+/// None for requests without an Authorization header, or that can't be rebuilt
+pub(crate) fn with_token(request: RequestBuilder, token: &str) -> Option<RequestBuilder> {
+    let (client, request) = request.build_split();
+    let mut request = request.ok()?;
+    if !request.headers().contains_key(AUTHORIZATION) {
+        return None;
+    }
+    let mut value = HeaderValue::from_str(&format!("Bearer {token}")).ok()?;
+    value.set_sensitive(true);
+    request.headers_mut().insert(AUTHORIZATION, value);
+    Some(RequestBuilder::from_parts(client, request))
+}
+
+impl MattermostClient {
+    /// Log in with connect-style credentials
+    ///
+    /// # Arguments
+    /// * `credentials` - "token", or "login_id" and "password" (and "mfa_token")
+    ///
+    /// # Returns
+    /// The logged in user. Password logins without MFA are kept for renewing
+    /// the session; other logins can't be renewed automatically.
+    pub async fn login_with_credentials(
+        &self,
+        credentials: &HashMap<String, String>,
+    ) -> Result<MattermostUser> {
+        let user = if let Some(token) = credentials.get("token") {
+            // Use Personal Access Token or existing session token
+            self.login_with_token(token).await?
+        } else if let (Some(login_id), Some(password)) =
+            (credentials.get("login_id"), credentials.get("password"))
+        {
+            if let Some(mfa_token) = credentials.get("mfa_token") {
+                self.login_with_mfa(login_id, password, mfa_token).await?
+            } else {
+                self.login(login_id, password).await?
+            }
+        } else {
+            return Err(Error::new(
+                ErrorCode::InvalidArgument,
+                "Missing authentication credentials (provide 'token' or 'login_id'+'password')",
+            ));
+        };
+
+        let renewable = match (
+            credentials.get("token"),
+            credentials.get("mfa_token"),
+            credentials.get("login_id"),
+            credentials.get("password"),
+        ) {
+            (None, None, Some(login_id), Some(password)) => {
+                Some((login_id.clone(), password.clone()))
+            }
+            _ => None,
+        };
+        self.session().set_credentials(renewable);
+        self.session().clear_expiry();
+        Ok(user)
+    }
+
+    /// Forget the credentials kept for renewing the session
+    pub(crate) fn forget_session_credentials(&self) {
+        self.session().set_credentials(None);
+        // Nothing to reconnect after a logout
+        self.session().take_renewed();
+    }
+
+    /// Renew a session a request was rejected for
+    ///
+    /// # Arguments
+    /// * `stale_token` - The token the rejected request was sent with
+    ///
+    /// # Returns
+    /// The token to retry the request with, or None if the session could not be renewed
+    pub(crate) async fn renew_session(&self, stale_token: &str) -> Option<String> {
+        if stale_token.is_empty() {
+            return None;
+        }
+        let _renewing = self.session().renewing.lock().await;
+
+        // Another request renewed the session while this one waited
+        let current = self.get_token().await.filter(|token| !token.is_empty())?;
+        if current != stale_token {
+            return Some(current);
+        }
+        if self.session().is_expired() {
+            return None;
+        }
+
+        let Some((login_id, password)) = self.session().credentials() else {
+            self.session().mark_expired(Error::new(
+                ErrorCode::AuthenticationFailed,
+                "Session expired; new credentials are needed",
+            ));
+            return None;
+        };
+        match self.login(&login_id, &password).await {
+            Ok(_) => {
+                self.session().mark_renewed();
+                self.get_token().await
+            }
+            Err(e) => {
+                self.session().mark_expired(Error::new(
+                    ErrorCode::AuthenticationFailed,
+                    format!("Session expired and logging in again failed: {}", e.message),
+                ));
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expiry_is_reported_once() {
+        let session = SessionRenewal::new();
+        assert!(!session.is_expired());
+
+        session.mark_expired(Error::new(ErrorCode::AuthenticationFailed, "first"));
+        session.mark_expired(Error::new(ErrorCode::AuthenticationFailed, "second"));
+        assert!(session.is_expired());
+        assert_eq!(session.take_expiry().unwrap().message, "first");
+        assert!(session.take_expiry().is_none());
+
+        // New credentials end the expiry
+        session.mark_renewed();
+        assert!(!session.is_expired());
+        assert!(session.take_renewed());
+        assert!(!session.take_renewed());
+    }
+
+    #[test]
+    fn test_with_token() {
+        let client = reqwest::Client::new();
+        let request = client
+            .get("https://mattermost.example.com/api/v4/users/me")
+            .bearer_auth("old");
+        let request = with_token(request, "new").unwrap().build().unwrap();
+        let values: Vec<_> = request.headers().get_all(AUTHORIZATION).iter().collect();
+        assert_eq!(values, vec!["Bearer new"]);
+
+        // Unauthenticated requests are not retried
+        let request = client.get("https://mattermost.example.com/api/v4/system/ping");
+        assert!(with_token(request, "new").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_renewal_without_credentials() {
+        let client = MattermostClient::new("https://mattermost.example.com").unwrap();
+        client.set_token("stale".to_string()).await;
+
+        assert_eq!(client.renew_session("stale").await, None);
+        assert!(client.session().is_expired());
+        assert_eq!(
+            client.session().take_expiry().unwrap().code,
+            ErrorCode::AuthenticationFailed
+        );
+
+        // A token replaced in the meantime is used as is
+        client.set_token("fresh".to_string()).await;
+        assert_eq!(
+            client.renew_session("stale").await.as_deref(),
+            Some("fresh")
+        );
+    }
+}
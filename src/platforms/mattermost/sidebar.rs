@@ -0,0 +1,189 @@
+use crate::error::{Error, ErrorCode, Result};
+
+use super::client::MattermostClient;
+use super::types::{MattermostSidebarCategories, MattermostSidebarCategory};
+
+/// Move a channel into a category, out of the category it is in
+///
+/// # Arguments
+/// * `categories` - All of the user's categories in the team
+/// * `channel_id` - The channel to move
+/// * `category_id` - The category to move it to
+/// * `index` - Position in the category, or None for its end; a position
+///   makes the category manually sorted
+///
+/// # Returns
+/// The categories that changed, or a NotFound error for an unknown category
+fn move_channel(
+    categories: &[MattermostSidebarCategory],
+    channel_id: &str,
+    category_id: &str,
+    index: Option<usize>,
+) -> Result<Vec<MattermostSidebarCategory>> {
+    if !categories.iter().any(|category| category.id == category_id) {
+        return Err(Error::new(
+            ErrorCode::NotFound,
+            format!("No sidebar category {category_id}"),
+        ));
+    }
+
+    let mut changed = Vec::new();
+    for category in categories {
+        let mut category = category.clone();
+        let was_in = category.channel_ids.iter().any(|id| id == channel_id);
+        if category.id == category_id {
+            category.channel_ids.retain(|id| id != channel_id);
+            let position = index
+                .unwrap_or(category.channel_ids.len())
+                .min(category.channel_ids.len());
+            category
+                .channel_ids
+                .insert(position, channel_id.to_string());
+            if index.is_some() {
+                category.sorting = "manual".to_string();
+            }
+            changed.push(category);
+        } else if was_in {
+            category.channel_ids.retain(|id| id != channel_id);
+            changed.push(category);
+        }
+    }
+    Ok(changed)
+}
+
+impl MattermostClient {
+    /// Get the current user's sidebar categories in a team
+    ///
+    /// # Arguments
+    /// * `team_id` - The ID of the team
+    ///
+    /// # Returns
+    /// A Result containing the categories in display order or an Error
+    pub async fn get_sidebar_categories(
+        &self,
+        team_id: &str,
+    ) -> Result<Vec<MattermostSidebarCategory>> {
+        let user_id = self.current_user_id().await?;
+        let endpoint = format!("/users/{user_id}/teams/{team_id}/channels/categories");
+        let response = self.get(&endpoint).await?;
+        let MattermostSidebarCategories {
+            mut categories,
+            order,
+        } = self.handle_response(response).await?;
+        // Categories missing from the order go last
+        categories.sort_by_key(|category| {
+            order
+                .iter()
+                .position(|id| *id == category.id)
+                .unwrap_or(usize::MAX)
+        });
+        Ok(categories)
+    }
+
+    /// Update some of the current user's sidebar categories in a team
+    ///
+    /// # Arguments
+    /// * `team_id` - The ID of the team
+    /// * `categories` - The categories with their new contents
+    ///
+    /// # Returns
+    /// A Result containing the updated categories or an Error
+    pub async fn update_sidebar_categories(
+        &self,
+        team_id: &str,
+        categories: &[MattermostSidebarCategory],
+    ) -> Result<Vec<MattermostSidebarCategory>> {
+        let user_id = self.current_user_id().await?;
+        let endpoint = format!("/users/{user_id}/teams/{team_id}/channels/categories");
+        let response = self.put(&endpoint, &categories).await?;
+        self.handle_response(response).await
+    }
+
+    /// Move a channel into a sidebar category
+    ///
+    /// # Arguments
+    /// * `team_id` - The ID of the team
+    /// * `channel_id` - The ID of the channel
+    /// * `category_id` - The ID of the category
+    /// * `index` - Position in the category, or None for its end
+    ///
+    /// # Returns
+    /// A Result indicating success or failure (NotFound for an unknown category)
+    pub async fn move_channel_to_category(
+        &self,
+        team_id: &str,
+        channel_id: &str,
+        category_id: &str,
+        index: Option<usize>,
+    ) -> Result<()> {
+        let categories = self.get_sidebar_categories(team_id).await?;
+        let changed = move_channel(&categories, channel_id, category_id, index)?;
+        self.update_sidebar_categories(team_id, &changed).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn category(id: &str, channel_ids: &[&str]) -> MattermostSidebarCategory {
+        MattermostSidebarCategory {
+            id: id.to_string(),
+            category_type: "custom".to_string(),
+            channel_ids: channel_ids.iter().map(|id| id.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_move_channel() {
+        let categories = vec![
+            category("favorites", &["c1"]),
+            category("work", &["c2", "c3"]),
+            category("other", &["c4"]),
+        ];
+
+        let changed = move_channel(&categories, "c1", "work", Some(1)).unwrap();
+        assert_eq!(changed.len(), 2);
+        assert!(changed[0].channel_ids.is_empty());
+        assert_eq!(changed[1].channel_ids, vec!["c2", "c1", "c3"]);
+        assert_eq!(changed[1].sorting, "manual");
+
+        // Moving within a category only changes that category
+        let changed = move_channel(&categories, "c3", "work", Some(0)).unwrap();
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].channel_ids, vec!["c3", "c2"]);
+
+        let changed = move_channel(&categories, "c4", "favorites", None).unwrap();
+        assert_eq!(changed[0].channel_ids, vec!["c1", "c4"]);
+        assert_eq!(changed[0].sorting, "");
+
+        let err = move_channel(&categories, "c1", "missing", None).unwrap_err();
+        assert_eq!(err.code, ErrorCode::NotFound);
+    }
+
+    #[cfg(feature = "simulation")]
+    #[tokio::test]
+    async fn test_get_sidebar_categories_in_order() {
+        let server = crate::simulation::add_server("sidebar.sim");
+        server.set_route(
+            "GET",
+            "/users/user1/teams/team1/channels/categories",
+            200,
+            r#"{"categories":[
+                {"id":"channels_1","type":"channels","display_name":"Channels","channel_ids":["c1"]},
+                {"id":"favorites_1","type":"favorites","display_name":"Favorites","channel_ids":[]}
+               ],
+               "order":["favorites_1","channels_1"]}"#,
+        );
+
+        let client = MattermostClient::new("http://sidebar.sim").unwrap();
+        client.set_user_id(Some("user1".to_string())).await;
+        let categories = client.get_sidebar_categories("team1").await.unwrap();
+        assert_eq!(categories[0].id, "favorites_1");
+        assert_eq!(categories[1].channel_ids, vec!["c1"]);
+
+        crate::simulation::remove_server("sidebar.sim");
+    }
+}
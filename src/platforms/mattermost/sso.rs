@@ -0,0 +1,226 @@
+//! Single sign-on through an OAuth 2.0 provider
+//!
+//! Mattermost servers with password login disabled authenticate through GitLab,
+//! Google, Office 365 or an OpenID Connect provider. The server runs the OAuth
+//! 2.0 authorization-code flow itself:
+//!
+//! 1. `GET /oauth/{provider}/login` redirects to the provider's authorize URL
+//!    and sets a cookie tying the flow to this client.
+//! 2. The user signs in with the provider in a browser, which is redirected to
+//!    `/signup/{provider}/complete?code=...&state=...` on the server.
+//! 3. That request, with the cookie from step 1, makes the server exchange
+//!    the code and answer with the session token in the `MMAUTHTOKEN` cookie.
+//!
+//! The embedder opens the authorize URL and hands back the URL the browser was
+//! redirected to; this client sends steps 1 and 3 without following redirects.
+
+use reqwest::header::{HeaderMap, COOKIE, LOCATION, SET_COOKIE};
+use reqwest::Response;
+
+use crate::error::{Error, ErrorCode, Result};
+
+use super::client::MattermostClient;
+
+/// Providers the server runs the OAuth 2.0 flow for
+const PROVIDERS: &[&str] = &["gitlab", "google", "office365", "openid"];
+
+/// Cookie the server returns the session token in
+const SESSION_COOKIE: &str = "MMAUTHTOKEN";
+
+/// SSO login waiting for the provider's redirect
+#[derive(Debug, Clone)]
+pub struct PendingSso {
+    provider: String,
+    /// `state` parameter of the authorize URL, echoed back by the provider
+    state: String,
+    /// Cookies set by the server when the flow started
+    cookies: Vec<String>,
+}
+
+/// Cookies set by a response, as "name=value"
+fn set_cookies(headers: &HeaderMap) -> Vec<String> {
+    headers
+        .get_all(SET_COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .filter_map(|value| value.split(';').next())
+        .map(|cookie| cookie.trim().to_string())
+        .filter(|cookie| cookie.contains('='))
+        .collect()
+}
+
+/// Value of a query parameter of a URL
+fn query_param(url: &url::Url, name: &str) -> Option<String> {
+    url.query_pairs()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.into_owned())
+}
+
+/// The `Location` of a redirect, resolved against the URL requested
+fn redirect_location(response: &Response) -> Option<url::Url> {
+    if !response.status().is_redirection() {
+        return None;
+    }
+    let location = response.headers().get(LOCATION)?.to_str().ok()?;
+    response.url().join(location).ok()
+}
+
+fn sso_failed(message: impl Into<String>) -> Error {
+    Error::new(ErrorCode::AuthenticationFailed, message)
+}
+
+impl MattermostClient {
+    /// Start an SSO login
+    ///
+    /// # Arguments
+    /// * `provider` - "gitlab", "google", "office365" or "openid"
+    ///
+    /// # Returns
+    /// The provider's authorize URL to open in a browser. A later call replaces
+    /// the pending login.
+    pub async fn begin_sso_login(&self, provider: &str) -> Result<String> {
+        if !PROVIDERS.contains(&provider) {
+            return Err(Error::invalid_argument(format!(
+                "Unsupported SSO provider '{provider}' (expected one of {})",
+                PROVIDERS.join(", ")
+            )));
+        }
+
+        let url = format!("{}/oauth/{provider}/login", self.get_base_url());
+        let request = self.http_client_without_redirects()?.get(&url);
+        let response = self.send_request(request, "GET").await?;
+
+        let Some(authorize_url) = redirect_location(&response) else {
+            let status = response.status();
+            if status.is_client_error() || status.is_server_error() {
+                self.handle_response::<serde_json::Value>(response).await?;
+            }
+            return Err(sso_failed(format!(
+                "Server did not redirect to {provider} (status {status}); is SSO with it enabled?"
+            )));
+        };
+        let state = query_param(&authorize_url, "state")
+            .ok_or_else(|| sso_failed("Authorize URL has no state parameter"))?;
+
+        *self.pending_sso().lock().unwrap() = Some(PendingSso {
+            provider: provider.to_string(),
+            state,
+            cookies: set_cookies(response.headers()),
+        });
+        Ok(authorize_url.into())
+    }
+
+    /// Finish an SSO login
+    ///
+    /// # Arguments
+    /// * `callback_url` - The URL the provider redirected the browser to, with
+    ///   its `code` and `state` parameters
+    ///
+    /// # Returns
+    /// The session token, to log in with as a "token" credential
+    pub async fn complete_sso_login(&self, callback_url: &str) -> Result<String> {
+        let callback = url::Url::parse(callback_url)
+            .map_err(|e| Error::invalid_argument(format!("Invalid callback URL: {e}")))?;
+        let pending = self
+            .pending_sso()
+            .lock()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| Error::new(ErrorCode::InvalidState, "No SSO login in progress"))?;
+
+        if let Some(error) = query_param(&callback, "error") {
+            self.pending_sso().lock().unwrap().take();
+            let description = query_param(&callback, "error_description").unwrap_or_default();
+            return Err(sso_failed(format!(
+                "{} sign-in failed: {error} {description}",
+                pending.provider
+            )));
+        }
+        let code = query_param(&callback, "code")
+            .ok_or_else(|| Error::invalid_argument("Callback URL has no code parameter"))?;
+        if query_param(&callback, "state").as_deref() != Some(pending.state.as_str()) {
+            return Err(Error::invalid_argument(
+                "Callback URL does not belong to the pending SSO login",
+            ));
+        }
+
+        // Sent to the configured server, whatever the host of the callback URL
+        let mut url = url::Url::parse(&format!(
+            "{}/signup/{}/complete",
+            self.get_base_url(),
+            pending.provider
+        ))
+        .map_err(|e| Error::invalid_argument(format!("Invalid server URL: {e}")))?;
+        url.query_pairs_mut()
+            .append_pair("code", &code)
+            .append_pair("state", &pending.state);
+
+        let mut request = self.http_client_without_redirects()?.get(url);
+        if !pending.cookies.is_empty() {
+            request = request.header(COOKIE, pending.cookies.join("; "));
+        }
+        let response = self.send_request(request, "GET").await?;
+        self.pending_sso().lock().unwrap().take();
+
+        let token = set_cookies(response.headers())
+            .into_iter()
+            .find_map(|cookie| {
+                let (name, value) = cookie.split_once('=')?;
+                (name == SESSION_COOKIE && !value.is_empty()).then(|| value.to_string())
+            });
+        token.ok_or_else(|| {
+            // The server redirects to its error page with a message
+            let message = redirect_location(&response)
+                .and_then(|location| query_param(&location, "message"))
+                .unwrap_or_else(|| format!("status {}", response.status()));
+            sso_failed(format!("SSO login failed: {message}"))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_set_cookies() {
+        let mut headers = HeaderMap::new();
+        headers.append(
+            SET_COOKIE,
+            HeaderValue::from_static("MMOAUTHTOKEN=abc; Path=/; HttpOnly"),
+        );
+        headers.append(SET_COOKIE, HeaderValue::from_static("MMAUTHTOKEN=tok"));
+        headers.append(SET_COOKIE, HeaderValue::from_static("invalid"));
+        assert_eq!(
+            set_cookies(&headers),
+            vec!["MMOAUTHTOKEN=abc", "MMAUTHTOKEN=tok"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sso_login_checks() {
+        let client = MattermostClient::new("https://mattermost.example.com").unwrap();
+
+        let error = client.begin_sso_login("saml").await.unwrap_err();
+        assert_eq!(error.code, ErrorCode::InvalidArgument);
+
+        let callback = "https://mattermost.example.com/signup/gitlab/complete?code=c&state=s";
+        let error = client.complete_sso_login(callback).await.unwrap_err();
+        assert_eq!(error.code, ErrorCode::InvalidState);
+
+        *client.pending_sso().lock().unwrap() = Some(PendingSso {
+            provider: "gitlab".to_string(),
+            state: "expected".to_string(),
+            cookies: Vec::new(),
+        });
+        let error = client.complete_sso_login(callback).await.unwrap_err();
+        assert_eq!(error.code, ErrorCode::InvalidArgument);
+
+        // A provider error ends the pending login
+        let denied = "https://mattermost.example.com/signup/gitlab/complete?error=access_denied";
+        let error = client.complete_sso_login(denied).await.unwrap_err();
+        assert_eq!(error.code, ErrorCode::AuthenticationFailed);
+        assert!(client.pending_sso().lock().unwrap().is_none());
+    }
+}
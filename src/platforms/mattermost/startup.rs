@@ -0,0 +1,136 @@
+//! Session snapshots for cached-data-first startup
+//!
+//! When a startup cache file is configured, the current user, the teams and
+//! the channels of the current team are saved to it as they are fetched. On
+//! the next connect to the same server, the snapshot is served right away
+//! (marked stale) while authentication completes in the background, so
+//! clients can render their sidebar before the server has answered.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, ErrorCode, Result};
+use crate::migrations;
+use crate::types::{Channel, ConnectionInfo, ConnectionState, Team, User};
+
+/// State of a previous session, saved for the next startup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartupSnapshot {
+    /// Server URL the session was connected to
+    pub server: String,
+    /// The current user
+    pub user: User,
+    /// The current team, if one was selected
+    #[serde(default)]
+    pub team_id: Option<String>,
+    /// Teams the user is a member of
+    #[serde(default)]
+    pub teams: Vec<Team>,
+    /// Channels of the current team
+    #[serde(default)]
+    pub channels: Vec<Channel>,
+}
+
+impl StartupSnapshot {
+    /// Create a snapshot of a session without teams and channels
+    pub fn new(server: impl Into<String>, user: User, team_id: Option<String>) -> Self {
+        Self {
+            server: server.into(),
+            user,
+            team_id,
+            teams: Vec::new(),
+            channels: Vec::new(),
+        }
+    }
+
+    /// Load a snapshot
+    ///
+    /// # Returns
+    /// The snapshot, or None if the file is missing, unreadable or can't be
+    /// migrated to the current format (it is only a cache, so a broken file
+    /// means a normal startup)
+    pub fn load(path: &Path) -> Option<Self> {
+        let document = migrations::open(&migrations::STARTUP_CACHE, path).ok()??;
+        serde_json::from_value(document).ok()
+    }
+
+    /// Save the snapshot, replacing the file atomically
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_value(self)
+            .map(|document| migrations::STARTUP_CACHE.stamp(document))
+            .and_then(|document| serde_json::to_string(&document))
+            .map_err(|e| {
+                Error::new(
+                    ErrorCode::Unknown,
+                    format!("Failed to serialize startup cache: {e}"),
+                )
+            })?;
+        let write_error = |e: std::io::Error| {
+            Error::new(
+                ErrorCode::Unknown,
+                format!("Failed to write startup cache {}: {e}", path.display()),
+            )
+        };
+        let temp_path = path.with_extension("tmp");
+        std::fs::write(&temp_path, json).map_err(write_error)?;
+        std::fs::rename(&temp_path, path).map_err(write_error)
+    }
+
+    /// Connection info for the session while it is being re-established
+    pub fn connection_info(&self) -> ConnectionInfo {
+        let mut info = ConnectionInfo::new(
+            "mattermost",
+            &self.server,
+            &self.user.id,
+            &self.user.username,
+        )
+        .with_state(ConnectionState::Connecting);
+        if let Some(team_id) = &self.team_id {
+            info = info.with_team(team_id, "");
+        }
+        info.stale = true;
+        info
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ChannelType;
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let path =
+            std::env::temp_dir().join(format!("communicator-startup-{}.json", std::process::id()));
+        let mut snapshot = StartupSnapshot::new(
+            "https://chat.example.com",
+            User::new("user-1", "alice", "Alice"),
+            Some("team-1".to_string()),
+        );
+        snapshot
+            .teams
+            .push(Team::new("team-1", "team-one", "Team One"));
+        snapshot.channels.push(Channel::new(
+            "chan-1",
+            "town-square",
+            "Town Square",
+            ChannelType::Public,
+        ));
+        snapshot.save(&path).unwrap();
+
+        let loaded = StartupSnapshot::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.server, "https://chat.example.com");
+        assert_eq!(loaded.user.id, "user-1");
+        assert_eq!(loaded.teams.len(), 1);
+        assert_eq!(loaded.channels[0].id, "chan-1");
+
+        let info = loaded.connection_info();
+        assert!(info.stale);
+        assert_eq!(info.state, ConnectionState::Connecting);
+        assert_eq!(info.team_id.as_deref(), Some("team-1"));
+
+        assert!(StartupSnapshot::load(&path).is_none());
+    }
+}
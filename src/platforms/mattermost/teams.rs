@@ -1,7 +1,7 @@
 //! Team management operations for Mattermost
 
 use super::client::MattermostClient;
-use super::types::MattermostTeam;
+use super::types::{MattermostTeam, TeamMember};
 use crate::error::Result;
 
 impl MattermostClient {
@@ -48,11 +48,27 @@ impl MattermostClient {
         let response = self.get(&endpoint).await?;
         self.handle_response(response).await
     }
+
+    /// Get a user's membership in a team
+    ///
+    /// # Arguments
+    /// * `team_id` - The unique identifier of the team
+    /// * `user_id` - The unique identifier of the user
+    ///
+    /// # Returns
+    /// A Result containing the TeamMember object, including the user's team roles
+    ///
+    /// # API Endpoint
+    /// GET /teams/{team_id}/members/{user_id}
+    pub async fn get_team_member(&self, team_id: &str, user_id: &str) -> Result<TeamMember> {
+        let endpoint = format!("/teams/{team_id}/members/{user_id}");
+        let response = self.get(&endpoint).await?;
+        self.handle_response(response).await
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
 
     #[test]
     fn test_team_endpoints() {
@@ -41,6 +41,7 @@ impl MattermostClient {
     ///
     /// # API Endpoint
     /// `GET /api/v4/users/{user_id}/teams/{team_id}/threads`
+    #[allow(clippy::too_many_arguments)]
     pub async fn get_user_threads(
         &self,
         user_id: &str,
@@ -76,6 +76,9 @@ pub struct MattermostUser {
     pub props: HashMap<String, serde_json::Value>,
     #[serde(default)]
     pub is_bot: bool,
+    /// Notification settings, only sent for the current user
+    #[serde(default)]
+    pub notify_props: HashMap<String, String>,
     pub create_at: i64,
     pub update_at: i64,
     pub delete_at: i64,
@@ -103,6 +106,9 @@ pub struct MattermostChannel {
     pub total_msg_count: i64,
     #[serde(default)]
     pub creator_id: String,
+    /// Whether this channel is shared with remote clusters (shared channels)
+    #[serde(default)]
+    pub shared: Option<bool>,
 }
 
 /// Mattermost Post (message) object from API
@@ -134,7 +140,12 @@ pub struct MattermostPost {
     #[serde(default)]
     pub pending_post_id: String,
     #[serde(default)]
+    pub is_pinned: bool,
+    #[serde(default)]
     pub metadata: PostMetadata,
+    /// ID of the remote cluster this post originated from (shared channels)
+    #[serde(default)]
+    pub remote_id: Option<String>,
 }
 
 /// Metadata for a Mattermost Post
@@ -158,6 +169,9 @@ pub struct FileInfo {
     pub id: String,
     pub user_id: String,
     pub post_id: String,
+    /// Channel the file was uploaded to; empty on servers before 6.0
+    #[serde(default)]
+    pub channel_id: String,
     pub create_at: i64,
     pub update_at: i64,
     pub delete_at: i64,
@@ -173,6 +187,98 @@ pub struct FileInfo {
     pub has_preview_image: bool,
 }
 
+/// Mattermost resumable upload session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadSession {
+    pub id: String,
+    /// Bytes the server has received so far
+    #[serde(default)]
+    pub file_offset: i64,
+    #[serde(default)]
+    pub file_size: i64,
+}
+
+/// Mattermost Draft object from API
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MattermostDraft {
+    #[serde(default)]
+    pub create_at: i64,
+    #[serde(default)]
+    pub update_at: i64,
+    #[serde(default)]
+    pub user_id: String,
+    pub channel_id: String,
+    #[serde(default)]
+    pub root_id: String,
+    #[serde(default)]
+    pub message: String,
+    #[serde(default)]
+    pub props: Option<HashMap<String, serde_json::Value>>,
+    #[serde(default)]
+    pub file_ids: Option<Vec<String>>,
+}
+
+/// Mattermost SidebarCategory object from API
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MattermostSidebarCategory {
+    pub id: String,
+    #[serde(default)]
+    pub user_id: String,
+    #[serde(default)]
+    pub team_id: String,
+    #[serde(default)]
+    pub sort_order: i64,
+    /// "", "alpha", "recent" or "manual"
+    #[serde(default)]
+    pub sorting: String,
+    /// "favorites", "channels", "direct_messages" or "custom"
+    #[serde(rename = "type")]
+    pub category_type: String,
+    #[serde(default)]
+    pub display_name: String,
+    #[serde(default)]
+    pub muted: bool,
+    #[serde(default)]
+    pub collapsed: bool,
+    #[serde(default)]
+    pub channel_ids: Vec<String>,
+}
+
+/// The current user's sidebar categories in a team
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MattermostSidebarCategories {
+    #[serde(default)]
+    pub categories: Vec<MattermostSidebarCategory>,
+    /// Category IDs in display order
+    #[serde(default)]
+    pub order: Vec<String>,
+}
+
+/// Mattermost ScheduledPost object from API
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MattermostScheduledPost {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub create_at: i64,
+    #[serde(default)]
+    pub user_id: String,
+    pub channel_id: String,
+    #[serde(default)]
+    pub root_id: String,
+    #[serde(default)]
+    pub message: String,
+    #[serde(default)]
+    pub props: Option<HashMap<String, serde_json::Value>>,
+    #[serde(default)]
+    pub file_ids: Option<Vec<String>>,
+    /// When to send the post (milliseconds since epoch)
+    pub scheduled_at: i64,
+    /// Why the server failed to send the post, if it did
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub error_code: String,
+}
+
 /// Mattermost Reaction object from API
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Reaction {
@@ -251,6 +357,12 @@ pub struct CreatePostRequest {
     pub file_ids: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub props: Option<HashMap<String, serde_json::Value>>,
+    /// Client-generated ID echoed back in the `posted` WebSocket event
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pending_post_id: Option<String>,
+    /// Post metadata set by the client, i.e. its priority
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
 }
 
 /// Response containing a list of posts
@@ -270,6 +382,9 @@ pub struct ChannelMember {
     pub channel_id: String,
     pub user_id: String,
     pub roles: String,
+    /// Whether the member holds the admin role of the channel's scheme
+    #[serde(default)]
+    pub scheme_admin: bool,
     pub last_viewed_at: i64,
     pub msg_count: i64,
     pub mention_count: i64,
@@ -277,6 +392,30 @@ pub struct ChannelMember {
     pub last_update_at: i64,
 }
 
+/// Team member object
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamMember {
+    pub team_id: String,
+    pub user_id: String,
+    #[serde(default)]
+    pub roles: String,
+    #[serde(default)]
+    pub delete_at: i64,
+}
+
+/// Role object, listing the permissions the role grants
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MattermostRole {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub display_name: String,
+    #[serde(default)]
+    pub permissions: Vec<String>,
+    #[serde(default)]
+    pub scheme_managed: bool,
+}
+
 // ============================================================================
 // Channel Read State Types
 // ============================================================================
@@ -407,6 +546,8 @@ impl CreatePostRequest {
             root_id: None,
             file_ids: None,
             props: None,
+            pending_post_id: None,
+            metadata: None,
         }
     }
 
@@ -422,11 +563,52 @@ impl CreatePostRequest {
         self
     }
 
+    /// Set the pending post ID used to recognize the post's WebSocket echo
+    pub fn with_pending_post_id(mut self, pending_post_id: String) -> Self {
+        self.pending_post_id = Some(pending_post_id);
+        self
+    }
+
     /// Add custom properties
     pub fn with_props(mut self, props: HashMap<String, serde_json::Value>) -> Self {
         self.props = Some(props);
         self
     }
+
+    /// Set a single custom property, keeping any existing ones
+    pub fn with_prop(mut self, key: impl Into<String>, value: serde_json::Value) -> Self {
+        self.props
+            .get_or_insert_with(HashMap::new)
+            .insert(key.into(), value);
+        self
+    }
+
+    /// Label the post with a priority ("important" or "urgent"), optionally
+    /// asking recipients to acknowledge it
+    pub fn with_priority(mut self, priority: &str, requested_ack: bool) -> Self {
+        self.metadata = Some(serde_json::json!({
+            "priority": {
+                "priority": priority,
+                "requested_ack": requested_ack,
+            }
+        }));
+        self
+    }
+
+    /// Override the displayed username and/or profile picture of the post
+    ///
+    /// Maps to `props.override_username` and `props.override_icon_url`. The server
+    /// only honors these for integrations/bots when `EnablePostUsernameOverride` /
+    /// `EnablePostIconOverride` are enabled in its configuration.
+    pub fn with_overrides(mut self, username: Option<String>, icon_url: Option<String>) -> Self {
+        if let Some(username) = username {
+            self = self.with_prop("override_username", serde_json::json!(username));
+        }
+        if let Some(icon_url) = icon_url {
+            self = self.with_prop("override_icon_url", serde_json::json!(icon_url));
+        }
+        self
+    }
 }
 
 /// User status response from Mattermost API
@@ -562,6 +744,265 @@ impl From<MattermostEmoji> for crate::types::Emoji {
     }
 }
 
+// ============================================================================
+// Integrations
+// ============================================================================
+
+/// Mattermost OAuth 2.0 app object from API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MattermostOAuthApp {
+    /// The client ID of the app
+    pub id: String,
+    /// The ID of the user that registered the app
+    #[serde(default)]
+    pub creator_id: String,
+    /// The time in milliseconds the app was registered
+    #[serde(default)]
+    pub create_at: i64,
+    /// The time in milliseconds the app was last updated
+    #[serde(default)]
+    pub update_at: i64,
+    /// The client secret (only returned to the creator when the app is registered)
+    #[serde(default)]
+    pub client_secret: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub icon_url: String,
+    #[serde(default)]
+    pub callback_urls: Vec<String>,
+    #[serde(default)]
+    pub homepage: String,
+    /// Whether users are authorized without being asked for consent
+    #[serde(default)]
+    pub is_trusted: bool,
+}
+
+/// OAuth app registration request
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateOAuthAppRequest {
+    pub name: String,
+    pub description: String,
+    pub homepage: String,
+    pub callback_urls: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_url: Option<String>,
+    pub is_trusted: bool,
+}
+
+impl From<&crate::types::OAuthAppSettings> for CreateOAuthAppRequest {
+    fn from(settings: &crate::types::OAuthAppSettings) -> Self {
+        Self {
+            name: settings.name.clone(),
+            description: settings.description.clone(),
+            homepage: settings.homepage.clone(),
+            callback_urls: settings.callback_urls.clone(),
+            icon_url: settings.icon_url.clone(),
+            is_trusted: settings.is_trusted,
+        }
+    }
+}
+
+impl From<MattermostOAuthApp> for crate::types::OAuthApp {
+    fn from(mm_app: MattermostOAuthApp) -> Self {
+        crate::types::OAuthApp {
+            id: mm_app.id,
+            name: mm_app.name,
+            description: mm_app.description,
+            homepage: mm_app.homepage,
+            icon_url: Some(mm_app.icon_url).filter(|url| !url.is_empty()),
+            callback_urls: mm_app.callback_urls,
+            client_secret: Some(mm_app.client_secret).filter(|secret| !secret.is_empty()),
+            is_trusted: mm_app.is_trusted,
+            creator_id: mm_app.creator_id,
+            created_at: mm_app.create_at,
+        }
+    }
+}
+
+/// Mattermost user access token object from API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserAccessToken {
+    pub id: String,
+    /// The token value (only returned when the token is created)
+    #[serde(default)]
+    pub token: String,
+    pub user_id: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub is_active: bool,
+}
+
+impl From<UserAccessToken> for crate::types::AccessToken {
+    fn from(mm_token: UserAccessToken) -> Self {
+        crate::types::AccessToken {
+            id: mm_token.id,
+            user_id: mm_token.user_id,
+            description: mm_token.description,
+            is_active: mm_token.is_active,
+            token: Some(mm_token.token).filter(|token| !token.is_empty()),
+        }
+    }
+}
+
+/// Mattermost bot object from API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MattermostBot {
+    /// The ID of the bot's user account
+    pub user_id: String,
+    pub username: String,
+    #[serde(default)]
+    pub display_name: String,
+    #[serde(default)]
+    pub description: String,
+    /// The ID of the user or plugin owning the bot
+    #[serde(default)]
+    pub owner_id: String,
+    #[serde(default)]
+    pub create_at: i64,
+    #[serde(default)]
+    pub update_at: i64,
+    /// The time in milliseconds the bot was disabled (0 if enabled)
+    #[serde(default)]
+    pub delete_at: i64,
+}
+
+impl From<MattermostBot> for crate::types::Bot {
+    fn from(mm_bot: MattermostBot) -> Self {
+        crate::types::Bot {
+            user_id: mm_bot.user_id,
+            username: mm_bot.username,
+            display_name: mm_bot.display_name,
+            description: mm_bot.description,
+            owner_id: mm_bot.owner_id,
+            is_disabled: mm_bot.delete_at != 0,
+            created_at: mm_bot.create_at,
+        }
+    }
+}
+
+// ============================================================================
+// User Groups
+// ============================================================================
+
+/// Mattermost user group object from API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MattermostGroup {
+    pub id: String,
+    /// The mention name; empty or missing for groups that cannot be mentioned
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub display_name: String,
+    #[serde(default)]
+    pub description: String,
+    /// "ldap", "custom" or a plugin-defined source
+    #[serde(default)]
+    pub source: String,
+    #[serde(default)]
+    pub member_count: Option<u32>,
+    /// Whether the group can be mentioned
+    #[serde(default)]
+    pub allow_reference: bool,
+    #[serde(default)]
+    pub delete_at: i64,
+}
+
+impl From<MattermostGroup> for crate::types::Group {
+    fn from(mm_group: MattermostGroup) -> Self {
+        let name = mm_group
+            .name
+            .filter(|name| mm_group.allow_reference && !name.is_empty());
+        crate::types::Group {
+            id: mm_group.id,
+            name,
+            display_name: mm_group.display_name,
+            description: mm_group.description,
+            source: mm_group.source,
+            member_count: mm_group.member_count,
+        }
+    }
+}
+
+/// Response of the group members endpoint
+#[derive(Debug, Clone, Deserialize)]
+pub struct GroupMembersResponse {
+    #[serde(default)]
+    pub members: Vec<MattermostUser>,
+    #[serde(default)]
+    pub total_member_count: u32,
+}
+
+// ============================================================================
+// Slash Commands
+// ============================================================================
+
+/// Mattermost slash command object from API
+///
+/// Only the fields used for autocomplete; built-in commands have no ID.
+/// The autocomplete endpoint only lists commands with autocomplete enabled.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MattermostCommand {
+    #[serde(default)]
+    pub id: String,
+    pub trigger: String,
+    #[serde(default)]
+    pub display_name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub auto_complete_desc: String,
+    #[serde(default)]
+    pub auto_complete_hint: String,
+}
+
+impl From<MattermostCommand> for crate::types::SlashCommand {
+    fn from(mm_command: MattermostCommand) -> Self {
+        let description = if mm_command.auto_complete_desc.is_empty() {
+            mm_command.description
+        } else {
+            mm_command.auto_complete_desc
+        };
+        crate::types::SlashCommand {
+            trigger: mm_command.trigger,
+            description,
+            hint: mm_command.auto_complete_hint,
+            display_name: mm_command.display_name,
+        }
+    }
+}
+
+/// Mattermost slash command response from API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MattermostCommandResponse {
+    /// "ephemeral" or "in_channel"
+    #[serde(default)]
+    pub response_type: String,
+    #[serde(default)]
+    pub text: String,
+    #[serde(default)]
+    pub goto_location: String,
+    #[serde(default)]
+    pub props: Option<serde_json::Value>,
+}
+
+impl From<MattermostCommandResponse> for crate::types::CommandResponse {
+    fn from(mm_response: MattermostCommandResponse) -> Self {
+        let response_type = match mm_response.response_type.as_str() {
+            "in_channel" => crate::types::CommandResponseType::InChannel,
+            _ => crate::types::CommandResponseType::Ephemeral,
+        };
+        crate::types::CommandResponse {
+            response_type,
+            text: mm_response.text,
+            goto_location: Some(mm_response.goto_location).filter(|url| !url.is_empty()),
+            props: mm_response.props,
+        }
+    }
+}
+
 // ============================================================================
 // User Preferences and Notifications
 // ============================================================================
@@ -735,6 +1176,56 @@ mod tests {
         assert_eq!(req.root_id, Some("post456".to_string()));
     }
 
+    #[test]
+    fn test_create_post_request_pending_post_id() {
+        let req = CreatePostRequest::new("channel123".to_string(), "Hello".to_string());
+        let json = serde_json::to_value(&req).unwrap();
+        assert!(json.get("pending_post_id").is_none());
+
+        let req = req.with_pending_post_id("user1:1000".to_string());
+        let json = serde_json::to_value(&req).unwrap();
+        assert_eq!(json["pending_post_id"], "user1:1000");
+    }
+
+    #[test]
+    fn test_create_post_request_priority() {
+        let req = CreatePostRequest::new("channel123".to_string(), "Hello".to_string());
+        assert!(serde_json::to_value(&req)
+            .unwrap()
+            .get("metadata")
+            .is_none());
+
+        let req = req.with_priority("urgent", true);
+        let json = serde_json::to_value(&req).unwrap();
+        assert_eq!(json["metadata"]["priority"]["priority"], "urgent");
+        assert_eq!(json["metadata"]["priority"]["requested_ack"], true);
+    }
+
+    #[test]
+    fn test_oauth_app_conversion_hides_empty_secret() {
+        let json = serde_json::json!({
+            "id": "app1",
+            "creator_id": "user1",
+            "create_at": 1000,
+            "update_at": 1000,
+            "client_secret": "",
+            "name": "Tool",
+            "description": "",
+            "icon_url": "",
+            "callback_urls": ["https://example.com/cb"],
+            "homepage": "https://example.com",
+            "is_trusted": false
+        });
+        let app: crate::types::OAuthApp = serde_json::from_value::<MattermostOAuthApp>(json)
+            .unwrap()
+            .into();
+
+        assert_eq!(app.id, "app1");
+        assert_eq!(app.client_secret, None);
+        assert_eq!(app.icon_url, None);
+        assert_eq!(app.created_at, 1000);
+    }
+
     #[test]
     fn test_login_request_serialization() {
         let login = LoginRequest {
@@ -822,7 +1313,7 @@ mod tests {
         assert_eq!(error.message, "Invalid login credentials");
         assert_eq!(error.request_id, "abc123");
         assert_eq!(error.status_code, 401);
-        assert_eq!(error.is_oauth, false);
+        assert!(!error.is_oauth);
     }
 
     #[test]
@@ -839,6 +1330,6 @@ mod tests {
         assert_eq!(error.message, "Failed to create post");
         assert_eq!(error.request_id, ""); // default value
         assert_eq!(error.status_code, 500);
-        assert_eq!(error.is_oauth, false); // default value
+        assert!(!error.is_oauth); // default value
     }
 }
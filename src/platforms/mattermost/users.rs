@@ -1,8 +1,20 @@
-use crate::error::Result;
+use reqwest::multipart;
+
+use crate::error::{Error, ErrorCode, Result};
+use crate::types::ProfileUpdate;
 
 use super::client::MattermostClient;
 use super::types::MattermostUser;
 
+/// A profile image and the ETag the server sent with it
+#[derive(Debug, Clone)]
+pub struct UserImage {
+    /// The raw image bytes
+    pub data: Vec<u8>,
+    /// The image's ETag, used to revalidate it once its cache entry expires
+    pub etag: Option<String>,
+}
+
 impl MattermostClient {
     /// Get a user by ID
     ///
@@ -63,6 +75,148 @@ impl MattermostClient {
         let response = self.post("/users/ids", &user_ids).await?;
         self.handle_response(response).await
     }
+
+    /// Get those of multiple users that were modified since a time
+    ///
+    /// # Arguments
+    /// * `user_ids` - A list of user IDs to check
+    /// * `since` - Unix timestamp in milliseconds
+    ///
+    /// # Returns
+    /// A Result containing the users updated after `since` or an Error
+    pub async fn get_users_modified_since(
+        &self,
+        user_ids: &[String],
+        since: i64,
+    ) -> Result<Vec<MattermostUser>> {
+        let endpoint = format!("/users/ids?since={since}");
+        let response = self.post(&endpoint, &user_ids).await?;
+        self.handle_response(response).await
+    }
+
+    /// Download a user's profile image
+    ///
+    /// # Arguments
+    /// * `user_id` - The ID of the user
+    ///
+    /// # Returns
+    /// A Result containing the raw image bytes or an Error
+    pub async fn get_user_image(&self, user_id: &str) -> Result<Vec<u8>> {
+        let image = self.get_user_image_if_modified(user_id, None).await?;
+        Ok(image.map(|image| image.data).unwrap_or_default())
+    }
+
+    /// Download a user's profile image unless it still has an ETag
+    ///
+    /// # Arguments
+    /// * `user_id` - The ID of the user
+    /// * `etag` - The ETag of the image already held, if any
+    ///
+    /// # Returns
+    /// A Result containing the image, None if it still has the given ETag, or an Error
+    pub async fn get_user_image_if_modified(
+        &self,
+        user_id: &str,
+        etag: Option<&str>,
+    ) -> Result<Option<UserImage>> {
+        let url = self.api_url(&format!("/users/{user_id}/image"));
+        let mut request = self.http_client().get(&url);
+        if let Some(token) = self.get_token().await {
+            request = request.bearer_auth(token);
+        }
+        if let Some(etag) = etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        let response = self.send_request(request, "GET").await?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_MODIFIED && etag.is_some() {
+            return Ok(None);
+        }
+        if !status.is_success() {
+            let error_code = match status.as_u16() {
+                404 => ErrorCode::NotFound,
+                401 => ErrorCode::AuthenticationFailed,
+                403 => ErrorCode::PermissionDenied,
+                _ => ErrorCode::NetworkError,
+            };
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(Error::new(
+                error_code,
+                format!("Failed to download profile image: {error_text}"),
+            )
+            .with_http_status(status.as_u16()));
+        }
+
+        let etag = response
+            .headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let data = response.bytes().await.map(|b| b.to_vec()).map_err(|e| {
+            Error::new(
+                ErrorCode::NetworkError,
+                format!("Failed to read profile image data: {e}"),
+            )
+        })?;
+        Ok(Some(UserImage { data, etag }))
+    }
+
+    /// Update fields of a user's profile
+    ///
+    /// # Arguments
+    /// * `user_id` - The ID of the user
+    /// * `update` - The fields to change; unset fields are left as they are
+    ///
+    /// # Returns
+    /// A Result containing the updated user or an Error
+    ///
+    /// # API Endpoint
+    /// `PUT /api/v4/users/{user_id}/patch`
+    pub async fn patch_user(
+        &self,
+        user_id: &str,
+        update: &ProfileUpdate,
+    ) -> Result<MattermostUser> {
+        let endpoint = format!("/users/{user_id}/patch");
+        let response = self.put(&endpoint, update).await?;
+        let user: MattermostUser = self.handle_response(response).await?;
+        self.invalidate_user_cache(user_id).await;
+        Ok(user)
+    }
+
+    /// Upload a new profile image for a user
+    ///
+    /// # Arguments
+    /// * `user_id` - The ID of the user
+    /// * `image` - The image data (PNG, JPEG, GIF or BMP)
+    ///
+    /// # Returns
+    /// A Result indicating success or failure
+    ///
+    /// # API Endpoint
+    /// `POST /api/v4/users/{user_id}/image`
+    pub async fn set_user_image(&self, user_id: &str, image: Vec<u8>) -> Result<()> {
+        let form = multipart::Form::new().part(
+            "image",
+            multipart::Part::bytes(image).file_name("profile".to_string()),
+        );
+
+        let url = self.api_url(&format!("/users/{user_id}/image"));
+        let mut request = self.http_client().post(&url);
+        if let Some(token) = self.get_token().await {
+            request = request.bearer_auth(token);
+        }
+
+        let response = self.send_request(request.multipart(form), "POST").await?;
+        let _: serde_json::Value = self.handle_upload_response(response).await?;
+        // The new image has a new ETag, so revalidation downloads it
+        self.invalidate_user_cache(user_id).await;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -83,4 +237,29 @@ mod tests {
             "https://mattermost.example.com/api/v4/users/me"
         );
     }
+
+    #[cfg(feature = "simulation")]
+    #[tokio::test]
+    async fn test_patch_user() {
+        let server = crate::simulation::add_server("patchuser.sim");
+        server.set_route(
+            "PUT",
+            "/users/user1/patch",
+            200,
+            r#"{"id":"user1","username":"alice","nickname":"ali","create_at":0,"update_at":5,"delete_at":0}"#,
+        );
+        server.set_route("POST", "/users/user1/image", 200, r#"{"status":"OK"}"#);
+
+        let client = MattermostClient::new("http://patchuser.sim").unwrap();
+        let update = ProfileUpdate::new().with_nickname("ali");
+        let user = client.patch_user("user1", &update).await.unwrap();
+        assert_eq!(user.nickname, "ali");
+        client.set_user_image("user1", vec![1, 2, 3]).await.unwrap();
+
+        server.set_route("PUT", "/users/user2/patch", 403, r#"{"message":"no"}"#);
+        let err = client.patch_user("user2", &update).await.unwrap_err();
+        assert_eq!(err.http_status(), Some(403));
+
+        crate::simulation::remove_server("patchuser.sim");
+    }
 }
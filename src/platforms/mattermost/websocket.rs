@@ -1,19 +1,37 @@
 use futures::{stream::SplitSink, SinkExt, StreamExt};
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::net::TcpStream;
-use tokio::sync::{mpsc, Mutex};
-use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::{mpsc, Mutex, Notify};
+use tokio_rustls::rustls::ClientConfig;
+use tokio_tungstenite::{
+    client_async_tls_with_config, tungstenite::Message, Connector, MaybeTlsStream, WebSocketStream,
+};
+use tracing::Instrument;
 
+use crate::capture::{self, Direction};
+use crate::dns::Resolver;
 use crate::error::{Error, ErrorCode, Result};
 use crate::platforms::platform_trait::PlatformEvent;
+use crate::proxy::Proxy;
+use crate::types::{ConnectionHealth, EventFilter};
 
+use super::endpoints::Endpoints;
 use super::types::{
     MattermostChannel, MattermostPost, WebSocketAuthChallenge, WebSocketAuthData,
     WebSocketAuthResponse, WebSocketEvent,
 };
 
+/// Byte stream a WebSocket connection runs over
+pub(crate) trait Transport: AsyncRead + AsyncWrite + Send + Unpin {}
+
+impl<T: AsyncRead + AsyncWrite + Send + Unpin> Transport for T {}
+
+/// An open WebSocket connection
+pub(crate) type WsStream = WebSocketStream<MaybeTlsStream<Box<dyn Transport>>>;
+
 /// Type alias for the WebSocket write half
-type WsWriter = SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>;
+type WsWriter = SplitSink<WsStream, Message>;
 
 /// WebSocket connection state
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -30,6 +48,58 @@ pub enum ConnectionState {
     ShuttingDown,
 }
 
+impl From<ConnectionState> for crate::types::ConnectionState {
+    fn from(state: ConnectionState) -> Self {
+        match state {
+            ConnectionState::Disconnected => Self::Disconnected,
+            ConnectionState::Connecting => Self::Connecting,
+            ConnectionState::Connected => Self::Connected,
+            ConnectionState::Reconnecting => Self::Reconnecting,
+            ConnectionState::ShuttingDown => Self::Disconnecting,
+        }
+    }
+}
+
+/// Length of the window messages per second are averaged over
+const EVENT_RATE_WINDOW_SECS: i64 = 10;
+
+/// Counts of messages received in each of the last seconds
+#[derive(Debug, Default)]
+struct EventRate {
+    /// (second since epoch, messages received in it), oldest first
+    buckets: std::collections::VecDeque<(i64, u32)>,
+}
+
+impl EventRate {
+    /// Count a message received at `now` (ms since epoch)
+    fn record(&mut self, now: i64) {
+        let second = now / 1000;
+        match self.buckets.back_mut() {
+            Some((last, count)) if *last == second => *count += 1,
+            _ => self.buckets.push_back((second, 1)),
+        }
+        while self
+            .buckets
+            .front()
+            .is_some_and(|(first, _)| *first <= second - EVENT_RATE_WINDOW_SECS)
+        {
+            self.buckets.pop_front();
+        }
+    }
+
+    /// Messages per second over the window ending at `now` (ms since epoch)
+    fn per_sec(&self, now: i64) -> f64 {
+        let second = now / 1000;
+        let count: u32 = self
+            .buckets
+            .iter()
+            .filter(|(bucket, _)| *bucket > second - EVENT_RATE_WINDOW_SECS)
+            .map(|(_, count)| count)
+            .sum();
+        f64::from(count) / EVENT_RATE_WINDOW_SECS as f64
+    }
+}
+
 /// Configuration for WebSocket connection
 #[derive(Debug, Clone)]
 pub struct WebSocketConfig {
@@ -49,6 +119,12 @@ pub struct WebSocketConfig {
     pub max_reconnect_delay_ms: u64,
     /// Backoff multiplier for exponential backoff (default: 2.0)
     pub reconnect_backoff_multiplier: f64,
+    /// Resolve the IDs of membership events to full users and channels
+    /// before they are delivered (default: false)
+    pub enrich_events: bool,
+    /// Fetch the posts changed while the connection was down after a
+    /// reconnect, and report them as the events that were missed (default: true)
+    pub resync_on_reconnect: bool,
 }
 
 impl Default for WebSocketConfig {
@@ -61,10 +137,53 @@ impl Default for WebSocketConfig {
             initial_reconnect_delay_ms: 1000,
             max_reconnect_delay_ms: 60000,
             reconnect_backoff_multiplier: 2.0,
+            enrich_events: false,
+            resync_on_reconnect: true,
         }
     }
 }
 
+/// Sequence numbers of received events, for gap detection
+///
+/// The server numbers the events of a connection from 0, starting with the
+/// `hello` event; a jump in the numbers means events were dropped.
+#[derive(Debug, Default)]
+struct SequenceTracker {
+    /// Sequence number of the last event
+    last_seq: i64,
+    /// When the last event was received (ms since epoch)
+    last_received_at: i64,
+    /// When the last event before the earliest unhandled gap was received
+    /// (ms since epoch)
+    gap_since: Option<i64>,
+}
+
+impl SequenceTracker {
+    /// Record a received event
+    ///
+    /// # Returns
+    /// The first and last sequence numbers missed before the event, if any
+    fn record(&mut self, event: &str, seq: i64, received_at: i64) -> Option<(i64, i64)> {
+        if event == "hello" {
+            // A new connection numbers its events from scratch
+            self.last_seq = seq;
+            self.last_received_at = received_at;
+            return None;
+        }
+        if seq <= 0 {
+            return None;
+        }
+
+        let gap = (seq > self.last_seq + 1).then_some((self.last_seq + 1, seq - 1));
+        if gap.is_some() && self.last_received_at > 0 {
+            self.gap_since.get_or_insert(self.last_received_at);
+        }
+        self.last_seq = self.last_seq.max(seq);
+        self.last_received_at = received_at;
+        gap
+    }
+}
+
 /// WebSocket connection manager for Mattermost
 pub struct WebSocketManager {
     /// URL for the WebSocket connection
@@ -83,12 +202,123 @@ pub struct WebSocketManager {
     shutdown_tx: Option<mpsc::Sender<()>>,
     /// Sequence number for WebSocket messages
     seq_number: Arc<Mutex<i64>>,
-    /// Last received sequence number for gap detection
-    last_received_seq: Arc<Mutex<i64>>,
+    /// Sequence numbers of received events, for gap detection
+    sequence: Arc<Mutex<SequenceTracker>>,
     /// Current connection state
     connection_state: Arc<Mutex<ConnectionState>>,
     /// Current number of reconnection attempts
     reconnect_attempts: Arc<Mutex<u32>>,
+    /// Number of successful reconnections
+    reconnects: Arc<AtomicU64>,
+    /// When the server was last heard from (ms since epoch)
+    last_message_at: Arc<AtomicI64>,
+    /// When the server was last heard from before the last reconnect (ms since epoch)
+    resync_since: Arc<AtomicI64>,
+    /// When the server last answered a ping (ms since epoch)
+    last_pong_at: Arc<AtomicI64>,
+    /// Delay before the current reconnection attempt (ms, 0 while connected)
+    backoff_ms: Arc<AtomicU64>,
+    /// Rate of messages received
+    event_rate: Arc<std::sync::Mutex<EventRate>>,
+    /// Server URLs to choose from when reconnecting, if there are several
+    endpoints: Option<Arc<Endpoints>>,
+    /// HTTP or SOCKS5 proxy to connect through, if any
+    proxy: Option<Proxy>,
+    /// Resolver for direct connections
+    resolver: Resolver,
+    /// TLS configuration, if not the default
+    tls: Option<Arc<ClientConfig>>,
+    /// Notified whenever an event is queued
+    event_signal: Arc<Notify>,
+    /// Events dropped before they are queued
+    event_filter: Arc<std::sync::RwLock<EventFilter>>,
+}
+
+/// Open a WebSocket connection, through a proxy if one is given
+async fn open_websocket(
+    ws_url: &str,
+    proxy: Option<&Proxy>,
+    resolver: &Resolver,
+    tls: Option<&Arc<ClientConfig>>,
+) -> std::result::Result<WsStream, String> {
+    #[cfg(feature = "simulation")]
+    if let Some(server) = crate::simulation::server_for(ws_url) {
+        return server.open_websocket(ws_url).await;
+    }
+
+    let url = url::Url::parse(ws_url).map_err(|e| e.to_string())?;
+    let host = url.host_str().ok_or("WebSocket URL has no host")?;
+    let port = url
+        .port_or_known_default()
+        .ok_or("WebSocket URL has no port")?;
+    let tcp = match proxy {
+        Some(proxy) => proxy.connect(host, port).await,
+        None => resolver.connect(host, port).await,
+    }
+    .map_err(|e| e.message)?;
+    let tcp: Box<dyn Transport> = Box::new(tcp);
+    let connector = tls.map(|tls| Connector::Rustls(Arc::clone(tls)));
+    client_async_tls_with_config(ws_url, tcp, None, connector)
+        .await
+        .map(|(stream, _)| stream)
+        .map_err(|e| e.to_string())
+}
+
+/// Convert an HTTP(S) server URL to the server's WebSocket URL
+fn websocket_url(base_url: &str) -> String {
+    let ws_url = base_url
+        .replace("https://", "wss://")
+        .replace("http://", "ws://");
+    format!("{ws_url}/api/v4/websocket")
+}
+
+/// Record a frame in the debug capture, if one is running
+fn capture_frame(direction: Direction, url: &str, frame: &Message) {
+    if !capture::is_capturing() {
+        return;
+    }
+    let (opcode, payload): (&str, &[u8]) = match frame {
+        Message::Text(text) => ("text", text.as_bytes()),
+        Message::Binary(data) => ("binary", data),
+        Message::Ping(data) => ("ping", data),
+        Message::Pong(data) => ("pong", data),
+        Message::Close(close) => ("close", close.as_ref().map_or(&[], |c| c.reason.as_bytes())),
+        Message::Frame(_) => return,
+    };
+    capture::record_frame(direction, url, opcode, payload);
+}
+
+/// Record a frame about to be sent in the debug capture, if one is running
+fn sending(url: &str, frame: Message) -> Message {
+    capture_frame(Direction::Sent, url, &frame);
+    frame
+}
+
+/// Queue a connection state change for the client
+///
+/// # Arguments
+/// * `attempt` - Reconnection attempt number (from 1) when reconnecting
+/// * `reason` - Why the connection was lost, when disconnected
+fn emit_state(
+    event_tx: &mpsc::Sender<PlatformEvent>,
+    event_signal: &Notify,
+    state: ConnectionState,
+    attempt: Option<u32>,
+    reason: Option<String>,
+) {
+    match state {
+        ConnectionState::Connected => tracing::info!("WebSocket reconnected"),
+        ConnectionState::Reconnecting => tracing::info!(attempt, "WebSocket reconnecting"),
+        _ => tracing::warn!(reason, "WebSocket disconnected"),
+    }
+    let event = PlatformEvent::ConnectionStateChanged {
+        state: state.into(),
+        attempt,
+        reason,
+    };
+    if event_tx.try_send(event).is_ok() {
+        event_signal.notify_one();
+    }
 }
 
 impl WebSocketManager {
@@ -109,10 +339,7 @@ impl WebSocketManager {
     /// * `config` - WebSocket configuration
     pub fn with_config(base_url: &str, token: String, config: WebSocketConfig) -> Self {
         // Convert HTTP(S) URL to WebSocket URL
-        let ws_url = base_url
-            .replace("https://", "wss://")
-            .replace("http://", "ws://");
-        let ws_url = format!("{ws_url}/api/v4/websocket");
+        let ws_url = websocket_url(base_url);
 
         // Create bounded channel for events with configured size
         let (event_tx, event_rx) = mpsc::channel(config.max_queue_size);
@@ -126,12 +353,116 @@ impl WebSocketManager {
             ws_writer: Arc::new(Mutex::new(None)),
             shutdown_tx: None,
             seq_number: Arc::new(Mutex::new(1)),
-            last_received_seq: Arc::new(Mutex::new(0)),
+            sequence: Arc::new(Mutex::new(SequenceTracker::default())),
             connection_state: Arc::new(Mutex::new(ConnectionState::Disconnected)),
             reconnect_attempts: Arc::new(Mutex::new(0)),
+            reconnects: Arc::new(AtomicU64::new(0)),
+            last_message_at: Arc::new(AtomicI64::new(0)),
+            resync_since: Arc::new(AtomicI64::new(0)),
+            last_pong_at: Arc::new(AtomicI64::new(0)),
+            backoff_ms: Arc::new(AtomicU64::new(0)),
+            event_rate: Arc::default(),
+            endpoints: None,
+            proxy: None,
+            resolver: Resolver::default(),
+            tls: None,
+            event_signal: Arc::new(Notify::new()),
+            event_filter: Arc::default(),
         }
     }
 
+    /// Choose among the server's URLs before each reconnection attempt
+    ///
+    /// The first reachable URL is used, so the connection can move between
+    /// endpoints (e.g. internal and external addresses) as the network changes.
+    pub fn with_endpoints(mut self, endpoints: Arc<Endpoints>) -> Self {
+        self.endpoints = Some(endpoints);
+        self
+    }
+
+    /// Connect through a proxy, which also resolves the server's host name
+    pub fn with_proxy(mut self, proxy: Option<Proxy>) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
+    /// Resolve the server's host name with the given resolver
+    pub fn with_resolver(mut self, resolver: Resolver) -> Self {
+        self.resolver = resolver;
+        self
+    }
+
+    /// Use a custom TLS configuration for `wss://` connections
+    pub fn with_tls(mut self, tls: Option<Arc<ClientConfig>>) -> Self {
+        self.tls = tls;
+        self
+    }
+
+    /// Enrich membership events with users and channels (see
+    /// `WebSocketConfig::enrich_events`)
+    pub fn with_event_enrichment(mut self, enabled: bool) -> Self {
+        self.config.enrich_events = enabled;
+        self
+    }
+
+    /// Drop the events the given filter rejects before they are queued
+    ///
+    /// The filter is shared, so changes to it apply to the running connection.
+    pub fn with_event_filter(mut self, event_filter: Arc<std::sync::RwLock<EventFilter>>) -> Self {
+        self.event_filter = event_filter;
+        self
+    }
+
+    /// Notify the given signal whenever an event is queued
+    pub fn with_event_signal(mut self, event_signal: Arc<Notify>) -> Self {
+        self.event_signal = event_signal;
+        self
+    }
+
+    /// Whether membership events are to be enriched with users and channels
+    pub fn enriches_events(&self) -> bool {
+        self.config.enrich_events
+    }
+
+    /// Report missed posts after a reconnect (see
+    /// `WebSocketConfig::resync_on_reconnect`)
+    pub fn with_resync(mut self, enabled: bool) -> Self {
+        self.config.resync_on_reconnect = enabled;
+        self
+    }
+
+    /// Whether missed posts are to be reported after a reconnect
+    pub fn resyncs_on_reconnect(&self) -> bool {
+        self.config.resync_on_reconnect
+    }
+
+    /// When the server was last heard from before the last reconnect
+    ///
+    /// Events after this time (milliseconds since epoch, client clock) may
+    /// have been lost. None before the first reconnect.
+    pub fn resync_since(&self) -> Option<i64> {
+        match self.resync_since.load(Ordering::SeqCst) {
+            0 => None,
+            since => Some(since),
+        }
+    }
+
+    /// When the last event before a sequence gap was received, clearing it
+    ///
+    /// Events after this time (milliseconds since epoch, client clock) may
+    /// have been lost. None if no gap was detected since the last call.
+    pub async fn take_gap_since(&self) -> Option<i64> {
+        self.sequence.lock().await.gap_since.take()
+    }
+
+    /// Number of times the connection was re-established after being lost
+    ///
+    /// Events sent while the connection was down are lost, so state derived
+    /// from events should be refreshed when this changes.
+    pub fn reconnect_count(&self) -> u64 {
+        self.reconnects.load(Ordering::SeqCst)
+    }
+
     /// Send typing indicator to a channel
     ///
     /// # Arguments
@@ -203,6 +534,27 @@ impl WebSocketManager {
         *self.connection_state.lock().await
     }
 
+    /// Health of the connection
+    pub async fn health(&self) -> ConnectionHealth {
+        let now = chrono::Utc::now().timestamp_millis();
+        let ms_ago = |at: &AtomicI64| match at.load(Ordering::SeqCst) {
+            0 => None,
+            at => Some((now - at).max(0) as u64),
+        };
+        let state = self.get_connection_state().await;
+        ConnectionHealth {
+            state: state.into(),
+            last_pong_ms_ago: ms_ago(&self.last_pong_at),
+            last_message_ms_ago: ms_ago(&self.last_message_at),
+            reconnect_attempts: *self.reconnect_attempts.lock().await,
+            reconnects: self.reconnect_count(),
+            backoff_ms: (state == ConnectionState::Reconnecting)
+                .then(|| self.backoff_ms.load(Ordering::SeqCst))
+                .filter(|delay| *delay > 0),
+            events_per_sec: self.event_rate.lock().unwrap().per_sec(now),
+        }
+    }
+
     /// Set the connection state
     async fn set_connection_state(&self, state: ConnectionState) {
         *self.connection_state.lock().await = state;
@@ -243,7 +595,7 @@ impl WebSocketManager {
     async fn send_ws_message(&self, message: Message) -> Result<()> {
         let mut writer = self.ws_writer.lock().await;
         if let Some(ws) = writer.as_mut() {
-            ws.send(message).await.map_err(|e| {
+            ws.send(sending(&self.ws_url, message)).await.map_err(|e| {
                 Error::new(
                     ErrorCode::NetworkError,
                     format!("Failed to send WebSocket message: {e}"),
@@ -273,7 +625,15 @@ impl WebSocketManager {
     pub async fn connect(&mut self) -> Result<()> {
         self.set_connection_state(ConnectionState::Connecting).await;
 
-        let (ws_stream, _) = connect_async(&self.ws_url).await.map_err(|e| {
+        let ws_stream = open_websocket(
+            &self.ws_url,
+            self.proxy.as_ref(),
+            &self.resolver,
+            self.tls.as_ref(),
+        )
+        .await
+        .map_err(|e| {
+            tracing::warn!(error = %e, "WebSocket connection failed");
             // Set state back to disconnected on failure
             let state = self.connection_state.clone();
             tokio::spawn(async move {
@@ -307,9 +667,12 @@ impl WebSocketManager {
             Error::new(ErrorCode::Unknown, format!("Failed to serialize auth: {e}"))
         })?;
 
-        write.send(Message::Text(auth_msg)).await.map_err(|e| {
-            Error::new(ErrorCode::NetworkError, format!("Failed to send auth: {e}"))
-        })?;
+        write
+            .send(sending(&self.ws_url, Message::Text(auth_msg)))
+            .await
+            .map_err(|e| {
+                Error::new(ErrorCode::NetworkError, format!("Failed to send auth: {e}"))
+            })?;
 
         // Store the write half for bidirectional communication
         *self.ws_writer.lock().await = Some(write);
@@ -323,6 +686,7 @@ impl WebSocketManager {
 
         // Mark as connected after successful authentication challenge sent
         self.set_connection_state(ConnectionState::Connected).await;
+        tracing::info!("WebSocket connected");
 
         // Reset reconnection counter on successful connection
         self.reset_reconnect_attempts().await;
@@ -333,60 +697,85 @@ impl WebSocketManager {
 
         // Clone references for the spawned task
         let event_tx = self.event_tx.clone();
+        let event_signal = Arc::clone(&self.event_signal);
+        let event_filter = Arc::clone(&self.event_filter);
         let connection_state = Arc::clone(&self.connection_state);
         let ws_writer = Arc::clone(&self.ws_writer);
-        let last_received_seq = Arc::clone(&self.last_received_seq);
+        let sequence = Arc::clone(&self.sequence);
         let reconnect_attempts = Arc::clone(&self.reconnect_attempts);
+        let reconnects = Arc::clone(&self.reconnects);
+        let last_message_at = Arc::clone(&self.last_message_at);
+        let resync_since = Arc::clone(&self.resync_since);
+        let last_pong_at = Arc::clone(&self.last_pong_at);
+        let backoff_ms = Arc::clone(&self.backoff_ms);
+        let event_rate = Arc::clone(&self.event_rate);
+        last_message_at.store(chrono::Utc::now().timestamp_millis(), Ordering::SeqCst);
         let ping_interval = std::time::Duration::from_secs(self.config.ping_interval_secs);
 
         // Clone config and connection info for reconnection
         let config = self.config.clone();
-        let ws_url = self.ws_url.clone();
+        let mut ws_url = self.ws_url.clone();
+        let endpoints = self.endpoints.clone();
+        let proxy = self.proxy.clone();
+        let resolver = self.resolver.clone();
+        let tls = self.tls.clone();
         let token = self.token.clone();
         let seq_number = Arc::clone(&self.seq_number);
 
         // Spawn a task to handle incoming messages with automatic reconnection
+        let span = tracing::info_span!("websocket", url = %self.ws_url);
         tokio::spawn(async move {
             let mut read = read; // Make read mutable for the task
             let mut ping_timer = tokio::time::interval(ping_interval);
             ping_timer.tick().await; // Skip first immediate tick
             let mut current_shutdown_rx = shutdown_rx;
 
-            loop {
+            let reason = loop {
                 tokio::select! {
                     // Handle incoming WebSocket messages
                     msg = read.next() => {
+                        if let Some(Ok(frame)) = &msg {
+                            capture_frame(Direction::Received, &ws_url, frame);
+                        }
                         match msg {
                             Some(Ok(Message::Text(text))) => {
-                                let _ = Self::handle_message(text, &event_tx, &last_received_seq).await;
+                                let now = chrono::Utc::now().timestamp_millis();
+                                last_message_at.store(now, Ordering::SeqCst);
+                                event_rate.lock().unwrap().record(now);
+                                if let Err(e) = Self::handle_message(text, &event_tx, &event_signal, &event_filter, &sequence).await {
+                                    tracing::debug!(error = %e.message, "Ignored WebSocket message");
+                                }
                             }
                             Some(Ok(Message::Ping(data))) => {
                                 // Respond to ping with pong
                                 if let Some(writer) = ws_writer.lock().await.as_mut() {
-                                    if writer.send(Message::Pong(data)).await.is_err() {
+                                    if writer.send(sending(&ws_url, Message::Pong(data))).await.is_err() {
                                         *connection_state.lock().await = ConnectionState::Disconnected;
                                         *ws_writer.lock().await = None;
-                                        break;
+                                        break "failed to send pong".to_string();
                                     }
                                 }
                             }
                             Some(Ok(Message::Pong(_))) => {
                                 // Pong received - connection is alive
+                                let now = chrono::Utc::now().timestamp_millis();
+                                last_message_at.store(now, Ordering::SeqCst);
+                                last_pong_at.store(now, Ordering::SeqCst);
                             }
                             Some(Ok(Message::Close(_))) => {
                                 *connection_state.lock().await = ConnectionState::Disconnected;
                                 *ws_writer.lock().await = None;
-                                break;
+                                break "closed by server".to_string();
                             }
-                            Some(Err(_)) => {
+                            Some(Err(e)) => {
                                 *connection_state.lock().await = ConnectionState::Disconnected;
                                 *ws_writer.lock().await = None;
-                                break;
+                                break format!("connection error: {e}");
                             }
                             None => {
                                 *connection_state.lock().await = ConnectionState::Disconnected;
                                 *ws_writer.lock().await = None;
-                                break;
+                                break "connection lost".to_string();
                             }
                             _ => {}
                         }
@@ -394,10 +783,10 @@ impl WebSocketManager {
                     // Send periodic ping to keep connection alive
                     _ = ping_timer.tick() => {
                         if let Some(writer) = ws_writer.lock().await.as_mut() {
-                            if writer.send(Message::Ping(vec![])).await.is_err() {
+                            if writer.send(sending(&ws_url, Message::Ping(vec![]))).await.is_err() {
                                 *connection_state.lock().await = ConnectionState::Disconnected;
                                 *ws_writer.lock().await = None;
-                                break;
+                                break "failed to send ping".to_string();
                             }
                         }
                     }
@@ -405,13 +794,22 @@ impl WebSocketManager {
                     _ = current_shutdown_rx.recv() => {
                         *connection_state.lock().await = ConnectionState::ShuttingDown;
                         *ws_writer.lock().await = None;
-                        break;
+                        break "disconnect requested".to_string();
                     }
                 }
-            }
+            };
 
             // After disconnect, check if we should attempt reconnection
             let current_state = *connection_state.lock().await;
+            if current_state != ConnectionState::ShuttingDown {
+                emit_state(
+                    &event_tx,
+                    &event_signal,
+                    ConnectionState::Disconnected,
+                    None,
+                    Some(reason),
+                );
+            }
 
             // Only attempt reconnection if not shutting down and auto-reconnect is enabled
             if current_state != ConnectionState::ShuttingDown && config.enable_auto_reconnect {
@@ -427,6 +825,15 @@ impl WebSocketManager {
                     if let Some(max_attempts) = config.max_reconnect_attempts {
                         if attempt_num >= max_attempts {
                             *connection_state.lock().await = ConnectionState::Disconnected;
+                            let reason =
+                                format!("gave up after {attempt_num} reconnection attempts");
+                            emit_state(
+                                &event_tx,
+                                &event_signal,
+                                ConnectionState::Disconnected,
+                                None,
+                                Some(reason),
+                            );
                             break;
                         }
                     }
@@ -439,18 +846,31 @@ impl WebSocketManager {
 
                     // Set state to Reconnecting
                     *connection_state.lock().await = ConnectionState::Reconnecting;
+                    emit_state(
+                        &event_tx,
+                        &event_signal,
+                        ConnectionState::Reconnecting,
+                        Some(attempt_num + 1),
+                        None,
+                    );
 
                     // Calculate backoff delay using the WebSocketManager method
                     // We need to create a temporary manager instance to access the method
                     // Actually, we can't access `self` here, so we'll use inline calculation
                     // But we should refactor calculate_backoff_delay to be a static method
                     let delay = Self::calculate_backoff_delay_static(&config, attempt_num);
+                    backoff_ms.store(delay, Ordering::SeqCst);
 
                     tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
 
                     // Attempt to reconnect
-                    match connect_async(&ws_url).await {
-                        Ok((ws_stream, _)) => {
+                    // Switch to the first reachable endpoint, if there are several
+                    if let Some(endpoints) = endpoints.as_ref().filter(|e| e.has_fallbacks()) {
+                        ws_url = websocket_url(&endpoints.select().await);
+                    }
+
+                    match open_websocket(&ws_url, proxy.as_ref(), &resolver, tls.as_ref()).await {
+                        Ok(ws_stream) => {
                             let (mut write, new_read) = ws_stream.split();
 
                             // Send authentication challenge
@@ -470,11 +890,28 @@ impl WebSocketManager {
                             };
 
                             if let Ok(auth_msg) = serde_json::to_string(&auth_challenge) {
-                                if write.send(Message::Text(auth_msg)).await.is_ok() {
+                                if write.send(sending(&ws_url, Message::Text(auth_msg))).await.is_ok() {
                                     // Successfully reconnected and authenticated
                                     *ws_writer.lock().await = Some(write);
                                     *connection_state.lock().await = ConnectionState::Connected;
                                     *reconnect_attempts.lock().await = 0; // Reset counter
+                                    backoff_ms.store(0, Ordering::SeqCst);
+                                    resync_since.store(
+                                        last_message_at.load(Ordering::SeqCst),
+                                        Ordering::SeqCst,
+                                    );
+                                    last_message_at.store(
+                                        chrono::Utc::now().timestamp_millis(),
+                                        Ordering::SeqCst,
+                                    );
+                                    reconnects.fetch_add(1, Ordering::SeqCst);
+                                    emit_state(
+                                        &event_tx,
+                                        &event_signal,
+                                        ConnectionState::Connected,
+                                        None,
+                                        None,
+                                    );
 
                                     // Continue with the new read stream
                                     read = new_read;
@@ -482,47 +919,59 @@ impl WebSocketManager {
                                     ping_timer.tick().await; // Skip first tick
 
                                     // Reconnection successful, return to message loop
-                                    'message_loop: loop {
+                                    let reason = 'message_loop: loop {
                                         tokio::select! {
                                             msg = read.next() => {
+                                                if let Some(Ok(frame)) = &msg {
+                                                    capture_frame(Direction::Received, &ws_url, frame);
+                                                }
                                                 match msg {
                                                     Some(Ok(Message::Text(text))) => {
-                                                        let _ = Self::handle_message(text, &event_tx, &last_received_seq).await;
+                                                        let now = chrono::Utc::now().timestamp_millis();
+                                                        last_message_at.store(now, Ordering::SeqCst);
+                                                        event_rate.lock().unwrap().record(now);
+                                                        if let Err(e) = Self::handle_message(text, &event_tx, &event_signal, &event_filter, &sequence).await {
+                                                            tracing::debug!(error = %e.message, "Ignored WebSocket message");
+                                                        }
                                                     }
                                                     Some(Ok(Message::Ping(data))) => {
                                                         if let Some(writer) = ws_writer.lock().await.as_mut() {
-                                                            if writer.send(Message::Pong(data)).await.is_err() {
+                                                            if writer.send(sending(&ws_url, Message::Pong(data))).await.is_err() {
                                                                 *connection_state.lock().await = ConnectionState::Disconnected;
                                                                 *ws_writer.lock().await = None;
-                                                                break 'message_loop;
+                                                                break 'message_loop "failed to send pong".to_string();
                                                             }
                                                         }
                                                     }
-                                                    Some(Ok(Message::Pong(_))) => {}
+                                                    Some(Ok(Message::Pong(_))) => {
+                                                        let now = chrono::Utc::now().timestamp_millis();
+                                                        last_message_at.store(now, Ordering::SeqCst);
+                                                        last_pong_at.store(now, Ordering::SeqCst);
+                                                    }
                                                     Some(Ok(Message::Close(_))) => {
                                                         *connection_state.lock().await = ConnectionState::Disconnected;
                                                         *ws_writer.lock().await = None;
-                                                        break 'message_loop;
+                                                        break 'message_loop "closed by server".to_string();
                                                     }
-                                                    Some(Err(_)) => {
+                                                    Some(Err(e)) => {
                                                         *connection_state.lock().await = ConnectionState::Disconnected;
                                                         *ws_writer.lock().await = None;
-                                                        break 'message_loop;
+                                                        break 'message_loop format!("connection error: {e}");
                                                     }
                                                     None => {
                                                         *connection_state.lock().await = ConnectionState::Disconnected;
                                                         *ws_writer.lock().await = None;
-                                                        break 'message_loop;
+                                                        break 'message_loop "connection lost".to_string();
                                                     }
                                                     _ => {}
                                                 }
                                             }
                                             _ = ping_timer.tick() => {
                                                 if let Some(writer) = ws_writer.lock().await.as_mut() {
-                                                    if writer.send(Message::Ping(vec![])).await.is_err() {
+                                                    if writer.send(sending(&ws_url, Message::Ping(vec![]))).await.is_err() {
                                                         *connection_state.lock().await = ConnectionState::Disconnected;
                                                         *ws_writer.lock().await = None;
-                                                        break 'message_loop;
+                                                        break 'message_loop "failed to send ping".to_string();
                                                     }
                                                 }
                                             }
@@ -532,13 +981,21 @@ impl WebSocketManager {
                                                 return; // Exit completely
                                             }
                                         }
-                                    }
+                                    };
                                     // If we break from the inner loop, continue the reconnection loop
+                                    emit_state(
+                                        &event_tx,
+                                        &event_signal,
+                                        ConnectionState::Disconnected,
+                                        None,
+                                        Some(reason),
+                                    );
                                 }
                             }
                         }
-                        Err(_) => {
+                        Err(e) => {
                             // Continue to next reconnection attempt
+                            tracing::debug!(error = %e, "Reconnection attempt failed");
                         }
                     }
                 }
@@ -547,7 +1004,7 @@ impl WebSocketManager {
             // Final cleanup - ensure we're marked as disconnected
             *connection_state.lock().await = ConnectionState::Disconnected;
             *ws_writer.lock().await = None;
-        });
+        }.instrument(span));
 
         Ok(())
     }
@@ -556,7 +1013,9 @@ impl WebSocketManager {
     async fn handle_message(
         text: String,
         event_tx: &mpsc::Sender<PlatformEvent>,
-        last_received_seq: &Arc<Mutex<i64>>,
+        event_signal: &Notify,
+        event_filter: &std::sync::RwLock<EventFilter>,
+        sequence: &Mutex<SequenceTracker>,
     ) -> Result<()> {
         // First, try to parse as authentication response
         // Auth responses have a different structure: {"status": "OK", "seq_reply": 1}
@@ -583,22 +1042,71 @@ impl WebSocketManager {
             )
         })?;
 
-        // Check for sequence gaps
-        if ws_event.seq > 0 {
-            let mut last_seq = last_received_seq.lock().await;
-            *last_seq = ws_event.seq;
+        // Check for sequence gaps; the events that were dropped are reported first
+        let gap = sequence.lock().await.record(
+            &ws_event.event,
+            ws_event.seq,
+            chrono::Utc::now().timestamp_millis(),
+        );
+        if let Some((from_seq, to_seq)) = gap {
+            if event_tx
+                .try_send(PlatformEvent::EventsMissed { from_seq, to_seq })
+                .is_ok()
+            {
+                event_signal.notify_one();
+            }
         }
 
-        // Convert WebSocket event to PlatformEvent
-        if let Some(platform_event) = Self::convert_event(ws_event) {
+        // Convert WebSocket event to PlatformEvent, unless it is filtered out
+        let platform_event = Self::convert_event(ws_event).filter(|event| {
+            event_filter
+                .read()
+                .unwrap()
+                .allows(event.category(), event.channel_id())
+        });
+        if let Some(platform_event) = platform_event {
             // Try to send event to channel
             // If full, drop the event silently (non-blocking)
-            let _ = event_tx.try_send(platform_event);
+            if event_tx.try_send(platform_event).is_ok() {
+                event_signal.notify_one();
+            }
         }
 
         Ok(())
     }
 
+    /// Add the delivery context of a `posted` event to the message metadata
+    ///
+    /// The event carries information that is not part of the post itself:
+    /// the channel type, the sender's display name and the IDs of mentioned users.
+    fn with_posted_context(
+        mut message: crate::types::Message,
+        data: &std::collections::HashMap<String, serde_json::Value>,
+    ) -> crate::types::Message {
+        // "mentions" is a JSON-encoded array of user IDs
+        let mentions: Vec<String> = data
+            .get("mentions")
+            .and_then(|m| m.as_str())
+            .and_then(|m| serde_json::from_str(m).ok())
+            .unwrap_or_default();
+
+        if let Some(serde_json::Value::Object(metadata)) = message.metadata.as_mut() {
+            for key in [
+                "channel_type",
+                "channel_name",
+                "channel_display_name",
+                "sender_name",
+            ] {
+                if let Some(value) = data.get(key).and_then(|v| v.as_str()) {
+                    metadata.insert(key.to_string(), serde_json::json!(value));
+                }
+            }
+            metadata.insert("mentions".to_string(), serde_json::json!(mentions));
+        }
+
+        message
+    }
+
     /// Convert a Mattermost WebSocket event to a PlatformEvent
     fn convert_event(ws_event: WebSocketEvent) -> Option<PlatformEvent> {
         match ws_event.event.as_str() {
@@ -609,7 +1117,7 @@ impl WebSocketManager {
                     // Get the string value directly (it's already JSON-encoded)
                     if let Some(post_str) = post_data.as_str() {
                         if let Ok(post) = serde_json::from_str::<MattermostPost>(post_str) {
-                            let message = post.into();
+                            let message = Self::with_posted_context(post.into(), &ws_event.data);
                             return Some(PlatformEvent::MessagePosted(message));
                         }
                     }
@@ -670,6 +1178,8 @@ impl WebSocketManager {
                     .unwrap_or("")
                     .to_string(),
                 channel_id: ws_event.broadcast.channel_id,
+                user: None,
+                channel: None,
             }),
             "user_removed" => Some(PlatformEvent::UserLeftChannel {
                 user_id: ws_event
@@ -679,6 +1189,8 @@ impl WebSocketManager {
                     .unwrap_or("")
                     .to_string(),
                 channel_id: ws_event.broadcast.channel_id,
+                user: None,
+                channel: None,
             }),
             "channel_created" => {
                 // Extract and deserialize the channel data from the event
@@ -1103,6 +1615,29 @@ impl WebSocketManager {
                     None
                 }
             }
+            "shared_channel_remote_updated" => {
+                let channel_id = ws_event
+                    .data
+                    .get("channel_id")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string())
+                    .unwrap_or_else(|| ws_event.broadcast.channel_id.clone());
+                let remote_id = ws_event
+                    .data
+                    .get("remote_id")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .to_string();
+
+                if !channel_id.is_empty() {
+                    Some(PlatformEvent::SharedChannelRemoteUpdated {
+                        channel_id,
+                        remote_id,
+                    })
+                } else {
+                    None
+                }
+            }
             "channel_member_updated" => {
                 let channel_id = ws_event.broadcast.channel_id.clone();
                 let user_id = ws_event
@@ -1116,6 +1651,8 @@ impl WebSocketManager {
                     Some(PlatformEvent::ChannelMemberUpdated {
                         channel_id,
                         user_id,
+                        user: None,
+                        channel: None,
                     })
                 } else {
                     None
@@ -1163,6 +1700,8 @@ impl WebSocketManager {
                     Some(PlatformEvent::MemberRoleUpdated {
                         channel_id,
                         user_id,
+                        user: None,
+                        channel: None,
                     })
                 } else {
                     None
@@ -1375,6 +1914,8 @@ mod tests {
             initial_reconnect_delay_ms: 1000,
             max_reconnect_delay_ms: 60000,
             reconnect_backoff_multiplier: 2.0,
+            enrich_events: false,
+            resync_on_reconnect: true,
         };
         let manager = WebSocketManager::with_config(
             "https://mattermost.example.com",
@@ -1437,6 +1978,27 @@ mod tests {
             assert_eq!(msg.text, "aweff");
             assert_eq!(msg.channel_id, "4ckrmjaeeb8mbpodbmo6bknpge");
             assert_eq!(msg.sender_id, "t1pn9rb63fnpjrqibgriijcx4r");
+            let metadata = msg.metadata.expect("metadata");
+            assert_eq!(metadata["channel_type"], "D");
+            assert_eq!(metadata["sender_name"], "@jay");
+        } else {
+            panic!("Expected MessagePosted event");
+        }
+    }
+
+    #[test]
+    fn test_parse_posted_event_mentions() {
+        let json = r#"{"event": "posted", "data": {"channel_display_name":"Town Square","channel_type":"O","mentions":"[\"user123\"]","post":"{\"id\":\"post123\",\"create_at\":1761422860825,\"update_at\":1761422860825,\"edit_at\":0,\"delete_at\":0,\"user_id\":\"user456\",\"channel_id\":\"channel789\",\"root_id\":\"\",\"message\":\"@alice hi\",\"type\":\"\",\"props\":{},\"hashtags\":\"\",\"metadata\":{}}","sender_name":"@bob","team_id":"team1"}, "broadcast": {"omit_users":null,"user_id":"","channel_id":"channel789","team_id":"team1"}, "seq": 3}"#;
+
+        let ws_event: WebSocketEvent =
+            serde_json::from_str(json).expect("Failed to parse WebSocket event");
+        let platform_event = WebSocketManager::convert_event(ws_event);
+
+        if let Some(PlatformEvent::MessagePosted(msg)) = platform_event {
+            let metadata = msg.metadata.expect("metadata");
+            assert_eq!(metadata["mentions"], serde_json::json!(["user123"]));
+            assert_eq!(metadata["channel_type"], "O");
+            assert_eq!(metadata["channel_display_name"], "Town Square");
         } else {
             panic!("Expected MessagePosted event");
         }
@@ -1460,6 +2022,7 @@ mod tests {
             assert_eq!(msg.text, "awe");
             assert_eq!(msg.channel_id, "4ckrmjaeeb8mbpodbmo6bknpge");
             assert_eq!(msg.sender_id, "t1pn9rb63fnpjrqibgriijcx4r");
+            assert_eq!(super::super::pinned::pinned_state(&msg), Some(false));
         } else {
             panic!("Expected MessageUpdated event");
         }
@@ -1508,7 +2071,7 @@ mod tests {
     fn test_reconnection_config_defaults() {
         let config = WebSocketConfig::default();
 
-        assert_eq!(config.enable_auto_reconnect, true);
+        assert!(config.enable_auto_reconnect);
         assert_eq!(config.max_reconnect_attempts, None);
         assert_eq!(config.initial_reconnect_delay_ms, 1000);
         assert_eq!(config.max_reconnect_delay_ms, 60000);
@@ -1525,9 +2088,11 @@ mod tests {
             initial_reconnect_delay_ms: 500,
             max_reconnect_delay_ms: 30000,
             reconnect_backoff_multiplier: 1.5,
+            enrich_events: false,
+            resync_on_reconnect: true,
         };
 
-        assert_eq!(config.enable_auto_reconnect, false);
+        assert!(!config.enable_auto_reconnect);
         assert_eq!(config.max_reconnect_attempts, Some(5));
         assert_eq!(config.initial_reconnect_delay_ms, 500);
         assert_eq!(config.max_reconnect_delay_ms, 30000);
@@ -1583,6 +2148,8 @@ mod tests {
             initial_reconnect_delay_ms: 500,
             max_reconnect_delay_ms: 10000,
             reconnect_backoff_multiplier: 1.5,
+            enrich_events: false,
+            resync_on_reconnect: true,
         };
 
         // Test with multiplier 1.5
@@ -2118,7 +2685,7 @@ mod tests {
             assert_eq!(thread_id, "thread123");
             assert_eq!(user_id, "user789");
             assert_eq!(channel_id, "channel456");
-            assert_eq!(following, true);
+            assert!(following);
         } else {
             panic!("Expected ThreadFollowChanged event");
         }
@@ -2367,6 +2934,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_shared_channel_remote_updated_event() {
+        let json = r#"{
+            "event": "shared_channel_remote_updated",
+            "data": {
+                "channel_id": "channel123",
+                "remote_id": "remote456"
+            },
+            "broadcast": {
+                "omit_users": null,
+                "user_id": "",
+                "channel_id": "",
+                "team_id": "team1",
+                "connection_id": "",
+                "omit_connection_id": ""
+            },
+            "seq": 62
+        }"#;
+
+        let ws_event: WebSocketEvent =
+            serde_json::from_str(json).expect("Failed to parse WebSocket event");
+        let platform_event = WebSocketManager::convert_event(ws_event);
+
+        if let Some(PlatformEvent::SharedChannelRemoteUpdated {
+            channel_id,
+            remote_id,
+        }) = platform_event
+        {
+            assert_eq!(channel_id, "channel123");
+            assert_eq!(remote_id, "remote456");
+        } else {
+            panic!("Expected SharedChannelRemoteUpdated event");
+        }
+    }
+
     #[test]
     fn test_parse_channel_member_updated_event() {
         let json = r#"{
@@ -2396,6 +2998,7 @@ mod tests {
         if let Some(PlatformEvent::ChannelMemberUpdated {
             channel_id,
             user_id,
+            ..
         }) = platform_event
         {
             assert_eq!(channel_id, "channel123");
@@ -2500,6 +3103,7 @@ mod tests {
         if let Some(PlatformEvent::MemberRoleUpdated {
             channel_id,
             user_id,
+            ..
         }) = platform_event
         {
             assert_eq!(channel_id, "channel456");
@@ -2761,4 +3365,185 @@ mod tests {
         assert_eq!(auth_response.status, "OK");
         assert_eq!(auth_response.seq_reply, 1);
     }
+
+    #[test]
+    fn test_sequence_gaps() {
+        let mut sequence = SequenceTracker::default();
+        assert_eq!(sequence.record("hello", 0, 100), None);
+        assert_eq!(sequence.record("posted", 1, 110), None);
+        assert_eq!(sequence.record("posted", 4, 120), Some((2, 3)));
+        assert_eq!(sequence.record("typing", 5, 130), None);
+        assert_eq!(sequence.record("posted", 7, 140), Some((6, 6)));
+        // The earliest unhandled gap is kept
+        assert_eq!(sequence.gap_since.take(), Some(110));
+
+        // A new connection starts over
+        assert_eq!(sequence.record("hello", 0, 200), None);
+        assert_eq!(sequence.record("posted", 1, 210), None);
+        assert_eq!(sequence.gap_since, None);
+    }
+
+    #[tokio::test]
+    async fn test_gap_reported_before_event() {
+        let (event_tx, mut event_rx) = mpsc::channel(10);
+        let signal = Notify::new();
+        let filter = std::sync::RwLock::new(EventFilter::default());
+        let sequence = Mutex::new(SequenceTracker::default());
+        for (event, seq) in [("hello", 0), ("user_updated", 1), ("user_updated", 3)] {
+            let text = serde_json::json!({
+                "event": event,
+                "data": {"user": {"id": "u1"}},
+                "broadcast": {},
+                "seq": seq
+            })
+            .to_string();
+            WebSocketManager::handle_message(text, &event_tx, &signal, &filter, &sequence)
+                .await
+                .unwrap();
+        }
+
+        let mut events = Vec::new();
+        while let Ok(event) = event_rx.try_recv() {
+            events.push(event);
+        }
+        assert_eq!(events.len(), 3);
+        assert!(matches!(
+            events[1],
+            PlatformEvent::EventsMissed {
+                from_seq: 2,
+                to_seq: 2
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_filtered_events_not_queued() {
+        let (event_tx, mut event_rx) = mpsc::channel(10);
+        let signal = Notify::new();
+        let filter = std::sync::RwLock::new(EventFilter {
+            exclude: vec![crate::types::EventCategory::Typing],
+            channel_ids: None,
+        });
+        let sequence = Mutex::new(SequenceTracker::default());
+        for (event, seq) in [("typing", 1), ("user_updated", 2)] {
+            let text = serde_json::json!({
+                "event": event,
+                "data": {"user_id": "u1", "user": {"id": "u1"}},
+                "broadcast": {"channel_id": "c1"},
+                "seq": seq
+            })
+            .to_string();
+            WebSocketManager::handle_message(text, &event_tx, &signal, &filter, &sequence)
+                .await
+                .unwrap();
+        }
+
+        assert!(matches!(
+            event_rx.try_recv(),
+            Ok(PlatformEvent::UserUpdated { .. })
+        ));
+        assert!(event_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_event_rate() {
+        let mut rate = EventRate::default();
+        for i in 0..20 {
+            rate.record(100_000 + i * 500);
+        }
+        assert_eq!(rate.per_sec(109_999), 2.0);
+        // Seconds leave the window as time passes
+        assert_eq!(rate.per_sec(115_000), 0.8);
+        assert_eq!(rate.per_sec(200_000), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_health_before_connect() {
+        let manager = WebSocketManager::new("https://mattermost.example.com", "token".to_string());
+        let health = manager.health().await;
+        assert_eq!(health.state, crate::types::ConnectionState::Disconnected);
+        assert_eq!(health.last_pong_ms_ago, None);
+        assert_eq!(health.backoff_ms, None);
+        assert_eq!(health.events_per_sec, 0.0);
+    }
+
+    #[cfg(feature = "simulation")]
+    #[tokio::test(start_paused = true)]
+    async fn test_connection_state_events() {
+        use crate::types::ConnectionState as State;
+
+        let server = crate::simulation::add_server("states.sim");
+        let mut ws = WebSocketManager::new("http://states.sim", "token".into());
+        ws.connect().await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+
+        // Attempts 1 and 2 fail (after 1s and 2s), attempt 3 succeeds 4s later
+        server.set_reachable(false);
+        tokio::time::sleep(std::time::Duration::from_millis(3500)).await;
+        server.set_reachable(true);
+        tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+
+        let mut transitions = Vec::new();
+        while let Some(event) = ws.poll_event().await {
+            if let PlatformEvent::ConnectionStateChanged {
+                state,
+                attempt,
+                reason,
+            } = event
+            {
+                transitions.push((state, attempt, reason.is_some()));
+            }
+        }
+        assert_eq!(
+            transitions,
+            vec![
+                (State::Disconnected, None, true),
+                (State::Reconnecting, Some(1), false),
+                (State::Reconnecting, Some(2), false),
+                (State::Reconnecting, Some(3), false),
+                (State::Connected, None, false),
+            ]
+        );
+
+        ws.disconnect().await;
+        crate::simulation::remove_server("states.sim");
+    }
+
+    #[cfg(feature = "simulation")]
+    #[tokio::test(start_paused = true)]
+    async fn test_debug_capture_redacts_token() {
+        crate::simulation::add_server("capture.sim");
+        // Roomy enough for the frames of tests running meanwhile
+        capture::start(capture::CaptureConfig {
+            capacity: 100_000,
+            ..Default::default()
+        })
+        .unwrap();
+        let mut ws = WebSocketManager::new("http://capture.sim", "secret-token".into());
+        ws.connect().await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        ws.disconnect().await;
+        capture::stop();
+
+        let frames: Vec<_> = capture::entries()
+            .into_iter()
+            .filter_map(|entry| match entry {
+                capture::CaptureEntry::Frame {
+                    direction,
+                    url,
+                    payload,
+                    ..
+                } if url.contains("capture.sim") => Some((direction, payload)),
+                _ => None,
+            })
+            .collect();
+        let (direction, auth) = &frames[0];
+        assert_eq!(*direction, Direction::Sent);
+        assert!(auth.contains("authentication_challenge"));
+        assert!(!auth.contains("secret-token"));
+        assert!(frames
+            .iter()
+            .any(|(direction, _)| *direction == Direction::Received));
+        crate::simulation::remove_server("capture.sim");
+    }
 }
@@ -0,0 +1,256 @@
+//! Per-channel message windows
+//!
+//! Keeps the most recent messages of recently viewed channels, so simple
+//! clients can render a channel without managing their own message arrays.
+//! A window is filled from the server when it is first requested and then kept
+//! up to date from message events. Memory stays bounded: a window holds at most
+//! `WINDOW_SIZE` messages, at most `MAX_WINDOWS` windows are kept, and windows
+//! that have not been requested for `WINDOW_IDLE_TIMEOUT` are dropped.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+use crate::types::Message;
+
+/// Number of most recent messages kept per channel
+pub const WINDOW_SIZE: usize = 200;
+
+/// Number of channels a window is kept for
+pub const MAX_WINDOWS: usize = 50;
+
+/// How long a window is kept without being requested
+pub const WINDOW_IDLE_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug)]
+struct Window {
+    /// Messages, oldest first
+    messages: Vec<Message>,
+    /// When the window was last requested
+    last_access: Instant,
+}
+
+impl Window {
+    /// Insert a message, or replace it if it is already in the window
+    fn upsert(&mut self, message: &Message) {
+        if let Some(existing) = self.messages.iter_mut().find(|m| m.id == message.id) {
+            *existing = message.clone();
+            return;
+        }
+        let position = self
+            .messages
+            .partition_point(|m| m.created_at <= message.created_at);
+        self.messages.insert(position, message.clone());
+        if self.messages.len() > WINDOW_SIZE {
+            let excess = self.messages.len() - WINDOW_SIZE;
+            self.messages.drain(..excess);
+        }
+    }
+}
+
+/// Keeps the most recent messages of recently viewed channels
+#[derive(Debug, Clone, Default)]
+pub struct MessageWindows {
+    windows: Arc<RwLock<HashMap<String, Window>>>,
+}
+
+impl MessageWindows {
+    /// Create an empty set of windows
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the messages of a channel's window, oldest first
+    ///
+    /// # Returns
+    /// None if the channel has no window (or it was evicted)
+    pub async fn get(&self, channel_id: &str, now: Instant) -> Option<Vec<Message>> {
+        let mut windows = self.windows.write().await;
+        evict_idle(&mut windows, now);
+        let window = windows.get_mut(channel_id)?;
+        window.last_access = now;
+        Some(window.messages.clone())
+    }
+
+    /// Create or replace a channel's window with messages fetched from the server
+    ///
+    /// Evicts the least recently requested window if too many are kept.
+    pub async fn fill(&self, channel_id: &str, messages: &[Message], now: Instant) {
+        let mut windows = self.windows.write().await;
+        evict_idle(&mut windows, now);
+
+        let mut window = Window {
+            messages: Vec::with_capacity(messages.len().min(WINDOW_SIZE)),
+            last_access: now,
+        };
+        for message in messages {
+            window.upsert(message);
+        }
+        windows.insert(channel_id.to_string(), window);
+
+        while windows.len() > MAX_WINDOWS {
+            let Some(oldest) = windows
+                .iter()
+                .min_by_key(|(_, window)| window.last_access)
+                .map(|(id, _)| id.clone())
+            else {
+                break;
+            };
+            windows.remove(&oldest);
+        }
+    }
+
+    /// Add messages fetched from the server to a channel's window, if it has one
+    pub async fn merge(&self, channel_id: &str, messages: &[Message]) {
+        let mut windows = self.windows.write().await;
+        if let Some(window) = windows.get_mut(channel_id) {
+            for message in messages {
+                window.upsert(message);
+            }
+        }
+    }
+
+    /// Add a newly posted message to its channel's window
+    pub async fn record_posted(&self, message: &Message) {
+        self.merge(&message.channel_id, std::slice::from_ref(message))
+            .await;
+    }
+
+    /// Replace an edited message, if it is in its channel's window
+    ///
+    /// Returns the message as it was before the edit, if it was in the window.
+    pub async fn record_updated(&self, message: &Message) -> Option<Message> {
+        let mut windows = self.windows.write().await;
+        windows
+            .get_mut(&message.channel_id)
+            .and_then(|window| window.messages.iter_mut().find(|m| m.id == message.id))
+            .map(|existing| std::mem::replace(existing, message.clone()))
+    }
+
+    /// Remove a deleted message from its channel's window
+    pub async fn record_deleted(&self, channel_id: &str, message_id: &str) {
+        let mut windows = self.windows.write().await;
+        if let Some(window) = windows.get_mut(channel_id) {
+            window.messages.retain(|m| m.id != message_id);
+        }
+    }
+
+    /// IDs of the channels with a window
+    pub async fn channel_ids(&self) -> Vec<String> {
+        self.windows.read().await.keys().cloned().collect()
+    }
+
+    /// The last update time (ms) of each message in a channel's window
+    ///
+    /// Messages without an `update_at` in their metadata are left out.
+    pub async fn update_times(&self, channel_id: &str) -> HashMap<String, i64> {
+        let windows = self.windows.read().await;
+        let Some(window) = windows.get(channel_id) else {
+            return HashMap::new();
+        };
+        window
+            .messages
+            .iter()
+            .filter_map(|message| {
+                let update_at = message.metadata.as_ref()?.get("update_at")?.as_i64()?;
+                Some((message.id.clone(), update_at))
+            })
+            .collect()
+    }
+
+    /// Drop a channel's window (e.g. when the channel is deleted)
+    pub async fn remove(&self, channel_id: &str) {
+        self.windows.write().await.remove(channel_id);
+    }
+
+    /// Drop all windows (e.g. on disconnect)
+    pub async fn reset(&self) {
+        self.windows.write().await.clear();
+    }
+}
+
+/// Drop windows that have not been requested for `WINDOW_IDLE_TIMEOUT`
+fn evict_idle(windows: &mut HashMap<String, Window>, now: Instant) {
+    windows.retain(|_, window| {
+        now.saturating_duration_since(window.last_access) < WINDOW_IDLE_TIMEOUT
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn message(id: &str, channel_id: &str, created_at: i64) -> Message {
+        let mut message = Message::new(id, format!("message {id}"), "user1", channel_id);
+        message.created_at = Utc.timestamp_millis_opt(created_at).unwrap();
+        message
+    }
+
+    fn ids(messages: &[Message]) -> Vec<&str> {
+        messages.iter().map(|m| m.id.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_window_follows_events() {
+        let windows = MessageWindows::new();
+        let now = Instant::now();
+
+        // Events for channels without a window are ignored
+        windows.record_posted(&message("m0", "ch1", 0)).await;
+        assert!(windows.get("ch1", now).await.is_none());
+
+        windows
+            .fill(
+                "ch1",
+                &[message("m2", "ch1", 2), message("m1", "ch1", 1)],
+                now,
+            )
+            .await;
+        windows.record_posted(&message("m3", "ch1", 3)).await;
+        let mut edited = message("m2", "ch1", 2);
+        edited.text = "edited".to_string();
+        let previous = windows.record_updated(&edited).await;
+        assert_eq!(previous.map(|m| m.text), Some("message m2".to_string()));
+        windows.record_deleted("ch1", "m1").await;
+
+        let window = windows.get("ch1", now).await.unwrap();
+        assert_eq!(ids(&window), vec!["m2", "m3"]);
+        assert_eq!(window[0].text, "edited");
+    }
+
+    #[tokio::test]
+    async fn test_window_is_bounded() {
+        let windows = MessageWindows::new();
+        let now = Instant::now();
+
+        let messages: Vec<Message> = (0..WINDOW_SIZE as i64 + 10)
+            .map(|i| message(&format!("m{i}"), "ch1", i))
+            .collect();
+        windows.fill("ch1", &messages, now).await;
+
+        let window = windows.get("ch1", now).await.unwrap();
+        assert_eq!(window.len(), WINDOW_SIZE);
+        assert_eq!(window[0].id, "m10");
+    }
+
+    #[tokio::test]
+    async fn test_windows_are_evicted() {
+        let windows = MessageWindows::new();
+        let now = Instant::now();
+
+        for i in 0..=MAX_WINDOWS {
+            let at = now + Duration::from_secs(i as u64);
+            windows.fill(&format!("ch{i}"), &[], at).await;
+        }
+        let later = now + Duration::from_secs(MAX_WINDOWS as u64);
+        // The least recently requested window made room for the last one
+        assert!(windows.get("ch0", later).await.is_none());
+        assert!(windows.get("ch1", later).await.is_some());
+
+        // Idle windows are dropped
+        let much_later = later + WINDOW_IDLE_TIMEOUT;
+        assert!(windows.get("ch1", much_later).await.is_none());
+    }
+}
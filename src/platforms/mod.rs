@@ -4,7 +4,9 @@
 /// communication interface for that specific service.
 mod platform_trait;
 
+pub mod matrix;
 pub mod mattermost;
+pub mod xmpp;
 
 // Re-export platform trait and related types
-pub use platform_trait::{Platform, PlatformConfig, PlatformEvent};
+pub use platform_trait::{ChunkSink, Platform, PlatformConfig, PlatformEvent, ProgressSink};
@@ -1,10 +1,24 @@
 //! Platform trait defining the interface all platform adapters must implement
 
 use crate::error::{Error, Result};
+use crate::tls::TlsConfig;
 use crate::types::user::UserStatus;
-use crate::types::{Channel, ConnectionInfo, Message, PlatformCapabilities, Team, User};
+use crate::types::{
+    CacheConfig, Channel, ConnectionInfo, EventCategory, EventFilter, Message,
+    PlatformCapabilities, RetryPolicy, Team, User,
+};
 use async_trait::async_trait;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Notify;
+
+/// Receives the chunks of a streamed download, with the progress including
+/// each chunk; an error aborts the download
+pub type ChunkSink<'a> = dyn FnMut(&[u8], crate::types::TransferProgress) -> Result<()> + Send + 'a;
+
+/// Receives the progress of a transfer; an error aborts the transfer
+pub type ProgressSink<'a> = dyn FnMut(crate::types::TransferProgress) -> Result<()> + Send + 'a;
 
 /// Configuration for connecting to a platform
 #[derive(Debug, Clone)]
@@ -13,12 +27,70 @@ pub struct PlatformConfig {
     pub server: String,
     /// Authentication credentials (e.g., token, username/password)
     pub credentials: HashMap<String, String>,
+    /// Alias of credentials in the credential store, filled into `credentials`
+    /// by `crate::credentials::resolve` for keys not given there
+    pub credentials_alias: Option<String>,
     /// Optional team/workspace/guild identifier
     /// Only applicable for platforms that support organizational hierarchies
     /// (check PlatformCapabilities.has_workspaces)
     pub team_id: Option<String>,
     /// Additional platform-specific configuration
     pub extra: HashMap<String, String>,
+    /// Other URLs of the same server, in order of preference, tried when `server`
+    /// is unreachable (e.g. an external address next to an internal one)
+    pub fallback_servers: Vec<String>,
+    /// Proxy to connect through: an HTTP proxy ("http://host:port") or a
+    /// SOCKS5 proxy ("socks5://host:port") such as Tor, optionally with
+    /// "user:password@" (percent-encoded) before the host
+    ///
+    /// Host names are resolved by the proxy, so Tor onion services (`.onion`)
+    /// are reachable through SOCKS5 and no DNS queries leak to the local
+    /// resolver.
+    pub proxy: Option<String>,
+    /// TLS settings: extra CA certificates, a client certificate, or accepting
+    /// invalid (e.g. self-signed) server certificates
+    pub tls: TlsConfig,
+    /// Host names mapped to the IP address to connect to instead of resolving
+    /// them through DNS (e.g. a staging server under the production name)
+    ///
+    /// Not used for connections through `proxy`, which resolves names itself.
+    pub hosts: HashMap<String, String>,
+    /// File to save the session's user, teams and channels to
+    ///
+    /// When it holds a session for the same server, `connect()` returns at once
+    /// with stale connection info and serves the saved data, then finishes
+    /// connecting in the background and reports the outcome as
+    /// `PlatformEvent::SessionReady` or `PlatformEvent::SessionFailed`.
+    pub startup_cache: Option<String>,
+    /// Largest file (bytes) `download_file()` buffers in memory
+    ///
+    /// Larger files fail with `ErrorCode::FileTooLarge` and must be downloaded
+    /// with `download_file_to_path()`. `None` keeps the platform's default.
+    pub download_spill_threshold: Option<u64>,
+    /// File the offline outbox is saved to
+    ///
+    /// Messages sent while disconnected are queued and sent when the
+    /// connection is back (see `PlatformEvent::MessageSendSucceeded`). With a
+    /// file, queued messages survive a restart; without one they are kept in
+    /// memory only.
+    pub outbox: Option<String>,
+    /// Retries of requests failing with transient errors (network errors,
+    /// 502/503/504 by default)
+    pub retry: RetryPolicy,
+    /// TTLs and size limits of the caches of users, channels, teams and
+    /// other entities, or caching disabled
+    pub cache: CacheConfig,
+    /// Resolve the user and channel IDs of membership events (users joining
+    /// or leaving channels, member updates) to full objects, from the cache
+    /// where possible, so clients need no lookups per event
+    pub enrich_events: bool,
+    /// After the real-time connection is re-established, fetch the posts of
+    /// open channels changed while it was down and report them as the
+    /// message events that were missed
+    pub resync_on_reconnect: bool,
+    /// When real-time events are found to be missing (a gap in their sequence
+    /// numbers), resync the posts of open channels as after a reconnect
+    pub resync_on_gap: bool,
 }
 
 impl PlatformConfig {
@@ -27,8 +99,21 @@ impl PlatformConfig {
         PlatformConfig {
             server: server.into(),
             credentials: HashMap::new(),
+            credentials_alias: None,
             team_id: None,
             extra: HashMap::new(),
+            fallback_servers: Vec::new(),
+            proxy: None,
+            tls: TlsConfig::default(),
+            hosts: HashMap::new(),
+            startup_cache: None,
+            download_spill_threshold: None,
+            outbox: None,
+            retry: RetryPolicy::default(),
+            cache: CacheConfig::default(),
+            enrich_events: false,
+            resync_on_reconnect: true,
+            resync_on_gap: false,
         }
     }
 
@@ -38,12 +123,90 @@ impl PlatformConfig {
         self
     }
 
+    /// Use credentials stored under an alias
+    pub fn with_credentials_alias(mut self, alias: impl Into<String>) -> Self {
+        self.credentials_alias = Some(alias.into());
+        self
+    }
+
     /// Set team/workspace ID
     pub fn with_team(mut self, team_id: impl Into<String>) -> Self {
         self.team_id = Some(team_id.into());
         self
     }
 
+    /// Add a fallback URL for the server
+    pub fn with_fallback_server(mut self, url: impl Into<String>) -> Self {
+        self.fallback_servers.push(url.into());
+        self
+    }
+
+    /// Connect through an HTTP or SOCKS5 proxy
+    pub fn with_proxy(mut self, proxy_url: impl Into<String>) -> Self {
+        self.proxy = Some(proxy_url.into());
+        self
+    }
+
+    /// Use custom TLS settings
+    pub fn with_tls(mut self, tls: TlsConfig) -> Self {
+        self.tls = tls;
+        self
+    }
+
+    /// Render from the previous session's data while connecting
+    pub fn with_startup_cache(mut self, path: impl Into<String>) -> Self {
+        self.startup_cache = Some(path.into());
+        self
+    }
+
+    /// Limit the size of files `download_file()` buffers in memory
+    pub fn with_download_spill_threshold(mut self, bytes: u64) -> Self {
+        self.download_spill_threshold = Some(bytes);
+        self
+    }
+
+    /// Save messages queued while disconnected to a file
+    pub fn with_outbox(mut self, path: impl Into<String>) -> Self {
+        self.outbox = Some(path.into());
+        self
+    }
+
+    /// Retry requests failing with transient errors by a custom policy
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Cache entities with custom TTLs and size limits
+    pub fn with_cache_config(mut self, cache: CacheConfig) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Resolve the IDs of membership events to full user and channel objects
+    pub fn with_event_enrichment(mut self) -> Self {
+        self.enrich_events = true;
+        self
+    }
+
+    /// Don't report missed message events after a reconnect
+    pub fn without_resync(mut self) -> Self {
+        self.resync_on_reconnect = false;
+        self
+    }
+
+    /// Report missed message events when real-time events are skipped
+    pub fn with_gap_resync(mut self) -> Self {
+        self.resync_on_gap = true;
+        self
+    }
+
+    /// Connect to an IP address instead of resolving a host name
+    pub fn with_host_override(mut self, host: impl Into<String>, addr: impl Into<String>) -> Self {
+        self.hosts.insert(host.into(), addr.into());
+        self
+    }
+
     /// Add extra configuration
     pub fn with_extra(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
         self.extra.insert(key.into(), value.into());
@@ -56,6 +219,26 @@ impl PlatformConfig {
 pub enum PlatformEvent {
     /// A new message was posted
     MessagePosted(Message),
+    /// The server echoed a message sent by this client
+    ///
+    /// Reported instead of `MessagePosted`, so clients that already show the
+    /// message (from the send call or optimistically) do not show it twice.
+    /// `pending_id` matches the `pending_post_id` metadata of the sent message.
+    MessageConfirmed {
+        pending_id: String,
+        message: Message,
+    },
+    /// A message queued while disconnected was sent
+    ///
+    /// `outbox_id` matches the `outbox_id` metadata of the placeholder message
+    /// returned when it was queued.
+    MessageSendSucceeded { outbox_id: String, message: Message },
+    /// A message queued while disconnected could not be sent and was dropped
+    MessageSendFailed {
+        outbox_id: String,
+        channel_id: String,
+        error: Error,
+    },
     /// A message was updated/edited
     MessageUpdated(Message),
     /// A message was deleted
@@ -63,6 +246,14 @@ pub enum PlatformEvent {
         message_id: String,
         channel_id: String,
     },
+    /// A message was pinned to its channel; follows the message's MessageUpdated event
+    MessagePinned(Message),
+    /// A message was unpinned from its channel; follows the message's
+    /// MessageUpdated event
+    MessageUnpinned {
+        message_id: String,
+        channel_id: String,
+    },
     /// A user's status changed
     UserStatusChanged {
         user_id: String,
@@ -77,11 +268,34 @@ pub enum PlatformEvent {
     /// A channel was deleted
     ChannelDeleted { channel_id: String },
     /// User joined a channel
-    UserJoinedChannel { user_id: String, channel_id: String },
+    ///
+    /// `user` and `channel` are filled in when event enrichment is enabled
+    /// (`PlatformConfig::enrich_events`), as are those of the other
+    /// membership events.
+    UserJoinedChannel {
+        user_id: String,
+        channel_id: String,
+        user: Option<Box<User>>,
+        channel: Option<Box<Channel>>,
+    },
     /// User left a channel
-    UserLeftChannel { user_id: String, channel_id: String },
+    UserLeftChannel {
+        user_id: String,
+        channel_id: String,
+        user: Option<Box<User>>,
+        channel: Option<Box<Channel>>,
+    },
     /// Connection state changed
-    ConnectionStateChanged(crate::types::connection::ConnectionState),
+    ConnectionStateChanged {
+        state: crate::types::connection::ConnectionState,
+        /// Reconnection attempt number (from 1) when reconnecting
+        attempt: Option<u32>,
+        /// Why the connection was lost, when disconnected
+        reason: Option<String>,
+    },
+    /// Real-time events were dropped: the sequence numbers from `from_seq` to
+    /// `to_seq` were skipped, so state derived from events should be refreshed
+    EventsMissed { from_seq: i64, to_seq: i64 },
     /// A reaction was added to a message
     ReactionAdded {
         message_id: String,
@@ -155,14 +369,30 @@ pub enum PlatformEvent {
     LicenseChanged,
     /// Channel was converted (e.g., public to private)
     ChannelConverted { channel_id: String },
+    /// A shared channel's connection to a remote server/organization changed
+    /// (e.g., the channel was shared with or unshared from a remote)
+    SharedChannelRemoteUpdated {
+        channel_id: String,
+        remote_id: String,
+    },
     /// Channel member was updated
-    ChannelMemberUpdated { channel_id: String, user_id: String },
+    ChannelMemberUpdated {
+        channel_id: String,
+        user_id: String,
+        user: Option<Box<User>>,
+        channel: Option<Box<Channel>>,
+    },
     /// Team was deleted
     TeamDeleted { team_id: String },
     /// Team was updated
     TeamUpdated { team_id: String },
     /// Member role was updated in a channel
-    MemberRoleUpdated { channel_id: String, user_id: String },
+    MemberRoleUpdated {
+        channel_id: String,
+        user_id: String,
+        user: Option<Box<User>>,
+        channel: Option<Box<Channel>>,
+    },
     /// Plugin was disabled
     PluginDisabled { plugin_id: String },
     /// Plugin was enabled
@@ -181,6 +411,142 @@ pub enum PlatformEvent {
     DialogOpened { dialog_id: String },
     /// Role was updated
     RoleUpdated { role_id: String },
+    /// A poll was posted, received votes or was closed
+    PollUpdated(crate::types::Poll),
+    /// A local reminder fell due
+    ReminderDue(crate::types::Reminder),
+    /// A posted message mentions the current user or matches one of their
+    /// keywords; follows the message's MessagePosted event
+    NotificationTriggered {
+        message: Message,
+        reason: crate::types::NotificationReason,
+    },
+    /// The circuit breaker guarding requests to the server changed state
+    CircuitStateChanged(crate::types::CircuitState),
+    /// The client switched to another of the server's URLs
+    ServerEndpointChanged { server_url: String },
+    /// A connection started from cached data was established; live data
+    /// replaces the cached data from now on
+    SessionReady(ConnectionInfo),
+    /// A connection started from cached data could not be established
+    SessionFailed(Error),
+    /// The session expired and could not be renewed automatically; requests
+    /// fail until new credentials are supplied with `Platform::renew_session`
+    SessionExpired(Error),
+    /// Coarse hint: the channel list should be redrawn (channels or teams
+    /// added, removed or changed)
+    ChannelListChanged,
+    /// Coarse hint: unread counts should be redrawn
+    UnreadsChanged,
+    /// Coarse hint: cached user profiles changed
+    ProfileCacheChanged,
+}
+
+impl PlatformEvent {
+    /// The category an event filter sorts the event into
+    ///
+    /// None for events the library raises itself, which are never filtered.
+    pub fn category(&self) -> Option<EventCategory> {
+        use PlatformEvent as E;
+        Some(match self {
+            E::MessagePosted(_)
+            | E::MessageConfirmed { .. }
+            | E::MessageUpdated(_)
+            | E::MessageDeleted { .. }
+            | E::MessagePinned(_)
+            | E::MessageUnpinned { .. }
+            | E::EphemeralMessage { .. }
+            | E::PollUpdated(_) => EventCategory::Messages,
+            E::ReactionAdded { .. } | E::ReactionRemoved { .. } => EventCategory::Reactions,
+            E::UserTyping { .. } => EventCategory::Typing,
+            E::UserStatusChanged { .. } => EventCategory::Presence,
+            E::ChannelCreated(_)
+            | E::ChannelUpdated(_)
+            | E::ChannelDeleted { .. }
+            | E::UserJoinedChannel { .. }
+            | E::UserLeftChannel { .. }
+            | E::DirectChannelAdded { .. }
+            | E::GroupChannelAdded { .. }
+            | E::ChannelConverted { .. }
+            | E::SharedChannelRemoteUpdated { .. }
+            | E::ChannelMemberUpdated { .. }
+            | E::MemberRoleUpdated { .. } => EventCategory::Channels,
+            E::ChannelViewed { .. } | E::PostUnread { .. } | E::ThreadReadChanged { .. } => {
+                EventCategory::ReadState
+            }
+            E::ThreadUpdated { .. } | E::ThreadFollowChanged { .. } => EventCategory::Threads,
+            E::UserAdded { .. } | E::UserUpdated { .. } | E::UserRoleUpdated { .. } => {
+                EventCategory::Users
+            }
+            E::AddedToTeam { .. }
+            | E::LeftTeam { .. }
+            | E::TeamDeleted { .. }
+            | E::TeamUpdated { .. } => EventCategory::Teams,
+            E::PreferenceChanged { .. } | E::PreferencesDeleted { .. } => {
+                EventCategory::Preferences
+            }
+            E::PluginDisabled { .. } | E::PluginEnabled { .. } | E::PluginStatusesChanged => {
+                EventCategory::Plugins
+            }
+            E::ConfigChanged | E::LicenseChanged | E::RoleUpdated { .. } | E::EmojiAdded { .. } => {
+                EventCategory::System
+            }
+            E::MessageSendSucceeded { .. }
+            | E::MessageSendFailed { .. }
+            | E::ConnectionStateChanged { .. }
+            | E::EventsMissed { .. }
+            | E::Response { .. }
+            | E::DialogOpened { .. }
+            | E::ReminderDue(_)
+            | E::NotificationTriggered { .. }
+            | E::CircuitStateChanged(_)
+            | E::ServerEndpointChanged { .. }
+            | E::SessionReady(_)
+            | E::SessionFailed(_)
+            | E::SessionExpired(_)
+            | E::ChannelListChanged
+            | E::UnreadsChanged
+            | E::ProfileCacheChanged => return None,
+        })
+    }
+
+    /// The channel the event is about, if any
+    pub fn channel_id(&self) -> Option<&str> {
+        use PlatformEvent as E;
+        match self {
+            E::MessagePosted(message)
+            | E::MessageConfirmed { message, .. }
+            | E::MessageSendSucceeded { message, .. }
+            | E::MessageUpdated(message)
+            | E::MessagePinned(message)
+            | E::NotificationTriggered { message, .. } => Some(&message.channel_id),
+            E::ChannelCreated(channel) | E::ChannelUpdated(channel) => Some(&channel.id),
+            E::PollUpdated(poll) => Some(&poll.channel_id),
+            E::ReminderDue(reminder) => Some(&reminder.channel_id),
+            E::MessageSendFailed { channel_id, .. }
+            | E::MessageDeleted { channel_id, .. }
+            | E::MessageUnpinned { channel_id, .. }
+            | E::UserTyping { channel_id, .. }
+            | E::ChannelDeleted { channel_id }
+            | E::UserJoinedChannel { channel_id, .. }
+            | E::UserLeftChannel { channel_id, .. }
+            | E::ReactionAdded { channel_id, .. }
+            | E::ReactionRemoved { channel_id, .. }
+            | E::DirectChannelAdded { channel_id }
+            | E::GroupChannelAdded { channel_id }
+            | E::EphemeralMessage { channel_id, .. }
+            | E::ChannelViewed { channel_id, .. }
+            | E::ThreadUpdated { channel_id, .. }
+            | E::ThreadReadChanged { channel_id, .. }
+            | E::ThreadFollowChanged { channel_id, .. }
+            | E::PostUnread { channel_id, .. }
+            | E::ChannelConverted { channel_id }
+            | E::SharedChannelRemoteUpdated { channel_id, .. }
+            | E::ChannelMemberUpdated { channel_id, .. }
+            | E::MemberRoleUpdated { channel_id, .. } => Some(channel_id),
+            _ => None,
+        }
+    }
 }
 
 /// Trait that all platform adapters must implement
@@ -210,6 +576,55 @@ pub trait Platform: Send + Sync {
     /// Disconnect from the platform
     async fn disconnect(&mut self) -> Result<()>;
 
+    /// Replace an expired session with one from new credentials
+    ///
+    /// # Arguments
+    /// * `credentials` - Credentials as for `PlatformConfig::credentials`, for
+    ///   the same account
+    ///
+    /// # Notes
+    /// Requests fail after `PlatformEvent::SessionExpired` until this succeeds.
+    /// Event subscriptions are renewed with the new session.
+    async fn renew_session(&self, credentials: HashMap<String, String>) -> Result<()> {
+        let _ = credentials;
+        Err(crate::error::Error::unsupported(
+            "Session renewal not supported by this platform",
+        ))
+    }
+
+    /// Start a single sign-on login through an OAuth 2.0 provider
+    ///
+    /// # Arguments
+    /// * `provider` - Name of the provider (e.g. "gitlab" for Mattermost)
+    ///
+    /// # Returns
+    /// The URL to open in a browser for the user to sign in
+    ///
+    /// # Notes
+    /// Can be called before `connect`. Pass the URL the browser is redirected
+    /// to afterwards to `complete_sso_login`.
+    async fn begin_sso_login(&self, provider: &str) -> Result<String> {
+        let _ = provider;
+        Err(crate::error::Error::unsupported(
+            "SSO login not supported by this platform",
+        ))
+    }
+
+    /// Finish a single sign-on login started with `begin_sso_login`
+    ///
+    /// # Arguments
+    /// * `callback_url` - The URL the provider redirected the browser to,
+    ///   carrying the authorization code
+    ///
+    /// # Returns
+    /// A session token, to connect with as the "token" credential
+    async fn complete_sso_login(&self, callback_url: &str) -> Result<String> {
+        let _ = callback_url;
+        Err(crate::error::Error::unsupported(
+            "SSO login not supported by this platform",
+        ))
+    }
+
     /// Get current connection information
     ///
     /// Returns None if not connected
@@ -230,6 +645,12 @@ pub trait Platform: Send + Sync {
     ///
     /// # Returns
     /// The created message
+    ///
+    /// # Notes
+    /// While events are subscribed and the connection is down, platforms with
+    /// an outbox queue the message and return a placeholder with `outbox_id`
+    /// metadata; the outcome is reported as `PlatformEvent::MessageSendSucceeded`
+    /// or `PlatformEvent::MessageSendFailed`.
     async fn send_message(&self, channel_id: &str, text: &str) -> Result<Message>;
 
     /// Get a list of channels the user has access to
@@ -257,6 +678,120 @@ pub trait Platform: Send + Sync {
     /// Get details about the currently authenticated user
     async fn get_current_user(&self) -> Result<User>;
 
+    /// Get the rules deciding which messages mention the current user
+    ///
+    /// # Returns
+    /// The user's notification settings where the platform has them; by
+    /// default, mentions by username and channel-wide mentions
+    async fn get_mention_rules(&self) -> Result<crate::types::MentionRules> {
+        let user = self.get_current_user().await?;
+        Ok(crate::types::MentionRules::new(user.id, user.username))
+    }
+
+    /// Update the current user's profile
+    ///
+    /// # Arguments
+    /// * `update` - The fields to change; unset fields are left as they are
+    ///
+    /// # Returns
+    /// The updated user
+    ///
+    /// # Notes
+    /// Platforms may refuse some fields, e.g. usernames managed by SSO, with
+    /// a permission error.
+    async fn update_profile(&self, update: &crate::types::ProfileUpdate) -> Result<User> {
+        let _ = update;
+        Err(crate::error::Error::unsupported(
+            "Profile updates not supported by this platform",
+        ))
+    }
+
+    /// Upload a new profile image for the current user
+    ///
+    /// # Arguments
+    /// * `image` - The image data
+    async fn set_profile_image(&self, image: Vec<u8>) -> Result<()> {
+        let _ = image;
+        Err(crate::error::Error::unsupported(
+            "Profile images not supported by this platform",
+        ))
+    }
+
+    /// Get the user groups visible to the current user
+    ///
+    /// # Returns
+    /// The groups; only those with a `name` can be mentioned
+    async fn get_groups(&self) -> Result<Vec<crate::types::Group>> {
+        Err(crate::error::Error::unsupported(
+            "User groups not supported by this platform",
+        ))
+    }
+
+    /// Get the members of a user group
+    ///
+    /// # Arguments
+    /// * `group_id` - The group ID (`Group::id`)
+    async fn get_group_members(&self, group_id: &str) -> Result<Vec<User>> {
+        let _ = group_id;
+        Err(crate::error::Error::unsupported(
+            "User groups not supported by this platform",
+        ))
+    }
+
+    /// Find the groups mentioned in a message, with their members
+    ///
+    /// # Arguments
+    /// * `text` - The message text
+    ///
+    /// # Returns
+    /// The mentioned groups in order of appearance; `@name` words that are not
+    /// group mentions (e.g. usernames) are skipped
+    async fn resolve_group_mentions(&self, text: &str) -> Result<Vec<crate::types::GroupMention>> {
+        let names = crate::types::group::mention_names(text);
+        if names.is_empty() {
+            return Ok(Vec::new());
+        }
+        let groups = self.get_groups().await?;
+        let mut mentions = Vec::new();
+        for name in names {
+            let Some(group) = groups.iter().find(|group| {
+                group
+                    .name
+                    .as_deref()
+                    .is_some_and(|n| n.eq_ignore_ascii_case(&name))
+            }) else {
+                continue;
+            };
+            let members = self.get_group_members(&group.id).await?;
+            mentions.push(crate::types::GroupMention {
+                group: group.clone(),
+                members,
+            });
+        }
+        Ok(mentions)
+    }
+
+    /// Get the channels known from earlier calls and events, without a request
+    ///
+    /// # Returns
+    /// The cached channels ordered by ID, so the same cache contents always
+    /// list in the same order. Empty before anything was fetched.
+    async fn cached_channels(&self) -> Result<Vec<Channel>> {
+        Err(crate::error::Error::unsupported(
+            "Cached channels not supported by this platform",
+        ))
+    }
+
+    /// Get the users known from earlier calls and events, without a request
+    ///
+    /// # Returns
+    /// The cached users ordered by ID. Empty before anything was fetched.
+    async fn cached_users(&self) -> Result<Vec<User>> {
+        Err(crate::error::Error::unsupported(
+            "Cached users not supported by this platform",
+        ))
+    }
+
     /// Create a direct message channel with another user
     ///
     /// # Arguments
@@ -393,6 +928,14 @@ pub trait Platform: Send + Sync {
     /// Returns None if no events are available.
     async fn poll_event(&mut self) -> Result<Option<PlatformEvent>>;
 
+    /// Get a signal that is notified when new events are queued
+    ///
+    /// Lets event consumers wait for events instead of polling on a timer.
+    /// Platforms without one return None and are polled periodically.
+    fn event_signal(&self) -> Option<Arc<Notify>> {
+        None
+    }
+
     // ========================================================================
     // Extended Platform Methods
     // ========================================================================
@@ -409,6 +952,7 @@ pub trait Platform: Send + Sync {
     ///
     /// # Notes
     /// Not all platforms support threading. Check `capabilities().has_threads` first.
+    /// Replies are queued while disconnected like `send_message()`.
     async fn send_reply(&self, channel_id: &str, text: &str, root_id: &str) -> Result<Message> {
         let _ = (channel_id, text, root_id);
         Err(crate::error::Error::unsupported(
@@ -416,116 +960,634 @@ pub trait Platform: Send + Sync {
         ))
     }
 
-    /// Update/edit a message
+    /// Send a message with an overridden sender name and/or avatar
     ///
     /// # Arguments
-    /// * `message_id` - The message ID to update
-    /// * `new_text` - The new message text
+    /// * `channel_id` - The channel to send the message to
+    /// * `text` - The message text
+    /// * `username` - Optional display name to show instead of the sender's
+    /// * `icon_url` - Optional URL of an image to show as the sender's avatar
     ///
     /// # Returns
-    /// The updated message
+    /// The created message
     ///
     /// # Notes
-    /// Not all platforms support message editing. Check `capabilities().supports_message_editing` first.
-    async fn update_message(&self, message_id: &str, new_text: &str) -> Result<Message> {
-        let _ = (message_id, new_text);
+    /// Intended for bots and bridges representing remote users. The token usually
+    /// needs integration/bot permissions and the server may ignore the overrides
+    /// depending on its configuration. Check `capabilities().supports_sender_overrides` first.
+    async fn send_message_as(
+        &self,
+        channel_id: &str,
+        text: &str,
+        username: Option<&str>,
+        icon_url: Option<&str>,
+    ) -> Result<Message> {
+        let _ = (channel_id, text, username, icon_url);
         Err(crate::error::Error::unsupported(
-            "Message editing not supported by this platform",
+            "Sender overrides not supported by this platform",
         ))
     }
 
-    /// Delete a message
+    /// Send a message with custom properties attached
     ///
     /// # Arguments
-    /// * `message_id` - The message ID to delete
+    /// * `channel_id` - The channel to send the message to
+    /// * `text` - The message text
+    /// * `props` - Custom key/value properties stored with the message
+    ///
+    /// # Returns
+    /// The created message
     ///
     /// # Notes
-    /// Not all platforms support message deletion. Check `capabilities().supports_message_deletion` first.
-    async fn delete_message(&self, message_id: &str) -> Result<()> {
-        let _ = message_id;
+    /// Props are returned in `Message::metadata["props"]` when the message is read back.
+    /// Used by the bridge helpers to tag relayed messages with their origin.
+    async fn send_message_with_props(
+        &self,
+        channel_id: &str,
+        text: &str,
+        props: HashMap<String, serde_json::Value>,
+    ) -> Result<Message> {
+        let _ = (channel_id, text, props);
         Err(crate::error::Error::unsupported(
-            "Message deletion not supported by this platform",
+            "Message properties not supported by this platform",
         ))
     }
 
-    /// Get a specific message by ID
+    /// Send a message with uploaded files attached
     ///
     /// # Arguments
-    /// * `message_id` - The message ID
+    /// * `channel_id` - The channel to send the message to
+    /// * `text` - The message text (may be empty)
+    /// * `file_ids` - IDs of files uploaded to the channel with `upload_file`
+    ///   or `upload_file_data`
+    /// * `props` - Custom key/value properties stored with the message
     ///
     /// # Returns
-    /// The message
-    async fn get_message(&self, message_id: &str) -> Result<Message> {
-        let _ = message_id;
+    /// The created message
+    async fn send_message_with_files(
+        &self,
+        channel_id: &str,
+        text: &str,
+        file_ids: Vec<String>,
+        props: HashMap<String, serde_json::Value>,
+    ) -> Result<Message> {
+        let _ = (channel_id, text, file_ids, props);
         Err(crate::error::Error::unsupported(
-            "Get message by ID not supported by this platform",
+            "File attachments not supported by this platform",
         ))
     }
 
-    /// Search for messages
+    /// Send a message with uploaded files attached
     ///
     /// # Arguments
-    /// * `query` - The search query
-    /// * `limit` - Maximum number of results
+    /// * `channel_id` - The channel to send the message to
+    /// * `text` - The message text (may be empty)
+    /// * `file_ids` - IDs of files uploaded to the channel with `upload_file`
+    ///   or `upload_file_data`
     ///
     /// # Returns
-    /// List of matching messages
+    /// The created message, with the files in `attachments`
     ///
     /// # Notes
-    /// Not all platforms support search. Check `capabilities().supports_search` first.
-    async fn search_messages(&self, query: &str, limit: usize) -> Result<Vec<Message>> {
-        let _ = (query, limit);
-        Err(crate::error::Error::unsupported(
-            "Message search not supported by this platform",
-        ))
+    /// Same as `send_message_with_files` without props.
+    async fn send_message_with_attachments(
+        &self,
+        channel_id: &str,
+        text: &str,
+        file_ids: Vec<String>,
+    ) -> Result<Message> {
+        if file_ids.is_empty() {
+            return Err(crate::error::Error::invalid_argument(
+                "No file IDs to attach",
+            ));
+        }
+        self.send_message_with_files(channel_id, text, file_ids, HashMap::new())
+            .await
     }
 
-    /// Get messages before a specific message (pagination)
+    /// Send a message with options: a thread, props, files, a priority
     ///
     /// # Arguments
-    /// * `channel_id` - The channel ID
-    /// * `before_id` - Get messages before this message ID
-    /// * `limit` - Maximum number of messages to retrieve
+    /// * `channel_id` - The channel to send the message to
+    /// * `text` - The message text (may be empty if files are attached)
+    /// * `options` - How to send the message
     ///
     /// # Returns
-    /// List of messages
-    async fn get_messages_before(
+    /// The created message
+    ///
+    /// # Notes
+    /// The default implementation covers replies, props and files with the
+    /// other send methods; a priority, an acknowledgement request or a
+    /// scheduled time fail with `ErrorCode::Unsupported`.
+    async fn send_message_ex(
         &self,
         channel_id: &str,
-        before_id: &str,
-        limit: usize,
-    ) -> Result<Vec<Message>> {
-        let _ = (channel_id, before_id, limit);
-        Err(crate::error::Error::unsupported(
-            "Message pagination not supported by this platform",
-        ))
+        text: &str,
+        options: &crate::types::SendOptions,
+    ) -> Result<Message> {
+        use crate::types::MessagePriority;
+
+        if options.priority != MessagePriority::Standard || options.requested_ack {
+            return Err(crate::error::Error::unsupported(
+                "Message priority not supported by this platform",
+            ));
+        }
+        if options.scheduled_at.is_some() {
+            return Err(crate::error::Error::unsupported(
+                "Scheduled messages not supported by this platform",
+            ));
+        }
+        if let Some(root_id) = &options.root_id {
+            if !options.props.is_empty() || !options.file_ids.is_empty() {
+                return Err(crate::error::Error::unsupported(
+                    "Replies with props or files not supported by this platform",
+                ));
+            }
+            return self.send_reply(channel_id, text, root_id).await;
+        }
+        if !options.file_ids.is_empty() {
+            return self
+                .send_message_with_files(
+                    channel_id,
+                    text,
+                    options.file_ids.clone(),
+                    options.props.clone(),
+                )
+                .await;
+        }
+        if !options.props.is_empty() {
+            return self
+                .send_message_with_props(channel_id, text, options.props.clone())
+                .await;
+        }
+        self.send_message(channel_id, text).await
     }
 
-    /// Get messages after a specific message (pagination)
+    /// Post a code snippet with syntax highlighting metadata
     ///
     /// # Arguments
-    /// * `channel_id` - The channel ID
-    /// * `after_id` - Get messages after this message ID
-    /// * `limit` - Maximum number of messages to retrieve
+    /// * `channel_id` - The channel to post the snippet to
+    /// * `language` - Language of the code (e.g. "rust"), if known
+    /// * `code` - The code to post
+    /// * `filename` - File name to show for the snippet, if any
     ///
     /// # Returns
-    /// List of messages
-    async fn get_messages_after(
+    /// The created message
+    ///
+    /// # Notes
+    /// Short snippets are posted as a fenced code block. Snippets that exceed the
+    /// platform's message length limit or `MAX_INLINE_SNIPPET_LINES` lines are
+    /// uploaded as a file instead, which the default implementation does not
+    /// support. The language and file name are stored in the `SNIPPET_PROP`
+    /// property where the platform supports message properties; see
+    /// `SnippetInfo::from_message`.
+    async fn send_code_snippet(
         &self,
         channel_id: &str,
-        after_id: &str,
-        limit: usize,
-    ) -> Result<Vec<Message>> {
-        let _ = (channel_id, after_id, limit);
+        language: Option<&str>,
+        code: &str,
+        filename: Option<&str>,
+    ) -> Result<Message> {
+        use crate::types::snippet;
+
+        let text = snippet::fenced_code_block(language, code);
+        if snippet::should_upload_snippet(code, text.chars().count(), &self.capabilities().limits) {
+            return Err(crate::error::Error::unsupported(
+                "Uploading snippets as files not supported by this platform",
+            ));
+        }
+
+        let info = snippet::SnippetInfo {
+            language: language.map(|l| l.to_string()),
+            filename: filename.map(|f| f.to_string()),
+            uploaded: false,
+        };
+        let mut props = HashMap::new();
+        props.insert(
+            snippet::SNIPPET_PROP.to_string(),
+            serde_json::to_value(&info).unwrap_or_default(),
+        );
+
+        match self.send_message_with_props(channel_id, &text, props).await {
+            Err(e) if e.code == crate::error::ErrorCode::Unsupported => {
+                self.send_message(channel_id, &text).await
+            }
+            result => result,
+        }
+    }
+
+    /// Get a link to a message
+    ///
+    /// # Arguments
+    /// * `message` - The message to link to
+    ///
+    /// # Returns
+    /// A URL that opens the message in the platform's clients
+    async fn message_permalink(&self, message: &Message) -> Result<String> {
+        let _ = message;
         Err(crate::error::Error::unsupported(
-            "Message pagination not supported by this platform",
+            "Message permalinks not supported by this platform",
         ))
     }
 
-    /// Add a reaction to a message
+    /// Quote a message, with a comment, in the quoted message's channel
     ///
     /// # Arguments
-    /// * `message_id` - The message ID to react to
+    /// * `target_id` - The ID of the message to quote
+    /// * `comment` - The comment to post below the quote (may be empty)
+    ///
+    /// # Returns
+    /// The created message
+    ///
+    /// # Notes
+    /// The text is a blockquote of the original with an attribution line that
+    /// links to it where the platform has permalinks. The quoted reference is
+    /// stored in the `QUOTE_PROP` property and is also attached to the returned
+    /// message; see `QuoteInfo::from_message`.
+    async fn quote_message(&self, target_id: &str, comment: &str) -> Result<Message> {
+        use crate::types::quote::{self, QuoteInfo};
+
+        let quoted = self.get_message(target_id).await?;
+        let sender_name = self
+            .get_user(&quoted.sender_id)
+            .await
+            .ok()
+            .map(|u| u.username);
+        let permalink = self.message_permalink(&quoted).await.ok();
+        let info = QuoteInfo::new(&quoted, sender_name, permalink);
+        let text = quote::quote_text(&quoted.text, &info, comment);
+
+        let mut props = HashMap::new();
+        props.insert(
+            quote::QUOTE_PROP.to_string(),
+            serde_json::to_value(&info).unwrap_or_default(),
+        );
+
+        let mut message = match self
+            .send_message_with_props(&quoted.channel_id, &text, props)
+            .await
+        {
+            Err(e) if e.code == crate::error::ErrorCode::Unsupported => {
+                self.send_message(&quoted.channel_id, &text).await?
+            }
+            result => result?,
+        };
+        info.attach_to(&mut message);
+        Ok(message)
+    }
+
+    /// Run a slash command in a channel
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel the command is run in
+    /// * `command` - The full command text, starting with `/` (e.g. "/away")
+    ///
+    /// # Returns
+    /// The command's response
+    ///
+    /// # Notes
+    /// Messages a command posts arrive as regular message events; the response
+    /// carries only text meant for the user and where to navigate, if anywhere.
+    async fn execute_command(
+        &self,
+        channel_id: &str,
+        command: &str,
+    ) -> Result<crate::types::CommandResponse> {
+        let _ = (channel_id, command);
+        Err(crate::error::Error::unsupported(
+            "Slash commands not supported by this platform",
+        ))
+    }
+
+    /// List the slash commands matching a partly typed command
+    ///
+    /// # Arguments
+    /// * `team_id` - The team to list commands for (None for the current team)
+    /// * `prefix` - The start of the command; a leading `/` is ignored and an
+    ///   empty prefix lists all commands
+    /// * `limit` - Maximum number of commands to return
+    ///
+    /// # Returns
+    /// The matching commands, sorted by trigger
+    async fn autocomplete_commands(
+        &self,
+        team_id: Option<&str>,
+        prefix: &str,
+        limit: usize,
+    ) -> Result<Vec<crate::types::SlashCommand>> {
+        let _ = (team_id, prefix, limit);
+        Err(crate::error::Error::unsupported(
+            "Slash commands not supported by this platform",
+        ))
+    }
+
+    /// Create a poll in a channel
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel to post the poll in
+    /// * `question` - The poll question
+    /// * `options` - The answer options
+    /// * `settings` - Anonymity, progress display and whether others may add options
+    ///
+    /// # Returns
+    /// The created poll
+    ///
+    /// # Notes
+    /// On Mattermost this requires the Matterpoll plugin. Changes to polls are
+    /// reported as `PlatformEvent::PollUpdated` events.
+    async fn create_poll(
+        &self,
+        channel_id: &str,
+        question: &str,
+        options: &[String],
+        settings: &crate::types::PollSettings,
+    ) -> Result<crate::types::Poll> {
+        let _ = (channel_id, question, options, settings);
+        Err(crate::error::Error::unsupported(
+            "Polls not supported by this platform",
+        ))
+    }
+
+    /// Get the current state of a poll
+    ///
+    /// # Arguments
+    /// * `poll_id` - The poll ID (`Poll::id`)
+    async fn get_poll(&self, poll_id: &str) -> Result<crate::types::Poll> {
+        let _ = poll_id;
+        Err(crate::error::Error::unsupported(
+            "Polls not supported by this platform",
+        ))
+    }
+
+    /// Vote for an option of a poll
+    ///
+    /// # Arguments
+    /// * `poll_id` - The poll ID (`Poll::id`)
+    /// * `option_index` - Position of the option in `Poll::options`
+    ///
+    /// # Notes
+    /// Voting again may change or toggle the vote, depending on the platform.
+    async fn vote_poll(&self, poll_id: &str, option_index: usize) -> Result<()> {
+        let _ = (poll_id, option_index);
+        Err(crate::error::Error::unsupported(
+            "Polls not supported by this platform",
+        ))
+    }
+
+    /// Close a poll, ending voting
+    ///
+    /// # Arguments
+    /// * `poll_id` - The poll ID (`Poll::id`)
+    ///
+    /// # Returns
+    /// The closed poll with its final results
+    async fn close_poll(&self, poll_id: &str) -> Result<crate::types::Poll> {
+        let _ = poll_id;
+        Err(crate::error::Error::unsupported(
+            "Polls not supported by this platform",
+        ))
+    }
+
+    /// Update/edit a message
+    ///
+    /// # Arguments
+    /// * `message_id` - The message ID to update
+    /// * `new_text` - The new message text
+    ///
+    /// # Returns
+    /// The updated message
+    ///
+    /// # Notes
+    /// Not all platforms support message editing. Check `capabilities().supports_message_editing` first.
+    async fn update_message(&self, message_id: &str, new_text: &str) -> Result<Message> {
+        let _ = (message_id, new_text);
+        Err(crate::error::Error::unsupported(
+            "Message editing not supported by this platform",
+        ))
+    }
+
+    /// Delete a message
+    ///
+    /// # Arguments
+    /// * `message_id` - The message ID to delete
+    ///
+    /// # Notes
+    /// Not all platforms support message deletion. Check `capabilities().supports_message_deletion` first.
+    async fn delete_message(&self, message_id: &str) -> Result<()> {
+        let _ = message_id;
+        Err(crate::error::Error::unsupported(
+            "Message deletion not supported by this platform",
+        ))
+    }
+
+    /// Get a specific message by ID
+    ///
+    /// # Arguments
+    /// * `message_id` - The message ID
+    ///
+    /// # Returns
+    /// The message
+    async fn get_message(&self, message_id: &str) -> Result<Message> {
+        let _ = message_id;
+        Err(crate::error::Error::unsupported(
+            "Get message by ID not supported by this platform",
+        ))
+    }
+
+    /// Search for messages
+    ///
+    /// # Arguments
+    /// * `query` - The search query
+    /// * `limit` - Maximum number of results
+    ///
+    /// # Returns
+    /// List of matching messages
+    ///
+    /// # Notes
+    /// Not all platforms support search. Check `capabilities().supports_search` first.
+    async fn search_messages(&self, query: &str, limit: usize) -> Result<Vec<Message>> {
+        let _ = (query, limit);
+        Err(crate::error::Error::unsupported(
+            "Message search not supported by this platform",
+        ))
+    }
+
+    /// Search for messages with a structured query
+    ///
+    /// # Arguments
+    /// * `query` - The search query; the platform translates it into its native search syntax
+    /// * `limit` - Maximum number of results
+    ///
+    /// # Returns
+    /// List of matching messages
+    ///
+    /// # Notes
+    /// The default implementation passes the query in the common search
+    /// mini-language to `search_messages` and filters attachments locally,
+    /// so a `has_attachment` search may return fewer than `limit` messages.
+    /// Platforms whose native syntax differs should override this.
+    async fn search_messages_query(
+        &self,
+        query: &crate::types::SearchQuery,
+        limit: usize,
+    ) -> Result<Vec<Message>> {
+        let mut messages = self.search_messages(&query.to_string(), limit).await?;
+        if query.has_attachment {
+            messages.retain(|m| !m.attachments.is_empty());
+        }
+        Ok(messages)
+    }
+
+    /// Get the messages of a channel around a point in time ("jump to date")
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel ID
+    /// * `date` - The point in time to jump to
+    /// * `limit` - Maximum number of messages to retrieve
+    ///
+    /// # Returns
+    /// Messages before and after the given time, oldest first. If the channel
+    /// has no messages that old, its oldest messages are returned.
+    async fn get_messages_at_date(
+        &self,
+        channel_id: &str,
+        date: chrono::DateTime<chrono::Utc>,
+        limit: usize,
+    ) -> Result<Vec<Message>> {
+        let _ = (channel_id, date, limit);
+        Err(crate::error::Error::unsupported(
+            "Jump to date not supported by this platform",
+        ))
+    }
+
+    /// Get messages before a specific message (pagination)
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel ID
+    /// * `before_id` - Get messages before this message ID
+    /// * `limit` - Maximum number of messages to retrieve
+    ///
+    /// # Returns
+    /// List of messages
+    async fn get_messages_before(
+        &self,
+        channel_id: &str,
+        before_id: &str,
+        limit: usize,
+    ) -> Result<Vec<Message>> {
+        let _ = (channel_id, before_id, limit);
+        Err(crate::error::Error::unsupported(
+            "Message pagination not supported by this platform",
+        ))
+    }
+
+    /// Get messages after a specific message (pagination)
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel ID
+    /// * `after_id` - Get messages after this message ID
+    /// * `limit` - Maximum number of messages to retrieve
+    ///
+    /// # Returns
+    /// List of messages
+    async fn get_messages_after(
+        &self,
+        channel_id: &str,
+        after_id: &str,
+        limit: usize,
+    ) -> Result<Vec<Message>> {
+        let _ = (channel_id, after_id, limit);
+        Err(crate::error::Error::unsupported(
+            "Message pagination not supported by this platform",
+        ))
+    }
+
+    /// Get the most recent messages of a channel as the first page of its history
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel ID
+    /// * `limit` - Maximum number of messages per page
+    ///
+    /// # Returns
+    /// A page of messages whose cursor (passed to [`next_page`](Self::next_page))
+    /// fetches older messages
+    async fn get_messages_page(
+        &self,
+        channel_id: &str,
+        limit: usize,
+    ) -> Result<crate::types::Page<Message>> {
+        let messages = self.get_messages(channel_id, limit).await?;
+        let next_cursor =
+            crate::types::pagination::older_messages_cursor(channel_id, &messages, limit);
+        Ok(crate::types::Page::new(messages, next_cursor))
+    }
+
+    /// Fetch the page a cursor points to
+    ///
+    /// Works with the cursor of any paginated list (message history, emojis,
+    /// followed threads and saved messages), so clients can use one loop for
+    /// each of them: call it with `next_cursor` until that is None.
+    ///
+    /// # Arguments
+    /// * `cursor` - The `next_cursor` of a previously returned page
+    ///
+    /// # Returns
+    /// The page, with items in the same format as the list that issued the cursor
+    async fn next_page(
+        &self,
+        cursor: &crate::types::Cursor,
+    ) -> Result<crate::types::Page<serde_json::Value>> {
+        use crate::types::pagination::{
+            emojis_cursor, newer_messages_cursor, older_messages_cursor,
+        };
+        use crate::types::{Page, PageRequest};
+
+        match cursor.request()? {
+            PageRequest::MessagesBefore {
+                channel_id,
+                before_id,
+                limit,
+            } => {
+                let messages = self
+                    .get_messages_before(&channel_id, &before_id, limit)
+                    .await?;
+                let next_cursor = older_messages_cursor(&channel_id, &messages, limit);
+                Page::new(messages, next_cursor).into_json()
+            }
+            PageRequest::MessagesAfter {
+                channel_id,
+                after_id,
+                limit,
+            } => {
+                let messages = self
+                    .get_messages_after(&channel_id, &after_id, limit)
+                    .await?;
+                let next_cursor = newer_messages_cursor(&channel_id, &messages, limit);
+                Page::new(messages, next_cursor).into_json()
+            }
+            PageRequest::Emojis { page, per_page } => {
+                let emojis = self.get_emojis(page, per_page).await?;
+                let next_cursor = emojis_cursor(page, per_page, emojis.len());
+                Page::new(emojis, next_cursor).into_json()
+            }
+            PageRequest::FollowedThreads {
+                team_id,
+                page,
+                per_page,
+            } => self
+                .get_followed_threads(team_id.as_deref(), page, per_page)
+                .await?
+                .into_json(),
+            PageRequest::FlaggedPosts { page, per_page } => {
+                self.get_flagged_posts(page, per_page).await?.into_json()
+            }
+        }
+    }
+
+    /// Add a reaction to a message
+    ///
+    /// # Arguments
+    /// * `message_id` - The message ID to react to
     /// * `emoji` - The emoji name (e.g., "thumbsup", "smile", "heart")
     ///
     /// # Notes
@@ -552,6 +1614,23 @@ pub trait Platform: Send + Sync {
         ))
     }
 
+    /// Get the reactions to a message
+    ///
+    /// # Arguments
+    /// * `message_id` - The message ID
+    ///
+    /// # Returns
+    /// The reactions, one per user and emoji, oldest first
+    ///
+    /// # Notes
+    /// Not all platforms support reactions. Check `capabilities().supports_reactions` first.
+    async fn get_reactions(&self, message_id: &str) -> Result<Vec<crate::types::Reaction>> {
+        let _ = message_id;
+        Err(crate::error::Error::unsupported(
+            "Reactions not supported by this platform",
+        ))
+    }
+
     /// Pin a message/post to its channel
     ///
     /// # Arguments
@@ -566,6 +1645,110 @@ pub trait Platform: Send + Sync {
         ))
     }
 
+    /// Ask the server to remind the user about a message
+    ///
+    /// # Arguments
+    /// * `message_id` - The ID of the message
+    /// * `due_at` - When to remind the user
+    ///
+    /// # Notes
+    /// The server delivers the reminder itself (e.g. as a system message), on all
+    /// of the user's devices. For reminders handled by the library instead, see
+    /// the `reminders` module.
+    async fn set_message_reminder(
+        &self,
+        message_id: &str,
+        due_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<()> {
+        let _ = (message_id, due_at);
+        Err(crate::error::Error::unsupported(
+            "Server-side reminders not supported by this platform",
+        ))
+    }
+
+    /// Ask the server to send a message at a later time
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel to send the message to
+    /// * `text` - The message text
+    /// * `send_at` - When to send the message
+    ///
+    /// # Returns
+    /// The scheduled message
+    ///
+    /// # Notes
+    /// The server sends the message even while no client is running. For
+    /// messages sent by the library instead, see the `scheduled_messages` module.
+    async fn schedule_message(
+        &self,
+        channel_id: &str,
+        text: &str,
+        send_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<crate::types::ScheduledMessage> {
+        let _ = (channel_id, text, send_at);
+        Err(crate::error::Error::unsupported(
+            "Server-side scheduled messages not supported by this platform",
+        ))
+    }
+
+    /// Get the user's messages scheduled on the server
+    ///
+    /// # Returns
+    /// The scheduled messages in the current team and direct channels
+    async fn get_scheduled_messages(&self) -> Result<Vec<crate::types::ScheduledMessage>> {
+        Err(crate::error::Error::unsupported(
+            "Server-side scheduled messages not supported by this platform",
+        ))
+    }
+
+    /// Cancel a message scheduled on the server
+    ///
+    /// # Arguments
+    /// * `scheduled_id` - The ID of the scheduled message
+    async fn cancel_scheduled_message(&self, scheduled_id: &str) -> Result<()> {
+        let _ = scheduled_id;
+        Err(crate::error::Error::unsupported(
+            "Server-side scheduled messages not supported by this platform",
+        ))
+    }
+
+    /// Save a draft on the server, replacing the draft of its channel/thread
+    ///
+    /// # Returns
+    /// The saved draft
+    ///
+    /// # Notes
+    /// Server-side drafts sync across the user's devices. For drafts kept by
+    /// the library where the platform has none, see the `drafts` module.
+    async fn save_draft(&self, draft: &crate::types::Draft) -> Result<crate::types::Draft> {
+        let _ = draft;
+        Err(crate::error::Error::unsupported(
+            "Server-side drafts not supported by this platform",
+        ))
+    }
+
+    /// Get the user's drafts stored on the server
+    ///
+    /// # Returns
+    /// The drafts in the current team, most recently saved first
+    async fn get_drafts(&self) -> Result<Vec<crate::types::Draft>> {
+        Err(crate::error::Error::unsupported(
+            "Server-side drafts not supported by this platform",
+        ))
+    }
+
+    /// Delete the draft of a channel or thread stored on the server
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel of the draft
+    /// * `root_id` - The thread of the draft, or None for the channel draft
+    async fn delete_draft(&self, channel_id: &str, root_id: Option<&str>) -> Result<()> {
+        let _ = (channel_id, root_id);
+        Err(crate::error::Error::unsupported(
+            "Server-side drafts not supported by this platform",
+        ))
+    }
+
     /// Unpin a message/post from its channel
     ///
     /// # Arguments
@@ -597,6 +1780,51 @@ pub trait Platform: Send + Sync {
         ))
     }
 
+    /// Flag (save) a message for the current user
+    ///
+    /// # Arguments
+    /// * `message_id` - The ID of the message to save
+    ///
+    /// # Notes
+    /// Saved messages are private to the user, unlike pinned messages.
+    async fn flag_post(&self, message_id: &str) -> Result<()> {
+        let _ = message_id;
+        Err(crate::error::Error::unsupported(
+            "Saved messages not supported by this platform",
+        ))
+    }
+
+    /// Remove a message from the current user's saved messages
+    ///
+    /// # Arguments
+    /// * `message_id` - The ID of the message to unsave
+    async fn unflag_post(&self, message_id: &str) -> Result<()> {
+        let _ = message_id;
+        Err(crate::error::Error::unsupported(
+            "Saved messages not supported by this platform",
+        ))
+    }
+
+    /// Get the current user's saved messages
+    ///
+    /// # Arguments
+    /// * `page` - Page number (0-indexed)
+    /// * `per_page` - Number of messages per page
+    ///
+    /// # Returns
+    /// A page of saved messages, most recent first, whose cursor (passed to
+    /// [`next_page`](Self::next_page)) fetches the following messages
+    async fn get_flagged_posts(
+        &self,
+        page: u32,
+        per_page: u32,
+    ) -> Result<crate::types::Page<Message>> {
+        let _ = (page, per_page);
+        Err(crate::error::Error::unsupported(
+            "Saved messages not supported by this platform",
+        ))
+    }
+
     /// Get a list of custom emojis available on the platform
     ///
     /// # Arguments
@@ -617,6 +1845,91 @@ pub trait Platform: Send + Sync {
         ))
     }
 
+    /// Get the first page of custom emojis
+    ///
+    /// # Arguments
+    /// * `per_page` - Number of emojis per page
+    ///
+    /// # Returns
+    /// A page of custom emojis whose cursor (passed to [`next_page`](Self::next_page))
+    /// fetches the following emojis
+    async fn get_emojis_page(
+        &self,
+        per_page: u32,
+    ) -> Result<crate::types::Page<crate::types::Emoji>> {
+        let emojis = self.get_emojis(0, per_page).await?;
+        let next_cursor = crate::types::pagination::emojis_cursor(0, per_page, emojis.len());
+        Ok(crate::types::Page::new(emojis, next_cursor))
+    }
+
+    /// Get the image for a custom emoji
+    ///
+    /// # Arguments
+    /// * `name_or_id` - The emoji name (e.g., "party_parrot" or ":party_parrot:") or emoji ID
+    ///
+    /// # Returns
+    /// The raw image bytes (PNG, GIF, etc. as uploaded to the server)
+    ///
+    /// # Notes
+    /// - Implementations should cache images so renderers can call this for
+    ///   every `:custom:` occurrence in a message without refetching
+    /// - Default implementation returns an unsupported error
+    async fn get_emoji_image(&self, name_or_id: &str) -> Result<Vec<u8>> {
+        let _ = name_or_id;
+        Err(crate::error::Error::unsupported(
+            "Custom emoji images not supported by this platform",
+        ))
+    }
+
+    /// Create a custom emoji
+    ///
+    /// # Arguments
+    /// * `name` - The emoji name (without colons); standard emoji names are taken
+    /// * `image` - The image bytes (PNG, JPEG or GIF)
+    ///
+    /// # Returns
+    /// The created emoji
+    async fn create_emoji(&self, name: &str, image: Vec<u8>) -> Result<crate::types::Emoji> {
+        let _ = (name, image);
+        Err(crate::error::Error::unsupported(
+            "Custom emoji creation not supported by this platform",
+        ))
+    }
+
+    /// Delete a custom emoji
+    ///
+    /// # Arguments
+    /// * `emoji_id` - The ID of the emoji
+    ///
+    /// # Notes
+    /// Usually only the emoji's creator and admins may delete it.
+    async fn delete_emoji(&self, emoji_id: &str) -> Result<()> {
+        let _ = emoji_id;
+        Err(crate::error::Error::unsupported(
+            "Custom emoji deletion not supported by this platform",
+        ))
+    }
+
+    /// Suggest custom emojis for a partly typed name
+    ///
+    /// # Arguments
+    /// * `prefix` - The start of the emoji name (a leading colon is ignored)
+    /// * `limit` - Maximum number of suggestions
+    ///
+    /// # Returns
+    /// Matching custom emojis; standard emojis are suggested by
+    /// [`crate::emoji::autocomplete`]
+    async fn autocomplete_emojis(
+        &self,
+        prefix: &str,
+        limit: usize,
+    ) -> Result<Vec<crate::types::Emoji>> {
+        let _ = (prefix, limit);
+        Err(crate::error::Error::unsupported(
+            "Custom emoji autocomplete not supported by this platform",
+        ))
+    }
+
     /// Get a channel by name
     ///
     /// # Arguments
@@ -673,6 +1986,59 @@ pub trait Platform: Send + Sync {
         ))
     }
 
+    /// Get a member's roles in a channel
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel ID
+    /// * `user_id` - The user ID of the member
+    async fn get_channel_member_roles(
+        &self,
+        channel_id: &str,
+        user_id: &str,
+    ) -> Result<crate::types::ChannelMemberRoles> {
+        let _ = (channel_id, user_id);
+        Err(crate::error::Error::unsupported(
+            "Channel moderation not supported by this platform",
+        ))
+    }
+
+    /// Make a channel member a channel admin, or revoke it
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel ID
+    /// * `user_id` - The user ID of the member
+    /// * `is_admin` - Whether the member should be a channel admin
+    async fn set_channel_admin(
+        &self,
+        channel_id: &str,
+        user_id: &str,
+        is_admin: bool,
+    ) -> Result<()> {
+        let _ = (channel_id, user_id, is_admin);
+        Err(crate::error::Error::unsupported(
+            "Channel moderation not supported by this platform",
+        ))
+    }
+
+    /// Convert a channel between public and private
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel ID
+    /// * `is_private` - Whether the channel should become private
+    ///
+    /// # Returns
+    /// The updated channel
+    ///
+    /// # Notes
+    /// Only public and private channels can be converted; direct and group
+    /// messages are rejected.
+    async fn set_channel_privacy(&self, channel_id: &str, is_private: bool) -> Result<Channel> {
+        let _ = (channel_id, is_private);
+        Err(crate::error::Error::unsupported(
+            "Channel moderation not supported by this platform",
+        ))
+    }
+
     /// Get a user by username
     ///
     /// # Arguments
@@ -715,6 +2081,41 @@ pub trait Platform: Send + Sync {
         ))
     }
 
+    /// Get a user's profile image
+    ///
+    /// # Arguments
+    /// * `user_id` - The ID of the user
+    ///
+    /// # Returns
+    /// The raw image bytes
+    ///
+    /// # Notes
+    /// Implementations should cache images, see `prefetch_channel_profiles`,
+    /// and revalidate expired ones (e.g. by ETag) instead of downloading them again.
+    async fn get_user_avatar(&self, user_id: &str) -> Result<Vec<u8>> {
+        let _ = user_id;
+        Err(crate::error::Error::unsupported(
+            "Profile images not supported by this platform",
+        ))
+    }
+
+    /// Warm the profile caches for the channels visible in the UI
+    ///
+    /// # Arguments
+    /// * `channel_ids` - The visible channels; an empty list only cancels
+    ///
+    /// # Notes
+    /// Fetches the users, profile images and custom statuses of the authors of
+    /// recent messages in the background and returns immediately. A new call
+    /// cancels the prefetch started by the previous one, so this can be called
+    /// whenever visibility changes.
+    async fn prefetch_channel_profiles(&self, channel_ids: Vec<String>) -> Result<()> {
+        let _ = channel_ids;
+        Err(crate::error::Error::unsupported(
+            "Profile prefetching not supported by this platform",
+        ))
+    }
+
     /// Set a custom status message
     ///
     /// # Arguments
@@ -763,6 +2164,25 @@ pub trait Platform: Send + Sync {
         ))
     }
 
+    /// Get the users currently typing in a channel
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel ID
+    ///
+    /// # Returns
+    /// IDs of the users whose typing indicator has not timed out
+    ///
+    /// # Notes
+    /// Typing state is derived from events (call `subscribe_events` first) and
+    /// is cleared when the event connection is re-established, since the
+    /// events that would have ended it may have been lost.
+    async fn get_typing_users(&self, channel_id: &str) -> Result<Vec<String>> {
+        let _ = channel_id;
+        Err(crate::error::Error::unsupported(
+            "Typing indicators not supported by this platform",
+        ))
+    }
+
     /// Request statuses for all users via WebSocket (async operation)
     ///
     /// This method sends a WebSocket request to get statuses for all users.
@@ -835,22 +2255,111 @@ pub trait Platform: Send + Sync {
     async fn get_team_by_name(&self, team_name: &str) -> Result<Team> {
         let _ = team_name;
         Err(crate::error::Error::unsupported(
-            "Team lookup by name not supported by this platform",
+            "Team lookup by name not supported by this platform",
+        ))
+    }
+
+    /// Set the active team/workspace ID
+    ///
+    /// # Arguments
+    /// * `team_id` - The team ID to set as active (or None to unset)
+    ///
+    /// # Notes
+    /// Only applicable for platforms with workspaces. Check `capabilities().has_workspaces` first.
+    /// This affects operations that are team-scoped, such as getting channels or searching messages.
+    async fn set_team_id(&self, team_id: Option<String>) -> Result<()> {
+        let _ = team_id;
+        Err(crate::error::Error::unsupported(
+            "Setting team ID not supported by this platform",
+        ))
+    }
+
+    /// Configure the concurrent request limit and the circuit breaker
+    ///
+    /// # Arguments
+    /// * `config` - Maximum requests in flight, the number of consecutive server
+    ///   errors or timeouts that open the circuit, and the cooldown before probing
+    ///
+    /// # Notes
+    /// While the circuit is open, requests fail with `ErrorCode::ServiceUnavailable`.
+    /// State changes are reported as `PlatformEvent::CircuitStateChanged`.
+    async fn set_circuit_breaker_config(
+        &self,
+        config: crate::types::CircuitBreakerConfig,
+    ) -> Result<()> {
+        let _ = config;
+        Err(crate::error::Error::unsupported(
+            "Circuit breaker not supported by this platform",
+        ))
+    }
+
+    /// Get the current state of the circuit breaker
+    async fn get_circuit_state(&self) -> Result<crate::types::CircuitState> {
+        Err(crate::error::Error::unsupported(
+            "Circuit breaker not supported by this platform",
+        ))
+    }
+
+    /// Change the TTLs and size limits of the platform's caches
+    ///
+    /// # Arguments
+    /// * `config` - Whether to cache at all, the TTL of each kind of entity,
+    ///   and the maximum number of entries of each cache
+    ///
+    /// # Notes
+    /// New TTLs apply to entries cached from now on; caches over the new size
+    /// limit are shrunk at once. Disabling caching clears the caches.
+    async fn configure_cache(&self, config: CacheConfig) -> Result<()> {
+        let _ = config;
+        Err(crate::error::Error::unsupported(
+            "Cache configuration not supported by this platform",
+        ))
+    }
+
+    /// Get the usage statistics of the platform's caches
+    async fn get_cache_stats(&self) -> Result<Vec<crate::types::CacheStats>> {
+        Err(crate::error::Error::unsupported(
+            "Cache statistics not supported by this platform",
+        ))
+    }
+
+    /// Drop real-time events as they are received
+    ///
+    /// # Arguments
+    /// * `filter` - Event categories to drop and the channels to restrict
+    ///   events to; an empty filter delivers every event
+    ///
+    /// # Notes
+    /// Filtered events never reach the event queue, so they can't overflow
+    /// it. They are not used to keep the platform's own state current either
+    /// (e.g. message windows of channels outside the filter go stale).
+    async fn set_event_filter(&self, filter: EventFilter) -> Result<()> {
+        let _ = filter;
+        Err(crate::error::Error::unsupported(
+            "Event filtering not supported by this platform",
+        ))
+    }
+
+    /// Get the health of the real-time connection
+    ///
+    /// # Returns
+    /// The connection state, time since the server was last heard from,
+    /// reconnection attempts and backoff, and the rate of received events
+    async fn connection_health(&self) -> Result<crate::types::ConnectionHealth> {
+        Err(crate::error::Error::unsupported(
+            "Connection health not supported by this platform",
         ))
     }
 
-    /// Set the active team/workspace ID
-    ///
-    /// # Arguments
-    /// * `team_id` - The team ID to set as active (or None to unset)
+    /// Get statistics of requests queued for the server's rate limit
     ///
     /// # Notes
-    /// Only applicable for platforms with workspaces. Check `capabilities().has_workspaces` first.
-    /// This affects operations that are team-scoped, such as getting channels or searching messages.
-    async fn set_team_id(&self, team_id: Option<String>) -> Result<()> {
-        let _ = team_id;
+    /// Requests exceeding the rate limit wait until it resets instead of
+    /// failing with `ErrorCode::RateLimited`; requests refused anyway are
+    /// retried with backoff.
+    async fn get_request_stats(&self) -> Result<crate::types::RequestStats> {
         Err(crate::error::Error::unsupported(
-            "Setting team ID not supported by this platform",
+            "Request statistics not supported by this platform",
         ))
     }
 
@@ -871,6 +2380,8 @@ pub trait Platform: Send + Sync {
     /// Not all platforms support file uploads. Check `capabilities().supports_file_attachments` first.
     /// The file is uploaded to the server but not yet attached to a message. Use the returned file ID
     /// when sending a message to attach the file.
+    /// Platforms may return the ID of an earlier, not yet attached upload of identical content to
+    /// the same channel instead of uploading it again.
     async fn upload_file(&self, channel_id: &str, file_path: &std::path::Path) -> Result<String> {
         let _ = (channel_id, file_path);
         Err(crate::error::Error::unsupported(
@@ -878,6 +2389,53 @@ pub trait Platform: Send + Sync {
         ))
     }
 
+    /// Upload a file from disk in chunks, reporting progress
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel ID where the file will be uploaded
+    /// * `file_path` - Path to the file to upload
+    /// * `progress` - Called after each chunk; an error aborts the upload
+    ///
+    /// # Returns
+    /// The file ID of the uploaded file, as with `upload_file`
+    ///
+    /// # Notes
+    /// Only one chunk of the file is held in memory at a time. An upload that
+    /// failed or was aborted resumes where it stopped when the same file is
+    /// uploaded to the same channel again, as long as it is unchanged.
+    async fn upload_file_with_progress(
+        &self,
+        channel_id: &str,
+        file_path: &std::path::Path,
+        progress: &mut ProgressSink<'_>,
+    ) -> Result<String> {
+        let _ = (channel_id, file_path, progress);
+        Err(crate::error::Error::unsupported(
+            "File uploads not supported by this platform",
+        ))
+    }
+
+    /// Upload file contents to a channel
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel ID where the file will be uploaded
+    /// * `filename` - The name of the file
+    /// * `data` - The file contents
+    ///
+    /// # Returns
+    /// The file ID of the uploaded file, as with `upload_file`
+    async fn upload_file_data(
+        &self,
+        channel_id: &str,
+        filename: &str,
+        data: Vec<u8>,
+    ) -> Result<String> {
+        let _ = (channel_id, filename, data);
+        Err(crate::error::Error::unsupported(
+            "File uploads not supported by this platform",
+        ))
+    }
+
     /// Download a file by its ID
     ///
     /// # Arguments
@@ -888,6 +2446,8 @@ pub trait Platform: Send + Sync {
     ///
     /// # Notes
     /// Not all platforms support file downloads. Check `capabilities().supports_file_attachments` first.
+    /// Where a checksum of the file is known, data that does not match it fails
+    /// with `ErrorCode::ChecksumMismatch`.
     async fn download_file(&self, file_id: &str) -> Result<Vec<u8>> {
         let _ = file_id;
         Err(crate::error::Error::unsupported(
@@ -895,6 +2455,54 @@ pub trait Platform: Send + Sync {
         ))
     }
 
+    /// Download a file to disk without buffering it in memory
+    ///
+    /// # Arguments
+    /// * `file_id` - The ID of the file to download
+    /// * `path` - The file to write, or `None` for a new file in the temp directory
+    ///
+    /// # Returns
+    /// The path of the downloaded file
+    ///
+    /// # Notes
+    /// Use this for files larger than the download spill threshold, which
+    /// `download_file()` rejects with `ErrorCode::FileTooLarge`. A temp file is
+    /// not removed by the library; the caller owns it. When a download to a
+    /// given path is interrupted, calling this again with the same path resumes
+    /// it. Data that does not match the file's checksum fails with
+    /// `ErrorCode::ChecksumMismatch`.
+    async fn download_file_to_path(&self, file_id: &str, path: Option<&Path>) -> Result<PathBuf> {
+        let _ = (file_id, path);
+        Err(crate::error::Error::unsupported(
+            "File downloads not supported by this platform",
+        ))
+    }
+
+    /// Download a file in chunks handed to a sink, without buffering it
+    ///
+    /// # Arguments
+    /// * `file_id` - The ID of the file to download
+    /// * `sink` - Called with each chunk and the progress including it; an
+    ///   error from it aborts the download and is returned
+    ///
+    /// # Returns
+    /// The size of the file
+    ///
+    /// # Notes
+    /// Where a checksum of the file is known it is verified after the last
+    /// chunk, so on `ErrorCode::ChecksumMismatch` the data received must be
+    /// discarded.
+    async fn download_file_streaming(
+        &self,
+        file_id: &str,
+        sink: &mut ChunkSink<'_>,
+    ) -> Result<u64> {
+        let _ = (file_id, sink);
+        Err(crate::error::Error::unsupported(
+            "File downloads not supported by this platform",
+        ))
+    }
+
     /// Get metadata for a file without downloading it
     ///
     /// # Arguments
@@ -1093,6 +2701,7 @@ pub trait Platform: Send + Sync {
     ///
     /// # Notes
     /// Returns platform-specific JSON. Not all platforms support thread listing.
+    #[allow(clippy::too_many_arguments)]
     async fn get_user_threads(
         &self,
         user_id: &str,
@@ -1135,6 +2744,32 @@ pub trait Platform: Send + Sync {
         ))
     }
 
+    /// Get the threads the current user follows
+    ///
+    /// # Arguments
+    /// * `team_id` - The team to list threads of, or None for the current team
+    /// * `page` - Page number (0-indexed)
+    /// * `per_page` - Number of threads per page
+    ///
+    /// # Returns
+    /// A page of threads, most recently active first, whose cursor (passed to
+    /// [`next_page`](Self::next_page)) fetches the following threads
+    ///
+    /// # Notes
+    /// Unlike [`get_thread`](Self::get_thread), threads carry their metadata
+    /// (reply count, participants, unread replies) rather than their replies.
+    async fn get_followed_threads(
+        &self,
+        team_id: Option<&str>,
+        page: u32,
+        per_page: u32,
+    ) -> Result<crate::types::Page<crate::types::Thread>> {
+        let _ = (team_id, page, per_page);
+        Err(crate::error::Error::unsupported(
+            "Thread listing not supported by this platform",
+        ))
+    }
+
     /// Mark all threads as read for a user in a team
     ///
     /// Bulk operation to mark all threads as read.
@@ -1209,6 +2844,7 @@ pub trait Platform: Send + Sync {
     /// Search for channels
     ///
     /// # Arguments
+    /// * `team_id` - The team to search in, or None for the current team
     /// * `query` - Search term to match against channel name or display name
     /// * `limit` - Maximum number of results to return
     ///
@@ -1218,8 +2854,13 @@ pub trait Platform: Send + Sync {
     /// # Notes
     /// Not all platforms support channel search. The search typically includes
     /// public channels and private channels the user is a member of.
-    async fn search_channels(&self, query: &str, limit: usize) -> Result<Vec<Channel>> {
-        let _ = (query, limit);
+    async fn search_channels(
+        &self,
+        team_id: Option<&str>,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<Channel>> {
+        let _ = (team_id, query, limit);
         Err(crate::error::Error::unsupported(
             "Channel search not supported by this platform",
         ))
@@ -1231,6 +2872,7 @@ pub trait Platform: Send + Sync {
     /// typically when typing ~channel-references.
     ///
     /// # Arguments
+    /// * `team_id` - The team to search in, or None for the current team
     /// * `query` - Channel name prefix to autocomplete
     /// * `limit` - Maximum number of results
     ///
@@ -1240,8 +2882,13 @@ pub trait Platform: Send + Sync {
     /// # Notes
     /// Not all platforms support channel autocomplete. Results typically include
     /// channels the user has access to.
-    async fn autocomplete_channels(&self, query: &str, limit: usize) -> Result<Vec<Channel>> {
-        let _ = (query, limit);
+    async fn autocomplete_channels(
+        &self,
+        team_id: Option<&str>,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<Channel>> {
+        let _ = (team_id, query, limit);
         Err(crate::error::Error::unsupported(
             "Channel autocomplete not supported by this platform",
         ))
@@ -1288,6 +2935,40 @@ pub trait Platform: Send + Sync {
         ))
     }
 
+    /// Get the current user's sidebar categories in the current team/workspace
+    ///
+    /// # Returns
+    /// The categories in display order, each with its channels in display
+    /// order (favorites, custom categories, channels, direct messages)
+    async fn get_sidebar_categories(&self) -> Result<Vec<crate::types::SidebarCategory>> {
+        Err(crate::error::Error::unsupported(
+            "Sidebar categories not supported by this platform",
+        ))
+    }
+
+    /// Move a channel into a sidebar category, out of the category it is in
+    ///
+    /// # Arguments
+    /// * `channel_id` - The ID of the channel
+    /// * `category_id` - The ID of the category
+    /// * `index` - Position in the category, or None for its end
+    ///
+    /// # Notes
+    /// Giving a position sorts the category manually, as dragging a channel
+    /// does in the official clients. Moving a channel into the favorites
+    /// category makes it a favorite.
+    async fn move_channel_to_category(
+        &self,
+        channel_id: &str,
+        category_id: &str,
+        index: Option<usize>,
+    ) -> Result<()> {
+        let _ = (channel_id, category_id, index);
+        Err(crate::error::Error::unsupported(
+            "Sidebar categories not supported by this platform",
+        ))
+    }
+
     /// Mute a channel for the current user
     ///
     /// # Arguments
@@ -1353,6 +3034,8 @@ pub trait Platform: Send + Sync {
     ///
     /// # Arguments
     /// * `channel_id` - The ID of the channel to mark as viewed
+    /// * `prev_channel_id` - Optional ID of the channel the user switched away from.
+    ///   If None, implementations may use the previously viewed channel they track.
     ///
     /// # Returns
     /// Result indicating success or failure
@@ -1360,8 +3043,12 @@ pub trait Platform: Send + Sync {
     /// # Notes
     /// Not all platforms support explicit channel viewing. Some platforms
     /// automatically mark channels as read when messages are retrieved.
-    async fn view_channel(&self, channel_id: &str) -> Result<()> {
-        let _ = channel_id;
+    /// Implementations may debounce repeated views of the same channel and
+    /// update local unread state optimistically, so this is safe to call on
+    /// every channel switch or focus change. Implementations may also report
+    /// the view as a `ChannelViewed` event before the server does.
+    async fn view_channel(&self, channel_id: &str, prev_channel_id: Option<&str>) -> Result<()> {
+        let _ = (channel_id, prev_channel_id);
         Err(crate::error::Error::unsupported(
             "Channel viewing not supported by this platform",
         ))
@@ -1388,6 +3075,50 @@ pub trait Platform: Send + Sync {
         ))
     }
 
+    /// Get hourly message counts for a channel
+    ///
+    /// Intended for activity sparklines and heatmaps. Implementations keep the
+    /// counts up to date from events, so repeated calls are cheap.
+    ///
+    /// # Arguments
+    /// * `channel_id` - The ID of the channel
+    /// * `days` - Number of days to cover, ending with the current hour
+    ///
+    /// # Returns
+    /// Result containing `days * 24` hourly counts, oldest first, or an Error
+    async fn get_channel_activity(
+        &self,
+        channel_id: &str,
+        days: u32,
+    ) -> Result<crate::types::ChannelActivity> {
+        let _ = (channel_id, days);
+        Err(crate::error::Error::unsupported(
+            "Channel activity not supported by this platform",
+        ))
+    }
+
+    /// Get the most recent messages of a channel from its in-memory window
+    ///
+    /// The first call for a channel fetches its latest messages; the window is
+    /// then kept up to date from message events, so later calls are answered
+    /// from memory. Windows hold a bounded number of messages and are dropped
+    /// when not requested for a while.
+    ///
+    /// # Arguments
+    /// * `channel_id` - The ID of the channel
+    ///
+    /// # Returns
+    /// Result containing the window's messages, oldest first, or an Error
+    ///
+    /// # Notes
+    /// The window only follows new messages while events are being polled.
+    async fn get_window(&self, channel_id: &str) -> Result<Vec<Message>> {
+        let _ = channel_id;
+        Err(crate::error::Error::unsupported(
+            "Message windows not supported by this platform",
+        ))
+    }
+
     /// Get unread counts for all channels in a specific team/workspace
     ///
     /// Returns unread message and mention counts for each channel the current
@@ -1409,6 +3140,36 @@ pub trait Platform: Send + Sync {
         ))
     }
 
+    /// Get unread counts for all channels in the current team/workspace
+    ///
+    /// # Returns
+    /// Result containing unread information for each channel the user is a
+    /// member of, or an Error
+    ///
+    /// # Notes
+    /// On platforms without teams, all of the user's channels.
+    async fn get_channel_unreads(&self) -> Result<Vec<crate::types::ChannelUnread>> {
+        Err(crate::error::Error::unsupported(
+            "Channel unread tracking not supported by this platform",
+        ))
+    }
+
+    /// Mark all messages in a channel as read
+    ///
+    /// # Arguments
+    /// * `channel_id` - The ID of the channel
+    ///
+    /// # Notes
+    /// Unlike `view_channel`, this is not debounced and does not change which
+    /// channel the user is considered to be viewing, so it suits "mark as read"
+    /// actions on channels that are not open.
+    async fn mark_channel_read(&self, channel_id: &str) -> Result<()> {
+        let _ = channel_id;
+        Err(crate::error::Error::unsupported(
+            "Channel read status not supported by this platform",
+        ))
+    }
+
     /// Get unread counts for all channels across all teams
     ///
     /// Returns comprehensive unread information for the authenticated user.
@@ -1450,6 +3211,187 @@ pub trait Platform: Send + Sync {
             "Unread posts tracking not supported by this platform",
         ))
     }
+
+    // ========================================================================
+    // Integration Management
+    // ========================================================================
+
+    /// Register an OAuth 2.0 app
+    ///
+    /// # Arguments
+    /// * `settings` - The app's name, homepage, callback URLs and options
+    ///
+    /// # Returns
+    /// The registered app. Its `client_secret` is only available in this result.
+    ///
+    /// # Notes
+    /// The server must allow OAuth apps and the user must be permitted to manage them.
+    async fn register_oauth_app(
+        &self,
+        settings: &crate::types::OAuthAppSettings,
+    ) -> Result<crate::types::OAuthApp> {
+        let _ = settings;
+        Err(crate::error::Error::unsupported(
+            "OAuth apps not supported by this platform",
+        ))
+    }
+
+    /// Get the OAuth 2.0 apps visible to the current user
+    ///
+    /// # Returns
+    /// The apps, without their client secrets
+    async fn get_oauth_apps(&self) -> Result<Vec<crate::types::OAuthApp>> {
+        Err(crate::error::Error::unsupported(
+            "OAuth apps not supported by this platform",
+        ))
+    }
+
+    /// Delete an OAuth 2.0 app
+    ///
+    /// # Arguments
+    /// * `app_id` - The app ID (`OAuthApp::id`)
+    async fn delete_oauth_app(&self, app_id: &str) -> Result<()> {
+        let _ = app_id;
+        Err(crate::error::Error::unsupported(
+            "OAuth apps not supported by this platform",
+        ))
+    }
+
+    /// Create a personal access token
+    ///
+    /// # Arguments
+    /// * `user_id` - The user the token acts as
+    /// * `description` - What the token is used for
+    ///
+    /// # Returns
+    /// The created token. Its `token` value is only available in this result.
+    ///
+    /// # Notes
+    /// The server must allow personal access tokens and the user must be
+    /// permitted to create them (for other users, to edit those users).
+    async fn create_access_token(
+        &self,
+        user_id: &str,
+        description: &str,
+    ) -> Result<crate::types::AccessToken> {
+        let _ = (user_id, description);
+        Err(crate::error::Error::unsupported(
+            "Personal access tokens not supported by this platform",
+        ))
+    }
+
+    /// Get a user's personal access tokens
+    ///
+    /// # Arguments
+    /// * `user_id` - The user whose tokens to list
+    ///
+    /// # Returns
+    /// The tokens, without their values
+    async fn get_access_tokens(&self, user_id: &str) -> Result<Vec<crate::types::AccessToken>> {
+        let _ = user_id;
+        Err(crate::error::Error::unsupported(
+            "Personal access tokens not supported by this platform",
+        ))
+    }
+
+    /// Revoke a personal access token
+    ///
+    /// # Arguments
+    /// * `token_id` - The token ID (`AccessToken::id`), not the token value
+    async fn revoke_access_token(&self, token_id: &str) -> Result<()> {
+        let _ = token_id;
+        Err(crate::error::Error::unsupported(
+            "Personal access tokens not supported by this platform",
+        ))
+    }
+
+    /// Create a bot account owned by the current user
+    ///
+    /// # Arguments
+    /// * `settings` - The bot's username, display name and description
+    ///
+    /// # Returns
+    /// The created bot
+    ///
+    /// # Notes
+    /// Bots act through personal access tokens; create them with
+    /// `create_access_token` for the bot's `user_id`.
+    async fn create_bot(&self, settings: &crate::types::BotSettings) -> Result<crate::types::Bot> {
+        let _ = settings;
+        Err(crate::error::Error::unsupported(
+            "Bot accounts not supported by this platform",
+        ))
+    }
+
+    /// Get the bot accounts visible to the current user
+    ///
+    /// # Arguments
+    /// * `include_disabled` - Whether to include disabled bots
+    async fn get_bots(&self, include_disabled: bool) -> Result<Vec<crate::types::Bot>> {
+        let _ = include_disabled;
+        Err(crate::error::Error::unsupported(
+            "Bot accounts not supported by this platform",
+        ))
+    }
+
+    /// Enable or disable a bot account
+    ///
+    /// # Arguments
+    /// * `bot_user_id` - The bot's user ID (`Bot::user_id`)
+    /// * `enabled` - Whether the bot should be enabled
+    ///
+    /// # Returns
+    /// The updated bot
+    async fn set_bot_enabled(&self, bot_user_id: &str, enabled: bool) -> Result<crate::types::Bot> {
+        let _ = (bot_user_id, enabled);
+        Err(crate::error::Error::unsupported(
+            "Bot accounts not supported by this platform",
+        ))
+    }
+
+    /// Transfer a bot account to another owner
+    ///
+    /// # Arguments
+    /// * `bot_user_id` - The bot's user ID (`Bot::user_id`)
+    /// * `owner_id` - The user ID of the new owner
+    ///
+    /// # Returns
+    /// The updated bot
+    async fn assign_bot(&self, bot_user_id: &str, owner_id: &str) -> Result<crate::types::Bot> {
+        let _ = (bot_user_id, owner_id);
+        Err(crate::error::Error::unsupported(
+            "Bot accounts not supported by this platform",
+        ))
+    }
+
+    /// Convert a user account into a bot account
+    ///
+    /// # Arguments
+    /// * `user_id` - The user to convert
+    ///
+    /// # Returns
+    /// The bot; the account can no longer log in with a password
+    async fn convert_user_to_bot(&self, user_id: &str) -> Result<crate::types::Bot> {
+        let _ = user_id;
+        Err(crate::error::Error::unsupported(
+            "Bot accounts not supported by this platform",
+        ))
+    }
+
+    /// Convert a bot account into a regular user account
+    ///
+    /// # Arguments
+    /// * `bot_user_id` - The bot's user ID (`Bot::user_id`)
+    /// * `password` - The password the user will log in with
+    ///
+    /// # Returns
+    /// The user
+    async fn convert_bot_to_user(&self, bot_user_id: &str, password: &str) -> Result<User> {
+        let _ = (bot_user_id, password);
+        Err(crate::error::Error::unsupported(
+            "Bot accounts not supported by this platform",
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -1461,7 +3403,9 @@ mod tests {
         let config = PlatformConfig::new("https://chat.example.com")
             .with_credential("token", "secret-token")
             .with_team("team-123")
-            .with_extra("timeout", "30");
+            .with_extra("timeout", "30")
+            .with_proxy("socks5://127.0.0.1:9050")
+            .with_host_override("chat.example.com", "10.0.0.5");
 
         assert_eq!(config.server, "https://chat.example.com");
         assert_eq!(
@@ -1470,5 +3414,10 @@ mod tests {
         );
         assert_eq!(config.team_id, Some("team-123".to_string()));
         assert_eq!(config.extra.get("timeout"), Some(&"30".to_string()));
+        assert_eq!(config.proxy.as_deref(), Some("socks5://127.0.0.1:9050"));
+        assert_eq!(
+            config.hosts.get("chat.example.com"),
+            Some(&"10.0.0.5".to_string())
+        );
     }
 }
@@ -0,0 +1,576 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, RwLock};
+
+use crate::error::{Error, ErrorCode, Result};
+
+use super::convert::{bare_jid, domain, localpart, stanza_error};
+use super::stream::{negotiate, write_raw, Inbound, Pending, Reader, Writer};
+use super::types::{
+    Bookmark, DiscoInfo, DiscoItem, RosterItem, NS_BOOKMARKS, NS_BOOKMARKS2, NS_CARBONS,
+    NS_CHATSTATES, NS_DATA, NS_DISCO_INFO, NS_DISCO_ITEMS, NS_MAM, NS_MUC, NS_PRIVATE, NS_PUBSUB,
+    NS_ROSTER, NS_RSM, NS_SID,
+};
+use super::xml::{Element, NS_CLIENT};
+
+/// Default port for client-to-server connections
+pub const DEFAULT_PORT: u16 = 5222;
+
+/// How long an IQ request may wait for its response
+const IQ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// An established session
+struct Session {
+    /// Full JID bound to the stream
+    jid: String,
+    writer: Writer,
+    pending: Arc<Pending>,
+    reader: Reader,
+}
+
+/// XMPP client-to-server connection
+pub struct XmppClient {
+    /// Host and port to connect to; None connects to the JID's domain
+    server: Option<(String, u16)>,
+    /// The current session, if connected
+    session: RwLock<Option<Session>>,
+    /// Counter making stanza IDs unique within this client
+    id_counter: AtomicU64,
+}
+
+/// Parse a "host[:port]" server address
+///
+/// # Returns
+/// None for an empty address, or an error if the port is invalid
+pub fn parse_server(server: &str) -> Result<Option<(String, u16)>> {
+    let server = server
+        .trim()
+        .trim_start_matches("xmpp://")
+        .trim_end_matches('/');
+    if server.is_empty() {
+        return Ok(None);
+    }
+    // Bracketed IPv6 addresses contain colons of their own
+    let (host, port) = match server.rsplit_once(':') {
+        Some((host, port)) if !port.contains(']') => {
+            let port = port.parse().map_err(|_| {
+                Error::invalid_argument(format!("Invalid XMPP server port: {port}"))
+            })?;
+            (host, port)
+        }
+        _ => (server, DEFAULT_PORT),
+    };
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    Ok(Some((host.to_string(), port)))
+}
+
+impl XmppClient {
+    /// Create a new XMPP client
+    ///
+    /// # Arguments
+    /// * `server` - "host[:port]" to connect to, or "" to connect to the
+    ///   domain of the JID on port 5222 (SRV records are not resolved)
+    ///
+    /// # Returns
+    /// A Result containing the client or an Error if the address is invalid
+    pub fn new(server: &str) -> Result<Self> {
+        Ok(Self {
+            server: parse_server(server)?,
+            session: RwLock::new(None),
+            id_counter: AtomicU64::new(0),
+        })
+    }
+
+    /// Connect and authenticate
+    ///
+    /// # Arguments
+    /// * `jid` - The account's JID (e.g. "alice@example.com")
+    /// * `password` - The account's password
+    ///
+    /// # Returns
+    /// A Result containing the full JID bound to the session
+    pub async fn connect(&self, jid: &str, password: &str) -> Result<String> {
+        let jid = bare_jid(jid);
+        if !jid.contains('@') {
+            return Err(Error::invalid_argument(format!(
+                "Invalid JID (expected user@domain): {jid}"
+            )));
+        }
+        let domain = domain(jid);
+        let (host, port) = self
+            .server
+            .clone()
+            .unwrap_or_else(|| (domain.to_string(), DEFAULT_PORT));
+
+        let (transport, parser, full_jid) =
+            negotiate(&host, port, domain, localpart(jid), password).await?;
+        let (read, write) = tokio::io::split(transport);
+        let writer: Writer = Arc::new(Mutex::new(write));
+        let pending = Arc::new(Pending::default());
+        let reader = Reader::start(read, parser, writer.clone(), pending.clone());
+
+        *self.session.write().await = Some(Session {
+            jid: full_jid.clone(),
+            writer,
+            pending,
+            reader,
+        });
+        Ok(full_jid)
+    }
+
+    /// Close the stream
+    pub async fn disconnect(&self) -> Result<()> {
+        if let Some(session) = self.session.write().await.take() {
+            // The server may already be gone; the session is dropped either way
+            let _ = write_raw(&mut *session.writer.lock().await, "</stream:stream>").await;
+        }
+        Ok(())
+    }
+
+    /// Get the full JID of the session
+    pub async fn get_jid(&self) -> Option<String> {
+        self.session
+            .read()
+            .await
+            .as_ref()
+            .map(|session| session.jid.clone())
+    }
+
+    /// Get the full JID of the session, or an error if not connected
+    pub async fn require_jid(&self) -> Result<String> {
+        self.get_jid().await.ok_or_else(not_connected)
+    }
+
+    /// Generate a unique stanza ID
+    pub fn next_id(&self) -> String {
+        let counter = self.id_counter.fetch_add(1, Ordering::Relaxed);
+        format!("lc{}.{counter}", chrono::Utc::now().timestamp_millis())
+    }
+
+    /// Send a stanza
+    pub async fn send(&self, stanza: &Element) -> Result<()> {
+        let writer = self
+            .session
+            .read()
+            .await
+            .as_ref()
+            .map(|session| session.writer.clone())
+            .ok_or_else(not_connected)?;
+        let xml = stanza.to_xml();
+        let mut writer = writer.lock().await;
+        write_raw(&mut *writer, &xml).await
+    }
+
+    async fn pending(&self) -> Result<Arc<Pending>> {
+        self.session
+            .read()
+            .await
+            .as_ref()
+            .map(|session| session.pending.clone())
+            .ok_or_else(not_connected)
+    }
+
+    /// Send an IQ request and wait for its response
+    ///
+    /// # Arguments
+    /// * `iq_type` - "get" or "set"
+    /// * `to` - The addressee, or None for the user's own account
+    /// * `payload` - The request's child element
+    ///
+    /// # Returns
+    /// The result stanza, or the stanza error converted to an Error
+    pub async fn iq(&self, iq_type: &str, to: Option<&str>, payload: Element) -> Result<Element> {
+        let id = self.next_id();
+        let mut iq = Element::new("iq", NS_CLIENT)
+            .attr("type", iq_type)
+            .attr("id", &id)
+            .child(payload);
+        if let Some(to) = to {
+            iq.set_attr("to", to);
+        }
+
+        let pending = self.pending().await?;
+        let response = pending.register_iq(&id)?;
+        if let Err(e) = self.send(&iq).await {
+            pending.forget_iq(&id);
+            return Err(e);
+        }
+
+        let response = match tokio::time::timeout(IQ_TIMEOUT, response).await {
+            Ok(Ok(response)) => response,
+            Ok(Err(_)) => {
+                return Err(Error::new(
+                    ErrorCode::NetworkError,
+                    "XMPP stream closed while waiting for a response",
+                ))
+            }
+            Err(_) => {
+                pending.forget_iq(&id);
+                return Err(Error::new(
+                    ErrorCode::Timeout,
+                    "XMPP request timed out waiting for a response",
+                ));
+            }
+        };
+        if response.get_attr("type") == Some("error") {
+            return Err(stanza_error(&response));
+        }
+        Ok(response)
+    }
+
+    /// Take the next received message or presence stanza, if any
+    pub async fn poll_inbound(&self) -> Option<Inbound> {
+        let session = self.session.read().await;
+        session.as_ref()?.reader.poll().await
+    }
+
+    // ========================================================================
+    // Service Discovery (XEP-0030)
+    // ========================================================================
+
+    /// Query an entity's identities and features
+    pub async fn disco_info(&self, jid: &str) -> Result<DiscoInfo> {
+        let response = self
+            .iq("get", Some(jid), Element::new("query", NS_DISCO_INFO))
+            .await?;
+        let mut info = DiscoInfo::default();
+        if let Some(query) = response.find("query", NS_DISCO_INFO) {
+            for child in query.elements() {
+                match child.name.as_str() {
+                    "identity" => info.identities.push((
+                        child.get_attr("category").unwrap_or_default().to_string(),
+                        child.get_attr("type").unwrap_or_default().to_string(),
+                        child.get_attr("name").map(str::to_string),
+                    )),
+                    "feature" => {
+                        if let Some(var) = child.get_attr("var") {
+                            info.features.push(var.to_string());
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(info)
+    }
+
+    /// List the items (e.g. services or rooms) of an entity
+    pub async fn disco_items(&self, jid: &str) -> Result<Vec<DiscoItem>> {
+        let response = self
+            .iq("get", Some(jid), Element::new("query", NS_DISCO_ITEMS))
+            .await?;
+        Ok(response
+            .find("query", NS_DISCO_ITEMS)
+            .map(|query| {
+                query
+                    .elements()
+                    .filter_map(|item| {
+                        Some(DiscoItem {
+                            jid: item.get_attr("jid")?.to_string(),
+                            name: item.get_attr("name").map(str::to_string),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    /// Find the server's multi-user chat service
+    pub async fn find_muc_service(&self) -> Result<String> {
+        let jid = self.require_jid().await?;
+        for item in self.disco_items(domain(&jid)).await? {
+            if let Ok(info) = self.disco_info(&item.jid).await {
+                if info.has_identity("conference", "text") {
+                    return Ok(item.jid);
+                }
+            }
+        }
+        Err(Error::unsupported(
+            "XMPP server has no multi-user chat service",
+        ))
+    }
+
+    // ========================================================================
+    // Roster, Presence and Carbons
+    // ========================================================================
+
+    /// Enable message carbons (XEP-0280)
+    pub async fn enable_carbons(&self) -> Result<()> {
+        self.iq("set", None, Element::new("enable", NS_CARBONS))
+            .await?;
+        Ok(())
+    }
+
+    /// Get the user's contacts
+    pub async fn get_roster(&self) -> Result<Vec<RosterItem>> {
+        let response = self
+            .iq("get", None, Element::new("query", NS_ROSTER))
+            .await?;
+        Ok(response
+            .find("query", NS_ROSTER)
+            .map(|query| {
+                query
+                    .elements()
+                    .filter_map(|item| {
+                        Some(RosterItem {
+                            jid: item.get_attr("jid")?.to_string(),
+                            name: item.get_attr("name").map(str::to_string),
+                            subscription: item
+                                .get_attr("subscription")
+                                .unwrap_or("none")
+                                .to_string(),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    /// Broadcast presence, or send it to a single entity (e.g. a room)
+    ///
+    /// # Arguments
+    /// * `to` - The addressee, or None to broadcast to contacts
+    /// * `show` - The `<show/>` value, or None for plain availability
+    /// * `status` - Optional status text
+    pub async fn send_presence(
+        &self,
+        to: Option<&str>,
+        show: Option<&str>,
+        status: Option<&str>,
+    ) -> Result<()> {
+        let mut presence = Element::new("presence", NS_CLIENT);
+        if let Some(to) = to {
+            presence.set_attr("to", to);
+        }
+        if let Some(show) = show {
+            presence = presence.child(Element::new("show", NS_CLIENT).text(show));
+        }
+        if let Some(status) = status {
+            presence = presence.child(Element::new("status", NS_CLIENT).text(status));
+        }
+        self.send(&presence).await
+    }
+
+    // ========================================================================
+    // Multi-User Chat (XEP-0045) and Bookmarks
+    // ========================================================================
+
+    /// Get the user's bookmarked rooms
+    ///
+    /// Uses PEP native bookmarks (XEP-0402) and falls back to bookmarks in
+    /// private XML storage (XEP-0048) for older servers.
+    pub async fn get_bookmarks(&self) -> Result<Vec<Bookmark>> {
+        let pubsub = Element::new("pubsub", NS_PUBSUB)
+            .child(Element::new("items", NS_PUBSUB).attr("node", NS_BOOKMARKS2));
+        if let Ok(response) = self.iq("get", None, pubsub).await {
+            let items = response
+                .find("pubsub", NS_PUBSUB)
+                .and_then(|pubsub| pubsub.find("items", NS_PUBSUB));
+            return Ok(items
+                .map(|items| {
+                    items
+                        .elements()
+                        .filter_map(|item| {
+                            let conference = item.find("conference", NS_BOOKMARKS2)?;
+                            Some(Bookmark {
+                                jid: item.get_attr("id")?.to_string(),
+                                name: conference.get_attr("name").map(str::to_string),
+                                autojoin: matches!(
+                                    conference.get_attr("autojoin"),
+                                    Some("true" | "1")
+                                ),
+                                nick: conference.child_text("nick", NS_BOOKMARKS2),
+                            })
+                        })
+                        .collect()
+                })
+                .unwrap_or_default());
+        }
+
+        let query = Element::new("query", NS_PRIVATE).child(Element::new("storage", NS_BOOKMARKS));
+        let response = self.iq("get", None, query).await?;
+        Ok(response
+            .find("query", NS_PRIVATE)
+            .and_then(|query| query.find("storage", NS_BOOKMARKS))
+            .map(|storage| {
+                storage
+                    .elements()
+                    .filter(|child| child.name == "conference")
+                    .filter_map(|conference| {
+                        Some(Bookmark {
+                            jid: conference.get_attr("jid")?.to_string(),
+                            name: conference.get_attr("name").map(str::to_string),
+                            autojoin: matches!(conference.get_attr("autojoin"), Some("true" | "1")),
+                            nick: conference.child_text("nick", NS_BOOKMARKS),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    /// Join a room
+    ///
+    /// History is not requested; use `mam_query` to load it.
+    pub async fn join_room(&self, room: &str, nick: &str) -> Result<()> {
+        let presence = Element::new("presence", NS_CLIENT)
+            .attr("to", format!("{room}/{nick}"))
+            .child(
+                Element::new("x", NS_MUC)
+                    .child(Element::new("history", NS_MUC).attr("maxstanzas", "0")),
+            );
+        self.send(&presence).await
+    }
+
+    /// Leave a room
+    pub async fn leave_room(&self, room: &str, nick: &str) -> Result<()> {
+        let presence = Element::new("presence", NS_CLIENT)
+            .attr("to", format!("{room}/{nick}"))
+            .attr("type", "unavailable");
+        self.send(&presence).await
+    }
+
+    /// Configure a room the user just created
+    ///
+    /// # Arguments
+    /// * `room` - The room's JID
+    /// * `fields` - Room configuration fields (`muc#roomconfig_*`) and values
+    pub async fn configure_room(&self, room: &str, fields: &[(&str, &str)]) -> Result<()> {
+        let mut form = Element::new("x", NS_DATA).attr("type", "submit").child(
+            Element::new("field", NS_DATA)
+                .attr("var", "FORM_TYPE")
+                .child(
+                    Element::new("value", NS_DATA)
+                        .text("http://jabber.org/protocol/muc#roomconfig"),
+                ),
+        );
+        for (var, value) in fields {
+            form = form.child(
+                Element::new("field", NS_DATA)
+                    .attr("var", *var)
+                    .child(Element::new("value", NS_DATA).text(*value)),
+            );
+        }
+        let query = Element::new("query", super::types::NS_MUC_OWNER).child(form);
+        self.iq("set", Some(room), query).await?;
+        Ok(())
+    }
+
+    // ========================================================================
+    // Messages
+    // ========================================================================
+
+    /// Send a message
+    ///
+    /// # Arguments
+    /// * `to` - The recipient's or room's JID
+    /// * `message_type` - "chat" or "groupchat"
+    /// * `body` - The message text
+    ///
+    /// # Returns
+    /// The message's ID, also sent as origin ID (XEP-0359)
+    pub async fn send_message(&self, to: &str, message_type: &str, body: &str) -> Result<String> {
+        let id = self.next_id();
+        let message = Element::new("message", NS_CLIENT)
+            .attr("to", to)
+            .attr("type", message_type)
+            .attr("id", &id)
+            .child(Element::new("body", NS_CLIENT).text(body))
+            .child(Element::new("origin-id", NS_SID).attr("id", &id))
+            .child(Element::new("active", NS_CHATSTATES));
+        self.send(&message).await?;
+        Ok(id)
+    }
+
+    /// Send a chat state notification (XEP-0085)
+    ///
+    /// # Arguments
+    /// * `to` - The recipient's or room's JID
+    /// * `message_type` - "chat" or "groupchat"
+    /// * `state` - "active", "composing", "paused", "inactive" or "gone"
+    pub async fn send_chat_state(&self, to: &str, message_type: &str, state: &str) -> Result<()> {
+        let message = Element::new("message", NS_CLIENT)
+            .attr("to", to)
+            .attr("type", message_type)
+            .child(Element::new(state, NS_CHATSTATES));
+        self.send(&message).await
+    }
+
+    /// Query a message archive (XEP-0313)
+    ///
+    /// # Arguments
+    /// * `archive` - The room's JID, or None for the user's own archive
+    /// * `with` - Only return messages exchanged with this JID
+    /// * `max` - Maximum number of messages
+    ///
+    /// # Returns
+    /// The latest `max` archived messages as `<result/>` elements, oldest first
+    pub async fn mam_query(
+        &self,
+        archive: Option<&str>,
+        with: Option<&str>,
+        max: usize,
+    ) -> Result<Vec<Element>> {
+        let query_id = self.next_id();
+        let mut form = Element::new("x", NS_DATA).attr("type", "submit").child(
+            Element::new("field", NS_DATA)
+                .attr("var", "FORM_TYPE")
+                .attr("type", "hidden")
+                .child(Element::new("value", NS_DATA).text(NS_MAM)),
+        );
+        if let Some(with) = with {
+            form = form.child(
+                Element::new("field", NS_DATA)
+                    .attr("var", "with")
+                    .child(Element::new("value", NS_DATA).text(with)),
+            );
+        }
+        // An empty <before/> requests the last page
+        let query = Element::new("query", NS_MAM)
+            .attr("queryid", &query_id)
+            .child(form)
+            .child(
+                Element::new("set", NS_RSM)
+                    .child(Element::new("max", NS_RSM).text(max.to_string()))
+                    .child(Element::new("before", NS_RSM)),
+            );
+
+        let pending = self.pending().await?;
+        pending.register_archive_query(&query_id);
+        let result = self.iq("set", archive, query).await;
+        let results = pending.take_archive_results(&query_id);
+        result?;
+        Ok(results)
+    }
+}
+
+fn not_connected() -> Error {
+    Error::new(
+        ErrorCode::InvalidState,
+        "Not connected - call connect() first",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_server() {
+        assert_eq!(parse_server("").unwrap(), None);
+        assert_eq!(
+            parse_server("xmpp.example.com").unwrap(),
+            Some(("xmpp.example.com".to_string(), DEFAULT_PORT))
+        );
+        assert_eq!(
+            parse_server("xmpp://xmpp.example.com:5223/").unwrap(),
+            Some(("xmpp.example.com".to_string(), 5223))
+        );
+        assert_eq!(
+            parse_server("[::1]:5222").unwrap(),
+            Some(("::1".to_string(), 5222))
+        );
+        assert!(parse_server("example.com:http").is_err());
+    }
+}
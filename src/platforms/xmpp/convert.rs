@@ -0,0 +1,503 @@
+//! Conversions from XMPP stanzas to the platform-agnostic types
+
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+use crate::error::{Error, ErrorCode};
+use crate::types::user::UserStatus;
+use crate::types::Message;
+
+use super::types::{
+    NS_CARBONS, NS_CHATSTATES, NS_CORRECT, NS_DELAY, NS_FORWARD, NS_MUC_USER, NS_SID, NS_STANZAS,
+};
+use super::xml::{Element, NS_CLIENT};
+
+/// Bare JID of a JID (e.g. "alice@example.com" for "alice@example.com/phone")
+pub fn bare_jid(jid: &str) -> &str {
+    jid.split_once('/').map(|(bare, _)| bare).unwrap_or(jid)
+}
+
+/// Resource part of a JID, if any
+pub fn resource(jid: &str) -> Option<&str> {
+    jid.split_once('/').map(|(_, resource)| resource)
+}
+
+/// Local part of a JID (e.g. "alice" for "alice@example.com/phone")
+pub fn localpart(jid: &str) -> &str {
+    let bare = bare_jid(jid);
+    bare.split_once('@').map(|(local, _)| local).unwrap_or(bare)
+}
+
+/// Domain part of a JID (e.g. "example.com" for "alice@example.com/phone")
+pub fn domain(jid: &str) -> &str {
+    let bare = bare_jid(jid);
+    bare.split_once('@')
+        .map(|(_, domain)| domain)
+        .unwrap_or(bare)
+}
+
+/// Map a presence stanza's type and `<show/>` to a UserStatus
+pub fn presence_to_status(presence_type: Option<&str>, show: Option<&str>) -> UserStatus {
+    if presence_type == Some("unavailable") {
+        return UserStatus::Offline;
+    }
+    match show {
+        None | Some("chat") => UserStatus::Online,
+        Some("away") | Some("xa") => UserStatus::Away,
+        Some("dnd") => UserStatus::DoNotDisturb,
+        Some(_) => UserStatus::Unknown,
+    }
+}
+
+/// Map a UserStatus to a presence `<show/>` value
+///
+/// Going offline would leave all rooms, so Offline is sent as extended away.
+pub fn status_to_show(status: UserStatus) -> Option<&'static str> {
+    match status {
+        UserStatus::Online | UserStatus::Unknown => None,
+        UserStatus::Away => Some("away"),
+        UserStatus::DoNotDisturb => Some("dnd"),
+        UserStatus::Offline => Some("xa"),
+    }
+}
+
+/// Convert a stanza error to an Error
+pub fn stanza_error(stanza: &Element) -> Error {
+    let error = stanza.find("error", NS_CLIENT);
+    let condition = error
+        .and_then(|error| {
+            error
+                .elements()
+                .find(|child| child.ns == NS_STANZAS && child.name != "text")
+        })
+        .map(|child| child.name.as_str())
+        .unwrap_or("undefined-condition");
+    let code = match condition {
+        "item-not-found" | "recipient-unavailable" | "remote-server-not-found" | "gone" => {
+            ErrorCode::NotFound
+        }
+        "forbidden"
+        | "not-allowed"
+        | "not-authorized"
+        | "registration-required"
+        | "subscription-required" => ErrorCode::PermissionDenied,
+        "feature-not-implemented" => ErrorCode::Unsupported,
+        "service-unavailable" => ErrorCode::ServiceUnavailable,
+        "bad-request" | "jid-malformed" | "not-acceptable" | "conflict" => {
+            ErrorCode::InvalidArgument
+        }
+        "remote-server-timeout" => ErrorCode::Timeout,
+        "resource-constraint" | "policy-violation" => ErrorCode::RateLimited,
+        _ => ErrorCode::Unknown,
+    };
+    let text = error
+        .and_then(|error| error.child_text("text", NS_STANZAS))
+        .filter(|text| !text.is_empty());
+    let message = match text {
+        Some(text) => format!("XMPP error {condition}: {text}"),
+        None => format!("XMPP error {condition}"),
+    };
+    Error::new(code, message)
+}
+
+/// Context needed to convert stanzas
+#[derive(Debug, Clone, Default)]
+pub struct ConversionContext {
+    /// Bare JID of the logged in user
+    pub own_jid: String,
+    /// Joined rooms and the nickname used in each
+    pub rooms: HashMap<String, String>,
+}
+
+impl ConversionContext {
+    /// Create a new conversion context
+    pub fn new(own_jid: impl Into<String>) -> Self {
+        Self {
+            own_jid: bare_jid(&own_jid.into()).to_string(),
+            rooms: HashMap::new(),
+        }
+    }
+
+    /// Whether a JID is the user's own occupant JID in a joined room
+    pub fn is_own_occupant(&self, jid: &str) -> bool {
+        self.rooms
+            .get(bare_jid(jid))
+            .is_some_and(|nick| resource(jid) == Some(nick.as_str()))
+    }
+}
+
+/// Something a message stanza reports
+#[derive(Debug, Clone)]
+pub enum MessageUpdate {
+    /// A new message
+    Posted(Message),
+    /// A correction of an earlier message; the message carries the corrected ID
+    Corrected(Message),
+    /// The sender started typing in a channel
+    Typing { user_id: String, channel_id: String },
+}
+
+/// Timestamp of a `<delay/>` element, if present and valid
+fn delay_stamp(element: &Element) -> Option<DateTime<Utc>> {
+    let stamp = element.find("delay", NS_DELAY)?.get_attr("stamp")?;
+    DateTime::parse_from_rfc3339(stamp)
+        .ok()
+        .map(|stamp| stamp.with_timezone(&Utc))
+}
+
+/// Unwrap a message forwarded by message carbons
+///
+/// Carbons are only accepted from the user's own account; anyone else could
+/// use them to forge messages.
+///
+/// # Returns
+/// The forwarded message, or None if the stanza is not a (valid) carbon
+fn unwrap_carbon<'a>(stanza: &'a Element, ctx: &ConversionContext) -> Option<&'a Element> {
+    let wrapper = stanza
+        .find("received", NS_CARBONS)
+        .or_else(|| stanza.find("sent", NS_CARBONS))?;
+    if stanza.get_attr("from").map(bare_jid) != Some(ctx.own_jid.as_str()) {
+        return None;
+    }
+    wrapper
+        .find("forwarded", NS_FORWARD)?
+        .find("message", NS_CLIENT)
+}
+
+/// Channel and sender of a message
+///
+/// Room messages belong to the room and are sent by occupant JIDs
+/// (`room@service/nick`). One-to-one messages belong to the channel named
+/// after the other party's bare JID.
+fn channel_and_sender(message: &Element, ctx: &ConversionContext) -> Option<(String, String)> {
+    let from = message.get_attr("from").unwrap_or(&ctx.own_jid);
+    if message.get_attr("type") == Some("groupchat") {
+        let sender = if ctx.is_own_occupant(from) {
+            ctx.own_jid.clone()
+        } else {
+            from.to_string()
+        };
+        return Some((bare_jid(from).to_string(), sender));
+    }
+
+    let sender = bare_jid(from).to_string();
+    let channel = if sender == ctx.own_jid {
+        bare_jid(message.get_attr("to")?).to_string()
+    } else {
+        sender.clone()
+    };
+    Some((channel, sender))
+}
+
+/// Convert a message stanza with a body to a Message
+///
+/// The message ID is the stanza's `id` (which XEP-0308 corrections refer to);
+/// the server-assigned archive ID is kept in the metadata as `stanza_id`.
+fn to_message(
+    message: &Element,
+    archive_id: Option<&str>,
+    timestamp: Option<DateTime<Utc>>,
+    ctx: &ConversionContext,
+) -> Option<Message> {
+    let body = message.child_text("body", NS_CLIENT)?;
+    let (channel_id, sender_id) = channel_and_sender(message, ctx)?;
+
+    let stanza_id = archive_id.map(str::to_string).or_else(|| {
+        message
+            .elements()
+            .find(|child| child.is("stanza-id", NS_SID))
+            .and_then(|child| child.get_attr("id"))
+            .map(str::to_string)
+    });
+    let id = message
+        .get_attr("id")
+        .map(str::to_string)
+        .or_else(|| stanza_id.clone())?;
+
+    let mut msg = Message::new(id, body, sender_id, channel_id);
+    if let Some(timestamp) = timestamp.or_else(|| delay_stamp(message)) {
+        msg.created_at = timestamp;
+    }
+    Some(msg.with_metadata(serde_json::json!({
+        "type": message.get_attr("type").unwrap_or("normal"),
+        "from": message.get_attr("from"),
+        "stanza_id": stanza_id,
+    })))
+}
+
+/// Convert an incoming message stanza
+///
+/// Handles carbons of messages sent or received by the user's other clients,
+/// corrections and chat states. Error messages and messages without content
+/// return None.
+pub fn message_to_update(stanza: &Element, ctx: &ConversionContext) -> Option<MessageUpdate> {
+    let message = match unwrap_carbon(stanza, ctx) {
+        Some(forwarded) => forwarded,
+        None if stanza.find("received", NS_CARBONS).is_some()
+            || stanza.find("sent", NS_CARBONS).is_some() =>
+        {
+            return None;
+        }
+        None => stanza,
+    };
+    if message.get_attr("type") == Some("error") {
+        return None;
+    }
+
+    if message.find("body", NS_CLIENT).is_none() {
+        message.find("composing", NS_CHATSTATES)?;
+        let (channel_id, user_id) = channel_and_sender(message, ctx)?;
+        if user_id == ctx.own_jid {
+            return None;
+        }
+        return Some(MessageUpdate::Typing {
+            user_id,
+            channel_id,
+        });
+    }
+
+    let replaces = message
+        .find("replace", NS_CORRECT)
+        .and_then(|replace| replace.get_attr("id"))
+        .map(str::to_string);
+    let mut msg = to_message(message, None, None, ctx)?;
+    match replaces {
+        Some(original_id) => {
+            msg.id = original_id;
+            msg.edited_at = Some(Utc::now());
+            Some(MessageUpdate::Corrected(msg))
+        }
+        None => Some(MessageUpdate::Posted(msg)),
+    }
+}
+
+/// Convert a message archive `<result/>` to a Message
+///
+/// Archived corrections are not messages of their own and return None.
+pub fn archive_result_to_message(result: &Element, ctx: &ConversionContext) -> Option<Message> {
+    let forwarded = result.find("forwarded", NS_FORWARD)?;
+    let message = forwarded.find("message", NS_CLIENT)?;
+    if message.find("replace", NS_CORRECT).is_some() {
+        return None;
+    }
+    to_message(message, result.get_attr("id"), delay_stamp(forwarded), ctx)
+}
+
+/// Something a presence stanza reports
+#[derive(Debug, Clone, PartialEq)]
+pub enum PresenceUpdate {
+    /// An occupant of a room became available or left
+    Occupant {
+        room: String,
+        nick: String,
+        available: bool,
+    },
+    /// A contact's presence changed
+    Contact {
+        jid: String,
+        status: UserStatus,
+        message: Option<String>,
+    },
+}
+
+/// Convert an incoming presence stanza
+///
+/// Subscription requests and errors return None.
+pub fn presence_to_update(stanza: &Element) -> Option<PresenceUpdate> {
+    let from = stanza.get_attr("from")?;
+    let presence_type = stanza.get_attr("type");
+    if !matches!(presence_type, None | Some("unavailable")) {
+        return None;
+    }
+
+    if stanza.find("x", NS_MUC_USER).is_some() {
+        return Some(PresenceUpdate::Occupant {
+            room: bare_jid(from).to_string(),
+            nick: resource(from)?.to_string(),
+            available: presence_type.is_none(),
+        });
+    }
+
+    Some(PresenceUpdate::Contact {
+        jid: bare_jid(from).to_string(),
+        status: presence_to_status(
+            presence_type,
+            stanza.child_text("show", NS_CLIENT).as_deref(),
+        ),
+        message: stanza
+            .child_text("status", NS_CLIENT)
+            .filter(|status| !status.is_empty()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx() -> ConversionContext {
+        let mut ctx = ConversionContext::new("alice@example.com/libcommunicator");
+        ctx.rooms
+            .insert("dev@muc.example.com".to_string(), "alice".to_string());
+        ctx
+    }
+
+    fn chat(from: &str, to: &str, id: &str, body: &str) -> Element {
+        Element::new("message", NS_CLIENT)
+            .attr("from", from)
+            .attr("to", to)
+            .attr("type", "chat")
+            .attr("id", id)
+            .child(Element::new("body", NS_CLIENT).text(body))
+    }
+
+    #[test]
+    fn test_jid_parts() {
+        assert_eq!(bare_jid("alice@example.com/phone"), "alice@example.com");
+        assert_eq!(resource("alice@example.com/phone/1"), Some("phone/1"));
+        assert_eq!(localpart("alice@example.com/phone"), "alice");
+        assert_eq!(domain("alice@example.com/phone"), "example.com");
+        assert_eq!(domain("example.com"), "example.com");
+    }
+
+    #[test]
+    fn test_chat_and_room_messages() {
+        let ctx = ctx();
+
+        let incoming = chat("bob@example.com/laptop", "alice@example.com", "m1", "hi");
+        let Some(MessageUpdate::Posted(msg)) = message_to_update(&incoming, &ctx) else {
+            panic!("expected message");
+        };
+        assert_eq!(msg.id, "m1");
+        assert_eq!(msg.channel_id, "bob@example.com");
+        assert_eq!(msg.sender_id, "bob@example.com");
+
+        let room = Element::new("message", NS_CLIENT)
+            .attr("from", "dev@muc.example.com/alice")
+            .attr("type", "groupchat")
+            .attr("id", "m2")
+            .child(Element::new("body", NS_CLIENT).text("own"))
+            .child(
+                Element::new("stanza-id", NS_SID)
+                    .attr("by", "dev@muc.example.com")
+                    .attr("id", "arch-2"),
+            );
+        let Some(MessageUpdate::Posted(msg)) = message_to_update(&room, &ctx) else {
+            panic!("expected message");
+        };
+        assert_eq!(msg.channel_id, "dev@muc.example.com");
+        assert_eq!(msg.sender_id, "alice@example.com");
+        assert_eq!(msg.metadata.unwrap()["stanza_id"], "arch-2");
+    }
+
+    #[test]
+    fn test_carbons_only_from_own_account() {
+        let ctx = ctx();
+        let sent = chat(
+            "alice@example.com/phone",
+            "bob@example.com",
+            "m3",
+            "from my phone",
+        );
+        let carbon = |from: &str| {
+            Element::new("message", NS_CLIENT)
+                .attr("from", from)
+                .attr("to", "alice@example.com/libcommunicator")
+                .child(
+                    Element::new("sent", NS_CARBONS)
+                        .child(Element::new("forwarded", NS_FORWARD).child(sent.clone())),
+                )
+        };
+
+        let Some(MessageUpdate::Posted(msg)) =
+            message_to_update(&carbon("alice@example.com"), &ctx)
+        else {
+            panic!("expected message");
+        };
+        assert_eq!(msg.channel_id, "bob@example.com");
+        assert_eq!(msg.sender_id, "alice@example.com");
+
+        assert!(message_to_update(&carbon("mallory@example.com"), &ctx).is_none());
+    }
+
+    #[test]
+    fn test_corrections_and_chat_states() {
+        let ctx = ctx();
+        let correction = chat("bob@example.com/laptop", "alice@example.com", "m5", "fixed")
+            .child(Element::new("replace", NS_CORRECT).attr("id", "m4"));
+        let Some(MessageUpdate::Corrected(msg)) = message_to_update(&correction, &ctx) else {
+            panic!("expected correction");
+        };
+        assert_eq!(msg.id, "m4");
+        assert_eq!(msg.text, "fixed");
+
+        let composing = Element::new("message", NS_CLIENT)
+            .attr("from", "bob@example.com/laptop")
+            .attr("type", "chat")
+            .child(Element::new("composing", NS_CHATSTATES));
+        assert!(matches!(
+            message_to_update(&composing, &ctx),
+            Some(MessageUpdate::Typing { user_id, channel_id })
+                if user_id == "bob@example.com" && channel_id == "bob@example.com"
+        ));
+    }
+
+    #[test]
+    fn test_archive_result() {
+        let ctx = ctx();
+        let result = Element::new("result", "urn:xmpp:mam:2")
+            .attr("id", "arch-9")
+            .child(
+                Element::new("forwarded", NS_FORWARD)
+                    .child(Element::new("delay", NS_DELAY).attr("stamp", "2024-05-01T12:00:00Z"))
+                    .child(chat("bob@example.com/x", "alice@example.com", "m9", "old")),
+            );
+        let msg = archive_result_to_message(&result, &ctx).unwrap();
+        assert_eq!(msg.id, "m9");
+        assert_eq!(msg.created_at.to_rfc3339(), "2024-05-01T12:00:00+00:00");
+        assert_eq!(msg.metadata.unwrap()["stanza_id"], "arch-9");
+    }
+
+    #[test]
+    fn test_presence() {
+        let occupant = Element::new("presence", NS_CLIENT)
+            .attr("from", "dev@muc.example.com/bob")
+            .attr("type", "unavailable")
+            .child(Element::new("x", NS_MUC_USER));
+        assert_eq!(
+            presence_to_update(&occupant),
+            Some(PresenceUpdate::Occupant {
+                room: "dev@muc.example.com".to_string(),
+                nick: "bob".to_string(),
+                available: false,
+            })
+        );
+
+        let contact = Element::new("presence", NS_CLIENT)
+            .attr("from", "bob@example.com/laptop")
+            .child(Element::new("show", NS_CLIENT).text("dnd"))
+            .child(Element::new("status", NS_CLIENT).text("Focusing"));
+        assert_eq!(
+            presence_to_update(&contact),
+            Some(PresenceUpdate::Contact {
+                jid: "bob@example.com".to_string(),
+                status: UserStatus::DoNotDisturb,
+                message: Some("Focusing".to_string()),
+            })
+        );
+
+        let subscribe = Element::new("presence", NS_CLIENT)
+            .attr("from", "eve@example.com")
+            .attr("type", "subscribe");
+        assert_eq!(presence_to_update(&subscribe), None);
+    }
+
+    #[test]
+    fn test_stanza_error() {
+        let iq = Element::new("iq", NS_CLIENT).attr("type", "error").child(
+            Element::new("error", NS_CLIENT)
+                .attr("type", "cancel")
+                .child(Element::new("item-not-found", NS_STANZAS)),
+        );
+        assert_eq!(stanza_error(&iq).code, ErrorCode::NotFound);
+    }
+}
@@ -0,0 +1,23 @@
+//! XMPP platform adapter
+//!
+//! This module implements a client-to-server XMPP connection (RFC 6120/6121)
+//! over STARTTLS with SASL PLAIN authentication. Multi-user chat rooms
+//! (XEP-0045) are mapped to channels and roster contacts to direct message
+//! channels; XMPP has no teams, so the workspace methods are unsupported.
+//! Message history is loaded from the server's message archive (XEP-0313)
+//! where available, and message carbons (XEP-0280) keep conversations in sync
+//! with the user's other clients. Capabilities are refined after connecting,
+//! based on what the server advertises through service discovery.
+
+mod client;
+mod convert;
+mod platform_impl;
+mod stream;
+mod types;
+mod xml;
+
+pub use client::XmppClient;
+pub use convert::{presence_to_status, status_to_show};
+pub use platform_impl::XmppPlatform;
+pub use types::*;
+pub use xml::{Element, Node};
@@ -0,0 +1,603 @@
+use async_trait::async_trait;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::error::{Error, ErrorCode, Result};
+use crate::platforms::platform_trait::{Platform, PlatformConfig, PlatformEvent};
+use crate::types::user::UserStatus;
+use crate::types::{
+    Channel, ChannelType, ConnectionInfo, ConnectionState, Message, PlatformCapabilities, Team,
+    User,
+};
+
+use super::client::XmppClient;
+use super::convert::{
+    archive_result_to_message, bare_jid, domain, localpart, message_to_update, presence_to_update,
+    resource, status_to_show, ConversionContext, MessageUpdate, PresenceUpdate,
+};
+use super::stream::Inbound;
+use super::types::{NS_CARBONS, NS_MAM};
+use super::xml::Element;
+
+/// Maximum number of events waiting to be polled
+const EVENT_QUEUE_SIZE: usize = 1000;
+
+/// State built from the stanzas received during a session
+#[derive(Debug, Default)]
+struct SessionState {
+    /// Own JID and joined rooms
+    ctx: ConversionContext,
+    /// Names of bookmarked rooms
+    room_names: HashMap<String, Option<String>>,
+    /// Nicknames of the occupants of joined rooms
+    occupants: HashMap<String, HashSet<String>>,
+    /// Contacts and the names the user gave them
+    roster: HashMap<String, Option<String>>,
+    /// Last known presence of contacts
+    presences: HashMap<String, (UserStatus, Option<String>)>,
+    /// Whether events are queued for polling
+    subscribed: bool,
+    events: VecDeque<PlatformEvent>,
+    /// Whether the stream was closed
+    closed: bool,
+}
+
+impl SessionState {
+    fn push_event(&mut self, event: PlatformEvent) {
+        if !self.subscribed {
+            return;
+        }
+        if self.events.len() >= EVENT_QUEUE_SIZE {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+    }
+
+    /// User ID of a room occupant: the user's own JID for their own nickname
+    fn occupant_id(&self, room: &str, nick: &str) -> String {
+        if self.ctx.rooms.get(room).map(String::as_str) == Some(nick) {
+            self.ctx.own_jid.clone()
+        } else {
+            format!("{room}/{nick}")
+        }
+    }
+
+    fn handle_message(&mut self, stanza: &Element) {
+        let event = match message_to_update(stanza, &self.ctx) {
+            Some(MessageUpdate::Posted(message)) => PlatformEvent::MessagePosted(message),
+            Some(MessageUpdate::Corrected(message)) => PlatformEvent::MessageUpdated(message),
+            Some(MessageUpdate::Typing {
+                user_id,
+                channel_id,
+            }) => PlatformEvent::UserTyping {
+                user_id,
+                channel_id,
+            },
+            None => return,
+        };
+        self.push_event(event);
+    }
+
+    fn handle_presence(&mut self, stanza: &Element) {
+        match presence_to_update(stanza) {
+            Some(PresenceUpdate::Occupant {
+                room,
+                nick,
+                available,
+            }) => {
+                if !self.ctx.rooms.contains_key(&room) {
+                    return;
+                }
+                let user_id = self.occupant_id(&room, &nick);
+                let occupants = self.occupants.entry(room.clone()).or_default();
+                if available {
+                    if occupants.insert(nick) {
+                        self.push_event(PlatformEvent::UserJoinedChannel {
+                            user_id,
+                            channel_id: room,
+                            user: None,
+                            channel: None,
+                        });
+                    }
+                    return;
+                }
+                if !occupants.remove(&nick) {
+                    return;
+                }
+                if user_id == self.ctx.own_jid {
+                    // Left, kicked or banned
+                    self.ctx.rooms.remove(&room);
+                    self.occupants.remove(&room);
+                }
+                self.push_event(PlatformEvent::UserLeftChannel {
+                    user_id,
+                    channel_id: room,
+                    user: None,
+                    channel: None,
+                });
+            }
+            Some(PresenceUpdate::Contact {
+                jid,
+                status,
+                message,
+            }) => {
+                let changed = self
+                    .presences
+                    .get(&jid)
+                    .is_none_or(|(previous, _)| *previous != status);
+                self.presences.insert(jid.clone(), (status, message));
+                if changed {
+                    self.push_event(PlatformEvent::UserStatusChanged {
+                        user_id: jid,
+                        status,
+                    });
+                }
+            }
+            None => {}
+        }
+    }
+}
+
+/// Wrapper struct that implements the Platform trait for XMPP
+pub struct XmppPlatform {
+    client: Arc<XmppClient>,
+    connection_info: Option<ConnectionInfo>,
+    server: String,
+    capabilities: PlatformCapabilities,
+    state: Mutex<SessionState>,
+}
+
+impl XmppPlatform {
+    /// Create a new XMPP platform instance
+    ///
+    /// # Arguments
+    /// * `server` - "host[:port]" to connect to, or "" to connect to the
+    ///   domain of the JID given to `connect`
+    pub fn new(server: &str) -> Result<Self> {
+        Ok(Self {
+            client: Arc::new(XmppClient::new(server)?),
+            connection_info: None,
+            server: server.to_string(),
+            capabilities: PlatformCapabilities::xmpp(),
+            state: Mutex::new(SessionState::default()),
+        })
+    }
+
+    /// Get the underlying client (for accessing XMPP-specific methods)
+    pub fn client(&self) -> &XmppClient {
+        &self.client
+    }
+
+    /// Process the stanzas received since the last call
+    async fn pump(&self) {
+        while let Some(inbound) = self.client.poll_inbound().await {
+            let mut state = self.state.lock().await;
+            match inbound {
+                Inbound::Stanza(stanza) if stanza.name == "message" => {
+                    state.handle_message(&stanza)
+                }
+                Inbound::Stanza(stanza) if stanza.name == "presence" => {
+                    state.handle_presence(&stanza)
+                }
+                Inbound::Stanza(_) => {}
+                Inbound::Closed => {
+                    state.closed = true;
+                    state.push_event(PlatformEvent::ConnectionStateChanged {
+                        state: ConnectionState::Disconnected,
+                        attempt: None,
+                        reason: Some("stream closed by server".to_string()),
+                    });
+                }
+            }
+        }
+    }
+
+    async fn own_jid(&self) -> Result<String> {
+        let jid = self.client.require_jid().await?;
+        Ok(bare_jid(&jid).to_string())
+    }
+
+    /// Whether a channel is a room the user has joined
+    async fn is_joined_room(&self, channel_id: &str) -> bool {
+        self.state.lock().await.ctx.rooms.contains_key(channel_id)
+    }
+
+    /// Whether a JID is a multi-user chat room
+    async fn is_room(&self, jid: &str) -> bool {
+        if self.is_joined_room(jid).await {
+            return true;
+        }
+        self.client
+            .disco_info(jid)
+            .await
+            .is_ok_and(|info| info.has_identity("conference", "text"))
+    }
+
+    async fn room_channel(&self, room: &str) -> Channel {
+        let (joined, bookmarked_name) = {
+            let state = self.state.lock().await;
+            (
+                state.ctx.rooms.contains_key(room),
+                state.room_names.get(room).cloned().flatten(),
+            )
+        };
+        let info = self.client.disco_info(room).await.unwrap_or_default();
+        let display_name = bookmarked_name
+            .or_else(|| info.name().map(str::to_string))
+            .unwrap_or_else(|| localpart(room).to_string());
+        let channel_type = if info.has_feature("muc_public") {
+            ChannelType::Public
+        } else {
+            ChannelType::Private
+        };
+        Channel::new(room, localpart(room), display_name, channel_type).with_metadata(
+            serde_json::json!({
+                "joined": joined,
+                "archived_history": info.has_feature(NS_MAM),
+            }),
+        )
+    }
+
+    async fn direct_channel(&self, jid: &str) -> Channel {
+        let jid = bare_jid(jid);
+        let name = self.state.lock().await.roster.get(jid).cloned().flatten();
+        Channel::new(
+            jid,
+            jid,
+            name.unwrap_or_else(|| localpart(jid).to_string()),
+            ChannelType::DirectMessage,
+        )
+    }
+}
+
+#[async_trait]
+impl Platform for XmppPlatform {
+    fn capabilities(&self) -> &PlatformCapabilities {
+        &self.capabilities
+    }
+
+    async fn connect(&mut self, config: PlatformConfig) -> Result<ConnectionInfo> {
+        let (Some(jid), Some(password)) = (
+            config
+                .credentials
+                .get("jid")
+                .or_else(|| config.credentials.get("login_id")),
+            config.credentials.get("password"),
+        ) else {
+            return Err(Error::new(
+                ErrorCode::InvalidArgument,
+                "Missing authentication credentials (provide 'jid'+'password')",
+            ));
+        };
+
+        let full_jid = self.client.connect(jid, password).await?;
+        let own_jid = bare_jid(&full_jid).to_string();
+
+        // Message history depends on the server's archive support
+        let mut capabilities = PlatformCapabilities::xmpp();
+        if let Ok(info) = self.client.disco_info(&own_jid).await {
+            if info.has_feature(NS_MAM) {
+                capabilities = capabilities.with_message_history();
+            }
+        }
+        if let Ok(info) = self.client.disco_info(domain(&own_jid)).await {
+            if info.has_feature(NS_CARBONS) {
+                // Without carbons, messages from the user's other clients are missed
+                let _ = self.client.enable_carbons().await;
+            }
+        }
+        self.capabilities = capabilities;
+
+        let roster = self.client.get_roster().await.unwrap_or_default();
+        let bookmarks = self.client.get_bookmarks().await.unwrap_or_default();
+        self.client.send_presence(None, None, None).await?;
+
+        let mut state = SessionState {
+            ctx: ConversionContext::new(&own_jid),
+            roster: roster
+                .into_iter()
+                .map(|item| (bare_jid(&item.jid).to_string(), item.name))
+                .collect(),
+            ..Default::default()
+        };
+        for bookmark in bookmarks {
+            if bookmark.autojoin {
+                let nick = bookmark
+                    .nick
+                    .clone()
+                    .unwrap_or_else(|| localpart(&own_jid).to_string());
+                self.client.join_room(&bookmark.jid, &nick).await?;
+                state.ctx.rooms.insert(bookmark.jid.clone(), nick);
+            }
+            state.room_names.insert(bookmark.jid, bookmark.name);
+        }
+        *self.state.lock().await = state;
+
+        let server = if self.server.is_empty() {
+            domain(&own_jid).to_string()
+        } else {
+            self.server.clone()
+        };
+        let conn_info = ConnectionInfo::new("xmpp", server, &own_jid, localpart(&own_jid))
+            .with_metadata(serde_json::json!({ "jid": full_jid }));
+        self.connection_info = Some(conn_info.clone());
+
+        Ok(conn_info)
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        self.client.disconnect().await?;
+        *self.state.lock().await = SessionState::default();
+        self.connection_info = None;
+        Ok(())
+    }
+
+    fn connection_info(&self) -> Option<&ConnectionInfo> {
+        self.connection_info.as_ref()
+    }
+
+    async fn send_message(&self, channel_id: &str, text: &str) -> Result<Message> {
+        let own_jid = self.own_jid().await?;
+        let message_type = if self.is_joined_room(channel_id).await {
+            "groupchat"
+        } else {
+            "chat"
+        };
+        let id = self
+            .client
+            .send_message(channel_id, message_type, text)
+            .await?;
+        Ok(Message::new(id, text, own_jid, channel_id)
+            .with_metadata(serde_json::json!({ "type": message_type })))
+    }
+
+    async fn get_channels(&self) -> Result<Vec<Channel>> {
+        let (mut rooms, contacts) = {
+            let state = self.state.lock().await;
+            let rooms: HashSet<String> = state
+                .ctx
+                .rooms
+                .keys()
+                .chain(state.room_names.keys())
+                .cloned()
+                .collect();
+            (
+                rooms.into_iter().collect::<Vec<_>>(),
+                state.roster.keys().cloned().collect::<Vec<_>>(),
+            )
+        };
+        rooms.sort();
+
+        let mut channels = Vec::new();
+        for room in &rooms {
+            channels.push(self.room_channel(room).await);
+        }
+        for contact in &contacts {
+            channels.push(self.direct_channel(contact).await);
+        }
+        Ok(channels)
+    }
+
+    async fn get_channel(&self, channel_id: &str) -> Result<Channel> {
+        if self.is_room(channel_id).await {
+            Ok(self.room_channel(channel_id).await)
+        } else {
+            Ok(self.direct_channel(channel_id).await)
+        }
+    }
+
+    async fn get_messages(&self, channel_id: &str, limit: usize) -> Result<Vec<Message>> {
+        if !self.capabilities.supports_message_history {
+            return Err(Error::unsupported(
+                "XMPP server does not support message archives (XEP-0313)",
+            ));
+        }
+
+        let results = if self.is_room(channel_id).await {
+            self.client.mam_query(Some(channel_id), None, limit).await?
+        } else {
+            self.client
+                .mam_query(None, Some(bare_jid(channel_id)), limit)
+                .await?
+        };
+
+        let ctx = self.state.lock().await.ctx.clone();
+        Ok(results
+            .iter()
+            .filter_map(|result| archive_result_to_message(result, &ctx))
+            .collect())
+    }
+
+    async fn get_channel_members(&self, channel_id: &str) -> Result<Vec<User>> {
+        self.pump().await;
+        let own_jid = self.own_jid().await?;
+        let state = self.state.lock().await;
+
+        if state.ctx.rooms.contains_key(channel_id) {
+            let mut users: Vec<User> = state
+                .occupants
+                .get(channel_id)
+                .into_iter()
+                .flatten()
+                .map(|nick| User::new(state.occupant_id(channel_id, nick), nick, nick))
+                .collect();
+            users.sort_by(|a, b| a.id.cmp(&b.id));
+            return Ok(users);
+        }
+
+        let peer = bare_jid(channel_id);
+        let peer_name = state.roster.get(peer).cloned().flatten();
+        Ok(vec![
+            User::new(&own_jid, localpart(&own_jid), localpart(&own_jid)),
+            User::new(
+                peer,
+                localpart(peer),
+                peer_name.unwrap_or_else(|| localpart(peer).to_string()),
+            ),
+        ])
+    }
+
+    async fn get_user(&self, user_id: &str) -> Result<User> {
+        self.pump().await;
+        let state = self.state.lock().await;
+
+        // Occupant JIDs (room@service/nick) only reveal the nickname
+        if let (true, Some(nick)) = (
+            state.ctx.rooms.contains_key(bare_jid(user_id)),
+            resource(user_id),
+        ) {
+            return Ok(User::new(user_id, nick, nick));
+        }
+
+        let jid = bare_jid(user_id);
+        let name = state
+            .roster
+            .get(jid)
+            .cloned()
+            .flatten()
+            .unwrap_or_else(|| localpart(jid).to_string());
+        let mut user = User::new(jid, localpart(jid), name);
+        if let Some((status, message)) = state.presences.get(jid) {
+            user = user.with_status(*status);
+            if let Some(message) = message {
+                user = user.with_status_message(message);
+            }
+        }
+        Ok(user)
+    }
+
+    async fn get_current_user(&self) -> Result<User> {
+        let own_jid = self.own_jid().await?;
+        self.get_user(&own_jid).await
+    }
+
+    async fn create_direct_channel(&self, user_id: &str) -> Result<Channel> {
+        // One-to-one chats need no setup
+        if !bare_jid(user_id).contains('@') {
+            return Err(Error::invalid_argument(format!(
+                "Invalid JID (expected user@domain): {user_id}"
+            )));
+        }
+        Ok(self.direct_channel(user_id).await)
+    }
+
+    async fn create_channel(
+        &self,
+        _team_id: &str,
+        name: &str,
+        display_name: &str,
+        is_private: bool,
+    ) -> Result<Channel> {
+        let own_jid = self.own_jid().await?;
+        let service = self.client.find_muc_service().await?;
+        let room = format!("{}@{service}", name.to_lowercase());
+        let nick = localpart(&own_jid).to_string();
+
+        // Joining a room that does not exist creates it; it stays locked
+        // until it is configured
+        self.client.join_room(&room, &nick).await?;
+        self.state.lock().await.ctx.rooms.insert(room.clone(), nick);
+
+        let (public, members_only) = if is_private { ("0", "1") } else { ("1", "0") };
+        self.client
+            .configure_room(
+                &room,
+                &[
+                    ("muc#roomconfig_roomname", display_name),
+                    ("muc#roomconfig_publicroom", public),
+                    ("muc#roomconfig_membersonly", members_only),
+                    ("muc#roomconfig_persistentroom", "1"),
+                ],
+            )
+            .await?;
+
+        Ok(self.room_channel(&room).await)
+    }
+
+    async fn get_teams(&self) -> Result<Vec<Team>> {
+        Err(Error::unsupported("XMPP has no teams/workspaces"))
+    }
+
+    async fn get_team(&self, team_id: &str) -> Result<Team> {
+        let _ = team_id;
+        Err(Error::unsupported("XMPP has no teams/workspaces"))
+    }
+
+    async fn set_status(&self, status: UserStatus, custom_message: Option<&str>) -> Result<()> {
+        let show = status_to_show(status);
+        self.client
+            .send_presence(None, show, custom_message)
+            .await?;
+
+        // Room presence is directed, so rooms are not covered by the broadcast
+        let occupant_jids: Vec<String> = self
+            .state
+            .lock()
+            .await
+            .ctx
+            .rooms
+            .iter()
+            .map(|(room, nick)| format!("{room}/{nick}"))
+            .collect();
+        for occupant_jid in occupant_jids {
+            self.client
+                .send_presence(Some(&occupant_jid), show, custom_message)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn get_user_status(&self, user_id: &str) -> Result<UserStatus> {
+        self.pump().await;
+        let state = self.state.lock().await;
+        let jid = bare_jid(user_id);
+        Ok(match state.presences.get(jid) {
+            Some((status, _)) => *status,
+            // Contacts send their presence when they are available
+            None if state.roster.contains_key(jid) => UserStatus::Offline,
+            None => UserStatus::Unknown,
+        })
+    }
+
+    async fn subscribe_events(&mut self) -> Result<()> {
+        self.client.require_jid().await?;
+        self.state.lock().await.subscribed = true;
+        Ok(())
+    }
+
+    async fn unsubscribe_events(&mut self) -> Result<()> {
+        let mut state = self.state.lock().await;
+        state.subscribed = false;
+        state.events.clear();
+        Ok(())
+    }
+
+    async fn poll_event(&mut self) -> Result<Option<PlatformEvent>> {
+        self.pump().await;
+        let mut state = self.state.lock().await;
+        if state.closed {
+            if let Some(info) = self.connection_info.as_mut() {
+                info.state = ConnectionState::Disconnected;
+            }
+        }
+        Ok(state.events.pop_front())
+    }
+
+    // ========================================================================
+    // Extended Platform Methods Implementation
+    // ========================================================================
+
+    async fn send_typing_indicator(&self, channel_id: &str, parent_id: Option<&str>) -> Result<()> {
+        // Chat states are per conversation
+        let _ = parent_id;
+        let message_type = if self.is_joined_room(channel_id).await {
+            "groupchat"
+        } else {
+            "chat"
+        };
+        self.client
+            .send_chat_state(channel_id, message_type, "composing")
+            .await
+    }
+}
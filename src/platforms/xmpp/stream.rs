@@ -0,0 +1,547 @@
+//! XMPP stream negotiation and the stanza reader
+//!
+//! `negotiate` opens a client-to-server stream and runs it through STARTTLS,
+//! SASL PLAIN authentication and resource binding (RFC 6120). Plaintext
+//! streams are refused: PLAIN sends the password as is, so the server must
+//! offer STARTTLS. Once negotiated, the stream is split; `Reader::start` reads
+//! stanzas in a background task, routing IQ responses and archive results to
+//! the requests waiting for them and queueing everything else for polling.
+
+use base64::Engine;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadHalf, WriteHalf};
+use tokio::net::TcpStream;
+use tokio::sync::{mpsc, oneshot, Mutex};
+use tokio::task::JoinHandle;
+use tokio_rustls::client::TlsStream;
+use tokio_rustls::rustls::pki_types::ServerName;
+use tokio_rustls::rustls::{ClientConfig, RootCertStore};
+use tokio_rustls::TlsConnector;
+
+use crate::error::{Error, ErrorCode, Result};
+
+use super::types::{NS_BIND, NS_MAM, NS_PING, NS_SASL, NS_SESSION, NS_STANZAS, NS_TLS};
+use super::xml::{escape, Element, StreamEvent, StreamParser, NS_CLIENT, NS_STREAM};
+
+/// Resource requested when binding; the server may assign another one
+pub const RESOURCE: &str = "libcommunicator";
+
+/// How long connecting and each negotiation step may take
+const NEGOTIATION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Interval of whitespace keepalives, which also detect dead connections
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Maximum number of stanzas waiting to be polled
+const INBOUND_QUEUE_SIZE: usize = 1000;
+
+/// The encrypted connection to the server
+pub type Transport = TlsStream<TcpStream>;
+
+/// Write half of the stream, shared by everything that sends stanzas
+pub type Writer = Arc<Mutex<WriteHalf<Transport>>>;
+
+fn network_error(msg: impl Into<String>) -> Error {
+    Error::new(ErrorCode::NetworkError, msg)
+}
+
+/// Write raw XML to the stream
+pub async fn write_raw<W: AsyncWrite + Unpin>(io: &mut W, data: &str) -> Result<()> {
+    io.write_all(data.as_bytes())
+        .await
+        .map_err(|e| network_error(format!("Failed to write to XMPP stream: {e}")))?;
+    io.flush()
+        .await
+        .map_err(|e| network_error(format!("Failed to write to XMPP stream: {e}")))
+}
+
+/// A stream during negotiation, before it is split
+struct XmlStream<S> {
+    io: S,
+    parser: StreamParser,
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> XmlStream<S> {
+    fn new(io: S) -> Self {
+        Self {
+            io,
+            parser: StreamParser::new(),
+        }
+    }
+
+    /// Open (or restart) the stream and read the server's stream features
+    async fn open(&mut self, domain: &str) -> Result<Element> {
+        self.parser = StreamParser::new();
+        let header = format!(
+            "<?xml version='1.0'?><stream:stream to='{}' version='1.0' xml:lang='en' \
+             xmlns='{NS_CLIENT}' xmlns:stream='{NS_STREAM}'>",
+            escape(domain)
+        );
+        write_raw(&mut self.io, &header).await?;
+
+        match self.next_event().await? {
+            StreamEvent::Start(_) => {}
+            _ => return Err(network_error("Server did not open an XMPP stream")),
+        }
+        let features = self.next_element().await?;
+        if !features.is("features", NS_STREAM) {
+            return Err(network_error("Server did not send stream features"));
+        }
+        Ok(features)
+    }
+
+    async fn send(&mut self, element: &Element) -> Result<()> {
+        write_raw(&mut self.io, &element.to_xml()).await
+    }
+
+    async fn next_event(&mut self) -> Result<StreamEvent> {
+        let mut buf = [0u8; 4096];
+        loop {
+            if let Some(event) = self.parser.next_event()? {
+                return Ok(event);
+            }
+            let read = tokio::time::timeout(NEGOTIATION_TIMEOUT, self.io.read(&mut buf))
+                .await
+                .map_err(|_| Error::new(ErrorCode::Timeout, "XMPP negotiation timed out"))?
+                .map_err(|e| network_error(format!("Failed to read from XMPP stream: {e}")))?;
+            if read == 0 {
+                return Err(network_error("XMPP server closed the connection"));
+            }
+            self.parser.feed(&buf[..read]);
+        }
+    }
+
+    /// Read the next element, failing on stream errors and the end of the stream
+    async fn next_element(&mut self) -> Result<Element> {
+        match self.next_event().await? {
+            StreamEvent::Element(element) if element.is("error", NS_STREAM) => {
+                Err(stream_error(&element))
+            }
+            StreamEvent::Element(element) => Ok(element),
+            _ => Err(network_error("XMPP server closed the stream")),
+        }
+    }
+}
+
+/// Convert a `<stream:error/>` to an Error
+fn stream_error(element: &Element) -> Error {
+    let condition = element
+        .elements()
+        .find(|child| child.name != "text")
+        .map(|child| child.name.clone())
+        .unwrap_or_else(|| "undefined-condition".to_string());
+    network_error(format!("XMPP stream error: {condition}"))
+}
+
+fn tls_connector() -> Result<TlsConnector> {
+    let roots = RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    let provider = Arc::new(tokio_rustls::rustls::crypto::ring::default_provider());
+    let config = ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|e| network_error(format!("Failed to configure TLS: {e}")))?
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    Ok(TlsConnector::from(Arc::new(config)))
+}
+
+/// SASL PLAIN initial response (RFC 4616)
+pub fn sasl_plain(username: &str, password: &str) -> String {
+    base64::engine::general_purpose::STANDARD.encode(format!("\0{username}\0{password}"))
+}
+
+/// Connect to a server and negotiate an authenticated stream
+///
+/// # Arguments
+/// * `host` - Host name or address to connect to
+/// * `port` - Port to connect to (usually 5222)
+/// * `domain` - Domain of the account, used as stream target and TLS server name
+/// * `username` - Local part of the account's JID
+/// * `password` - The account's password
+///
+/// # Returns
+/// The stream, the parser holding its state and any data already received,
+/// and the full JID the server bound to the stream
+pub async fn negotiate(
+    host: &str,
+    port: u16,
+    domain: &str,
+    username: &str,
+    password: &str,
+) -> Result<(Transport, StreamParser, String)> {
+    let tcp = tokio::time::timeout(NEGOTIATION_TIMEOUT, TcpStream::connect((host, port)))
+        .await
+        .map_err(|_| Error::new(ErrorCode::Timeout, "Connecting to XMPP server timed out"))?
+        .map_err(|e| network_error(format!("Failed to connect to {host}:{port}: {e}")))?;
+
+    // STARTTLS
+    let mut stream = XmlStream::new(tcp);
+    let features = stream.open(domain).await?;
+    if features.find("starttls", NS_TLS).is_none() {
+        return Err(network_error(
+            "XMPP server does not offer STARTTLS; refusing to authenticate in plaintext",
+        ));
+    }
+    stream.send(&Element::new("starttls", NS_TLS)).await?;
+    let response = stream.next_element().await?;
+    if !response.is("proceed", NS_TLS) {
+        return Err(network_error("XMPP server refused STARTTLS"));
+    }
+
+    let server_name = ServerName::try_from(domain.to_string())
+        .map_err(|_| Error::invalid_argument(format!("Invalid XMPP domain: {domain}")))?;
+    let tls = tls_connector()?
+        .connect(server_name, stream.io)
+        .await
+        .map_err(|e| network_error(format!("TLS handshake failed: {e}")))?;
+
+    // SASL PLAIN
+    let mut stream = XmlStream::new(tls);
+    let features = stream.open(domain).await?;
+    let offers_plain = features
+        .find("mechanisms", NS_SASL)
+        .is_some_and(|mechanisms| {
+            mechanisms
+                .elements()
+                .any(|mechanism| mechanism.text_content() == "PLAIN")
+        });
+    if !offers_plain {
+        return Err(Error::new(
+            ErrorCode::Unsupported,
+            "XMPP server does not offer SASL PLAIN authentication",
+        ));
+    }
+    let auth = Element::new("auth", NS_SASL)
+        .attr("mechanism", "PLAIN")
+        .text(sasl_plain(username, password));
+    stream.send(&auth).await?;
+    let response = stream.next_element().await?;
+    if !response.is("success", NS_SASL) {
+        let condition = response
+            .elements()
+            .find(|child| child.name != "text")
+            .map(|child| child.name.clone())
+            .unwrap_or_else(|| "unknown".to_string());
+        return Err(Error::new(
+            ErrorCode::AuthenticationFailed,
+            format!("XMPP authentication failed: {condition}"),
+        ));
+    }
+
+    // Resource binding
+    let features = stream.open(domain).await?;
+    let bind = Element::new("iq", NS_CLIENT)
+        .attr("type", "set")
+        .attr("id", "bind")
+        .child(
+            Element::new("bind", NS_BIND).child(Element::new("resource", NS_BIND).text(RESOURCE)),
+        );
+    stream.send(&bind).await?;
+    let response = stream.next_element().await?;
+    let jid = response
+        .find("bind", NS_BIND)
+        .and_then(|bind| bind.child_text("jid", NS_BIND))
+        .filter(|_| response.get_attr("type") == Some("result"))
+        .ok_or_else(|| network_error("XMPP resource binding failed"))?;
+
+    // Servers following RFC 3921 still require a session
+    if let Some(session) = features.find("session", NS_SESSION) {
+        if session.find("optional", NS_SESSION).is_none() {
+            let iq = Element::new("iq", NS_CLIENT)
+                .attr("type", "set")
+                .attr("id", "session")
+                .child(Element::new("session", NS_SESSION));
+            stream.send(&iq).await?;
+            let response = stream.next_element().await?;
+            if response.get_attr("type") != Some("result") {
+                return Err(network_error("XMPP session establishment failed"));
+            }
+        }
+    }
+
+    Ok((stream.io, stream.parser, jid))
+}
+
+/// Something received on the stream that is not a response to a request
+#[derive(Debug, Clone)]
+pub enum Inbound {
+    /// A message or presence stanza
+    Stanza(Element),
+    /// The stream was closed or failed
+    Closed,
+}
+
+/// Requests waiting for their responses
+#[derive(Debug, Default)]
+pub struct Pending {
+    /// IQ requests by stanza ID
+    iqs: StdMutex<HashMap<String, oneshot::Sender<Element>>>,
+    /// Archive query results by query ID
+    archive: StdMutex<HashMap<String, Vec<Element>>>,
+    /// Whether the stream has been closed
+    closed: AtomicBool,
+}
+
+impl Pending {
+    /// Register an IQ request
+    ///
+    /// # Returns
+    /// A receiver for the response, or an error if the stream is closed
+    pub fn register_iq(&self, id: &str) -> Result<oneshot::Receiver<Element>> {
+        if self.closed.load(Ordering::Acquire) {
+            return Err(network_error("XMPP stream is closed"));
+        }
+        let (tx, rx) = oneshot::channel();
+        self.iqs.lock().unwrap().insert(id.to_string(), tx);
+        Ok(rx)
+    }
+
+    /// Stop waiting for an IQ response (e.g. after a timeout)
+    pub fn forget_iq(&self, id: &str) {
+        self.iqs.lock().unwrap().remove(id);
+    }
+
+    /// Start collecting the results of an archive query
+    pub fn register_archive_query(&self, query_id: &str) {
+        self.archive
+            .lock()
+            .unwrap()
+            .insert(query_id.to_string(), Vec::new());
+    }
+
+    /// Stop collecting the results of an archive query
+    ///
+    /// # Returns
+    /// The `<result/>` elements received so far, in order
+    pub fn take_archive_results(&self, query_id: &str) -> Vec<Element> {
+        self.archive
+            .lock()
+            .unwrap()
+            .remove(query_id)
+            .unwrap_or_default()
+    }
+
+    /// Route a stanza to a waiting request
+    ///
+    /// # Returns
+    /// The stanza if no request was waiting for it
+    fn route(&self, stanza: Element) -> Option<Element> {
+        if stanza.name == "iq" && matches!(stanza.get_attr("type"), Some("result" | "error")) {
+            let waiter = stanza
+                .get_attr("id")
+                .and_then(|id| self.iqs.lock().unwrap().remove(id));
+            if let Some(waiter) = waiter {
+                let _ = waiter.send(stanza);
+            }
+            return None;
+        }
+
+        if stanza.name == "message" {
+            let query_id = stanza
+                .find("result", NS_MAM)
+                .and_then(|result| result.get_attr("queryid"))
+                .map(str::to_string);
+            if let Some(query_id) = query_id {
+                if let Some(results) = self.archive.lock().unwrap().get_mut(&query_id) {
+                    if let Some(result) = stanza.find("result", NS_MAM) {
+                        results.push(result.clone());
+                    }
+                }
+                return None;
+            }
+        }
+
+        Some(stanza)
+    }
+
+    /// Mark the stream closed and fail all waiting requests
+    fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+        self.iqs.lock().unwrap().clear();
+    }
+}
+
+/// Reads stanzas from the stream in a background task
+pub struct Reader {
+    inbound_rx: Mutex<mpsc::Receiver<Inbound>>,
+    task: JoinHandle<()>,
+}
+
+impl Reader {
+    /// Start reading from the stream
+    ///
+    /// The reader also answers pings from the server and sends whitespace
+    /// keepalives through `writer`.
+    pub fn start(
+        read: ReadHalf<Transport>,
+        parser: StreamParser,
+        writer: Writer,
+        pending: Arc<Pending>,
+    ) -> Self {
+        let (inbound_tx, inbound_rx) = mpsc::channel(INBOUND_QUEUE_SIZE);
+        let task = tokio::spawn(run(read, parser, writer, pending, inbound_tx));
+        Self {
+            inbound_rx: Mutex::new(inbound_rx),
+            task,
+        }
+    }
+
+    /// Take the next queued stanza, if any
+    pub async fn poll(&self) -> Option<Inbound> {
+        self.inbound_rx.lock().await.try_recv().ok()
+    }
+}
+
+impl Drop for Reader {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Answer an IQ request addressed to this client
+///
+/// Pings are answered; everything else is rejected as RFC 6120 requires.
+fn iq_response(request: &Element) -> Element {
+    let mut response = Element::new("iq", NS_CLIENT);
+    if let Some(id) = request.get_attr("id") {
+        response.set_attr("id", id);
+    }
+    if let Some(from) = request.get_attr("from") {
+        response.set_attr("to", from);
+    }
+    if request.find("ping", NS_PING).is_some() {
+        return response.attr("type", "result");
+    }
+    response.attr("type", "error").child(
+        Element::new("error", NS_CLIENT)
+            .attr("type", "cancel")
+            .child(Element::new("service-unavailable", NS_STANZAS)),
+    )
+}
+
+async fn run(
+    mut read: ReadHalf<Transport>,
+    mut parser: StreamParser,
+    writer: Writer,
+    pending: Arc<Pending>,
+    inbound_tx: mpsc::Sender<Inbound>,
+) {
+    let mut buf = [0u8; 8192];
+    let mut keepalive = tokio::time::interval(KEEPALIVE_INTERVAL);
+    keepalive.tick().await;
+
+    'read: loop {
+        tokio::select! {
+            result = read.read(&mut buf) => {
+                match result {
+                    Ok(0) | Err(_) => break 'read,
+                    Ok(n) => parser.feed(&buf[..n]),
+                }
+            }
+            _ = keepalive.tick() => {
+                if write_raw(&mut *writer.lock().await, " ").await.is_err() {
+                    break 'read;
+                }
+                continue;
+            }
+        }
+
+        loop {
+            let stanza = match parser.next_event() {
+                Ok(Some(StreamEvent::Element(element))) => element,
+                Ok(Some(StreamEvent::Start(_))) => continue,
+                Ok(None) => break,
+                Ok(Some(StreamEvent::End)) | Err(_) => break 'read,
+            };
+            if stanza.is("error", NS_STREAM) {
+                break 'read;
+            }
+            let Some(stanza) = pending.route(stanza) else {
+                continue;
+            };
+            if stanza.name == "iq" {
+                if matches!(stanza.get_attr("type"), Some("get" | "set")) {
+                    let response = iq_response(&stanza).to_xml();
+                    if write_raw(&mut *writer.lock().await, &response)
+                        .await
+                        .is_err()
+                    {
+                        break 'read;
+                    }
+                }
+                continue;
+            }
+            // Drop stanzas rather than stall the stream when nobody polls
+            let _ = inbound_tx.try_send(Inbound::Stanza(stanza));
+        }
+    }
+
+    pending.close();
+    let _ = inbound_tx.send(Inbound::Closed).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sasl_plain() {
+        assert_eq!(sasl_plain("alice", "secret"), "AGFsaWNlAHNlY3JldA==");
+    }
+
+    #[test]
+    fn test_routes_responses_and_archive_results() {
+        let pending = Pending::default();
+        let mut rx = pending.register_iq("q1").unwrap();
+        pending.register_archive_query("mam1");
+
+        let result = Element::new("iq", NS_CLIENT)
+            .attr("type", "result")
+            .attr("id", "q1");
+        assert!(pending.route(result).is_none());
+        assert!(rx.try_recv().is_ok());
+
+        let archived = Element::new("message", NS_CLIENT).child(
+            Element::new("result", NS_MAM)
+                .attr("queryid", "mam1")
+                .attr("id", "arch1"),
+        );
+        assert!(pending.route(archived).is_none());
+        let results = pending.take_archive_results("mam1");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].get_attr("id"), Some("arch1"));
+
+        let chat = Element::new("message", NS_CLIENT).attr("type", "chat");
+        assert!(pending.route(chat).is_some());
+
+        pending.close();
+        assert!(pending.register_iq("q2").is_err());
+    }
+
+    #[test]
+    fn test_answers_pings_and_rejects_other_requests() {
+        let ping = Element::new("iq", NS_CLIENT)
+            .attr("type", "get")
+            .attr("id", "p1")
+            .attr("from", "example.com")
+            .child(Element::new("ping", NS_PING));
+        let response = iq_response(&ping);
+        assert_eq!(response.get_attr("type"), Some("result"));
+        assert_eq!(response.get_attr("to"), Some("example.com"));
+
+        let version = Element::new("iq", NS_CLIENT)
+            .attr("type", "get")
+            .attr("id", "v1")
+            .child(Element::new("query", "jabber:iq:version"));
+        let response = iq_response(&version);
+        assert_eq!(response.get_attr("type"), Some("error"));
+        assert!(response
+            .find("error", NS_CLIENT)
+            .and_then(|error| error.find("service-unavailable", NS_STANZAS))
+            .is_some());
+    }
+}
@@ -0,0 +1,109 @@
+//! XMPP namespaces and protocol data types
+
+/// STARTTLS negotiation (RFC 6120)
+pub const NS_TLS: &str = "urn:ietf:params:xml:ns:xmpp-tls";
+/// SASL authentication (RFC 6120)
+pub const NS_SASL: &str = "urn:ietf:params:xml:ns:xmpp-sasl";
+/// Resource binding (RFC 6120)
+pub const NS_BIND: &str = "urn:ietf:params:xml:ns:xmpp-bind";
+/// Legacy session establishment (RFC 3921)
+pub const NS_SESSION: &str = "urn:ietf:params:xml:ns:xmpp-session";
+/// Stanza error conditions (RFC 6120)
+pub const NS_STANZAS: &str = "urn:ietf:params:xml:ns:xmpp-stanzas";
+/// Roster management (RFC 6121)
+pub const NS_ROSTER: &str = "jabber:iq:roster";
+/// Service discovery (XEP-0030)
+pub const NS_DISCO_INFO: &str = "http://jabber.org/protocol/disco#info";
+pub const NS_DISCO_ITEMS: &str = "http://jabber.org/protocol/disco#items";
+/// Multi-user chat (XEP-0045)
+pub const NS_MUC: &str = "http://jabber.org/protocol/muc";
+pub const NS_MUC_USER: &str = "http://jabber.org/protocol/muc#user";
+pub const NS_MUC_OWNER: &str = "http://jabber.org/protocol/muc#owner";
+/// Data forms (XEP-0004)
+pub const NS_DATA: &str = "jabber:x:data";
+/// Result set management (XEP-0059)
+pub const NS_RSM: &str = "http://jabber.org/protocol/rsm";
+/// Message archive management (XEP-0313)
+pub const NS_MAM: &str = "urn:xmpp:mam:2";
+/// Message carbons (XEP-0280)
+pub const NS_CARBONS: &str = "urn:xmpp:carbons:2";
+/// Stanza forwarding (XEP-0297)
+pub const NS_FORWARD: &str = "urn:xmpp:forward:0";
+/// Delayed delivery (XEP-0203)
+pub const NS_DELAY: &str = "urn:xmpp:delay";
+/// Unique and stable stanza IDs (XEP-0359)
+pub const NS_SID: &str = "urn:xmpp:sid:0";
+/// Last message correction (XEP-0308)
+pub const NS_CORRECT: &str = "urn:xmpp:message-correct:0";
+/// Chat state notifications (XEP-0085)
+pub const NS_CHATSTATES: &str = "http://jabber.org/protocol/chatstates";
+/// XMPP ping (XEP-0199)
+pub const NS_PING: &str = "urn:xmpp:ping";
+/// PEP native bookmarks (XEP-0402)
+pub const NS_BOOKMARKS2: &str = "urn:xmpp:bookmarks:1";
+/// Private XML storage bookmarks (XEP-0048 via XEP-0049)
+pub const NS_BOOKMARKS: &str = "storage:bookmarks";
+pub const NS_PRIVATE: &str = "jabber:iq:private";
+/// Publish-subscribe (XEP-0060)
+pub const NS_PUBSUB: &str = "http://jabber.org/protocol/pubsub";
+
+/// Result of a service discovery info query
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DiscoInfo {
+    /// Identities as (category, type, name)
+    pub identities: Vec<(String, String, Option<String>)>,
+    /// Supported feature namespaces
+    pub features: Vec<String>,
+}
+
+impl DiscoInfo {
+    /// Whether the entity supports a feature
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.features.iter().any(|f| f == feature)
+    }
+
+    /// Whether the entity has an identity of the given category and type
+    pub fn has_identity(&self, category: &str, kind: &str) -> bool {
+        self.identities
+            .iter()
+            .any(|(c, t, _)| c == category && t == kind)
+    }
+
+    /// Name of the first identity that has one
+    pub fn name(&self) -> Option<&str> {
+        self.identities
+            .iter()
+            .find_map(|(_, _, name)| name.as_deref())
+    }
+}
+
+/// An item returned by a service discovery items query
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscoItem {
+    pub jid: String,
+    pub name: Option<String>,
+}
+
+/// A contact in the user's roster
+#[derive(Debug, Clone, PartialEq)]
+pub struct RosterItem {
+    /// Bare JID of the contact
+    pub jid: String,
+    /// Name the user gave the contact
+    pub name: Option<String>,
+    /// Presence subscription state ("none", "to", "from", "both")
+    pub subscription: String,
+}
+
+/// A bookmarked multi-user chat room
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bookmark {
+    /// Bare JID of the room
+    pub jid: String,
+    /// Name the user gave the room
+    pub name: Option<String>,
+    /// Whether the room should be joined on login
+    pub autojoin: bool,
+    /// Nickname to use in the room
+    pub nick: Option<String>,
+}
@@ -0,0 +1,574 @@
+//! Minimal XML support for XMPP streams
+//!
+//! An XMPP session is one long XML document: the `<stream:stream>` root is
+//! opened at the start and each stanza is a complete child element of it.
+//! `StreamParser` is fed raw bytes as they arrive and yields the stream header
+//! and then every complete top-level element. It supports the XML subset
+//! XMPP allows (RFC 6120 §11): no DTDs, and comments and processing
+//! instructions are skipped.
+
+use std::collections::HashMap;
+
+use crate::error::{Error, ErrorCode, Result};
+
+/// Namespace of the stream root and stream-level elements
+pub const NS_STREAM: &str = "http://etherx.jabber.org/streams";
+
+/// Default namespace of stanzas on a client stream
+pub const NS_CLIENT: &str = "jabber:client";
+
+/// A node in an element's content
+#[derive(Debug, Clone, PartialEq)]
+pub enum Node {
+    Element(Element),
+    Text(String),
+}
+
+/// An XML element with its resolved namespace
+#[derive(Debug, Clone, PartialEq)]
+pub struct Element {
+    /// Local name, without a namespace prefix
+    pub name: String,
+    /// Namespace the element belongs to
+    pub ns: String,
+    /// Attributes other than namespace declarations, in document order
+    pub attrs: Vec<(String, String)>,
+    /// Child elements and text
+    pub children: Vec<Node>,
+}
+
+impl Element {
+    /// Create an empty element
+    pub fn new(name: impl Into<String>, ns: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ns: ns.into(),
+            attrs: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Set an attribute
+    pub fn attr(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.set_attr(name, value);
+        self
+    }
+
+    /// Set an attribute in place
+    pub fn set_attr(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        let name = name.into();
+        let value = value.into();
+        match self.attrs.iter_mut().find(|(n, _)| *n == name) {
+            Some(attr) => attr.1 = value,
+            None => self.attrs.push((name, value)),
+        }
+    }
+
+    /// Append a child element
+    pub fn child(mut self, child: Element) -> Self {
+        self.children.push(Node::Element(child));
+        self
+    }
+
+    /// Append text content
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.children.push(Node::Text(text.into()));
+        self
+    }
+
+    /// Get an attribute value
+    pub fn get_attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Whether the element has the given name and namespace
+    pub fn is(&self, name: &str, ns: &str) -> bool {
+        self.name == name && self.ns == ns
+    }
+
+    /// Child elements
+    pub fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|node| match node {
+            Node::Element(element) => Some(element),
+            Node::Text(_) => None,
+        })
+    }
+
+    /// First child element with the given name and namespace
+    pub fn find(&self, name: &str, ns: &str) -> Option<&Element> {
+        self.elements().find(|child| child.is(name, ns))
+    }
+
+    /// Concatenated text content of the element itself (not of its children)
+    pub fn text_content(&self) -> String {
+        self.children
+            .iter()
+            .filter_map(|node| match node {
+                Node::Text(text) => Some(text.as_str()),
+                Node::Element(_) => None,
+            })
+            .collect()
+    }
+
+    /// Text content of the first child with the given name and namespace
+    pub fn child_text(&self, name: &str, ns: &str) -> Option<String> {
+        self.find(name, ns).map(|child| child.text_content())
+    }
+
+    /// Serialize the element as a top-level stanza of a client stream
+    pub fn to_xml(&self) -> String {
+        let mut out = String::new();
+        self.write_xml(NS_CLIENT, &mut out);
+        out
+    }
+
+    fn write_xml(&self, parent_ns: &str, out: &mut String) {
+        out.push('<');
+        out.push_str(&self.name);
+        if self.ns != parent_ns {
+            out.push_str(" xmlns='");
+            out.push_str(&escape(&self.ns));
+            out.push('\'');
+        }
+        for (name, value) in &self.attrs {
+            out.push(' ');
+            out.push_str(name);
+            out.push_str("='");
+            out.push_str(&escape(value));
+            out.push('\'');
+        }
+        if self.children.is_empty() {
+            out.push_str("/>");
+            return;
+        }
+        out.push('>');
+        for node in &self.children {
+            match node {
+                Node::Element(child) => child.write_xml(&self.ns, out),
+                Node::Text(text) => out.push_str(&escape(text)),
+            }
+        }
+        out.push_str("</");
+        out.push_str(&self.name);
+        out.push('>');
+    }
+}
+
+/// Escape text for use in content or a single-quoted attribute value
+pub fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '\'' => out.push_str("&apos;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Replace entity and character references
+fn unescape(text: &str) -> Result<String> {
+    if !text.contains('&') {
+        return Ok(text.to_string());
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find(';')
+            .ok_or_else(|| parse_error("unterminated entity reference"))?;
+        let entity = &rest[start + 1..start + end];
+        let c = match entity {
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "apos" => '\'',
+            "quot" => '"',
+            _ => {
+                let code = if let Some(hex) = entity.strip_prefix("#x") {
+                    u32::from_str_radix(hex, 16).ok()
+                } else if let Some(dec) = entity.strip_prefix('#') {
+                    dec.parse().ok()
+                } else {
+                    None
+                };
+                code.and_then(char::from_u32)
+                    .ok_or_else(|| parse_error(format!("unknown entity &{entity};")))?
+            }
+        };
+        out.push(c);
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+fn parse_error(msg: impl Into<String>) -> Error {
+    Error::new(
+        ErrorCode::NetworkError,
+        format!("Invalid XML from server: {}", msg.into()),
+    )
+}
+
+/// Something read from the stream
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamEvent {
+    /// The server opened its stream; carries the root's attributes
+    Start(Vec<(String, String)>),
+    /// A complete top-level element (stanza or stream-level element)
+    Element(Element),
+    /// The server closed its stream
+    End,
+}
+
+/// A start tag as written in the document
+struct StartTag {
+    name: String,
+    attrs: Vec<(String, String)>,
+    self_closing: bool,
+}
+
+/// Incremental parser for an XMPP stream
+#[derive(Debug, Default)]
+pub struct StreamParser {
+    buf: Vec<u8>,
+    /// Open elements below the stream root
+    stack: Vec<Element>,
+    /// Namespace declarations (prefix, "" for the default) of the open elements
+    scopes: Vec<HashMap<String, String>>,
+    /// Whether the stream root has been opened
+    started: bool,
+}
+
+impl StreamParser {
+    /// Create a parser for a new stream
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append received bytes
+    pub fn feed(&mut self, data: &[u8]) {
+        self.buf.extend_from_slice(data);
+    }
+
+    /// Parse the next event from the received bytes
+    ///
+    /// # Returns
+    /// None if more data is needed
+    pub fn next_event(&mut self) -> Result<Option<StreamEvent>> {
+        loop {
+            if self.buf.is_empty() {
+                return Ok(None);
+            }
+
+            if self.buf[0] != b'<' {
+                // Text runs until the next tag
+                let Some(end) = self.buf.iter().position(|&b| b == b'<') else {
+                    return Ok(None);
+                };
+                let text = self.take_str(end)?;
+                if let Some(parent) = self.stack.last_mut() {
+                    parent.children.push(Node::Text(unescape(&text)?));
+                }
+                // Text outside stanzas is whitespace keepalive
+                continue;
+            }
+
+            if self.buf.starts_with(b"<?") || self.buf.starts_with(b"<!--") {
+                let terminator: &[u8] = if self.buf.starts_with(b"<?") {
+                    b"?>"
+                } else {
+                    b"-->"
+                };
+                let Some(end) = find(&self.buf, terminator) else {
+                    return Ok(None);
+                };
+                self.buf.drain(..end + terminator.len());
+                continue;
+            }
+
+            if self.buf.starts_with(b"<![CDATA[") {
+                let Some(end) = find(&self.buf, b"]]>") else {
+                    return Ok(None);
+                };
+                let text = String::from_utf8(self.buf[9..end].to_vec())
+                    .map_err(|_| parse_error("invalid UTF-8"))?;
+                self.buf.drain(..end + 3);
+                if let Some(parent) = self.stack.last_mut() {
+                    parent.children.push(Node::Text(text));
+                }
+                continue;
+            }
+
+            let Some(end) = tag_end(&self.buf) else {
+                return Ok(None);
+            };
+            let tag = self.take_str(end + 1)?;
+
+            if let Some(name) = tag.strip_prefix("</") {
+                let name = name.trim_end_matches('>').trim();
+                return match self.stack.pop() {
+                    Some(element) => {
+                        self.scopes.pop();
+                        if let Some(parent) = self.stack.last_mut() {
+                            parent.children.push(Node::Element(element));
+                            continue;
+                        }
+                        Ok(Some(StreamEvent::Element(element)))
+                    }
+                    None if local_name(name) == "stream" => {
+                        self.scopes.clear();
+                        Ok(Some(StreamEvent::End))
+                    }
+                    None => Err(parse_error(format!("unexpected end tag </{name}>"))),
+                };
+            }
+
+            let start = parse_start_tag(&tag)?;
+            let mut scope = HashMap::new();
+            let mut attrs = Vec::new();
+            for (name, value) in start.attrs {
+                if name == "xmlns" {
+                    scope.insert(String::new(), value);
+                } else if let Some(prefix) = name.strip_prefix("xmlns:") {
+                    scope.insert(prefix.to_string(), value);
+                } else {
+                    attrs.push((name, value));
+                }
+            }
+            self.scopes.push(scope);
+
+            if !self.started {
+                if local_name(&start.name) != "stream" {
+                    return Err(parse_error(format!(
+                        "expected stream header, got <{}>",
+                        start.name
+                    )));
+                }
+                self.started = true;
+                return Ok(Some(StreamEvent::Start(attrs)));
+            }
+
+            let (prefix, name) = match start.name.split_once(':') {
+                Some((prefix, name)) => (prefix, name),
+                None => ("", start.name.as_str()),
+            };
+            let ns = self.resolve(prefix).unwrap_or_default();
+            let element = Element {
+                name: name.to_string(),
+                ns,
+                attrs,
+                children: Vec::new(),
+            };
+
+            if !start.self_closing {
+                self.stack.push(element);
+                continue;
+            }
+
+            self.scopes.pop();
+            match self.stack.last_mut() {
+                Some(parent) => parent.children.push(Node::Element(element)),
+                None => return Ok(Some(StreamEvent::Element(element))),
+            }
+        }
+    }
+
+    fn resolve(&self, prefix: &str) -> Option<String> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(prefix).cloned())
+    }
+
+    /// Remove `len` bytes from the buffer and decode them
+    fn take_str(&mut self, len: usize) -> Result<String> {
+        let bytes: Vec<u8> = self.buf.drain(..len).collect();
+        String::from_utf8(bytes).map_err(|_| parse_error("invalid UTF-8"))
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+/// Position of the `>` closing the tag at the start of `buf`, skipping quoted values
+fn tag_end(buf: &[u8]) -> Option<usize> {
+    let mut quote = None;
+    for (i, &b) in buf.iter().enumerate() {
+        match quote {
+            Some(q) if b == q => quote = None,
+            Some(_) => {}
+            None if b == b'"' || b == b'\'' => quote = Some(b),
+            None if b == b'>' => return Some(i),
+            None => {}
+        }
+    }
+    None
+}
+
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+fn parse_start_tag(tag: &str) -> Result<StartTag> {
+    let inner = tag
+        .strip_prefix('<')
+        .and_then(|t| t.strip_suffix('>'))
+        .ok_or_else(|| parse_error("malformed tag"))?;
+    let (inner, self_closing) = match inner.strip_suffix('/') {
+        Some(inner) => (inner, true),
+        None => (inner, false),
+    };
+
+    let name_end = inner
+        .find(|c: char| c.is_whitespace())
+        .unwrap_or(inner.len());
+    let name = inner[..name_end].to_string();
+    if name.is_empty() {
+        return Err(parse_error("missing element name"));
+    }
+
+    let mut attrs = Vec::new();
+    let mut rest = inner[name_end..].trim_start();
+    while !rest.is_empty() {
+        let eq = rest
+            .find('=')
+            .ok_or_else(|| parse_error(format!("malformed attribute in <{name}>")))?;
+        let attr_name = rest[..eq].trim().to_string();
+        let value_part = rest[eq + 1..].trim_start();
+        let quote = value_part
+            .chars()
+            .next()
+            .filter(|&c| c == '"' || c == '\'')
+            .ok_or_else(|| parse_error(format!("unquoted attribute in <{name}>")))?;
+        let value_end = value_part[1..]
+            .find(quote)
+            .ok_or_else(|| parse_error(format!("unterminated attribute in <{name}>")))?;
+        attrs.push((attr_name, unescape(&value_part[1..1 + value_end])?));
+        rest = value_part[value_end + 2..].trim_start();
+    }
+
+    Ok(StartTag {
+        name,
+        attrs,
+        self_closing,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str = "<?xml version='1.0'?><stream:stream xmlns='jabber:client' \
+        xmlns:stream='http://etherx.jabber.org/streams' id='abc' from='example.com' version='1.0'>";
+
+    fn events(parser: &mut StreamParser) -> Vec<StreamEvent> {
+        let mut events = Vec::new();
+        while let Some(event) = parser.next_event().unwrap() {
+            events.push(event);
+        }
+        events
+    }
+
+    #[test]
+    fn test_parses_stream_in_fragments() {
+        let input = format!(
+            "{HEADER}<stream:features><starttls xmlns='urn:ietf:params:xml:ns:xmpp-tls'><required/></starttls></stream:features> \
+             <message from='a@example.com/r' type='chat'><body>1 &lt; 2 &amp; caf\u{e9} &#x1F600;</body></message></stream:stream>"
+        );
+
+        // Feed byte by byte to exercise partial tags, text and UTF-8 sequences
+        let mut parser = StreamParser::new();
+        let mut all = Vec::new();
+        for byte in input.as_bytes() {
+            parser.feed(&[*byte]);
+            all.extend(events(&mut parser));
+        }
+
+        assert_eq!(all.len(), 4);
+        assert!(
+            matches!(&all[0], StreamEvent::Start(attrs) if attrs.iter().any(|(n, v)| n == "id" && v == "abc"))
+        );
+
+        let StreamEvent::Element(features) = &all[1] else {
+            panic!("expected features");
+        };
+        assert!(features.is("features", NS_STREAM));
+        let starttls = features
+            .find("starttls", "urn:ietf:params:xml:ns:xmpp-tls")
+            .unwrap();
+        assert!(starttls
+            .find("required", "urn:ietf:params:xml:ns:xmpp-tls")
+            .is_some());
+
+        let StreamEvent::Element(message) = &all[2] else {
+            panic!("expected message");
+        };
+        assert!(message.is("message", NS_CLIENT));
+        assert_eq!(message.get_attr("type"), Some("chat"));
+        assert_eq!(
+            message.child_text("body", NS_CLIENT).as_deref(),
+            Some("1 < 2 & caf\u{e9} \u{1F600}")
+        );
+
+        assert_eq!(all[3], StreamEvent::End);
+    }
+
+    #[test]
+    fn test_attribute_values_may_contain_angle_brackets() {
+        let mut parser = StreamParser::new();
+        parser.feed(HEADER.as_bytes());
+        parser.feed(b"<presence status=\"a > b\"/>");
+        let all = events(&mut parser);
+        let StreamEvent::Element(presence) = &all[1] else {
+            panic!("expected presence");
+        };
+        assert_eq!(presence.get_attr("status"), Some("a > b"));
+    }
+
+    #[test]
+    fn test_serialization_round_trip() {
+        let message = Element::new("message", NS_CLIENT)
+            .attr("to", "room@muc.example.com")
+            .attr("type", "groupchat")
+            .child(Element::new("body", NS_CLIENT).text("it's <b> & more"))
+            .child(Element::new(
+                "active",
+                "http://jabber.org/protocol/chatstates",
+            ));
+
+        let xml = message.to_xml();
+        assert_eq!(
+            xml,
+            "<message to='room@muc.example.com' type='groupchat'><body>it&apos;s &lt;b&gt; &amp; more</body>\
+             <active xmlns='http://jabber.org/protocol/chatstates'/></message>"
+        );
+
+        let mut parser = StreamParser::new();
+        parser.feed(HEADER.as_bytes());
+        parser.feed(xml.as_bytes());
+        let all = events(&mut parser);
+        assert_eq!(all[1], StreamEvent::Element(message));
+    }
+
+    #[test]
+    fn test_rejects_unexpected_end_tag() {
+        let mut parser = StreamParser::new();
+        parser.feed(HEADER.as_bytes());
+        parser.feed(b"</message>");
+        assert!(parser.next_event().is_ok());
+        assert!(parser.next_event().is_err());
+    }
+}
@@ -0,0 +1,357 @@
+//! Activity-driven presence
+//!
+//! Host applications know things the chat server does not: the user joined a
+//! call, started sharing their screen, or has a meeting on the calendar. Activity
+//! sources report these signals here, and the presence manager maps them to a
+//! platform status using user-configurable rules. When several activities are
+//! active at once, the rule listed first wins.
+
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "ffi")]
+use std::sync::Arc;
+use std::sync::Mutex;
+
+#[cfg(feature = "ffi")]
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::platforms::Platform;
+use crate::types::user::UserStatus;
+
+/// Activity reported while the user is in a meeting
+pub const ACTIVITY_IN_MEETING: &str = "in_meeting";
+/// Activity reported while the user is presenting or sharing their screen
+pub const ACTIVITY_PRESENTING: &str = "presenting";
+/// Activity reported while the user is on a call
+pub const ACTIVITY_ON_CALL: &str = "on_call";
+
+/// Maps an activity to a platform status
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PresenceRule {
+    /// The activity name (e.g. "in_meeting")
+    pub activity: String,
+    /// The status to set while the activity is active
+    pub status: UserStatus,
+    /// Optional status message to set alongside the status
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+impl PresenceRule {
+    /// Create a new rule
+    pub fn new(activity: impl Into<String>, status: UserStatus) -> Self {
+        Self {
+            activity: activity.into(),
+            status,
+            message: None,
+        }
+    }
+
+    /// Set the status message
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+}
+
+/// Presence mapping configuration
+///
+/// Rules are ordered by precedence: the first rule whose activity is active
+/// determines the status.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PresenceConfig {
+    /// Activity rules, highest precedence first
+    #[serde(default)]
+    pub rules: Vec<PresenceRule>,
+    /// Status restored when no mapped activity is active (default: online)
+    #[serde(default = "default_idle_status")]
+    pub idle_status: UserStatus,
+}
+
+fn default_idle_status() -> UserStatus {
+    UserStatus::Online
+}
+
+impl Default for PresenceConfig {
+    fn default() -> Self {
+        Self {
+            rules: vec![
+                PresenceRule::new(ACTIVITY_PRESENTING, UserStatus::DoNotDisturb)
+                    .with_message("Presenting"),
+                PresenceRule::new(ACTIVITY_ON_CALL, UserStatus::DoNotDisturb)
+                    .with_message("On a call"),
+                PresenceRule::new(ACTIVITY_IN_MEETING, UserStatus::DoNotDisturb)
+                    .with_message("In a meeting"),
+            ],
+            idle_status: default_idle_status(),
+        }
+    }
+}
+
+/// The status derived from the current activities
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PresenceState {
+    /// The status to set
+    pub status: UserStatus,
+    /// The status message to set, if any
+    pub message: Option<String>,
+}
+
+/// A source of activity signals (calendar, screen-share detection, ...)
+///
+/// Sources implemented in Rust can be refreshed with [`PresenceManager::refresh`];
+/// hosts using the C API report activities directly instead.
+pub trait ActivitySource: Send + Sync {
+    /// Unique name of the source (e.g. "calendar")
+    fn name(&self) -> &str;
+
+    /// Activities currently active according to this source
+    fn current_activities(&self) -> Vec<String>;
+}
+
+/// Tracks reported activities and derives the presence status
+#[derive(Debug)]
+pub struct PresenceManager {
+    config: PresenceConfig,
+    /// Active activities, per source
+    activities: Mutex<HashMap<String, HashSet<String>>>,
+    /// The last state applied to the platform
+    applied: Mutex<Option<PresenceState>>,
+}
+
+impl PresenceManager {
+    /// Create a new presence manager
+    pub fn new(config: PresenceConfig) -> Self {
+        Self {
+            config,
+            activities: Mutex::new(HashMap::new()),
+            applied: Mutex::new(None),
+        }
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &PresenceConfig {
+        &self.config
+    }
+
+    /// Record that an activity started or ended
+    ///
+    /// # Arguments
+    /// * `source` - The name of the reporting source
+    /// * `activity` - The activity name
+    /// * `active` - Whether the activity started (true) or ended (false)
+    ///
+    /// # Returns
+    /// The new presence state if it changed since it was last applied
+    pub fn set_activity(
+        &self,
+        source: &str,
+        activity: &str,
+        active: bool,
+    ) -> Option<PresenceState> {
+        {
+            let mut activities = self.activities.lock().unwrap();
+            if active {
+                activities
+                    .entry(source.to_string())
+                    .or_default()
+                    .insert(activity.to_string());
+            } else if let Some(set) = activities.get_mut(source) {
+                set.remove(activity);
+                if set.is_empty() {
+                    activities.remove(source);
+                }
+            }
+        }
+        self.pending_change()
+    }
+
+    /// Replace all activities of a source with its current activities
+    ///
+    /// # Returns
+    /// The new presence state if it changed since it was last applied
+    pub fn refresh(&self, source: &dyn ActivitySource) -> Option<PresenceState> {
+        {
+            let current: HashSet<String> = source.current_activities().into_iter().collect();
+            let mut activities = self.activities.lock().unwrap();
+            if current.is_empty() {
+                activities.remove(source.name());
+            } else {
+                activities.insert(source.name().to_string(), current);
+            }
+        }
+        self.pending_change()
+    }
+
+    /// Compute the presence state from the active activities
+    pub fn current_state(&self) -> PresenceState {
+        let activities = self.activities.lock().unwrap();
+        let is_active = |activity: &str| activities.values().any(|set| set.contains(activity));
+
+        match self
+            .config
+            .rules
+            .iter()
+            .find(|rule| is_active(&rule.activity))
+        {
+            Some(rule) => PresenceState {
+                status: rule.status,
+                message: rule.message.clone(),
+            },
+            None => PresenceState {
+                status: self.config.idle_status,
+                message: None,
+            },
+        }
+    }
+
+    /// Return the current state if it differs from the last applied state
+    fn pending_change(&self) -> Option<PresenceState> {
+        let state = self.current_state();
+        let applied = self.applied.lock().unwrap();
+        match applied.as_ref() {
+            Some(previous) if *previous == state => None,
+            // Nothing applied yet and no activity: leave the user's status alone
+            None if state.status == self.config.idle_status && state.message.is_none() => None,
+            _ => Some(state),
+        }
+    }
+
+    /// Set a presence state on the platform and remember it as applied
+    ///
+    /// # Arguments
+    /// * `platform` - The platform to update
+    /// * `state` - The state returned by [`set_activity`](Self::set_activity) or [`refresh`](Self::refresh)
+    pub async fn apply(&self, platform: &dyn Platform, state: PresenceState) -> Result<()> {
+        platform
+            .set_status(state.status, state.message.as_deref())
+            .await?;
+        *self.applied.lock().unwrap() = Some(state);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "ffi")]
+lazy_static! {
+    /// Presence managers, keyed by platform handle address
+    static ref MANAGERS: Mutex<HashMap<usize, Arc<PresenceManager>>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Install a presence manager for a platform handle
+#[cfg(feature = "ffi")]
+pub(crate) fn register(handle_key: usize, manager: PresenceManager) {
+    if let Ok(mut managers) = MANAGERS.lock() {
+        managers.insert(handle_key, Arc::new(manager));
+    }
+}
+
+/// Get the presence manager of a platform handle
+#[cfg(feature = "ffi")]
+pub(crate) fn get(handle_key: usize) -> Option<Arc<PresenceManager>> {
+    MANAGERS
+        .lock()
+        .ok()
+        .and_then(|managers| managers.get(&handle_key).cloned())
+}
+
+/// Remove the presence manager of a platform handle
+#[cfg(feature = "ffi")]
+pub(crate) fn clear(handle_key: usize) {
+    if let Ok(mut managers) = MANAGERS.lock() {
+        managers.remove(&handle_key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Calendar {
+        activities: Vec<String>,
+    }
+
+    impl ActivitySource for Calendar {
+        fn name(&self) -> &str {
+            "calendar"
+        }
+
+        fn current_activities(&self) -> Vec<String> {
+            self.activities.clone()
+        }
+    }
+
+    fn mark_applied(manager: &PresenceManager, state: &PresenceState) {
+        *manager.applied.lock().unwrap() = Some(state.clone());
+    }
+
+    #[test]
+    fn test_activity_maps_to_status() {
+        let manager = PresenceManager::new(PresenceConfig::default());
+        let state = manager
+            .set_activity("calendar", ACTIVITY_IN_MEETING, true)
+            .unwrap();
+        assert_eq!(state.status, UserStatus::DoNotDisturb);
+        assert_eq!(state.message.as_deref(), Some("In a meeting"));
+    }
+
+    #[test]
+    fn test_precedence_follows_rule_order() {
+        let manager = PresenceManager::new(PresenceConfig::default());
+        manager.set_activity("calendar", ACTIVITY_IN_MEETING, true);
+        let state = manager
+            .set_activity("screen", ACTIVITY_PRESENTING, true)
+            .unwrap();
+        assert_eq!(state.message.as_deref(), Some("Presenting"));
+
+        // Presenting ends; the meeting is still going on
+        let state = manager
+            .set_activity("screen", ACTIVITY_PRESENTING, false)
+            .unwrap();
+        assert_eq!(state.message.as_deref(), Some("In a meeting"));
+    }
+
+    #[test]
+    fn test_idle_status_restored_and_unchanged_state_skipped() {
+        let manager = PresenceManager::new(PresenceConfig::default());
+
+        // No activity and nothing applied yet: no change
+        assert!(manager.set_activity("calendar", "unmapped", true).is_none());
+
+        let state = manager
+            .set_activity("calendar", ACTIVITY_IN_MEETING, true)
+            .unwrap();
+        mark_applied(&manager, &state);
+        assert!(manager
+            .set_activity("calendar", ACTIVITY_IN_MEETING, true)
+            .is_none());
+
+        let state = manager
+            .set_activity("calendar", ACTIVITY_IN_MEETING, false)
+            .unwrap();
+        assert_eq!(state.status, UserStatus::Online);
+        assert!(state.message.is_none());
+    }
+
+    #[test]
+    fn test_refresh_from_source() {
+        let manager = PresenceManager::new(PresenceConfig::default());
+        let calendar = Calendar {
+            activities: vec![ACTIVITY_ON_CALL.to_string()],
+        };
+        let state = manager.refresh(&calendar).unwrap();
+        assert_eq!(state.message.as_deref(), Some("On a call"));
+    }
+
+    #[test]
+    fn test_config_from_json() {
+        let config: PresenceConfig = serde_json::from_str(
+            r#"{"rules": [{"activity": "focus", "status": "away", "message": "Focusing"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(config.rules.len(), 1);
+        assert_eq!(config.rules[0].status, UserStatus::Away);
+        assert_eq!(config.idle_status, UserStatus::Online);
+    }
+}
@@ -0,0 +1,229 @@
+//! Local reminders
+//!
+//! "Remind me about this message" without a separate daemon: reminders are kept
+//! by the platform's event pipeline (see `crate::pipeline`) and checked whenever
+//! events are polled. Due reminders are delivered as `reminder_due` events
+//! (`PlatformEvent::ReminderDue`), before any other pending event. Reminders can
+//! be persisted to a JSON file so they survive restarts; the file is rewritten
+//! on every change.
+//!
+//! Where the platform offers server-side reminders
+//! (`Platform::set_message_reminder`), reminders about messages can be handed to
+//! the server instead, which then notifies the user on all of their devices.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+
+use crate::error::{Error, ErrorCode, Result};
+use crate::migrations;
+use crate::types::Reminder;
+
+#[derive(Debug, Default)]
+struct SchedulerState {
+    /// Pending reminders, earliest first
+    reminders: Vec<Reminder>,
+    /// File the reminders are persisted to
+    store_path: Option<PathBuf>,
+    next_id: u64,
+}
+
+/// Schedules local reminders
+#[derive(Debug, Default)]
+pub struct ReminderScheduler {
+    state: Mutex<SchedulerState>,
+}
+
+impl ReminderScheduler {
+    /// Create an empty scheduler without persistence
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Persist reminders to a file, loading any reminders already stored there
+    ///
+    /// # Arguments
+    /// * `path` - The JSON file to store reminders in; created on first change
+    ///
+    /// # Returns
+    /// An error if the file exists but cannot be read, parsed or migrated to
+    /// the current format, or if the current reminders cannot be written to it
+    pub fn set_store(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref().to_path_buf();
+        let stored: Vec<Reminder> = match migrations::open(&migrations::REMINDERS, &path)? {
+            Some(mut document) => {
+                serde_json::from_value(document["reminders"].take()).map_err(|e| {
+                    Error::invalid_argument(format!(
+                        "Invalid reminder store {}: {e}",
+                        path.display()
+                    ))
+                })?
+            }
+            None => Vec::new(),
+        };
+
+        let mut state = self.state.lock().unwrap();
+        for reminder in stored {
+            if !state.reminders.iter().any(|r| r.id == reminder.id) {
+                state.reminders.push(reminder);
+            }
+        }
+        state.reminders.sort_by_key(|r| r.due_at);
+        state.store_path = Some(path);
+        save(&state)
+    }
+
+    /// Schedule a reminder
+    ///
+    /// # Arguments
+    /// * `channel_id` - The channel the reminder is about
+    /// * `message_id` - The message the reminder is about, if any
+    /// * `note` - Optional note shown with the reminder
+    /// * `due_at` - When the reminder is due
+    ///
+    /// # Returns
+    /// The scheduled reminder
+    pub fn add(
+        &self,
+        channel_id: &str,
+        message_id: Option<&str>,
+        note: Option<&str>,
+        due_at: DateTime<Utc>,
+    ) -> Result<Reminder> {
+        let mut state = self.state.lock().unwrap();
+        state.next_id += 1;
+        let id = format!(
+            "reminder-{}-{}",
+            Utc::now().timestamp_millis(),
+            state.next_id
+        );
+
+        let mut reminder = Reminder::new(id, channel_id, due_at);
+        reminder.message_id = message_id.map(|m| m.to_string());
+        reminder.note = note.map(|n| n.to_string());
+
+        let position = state.reminders.partition_point(|r| r.due_at <= due_at);
+        state.reminders.insert(position, reminder.clone());
+        save(&state)?;
+        Ok(reminder)
+    }
+
+    /// Cancel a reminder
+    ///
+    /// # Returns
+    /// Whether a reminder with this ID was pending
+    pub fn cancel(&self, reminder_id: &str) -> Result<bool> {
+        let mut state = self.state.lock().unwrap();
+        let before = state.reminders.len();
+        state.reminders.retain(|r| r.id != reminder_id);
+        if state.reminders.len() == before {
+            return Ok(false);
+        }
+        save(&state)?;
+        Ok(true)
+    }
+
+    /// Get all pending reminders, earliest first
+    pub fn list(&self) -> Vec<Reminder> {
+        self.state.lock().unwrap().reminders.clone()
+    }
+
+    /// Remove and return the earliest reminder that is due
+    ///
+    /// Reminders that fell due while the application was not running are
+    /// returned as soon as they are loaded.
+    pub fn pop_due(&self, now: DateTime<Utc>) -> Option<Reminder> {
+        let mut state = self.state.lock().unwrap();
+        if !state.reminders.first()?.is_due(now) {
+            return None;
+        }
+        let reminder = state.reminders.remove(0);
+        // Delivery must not fail because the store is unwritable; the reminder
+        // would then fire again after a restart, which is the lesser evil
+        let _ = save(&state);
+        Some(reminder)
+    }
+}
+
+/// Write the reminders to the store file, if one is set
+fn save(state: &SchedulerState) -> Result<()> {
+    let Some(path) = &state.store_path else {
+        return Ok(());
+    };
+    let document = migrations::REMINDERS.stamp(serde_json::json!({
+        "reminders": state.reminders,
+    }));
+    let json = serde_json::to_string_pretty(&document).map_err(|e| {
+        Error::new(
+            ErrorCode::Unknown,
+            format!("Failed to serialize reminders: {e}"),
+        )
+    })?;
+    std::fs::write(path, json).map_err(|e| {
+        Error::new(
+            ErrorCode::Unknown,
+            format!("Failed to write reminder store {}: {e}", path.display()),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_due_order() {
+        let scheduler = ReminderScheduler::new();
+        let now = Utc::now();
+        let later = scheduler
+            .add("ch1", None, Some("later"), now + Duration::minutes(10))
+            .unwrap();
+        let soon = scheduler
+            .add("ch1", Some("msg1"), None, now + Duration::minutes(1))
+            .unwrap();
+
+        assert_eq!(scheduler.list(), vec![soon.clone(), later.clone()]);
+        assert!(scheduler.pop_due(now).is_none());
+        assert_eq!(scheduler.pop_due(now + Duration::hours(1)), Some(soon));
+        assert_eq!(scheduler.pop_due(now + Duration::hours(1)), Some(later));
+        assert!(scheduler.pop_due(now + Duration::hours(1)).is_none());
+    }
+
+    #[test]
+    fn test_cancel() {
+        let scheduler = ReminderScheduler::new();
+        let reminder = scheduler.add("ch1", None, None, Utc::now()).unwrap();
+        assert!(scheduler.cancel(&reminder.id).unwrap());
+        assert!(!scheduler.cancel(&reminder.id).unwrap());
+        assert!(scheduler.list().is_empty());
+    }
+
+    #[test]
+    fn test_persistence() {
+        let path = std::env::temp_dir().join(format!(
+            "libcommunicator-reminders-{}.json",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        let scheduler = ReminderScheduler::new();
+        scheduler.set_store(&path).unwrap();
+        let reminder = scheduler
+            .add("ch1", Some("msg1"), Some("follow up"), Utc::now())
+            .unwrap();
+
+        let restored = ReminderScheduler::new();
+        restored.set_store(&path).unwrap();
+        assert_eq!(restored.list(), vec![reminder.clone()]);
+
+        // Delivering the reminder removes it from the store
+        assert_eq!(restored.pop_due(Utc::now()), Some(reminder));
+        let reloaded = ReminderScheduler::new();
+        reloaded.set_store(&path).unwrap();
+        assert!(reloaded.list().is_empty());
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,421 @@
+//! Automation rules
+//!
+//! A small rules engine for simple automations such as "react with :eyes: to
+//! messages mentioning me in #alerts". Each rule pairs a trigger (an event
+//! filter) with a list of actions (send a message, add a reaction, or invoke a
+//! host callback). Rules are configured as JSON:
+//!
+//! ```json
+//! [{
+//!   "name": "watch-alerts",
+//!   "trigger": {"event": "message_posted", "channel": "alerts", "mentions_me": true},
+//!   "actions": [{"type": "add_reaction", "emoji": "eyes"}]
+//! }]
+//! ```
+//!
+//! Rules are evaluated against events in the JSON form returned by
+//! `communicator_platform_poll_event`.
+
+use std::ffi::CString;
+use std::os::raw::{c_char, c_void};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::platforms::Platform;
+
+/// Callback invoked by `callback` actions
+/// Parameters: rule name, event JSON, user_data
+pub type RuleCallback = extern "C" fn(*const c_char, *const c_char, *mut c_void);
+
+/// Event filter that decides whether a rule fires
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RuleTrigger {
+    /// Event type (e.g. "message_posted"); None matches every event
+    pub event: Option<String>,
+    /// Channel ID or channel name (with or without a leading '#')
+    pub channel: Option<String>,
+    /// Only fire for messages that mention the current user
+    pub mentions_me: bool,
+    /// Only fire for messages containing this text (case-insensitive)
+    pub contains: Option<String>,
+    /// Only fire for messages sent by this user ID
+    pub from_user: Option<String>,
+    /// Only fire for messages detected to be in this language (ISO 639-1 code)
+    pub language: Option<String>,
+    /// Also fire for the current user's own messages (default: false)
+    pub include_own: bool,
+}
+
+/// Action performed when a rule fires
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RuleAction {
+    /// Send a message
+    SendMessage {
+        /// Target channel; defaults to the channel of the triggering event
+        #[serde(default)]
+        channel_id: Option<String>,
+        /// Message text
+        text: String,
+    },
+    /// React to the triggering message
+    AddReaction {
+        /// Emoji name (without colons)
+        emoji: String,
+    },
+    /// Invoke the host callback with the rule name and event JSON
+    Callback,
+}
+
+/// An automation rule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rule {
+    /// Rule name (passed to callbacks)
+    pub name: String,
+    /// When the rule fires
+    #[serde(default)]
+    pub trigger: RuleTrigger,
+    /// What the rule does
+    pub actions: Vec<RuleAction>,
+}
+
+/// Host callback and its user data
+#[derive(Clone, Copy)]
+struct CallbackTarget {
+    callback: RuleCallback,
+    user_data: usize,
+}
+
+/// Evaluates rules against events and performs their actions
+pub struct RulesEngine {
+    rules: Vec<Rule>,
+    /// ID of the current user (for `mentions_me` and own-message filtering)
+    user_id: String,
+    callback: Option<CallbackTarget>,
+}
+
+impl RulesEngine {
+    /// Create a rules engine
+    ///
+    /// # Arguments
+    /// * `rules` - The rules, evaluated in order
+    /// * `user_id` - The ID of the current user
+    pub fn new(rules: Vec<Rule>, user_id: impl Into<String>) -> Self {
+        Self {
+            rules,
+            user_id: user_id.into(),
+            callback: None,
+        }
+    }
+
+    /// Parse rules from a JSON array
+    pub fn parse_rules(json: &str) -> Result<Vec<Rule>> {
+        serde_json::from_str(json).map_err(|e| {
+            crate::error::Error::invalid_argument(format!("Failed to parse rules JSON: {e}"))
+        })
+    }
+
+    /// Set the callback invoked by `callback` actions
+    pub fn with_callback(mut self, callback: RuleCallback, user_data: *mut c_void) -> Self {
+        self.callback = Some(CallbackTarget {
+            callback,
+            user_data: user_data as usize,
+        });
+        self
+    }
+
+    /// Get the configured rules
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Find the rules triggered by an event
+    pub fn matching_rules(&self, event: &serde_json::Value) -> Vec<&Rule> {
+        self.rules
+            .iter()
+            .filter(|rule| self.trigger_matches(&rule.trigger, event))
+            .collect()
+    }
+
+    fn trigger_matches(&self, trigger: &RuleTrigger, event: &serde_json::Value) -> bool {
+        let event_type = event.get("type").and_then(|t| t.as_str()).unwrap_or("");
+        if let Some(expected) = &trigger.event {
+            if expected != event_type {
+                return false;
+            }
+        }
+
+        let message = event_message(event);
+        let metadata = message.and_then(|m| m.get("metadata"));
+
+        if let Some(channel) = &trigger.channel {
+            let channel = channel.trim_start_matches('#');
+            let channel_id = event_channel_id(event);
+            let channel_name = metadata
+                .and_then(|m| m.get("channel_name"))
+                .and_then(|n| n.as_str());
+            if channel_id != Some(channel) && channel_name != Some(channel) {
+                return false;
+            }
+        }
+
+        let Some(message) = message else {
+            // Non-message events only match triggers without message filters
+            return !trigger.mentions_me
+                && trigger.contains.is_none()
+                && trigger.from_user.is_none()
+                && trigger.language.is_none();
+        };
+
+        let sender = message.get("sender_id").and_then(|s| s.as_str());
+        if !trigger.include_own && sender == Some(self.user_id.as_str()) {
+            return false;
+        }
+
+        if let Some(from_user) = &trigger.from_user {
+            if sender != Some(from_user.as_str()) {
+                return false;
+            }
+        }
+
+        if let Some(needle) = &trigger.contains {
+            let text = message.get("text").and_then(|t| t.as_str()).unwrap_or("");
+            if !text.to_lowercase().contains(&needle.to_lowercase()) {
+                return false;
+            }
+        }
+
+        if let Some(language) = &trigger.language {
+            let detected = message.get("language").and_then(|l| l.as_str());
+            if !detected.is_some_and(|detected| detected.eq_ignore_ascii_case(language)) {
+                return false;
+            }
+        }
+
+        if trigger.mentions_me {
+            let mentioned = metadata
+                .and_then(|m| m.get("mentions"))
+                .and_then(|m| m.as_array())
+                .map(|ids| {
+                    ids.iter()
+                        .any(|id| id.as_str() == Some(self.user_id.as_str()))
+                })
+                .unwrap_or(false);
+            if !mentioned {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Run the actions of all rules triggered by an event
+    ///
+    /// Every action is attempted; the first error is returned.
+    ///
+    /// # Arguments
+    /// * `platform` - The platform to perform actions on
+    /// * `event` - The event JSON
+    pub async fn run(&self, platform: &dyn Platform, event: &serde_json::Value) -> Result<()> {
+        let mut first_error = None;
+
+        for rule in self.matching_rules(event) {
+            for action in &rule.actions {
+                if let Err(e) = self.perform(platform, rule, action, event).await {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    async fn perform(
+        &self,
+        platform: &dyn Platform,
+        rule: &Rule,
+        action: &RuleAction,
+        event: &serde_json::Value,
+    ) -> Result<()> {
+        match action {
+            RuleAction::SendMessage { channel_id, text } => {
+                let channel_id = channel_id
+                    .as_deref()
+                    .or_else(|| event_channel_id(event))
+                    .ok_or_else(|| {
+                        crate::error::Error::invalid_argument(format!(
+                            "Rule '{}' has no target channel for this event",
+                            rule.name
+                        ))
+                    })?;
+                platform.send_message(channel_id, text).await.map(|_| ())
+            }
+            RuleAction::AddReaction { emoji } => {
+                let message_id = event_message(event)
+                    .and_then(|m| m.get("id"))
+                    .and_then(|id| id.as_str())
+                    .ok_or_else(|| {
+                        crate::error::Error::invalid_argument(format!(
+                            "Rule '{}' can only react to message events",
+                            rule.name
+                        ))
+                    })?;
+                platform.add_reaction(message_id, emoji).await
+            }
+            RuleAction::Callback => {
+                if let Some(target) = self.callback {
+                    if let (Ok(name), Ok(json)) = (
+                        CString::new(rule.name.as_str()),
+                        CString::new(event.to_string()),
+                    ) {
+                        (target.callback)(
+                            name.as_ptr(),
+                            json.as_ptr(),
+                            target.user_data as *mut c_void,
+                        );
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Get the message carried by a message event
+fn event_message(event: &serde_json::Value) -> Option<&serde_json::Value> {
+    event.get("data").filter(|data| data.get("text").is_some())
+}
+
+/// Get the channel an event belongs to
+fn event_channel_id(event: &serde_json::Value) -> Option<&str> {
+    event_message(event)
+        .and_then(|m| m.get("channel_id"))
+        .or_else(|| event.get("channel_id"))
+        .and_then(|id| id.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn posted(sender: &str, text: &str, mentions: &[&str]) -> serde_json::Value {
+        serde_json::json!({
+            "type": "message_posted",
+            "data": {
+                "id": "msg-1",
+                "text": text,
+                "sender_id": sender,
+                "channel_id": "ch-alerts",
+                "metadata": {"channel_name": "alerts", "mentions": mentions}
+            }
+        })
+    }
+
+    fn engine(json: &str) -> RulesEngine {
+        RulesEngine::new(RulesEngine::parse_rules(json).unwrap(), "me")
+    }
+
+    #[test]
+    fn test_parse_rules() {
+        let rules = RulesEngine::parse_rules(
+            r##"[{"name": "r", "trigger": {"channel": "#alerts"},
+                 "actions": [{"type": "add_reaction", "emoji": "eyes"},
+                             {"type": "send_message", "text": "ack"},
+                             {"type": "callback"}]}]"##,
+        )
+        .unwrap();
+        assert_eq!(rules[0].trigger.channel.as_deref(), Some("#alerts"));
+        assert_eq!(
+            rules[0].actions[0],
+            RuleAction::AddReaction {
+                emoji: "eyes".to_string()
+            }
+        );
+        assert_eq!(
+            rules[0].actions[1],
+            RuleAction::SendMessage {
+                channel_id: None,
+                text: "ack".to_string()
+            }
+        );
+        assert_eq!(rules[0].actions[2], RuleAction::Callback);
+
+        assert!(RulesEngine::parse_rules(r#"[{"name": "r"}]"#).is_err());
+    }
+
+    #[test]
+    fn test_mention_in_channel_trigger() {
+        let engine = engine(
+            r##"[{"name": "watch", "trigger": {"event": "message_posted", "channel": "#alerts", "mentions_me": true},
+                 "actions": [{"type": "add_reaction", "emoji": "eyes"}]}]"##,
+        );
+
+        assert_eq!(
+            engine
+                .matching_rules(&posted("bob", "@me look", &["me"]))
+                .len(),
+            1
+        );
+        assert!(engine
+            .matching_rules(&posted("bob", "no mention", &[]))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_own_messages_ignored_by_default() {
+        let engine = engine(r#"[{"name": "all", "actions": [{"type": "callback"}]}]"#);
+        assert!(engine.matching_rules(&posted("me", "hi", &[])).is_empty());
+        assert_eq!(engine.matching_rules(&posted("bob", "hi", &[])).len(), 1);
+    }
+
+    #[test]
+    fn test_contains_and_sender_filters() {
+        let engine = engine(
+            r#"[{"name": "deploy", "trigger": {"contains": "DEPLOY", "from_user": "ci-bot"},
+                 "actions": [{"type": "callback"}]}]"#,
+        );
+        assert_eq!(
+            engine
+                .matching_rules(&posted("ci-bot", "deploy finished", &[]))
+                .len(),
+            1
+        );
+        assert!(engine
+            .matching_rules(&posted("bob", "deploy finished", &[]))
+            .is_empty());
+        assert!(engine
+            .matching_rules(&posted("ci-bot", "build finished", &[]))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_language_filter() {
+        let engine = engine(
+            r#"[{"name": "german", "trigger": {"language": "de"},
+                 "actions": [{"type": "callback"}]}]"#,
+        );
+        let mut event = posted("bob", "Hallo zusammen", &[]);
+        assert!(engine.matching_rules(&event).is_empty());
+        event["data"]["language"] = "de".into();
+        assert_eq!(engine.matching_rules(&event).len(), 1);
+        event["data"]["language"] = "en".into();
+        assert!(engine.matching_rules(&event).is_empty());
+    }
+
+    #[test]
+    fn test_non_message_events() {
+        let engine = engine(
+            r#"[{"name": "typing", "trigger": {"event": "user_typing"},
+                 "actions": [{"type": "callback"}]}]"#,
+        );
+        let typing =
+            serde_json::json!({"type": "user_typing", "user_id": "bob", "channel_id": "ch"});
+        assert_eq!(engine.matching_rules(&typing).len(), 1);
+        assert!(engine.matching_rules(&posted("bob", "hi", &[])).is_empty());
+    }
+}
@@ -2,39 +2,209 @@
 //!
 //! This module provides a global Tokio runtime that allows FFI functions
 //! to execute async Rust code synchronously from the C perspective.
+//!
+//! By default the runtime has its own worker threads. Embedders with their own
+//! event loop (game engines, single-threaded GUI toolkits) can instead make it
+//! host-driven: no threads are spawned, and background work (WebSocket I/O,
+//! timers, spawned tasks) only progresses while the host calls [`tick`] or a
+//! library function, on the host's thread.
+//!
+//! The threaded runtime can be tuned with [`RuntimeOptions`]: the number of
+//! worker threads and their names, or, for Rust programs embedding the crate,
+//! reusing the program's own Tokio runtime instead of starting another one.
 
+use serde::{Deserialize, Serialize};
 use std::future::Future;
-use std::sync::Mutex;
-use tokio::runtime::Runtime;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::runtime::{Builder, Handle, Runtime, RuntimeFlavor};
+
+use crate::error::{Error, ErrorCode, Result};
+
+/// Options of the threaded runtime
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RuntimeOptions {
+    /// Number of worker threads, None for one per CPU core
+    pub worker_threads: Option<usize>,
+    /// Prefix of the worker threads' names, numbered from 0 ("prefix-0")
+    pub thread_name_prefix: Option<String>,
+    /// Reuse the Tokio runtime the initializing thread runs in instead of
+    /// starting one; it must be multi-threaded and outlive the library's use
+    pub shared: bool,
+}
+
+/// Metrics of the runtime
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuntimeMetrics {
+    /// Number of worker threads
+    pub workers: usize,
+    /// Number of tasks alive (WebSocket connections, timers, callbacks, ...)
+    pub alive_tasks: usize,
+    /// Number of tasks waiting in the runtime's global queue
+    pub global_queue_depth: usize,
+    /// Whether the runtime is driven by the host's event loop
+    pub host_driven: bool,
+    /// Whether the runtime is the embedder's own
+    pub shared: bool,
+}
+
+/// The runtime library calls run on
+enum Instance {
+    /// Started by the library
+    Owned(Runtime),
+    /// The embedder's runtime, reused
+    Shared(Handle),
+}
+
+impl Instance {
+    fn handle(&self) -> &Handle {
+        match self {
+            Instance::Owned(runtime) => runtime.handle(),
+            Instance::Shared(handle) => handle,
+        }
+    }
+
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        crate::telemetry::scoped(|| match self {
+            Instance::Owned(runtime) => runtime.block_on(future),
+            Instance::Shared(handle) => handle.block_on(future),
+        })
+    }
+}
+
+/// Whether the runtime is driven by the host's event loop
+static HOST_DRIVEN: AtomicBool = AtomicBool::new(false);
+
+/// Whether the clock of the runtime is paused for a simulation
+#[cfg(feature = "simulation")]
+static CLOCK_PAUSED: AtomicBool = AtomicBool::new(false);
 
 lazy_static::lazy_static! {
     /// Global Tokio runtime for async operations
-    static ref RUNTIME: Mutex<Option<Runtime>> = Mutex::new(None);
+    ///
+    /// Shared so calls can block on it without holding the lock.
+    static ref RUNTIME: Mutex<Option<Arc<Instance>>> = Mutex::new(None);
+
+    /// Options the runtime was initialized with, if any were given
+    static ref OPTIONS: Mutex<Option<RuntimeOptions>> = Mutex::new(None);
+
+    /// Deadline budget of each block_on call, if any
+    static ref CALL_TIMEOUT: Mutex<Option<Duration>> = Mutex::new(None);
+}
+
+/// Set the time budget of each call made through block_on
+///
+/// Every call runs under a deadline this far in the future (see
+/// [`crate::deadline`]), so a call making several requests fails with
+/// `ErrorCode::Timeout` once the budget is spent. None removes the budget.
+pub fn set_call_timeout(timeout: Option<Duration>) {
+    *CALL_TIMEOUT.lock().unwrap() = timeout;
 }
 
 /// Initialize the async runtime
 ///
 /// This should be called during library initialization.
 /// It's safe to call multiple times - subsequent calls are no-ops.
-pub fn init_runtime() -> crate::error::Result<()> {
-    let mut runtime_guard = RUNTIME.lock().map_err(|_| {
-        crate::error::Error::new(
-            crate::error::ErrorCode::Unknown,
-            "Failed to acquire runtime lock",
-        )
-    })?;
+pub fn init_runtime() -> Result<()> {
+    init(false, None)
+}
+
+/// Initialize the async runtime with options
+///
+/// Like [`init_runtime`], but calling it again fails with
+/// `ErrorCode::InvalidState` unless the options are the same.
+pub fn init_runtime_with_options(options: RuntimeOptions) -> Result<()> {
+    init(false, Some(options))
+}
+
+/// Initialize a host-driven runtime
+///
+/// Like [`init_runtime`], but no worker threads are spawned: the host drives
+/// background work by calling [`tick`] from its event loop.
+pub fn init_host_driven_runtime() -> Result<()> {
+    init(true, None)
+}
+
+fn init(host_driven: bool, options: Option<RuntimeOptions>) -> Result<()> {
+    let mut runtime_guard = RUNTIME
+        .lock()
+        .map_err(|_| Error::new(ErrorCode::Unknown, "Failed to acquire runtime lock"))?;
+
+    if runtime_guard.is_some() {
+        if HOST_DRIVEN.load(Ordering::SeqCst) != host_driven {
+            return Err(Error::new(
+                ErrorCode::InvalidState,
+                "The runtime is already initialized in the other mode; call communicator_cleanup() first",
+            ));
+        }
+        let current_options = OPTIONS.lock().unwrap().clone().unwrap_or_default();
+        if options.is_some_and(|options| options != current_options) {
+            return Err(Error::new(
+                ErrorCode::InvalidState,
+                "The runtime is already initialized with other options; call communicator_cleanup() first",
+            ));
+        }
+        return Ok(());
+    }
+
+    let instance = build(host_driven, options.as_ref())?;
+    HOST_DRIVEN.store(host_driven, Ordering::SeqCst);
+    *OPTIONS.lock().unwrap() = options;
+    *runtime_guard = Some(Arc::new(instance));
+
+    Ok(())
+}
 
-    if runtime_guard.is_none() {
-        let runtime = Runtime::new().map_err(|e| {
-            crate::error::Error::new(
-                crate::error::ErrorCode::Unknown,
-                format!("Failed to create Tokio runtime: {e}"),
+/// Create the runtime for a mode and options
+fn build(host_driven: bool, options: Option<&RuntimeOptions>) -> Result<Instance> {
+    let options = options.cloned().unwrap_or_default();
+    if options.shared {
+        let handle = Handle::try_current().map_err(|_| {
+            Error::new(
+                ErrorCode::InvalidState,
+                "A shared runtime needs the library to be initialized from within a Tokio runtime",
             )
         })?;
-        *runtime_guard = Some(runtime);
+        if handle.runtime_flavor() == RuntimeFlavor::CurrentThread {
+            return Err(Error::new(
+                ErrorCode::InvalidState,
+                "A shared runtime must be multi-threaded",
+            ));
+        }
+        return Ok(Instance::Shared(handle));
     }
 
-    Ok(())
+    let mut builder = if host_driven {
+        Builder::new_current_thread()
+    } else {
+        Builder::new_multi_thread()
+    };
+    builder.enable_all();
+    // Log callbacks receive the events of the library's threads
+    builder.on_thread_start(crate::telemetry::enter_thread);
+    if let Some(worker_threads) = options.worker_threads {
+        if worker_threads == 0 {
+            return Err(Error::invalid_argument("worker_threads must be at least 1"));
+        }
+        builder.worker_threads(worker_threads);
+    }
+    if let Some(prefix) = options.thread_name_prefix {
+        let next = AtomicUsize::new(0);
+        builder.thread_name_fn(move || format!("{prefix}-{}", next.fetch_add(1, Ordering::SeqCst)));
+    }
+    builder.build().map(Instance::Owned).map_err(|e| {
+        Error::new(
+            ErrorCode::Unknown,
+            format!("Failed to create Tokio runtime: {e}"),
+        )
+    })
+}
+
+/// Whether the runtime is initialized and driven by the host
+pub fn is_host_driven() -> bool {
+    current().is_some() && HOST_DRIVEN.load(Ordering::SeqCst)
 }
 
 /// Shutdown the async runtime
@@ -43,17 +213,105 @@ pub fn init_runtime() -> crate::error::Result<()> {
 /// After calling this, no async operations can be performed until
 /// init_runtime is called again.
 pub fn shutdown_runtime() {
-    if let Ok(mut runtime_guard) = RUNTIME.lock() {
-        if let Some(runtime) = runtime_guard.take() {
+    let runtime = RUNTIME.lock().ok().and_then(|mut guard| guard.take());
+    if let Some(runtime) = runtime {
+        // A call still blocking on it drops the last reference when it returns;
+        // a shared runtime is the embedder's to shut down
+        if let Ok(Instance::Owned(runtime)) = Arc::try_unwrap(runtime) {
             runtime.shutdown_timeout(std::time::Duration::from_secs(5));
         }
     }
+    if let Ok(mut options) = OPTIONS.lock() {
+        *options = None;
+    }
+    #[cfg(feature = "simulation")]
+    CLOCK_PAUSED.store(false, Ordering::SeqCst);
+}
+
+/// Pause the clock of a host-driven runtime
+///
+/// Timers then only fire as the clock is advanced (see
+/// [`crate::simulation::advance`]). Pausing it again is a no-op.
+#[cfg(feature = "simulation")]
+pub fn pause_clock() -> Result<()> {
+    if !is_host_driven() {
+        return Err(Error::new(
+            ErrorCode::InvalidState,
+            "The simulation clock needs a runtime initialized with communicator_init_host_driven()",
+        ));
+    }
+    if !CLOCK_PAUSED.swap(true, Ordering::SeqCst) {
+        block_on(async { tokio::time::pause() });
+    }
+    Ok(())
+}
+
+/// Whether the clock is paused for a simulation
+#[cfg(feature = "simulation")]
+pub fn is_clock_paused() -> bool {
+    is_host_driven() && CLOCK_PAUSED.load(Ordering::SeqCst)
+}
+
+/// Run ready background work of a host-driven runtime
+///
+/// Returns without waiting: each unit of work is one pass over the tasks
+/// that can make progress and the I/O and timers that are ready.
+///
+/// # Arguments
+/// * `max_work` - Maximum number of passes (at least one is made)
+///
+/// # Returns
+/// The number of background tasks still alive, so the host knows whether to
+/// keep ticking, or an error if the runtime is not host-driven
+pub fn tick(max_work: u32) -> Result<usize> {
+    match current().as_deref() {
+        Some(Instance::Owned(runtime)) if HOST_DRIVEN.load(Ordering::SeqCst) => {
+            Ok(drive(runtime, max_work))
+        }
+        _ => Err(Error::new(
+            ErrorCode::InvalidState,
+            "communicator_tick() needs a runtime initialized with communicator_init_host_driven()",
+        )),
+    }
+}
+
+fn drive(runtime: &Runtime, max_work: u32) -> usize {
+    crate::telemetry::scoped(|| {
+        runtime.block_on(async {
+            for _ in 0..max_work.max(1) {
+                tokio::task::yield_now().await;
+            }
+        })
+    });
+    runtime.metrics().num_alive_tasks()
+}
+
+fn current() -> Option<Arc<Instance>> {
+    RUNTIME.lock().ok()?.clone()
+}
+
+/// Get the metrics of the runtime
+///
+/// # Returns
+/// The metrics, or None if the runtime is not initialized
+pub fn metrics() -> Option<RuntimeMetrics> {
+    let instance = current()?;
+    let metrics = instance.handle().metrics();
+    Some(RuntimeMetrics {
+        workers: metrics.num_workers(),
+        alive_tasks: metrics.num_alive_tasks(),
+        global_queue_depth: metrics.global_queue_depth(),
+        host_driven: HOST_DRIVEN.load(Ordering::SeqCst),
+        shared: matches!(*instance, Instance::Shared(_)),
+    })
 }
 
 /// Execute an async future synchronously
 ///
 /// This blocks the current thread until the future completes.
 /// The runtime must be initialized before calling this function.
+/// Calls from several threads run concurrently. On a host-driven runtime
+/// the call also drives background work while it blocks.
 ///
 /// # Panics
 /// Panics if the runtime is not initialized
@@ -62,16 +320,22 @@ where
     F: Future + Send,
     F::Output: Send,
 {
-    let runtime_guard = RUNTIME.lock().expect("Failed to acquire runtime lock");
-    let runtime = runtime_guard.as_ref().expect("Runtime not initialized");
-    runtime.handle().block_on(future)
+    let call_timeout = *CALL_TIMEOUT.lock().unwrap();
+    // The runtime lock is released before blocking, so other threads can make calls
+    let runtime = current().expect("Runtime not initialized");
+    runtime.block_on(async move {
+        match call_timeout {
+            Some(timeout) => crate::deadline::with_timeout(timeout, future).await,
+            None => future.await,
+        }
+    })
 }
 
 /// Get a handle to the runtime for spawning background tasks
 ///
 /// Returns None if the runtime is not initialized
 pub fn runtime_handle() -> Option<tokio::runtime::Handle> {
-    RUNTIME.lock().ok()?.as_ref().map(|rt| rt.handle().clone())
+    current().map(|rt| rt.handle().clone())
 }
 
 /// Spawn a background task on the runtime
@@ -125,11 +389,82 @@ mod tests {
         // and other tests may be using it concurrently
     }
 
+    #[test]
+    fn test_drive_runs_spawned_tasks() {
+        let runtime = Builder::new_current_thread().enable_all().build().unwrap();
+        let task = runtime.spawn(async {
+            for _ in 0..3 {
+                tokio::task::yield_now().await;
+            }
+        });
+        assert_eq!(runtime.metrics().num_alive_tasks(), 1);
+
+        let mut passes = 0;
+        while drive(&runtime, 1) > 0 {
+            passes += 1;
+            assert!(passes < 100, "task never finished");
+        }
+        assert!(task.is_finished());
+    }
+
+    #[test]
+    fn test_tick_needs_host_driven_runtime() {
+        init_runtime().expect("Failed to initialize runtime");
+        assert_eq!(tick(1).unwrap_err().code, ErrorCode::InvalidState);
+        assert!(init_host_driven_runtime().is_err());
+        assert!(!is_host_driven());
+    }
+
+    #[test]
+    fn test_runtime_options() {
+        let options = RuntimeOptions {
+            worker_threads: Some(2),
+            thread_name_prefix: Some("comm".to_string()),
+            shared: false,
+        };
+        let instance = build(false, Some(&options)).unwrap();
+        assert_eq!(instance.handle().metrics().num_workers(), 2);
+        let name = instance.block_on(async {
+            tokio::spawn(async { std::thread::current().name().map(str::to_string) })
+                .await
+                .unwrap()
+        });
+        assert!(name.unwrap().starts_with("comm-"));
+
+        let zero = RuntimeOptions {
+            worker_threads: Some(0),
+            ..Default::default()
+        };
+        assert!(build(false, Some(&zero)).is_err());
+
+        // A shared runtime is the one the initializing thread runs in
+        let shared = RuntimeOptions {
+            shared: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            build(false, Some(&shared)).err().unwrap().code,
+            ErrorCode::InvalidState
+        );
+        let _guard = instance.handle().enter();
+        let reused = build(false, Some(&shared)).unwrap();
+        assert!(matches!(reused, Instance::Shared(_)));
+        assert_eq!(reused.block_on(async { 42 }), 42);
+    }
+
     #[test]
     fn test_multiple_init() {
         // Multiple initializations should be safe
         init_runtime().expect("Failed to initialize runtime");
         init_runtime().expect("Second init should be a no-op");
+        init_runtime_with_options(RuntimeOptions::default())
+            .expect("Init with the same options should be a no-op");
+        let other = RuntimeOptions {
+            worker_threads: Some(1),
+            ..Default::default()
+        };
+        assert!(init_runtime_with_options(other).is_err());
+        assert!(metrics().unwrap().workers > 0);
 
         // Note: Don't shutdown runtime in tests - it's shared globally
         // and other tests may be using it concurrently
@@ -0,0 +1,226 @@
+//! Scheduled messages
+//!
+//! Messages are scheduled on the server where the platform supports it
+//! (`Platform::schedule_message`), which then sends them even while no client
+//! is running. Otherwise they are kept by the platform's event pipeline (see
+//! `crate::pipeline`) and sent by the library once due, checked whenever events
+//! are polled. A locally scheduled
+//! message that fails to send stays listed with its error until cancelled.
+
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+
+use crate::error::{Error, ErrorCode, Result};
+use crate::platforms::Platform;
+use crate::types::ScheduledMessage;
+
+/// Prefix of the IDs of locally scheduled messages
+const LOCAL_PREFIX: &str = "local-";
+
+#[derive(Debug, Default)]
+struct QueueState {
+    /// Pending messages, earliest first
+    messages: Vec<ScheduledMessage>,
+    next_id: u64,
+}
+
+/// Messages scheduled by the library
+#[derive(Debug, Default)]
+pub struct ScheduledQueue {
+    state: Mutex<QueueState>,
+}
+
+impl ScheduledQueue {
+    /// Create an empty queue
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Schedule a message
+    ///
+    /// # Returns
+    /// The scheduled message
+    pub fn add(
+        &self,
+        channel_id: &str,
+        text: &str,
+        send_at: DateTime<Utc>,
+        root_id: Option<&str>,
+    ) -> ScheduledMessage {
+        let mut state = self.state.lock().unwrap();
+        state.next_id += 1;
+        let id = format!(
+            "{LOCAL_PREFIX}{}-{}",
+            Utc::now().timestamp_millis(),
+            state.next_id
+        );
+
+        let mut message = ScheduledMessage::new(id, channel_id, text, send_at);
+        message.root_id = root_id.map(|r| r.to_string());
+
+        let position = state
+            .messages
+            .partition_point(|m| m.scheduled_at <= send_at);
+        state.messages.insert(position, message.clone());
+        message
+    }
+
+    /// Cancel a scheduled message
+    ///
+    /// # Returns
+    /// Whether a message with this ID was pending
+    pub fn cancel(&self, id: &str) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let before = state.messages.len();
+        state.messages.retain(|m| m.id != id);
+        state.messages.len() != before
+    }
+
+    /// Get all pending messages, earliest first
+    pub fn list(&self) -> Vec<ScheduledMessage> {
+        self.state.lock().unwrap().messages.clone()
+    }
+
+    /// Check whether any message is due
+    pub fn has_due(&self, now: DateTime<Utc>) -> bool {
+        self.state
+            .lock()
+            .unwrap()
+            .messages
+            .iter()
+            .any(|m| m.is_due(now))
+    }
+
+    /// Remove and return the messages that are due
+    pub fn take_due(&self, now: DateTime<Utc>) -> Vec<ScheduledMessage> {
+        let mut state = self.state.lock().unwrap();
+        let (due, pending) = std::mem::take(&mut state.messages)
+            .into_iter()
+            .partition(|m| m.is_due(now));
+        state.messages = pending;
+        due
+    }
+
+    /// Put back a message that failed to send, with the reason
+    fn fail(&self, mut message: ScheduledMessage, error: &Error) {
+        message.error = Some(error.message.clone());
+        let mut state = self.state.lock().unwrap();
+        let position = state
+            .messages
+            .partition_point(|m| m.scheduled_at <= message.scheduled_at);
+        state.messages.insert(position, message);
+    }
+}
+
+/// Schedule a message on the server, or locally if the platform cannot
+pub(crate) async fn schedule(
+    queue: &ScheduledQueue,
+    platform: &dyn Platform,
+    channel_id: &str,
+    text: &str,
+    send_at: DateTime<Utc>,
+) -> Result<ScheduledMessage> {
+    if send_at <= Utc::now() {
+        return Err(Error::invalid_argument("Scheduled time is in the past"));
+    }
+    match platform.schedule_message(channel_id, text, send_at).await {
+        Ok(scheduled) => Ok(scheduled),
+        Err(e) if e.code == ErrorCode::Unsupported => {
+            Ok(queue.add(channel_id, text, send_at, None))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// List the messages scheduled on the server and locally, earliest first
+pub(crate) async fn list(
+    queue: &ScheduledQueue,
+    platform: &dyn Platform,
+) -> Result<Vec<ScheduledMessage>> {
+    let mut messages = match platform.get_scheduled_messages().await {
+        Ok(messages) => messages,
+        Err(e) if e.code == ErrorCode::Unsupported => Vec::new(),
+        Err(e) => return Err(e),
+    };
+    messages.extend(queue.list());
+    messages.sort_by_key(|m| m.scheduled_at);
+    Ok(messages)
+}
+
+/// Cancel a scheduled message
+///
+/// # Returns
+/// A NotFound error for an unknown locally scheduled message
+pub(crate) async fn cancel(
+    queue: &ScheduledQueue,
+    platform: &dyn Platform,
+    id: &str,
+) -> Result<()> {
+    if !id.starts_with(LOCAL_PREFIX) {
+        return platform.cancel_scheduled_message(id).await;
+    }
+    if queue.cancel(id) {
+        Ok(())
+    } else {
+        Err(Error::new(
+            ErrorCode::NotFound,
+            format!("No scheduled message {id}"),
+        ))
+    }
+}
+
+/// Send the locally scheduled messages that are due
+pub(crate) async fn send_due(queue: &ScheduledQueue, platform: &dyn Platform) {
+    for message in queue.take_due(Utc::now()) {
+        let sent = match &message.root_id {
+            Some(root_id) => {
+                platform
+                    .send_reply(&message.channel_id, &message.message, root_id)
+                    .await
+            }
+            None => {
+                platform
+                    .send_message(&message.channel_id, &message.message)
+                    .await
+            }
+        };
+        if let Err(e) = sent {
+            queue.fail(message, &e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_take_due() {
+        let queue = ScheduledQueue::new();
+        let now = Utc::now();
+        let later = queue.add("ch1", "later", now + Duration::minutes(10), None);
+        let soon = queue.add("ch1", "soon", now + Duration::minutes(1), Some("post1"));
+
+        assert_eq!(queue.list(), vec![soon.clone(), later.clone()]);
+        assert!(queue.take_due(now).is_empty());
+        assert_eq!(
+            queue.take_due(now + Duration::minutes(5)),
+            vec![soon.clone()]
+        );
+
+        queue.fail(
+            soon.clone(),
+            &Error::new(ErrorCode::NetworkError, "offline"),
+        );
+        // Failed messages stay listed but are not sent again
+        assert!(queue
+            .take_due(now + Duration::hours(1))
+            .iter()
+            .all(|m| m.id == later.id));
+        assert_eq!(queue.list()[0].error.as_deref(), Some("offline"));
+        assert!(queue.cancel(&soon.id));
+        assert!(queue.list().is_empty());
+    }
+}
@@ -0,0 +1,544 @@
+//! Script hooks for message processing (`scripting` feature)
+//!
+//! Lets power users run their own scripts in the message pipeline without
+//! modifying the host application. A hook is a Lua 5.4 script, run by an
+//! interpreter embedded in the library. The script returns a function that
+//! is called with the message as a table and returns the transformed message,
+//! or nil to drop it:
+//!
+//! ```lua
+//! return function(message)
+//!   message.text = message.text:gsub("hunter2", "*******")
+//!   return message
+//! end
+//! ```
+//!
+//! - Inbound hooks see every `message_posted`/`message_updated` event before it
+//!   is returned by `communicator_platform_poll_event`. The script receives and
+//!   returns a Message object.
+//! - Outbound hooks see messages before they are sent. The script receives and
+//!   returns `{channel_id = "...", text = "..."}`.
+//!
+//! Scripts are sandboxed. Every run gets a fresh interpreter with the
+//! `string`, `table`, `math` and `utf8` libraries and the base functions that
+//! have no side effects: there is no file, process, network or module access
+//! (`io`, `os`, `package`, `require`, `load`, `dofile`, `print`, ... are
+//! absent), and nothing is kept between runs. A run is aborted when it
+//! exceeds its memory, instruction or time limit. The instruction and time
+//! limits are checked between Lua instructions, so a single library call
+//! (e.g. a pathological pattern match) can run past them.
+
+use std::cell::Cell;
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use mlua::{HookTriggers, Lua, LuaOptions, LuaSerdeExt, StdLib, VmState};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, ErrorCode, Result};
+use crate::types::{Message, Transformation};
+
+/// Default time a script may run before it is aborted
+pub const DEFAULT_SCRIPT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Default memory a script may allocate, in bytes
+pub const DEFAULT_SCRIPT_MEMORY_LIMIT: u64 = 16 * 1024 * 1024;
+
+/// Default number of Lua instructions a script may execute
+pub const DEFAULT_SCRIPT_INSTRUCTION_LIMIT: u64 = 10_000_000;
+
+/// Base functions removed from the sandbox, as they read files, load code or
+/// write to the host's stdout
+const UNSAFE_GLOBALS: &[&str] = &["dofile", "loadfile", "load", "print", "collectgarbage"];
+
+/// How often the time limit is checked, in instructions
+const CLOCK_INTERVAL: u64 = 1024;
+
+/// Where in the message pipeline a hook runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookStage {
+    /// Received messages, before they are returned to the application
+    Inbound,
+    /// Messages being sent, before they reach the platform
+    Outbound,
+}
+
+impl HookStage {
+    /// Parse a stage name ("inbound" or "outbound")
+    pub fn parse(stage: &str) -> Option<Self> {
+        match stage {
+            "inbound" => Some(HookStage::Inbound),
+            "outbound" => Some(HookStage::Outbound),
+            _ => None,
+        }
+    }
+}
+
+/// A message being sent, as seen by outbound hooks
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutgoingMessage {
+    /// The target channel
+    pub channel_id: String,
+    /// The message text
+    pub text: String,
+}
+
+/// A limit a script run exceeded
+#[derive(Debug, Clone, Copy)]
+enum Exceeded {
+    Instructions,
+    Time,
+}
+
+/// A script in the message pipeline
+#[derive(Debug, Clone)]
+pub struct ScriptHook {
+    /// Lua source returning the hook function
+    source: String,
+    name: String,
+    stage: HookStage,
+    /// Maximum run time
+    timeout: Duration,
+    /// Maximum memory in bytes
+    memory_limit: u64,
+    /// Maximum number of Lua instructions
+    instruction_limit: u64,
+}
+
+impl ScriptHook {
+    /// Create a new hook
+    ///
+    /// # Arguments
+    /// * `source` - Lua source returning the hook function
+    /// * `stage` - Where in the pipeline the hook runs
+    ///
+    /// # Returns
+    /// The hook, or an InvalidArgument error if the source does not compile
+    pub fn new(source: impl Into<String>, stage: HookStage) -> Result<Self> {
+        let source = source.into();
+        sandbox()?
+            .load(&source)
+            .into_function()
+            .map_err(|e| Error::invalid_argument(format!("Invalid script: {e}")))?;
+
+        Ok(Self {
+            source,
+            name: "script".to_string(),
+            stage,
+            timeout: DEFAULT_SCRIPT_TIMEOUT,
+            memory_limit: DEFAULT_SCRIPT_MEMORY_LIMIT,
+            instruction_limit: DEFAULT_SCRIPT_INSTRUCTION_LIMIT,
+        })
+    }
+
+    /// Set the name recorded in `Message::transformations` (default "script")
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Set the time limit
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the memory limit in bytes
+    pub fn with_memory_limit(mut self, bytes: u64) -> Self {
+        self.memory_limit = bytes;
+        self
+    }
+
+    /// Set the maximum number of Lua instructions per run
+    pub fn with_instruction_limit(mut self, instructions: u64) -> Self {
+        self.instruction_limit = instructions;
+        self
+    }
+
+    /// Get the stage the hook runs in
+    pub fn stage(&self) -> HookStage {
+        self.stage
+    }
+
+    /// Name recorded in `Message::transformations` when the hook changes a message
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    /// Run the script with JSON input
+    ///
+    /// # Returns
+    /// The JSON returned by the script (`Value::Null` drops the message)
+    pub fn run(&self, input: &serde_json::Value) -> Result<serde_json::Value> {
+        let lua = sandbox()?;
+        lua.set_memory_limit(usize::try_from(self.memory_limit).unwrap_or(usize::MAX))
+            .map_err(|e| Error::new(ErrorCode::Unknown, e.to_string()))?;
+
+        let exceeded = Rc::new(Cell::new(None));
+        let started = Instant::now();
+        let executed = Cell::new(0u64);
+        let (timeout, instruction_limit) = (self.timeout, self.instruction_limit);
+        let hook_exceeded = Rc::clone(&exceeded);
+        // Every instruction, so that a script catching the error with pcall
+        // fails again at its next instruction
+        lua.set_hook(HookTriggers::new().every_nth_instruction(1), move |_, _| {
+            let count = executed.get() + 1;
+            executed.set(count);
+            if hook_exceeded.get().is_none() {
+                if count > instruction_limit {
+                    hook_exceeded.set(Some(Exceeded::Instructions));
+                } else if count.is_multiple_of(CLOCK_INTERVAL) && started.elapsed() >= timeout {
+                    hook_exceeded.set(Some(Exceeded::Time));
+                }
+            }
+            match hook_exceeded.get() {
+                Some(_) => Err(mlua::Error::runtime("script limit exceeded")),
+                None => Ok(VmState::Continue),
+            }
+        });
+
+        let result = lua
+            .load(&self.source)
+            .set_name("=script")
+            .eval::<mlua::Function>()
+            .and_then(|function| function.call::<mlua::Value>(lua.to_value(input)?))
+            .and_then(|output| lua.from_value::<serde_json::Value>(output));
+
+        match (exceeded.get(), result) {
+            (Some(Exceeded::Instructions), _) => Err(Error::new(
+                ErrorCode::Timeout,
+                format!(
+                    "Script exceeded its limit of {} instructions",
+                    self.instruction_limit
+                ),
+            )),
+            (Some(Exceeded::Time), _) => Err(Error::new(
+                ErrorCode::Timeout,
+                format!("Script exceeded its time limit of {:?}", self.timeout),
+            )),
+            (None, Ok(output)) => Ok(output),
+            (None, Err(mlua::Error::MemoryError(_))) => Err(Error::new(
+                ErrorCode::OutOfMemory,
+                format!(
+                    "Script exceeded its memory limit of {} bytes",
+                    self.memory_limit
+                ),
+            )),
+            (None, Err(e)) => Err(Error::new(
+                ErrorCode::Unknown,
+                format!("Script failed: {e}"),
+            )),
+        }
+    }
+}
+
+/// Create an interpreter without I/O or code loading
+fn sandbox() -> Result<Lua> {
+    let lua = Lua::new_with(
+        StdLib::STRING | StdLib::TABLE | StdLib::MATH | StdLib::UTF8,
+        LuaOptions::default(),
+    )
+    .map_err(|e| {
+        Error::new(
+            ErrorCode::Unknown,
+            format!("Failed to create interpreter: {e}"),
+        )
+    })?;
+
+    let globals = lua.globals();
+    for name in UNSAFE_GLOBALS {
+        globals
+            .raw_remove(*name)
+            .map_err(|e| Error::new(ErrorCode::Unknown, e.to_string()))?;
+    }
+    drop(globals);
+    Ok(lua)
+}
+
+/// The hooks installed for a platform
+#[derive(Debug, Clone, Default)]
+pub struct ScriptPipeline {
+    hooks: Vec<ScriptHook>,
+}
+
+impl ScriptPipeline {
+    /// Create an empty pipeline
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a hook (hooks of a stage run in the order they were added)
+    pub fn add(&mut self, hook: ScriptHook) {
+        self.hooks.push(hook);
+    }
+
+    /// Check whether the pipeline has hooks for a stage
+    pub fn has_stage(&self, stage: HookStage) -> bool {
+        self.hooks.iter().any(|hook| hook.stage == stage)
+    }
+
+    /// Run inbound hooks on a received message
+    ///
+    /// The text as received is kept in `Message::original_text`, and every hook
+    /// that changes the text is listed in `Message::transformations` (scripts may
+    /// add their own, more descriptive entries instead). Scripts cannot alter
+    /// the original text or earlier transformations.
+    ///
+    /// # Returns
+    /// The transformed message, or None if a script dropped it
+    pub fn process_inbound(&self, mut message: Message) -> Result<Option<Message>> {
+        for hook in self
+            .hooks
+            .iter()
+            .filter(|hook| hook.stage == HookStage::Inbound)
+        {
+            let input = serde_json::to_value(&message)
+                .map_err(|e| Error::new(ErrorCode::Unknown, e.to_string()))?;
+            let output = hook.run(&input)?;
+            if output.is_null() {
+                return Ok(None);
+            }
+            let mut next: Message = serde_json::from_value(output).map_err(|e| {
+                Error::new(ErrorCode::Unknown, format!("Invalid script output: {e}"))
+            })?;
+
+            let known = message.transformations.len();
+            let added = if next.transformations.len() > known {
+                next.transformations.split_off(known)
+            } else {
+                Vec::new()
+            };
+            let new_text = std::mem::take(&mut next.text);
+            next.text = message.text;
+            next.original_text = message.original_text;
+            next.transformations = message.transformations;
+
+            if new_text != next.text {
+                let mut added = added.into_iter();
+                let first = added
+                    .next()
+                    .unwrap_or_else(|| Transformation::new(hook.name()));
+                next.transform(first, new_text);
+                next.transformations.extend(added);
+            }
+            message = next;
+        }
+        Ok(Some(message))
+    }
+
+    /// Run outbound hooks on a message being sent
+    ///
+    /// # Returns
+    /// The transformed message, or None if a script dropped it
+    pub fn process_outbound(&self, message: OutgoingMessage) -> Result<Option<OutgoingMessage>> {
+        self.process(HookStage::Outbound, message)
+    }
+
+    fn process<T>(&self, stage: HookStage, value: T) -> Result<Option<T>>
+    where
+        T: Serialize + for<'de> Deserialize<'de>,
+    {
+        let mut json = serde_json::to_value(value)
+            .map_err(|e| Error::new(ErrorCode::Unknown, e.to_string()))?;
+
+        for hook in self.hooks.iter().filter(|hook| hook.stage == stage) {
+            json = hook.run(&json)?;
+            if json.is_null() {
+                return Ok(None);
+            }
+        }
+
+        serde_json::from_value(json)
+            .map(Some)
+            .map_err(|e| Error::new(ErrorCode::Unknown, format!("Invalid script output: {e}")))
+    }
+}
+
+/// Number of transformed messages whose original text is kept per platform
+const MAX_KEPT_ORIGINALS: usize = 1000;
+
+/// Original texts of recently transformed messages, oldest first
+#[derive(Debug, Default)]
+pub(crate) struct KeptOriginals {
+    order: VecDeque<String>,
+    messages: HashMap<String, (String, Vec<Transformation>)>,
+}
+
+impl KeptOriginals {
+    /// Keep the original text of a transformed message for later lookup
+    pub(crate) fn keep(&mut self, message: &Message) {
+        let Some(original) = &message.original_text else {
+            return;
+        };
+        let entry = (original.clone(), message.transformations.clone());
+        if self.messages.insert(message.id.clone(), entry).is_none() {
+            self.order.push_back(message.id.clone());
+        }
+        while self.order.len() > MAX_KEPT_ORIGINALS {
+            if let Some(oldest) = self.order.pop_front() {
+                self.messages.remove(&oldest);
+            }
+        }
+    }
+
+    /// Get the original text and transformations of a transformed message
+    pub(crate) fn get(&self, message_id: &str) -> Option<(String, Vec<Transformation>)> {
+        self.messages.get(message_id).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lua(body: &str, stage: HookStage) -> ScriptHook {
+        ScriptHook::new(format!("return function(m)\n{body}\nend"), stage).unwrap()
+    }
+
+    #[test]
+    fn test_outbound_transform() {
+        let mut pipeline = ScriptPipeline::new();
+        pipeline.add(lua(
+            r#"m.text = m.text:gsub("secret", "[redacted]") return m"#,
+            HookStage::Outbound,
+        ));
+
+        let result = pipeline
+            .process_outbound(OutgoingMessage {
+                channel_id: "ch-1".to_string(),
+                text: "the secret is 42".to_string(),
+            })
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.text, "the [redacted] is 42");
+        assert_eq!(result.channel_id, "ch-1");
+    }
+
+    #[test]
+    fn test_inbound_keeps_original() {
+        let mut pipeline = ScriptPipeline::new();
+        pipeline.add(
+            lua(
+                r#"m.text = m.text:gsub("hallo", "hello") return m"#,
+                HookStage::Inbound,
+            )
+            .with_name("translate"),
+        );
+        // Tries to hide the original text
+        pipeline.add(lua(
+            "m.text = m.text:upper() m.original_text = nil return m",
+            HookStage::Inbound,
+        ));
+        pipeline.add(lua("return m", HookStage::Inbound));
+
+        let message = Message::new("msg-1", "hallo", "user-1", "ch-1");
+        let result = pipeline.process_inbound(message).unwrap().unwrap();
+        assert_eq!(result.text, "HELLO");
+        assert_eq!(result.original(), "hallo");
+        assert_eq!(result.transformations.len(), 2);
+        assert_eq!(result.transformations[0].name, "translate");
+        assert_eq!(result.transformations[1].name, "script");
+    }
+
+    #[test]
+    fn test_kept_originals() {
+        let mut kept = KeptOriginals::default();
+        let mut message = Message::new("msg-1", "hallo", "user-1", "ch-1");
+        kept.keep(&message);
+        assert!(kept.get("msg-1").is_none());
+
+        message.transform(Transformation::new("translate"), "hello");
+        kept.keep(&message);
+        let (original, transformations) = kept.get("msg-1").unwrap();
+        assert_eq!(original, "hallo");
+        assert_eq!(transformations.len(), 1);
+    }
+
+    #[test]
+    fn test_inbound_drop_and_stage_isolation() {
+        let mut pipeline = ScriptPipeline::new();
+        pipeline.add(lua("return nil", HookStage::Inbound));
+        assert!(pipeline.has_stage(HookStage::Inbound));
+        assert!(!pipeline.has_stage(HookStage::Outbound));
+
+        let message = Message::new("msg-1", "hello", "user-1", "ch-1");
+        assert!(pipeline.process_inbound(message).unwrap().is_none());
+
+        // Outbound messages are not affected by inbound hooks
+        let outgoing = OutgoingMessage {
+            channel_id: "ch-1".to_string(),
+            text: "hi".to_string(),
+        };
+        assert_eq!(
+            pipeline.process_outbound(outgoing.clone()).unwrap(),
+            Some(outgoing)
+        );
+    }
+
+    #[test]
+    fn test_script_limits() {
+        let input = serde_json::json!({});
+
+        let hook = lua("while true do end", HookStage::Inbound).with_instruction_limit(10_000);
+        let err = hook.run(&input).unwrap_err();
+        assert_eq!(err.code, ErrorCode::Timeout);
+        assert!(err.message.contains("instructions"));
+
+        // Catching the limit error doesn't let the script go on
+        let hook = lua(
+            "while true do pcall(function() while true do end end) end",
+            HookStage::Inbound,
+        )
+        .with_instruction_limit(u64::MAX)
+        .with_timeout(Duration::from_millis(50));
+        let started = Instant::now();
+        let err = hook.run(&input).unwrap_err();
+        assert_eq!(err.code, ErrorCode::Timeout);
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        let hook = lua(
+            "local t = {} for i = 1, 1e8 do t[i] = i end return m",
+            HookStage::Inbound,
+        )
+        .with_memory_limit(1024 * 1024);
+        assert_eq!(hook.run(&input).unwrap_err().code, ErrorCode::OutOfMemory);
+    }
+
+    #[test]
+    fn test_sandbox() {
+        // No I/O, process or code loading functions
+        let hook = lua(
+            r#"return { io = io, os = os, require = require, load = load, dofile = dofile,
+                        print = print, string = type(string.format) }"#,
+            HookStage::Inbound,
+        );
+        let output = hook.run(&serde_json::json!({})).unwrap();
+        assert_eq!(output, serde_json::json!({ "string": "function" }));
+
+        // Nothing is kept between runs
+        let hook = lua(
+            "counter = (counter or 0) + 1 return counter",
+            HookStage::Inbound,
+        );
+        assert_eq!(hook.run(&serde_json::json!({})).unwrap(), 1);
+        assert_eq!(hook.run(&serde_json::json!({})).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_script_failure() {
+        let hook = lua(r#"error("nope")"#, HookStage::Inbound);
+        assert_eq!(
+            hook.run(&serde_json::json!({})).unwrap_err().code,
+            ErrorCode::Unknown
+        );
+
+        // The script must return a function
+        let hook = ScriptHook::new("return 42", HookStage::Inbound).unwrap();
+        assert!(hook.run(&serde_json::json!({})).is_err());
+
+        let err = ScriptHook::new("return function(", HookStage::Inbound).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidArgument);
+    }
+}
@@ -0,0 +1,149 @@
+//! Shared platform connections
+//!
+//! Independent consumers in one process (e.g. an editor plugin and its
+//! embedded terminal) can ask for the platform handle of a connection key
+//! instead of creating their own. The first request creates the handle; later
+//! requests with the same key get the same handle, so all consumers share one
+//! session and its caches. Handles are reference counted:
+//! `communicator_platform_destroy` only frees a shared handle once every
+//! consumer has destroyed it.
+//!
+//! Consumers of a shared handle share everything registered on it, including
+//! its event queue: an event is delivered to whichever consumer polls first.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+
+use crate::error::{Error, Result};
+
+#[derive(Debug)]
+struct SharedEntry {
+    /// Address of the platform handle
+    handle: usize,
+    /// Server the handle was created for
+    server_url: String,
+    /// Number of consumers holding the handle
+    refs: usize,
+}
+
+lazy_static! {
+    /// Shared handles, keyed by connection key
+    static ref SHARED: Mutex<HashMap<String, SharedEntry>> = Mutex::new(HashMap::new());
+}
+
+/// Get the shared handle of a connection key, creating it if needed
+///
+/// # Arguments
+/// * `key` - Identifies the connection among consumers
+/// * `server_url` - Server of the connection; must match if the handle exists
+/// * `create` - Creates a new handle and returns its address
+///
+/// # Returns
+/// The handle address, or an error if the key is in use for another server
+pub(crate) fn acquire(
+    key: &str,
+    server_url: &str,
+    create: impl FnOnce() -> Result<usize>,
+) -> Result<usize> {
+    let mut shared = SHARED.lock().unwrap();
+    if let Some(entry) = shared.get_mut(key) {
+        if entry.server_url != server_url {
+            return Err(Error::invalid_argument(format!(
+                "Connection key '{key}' is already used for {}",
+                entry.server_url
+            )));
+        }
+        entry.refs += 1;
+        return Ok(entry.handle);
+    }
+
+    let handle = create()?;
+    shared.insert(
+        key.to_string(),
+        SharedEntry {
+            handle,
+            server_url: server_url.to_string(),
+            refs: 1,
+        },
+    );
+    Ok(handle)
+}
+
+/// Drop one reference to a handle
+///
+/// # Returns
+/// Whether the handle should be freed: true for the last reference to a
+/// shared handle and for handles that are not shared
+pub(crate) fn release(handle: usize) -> bool {
+    let mut shared = SHARED.lock().unwrap();
+    let Some(key) = shared
+        .iter()
+        .find(|(_, entry)| entry.handle == handle)
+        .map(|(key, _)| key.clone())
+    else {
+        return true;
+    };
+
+    let entry = shared.get_mut(&key).expect("entry was just found");
+    entry.refs -= 1;
+    if entry.refs > 0 {
+        return false;
+    }
+    shared.remove(&key);
+    true
+}
+
+/// Number of consumers holding a handle, or 0 if it is not shared
+pub(crate) fn ref_count(handle: usize) -> usize {
+    SHARED
+        .lock()
+        .unwrap()
+        .values()
+        .find(|entry| entry.handle == handle)
+        .map(|entry| entry.refs)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorCode;
+
+    #[test]
+    fn test_shared_handles_are_reference_counted() {
+        let key = "test-shared-refcount";
+        let first = acquire(key, "https://a.example.com", || Ok(0x1000)).unwrap();
+        let second = acquire(key, "https://a.example.com", || {
+            panic!("handle must be reused")
+        })
+        .unwrap();
+        assert_eq!(first, second);
+        assert_eq!(ref_count(first), 2);
+
+        assert!(!release(first));
+        assert_eq!(ref_count(first), 1);
+        assert!(release(first));
+        assert_eq!(ref_count(first), 0);
+
+        // Once released, the key creates a new handle
+        let third = acquire(key, "https://a.example.com", || Ok(0x2000)).unwrap();
+        assert_eq!(third, 0x2000);
+        assert!(release(third));
+    }
+
+    #[test]
+    fn test_key_bound_to_server() {
+        let key = "test-shared-server";
+        let handle = acquire(key, "https://a.example.com", || Ok(0x3000)).unwrap();
+        let err = acquire(key, "https://b.example.com", || Ok(0x4000)).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidArgument);
+        assert!(release(handle));
+    }
+
+    #[test]
+    fn test_unshared_handle_is_released() {
+        assert!(release(0x5000));
+    }
+}
@@ -0,0 +1,460 @@
+//! Deterministic simulation of servers and time (`simulation` feature)
+//!
+//! Downstream applications test scenarios such as "the server drops the
+//! connection for 30 seconds, then 100 messages arrive on reconnect" against
+//! the library's real reconnect, backoff and event code. A [`SimulatedServer`]
+//! registered for a host name answers the HTTP requests and WebSocket
+//! connections to that host in memory, and the paused clock only moves when
+//! the test [`advance`]s it, firing backoff delays, pings and timeouts in
+//! order. A scenario takes the same steps on every run, in no real time.
+//!
+//! The clock can only be paused on a host-driven runtime (see
+//! [`crate::runtime`]). Timestamps taken from the system clock (e.g. message
+//! times) are not simulated.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::{SinkExt, StreamExt};
+use lazy_static::lazy_static;
+use serde_json::Value;
+use tokio::io::DuplexStream;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::MaybeTlsStream;
+
+use crate::error::{Error, ErrorCode, Result};
+use crate::platforms::mattermost::websocket::{Transport, WsStream};
+use crate::runtime;
+
+/// Session token handed out by simulated logins
+const TOKEN: &str = "simulated-token";
+
+/// Buffer size of the in-memory connection between client and server
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// Scripted Mattermost server answering in memory
+///
+/// Logins succeed with the configured user, and other requests get the
+/// responses set with [`SimulatedServer::set_route`] (404 otherwise). While
+/// the server is unreachable, WebSocket connections are refused and HTTP
+/// requests get 502 Bad Gateway, as from a proxy in front of a stopped server.
+#[derive(Debug)]
+pub struct SimulatedServer {
+    state: Mutex<ServerState>,
+}
+
+#[derive(Debug)]
+struct ServerState {
+    reachable: bool,
+    user: Value,
+    /// Scripted responses by method and API path (without `/api/v4`)
+    routes: HashMap<(String, String), (u16, String)>,
+    /// One-off responses, answered in order before the scripted ones
+    queued: HashMap<(String, String), VecDeque<(u16, String)>>,
+    /// Events pushed while no connection was open, sent on the next one
+    backlog: VecDeque<Value>,
+    /// Number and event sender of the open connection
+    live: Option<(u64, mpsc::UnboundedSender<Value>)>,
+    connections: u64,
+}
+
+impl SimulatedServer {
+    fn new() -> Self {
+        Self {
+            state: Mutex::new(ServerState {
+                reachable: true,
+                user: serde_json::json!({
+                    "id": "simulated-user",
+                    "username": "simulated",
+                    "create_at": 0,
+                    "update_at": 0,
+                    "delete_at": 0,
+                }),
+                routes: HashMap::new(),
+                queued: HashMap::new(),
+                backlog: VecDeque::new(),
+                live: None,
+                connections: 0,
+            }),
+        }
+    }
+
+    /// Set the user logins and `GET /users/me` return
+    pub fn set_user(&self, user: Value) {
+        self.state.lock().unwrap().user = user;
+    }
+
+    /// Answer requests to an API path with a fixed response
+    ///
+    /// # Arguments
+    /// * `method` - HTTP method, e.g. "GET"
+    /// * `path` - API path without `/api/v4`, e.g. "/users/me/teams"
+    /// * `status` - HTTP status code
+    /// * `body` - Response body (JSON)
+    pub fn set_route(&self, method: &str, path: &str, status: u16, body: impl Into<String>) {
+        self.state.lock().unwrap().routes.insert(
+            (method.to_ascii_uppercase(), path.to_string()),
+            (status, body.into()),
+        );
+    }
+
+    /// Answer the next request to an API path with a response, once
+    ///
+    /// Queued responses are used in order, e.g. for successive pages, before
+    /// the response set with [`set_route`](Self::set_route).
+    pub fn queue_route(&self, method: &str, path: &str, status: u16, body: impl Into<String>) {
+        self.state
+            .lock()
+            .unwrap()
+            .queued
+            .entry((method.to_ascii_uppercase(), path.to_string()))
+            .or_default()
+            .push_back((status, body.into()));
+    }
+
+    /// Send a WebSocket event, e.g. `{"event": "posted", "data": {...}}`
+    ///
+    /// The server numbers events itself. Without an open connection, the
+    /// event is sent once a client connects.
+    pub fn push_event(&self, event: Value) {
+        let mut state = self.state.lock().unwrap();
+        let event = match &state.live {
+            Some((_, live)) => match live.send(event) {
+                Ok(()) => return,
+                Err(mpsc::error::SendError(event)) => event,
+            },
+            None => event,
+        };
+        state.live = None;
+        state.backlog.push_back(event);
+    }
+
+    /// Take the server off the network, or bring it back
+    ///
+    /// Going offline drops the open WebSocket connection.
+    pub fn set_reachable(&self, reachable: bool) {
+        let mut state = self.state.lock().unwrap();
+        state.reachable = reachable;
+        if !reachable {
+            state.live = None;
+        }
+    }
+
+    /// Whether the server is reachable
+    pub fn is_reachable(&self) -> bool {
+        self.state.lock().unwrap().reachable
+    }
+
+    /// Number of WebSocket connections accepted so far
+    pub fn connections(&self) -> u64 {
+        self.state.lock().unwrap().connections
+    }
+
+    /// Answer an HTTP request
+    pub(crate) fn respond(&self, request: &reqwest::Request) -> reqwest::Response {
+        let mut state = self.state.lock().unwrap();
+        let method = request.method().as_str();
+        let path = request.url().path();
+        let path = path.strip_prefix("/api/v4").unwrap_or(path);
+        let key = (method.to_string(), path.to_string());
+
+        let (status, body) = if !state.reachable {
+            (502, r#"{"message":"Bad Gateway"}"#.to_string())
+        } else if let Some(response) = state.queued.get_mut(&key).and_then(VecDeque::pop_front) {
+            response
+        } else if let Some(response) = state.routes.get(&key) {
+            response.clone()
+        } else {
+            match (method, path) {
+                ("POST", "/users/login") | ("GET", "/users/me") => (200, state.user.to_string()),
+                ("GET", "/system/ping") => (200, r#"{"status":"OK"}"#.to_string()),
+                _ => (
+                    404,
+                    serde_json::json!({
+                        "id": "api.context.404.app_error",
+                        "message": "Sorry, we could not find the page.",
+                        "status_code": 404,
+                    })
+                    .to_string(),
+                ),
+            }
+        };
+
+        let mut response = http::Response::builder()
+            .status(status)
+            .header("Content-Type", "application/json");
+        if path == "/users/login" && (200..300).contains(&status) {
+            response = response.header("Token", TOKEN);
+        }
+        let response = response
+            .body(body)
+            .unwrap_or_else(|_| http::Response::new(String::new()));
+        reqwest::Response::from(response)
+    }
+
+    /// Open a WebSocket connection to the server
+    pub(crate) async fn open_websocket(
+        self: Arc<Self>,
+        ws_url: &str,
+    ) -> std::result::Result<WsStream, String> {
+        if !self.is_reachable() {
+            return Err("Connection refused (simulated server unreachable)".to_string());
+        }
+        let (client, server) = tokio::io::duplex(BUFFER_SIZE);
+        tokio::spawn(self.serve(server));
+
+        let client: Box<dyn Transport> = Box::new(client);
+        tokio_tungstenite::client_async(ws_url, MaybeTlsStream::Plain(client))
+            .await
+            .map(|(stream, _)| stream)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Server side of a WebSocket connection
+    async fn serve(self: Arc<Self>, stream: DuplexStream) {
+        let Ok(ws) = tokio_tungstenite::accept_async(stream).await else {
+            return;
+        };
+        let (mut write, mut read) = ws.split();
+
+        // Answer the authentication challenge, then greet like a real server
+        let seq_reply = match read.next().await {
+            Some(Ok(Message::Text(text))) => serde_json::from_str::<Value>(&text)
+                .ok()
+                .and_then(|challenge| challenge.get("seq")?.as_i64())
+                .unwrap_or(1),
+            _ => return,
+        };
+        let (live, mut events) = mpsc::unbounded_channel();
+        let (connection, backlog): (u64, Vec<Value>) = {
+            let mut state = self.state.lock().unwrap();
+            state.connections += 1;
+            state.live = Some((state.connections, live));
+            (state.connections, state.backlog.drain(..).collect())
+        };
+        let greeting = [
+            serde_json::json!({ "status": "OK", "seq_reply": seq_reply }),
+            serde_json::json!({
+                "event": "hello",
+                "data": { "server_version": "simulated" },
+                "broadcast": {},
+                "seq": 0,
+            }),
+        ];
+        for message in greeting {
+            if write
+                .send(Message::Text(message.to_string()))
+                .await
+                .is_err()
+            {
+                return;
+            }
+        }
+
+        let mut seq = 0;
+        let mut pending: VecDeque<Value> = backlog.into();
+        loop {
+            let event = match pending.pop_front() {
+                Some(event) => event,
+                None => tokio::select! {
+                    event = events.recv() => match event {
+                        Some(event) => event,
+                        // Taken offline
+                        None => break,
+                    },
+                    message = read.next() => match message {
+                        Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                        Some(Ok(_)) => continue,
+                    },
+                },
+            };
+            seq += 1;
+            let mut event = event;
+            if let Some(object) = event.as_object_mut() {
+                object.insert("seq".to_string(), seq.into());
+            }
+            if write.send(Message::Text(event.to_string())).await.is_err() {
+                break;
+            }
+        }
+
+        let mut state = self.state.lock().unwrap();
+        if matches!(state.live, Some((current, _)) if current == connection) {
+            state.live = None;
+        }
+        // Events sent after the connection broke are kept for the next one
+        while let Ok(event) = events.try_recv() {
+            state.backlog.push_back(event);
+        }
+    }
+}
+
+lazy_static! {
+    /// Simulated servers by host name
+    static ref SERVERS: Mutex<HashMap<String, Arc<SimulatedServer>>> = Mutex::new(HashMap::new());
+}
+
+/// Simulate a server for a host name, replacing any previous one
+///
+/// Platforms connecting to e.g. `http://<host>` then talk to it.
+pub fn add_server(host: &str) -> Arc<SimulatedServer> {
+    let server = Arc::new(SimulatedServer::new());
+    SERVERS
+        .lock()
+        .unwrap()
+        .insert(host.to_ascii_lowercase(), Arc::clone(&server));
+    server
+}
+
+/// The simulated server of a host name
+pub fn server(host: &str) -> Option<Arc<SimulatedServer>> {
+    SERVERS
+        .lock()
+        .unwrap()
+        .get(&host.to_ascii_lowercase())
+        .cloned()
+}
+
+/// Stop simulating a server
+pub fn remove_server(host: &str) {
+    SERVERS.lock().unwrap().remove(&host.to_ascii_lowercase());
+}
+
+/// The simulated server a URL points to, if any
+pub(crate) fn server_for(url: &str) -> Option<Arc<SimulatedServer>> {
+    let url = url::Url::parse(url).ok()?;
+    server(url.host_str()?)
+}
+
+/// Send an HTTP request, to the simulated server of its host if there is one
+pub(crate) async fn send(request: reqwest::RequestBuilder) -> reqwest::Result<reqwest::Response> {
+    let (client, request) = request.build_split();
+    let request = request?;
+    match server_for(request.url().as_str()) {
+        Some(server) => Ok(server.respond(&request)),
+        None => client.execute(request).await,
+    }
+}
+
+/// Pause the clock
+///
+/// # Returns
+/// An error unless the library was initialized with
+/// `communicator_init_host_driven()`
+pub fn enable() -> Result<()> {
+    runtime::pause_clock()
+}
+
+/// Advance the paused clock, running everything due meanwhile in order
+pub fn advance(duration: Duration) -> Result<()> {
+    if !runtime::is_clock_paused() {
+        return Err(Error::new(
+            ErrorCode::InvalidState,
+            "The simulation clock is not enabled",
+        ));
+    }
+    runtime::block_on(tokio::time::sleep(duration));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platforms::mattermost::websocket::{WebSocketConfig, WebSocketManager};
+    use crate::platforms::PlatformEvent;
+
+    fn posted(id: &str) -> Value {
+        let post = serde_json::json!({
+            "id": id,
+            "channel_id": "c1",
+            "user_id": "u1",
+            "message": "hi",
+            "create_at": 0,
+            "edit_at": 0,
+            "update_at": 0,
+            "delete_at": 0,
+        });
+        serde_json::json!({
+            "event": "posted",
+            "data": { "post": post.to_string() },
+            "broadcast": { "channel_id": "c1" },
+        })
+    }
+
+    async fn drain(ws: &WebSocketManager) -> Vec<String> {
+        let mut ids = Vec::new();
+        while let Some(event) = ws.poll_event().await {
+            if let PlatformEvent::MessagePosted(message) = event {
+                ids.push(message.id);
+            }
+        }
+        ids
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_outage_and_backlog_on_reconnect() {
+        let server = add_server("outage.sim");
+        let config = WebSocketConfig {
+            initial_reconnect_delay_ms: 1000,
+            max_reconnect_delay_ms: 8000,
+            ..Default::default()
+        };
+        let mut ws = WebSocketManager::with_config("http://outage.sim", "token".into(), config);
+        ws.connect().await.unwrap();
+
+        server.push_event(posted("p0"));
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(drain(&ws).await, ["p0"]);
+
+        // Down for 30 seconds, while 100 messages are posted
+        server.set_reachable(false);
+        for i in 1..=100 {
+            server.push_event(posted(&format!("p{i}")));
+        }
+        tokio::time::sleep(Duration::from_secs(30)).await;
+        assert_eq!(server.connections(), 1);
+        assert!(drain(&ws).await.is_empty());
+
+        // The next backoff attempt (at most 8s later) gets the backlog
+        server.set_reachable(true);
+        tokio::time::sleep(Duration::from_secs(9)).await;
+        assert_eq!(server.connections(), 2);
+        assert_eq!(ws.reconnect_count(), 1);
+        let ids = drain(&ws).await;
+        assert_eq!(ids.len(), 100);
+        assert_eq!(ids.first().map(String::as_str), Some("p1"));
+        assert_eq!(ids.last().map(String::as_str), Some("p100"));
+
+        ws.disconnect().await;
+        remove_server("outage.sim");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_http_routes() {
+        let server = add_server("http.sim");
+        server.set_route("GET", "/users/me/teams", 200, "[]");
+        let client = reqwest::Client::new();
+
+        let login = send(client.post("http://http.sim/api/v4/users/login"))
+            .await
+            .unwrap();
+        assert_eq!(login.headers()["Token"], TOKEN);
+        let teams = send(client.get("http://http.sim/api/v4/users/me/teams"))
+            .await
+            .unwrap();
+        assert_eq!(teams.text().await.unwrap(), "[]");
+        let missing = send(client.get("http://http.sim/api/v4/nothing"))
+            .await
+            .unwrap();
+        assert_eq!(missing.status(), 404);
+
+        server.set_reachable(false);
+        let down = send(client.get("http://http.sim/api/v4/users/me"))
+            .await
+            .unwrap();
+        assert_eq!(down.status(), 502);
+        remove_server("http.sim");
+    }
+}
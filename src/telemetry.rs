@@ -0,0 +1,291 @@
+//! Tracing events delivered to log callbacks
+//!
+//! The library reports what it does (HTTP requests, WebSocket connections and
+//! reconnections, connecting to a platform) as `tracing` events within spans,
+//! so Rust programs see them in their own subscriber. Behind the C API, a
+//! dispatcher passes each event to the log callbacks of all contexts whose log
+//! level it reaches. Plain log callbacks get the message followed by its
+//! fields:
+//!
+//! ```text
+//! Request failed method=GET server=https://chat.example.com status=502
+//! ```
+//!
+//! and structured log callbacks a JSON object:
+//!
+//! ```json
+//! {"target": "communicator::platforms::mattermost::client",
+//!  "message": "Request failed",
+//!  "fields": {"server": "https://chat.example.com", "method": "GET", "status": 502},
+//!  "spans": ["connect", "request"]}
+//! ```
+//!
+//! `fields` holds the event's fields after those of the spans it happened in.
+//!
+//! The dispatcher is not installed as the global default, which would fail or
+//! take over when the host program has a `tracing` subscriber of its own.
+//! Instead the runtime makes it the default on the library's own threads and
+//! while a call blocks on the runtime. On a shared runtime
+//! (`RuntimeOptions::shared`), background tasks run on the host's threads, so
+//! their events go to the host's subscriber instead.
+
+use std::cell::RefCell;
+use std::sync::OnceLock;
+
+use serde_json::{Map, Value};
+use tracing::dispatcher::DefaultGuard;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Dispatch, Event, Level, Metadata, Subscriber};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Registry;
+
+use crate::context::{self, LogLevel};
+
+/// The dispatcher passing events to the log callbacks
+pub(crate) fn dispatch() -> &'static Dispatch {
+    static DISPATCH: OnceLock<Dispatch> = OnceLock::new();
+    DISPATCH.get_or_init(|| Dispatch::new(Registry::default().with(CallbackLayer)))
+}
+
+/// Make the dispatcher the default on the current thread until it exits
+pub(crate) fn enter_thread() {
+    thread_local! {
+        static GUARD: RefCell<Option<DefaultGuard>> = const { RefCell::new(None) };
+    }
+    GUARD.with(|guard| {
+        guard
+            .borrow_mut()
+            .get_or_insert_with(|| tracing::dispatcher::set_default(dispatch()));
+    });
+}
+
+/// Run a function with the dispatcher as the default
+pub(crate) fn scoped<T>(f: impl FnOnce() -> T) -> T {
+    tracing::dispatcher::with_default(dispatch(), f)
+}
+
+/// Make the subscriber ask again which events are wanted, after the log
+/// levels changed
+pub(crate) fn levels_changed() {
+    tracing::callsite::rebuild_interest_cache();
+}
+
+/// A log record as passed to the log callbacks
+pub(crate) struct LogRecord<'a> {
+    pub(crate) target: &'a str,
+    pub(crate) message: &'a str,
+    pub(crate) fields: Map<String, Value>,
+    pub(crate) spans: Vec<&'a str>,
+}
+
+impl LogRecord<'_> {
+    /// The record as JSON, for structured log callbacks
+    pub(crate) fn to_json(&self) -> String {
+        serde_json::json!({
+            "target": self.target,
+            "message": self.message,
+            "fields": self.fields,
+            "spans": self.spans,
+        })
+        .to_string()
+    }
+
+    /// The record as text, for plain log callbacks: the message followed by
+    /// its fields as `name=value`
+    pub(crate) fn to_text(&self) -> String {
+        let mut text = self.message.to_string();
+        for (name, value) in &self.fields {
+            let value = match value {
+                Value::String(value) => value.clone(),
+                value => value.to_string(),
+            };
+            text.push_str(&format!(" {name}={value}"));
+        }
+        text
+    }
+}
+
+fn log_level(level: &Level) -> LogLevel {
+    match *level {
+        Level::ERROR => LogLevel::Error,
+        Level::WARN => LogLevel::Warning,
+        Level::INFO => LogLevel::Info,
+        _ => LogLevel::Debug,
+    }
+}
+
+/// Fields of an event or span as JSON values
+#[derive(Default)]
+struct JsonFields(Map<String, Value>);
+
+impl Visit for JsonFields {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), Value::from(format!("{value:?}")));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_error(&mut self, field: &Field, value: &(dyn std::error::Error + 'static)) {
+        self.0
+            .insert(field.name().to_string(), Value::from(value.to_string()));
+    }
+}
+
+/// Passes events to the log callbacks
+struct CallbackLayer;
+
+impl<S> Layer<S> for CallbackLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn enabled(&self, metadata: &Metadata<'_>, _ctx: Context<'_, S>) -> bool {
+        context::wants(log_level(metadata.level()))
+    }
+
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut fields = JsonFields::default();
+        attrs.record(&mut fields);
+        span.extensions_mut().insert(fields);
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+        if let Some(fields) = extensions.get_mut::<JsonFields>() {
+            values.record(fields);
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut fields = Map::new();
+        let mut spans = Vec::new();
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                spans.push(span.name());
+                if let Some(span_fields) = span.extensions().get::<JsonFields>() {
+                    fields.extend(span_fields.0.clone());
+                }
+            }
+        }
+
+        let mut event_fields = JsonFields::default();
+        event.record(&mut event_fields);
+        let message = match event_fields.0.remove("message") {
+            Some(Value::String(message)) => message,
+            Some(other) => other.to_string(),
+            None => String::new(),
+        };
+        fields.extend(event_fields.0);
+
+        let metadata = event.metadata();
+        let record = LogRecord {
+            target: metadata.target(),
+            message: &message,
+            fields,
+            spans,
+        };
+        context::log_all(log_level(metadata.level()), &record);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::Context as LogContext;
+    use std::ffi::CStr;
+    use std::os::raw::{c_char, c_void};
+    use std::sync::Mutex;
+
+    static RECEIVED: Mutex<Vec<(LogLevel, Value)>> = Mutex::new(Vec::new());
+    static RECEIVED_TEXT: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    extern "C" fn collect(level: LogLevel, message: *const c_char, _user_data: *mut c_void) {
+        let message = unsafe { CStr::from_ptr(message) }.to_str().unwrap();
+        let record: Value = serde_json::from_str(message).unwrap();
+        // Other tests may log meanwhile
+        if record["target"] == module_path!() && record["fields"]["test"].is_null() {
+            RECEIVED.lock().unwrap().push((level, record));
+        }
+    }
+
+    extern "C" fn collect_text(_level: LogLevel, message: *const c_char, _user_data: *mut c_void) {
+        let message = unsafe { CStr::from_ptr(message) }.to_str().unwrap();
+        // Other tests may log meanwhile
+        if message.contains("test=plain") {
+            RECEIVED_TEXT.lock().unwrap().push(message.to_string());
+        }
+    }
+
+    #[test]
+    fn test_plain_log_callbacks_get_text() {
+        let mut ctx = LogContext::new("telemetry-text");
+        ctx.set_log_callback(collect_text, std::ptr::null_mut())
+            .wait();
+
+        scoped(|| {
+            tracing::info_span!("connect", test = "plain").in_scope(|| {
+                tracing::warn!(status = 502u64, "Request failed");
+            });
+        });
+
+        assert_eq!(
+            *RECEIVED_TEXT.lock().unwrap(),
+            vec!["Request failed status=502 test=plain".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_events_reach_log_callbacks() {
+        let mut ctx = LogContext::new("telemetry");
+        ctx.set_structured_log_callback(collect, std::ptr::null_mut())
+            .wait();
+
+        scoped(|| {
+            tracing::info_span!("connect", server = "https://chat.example.com").in_scope(|| {
+                tracing::warn!(status = 502u64, "Request failed");
+                tracing::debug!("Below the context's level");
+            });
+        });
+        ctx.set_log_level(LogLevel::Debug);
+        scoped(|| tracing::debug!(attempt = 2u64, "Now wanted"));
+        // Outside the library's threads and calls, events go to the host
+        tracing::warn!("Not for the callbacks");
+
+        let received = std::mem::take(&mut *RECEIVED.lock().unwrap());
+        assert_eq!(received.len(), 2);
+        let (level, record) = &received[0];
+        assert_eq!(*level, LogLevel::Warning);
+        assert_eq!(record["message"], "Request failed");
+        assert_eq!(record["fields"]["server"], "https://chat.example.com");
+        assert_eq!(record["fields"]["status"], 502);
+        assert_eq!(record["spans"], serde_json::json!(["connect"]));
+        assert_eq!(received[1].0, LogLevel::Debug);
+        assert_eq!(received[1].1["fields"]["attempt"], 2);
+    }
+}
@@ -0,0 +1,204 @@
+//! TLS configuration for self-hosted servers
+//!
+//! Servers behind an internal CA, with self-signed certificates or requiring
+//! client certificates are reached with a [`TlsConfig`]. It is turned into one
+//! rustls configuration shared by HTTP requests and the WebSocket connection,
+//! so both trust the same certificates.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tokio_rustls::rustls::client::danger::{
+    HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier,
+};
+use tokio_rustls::rustls::crypto::{ring, CryptoProvider};
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use tokio_rustls::rustls::{ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme};
+
+use crate::error::{Error, Result};
+
+/// TLS settings for connections to a server
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TlsConfig {
+    /// PEM file of CA certificates to trust besides the built-in roots
+    #[serde(default)]
+    pub ca_bundle: Option<String>,
+    /// Accept any server certificate, e.g. a self-signed one
+    ///
+    /// Connections are still encrypted but no longer authenticated, so only
+    /// use this for testing.
+    #[serde(default)]
+    pub accept_invalid_certs: bool,
+    /// PEM file of the client certificate chain, for servers requiring one
+    #[serde(default)]
+    pub client_cert: Option<String>,
+    /// PEM file of the client certificate's private key
+    #[serde(default)]
+    pub client_key: Option<String>,
+}
+
+impl TlsConfig {
+    /// Whether the built-in defaults are used unchanged
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Build the rustls configuration
+    ///
+    /// # Returns
+    /// An error if a file can't be read or holds no certificate or key, or if
+    /// only one of `client_cert` and `client_key` is set
+    pub fn client_config(&self) -> Result<Arc<ClientConfig>> {
+        let mut roots = RootCertStore {
+            roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+        };
+        if let Some(path) = &self.ca_bundle {
+            let certs = read_certs(path)?;
+            let (added, _) = roots.add_parsable_certificates(certs);
+            if added == 0 {
+                return Err(Error::invalid_argument(format!(
+                    "No usable CA certificate in {path}"
+                )));
+            }
+        }
+
+        let provider = Arc::new(ring::default_provider());
+        let builder = ClientConfig::builder_with_provider(Arc::clone(&provider))
+            .with_safe_default_protocol_versions()
+            .map_err(|e| Error::invalid_argument(format!("Failed to configure TLS: {e}")))?
+            .with_root_certificates(roots);
+        let mut config = match (&self.client_cert, &self.client_key) {
+            (Some(cert), Some(key)) => builder
+                .with_client_auth_cert(read_certs(cert)?, read_key(key)?)
+                .map_err(|e| Error::invalid_argument(format!("Invalid client certificate: {e}")))?,
+            (None, None) => builder.with_no_client_auth(),
+            _ => {
+                return Err(Error::invalid_argument(
+                    "client_cert and client_key must be set together",
+                ))
+            }
+        };
+        if self.accept_invalid_certs {
+            config
+                .dangerous()
+                .set_certificate_verifier(Arc::new(AcceptAnyCert(provider)));
+        }
+        Ok(Arc::new(config))
+    }
+}
+
+fn read_pem(path: &str) -> Result<Vec<u8>> {
+    std::fs::read(Path::new(path))
+        .map_err(|e| Error::invalid_argument(format!("Failed to read {path}: {e}")))
+}
+
+fn read_certs(path: &str) -> Result<Vec<CertificateDer<'static>>> {
+    let pem = read_pem(path)?;
+    let certs = CertificateDer::pem_slice_iter(&pem)
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| Error::invalid_argument(format!("Invalid certificate in {path}: {e}")))?;
+    if certs.is_empty() {
+        return Err(Error::invalid_argument(format!("No certificate in {path}")));
+    }
+    Ok(certs)
+}
+
+fn read_key(path: &str) -> Result<PrivateKeyDer<'static>> {
+    PrivateKeyDer::from_pem_slice(&read_pem(path)?)
+        .map_err(|e| Error::invalid_argument(format!("No private key in {path}: {e}")))
+}
+
+/// Verifier accepting any server certificate, still checking that handshake
+/// signatures match it
+#[derive(Debug)]
+struct AcceptAnyCert(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for AcceptAnyCert {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, tokio_rustls::rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, tokio_rustls::rustls::Error> {
+        tokio_rustls::rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, tokio_rustls::rustls::Error> {
+        tokio_rustls::rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_config() {
+        assert!(TlsConfig::default().is_default());
+        assert!(TlsConfig::default().client_config().is_ok());
+
+        let insecure = TlsConfig {
+            accept_invalid_certs: true,
+            ..Default::default()
+        };
+        assert!(insecure.client_config().is_ok());
+
+        let missing = TlsConfig {
+            ca_bundle: Some("/nonexistent/ca.pem".to_string()),
+            ..Default::default()
+        };
+        assert!(missing.client_config().is_err());
+
+        let half = TlsConfig {
+            client_key: Some("/nonexistent/key.pem".to_string()),
+            ..Default::default()
+        };
+        assert!(half.client_config().is_err());
+    }
+
+    #[test]
+    fn test_ca_bundle_without_certificates() {
+        let path =
+            std::env::temp_dir().join(format!("libcommunicator-tls-{}.pem", std::process::id()));
+        std::fs::write(&path, "not a certificate").unwrap();
+        let config = TlsConfig {
+            ca_bundle: Some(path.display().to_string()),
+            ..Default::default()
+        };
+        let error = config.client_config().unwrap_err();
+        let _ = std::fs::remove_file(&path);
+        assert!(error.message.contains("No certificate"));
+    }
+}
@@ -0,0 +1,91 @@
+//! Caching of platform entities
+//!
+//! Platforms may cache entities such as users, channels and teams that
+//! rarely change, so they are not fetched for every message. Caches are kept
+//! up to date by real-time events; the TTL bounds how long an entry is trusted
+//! when an event is missed.
+
+use serde::{Deserialize, Serialize};
+
+/// How a platform caches entities
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// Whether entities are cached at all
+    pub enabled: bool,
+    /// Time-to-live of users and profile images, in seconds
+    pub user_ttl_secs: u64,
+    /// Time-to-live of channels and channel permissions, in seconds
+    pub channel_ttl_secs: u64,
+    /// Time-to-live of teams and roles, in seconds
+    pub team_ttl_secs: u64,
+    /// Time-to-live of custom emojis and their images, in seconds
+    pub emoji_ttl_secs: u64,
+    /// Maximum number of entries of each cache; None for no limit
+    pub max_entries: Option<usize>,
+    /// File the cached users, channels and teams are saved to when
+    /// disconnecting and loaded from after the next login, revalidated by
+    /// their last update; None keeps them in memory only
+    pub path: Option<String>,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            user_ttl_secs: 300,
+            channel_ttl_secs: 120,
+            team_ttl_secs: 600,
+            emoji_ttl_secs: 3600,
+            max_entries: None,
+            path: None,
+        }
+    }
+}
+
+impl CacheConfig {
+    /// A configuration with caching disabled
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::default()
+        }
+    }
+}
+
+/// Usage statistics of one cache
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheStats {
+    /// What the cache holds, e.g. "users" or "channels"
+    pub name: String,
+    /// Entries currently stored, including expired ones
+    pub entries: usize,
+    /// Stored entries that have expired
+    pub expired: usize,
+    /// Maximum number of entries, if limited
+    pub max_entries: Option<usize>,
+    /// Time-to-live of new entries, in seconds
+    pub ttl_secs: u64,
+    /// Lookups answered from the cache so far
+    pub hits: u64,
+    /// Lookups of missing or expired entries so far
+    pub misses: u64,
+    /// Entries removed to make room for new ones so far
+    pub evictions: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_config_defaults() {
+        let config: CacheConfig =
+            serde_json::from_str(r#"{"user_ttl_secs":60,"max_entries":500}"#).unwrap();
+        assert!(config.enabled);
+        assert_eq!(config.user_ttl_secs, 60);
+        assert_eq!(config.channel_ttl_secs, 120);
+        assert_eq!(config.max_entries, Some(500));
+        assert!(!CacheConfig::disabled().enabled);
+    }
+}
@@ -5,6 +5,54 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::error::{Error, ErrorCode, Result};
+
+/// Server-imposed limits
+///
+/// Limits are None when the platform does not report them. Use the `check_*`
+/// methods to validate content locally before sending it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlatformLimits {
+    /// Maximum message length in characters
+    pub max_message_length: Option<usize>,
+    /// Maximum size of an uploaded file in bytes
+    pub max_file_size: Option<u64>,
+    /// Maximum number of API requests per second
+    pub max_requests_per_second: Option<u32>,
+}
+
+impl PlatformLimits {
+    /// Check a message against the maximum length
+    ///
+    /// # Returns
+    /// A MessageTooLong error carrying the limit if the message is too long
+    pub fn check_message(&self, text: &str) -> Result<()> {
+        match self.max_message_length {
+            Some(max) if text.chars().count() > max => Err(Error::new(
+                ErrorCode::MessageTooLong,
+                format!("Message exceeds the maximum length of {max} characters"),
+            )
+            .with_limit(max as u64)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check a file size against the maximum upload size
+    ///
+    /// # Returns
+    /// A FileTooLarge error carrying the limit if the file is too large
+    pub fn check_file_size(&self, size: u64) -> Result<()> {
+        match self.max_file_size {
+            Some(max) if size > max => Err(Error::new(
+                ErrorCode::FileTooLarge,
+                format!("File exceeds the maximum upload size of {max} bytes"),
+            )
+            .with_limit(max)),
+            _ => Ok(()),
+        }
+    }
+}
+
 /// Platform capabilities and feature flags
 ///
 /// This struct describes what features a particular platform implementation supports.
@@ -40,6 +88,10 @@ pub struct PlatformCapabilities {
     /// Does the platform support rich text/markdown?
     pub supports_rich_text: bool,
 
+    /// Can messages be posted with an overridden sender name/avatar
+    /// (e.g., integrations and bridge bots representing remote users)?
+    pub supports_sender_overrides: bool,
+
     // Status and presence
     /// Does the platform support basic user status (online/away/dnd/offline)?
     pub supports_status: bool,
@@ -76,6 +128,11 @@ pub struct PlatformCapabilities {
 
     /// Can users load message history?
     pub supports_message_history: bool,
+
+    // Limits
+    /// Server-imposed limits (message length, file size, request rate)
+    #[serde(default)]
+    pub limits: PlatformLimits,
 }
 
 impl PlatformCapabilities {
@@ -91,6 +148,7 @@ impl PlatformCapabilities {
             supports_reactions: false,
             supports_file_attachments: false,
             supports_rich_text: false,
+            supports_sender_overrides: false,
             supports_status: false,
             supports_custom_status: false,
             supports_typing_indicators: false,
@@ -102,6 +160,7 @@ impl PlatformCapabilities {
             supports_webhooks: false,
             supports_search: false,
             supports_message_history: false,
+            limits: PlatformLimits::default(),
         }
     }
 
@@ -153,6 +212,12 @@ impl PlatformCapabilities {
         self
     }
 
+    /// Enable sender name/avatar overrides
+    pub fn with_sender_overrides(mut self) -> Self {
+        self.supports_sender_overrides = true;
+        self
+    }
+
     /// Enable basic status support
     pub fn with_status(mut self) -> Self {
         self.supports_status = true;
@@ -218,6 +283,12 @@ impl PlatformCapabilities {
         self.supports_message_history = true;
         self
     }
+
+    /// Set server limits
+    pub fn with_limits(mut self, limits: PlatformLimits) -> Self {
+        self.limits = limits;
+        self
+    }
 }
 
 /// Preset capabilities for common platforms
@@ -233,6 +304,7 @@ impl PlatformCapabilities {
             .with_reactions()
             .with_file_attachments()
             .with_rich_text()
+            .with_sender_overrides()
             .with_status()
             .with_custom_status()
             .with_typing_indicators()
@@ -246,6 +318,37 @@ impl PlatformCapabilities {
             .with_message_history()
     }
 
+    /// Create capabilities for Matrix
+    pub fn matrix() -> Self {
+        PlatformCapabilities::new("matrix")
+            .with_version("v3")
+            .with_threads()
+            .with_status()
+            .with_custom_status()
+            .with_typing_indicators()
+            .with_public_channels()
+            .with_private_channels()
+            .with_direct_messages()
+            .with_group_messages()
+            .with_realtime_events()
+            .with_message_history()
+    }
+
+    /// Create capabilities for XMPP
+    ///
+    /// Message history depends on the server's archive support (XEP-0313);
+    /// the XMPP platform adds it after connecting if the server advertises it.
+    pub fn xmpp() -> Self {
+        PlatformCapabilities::new("xmpp")
+            .with_status()
+            .with_custom_status()
+            .with_typing_indicators()
+            .with_public_channels()
+            .with_private_channels()
+            .with_direct_messages()
+            .with_realtime_events()
+    }
+
     /// Create capabilities for Slack
     pub fn slack() -> Self {
         PlatformCapabilities::new("slack")
@@ -322,6 +425,25 @@ mod tests {
         assert!(caps.has_workspaces);
         assert!(caps.has_threads);
         assert!(caps.supports_custom_status);
+        assert!(caps.supports_sender_overrides);
+    }
+
+    #[test]
+    fn test_matrix_preset() {
+        let caps = PlatformCapabilities::matrix();
+        assert_eq!(caps.platform_name, "matrix");
+        assert!(!caps.has_workspaces);
+        assert!(caps.supports_direct_messages);
+        assert!(caps.supports_realtime_events);
+    }
+
+    #[test]
+    fn test_xmpp_preset() {
+        let caps = PlatformCapabilities::xmpp();
+        assert_eq!(caps.platform_name, "xmpp");
+        assert!(!caps.has_workspaces);
+        assert!(caps.supports_public_channels);
+        assert!(!caps.supports_message_history);
     }
 
     #[test]
@@ -339,4 +461,28 @@ mod tests {
         assert!(caps.has_workspaces); // Discord guilds
         assert!(caps.supports_typing_indicators);
     }
+
+    #[test]
+    fn test_limit_checks() {
+        let limits = PlatformLimits {
+            max_message_length: Some(5),
+            max_file_size: Some(100),
+            max_requests_per_second: None,
+        };
+
+        assert!(limits.check_message("héllo").is_ok());
+        let err = limits.check_message("hello!").unwrap_err();
+        assert_eq!(err.code, ErrorCode::MessageTooLong);
+        assert_eq!(err.limit(), Some(5));
+
+        assert!(limits.check_file_size(100).is_ok());
+        let err = limits.check_file_size(101).unwrap_err();
+        assert_eq!(err.code, ErrorCode::FileTooLarge);
+        assert_eq!(err.limit(), Some(100));
+
+        // Unknown limits never fail
+        assert!(PlatformLimits::default()
+            .check_message(&"x".repeat(100_000))
+            .is_ok());
+    }
 }
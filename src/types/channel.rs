@@ -27,10 +27,32 @@ pub struct Channel {
     pub last_activity_at: Option<DateTime<Utc>>,
     /// Whether the channel is archived
     pub is_archived: bool,
+    /// Whether the channel is shared with other servers/organizations (federation)
+    #[serde(default)]
+    pub is_shared: bool,
+    /// What the current user may do in the channel (None if not known)
+    #[serde(default)]
+    pub permissions: Option<ChannelPermissions>,
     /// Optional metadata (platform-specific)
     pub metadata: Option<serde_json::Value>,
 }
 
+/// Actions the current user is allowed to perform in a channel
+///
+/// Computed from the user's roles, so clients can enable or disable UI
+/// elements without asking the server per channel.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelPermissions {
+    /// Whether the user can post messages
+    pub can_post: bool,
+    /// Whether the user can add reactions
+    pub can_react: bool,
+    /// Whether the user can upload files
+    pub can_upload: bool,
+    /// Whether the user can add and remove channel members
+    pub can_manage_members: bool,
+}
+
 /// Type of channel
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -64,6 +86,8 @@ impl Channel {
             created_at: Utc::now(),
             last_activity_at: None,
             is_archived: false,
+            is_shared: false,
+            permissions: None,
             metadata: None,
         }
     }
@@ -98,6 +122,18 @@ impl Channel {
         self
     }
 
+    /// Mark as shared with other servers/organizations
+    pub fn shared(mut self) -> Self {
+        self.is_shared = true;
+        self
+    }
+
+    /// Set the current user's permissions
+    pub fn with_permissions(mut self, permissions: ChannelPermissions) -> Self {
+        self.permissions = Some(permissions);
+        self
+    }
+
     /// Set metadata
     pub fn with_metadata(mut self, metadata: serde_json::Value) -> Self {
         self.metadata = Some(metadata);
@@ -165,6 +201,58 @@ impl ChannelUnread {
     }
 }
 
+/// A channel member's roles in the channel
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelMemberRoles {
+    /// Channel ID
+    pub channel_id: String,
+    /// User ID of the member
+    pub user_id: String,
+    /// Whether the member is a channel admin (moderator)
+    pub is_admin: bool,
+    /// Platform-specific role names held in the channel
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+/// Hourly message counts of a channel, for activity sparklines and heatmaps
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelActivity {
+    /// Channel ID
+    pub channel_id: String,
+    /// Start of the first hour (milliseconds since epoch, aligned to the hour)
+    pub start: i64,
+    /// Number of messages posted in each hour, oldest first
+    pub hourly_counts: Vec<u32>,
+}
+
+impl ChannelActivity {
+    /// Length of one bucket in milliseconds
+    pub const HOUR_MS: i64 = 60 * 60 * 1000;
+
+    /// Create activity with no messages for the given number of hours
+    pub fn new(channel_id: impl Into<String>, start: i64, hours: usize) -> Self {
+        ChannelActivity {
+            channel_id: channel_id.into(),
+            start,
+            hourly_counts: vec![0; hours],
+        }
+    }
+
+    /// Total number of messages in the period
+    pub fn total(&self) -> u64 {
+        self.hourly_counts.iter().map(|&c| u64::from(c)).sum()
+    }
+
+    /// Message counts summed per 24 hours, oldest first
+    pub fn daily_counts(&self) -> Vec<u32> {
+        self.hourly_counts
+            .chunks(24)
+            .map(|day| day.iter().sum())
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -225,6 +313,18 @@ mod tests {
         assert!(channel.is_archived);
     }
 
+    #[test]
+    fn test_shared_channel() {
+        let channel = Channel::new("ch-1", "partners", "Partners", ChannelType::Public);
+        assert!(!channel.is_shared);
+
+        let channel = channel.shared();
+        assert!(channel.is_shared);
+
+        let json = serde_json::to_string(&channel).unwrap();
+        assert!(json.contains(r#""is_shared":true"#));
+    }
+
     #[test]
     fn test_channel_json_serialization() {
         let channel = Channel::new("ch-1", "general", "General", ChannelType::Public);
@@ -256,4 +356,15 @@ mod tests {
         assert_eq!(channel.id, "ch-123");
         assert_eq!(channel.channel_type, ChannelType::Private);
     }
+
+    #[test]
+    fn test_channel_activity_totals() {
+        let mut activity = ChannelActivity::new("ch-1", 0, 48);
+        activity.hourly_counts[0] = 2;
+        activity.hourly_counts[23] = 3;
+        activity.hourly_counts[30] = 1;
+
+        assert_eq!(activity.total(), 6);
+        assert_eq!(activity.daily_counts(), vec![5, 1]);
+    }
 }
@@ -0,0 +1,77 @@
+//! Slash command types
+
+use serde::{Deserialize, Serialize};
+
+/// A slash command users can run, as listed for autocomplete
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlashCommand {
+    /// The word after the slash that runs the command, e.g. "away"
+    pub trigger: String,
+    /// What the command does
+    #[serde(default)]
+    pub description: String,
+    /// Hint for the arguments, e.g. "[message]"
+    #[serde(default)]
+    pub hint: String,
+    /// Name of the integration or plugin providing the command, if any
+    #[serde(default)]
+    pub display_name: String,
+}
+
+impl SlashCommand {
+    /// The command as typed, with its leading slash
+    pub fn command(&self) -> String {
+        format!("/{}", self.trigger)
+    }
+}
+
+/// Who sees the response of a slash command
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandResponseType {
+    /// Only the user who ran the command
+    #[default]
+    Ephemeral,
+    /// Everyone in the channel
+    InChannel,
+}
+
+/// The result of running a slash command
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CommandResponse {
+    /// Who sees the response
+    pub response_type: CommandResponseType,
+    /// Response text; often empty when the command posts its own messages
+    #[serde(default)]
+    pub text: String,
+    /// A location the client should navigate to, e.g. a channel link
+    #[serde(default)]
+    pub goto_location: Option<String>,
+    /// Platform-specific response properties
+    #[serde(default)]
+    pub props: Option<serde_json::Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_response_serialization() {
+        let response = CommandResponse {
+            response_type: CommandResponseType::InChannel,
+            text: "done".to_string(),
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["response_type"], "in_channel");
+
+        let command = SlashCommand {
+            trigger: "away".to_string(),
+            description: String::new(),
+            hint: String::new(),
+            display_name: String::new(),
+        };
+        assert_eq!(command.command(), "/away");
+    }
+}
@@ -24,6 +24,10 @@ pub struct ConnectionInfo {
     pub team_name: Option<String>,
     /// Optional metadata (platform-specific)
     pub metadata: Option<serde_json::Value>,
+    /// Whether the info was restored from a previous session while the
+    /// connection is still being established
+    #[serde(default)]
+    pub stale: bool,
 }
 
 /// Connection state
@@ -46,6 +50,150 @@ pub enum ConnectionState {
     Reconnecting,
 }
 
+/// State of the circuit breaker guarding requests to a server
+///
+/// The circuit opens after repeated server errors or timeouts. While it is
+/// open, requests fail fast with `ErrorCode::ServiceUnavailable` instead of
+/// reaching the server; after a cooldown a single probe request is let through
+/// (half-open) and its outcome decides whether the circuit closes again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Requests are sent normally
+    #[default]
+    Closed,
+    /// The server is considered unhealthy; requests fail fast
+    Open,
+    /// A probe request is testing whether the server has recovered
+    HalfOpen,
+}
+
+/// Limits protecting a server from being overloaded by the client
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CircuitBreakerConfig {
+    /// Maximum number of requests in flight at once; further requests wait
+    pub max_concurrent_requests: usize,
+    /// Consecutive server errors or timeouts that open the circuit
+    pub failure_threshold: u32,
+    /// How long the circuit stays open before a probe request, in milliseconds
+    pub cooldown_ms: u64,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_requests: 8,
+            failure_threshold: 5,
+            cooldown_ms: 30_000,
+        }
+    }
+}
+
+/// Retries of requests failing with transient errors
+///
+/// Only GET requests are retried unless `retry_posts` is set; other requests
+/// may have taken effect on the server even if their response was lost.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// Attempts per request including the first; 1 disables retries
+    pub max_attempts: u32,
+    /// Wait before the first retry, in milliseconds
+    pub initial_backoff_ms: u64,
+    /// Longest wait between attempts, in milliseconds
+    pub max_backoff_ms: u64,
+    /// Factor the wait grows by after each retry
+    pub backoff_multiplier: f64,
+    /// HTTP statuses to retry; requests that could not be sent at all are
+    /// always retried
+    pub retry_on: Vec<u16>,
+    /// Retry POST requests too, which the server may then apply twice
+    pub retry_posts: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff_ms: 250,
+            max_backoff_ms: 5_000,
+            backoff_multiplier: 2.0,
+            retry_on: vec![502, 503, 504],
+            retry_posts: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy sending every request once
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Whether requests of an HTTP method may be retried
+    pub fn retries_method(&self, method: &str) -> bool {
+        match method {
+            "GET" => true,
+            "POST" => self.retry_posts,
+            _ => false,
+        }
+    }
+
+    /// Wait before a retry
+    ///
+    /// # Arguments
+    /// * `retry` - Retries of the request so far
+    pub fn backoff(&self, retry: u32) -> std::time::Duration {
+        let multiplier = self.backoff_multiplier.max(1.0).powi(retry.min(64) as i32);
+        let backoff_ms =
+            (self.initial_backoff_ms as f64 * multiplier).min(self.max_backoff_ms as f64);
+        std::time::Duration::from_millis(backoff_ms as u64)
+    }
+}
+
+/// Requests waiting for the server's rate limit, and how the limit was met
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RequestStats {
+    /// Requests queued until the rate limit resets
+    pub queued: usize,
+    /// Requests that had to wait for the rate limit so far
+    pub delayed: u64,
+    /// Responses with 429 Too Many Requests so far
+    pub rate_limited: u64,
+    /// Requests sent again after a 429 response
+    pub retried: u64,
+    /// Requests per second the server allows, if it reports a limit
+    pub limit: Option<u32>,
+    /// Requests left in the current window, if known
+    pub remaining: Option<u32>,
+    /// Milliseconds until the window resets, if known
+    pub reset_in_ms: Option<u64>,
+}
+
+/// Health of the real-time connection, for "connecting…" states and diagnostics
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConnectionHealth {
+    /// State of the connection (disconnected if events are not subscribed)
+    pub state: ConnectionState,
+    /// Milliseconds since the server last answered a ping, if it has
+    pub last_pong_ms_ago: Option<u64>,
+    /// Milliseconds since anything was received from the server, if it was
+    pub last_message_ms_ago: Option<u64>,
+    /// Reconnection attempts since the connection was lost (0 while connected)
+    pub reconnect_attempts: u32,
+    /// Times the connection was re-established after being lost
+    pub reconnects: u64,
+    /// Delay before the current reconnection attempt, in milliseconds, while
+    /// reconnecting
+    pub backoff_ms: Option<u64>,
+    /// Events received per second, averaged over the last 10 seconds
+    pub events_per_sec: f64,
+}
+
 impl ConnectionInfo {
     /// Create a new connection info
     pub fn new(
@@ -64,6 +212,7 @@ impl ConnectionInfo {
             team_id: None,
             team_name: None,
             metadata: None,
+            stale: false,
         }
     }
 
@@ -158,4 +307,20 @@ mod tests {
         assert!(info.is_connecting());
         assert!(!info.is_connected());
     }
+
+    #[test]
+    fn test_retry_policy_backoff() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.backoff(0).as_millis(), 250);
+        assert_eq!(policy.backoff(2).as_millis(), 1_000);
+        assert_eq!(policy.backoff(30).as_millis(), 5_000);
+
+        assert!(policy.retries_method("GET"));
+        assert!(!policy.retries_method("POST"));
+        assert!(!policy.retries_method("DELETE"));
+
+        let policy: RetryPolicy = serde_json::from_str(r#"{"retry_posts":true}"#).unwrap();
+        assert!(policy.retries_method("POST"));
+        assert_eq!(policy.max_attempts, 3);
+    }
 }
@@ -0,0 +1,59 @@
+//! Draft types
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// An unsent message being composed in a channel or thread
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Draft {
+    /// The channel the draft belongs to
+    pub channel_id: String,
+    /// The thread the draft replies to, if any
+    #[serde(default)]
+    pub root_id: Option<String>,
+    /// The text composed so far
+    #[serde(default)]
+    pub message: String,
+    /// Files uploaded to the channel for the draft
+    #[serde(default)]
+    pub file_ids: Vec<String>,
+    /// Custom key/value properties of the draft
+    #[serde(default)]
+    pub props: HashMap<String, serde_json::Value>,
+    /// When the draft was last saved
+    #[serde(default = "Utc::now")]
+    pub updated_at: DateTime<Utc>,
+    /// Whether the draft is stored on the server (synced across devices)
+    /// rather than kept by the library
+    #[serde(default)]
+    pub server_side: bool,
+}
+
+impl Draft {
+    /// Create a new draft of a channel message
+    pub fn new(channel_id: impl Into<String>, message: impl Into<String>) -> Self {
+        Draft {
+            channel_id: channel_id.into(),
+            root_id: None,
+            message: message.into(),
+            file_ids: Vec::new(),
+            props: HashMap::new(),
+            updated_at: Utc::now(),
+            server_side: false,
+        }
+    }
+
+    /// Make the draft a reply in a thread
+    pub fn with_root(mut self, root_id: impl Into<String>) -> Self {
+        self.root_id = Some(root_id.into());
+        self
+    }
+
+    /// Check whether this is the draft of the given channel and thread
+    pub fn is_for(&self, channel_id: &str, root_id: Option<&str>) -> bool {
+        self.channel_id == channel_id
+            && self.root_id.as_deref().unwrap_or("") == root_id.unwrap_or("")
+    }
+}
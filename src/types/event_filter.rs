@@ -0,0 +1,99 @@
+//! Filtering of real-time events
+//!
+//! Busy servers send many events most clients ignore, such as typing
+//! indicators and status changes. An event filter drops them as they are
+//! received, before they take up room in the platform's bounded event queue.
+
+use serde::{Deserialize, Serialize};
+
+/// Kinds of real-time events, for filtering
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventCategory {
+    /// Messages posted, edited, deleted or pinned, ephemeral messages and polls
+    Messages,
+    /// Reactions added or removed
+    Reactions,
+    /// Typing indicators
+    Typing,
+    /// User status changes
+    Presence,
+    /// Channels created, changed or deleted, and channel membership changes
+    Channels,
+    /// Channels viewed, and messages or threads marked unread
+    ReadState,
+    /// Thread updates and follow changes
+    Threads,
+    /// Users added or changed
+    Users,
+    /// Teams joined, left, changed or deleted
+    Teams,
+    /// User preferences changed or deleted
+    Preferences,
+    /// Plugins enabled or disabled, and plugin status changes
+    Plugins,
+    /// Server configuration, license, role and custom emoji changes
+    System,
+}
+
+/// Which real-time events a platform delivers
+///
+/// Only events received from the server are filtered; events the library
+/// raises itself (connection, session and outbox events, sequence gaps) are
+/// always delivered.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventFilter {
+    /// Categories of events to drop
+    pub exclude: Vec<EventCategory>,
+    /// Only deliver events of these channels; events not tied to a channel
+    /// are delivered regardless. None for all channels
+    pub channel_ids: Option<Vec<String>>,
+}
+
+impl EventFilter {
+    /// Whether the filter lets every event through
+    pub fn is_empty(&self) -> bool {
+        self.exclude.is_empty() && self.channel_ids.is_none()
+    }
+
+    /// Whether an event passes the filter
+    ///
+    /// # Arguments
+    /// * `category` - The event's category, None if it can't be filtered
+    /// * `channel_id` - The channel the event is about, if any
+    pub fn allows(&self, category: Option<EventCategory>, channel_id: Option<&str>) -> bool {
+        let Some(category) = category else {
+            return true;
+        };
+        if self.exclude.contains(&category) {
+            return false;
+        }
+        match (&self.channel_ids, channel_id) {
+            (Some(channel_ids), Some(channel_id)) => channel_ids.iter().any(|id| id == channel_id),
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_filter() {
+        let filter: EventFilter =
+            serde_json::from_str(r#"{"exclude": ["typing", "presence"], "channel_ids": ["c1"]}"#)
+                .unwrap();
+        assert!(!filter.allows(Some(EventCategory::Typing), Some("c1")));
+        assert!(!filter.allows(Some(EventCategory::Presence), None));
+        assert!(filter.allows(Some(EventCategory::Messages), Some("c1")));
+        assert!(!filter.allows(Some(EventCategory::Messages), Some("c2")));
+        assert!(filter.allows(Some(EventCategory::Teams), None));
+        // Events raised by the library are never filtered
+        assert!(filter.allows(None, Some("c2")));
+
+        assert!(EventFilter::default().is_empty());
+        assert!(serde_json::from_str::<EventFilter>(r#"{"exclude": ["noise"]}"#).is_err());
+    }
+}
@@ -0,0 +1,96 @@
+//! User groups
+//!
+//! Groups are named sets of users, synced from a directory (e.g. LDAP) or
+//! managed by users ("custom" groups). Referenceable groups can be mentioned
+//! as `@name`, which notifies every member.
+
+use serde::{Deserialize, Serialize};
+
+use super::User;
+
+/// A group of users
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Group {
+    /// Unique identifier for the group
+    pub id: String,
+    /// Name used to mention the group (without "@"), if it can be mentioned
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Human-readable display name
+    pub display_name: String,
+    /// Description of the group
+    #[serde(default)]
+    pub description: String,
+    /// Where the group comes from, e.g. "ldap" or "custom"
+    pub source: String,
+    /// Number of members, if known
+    #[serde(default)]
+    pub member_count: Option<u32>,
+}
+
+impl Group {
+    /// The mention for this group (e.g. "@developers"), if it can be mentioned
+    pub fn mention(&self) -> Option<String> {
+        self.name.as_ref().map(|name| format!("@{name}"))
+    }
+}
+
+/// A group mentioned in a message, with its members
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupMention {
+    /// The mentioned group
+    pub group: Group,
+    /// Members of the group
+    pub members: Vec<User>,
+}
+
+/// Extract the names mentioned with "@" in a message
+///
+/// Names are returned lowercased, without "@" and without duplicates, in order
+/// of appearance. Email addresses are not mentions, and trailing punctuation
+/// ("@team." at the end of a sentence) is dropped.
+pub fn mention_names(text: &str) -> Vec<String> {
+    let is_name_char = |c: char| c.is_alphanumeric() || matches!(c, '.' | '_' | '-');
+    let mut names: Vec<String> = Vec::new();
+    let mut previous = None;
+    for (index, c) in text.char_indices() {
+        if c == '@' && !previous.is_some_and(|p: char| p.is_alphanumeric()) {
+            let rest = &text[index + 1..];
+            let end = rest.find(|c| !is_name_char(c)).unwrap_or(rest.len());
+            let name = rest[..end].trim_end_matches(['.', '-', '_']).to_lowercase();
+            if !name.is_empty() && !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        previous = Some(c);
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mention_names() {
+        assert_eq!(
+            mention_names("@Developers and @qa-team, please review. Thanks @developers."),
+            vec!["developers", "qa-team"]
+        );
+        assert!(mention_names("mail me at alice@example.com").is_empty());
+        assert!(mention_names("@ alone").is_empty());
+    }
+
+    #[test]
+    fn test_group_mention() {
+        let group = Group {
+            id: "g1".to_string(),
+            name: Some("developers".to_string()),
+            display_name: "Developers".to_string(),
+            description: String::new(),
+            source: "custom".to_string(),
+            member_count: Some(3),
+        };
+        assert_eq!(group.mention(), Some("@developers".to_string()));
+    }
+}
@@ -0,0 +1,237 @@
+//! Integration types: OAuth 2.0 apps, personal access tokens and bot accounts
+
+use serde::{Deserialize, Serialize};
+
+/// An OAuth 2.0 app registered with the platform
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthApp {
+    /// Unique identifier for the app, also used as the OAuth client ID
+    pub id: String,
+
+    /// Display name of the app
+    pub name: String,
+
+    /// Description of the app
+    #[serde(default)]
+    pub description: String,
+
+    /// URL of the app's homepage
+    #[serde(default)]
+    pub homepage: String,
+
+    /// URL of the app's icon, if any
+    #[serde(default)]
+    pub icon_url: Option<String>,
+
+    /// URLs the authorization server may redirect to
+    #[serde(default)]
+    pub callback_urls: Vec<String>,
+
+    /// Client secret; only returned when the app is registered
+    #[serde(default)]
+    pub client_secret: Option<String>,
+
+    /// Whether users are authorized without being asked for consent
+    #[serde(default)]
+    pub is_trusted: bool,
+
+    /// User ID of the person who registered the app
+    #[serde(default)]
+    pub creator_id: String,
+
+    /// Timestamp when the app was registered (Unix timestamp in milliseconds)
+    #[serde(default)]
+    pub created_at: i64,
+}
+
+/// Settings for registering an OAuth 2.0 app
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OAuthAppSettings {
+    /// Display name of the app
+    pub name: String,
+
+    /// Description of the app
+    #[serde(default)]
+    pub description: String,
+
+    /// URL of the app's homepage
+    pub homepage: String,
+
+    /// URLs the authorization server may redirect to (at least one)
+    pub callback_urls: Vec<String>,
+
+    /// URL of the app's icon
+    #[serde(default)]
+    pub icon_url: Option<String>,
+
+    /// Authorize users without asking for consent (usually requires admin rights)
+    #[serde(default)]
+    pub is_trusted: bool,
+}
+
+impl OAuthAppSettings {
+    /// Create settings for an app with a single callback URL
+    pub fn new(
+        name: impl Into<String>,
+        homepage: impl Into<String>,
+        callback_url: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            homepage: homepage.into(),
+            callback_urls: vec![callback_url.into()],
+            ..Default::default()
+        }
+    }
+
+    /// Check that the required settings are present
+    pub fn validate(&self) -> crate::error::Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(crate::error::Error::invalid_argument(
+                "OAuth app name must not be empty",
+            ));
+        }
+        if self.homepage.trim().is_empty() {
+            return Err(crate::error::Error::invalid_argument(
+                "OAuth app homepage must not be empty",
+            ));
+        }
+        if self.callback_urls.iter().all(|url| url.trim().is_empty()) {
+            return Err(crate::error::Error::invalid_argument(
+                "OAuth app needs at least one callback URL",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// A personal access token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessToken {
+    /// Unique identifier for the token (not the token itself)
+    pub id: String,
+
+    /// User ID of the token's owner
+    pub user_id: String,
+
+    /// Description given when the token was created
+    #[serde(default)]
+    pub description: String,
+
+    /// Whether the token can currently be used
+    #[serde(default)]
+    pub is_active: bool,
+
+    /// The token value; only returned when the token is created
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+/// A bot account
+///
+/// Bots are users without a login of their own; they act through personal
+/// access tokens created for `user_id`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bot {
+    /// User ID of the bot's user account
+    pub user_id: String,
+
+    /// Username of the bot
+    pub username: String,
+
+    /// Display name of the bot
+    #[serde(default)]
+    pub display_name: String,
+
+    /// Description of what the bot does
+    #[serde(default)]
+    pub description: String,
+
+    /// User ID (or plugin ID) of the bot's owner
+    #[serde(default)]
+    pub owner_id: String,
+
+    /// Whether the bot is disabled
+    #[serde(default)]
+    pub is_disabled: bool,
+
+    /// Timestamp when the bot was created (Unix timestamp in milliseconds)
+    #[serde(default)]
+    pub created_at: i64,
+}
+
+/// Settings for creating a bot account
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BotSettings {
+    /// Username of the bot
+    pub username: String,
+
+    /// Display name of the bot
+    #[serde(default)]
+    pub display_name: String,
+
+    /// Description of what the bot does
+    #[serde(default)]
+    pub description: String,
+}
+
+impl BotSettings {
+    /// Create settings for a bot with only a username
+    pub fn new(username: impl Into<String>) -> Self {
+        Self {
+            username: username.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Check that the required settings are present
+    pub fn validate(&self) -> crate::error::Result<()> {
+        if self.username.trim().is_empty() {
+            return Err(crate::error::Error::invalid_argument(
+                "Bot username must not be empty",
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oauth_app_settings_validation() {
+        let settings =
+            OAuthAppSettings::new("Tool", "https://example.com", "https://example.com/cb");
+        assert!(settings.validate().is_ok());
+
+        let mut missing_name = settings.clone();
+        missing_name.name = " ".to_string();
+        assert!(missing_name.validate().is_err());
+
+        let mut missing_callbacks = settings;
+        missing_callbacks.callback_urls.clear();
+        assert!(missing_callbacks.validate().is_err());
+    }
+
+    #[test]
+    fn test_oauth_app_settings_from_json_defaults() {
+        let settings: OAuthAppSettings = serde_json::from_str(
+            r#"{"name": "Tool", "homepage": "https://example.com", "callback_urls": ["https://example.com/cb"]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(settings.description, "");
+        assert_eq!(settings.icon_url, None);
+        assert!(!settings.is_trusted);
+    }
+
+    #[test]
+    fn test_bot_settings_validation() {
+        assert!(BotSettings::new("deploy-bot").validate().is_ok());
+        assert!(BotSettings::new("  ").validate().is_err());
+
+        let settings: BotSettings = serde_json::from_str(r#"{"username": "deploy-bot"}"#).unwrap();
+        assert_eq!(settings.display_name, "");
+    }
+}
@@ -20,6 +20,21 @@ pub struct Message {
     pub edited_at: Option<DateTime<Utc>>,
     /// Optional attachments (files, images, etc.)
     pub attachments: Vec<Attachment>,
+    /// Identifier of the remote server/organization this message originated from
+    /// (None for messages created on the local server)
+    #[serde(default)]
+    pub origin: Option<String>,
+    /// The text as received, if the message pipeline changed it
+    /// (None for messages whose text was not transformed)
+    #[serde(default)]
+    pub original_text: Option<String>,
+    /// Transformations applied to the text, in order
+    #[serde(default)]
+    pub transformations: Vec<Transformation>,
+    /// Language of the text as an ISO 639-1 code, if language detection is
+    /// enabled for the channel and recognized it
+    #[serde(default)]
+    pub language: Option<String>,
     /// Optional metadata (platform-specific)
     pub metadata: Option<serde_json::Value>,
 }
@@ -40,6 +55,10 @@ impl Message {
             created_at: Utc::now(),
             edited_at: None,
             attachments: Vec::new(),
+            origin: None,
+            original_text: None,
+            transformations: Vec::new(),
+            language: None,
             metadata: None,
         }
     }
@@ -50,11 +69,122 @@ impl Message {
         self
     }
 
+    /// Set the remote origin of this message
+    pub fn with_origin(mut self, origin: impl Into<String>) -> Self {
+        self.origin = Some(origin.into());
+        self
+    }
+
+    /// Check if this message originated from a remote server/organization
+    pub fn is_remote(&self) -> bool {
+        self.origin.is_some()
+    }
+
     /// Set metadata for this message
     pub fn with_metadata(mut self, metadata: serde_json::Value) -> Self {
         self.metadata = Some(metadata);
         self
     }
+
+    /// Check whether the text was changed by the message pipeline
+    pub fn is_transformed(&self) -> bool {
+        self.original_text.is_some()
+    }
+
+    /// Get the text as received, before any transformations
+    pub fn original(&self) -> &str {
+        self.original_text.as_deref().unwrap_or(&self.text)
+    }
+
+    /// Replace the text, keeping the original and recording the transformation
+    ///
+    /// Does nothing if the new text is unchanged.
+    pub fn transform(&mut self, transformation: Transformation, new_text: impl Into<String>) {
+        let new_text = new_text.into();
+        if new_text == self.text {
+            return;
+        }
+        if self.original_text.is_none() {
+            self.original_text = Some(std::mem::take(&mut self.text));
+        }
+        self.text = new_text;
+        self.transformations.push(transformation);
+    }
+}
+
+/// A change made to a message's text by the message pipeline
+/// (e.g. translation or redaction)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Transformation {
+    /// What changed the text (e.g. "translate" or a script hook command)
+    pub name: String,
+    /// Optional details (e.g. "de -> en")
+    #[serde(default)]
+    pub detail: Option<String>,
+}
+
+impl Transformation {
+    /// Create a new transformation record
+    pub fn new(name: impl Into<String>) -> Self {
+        Transformation {
+            name: name.into(),
+            detail: None,
+        }
+    }
+
+    /// Set the details
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+}
+
+/// A reaction to a message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reaction {
+    /// The emoji name (e.g., "thumbsup")
+    pub emoji: String,
+    /// The user who reacted
+    pub user_id: String,
+    /// When the reaction was added
+    pub created_at: DateTime<Utc>,
+}
+
+/// Priority label of a message
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessagePriority {
+    #[default]
+    Standard,
+    Important,
+    Urgent,
+}
+
+/// Options of a message beyond its text
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SendOptions {
+    /// Post as a reply in the thread of this message
+    pub root_id: Option<String>,
+    /// Custom key/value properties stored with the message
+    pub props: std::collections::HashMap<String, serde_json::Value>,
+    /// Files uploaded to the channel to attach
+    pub file_ids: Vec<String>,
+    /// Priority label shown to recipients
+    pub priority: MessagePriority,
+    /// Ask recipients to acknowledge the message
+    pub requested_ack: bool,
+    /// Send the message at this time instead of now
+    pub scheduled_at: Option<DateTime<Utc>>,
+}
+
+/// Progress of a file transfer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferProgress {
+    /// Bytes transferred so far
+    pub transferred: u64,
+    /// Size of the file, if known
+    pub total: Option<u64>,
 }
 
 /// Represents a file or media attachment
@@ -72,6 +202,9 @@ pub struct Attachment {
     pub url: String,
     /// Optional thumbnail URL (for images/videos)
     pub thumbnail_url: Option<String>,
+    /// SHA-256 checksum (hex), if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
 }
 
 impl Attachment {
@@ -90,6 +223,7 @@ impl Attachment {
             size,
             url: url.into(),
             thumbnail_url: None,
+            checksum: None,
         }
     }
 
@@ -113,6 +247,35 @@ mod tests {
         assert_eq!(msg.channel_id, "channel-1");
         assert!(msg.attachments.is_empty());
         assert!(msg.metadata.is_none());
+        assert!(!msg.is_remote());
+    }
+
+    #[test]
+    fn test_message_transform() {
+        let mut msg = Message::new("msg-1", "Hallo Welt", "user-1", "channel-1");
+        assert!(!msg.is_transformed());
+
+        msg.transform(
+            Transformation::new("translate").with_detail("de -> en"),
+            "Hello world",
+        );
+        msg.transform(Transformation::new("redact"), "Hello [redacted]");
+        // Unchanged text is not recorded
+        msg.transform(Transformation::new("noop"), "Hello [redacted]");
+
+        assert!(msg.is_transformed());
+        assert_eq!(msg.text, "Hello [redacted]");
+        assert_eq!(msg.original(), "Hallo Welt");
+        assert_eq!(msg.transformations.len(), 2);
+        assert_eq!(msg.transformations[0].name, "translate");
+    }
+
+    #[test]
+    fn test_message_with_origin() {
+        let msg = Message::new("msg-1", "Hi from afar", "user-1", "channel-1")
+            .with_origin("remote-cluster-1");
+        assert!(msg.is_remote());
+        assert_eq!(msg.origin, Some("remote-cluster-1".to_string()));
     }
 
     #[test]
@@ -2,19 +2,58 @@
 //!
 //! This module contains platform-agnostic types used across all platform adapters.
 
+pub mod cache;
 pub mod capabilities;
 pub mod channel;
+pub mod command;
 pub mod connection;
+pub mod draft;
 pub mod emoji;
+pub mod event_filter;
+pub mod group;
+pub mod integration;
 pub mod message;
+pub mod notification;
+pub mod pagination;
+pub mod poll;
+pub mod quote;
+pub mod reminder;
+pub mod scheduled;
+pub mod search;
+pub mod sidebar;
+pub mod snippet;
 pub mod team;
+pub mod thread;
 pub mod user;
 
 // Re-export for convenience
-pub use capabilities::PlatformCapabilities;
-pub use channel::{Channel, ChannelType, ChannelUnread};
-pub use connection::{ConnectionInfo, ConnectionState};
+pub use cache::{CacheConfig, CacheStats};
+pub use capabilities::{PlatformCapabilities, PlatformLimits};
+pub use channel::{
+    Channel, ChannelActivity, ChannelMemberRoles, ChannelPermissions, ChannelType, ChannelUnread,
+};
+pub use command::{CommandResponse, CommandResponseType, SlashCommand};
+pub use connection::{
+    CircuitBreakerConfig, CircuitState, ConnectionHealth, ConnectionInfo, ConnectionState,
+    RequestStats, RetryPolicy,
+};
+pub use draft::Draft;
 pub use emoji::Emoji;
-pub use message::{Attachment, Message};
+pub use event_filter::{EventCategory, EventFilter};
+pub use group::{Group, GroupMention};
+pub use integration::{AccessToken, Bot, BotSettings, OAuthApp, OAuthAppSettings};
+pub use message::{
+    Attachment, Message, MessagePriority, Reaction, SendOptions, TransferProgress, Transformation,
+};
+pub use notification::{MentionRules, NotificationReason};
+pub use pagination::{Cursor, Page, PageRequest};
+pub use poll::{Poll, PollOption, PollSettings};
+pub use quote::QuoteInfo;
+pub use reminder::Reminder;
+pub use scheduled::ScheduledMessage;
+pub use search::SearchQuery;
+pub use sidebar::{CategorySorting, SidebarCategory, SidebarCategoryType};
+pub use snippet::SnippetInfo;
 pub use team::{Team, TeamType, TeamUnread};
-pub use user::User;
+pub use thread::Thread;
+pub use user::{ProfileUpdate, User};
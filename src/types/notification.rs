@@ -0,0 +1,74 @@
+//! Mention rule and notification types
+
+use serde::{Deserialize, Serialize};
+
+/// The rules deciding which messages mention the current user
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MentionRules {
+    /// ID of the current user, whose own messages never notify
+    pub user_id: String,
+    /// Username, mentioned as "@username"
+    pub username: String,
+    /// First name, mentioned without "@" if set
+    #[serde(default)]
+    pub first_name: Option<String>,
+    /// Whether @channel, @all and @here mention the user
+    #[serde(default = "default_channel_wide")]
+    pub channel_wide: bool,
+    /// Further words that count as mentions, matched case-insensitively
+    #[serde(default)]
+    pub keywords: Vec<String>,
+}
+
+fn default_channel_wide() -> bool {
+    true
+}
+
+impl MentionRules {
+    /// Create rules mentioning a user by username and channel-wide mentions
+    pub fn new(user_id: impl Into<String>, username: impl Into<String>) -> Self {
+        MentionRules {
+            user_id: user_id.into(),
+            username: username.into(),
+            first_name: None,
+            channel_wide: true,
+            keywords: Vec::new(),
+        }
+    }
+}
+
+/// Why a message notifies the current user
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum NotificationReason {
+    /// The message was posted in a direct message channel
+    DirectMessage,
+    /// The message mentions the user by username
+    Mention { keyword: String },
+    /// The message mentions everyone in the channel (@channel, @all, @here)
+    ChannelWide { keyword: String },
+    /// The message contains one of the user's keywords or first name
+    Keyword { keyword: String },
+}
+
+impl NotificationReason {
+    /// The reason's name, as in its JSON form
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::DirectMessage => "direct_message",
+            Self::Mention { .. } => "mention",
+            Self::ChannelWide { .. } => "channel_wide",
+            Self::Keyword { .. } => "keyword",
+        }
+    }
+
+    /// The word that triggered the notification, if any
+    pub fn keyword(&self) -> Option<&str> {
+        match self {
+            Self::DirectMessage => None,
+            Self::Mention { keyword }
+            | Self::ChannelWide { keyword }
+            | Self::Keyword { keyword } => Some(keyword),
+        }
+    }
+}
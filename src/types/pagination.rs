@@ -0,0 +1,305 @@
+//! Cursor-based pagination
+//!
+//! Platforms paginate differently: by page number, by "before/after this
+//! message", or by server-issued tokens. List APIs that support pagination
+//! return a [`Page`] whose `next_cursor` is an opaque token; passing it to
+//! `Platform::next_page` fetches the following page, whatever the underlying
+//! mechanism. A page without a `next_cursor` is the last one.
+//!
+//! So far these are a channel's message history, custom emojis, followed
+//! threads and saved messages (the variants of [`PageRequest`]). Channels,
+//! members, users and search results are still returned whole or up to a
+//! limit.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::types::Message;
+
+/// An opaque token identifying the next page of a list
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Cursor(String);
+
+impl Cursor {
+    /// Create a cursor from a page request
+    pub fn new(request: &PageRequest) -> Self {
+        let json = serde_json::to_vec(request).unwrap_or_default();
+        Cursor(json.iter().map(|b| format!("{b:02x}")).collect())
+    }
+
+    /// Wrap a cursor string received from a client
+    pub fn from_string(cursor: impl Into<String>) -> Self {
+        Cursor(cursor.into())
+    }
+
+    /// Get the cursor as a string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Decode the page request the cursor stands for
+    ///
+    /// # Returns
+    /// The page request, or an InvalidArgument error if the cursor is malformed
+    pub fn request(&self) -> Result<PageRequest> {
+        let invalid = || Error::invalid_argument("Invalid pagination cursor");
+
+        if !self.0.len().is_multiple_of(2) || !self.0.is_ascii() {
+            return Err(invalid());
+        }
+        let bytes = (0..self.0.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&self.0[i..i + 2], 16))
+            .collect::<std::result::Result<Vec<u8>, _>>()
+            .map_err(|_| invalid())?;
+
+        serde_json::from_slice(&bytes).map_err(|_| invalid())
+    }
+}
+
+impl fmt::Display for Cursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// What a cursor fetches
+///
+/// This is an implementation detail of cursors; clients should treat cursors
+/// as opaque.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PageRequest {
+    /// Messages older than a message
+    MessagesBefore {
+        channel_id: String,
+        before_id: String,
+        limit: usize,
+    },
+    /// Messages newer than a message
+    MessagesAfter {
+        channel_id: String,
+        after_id: String,
+        limit: usize,
+    },
+    /// A page of custom emojis
+    Emojis { page: u32, per_page: u32 },
+    /// A page of followed threads, in a team or the current team
+    FollowedThreads {
+        team_id: Option<String>,
+        page: u32,
+        per_page: u32,
+    },
+    /// A page of the current user's saved messages
+    FlaggedPosts { page: u32, per_page: u32 },
+}
+
+/// One page of a list result
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Page<T> {
+    /// The items on this page
+    pub items: Vec<T>,
+    /// Cursor for the next page, or None if this is the last page
+    pub next_cursor: Option<Cursor>,
+}
+
+impl<T> Page<T> {
+    /// Create a page
+    pub fn new(items: Vec<T>, next_cursor: Option<Cursor>) -> Self {
+        Page { items, next_cursor }
+    }
+
+    /// Check whether more pages follow
+    pub fn has_more(&self) -> bool {
+        self.next_cursor.is_some()
+    }
+}
+
+impl<T: Serialize> Page<T> {
+    /// Convert the items to JSON values
+    pub fn into_json(self) -> Result<Page<serde_json::Value>> {
+        let items = self
+            .items
+            .into_iter()
+            .map(serde_json::to_value)
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| {
+                Error::new(
+                    crate::error::ErrorCode::Unknown,
+                    format!("Failed to serialize page: {e}"),
+                )
+            })?;
+        Ok(Page::new(items, self.next_cursor))
+    }
+}
+
+impl Page<serde_json::Value> {
+    /// Convert JSON items (as returned by `Platform::next_page`) to a concrete type
+    pub fn decode<T: for<'de> Deserialize<'de>>(self) -> Result<Page<T>> {
+        let items = self
+            .items
+            .into_iter()
+            .map(serde_json::from_value)
+            .collect::<std::result::Result<Vec<T>, _>>()
+            .map_err(|e| Error::invalid_argument(format!("Unexpected page item: {e}")))?;
+        Ok(Page::new(items, self.next_cursor))
+    }
+}
+
+/// Cursor for the messages older than a full page of messages
+pub(crate) fn older_messages_cursor(
+    channel_id: &str,
+    messages: &[Message],
+    limit: usize,
+) -> Option<Cursor> {
+    if limit == 0 || messages.len() < limit {
+        return None;
+    }
+    messages.iter().min_by_key(|m| m.created_at).map(|oldest| {
+        Cursor::new(&PageRequest::MessagesBefore {
+            channel_id: channel_id.to_string(),
+            before_id: oldest.id.clone(),
+            limit,
+        })
+    })
+}
+
+/// Cursor for the messages newer than a full page of messages
+pub(crate) fn newer_messages_cursor(
+    channel_id: &str,
+    messages: &[Message],
+    limit: usize,
+) -> Option<Cursor> {
+    if limit == 0 || messages.len() < limit {
+        return None;
+    }
+    messages.iter().max_by_key(|m| m.created_at).map(|newest| {
+        Cursor::new(&PageRequest::MessagesAfter {
+            channel_id: channel_id.to_string(),
+            after_id: newest.id.clone(),
+            limit,
+        })
+    })
+}
+
+/// Cursor for the next page of a page-numbered list
+pub(crate) fn emojis_cursor(page: u32, per_page: u32, returned: usize) -> Option<Cursor> {
+    if per_page == 0 || returned < per_page as usize {
+        return None;
+    }
+    Some(Cursor::new(&PageRequest::Emojis {
+        page: page + 1,
+        per_page,
+    }))
+}
+
+/// Cursor for the next page of followed threads
+pub(crate) fn followed_threads_cursor(
+    team_id: Option<&str>,
+    page: u32,
+    per_page: u32,
+    returned: usize,
+) -> Option<Cursor> {
+    if per_page == 0 || returned < per_page as usize {
+        return None;
+    }
+    Some(Cursor::new(&PageRequest::FollowedThreads {
+        team_id: team_id.map(str::to_string),
+        page: page + 1,
+        per_page,
+    }))
+}
+
+/// Cursor for the next page of saved messages
+pub(crate) fn flagged_posts_cursor(page: u32, per_page: u32, returned: usize) -> Option<Cursor> {
+    if per_page == 0 || returned < per_page as usize {
+        return None;
+    }
+    Some(Cursor::new(&PageRequest::FlaggedPosts {
+        page: page + 1,
+        per_page,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let request = PageRequest::MessagesBefore {
+            channel_id: "ch-1".to_string(),
+            before_id: "msg-9".to_string(),
+            limit: 50,
+        };
+        let cursor = Cursor::new(&request);
+        assert!(cursor.as_str().chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(
+            Cursor::from_string(cursor.to_string()).request().unwrap(),
+            request
+        );
+    }
+
+    #[test]
+    fn test_invalid_cursor() {
+        assert!(Cursor::from_string("abc").request().is_err());
+        assert!(Cursor::from_string("zz").request().is_err());
+        assert!(Cursor::from_string("7b7d").request().is_err());
+        assert!(Cursor::from_string("éé").request().is_err());
+    }
+
+    #[test]
+    fn test_page_json() {
+        let page = Page::new(vec![1, 2], Some(Cursor::from_string("00")));
+        let json = serde_json::to_value(&page).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"items": [1, 2], "next_cursor": "00"})
+        );
+
+        let decoded: Page<i32> = page.into_json().unwrap().decode().unwrap();
+        assert_eq!(decoded.items, vec![1, 2]);
+        assert!(decoded.has_more());
+    }
+
+    #[test]
+    fn test_message_cursors() {
+        let messages = vec![
+            Message::new("m1", "a", "u", "ch-1"),
+            Message::new("m2", "b", "u", "ch-1"),
+        ];
+
+        // A short page is the last one
+        assert!(older_messages_cursor("ch-1", &messages, 3).is_none());
+
+        let cursor = older_messages_cursor("ch-1", &messages, 2).unwrap();
+        assert!(matches!(
+            cursor.request().unwrap(),
+            PageRequest::MessagesBefore { before_id, limit: 2, .. } if before_id == "m1"
+        ));
+
+        assert_eq!(
+            emojis_cursor(0, 2, 2).unwrap().request().unwrap(),
+            PageRequest::Emojis {
+                page: 1,
+                per_page: 2
+            }
+        );
+        assert!(emojis_cursor(0, 2, 1).is_none());
+
+        assert_eq!(
+            followed_threads_cursor(None, 3, 2, 2)
+                .unwrap()
+                .request()
+                .unwrap(),
+            PageRequest::FollowedThreads {
+                team_id: None,
+                page: 4,
+                per_page: 2
+            }
+        );
+    }
+}
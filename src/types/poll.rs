@@ -0,0 +1,89 @@
+//! Polls
+//!
+//! A platform-independent view of a poll posted to a channel. Platforms with
+//! native polls map them directly; others build polls from messages posted by a
+//! poll integration (e.g. the Matterpoll plugin on Mattermost). Options are
+//! addressed by their position in `Poll::options`.
+
+use serde::{Deserialize, Serialize};
+
+/// Settings chosen when creating a poll
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PollSettings {
+    /// Hide who voted for which option
+    pub anonymous: bool,
+    /// Show vote counts while the poll is open
+    pub show_progress: bool,
+    /// Let anyone add options to the poll
+    pub allow_new_options: bool,
+}
+
+/// One answer option of a poll
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PollOption {
+    /// The option text
+    pub text: String,
+    /// Number of votes, if the platform reveals it (always for closed polls)
+    pub votes: Option<u32>,
+    /// Display names of the voters, if revealed (never for anonymous polls)
+    #[serde(default)]
+    pub voters: Vec<String>,
+}
+
+impl PollOption {
+    /// Create an option without vote information
+    pub fn new(text: impl Into<String>) -> Self {
+        PollOption {
+            text: text.into(),
+            votes: None,
+            voters: Vec::new(),
+        }
+    }
+}
+
+/// A poll posted to a channel
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Poll {
+    /// ID of the message displaying the poll; used to vote on and close it
+    pub id: String,
+    /// The channel the poll was posted in
+    pub channel_id: String,
+    /// The poll question
+    pub question: String,
+    /// The answer options, in display order
+    pub options: Vec<PollOption>,
+    /// Settings of the poll, as far as they can be determined
+    pub settings: PollSettings,
+    /// Whether voting has ended
+    pub closed: bool,
+}
+
+impl Poll {
+    /// Total number of votes, if vote counts are revealed
+    pub fn total_votes(&self) -> Option<u32> {
+        self.options.iter().map(|o| o.votes).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_total_votes() {
+        let mut poll = Poll {
+            id: "post1".to_string(),
+            channel_id: "ch1".to_string(),
+            question: "Lunch?".to_string(),
+            options: vec![PollOption::new("Pizza"), PollOption::new("Sushi")],
+            settings: PollSettings::default(),
+            closed: false,
+        };
+        assert_eq!(poll.total_votes(), None);
+
+        poll.options[0].votes = Some(2);
+        poll.options[1].votes = Some(3);
+        assert_eq!(poll.total_votes(), Some(5));
+    }
+}
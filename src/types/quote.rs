@@ -0,0 +1,141 @@
+//! Quoting messages
+//!
+//! A quote is posted as a Markdown blockquote of the original text followed by
+//! an attribution line linking to the original, then the comment. The quoted
+//! reference is kept in the [`QUOTE_PROP`] message property so receiving
+//! clients can render the quote richly instead of parsing the blockquote.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::Message;
+
+/// Message property holding the [`QuoteInfo`] of a quoting message
+pub const QUOTE_PROP: &str = "libcommunicator_quote";
+
+/// Describes the message quoted by a message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuoteInfo {
+    /// ID of the quoted message
+    pub message_id: String,
+    /// Channel of the quoted message
+    pub channel_id: String,
+    /// User ID of the quoted message's sender
+    pub sender_id: String,
+    /// Username of the quoted message's sender, if it could be looked up
+    pub sender_name: Option<String>,
+    /// When the quoted message was created
+    pub created_at: DateTime<Utc>,
+    /// Link to the quoted message, on platforms that have permalinks
+    pub permalink: Option<String>,
+}
+
+impl QuoteInfo {
+    /// Describe a message being quoted
+    pub fn new(quoted: &Message, sender_name: Option<String>, permalink: Option<String>) -> Self {
+        QuoteInfo {
+            message_id: quoted.id.clone(),
+            channel_id: quoted.channel_id.clone(),
+            sender_id: quoted.sender_id.clone(),
+            sender_name,
+            created_at: quoted.created_at,
+            permalink,
+        }
+    }
+
+    /// Read the quote info of a received message, if it quotes another message
+    pub fn from_message(message: &Message) -> Option<Self> {
+        let value = message.metadata.as_ref()?.get("props")?.get(QUOTE_PROP)?;
+        serde_json::from_value(value.clone()).ok()
+    }
+
+    /// Store the quote info in a message's metadata, as it is read back
+    ///
+    /// Used when the platform does not store message properties, so
+    /// [`QuoteInfo::from_message`] still works on the sent message.
+    pub fn attach_to(&self, message: &mut Message) {
+        let metadata = message
+            .metadata
+            .get_or_insert_with(|| serde_json::json!({}));
+        if !metadata.is_object() {
+            *metadata = serde_json::json!({});
+        }
+        if !metadata["props"].is_object() {
+            metadata["props"] = serde_json::json!({});
+        }
+        metadata["props"][QUOTE_PROP] = serde_json::to_value(self).unwrap_or_default();
+    }
+}
+
+/// Format the text of a message quoting another
+///
+/// # Arguments
+/// * `quoted_text` - Text of the quoted message
+/// * `info` - The quoted reference, for the attribution line
+/// * `comment` - The quoting user's comment (may be empty)
+pub fn quote_text(quoted_text: &str, info: &QuoteInfo, comment: &str) -> String {
+    let mut text = String::new();
+    for line in quoted_text.lines() {
+        if line.is_empty() {
+            text.push_str(">\n");
+        } else {
+            text.push_str("> ");
+            text.push_str(line);
+            text.push('\n');
+        }
+    }
+
+    let sender = match &info.sender_name {
+        Some(name) => format!("@{name}"),
+        None => info.sender_id.clone(),
+    };
+    match &info.permalink {
+        Some(link) => text.push_str(&format!("> — {sender}, [original message]({link})")),
+        None => text.push_str(&format!("> — {sender}")),
+    }
+
+    if !comment.is_empty() {
+        text.push_str("\n\n");
+        text.push_str(comment);
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quoted() -> Message {
+        Message::new("post1", "First line\n\nThird line", "user1", "chan1")
+    }
+
+    #[test]
+    fn test_quote_text() {
+        let info = QuoteInfo::new(
+            &quoted(),
+            Some("alice".to_string()),
+            Some("https://chat.example.com/team/pl/post1".to_string()),
+        );
+        assert_eq!(
+            quote_text("First line\n\nThird line", &info, "Agreed"),
+            "> First line\n>\n> Third line\n\
+             > — @alice, [original message](https://chat.example.com/team/pl/post1)\n\nAgreed"
+        );
+
+        // Without a username or permalink, and without a comment
+        let info = QuoteInfo::new(&quoted(), None, None);
+        assert_eq!(quote_text("Hi", &info, ""), "> Hi\n> — user1");
+    }
+
+    #[test]
+    fn test_attach_and_read_back() {
+        let info = QuoteInfo::new(&quoted(), Some("alice".to_string()), None);
+        let mut message = Message::new("post2", "text", "user2", "chan1")
+            .with_metadata(serde_json::json!({"props": {"other": 1}}));
+        info.attach_to(&mut message);
+
+        assert_eq!(QuoteInfo::from_message(&message), Some(info));
+        assert_eq!(message.metadata.unwrap()["props"]["other"], 1);
+        assert!(QuoteInfo::from_message(&quoted()).is_none());
+    }
+}
@@ -0,0 +1,62 @@
+//! Reminder types
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A reminder about a message or channel
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reminder {
+    /// Unique identifier for this reminder
+    pub id: String,
+    /// The channel the reminder is about
+    pub channel_id: String,
+    /// The message the reminder is about, if any
+    #[serde(default)]
+    pub message_id: Option<String>,
+    /// Optional note shown with the reminder
+    #[serde(default)]
+    pub note: Option<String>,
+    /// When the reminder is due
+    pub due_at: DateTime<Utc>,
+    /// When the reminder was created
+    pub created_at: DateTime<Utc>,
+    /// Whether the server delivers this reminder instead of the local scheduler
+    #[serde(default)]
+    pub server_side: bool,
+}
+
+impl Reminder {
+    /// Create a new local reminder
+    pub fn new(
+        id: impl Into<String>,
+        channel_id: impl Into<String>,
+        due_at: DateTime<Utc>,
+    ) -> Self {
+        Reminder {
+            id: id.into(),
+            channel_id: channel_id.into(),
+            message_id: None,
+            note: None,
+            due_at,
+            created_at: Utc::now(),
+            server_side: false,
+        }
+    }
+
+    /// Set the message the reminder is about
+    pub fn with_message(mut self, message_id: impl Into<String>) -> Self {
+        self.message_id = Some(message_id.into());
+        self
+    }
+
+    /// Set the note shown with the reminder
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.note = Some(note.into());
+        self
+    }
+
+    /// Check whether the reminder is due at the given time
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.due_at <= now
+    }
+}
@@ -0,0 +1,54 @@
+//! Scheduled message types
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A message to be sent at a later time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduledMessage {
+    /// Unique identifier for this scheduled message
+    pub id: String,
+    /// The channel the message will be sent to
+    pub channel_id: String,
+    /// The thread the message will reply to, if any
+    #[serde(default)]
+    pub root_id: Option<String>,
+    /// The message text
+    pub message: String,
+    /// When the message will be sent
+    pub scheduled_at: DateTime<Utc>,
+    /// When the message was scheduled
+    pub created_at: DateTime<Utc>,
+    /// Whether the server sends this message instead of the library
+    #[serde(default)]
+    pub server_side: bool,
+    /// Why sending the message failed, once it has
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl ScheduledMessage {
+    /// Create a new scheduled message
+    pub fn new(
+        id: impl Into<String>,
+        channel_id: impl Into<String>,
+        message: impl Into<String>,
+        scheduled_at: DateTime<Utc>,
+    ) -> Self {
+        ScheduledMessage {
+            id: id.into(),
+            channel_id: channel_id.into(),
+            root_id: None,
+            message: message.into(),
+            scheduled_at,
+            created_at: Utc::now(),
+            server_side: false,
+            error: None,
+        }
+    }
+
+    /// Check whether the message is due at the given time
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.error.is_none() && self.scheduled_at <= now
+    }
+}
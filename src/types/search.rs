@@ -0,0 +1,321 @@
+//! Structured message search queries
+//!
+//! `SearchQuery` describes an advanced message search independently of any
+//! platform. Adapters translate it into their native search syntax. Queries can
+//! be built programmatically or parsed from a common mini-language:
+//!
+//! ```text
+//! deploy "release notes" -draft from:alice to:bob in:town-square
+//!     after:2024-01-01 before:2024-02-01 on:2024-01-15 has:attachment
+//! ```
+
+use std::fmt;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+
+/// A platform-independent message search query
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchQuery {
+    /// Words that must appear in the message
+    pub terms: Vec<String>,
+    /// Exact phrases that must appear in the message
+    pub phrases: Vec<String>,
+    /// Words that must not appear in the message
+    pub excluded_terms: Vec<String>,
+    /// Only messages sent by these users (usernames)
+    pub from_users: Vec<String>,
+    /// Only direct messages with these users (usernames)
+    pub to_users: Vec<String>,
+    /// Only messages in these channels (channel names)
+    pub in_channels: Vec<String>,
+    /// Only messages sent before this date
+    pub before: Option<NaiveDate>,
+    /// Only messages sent after this date
+    pub after: Option<NaiveDate>,
+    /// Only messages sent on this date
+    pub on: Option<NaiveDate>,
+    /// Only messages with file attachments
+    pub has_attachment: bool,
+}
+
+impl SearchQuery {
+    /// Create an empty query
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a search word
+    pub fn term(mut self, term: impl Into<String>) -> Self {
+        self.terms.push(term.into());
+        self
+    }
+
+    /// Add an exact phrase
+    pub fn phrase(mut self, phrase: impl Into<String>) -> Self {
+        self.phrases.push(phrase.into());
+        self
+    }
+
+    /// Exclude messages containing a word
+    pub fn exclude(mut self, term: impl Into<String>) -> Self {
+        self.excluded_terms.push(term.into());
+        self
+    }
+
+    /// Only match messages from a user
+    pub fn from_user(mut self, username: impl Into<String>) -> Self {
+        self.from_users.push(username.into());
+        self
+    }
+
+    /// Only match direct messages with a user
+    pub fn to_user(mut self, username: impl Into<String>) -> Self {
+        self.to_users.push(username.into());
+        self
+    }
+
+    /// Only match messages in a channel
+    pub fn in_channel(mut self, channel: impl Into<String>) -> Self {
+        self.in_channels.push(channel.into());
+        self
+    }
+
+    /// Only match messages sent before a date
+    pub fn before(mut self, date: NaiveDate) -> Self {
+        self.before = Some(date);
+        self
+    }
+
+    /// Only match messages sent after a date
+    pub fn after(mut self, date: NaiveDate) -> Self {
+        self.after = Some(date);
+        self
+    }
+
+    /// Only match messages sent on a date
+    pub fn on(mut self, date: NaiveDate) -> Self {
+        self.on = Some(date);
+        self
+    }
+
+    /// Only match messages with attachments
+    pub fn with_attachment(mut self) -> Self {
+        self.has_attachment = true;
+        self
+    }
+
+    /// Check whether the query has no criteria
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Parse a query from the common search mini-language
+    ///
+    /// Supported syntax: plain words, `"exact phrases"`, `-excluded`,
+    /// `from:user`, `to:user`, `in:channel`, `before:YYYY-MM-DD`,
+    /// `after:YYYY-MM-DD`, `on:YYYY-MM-DD` and `has:attachment`.
+    /// A leading `@` on usernames and `#`/`~` on channel names is ignored.
+    ///
+    /// # Returns
+    /// The parsed query, or an InvalidArgument error for malformed filters
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut query = SearchQuery::new();
+
+        for token in tokenize(input)? {
+            let word = match token {
+                Token::Phrase(phrase) => {
+                    query.phrases.push(phrase);
+                    continue;
+                }
+                Token::Word(word) => word,
+            };
+
+            if let Some(excluded) = word.strip_prefix('-').filter(|w| !w.is_empty()) {
+                query.excluded_terms.push(excluded.to_string());
+                continue;
+            }
+
+            let Some((key, value)) = word.split_once(':') else {
+                query.terms.push(word);
+                continue;
+            };
+
+            match key.to_lowercase().as_str() {
+                "from" => query
+                    .from_users
+                    .push(non_empty(key, value.trim_start_matches('@'))?),
+                "to" => query
+                    .to_users
+                    .push(non_empty(key, value.trim_start_matches('@'))?),
+                "in" => query
+                    .in_channels
+                    .push(non_empty(key, value.trim_start_matches(['#', '~']))?),
+                "before" => query.before = Some(parse_date(key, value)?),
+                "after" => query.after = Some(parse_date(key, value)?),
+                "on" => query.on = Some(parse_date(key, value)?),
+                "has" => match value.to_lowercase().as_str() {
+                    "attachment" | "attachments" | "file" | "files" => query.has_attachment = true,
+                    _ => {
+                        return Err(Error::invalid_argument(format!(
+                            "Unsupported filter has:{value}"
+                        )))
+                    }
+                },
+                // Not a filter (e.g. a URL or time); search for it literally
+                _ => query.terms.push(word),
+            }
+        }
+
+        Ok(query)
+    }
+}
+
+impl fmt::Display for SearchQuery {
+    /// Format the query in the common search mini-language
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts: Vec<String> = Vec::new();
+        parts.extend(self.terms.iter().cloned());
+        parts.extend(self.phrases.iter().map(|p| format!("\"{p}\"")));
+        parts.extend(self.excluded_terms.iter().map(|t| format!("-{t}")));
+        parts.extend(self.from_users.iter().map(|u| format!("from:{u}")));
+        parts.extend(self.to_users.iter().map(|u| format!("to:{u}")));
+        parts.extend(self.in_channels.iter().map(|c| format!("in:{c}")));
+        if let Some(date) = self.after {
+            parts.push(format!("after:{}", date.format("%Y-%m-%d")));
+        }
+        if let Some(date) = self.before {
+            parts.push(format!("before:{}", date.format("%Y-%m-%d")));
+        }
+        if let Some(date) = self.on {
+            parts.push(format!("on:{}", date.format("%Y-%m-%d")));
+        }
+        if self.has_attachment {
+            parts.push("has:attachment".to_string());
+        }
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+enum Token {
+    Word(String),
+    Phrase(String),
+}
+
+/// Split input into words and quoted phrases
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            let mut phrase = String::new();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some(c) => phrase.push(c),
+                    None => return Err(Error::invalid_argument("Unterminated quoted phrase")),
+                }
+            }
+            if !phrase.trim().is_empty() {
+                tokens.push(Token::Phrase(phrase));
+            }
+        } else {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || c == '"' {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+            tokens.push(Token::Word(word));
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn non_empty(key: &str, value: &str) -> Result<String> {
+    if value.is_empty() {
+        Err(Error::invalid_argument(format!(
+            "Missing value for filter {key}:"
+        )))
+    } else {
+        Ok(value.to_string())
+    }
+}
+
+fn parse_date(key: &str, value: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+        Error::invalid_argument(format!(
+            "Invalid date for {key}: '{value}' (expected YYYY-MM-DD)"
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_parse_full_query() {
+        let query = SearchQuery::parse(
+            r#"deploy "release notes" -draft from:@alice to:bob in:#town-square after:2024-01-01 before:2024-02-01 has:attachment"#,
+        )
+        .unwrap();
+
+        assert_eq!(query.terms, vec!["deploy"]);
+        assert_eq!(query.phrases, vec!["release notes"]);
+        assert_eq!(query.excluded_terms, vec!["draft"]);
+        assert_eq!(query.from_users, vec!["alice"]);
+        assert_eq!(query.to_users, vec!["bob"]);
+        assert_eq!(query.in_channels, vec!["town-square"]);
+        assert_eq!(query.after, Some(date(2024, 1, 1)));
+        assert_eq!(query.before, Some(date(2024, 2, 1)));
+        assert!(query.has_attachment);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(SearchQuery::parse("before:yesterday").is_err());
+        assert!(SearchQuery::parse("from:").is_err());
+        assert!(SearchQuery::parse("has:emoji").is_err());
+        assert!(SearchQuery::parse("\"unterminated").is_err());
+    }
+
+    #[test]
+    fn test_unknown_prefix_is_a_term() {
+        let query = SearchQuery::parse("https://example.com 10:30").unwrap();
+        assert_eq!(query.terms, vec!["https://example.com", "10:30"]);
+    }
+
+    #[test]
+    fn test_builder_round_trip() {
+        let query = SearchQuery::new()
+            .term("outage")
+            .phrase("root cause")
+            .from_user("alice")
+            .in_channel("incidents")
+            .on(date(2024, 3, 5))
+            .with_attachment();
+
+        let text = query.to_string();
+        assert_eq!(
+            text,
+            r#"outage "root cause" from:alice in:incidents on:2024-03-05 has:attachment"#
+        );
+        assert_eq!(SearchQuery::parse(&text).unwrap(), query);
+        assert!(SearchQuery::new().is_empty());
+    }
+}
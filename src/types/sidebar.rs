@@ -0,0 +1,54 @@
+//! Sidebar category types
+
+use serde::{Deserialize, Serialize};
+
+/// A group of channels in the sidebar
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SidebarCategory {
+    /// Unique identifier for this category
+    pub id: String,
+    /// The team whose sidebar the category belongs to
+    pub team_id: String,
+    /// Human-readable name
+    pub display_name: String,
+    /// Kind of category
+    #[serde(rename = "type")]
+    pub category_type: SidebarCategoryType,
+    /// How the category's channels are ordered
+    pub sorting: CategorySorting,
+    /// The channels in the category, in display order
+    pub channel_ids: Vec<String>,
+    /// Whether the category is collapsed
+    pub collapsed: bool,
+    /// Whether the category's channels are muted
+    pub muted: bool,
+}
+
+/// Kind of sidebar category
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SidebarCategoryType {
+    /// Channels marked as favorites
+    Favorites,
+    /// Public and private channels not in another category
+    Channels,
+    /// Direct and group messages not in another category
+    DirectMessages,
+    /// A category created by the user
+    Custom,
+}
+
+/// How the channels of a sidebar category are ordered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CategorySorting {
+    /// The platform's default order for the category
+    #[default]
+    Default,
+    /// By display name
+    Alphabetical,
+    /// Most recent activity first
+    Recent,
+    /// As arranged by the user (the order of `channel_ids`)
+    Manual,
+}
@@ -0,0 +1,140 @@
+//! Code snippets
+//!
+//! Short snippets are posted as fenced code blocks; long ones are uploaded as
+//! files so they do not flood the channel. Either way the language is kept in
+//! the [`SNIPPET_PROP`] message property so receiving clients can highlight it.
+
+use serde::{Deserialize, Serialize};
+
+use super::{Message, PlatformLimits};
+
+/// Message property holding the [`SnippetInfo`] of a snippet message
+pub const SNIPPET_PROP: &str = "libcommunicator_snippet";
+
+/// Snippets with more lines than this are uploaded as files
+pub const MAX_INLINE_SNIPPET_LINES: usize = 100;
+
+/// Describes a code snippet message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnippetInfo {
+    /// Language for syntax highlighting (e.g. "rust")
+    pub language: Option<String>,
+    /// File name of the snippet
+    pub filename: Option<String>,
+    /// Whether the snippet was uploaded as a file instead of posted inline
+    pub uploaded: bool,
+}
+
+impl SnippetInfo {
+    /// Read the snippet info of a received message, if it is a snippet
+    pub fn from_message(message: &Message) -> Option<Self> {
+        let value = message.metadata.as_ref()?.get("props")?.get(SNIPPET_PROP)?;
+        serde_json::from_value(value.clone()).ok()
+    }
+}
+
+/// Format code as a fenced code block
+///
+/// The fence is made longer than any run of backticks in the code, so the
+/// code cannot close the block early.
+pub fn fenced_code_block(language: Option<&str>, code: &str) -> String {
+    let mut longest_run = 0;
+    let mut run = 0;
+    for c in code.chars() {
+        run = if c == '`' { run + 1 } else { 0 };
+        longest_run = longest_run.max(run);
+    }
+    let fence = "`".repeat((longest_run + 1).max(3));
+
+    let mut block = format!("{fence}{}\n{code}", language.unwrap_or(""));
+    if !code.ends_with('\n') {
+        block.push('\n');
+    }
+    block.push_str(&fence);
+    block
+}
+
+/// Decide whether a snippet should be uploaded as a file rather than posted inline
+pub fn should_upload_snippet(code: &str, fenced_len: usize, limits: &PlatformLimits) -> bool {
+    code.lines().count() > MAX_INLINE_SNIPPET_LINES
+        || limits
+            .max_message_length
+            .map(|max| fenced_len > max)
+            .unwrap_or(false)
+}
+
+/// File name for an uploaded snippet
+///
+/// Uses the given name, or `snippet.<ext>` with an extension matching the language.
+pub fn snippet_filename(language: Option<&str>, filename: Option<&str>) -> String {
+    if let Some(name) = filename.filter(|n| !n.is_empty()) {
+        return name.to_string();
+    }
+
+    let ext = match language.map(|l| l.to_lowercase()).as_deref() {
+        Some("rust") => "rs",
+        Some("python") | Some("py") => "py",
+        Some("javascript") | Some("js") => "js",
+        Some("typescript") | Some("ts") => "ts",
+        Some("go") | Some("golang") => "go",
+        Some("c") => "c",
+        Some("cpp") | Some("c++") => "cpp",
+        Some("csharp") | Some("c#") | Some("cs") => "cs",
+        Some("java") => "java",
+        Some("kotlin") => "kt",
+        Some("swift") => "swift",
+        Some("ruby") | Some("rb") => "rb",
+        Some("php") => "php",
+        Some("shell") | Some("bash") | Some("sh") => "sh",
+        Some("sql") => "sql",
+        Some("json") => "json",
+        Some("yaml") | Some("yml") => "yaml",
+        Some("toml") => "toml",
+        Some("xml") => "xml",
+        Some("html") => "html",
+        Some("css") => "css",
+        Some("markdown") | Some("md") => "md",
+        _ => "txt",
+    };
+    format!("snippet.{ext}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fenced_code_block() {
+        assert_eq!(
+            fenced_code_block(Some("rust"), "fn main() {}"),
+            "```rust\nfn main() {}\n```"
+        );
+        // Code containing a fence gets a longer one
+        assert_eq!(
+            fenced_code_block(None, "```\nx\n```\n"),
+            "````\n```\nx\n```\n````"
+        );
+    }
+
+    #[test]
+    fn test_should_upload_snippet() {
+        let limits = PlatformLimits {
+            max_message_length: Some(50),
+            ..Default::default()
+        };
+        assert!(!should_upload_snippet("x", 10, &limits));
+        assert!(should_upload_snippet("x", 51, &limits));
+        assert!(should_upload_snippet(
+            &"x\n".repeat(MAX_INLINE_SNIPPET_LINES + 1),
+            10,
+            &PlatformLimits::default()
+        ));
+    }
+
+    #[test]
+    fn test_snippet_filename() {
+        assert_eq!(snippet_filename(Some("Rust"), None), "snippet.rs");
+        assert_eq!(snippet_filename(None, None), "snippet.txt");
+        assert_eq!(snippet_filename(Some("go"), Some("main.go")), "main.go");
+    }
+}
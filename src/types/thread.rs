@@ -0,0 +1,36 @@
+//! Thread types
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::Message;
+
+/// A reply thread the current user follows, with its metadata
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Thread {
+    /// Unique identifier for this thread (the root message's ID)
+    pub id: String,
+    /// The channel the thread is in
+    pub channel_id: String,
+    /// The message that started the thread
+    pub root: Message,
+    /// Number of replies to the root message
+    pub reply_count: u32,
+    /// IDs of the users who posted in the thread
+    pub participants: Vec<String>,
+    /// When the last reply was posted, None if there are no replies
+    pub last_reply_at: Option<DateTime<Utc>>,
+    /// When the current user last viewed the thread, None if never
+    pub last_viewed_at: Option<DateTime<Utc>>,
+    /// Number of replies the current user has not read
+    pub unread_replies: u32,
+    /// Number of unread replies mentioning the current user
+    pub unread_mentions: u32,
+}
+
+impl Thread {
+    /// Check whether the thread has unread replies
+    pub fn is_unread(&self) -> bool {
+        self.unread_replies > 0
+    }
+}
@@ -36,6 +36,7 @@ pub enum UserStatus {
     /// User is away/idle
     Away,
     /// User is in "do not disturb" mode
+    #[serde(alias = "dnd")]
     DoNotDisturb,
     /// User is offline
     Offline,
@@ -101,6 +102,71 @@ impl User {
     }
 }
 
+/// Changes to the current user's profile
+///
+/// Fields left as `None` are not changed. Platforms may refuse some changes,
+/// e.g. a username managed by an SSO provider.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileUpdate {
+    /// New first name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_name: Option<String>,
+    /// New last name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_name: Option<String>,
+    /// New nickname
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nickname: Option<String>,
+    /// New username, where the platform allows changing it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    /// New position (job title)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<String>,
+}
+
+impl ProfileUpdate {
+    /// Create an update that changes nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the first name
+    pub fn with_first_name(mut self, first_name: impl Into<String>) -> Self {
+        self.first_name = Some(first_name.into());
+        self
+    }
+
+    /// Set the last name
+    pub fn with_last_name(mut self, last_name: impl Into<String>) -> Self {
+        self.last_name = Some(last_name.into());
+        self
+    }
+
+    /// Set the nickname
+    pub fn with_nickname(mut self, nickname: impl Into<String>) -> Self {
+        self.nickname = Some(nickname.into());
+        self
+    }
+
+    /// Set the username
+    pub fn with_username(mut self, username: impl Into<String>) -> Self {
+        self.username = Some(username.into());
+        self
+    }
+
+    /// Set the position
+    pub fn with_position(mut self, position: impl Into<String>) -> Self {
+        self.position = Some(position.into());
+        self
+    }
+
+    /// Whether the update changes nothing
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -143,4 +209,17 @@ mod tests {
         let json = serde_json::to_string(&status).unwrap();
         assert_eq!(json, "\"online\"");
     }
+
+    #[test]
+    fn test_profile_update_serialization() {
+        let update = ProfileUpdate::new().with_nickname("ali");
+        assert!(!update.is_empty());
+        assert!(ProfileUpdate::new().is_empty());
+
+        let json = serde_json::to_string(&update).unwrap();
+        assert_eq!(json, r#"{"nickname":"ali"}"#);
+
+        let parsed: ProfileUpdate = serde_json::from_str(r#"{"first_name":"Alice"}"#).unwrap();
+        assert_eq!(parsed, ProfileUpdate::new().with_first_name("Alice"));
+    }
 }
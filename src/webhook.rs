@@ -0,0 +1,172 @@
+//! Event webhooks
+//!
+//! Forwards selected platform events as JSON POST requests to a local HTTP
+//! endpoint, so scripts and other tools (home automation, shell hooks, ...)
+//! can consume events without binding to the C API.
+//!
+//! Webhooks are registered on a platform's event pipeline (see
+//! `crate::pipeline`). Events are delivered in the background as they are
+//! polled; delivery failures never affect polling.
+
+use std::collections::HashSet;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use url::{Host, Url};
+
+use crate::error::{Error, ErrorCode, Result};
+
+/// A local HTTP endpoint that receives events
+#[derive(Debug, Clone)]
+pub struct EventWebhook {
+    url: Url,
+    /// Event types to forward (None forwards all events)
+    event_types: Option<HashSet<String>>,
+    http_client: reqwest::Client,
+}
+
+impl EventWebhook {
+    /// Create a new webhook
+    ///
+    /// # Arguments
+    /// * `url` - The endpoint to POST events to. Must be an http(s) URL on a
+    ///   loopback address (localhost, 127.0.0.0/8 or ::1).
+    /// * `event_types` - Event types to forward (e.g. "message_posted"); None forwards all events
+    ///
+    /// # Returns
+    /// A Result containing the EventWebhook or an Error
+    pub fn new(url: &str, event_types: Option<Vec<String>>) -> Result<Self> {
+        let url = Url::parse(url)
+            .map_err(|e| Error::invalid_argument(format!("Invalid webhook URL: {e}")))?;
+
+        if url.scheme() != "http" && url.scheme() != "https" {
+            return Err(Error::invalid_argument(
+                "Webhook URL must use http or https",
+            ));
+        }
+
+        if !is_loopback_host(&url) {
+            return Err(Error::invalid_argument(
+                "Webhook URL must point to a local (loopback) address",
+            ));
+        }
+
+        let http_client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()
+            .map_err(|e| {
+                Error::new(
+                    ErrorCode::NetworkError,
+                    format!("Failed to create HTTP client: {e}"),
+                )
+            })?;
+
+        Ok(Self {
+            url,
+            event_types: event_types.map(|types| types.into_iter().collect()),
+            http_client,
+        })
+    }
+
+    /// Get the webhook URL
+    pub fn url(&self) -> &str {
+        self.url.as_str()
+    }
+
+    /// Check whether an event type should be forwarded to this webhook
+    pub fn matches(&self, event_type: &str) -> bool {
+        self.event_types
+            .as_ref()
+            .map(|types| types.contains(event_type))
+            .unwrap_or(true)
+    }
+
+    /// POST an event to the webhook
+    ///
+    /// # Arguments
+    /// * `event` - The event JSON (as returned by communicator_platform_poll_event)
+    pub async fn deliver(&self, event: &serde_json::Value) -> Result<()> {
+        let response = self
+            .http_client
+            .post(self.url.clone())
+            .json(event)
+            .send()
+            .await
+            .map_err(|e| {
+                Error::new(
+                    ErrorCode::NetworkError,
+                    format!("Webhook delivery failed: {e}"),
+                )
+            })?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(Error::new(
+                ErrorCode::NetworkError,
+                format!("Webhook returned status {}", response.status()),
+            )
+            .with_http_status(response.status().as_u16()))
+        }
+    }
+}
+
+/// Check whether a URL points to a loopback address
+fn is_loopback_host(url: &Url) -> bool {
+    match url.host() {
+        Some(Host::Domain(domain)) => domain.eq_ignore_ascii_case("localhost"),
+        Some(Host::Ipv4(ip)) => IpAddr::V4(ip).is_loopback(),
+        Some(Host::Ipv6(ip)) => IpAddr::V6(ip).is_loopback(),
+        None => false,
+    }
+}
+
+/// Deliver an event to the webhooks that forward its type, in the background
+///
+/// Must be called within a Tokio runtime.
+pub(crate) fn dispatch(webhooks: &[Arc<EventWebhook>], event: &serde_json::Value) {
+    let event_type = event.get("type").and_then(|t| t.as_str()).unwrap_or("");
+
+    for webhook in webhooks.iter().filter(|w| w.matches(event_type)) {
+        let webhook = Arc::clone(webhook);
+        let event = event.clone();
+        // Delivery is best-effort; failures must not affect event polling
+        tokio::spawn(async move {
+            let _ = webhook.deliver(&event).await;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_webhook_requires_loopback_url() {
+        assert!(EventWebhook::new("http://localhost:8080/events", None).is_ok());
+        assert!(EventWebhook::new("http://127.0.0.1:9000/", None).is_ok());
+        assert!(EventWebhook::new("http://[::1]:9000/", None).is_ok());
+
+        let err = EventWebhook::new("https://example.com/hook", None).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidArgument);
+        assert!(EventWebhook::new("http://192.168.1.10/hook", None).is_err());
+        assert!(EventWebhook::new("ftp://localhost/hook", None).is_err());
+        assert!(EventWebhook::new("not a url", None).is_err());
+    }
+
+    #[test]
+    fn test_webhook_event_filter() {
+        let all = EventWebhook::new("http://localhost/hook", None).unwrap();
+        assert!(all.matches("message_posted"));
+        assert!(all.matches("user_typing"));
+
+        let filtered = EventWebhook::new(
+            "http://localhost/hook",
+            Some(vec!["message_posted".to_string()]),
+        )
+        .unwrap();
+        assert!(filtered.matches("message_posted"));
+        assert!(!filtered.matches("user_typing"));
+    }
+}